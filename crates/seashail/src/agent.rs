@@ -147,27 +147,68 @@ fn claude_desktop_default_path() -> eyre::Result<PathBuf> {
     }
 }
 
-fn insert_server(
+/// What `install_template` did to the `seashail` server entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryChange {
+    Added,
+    Updated,
+    Unchanged,
+}
+
+impl EntryChange {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Updated => "updated",
+            Self::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Merge `server` into `root[servers_key][server_name]` without touching anything else.
+///
+/// Fields the template sets are overwritten; fields only the user set (e.g. `env`) are kept, so
+/// re-running the install is idempotent. Refuses (rather than replaces) a root or servers value
+/// that is not a JSON object. Returns the change and the fields that were written.
+fn merge_server(
     root: &mut Value,
     servers_key: &str,
     server_name: &str,
-    server: Value,
-) -> eyre::Result<()> {
-    if !root.is_object() {
-        *root = json!({});
-    }
+    server: &Value,
+) -> eyre::Result<(EntryChange, Vec<String>)> {
     let Some(obj) = root.as_object_mut() else {
-        eyre::bail!("root must be an object");
+        eyre::bail!("top-level JSON is not an object; refusing to overwrite it");
     };
     let servers = obj.entry(servers_key).or_insert_with(|| json!({}));
-    if !servers.is_object() {
-        *servers = json!({});
+    let Some(servers) = servers.as_object_mut() else {
+        eyre::bail!("`{servers_key}` is not an object; refusing to overwrite it");
+    };
+    let Some(template) = server.as_object() else {
+        eyre::bail!("template server entry must be an object");
+    };
+
+    let Some(existing) = servers.get_mut(server_name) else {
+        servers.insert(server_name.to_owned(), server.clone());
+        return Ok((EntryChange::Added, template.keys().cloned().collect()));
+    };
+    let Some(existing) = existing.as_object_mut() else {
+        *existing = server.clone();
+        return Ok((EntryChange::Updated, template.keys().cloned().collect()));
+    };
+
+    let mut changed = vec![];
+    for (k, v) in template {
+        if existing.get(k) != Some(v) {
+            existing.insert(k.clone(), v.clone());
+            changed.push(k.clone());
+        }
     }
-    let Some(s) = servers.as_object_mut() else {
-        eyre::bail!("{servers_key} must be an object");
+    let change = if changed.is_empty() {
+        EntryChange::Unchanged
+    } else {
+        EntryChange::Updated
     };
-    s.insert(server_name.to_owned(), server);
-    Ok(())
+    Ok((change, changed))
 }
 
 fn extract_server_entry(template: &Value, servers_key: &str) -> eyre::Result<Value> {
@@ -188,16 +229,73 @@ fn extract_server_entry(template: &Value, servers_key: &str) -> eyre::Result<Val
 
 fn read_json_file(path: &Path) -> eyre::Result<Value> {
     let s = fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
-    let v: Value = serde_json::from_str(&s).with_context(|| format!("parse {}", path.display()))?;
+    let v: Value = serde_json::from_str(&s).with_context(|| {
+        format!(
+            "parse {} (comments and trailing commas are not supported; fix or move the file)",
+            path.display()
+        )
+    })?;
     Ok(v)
 }
 
-fn write_json_file(path: &Path, v: &Value) -> eyre::Result<()> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
-    }
+/// Write `v` via a temp file + rename, keeping the original's permissions. Whenever an existing
+/// file is replaced, its previous contents are copied to `<name>.bak.seashail` first, so the
+/// backup always holds the config as it was right before the latest write. Returns the backup
+/// path when one was written.
+fn write_json_file_atomic(path: &Path, v: &Value) -> eyre::Result<Option<PathBuf>> {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    let file_name = path
+        .file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or("mcp.json");
+
+    let perms = fs::metadata(path).ok().map(|m| m.permissions());
+
     let s = serde_json::to_string_pretty(v).context("serialize json")?;
-    fs::write(path, format!("{s}\n")).with_context(|| format!("write {}", path.display()))?;
+    let tmp = parent.join(format!(".{file_name}.seashail.tmp"));
+    fs::write(&tmp, format!("{s}\n")).with_context(|| format!("write {}", tmp.display()))?;
+    if let Some(perms) = perms {
+        if let Err(e) = fs::set_permissions(&tmp, perms) {
+            // Not fatal: the merged config is still written, just with default permissions.
+            tracing::warn!(path = %tmp.display(), error = %e, "agent install: cannot copy permissions");
+        }
+    }
+
+    let backup = if path.exists() {
+        let bak = parent.join(format!("{file_name}.bak.seashail"));
+        fs::copy(path, &bak).with_context(|| format!("back up {}", path.display()))?;
+        Some(bak)
+    } else {
+        None
+    };
+
+    fs::rename(&tmp, path)
+        .with_context(|| format!("rename {} -> {}", tmp.display(), path.display()))?;
+    Ok(backup)
+}
+
+/// Read the installed file back and check it parses and carries the expected `seashail` entry.
+fn verify_installed(path: &Path, servers_key: &str, server: &Value) -> eyre::Result<()> {
+    let root = read_json_file(path)?;
+    let installed = root
+        .get(servers_key)
+        .and_then(|s| s.get("seashail"))
+        .with_context(|| format!("{} has no {servers_key}.seashail entry", path.display()))?;
+    let Some(template) = server.as_object() else {
+        eyre::bail!("template server entry must be an object");
+    };
+    for (k, v) in template {
+        if installed.get(k) != Some(v) {
+            eyre::bail!(
+                "{}: {servers_key}.seashail.{k} does not match the template",
+                path.display()
+            );
+        }
+    }
     Ok(())
 }
 
@@ -227,13 +325,36 @@ pub fn install_template(
         json!({})
     };
 
-    insert_server(&mut root, servers_key, "seashail", server)?;
-    write_json_file(&path, &root)?;
+    let (change, fields) = merge_server(&mut root, servers_key, "seashail", &server)
+        .with_context(|| format!("merge seashail into {}", path.display()))?;
+    let other_servers: Vec<String> = root
+        .get(servers_key)
+        .and_then(Value::as_object)
+        .map(|m| m.keys().filter(|k| *k != "seashail").cloned().collect())
+        .unwrap_or_default();
+
+    let backup = if change == EntryChange::Unchanged {
+        None
+    } else {
+        write_json_file_atomic(&path, &root)?
+    };
+    verify_installed(&path, servers_key, &server)?;
 
     // Print a minimal confirmation line (human-readable).
     let mut out = io::stdout().lock();
-    writeln!(&mut out, "{}", json!({ "ok": true, "path": path }))
-        .context("write install confirmation")?;
+    writeln!(
+        &mut out,
+        "{}",
+        json!({
+          "ok": true,
+          "path": path,
+          "action": change.as_str(),
+          "fields": fields,
+          "kept_servers": other_servers,
+          "backup": backup,
+        })
+    )
+    .context("write install confirmation")?;
     Ok(())
 }
 
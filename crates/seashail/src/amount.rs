@@ -1,14 +1,26 @@
 use eyre::Context as _;
 
+fn is_ascii_digits(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
 pub fn parse_amount_base_u128(s: &str) -> eyre::Result<u128> {
     let s = s.trim();
     if s.is_empty() {
         eyre::bail!("empty amount");
     }
+    if !is_ascii_digits(s) {
+        eyre::bail!("base amount must be a non-negative integer");
+    }
     let v: u128 = s.parse().context("parse base amount")?;
     Ok(v)
 }
 
+/// Parse a UI decimal string into base units using integer math only.
+///
+/// Rejects anything that would lose precision: more significant decimal places than the token
+/// supports, or a value that does not fit in `u128`. Trailing zeros past `decimals` are accepted
+/// since they carry no value.
 pub fn parse_amount_ui_to_base_u128(s: &str, decimals: u32) -> eyre::Result<u128> {
     let s = s.trim();
     if s.is_empty() {
@@ -23,15 +35,21 @@ pub fn parse_amount_ui_to_base_u128(s: &str, decimals: u32) -> eyre::Result<u128
     if whole.starts_with('-') {
         eyre::bail!("amount must be non-negative");
     }
+    if (whole.is_empty() && frac.is_empty()) || !is_ascii_digits(whole) || !is_ascii_digits(frac) {
+        eyre::bail!("invalid amount {s:?}: expected a plain decimal number");
+    }
 
     let whole_v: u128 = if whole.is_empty() {
         0
     } else {
-        whole.parse().context("parse whole")?
+        whole.parse().context("amount overflow")?
     };
 
+    let frac = frac.trim_end_matches('0');
     if frac.len() > decimals as usize {
-        eyre::bail!("too many decimal places for token (decimals={decimals})");
+        eyre::bail!(
+            "too many decimal places for token (decimals={decimals}); refusing to truncate {s}"
+        );
     }
 
     let mut frac_s = frac.to_owned();
@@ -80,6 +98,92 @@ pub fn format_amount_base_to_ui_string(base: u128, decimals: u32) -> eyre::Resul
     Ok(format!("{whole}.{frac_s}"))
 }
 
+/// Group integer digits with `,` every three places (`"1234567"` => `"1,234,567"`).
+pub fn group_thousands(digits: &str) -> String {
+    let len = digits.chars().count();
+    let mut out = String::with_capacity(len.saturating_add(len / 3));
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (len - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Human-readable token amount for CLI text: at most `max_decimals` fractional digits
+/// (truncated, trailing zeros trimmed), optionally grouped with thousands separators. Non-zero
+/// amounts that truncate to zero render as `<0.0…1`. Tool JSON keeps
+/// [`format_amount_base_to_ui_string`].
+pub fn format_amount_base_for_display(
+    base: u128,
+    decimals: u32,
+    max_decimals: u32,
+    thousands: bool,
+) -> eyre::Result<String> {
+    let ui = format_amount_base_to_ui_string(base, decimals)?;
+    let (whole, frac) = ui.split_once('.').unwrap_or((ui.as_str(), ""));
+    let keep = usize::try_from(max_decimals).unwrap_or(usize::MAX);
+    let frac: String = frac.chars().take(keep).collect();
+    let frac = frac.trim_end_matches('0');
+    if base > 0 && whole == "0" && frac.is_empty() && keep > 0 {
+        return Ok(format!("<0.{}1", "0".repeat(keep - 1)));
+    }
+    let whole = if thousands {
+        group_thousands(whole)
+    } else {
+        whole.to_owned()
+    };
+    if frac.is_empty() {
+        Ok(whole)
+    } else {
+        Ok(format!("{whole}.{frac}"))
+    }
+}
+
+/// Human-readable USD value for CLI text, e.g. `$1,234.57` (rounded to `decimals` places, at most 8).
+pub fn format_usd_for_display(usd: f64, decimals: u8, thousands: bool) -> String {
+    if !usd.is_finite() {
+        return "unknown".to_owned();
+    }
+    let s = format!("{:.*}", usize::from(decimals.min(8)), usd.abs());
+    let (whole, frac) = s.split_once('.').unwrap_or((s.as_str(), ""));
+    let whole = if thousands {
+        group_thousands(whole)
+    } else {
+        whole.to_owned()
+    };
+    let sign = if usd < 0.0_f64 && s.chars().any(|c| matches!(c, '1'..='9')) {
+        "-"
+    } else {
+        ""
+    };
+    if frac.is_empty() {
+        format!("{sign}${whole}")
+    } else {
+        format!("{sign}${whole}.{frac}")
+    }
+}
+
+/// Convert an input amount (base units) at a UI price (output per 1 input) into output base units.
+///
+/// Uses integer math only; rounds down.
+pub fn apply_ui_price_to_base_u128(
+    amount_in_base: u128,
+    price_ui: &str,
+    decimals_in: u32,
+    decimals_out: u32,
+) -> eyre::Result<u128> {
+    let price_base = parse_amount_ui_to_base_u128(price_ui, decimals_out).context("parse price")?;
+    let scale = 10_u128
+        .checked_pow(decimals_in)
+        .ok_or_else(|| eyre::eyre!("decimals too large"))?;
+    let out = amount_in_base
+        .checked_mul(price_base)
+        .ok_or_else(|| eyre::eyre!("amount overflow"))?;
+    Ok(out / scale)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,6 +227,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_ui_accepts_trailing_zeros_past_decimals() {
+        assert_eq!(
+            parse_amount_ui_to_base_u128("1.500000000", 6).ok(),
+            Some(1_500_000),
+            "trailing zeros carry no value"
+        );
+        assert_eq!(
+            parse_amount_ui_to_base_u128("7.000", 0).ok(),
+            Some(7),
+            "zero-decimal token"
+        );
+        assert_eq!(
+            parse_amount_ui_to_base_u128("0.10", 1).ok(),
+            Some(1),
+            "single decimal"
+        );
+        assert_eq!(
+            parse_amount_ui_to_base_u128(".5", 1).ok(),
+            Some(5),
+            "missing whole part"
+        );
+        assert_eq!(
+            parse_amount_ui_to_base_u128("5.", 2).ok(),
+            Some(500),
+            "missing fraction"
+        );
+        assert!(
+            parse_amount_ui_to_base_u128("1.0000010", 6).is_err(),
+            "a significant digit past decimals must still be rejected"
+        );
+    }
+
+    #[test]
+    fn parse_ui_rejects_malformed_numbers() {
+        for bad in [
+            "+1", "1.+5", "1e18", ".", "1.2.3", "1,5", "-0", "0x10", "1 000", "NaN",
+        ] {
+            assert!(
+                parse_amount_ui_to_base_u128(bad, 18).is_err(),
+                "expected {bad:?} to be rejected"
+            );
+        }
+        for bad in ["+5", "1.0", "-1", "1e3"] {
+            assert!(
+                parse_amount_base_u128(bad).is_err(),
+                "expected base {bad:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_ui_18_decimals_at_u128_limit() -> eyre::Result<()> {
+        let max_ui = "340282366920938463463.374607431768211455";
+        assert_eq!(
+            parse_amount_ui_to_base_u128(max_ui, 18)?,
+            u128::MAX,
+            "u128::MAX at 18 decimals"
+        );
+        assert_eq!(
+            format_amount_base_to_ui_string(u128::MAX, 18)?,
+            max_ui,
+            "format round-trips"
+        );
+        assert!(
+            parse_amount_ui_to_base_u128("340282366920938463463.374607431768211456", 18).is_err(),
+            "one base unit past u128::MAX overflows"
+        );
+        assert!(
+            parse_amount_ui_to_base_u128("1000000000000000000000", 18).is_err(),
+            "whole part too large for 18 decimals"
+        );
+        assert_eq!(
+            parse_amount_ui_to_base_u128("0.000000000000000001", 18)?,
+            1,
+            "smallest 18-decimal unit"
+        );
+        Ok(())
+    }
+
     #[test]
     fn format_base_to_ui() -> eyre::Result<()> {
         let s1 = format_amount_base_to_ui_string(1_500_000, 6)?;
@@ -133,4 +317,44 @@ mod tests {
         assert_eq!(s3, "10");
         Ok(())
     }
+
+    #[test]
+    fn format_for_display_groups_and_truncates() -> eyre::Result<()> {
+        assert_eq!(group_thousands("1234567"), "1,234,567", "grouped");
+        assert_eq!(group_thousands("123"), "123", "short");
+        let s = format_amount_base_for_display(1_234_567_891_234_567, 9, 6, true)?;
+        assert_eq!(s, "1,234,567.891234", "truncated to 6 decimals");
+        let s = format_amount_base_for_display(1_500_000_000, 6, 6, false)?;
+        assert_eq!(s, "1500", "no separator, trailing zeros trimmed");
+        let s = format_amount_base_for_display(1, 18, 6, true)?;
+        assert_eq!(s, "<0.000001", "dust is not shown as zero");
+        let s = format_amount_base_for_display(0, 18, 6, true)?;
+        assert_eq!(s, "0", "zero stays zero");
+        assert_eq!(
+            format_usd_for_display(1_234_567.891_f64, 2, true),
+            "$1,234,567.89",
+            "usd grouped"
+        );
+        assert_eq!(
+            format_usd_for_display(-0.001_f64, 2, true),
+            "$0.00",
+            "no negative zero"
+        );
+        assert_eq!(
+            format_usd_for_display(-12.4_f64, 0, false),
+            "-$12",
+            "sign kept"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn apply_ui_price_rounds_down_with_integer_math() -> eyre::Result<()> {
+        // 1.5 (9 decimals) at 150.25 (6 decimals) per unit => 225.375.
+        let out = apply_ui_price_to_base_u128(1_500_000_000, "150.25", 9, 6)?;
+        assert_eq!(out, 225_375_000);
+        let dust = apply_ui_price_to_base_u128(1, "0.5", 0, 0);
+        assert!(dust.is_err(), "expected precision error, got {dust:?}");
+        Ok(())
+    }
 }
@@ -51,6 +51,13 @@ struct AddrResp {
     mempool_stats: AddrStats,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct TxStatus {
+    pub confirmed: bool,
+    #[serde(default)]
+    pub block_height: Option<u64>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Utxo {
     pub txid: String,
@@ -103,6 +110,41 @@ impl BitcoinChain {
         Ok(v)
     }
 
+    pub async fn tip_height(&self) -> eyre::Result<u64> {
+        // blockstream: GET /blocks/tip/height returns the height as text
+        let client = Self::client()?;
+        let url = format!("{}/blocks/tip/height", self.base_url);
+        let resp = client
+            .get(url)
+            .send()
+            .await
+            .context("fetch btc tip height")?;
+        if !resp.status().is_success() {
+            eyre::bail!("bitcoin upstream returned http {}", resp.status());
+        }
+        let text = resp.text().await.context("read btc tip height")?;
+        text.trim().parse::<u64>().context("parse btc tip height")
+    }
+
+    /// Esplora `GET /tx/:txid/status`. `None` if the upstream does not know the transaction.
+    pub async fn tx_status(&self, txid: &str) -> eyre::Result<Option<TxStatus>> {
+        let client = Self::client()?;
+        let url = format!("{}/tx/{}/status", self.base_url, txid.trim());
+        let resp = client
+            .get(url)
+            .send()
+            .await
+            .context("fetch btc tx status")?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            eyre::bail!("bitcoin upstream returned http {}", resp.status());
+        }
+        let v: TxStatus = resp.json().await.context("decode btc tx status json")?;
+        Ok(Some(v))
+    }
+
     pub async fn fee_rate_sats_per_vb(&self) -> eyre::Result<u64> {
         // blockstream: GET /fee-estimates returns a map of confirmation target -> sats/vbyte
         let client = Self::client()?;
@@ -126,6 +168,31 @@ impl BitcoinChain {
         Ok(rate)
     }
 
+    /// Fee estimates keyed by confirmation target (blocks), in sats/vbyte (rounded up).
+    pub async fn fee_estimates_sats_per_vb(
+        &self,
+    ) -> eyre::Result<std::collections::BTreeMap<u32, u64>> {
+        let client = Self::client()?;
+        let url = format!("{}/fee-estimates", self.base_url);
+        let resp = client
+            .get(url)
+            .send()
+            .await
+            .context("fetch btc fee estimates")?;
+        if !resp.status().is_success() {
+            eyre::bail!("bitcoin upstream returned http {}", resp.status());
+        }
+        let v: serde_json::Value = resp.json().await.context("decode btc fee json")?;
+        let mut out = std::collections::BTreeMap::new();
+        for (k, rate) in v.as_object().into_iter().flatten() {
+            let (Ok(target), Some(rate)) = (k.parse::<u32>(), rate.as_f64()) else {
+                continue;
+            };
+            out.insert(target, crate::financial_math::clamp_fee_rate(rate.ceil()));
+        }
+        Ok(out)
+    }
+
     pub async fn broadcast_tx_hex(&self, tx_hex: &str) -> eyre::Result<String> {
         // blockstream: POST /tx returns txid as text
         let client = Self::client()?;
@@ -0,0 +1,503 @@
+//! ERC-4337 (account abstraction) execution for `EntryPoint` v0.7.
+//!
+//! A wallet's EVM key owns an eth-infinitism `SimpleAccount`; calls are wrapped in a
+//! `UserOperation`, optionally sponsored by an ERC-7677 paymaster, and submitted to a bundler.
+
+use crate::chains::evm::{compute_eip1559_fees, extract_tx_to_address, EvmChain};
+use alloy::{
+    network::TransactionBuilder as _,
+    primitives::{aliases::U192, keccak256, Address, Bytes, B256, U256},
+    rpc::types::TransactionRequest,
+    signers::{local::PrivateKeySigner, SignerSync as _},
+    sol,
+    sol_types::{SolCall as _, SolValue as _},
+};
+use eyre::Context as _;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::time::sleep;
+
+pub const ENTRY_POINT_V07: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+pub const SIMPLE_ACCOUNT_FACTORY_V07: &str = "0x91E60e0613810449d098b0b5Ec8b51A0FE8c8985";
+
+/// Well-formed placeholder signature for gas estimation (`SimpleAccount` recovers it without
+/// reverting, so bundlers can simulate validation before the real signature exists).
+const DUMMY_SIGNATURE: &str = "0xfffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+const BUNDLER_TIMEOUT: Duration = Duration::from_secs(20);
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(60);
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+sol! {
+    contract ISimpleAccountFactory {
+        function createAccount(address owner, uint256 salt) returns (address);
+        function getAddress(address owner, uint256 salt) view returns (address);
+    }
+}
+
+sol! {
+    contract ISimpleAccount {
+        function execute(address dest, uint256 value, bytes func);
+    }
+}
+
+sol! {
+    contract IEntryPoint {
+        function getNonce(address sender, uint192 key) view returns (uint256 nonce);
+    }
+}
+
+fn is_loopback_http(url: &str) -> bool {
+    fn host_prefix_ok(s: &str, prefix: &str) -> bool {
+        if !s.starts_with(prefix) {
+            return false;
+        }
+        matches!(s.as_bytes().get(prefix.len()), None | Some(b':' | b'/'))
+    }
+    let u = url.trim();
+    host_prefix_ok(u, "http://127.0.0.1")
+        || host_prefix_ok(u, "http://localhost")
+        || host_prefix_ok(u, "http://[::1]")
+}
+
+fn ensure_https_or_loopback(url: &str, name: &str) -> eyre::Result<()> {
+    let u = url.trim();
+    if u.starts_with("https://") || is_loopback_http(u) {
+        return Ok(());
+    }
+    eyre::bail!("{name} must use https (or http://localhost for local testing)");
+}
+
+/// Unpacked v0.7 `UserOperation`, as exchanged with bundlers over JSON-RPC.
+#[derive(Debug, Clone, Default)]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub factory: Option<Address>,
+    pub factory_data: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: u128,
+    pub verification_gas_limit: u128,
+    pub pre_verification_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub paymaster: Option<Address>,
+    pub paymaster_verification_gas_limit: u128,
+    pub paymaster_post_op_gas_limit: u128,
+    pub paymaster_data: Bytes,
+    pub signature: Bytes,
+}
+
+fn pack_u128_pair(hi: u128, lo: u128) -> B256 {
+    B256::from((U256::from(hi) << 128_usize) | U256::from(lo))
+}
+
+fn quantity(v: u128) -> String {
+    format!("{v:#x}")
+}
+
+fn parse_quantity(v: Option<&Value>) -> Option<u128> {
+    let s = v?.as_str()?.trim();
+    let h = s.strip_prefix("0x").unwrap_or(s);
+    u128::from_str_radix(h, 16).ok()
+}
+
+fn parse_bytes(v: Option<&Value>) -> eyre::Result<Bytes> {
+    let s = v.and_then(Value::as_str).unwrap_or("0x").trim();
+    let h = s.strip_prefix("0x").unwrap_or(s);
+    Ok(Bytes::from(hex::decode(h).context("invalid hex bytes")?))
+}
+
+impl UserOperation {
+    /// `initCode` = factory ++ factoryData (empty when the account is already deployed).
+    pub fn init_code(&self) -> Vec<u8> {
+        self.factory.map_or_else(Vec::new, |f| {
+            let mut v = f.to_vec();
+            v.extend_from_slice(&self.factory_data);
+            v
+        })
+    }
+
+    /// `paymasterAndData` = paymaster ++ verificationGas(16) ++ postOpGas(16) ++ paymasterData.
+    pub fn paymaster_and_data(&self) -> Vec<u8> {
+        self.paymaster.map_or_else(Vec::new, |p| {
+            let mut v = p.to_vec();
+            v.extend_from_slice(&self.paymaster_verification_gas_limit.to_be_bytes());
+            v.extend_from_slice(&self.paymaster_post_op_gas_limit.to_be_bytes());
+            v.extend_from_slice(&self.paymaster_data);
+            v
+        })
+    }
+
+    pub fn to_rpc_json(&self) -> Value {
+        let mut v = json!({
+          "sender": self.sender.to_string(),
+          "nonce": format!("{:#x}", self.nonce),
+          "callData": self.call_data.to_string(),
+          "callGasLimit": quantity(self.call_gas_limit),
+          "verificationGasLimit": quantity(self.verification_gas_limit),
+          "preVerificationGas": quantity(self.pre_verification_gas),
+          "maxFeePerGas": quantity(self.max_fee_per_gas),
+          "maxPriorityFeePerGas": quantity(self.max_priority_fee_per_gas),
+          "signature": self.signature.to_string()
+        });
+        if let (Some(f), Some(obj)) = (self.factory, v.as_object_mut()) {
+            obj.insert("factory".into(), json!(f.to_string()));
+            obj.insert("factoryData".into(), json!(self.factory_data.to_string()));
+        }
+        if let (Some(p), Some(obj)) = (self.paymaster, v.as_object_mut()) {
+            obj.insert("paymaster".into(), json!(p.to_string()));
+            obj.insert(
+                "paymasterVerificationGasLimit".into(),
+                json!(quantity(self.paymaster_verification_gas_limit)),
+            );
+            obj.insert(
+                "paymasterPostOpGasLimit".into(),
+                json!(quantity(self.paymaster_post_op_gas_limit)),
+            );
+            obj.insert(
+                "paymasterData".into(),
+                json!(self.paymaster_data.to_string()),
+            );
+        }
+        v
+    }
+
+    /// Apply paymaster fields from an ERC-7677 `pm_getPaymasterStubData`/`pm_getPaymasterData`
+    /// response. Gas limits are only overwritten when the paymaster provides them.
+    fn apply_paymaster(&mut self, v: &Value) -> eyre::Result<()> {
+        let pm = v
+            .get("paymaster")
+            .and_then(Value::as_str)
+            .ok_or_else(|| eyre::eyre!("paymaster response missing paymaster"))?;
+        self.paymaster = Some(EvmChain::parse_address(pm)?);
+        self.paymaster_data = parse_bytes(v.get("paymasterData"))?;
+        if let Some(g) = parse_quantity(v.get("paymasterVerificationGasLimit")) {
+            self.paymaster_verification_gas_limit = g;
+        }
+        if let Some(g) = parse_quantity(v.get("paymasterPostOpGasLimit")) {
+            self.paymaster_post_op_gas_limit = g;
+        }
+        Ok(())
+    }
+
+    fn apply_gas_estimate(&mut self, v: &Value) -> eyre::Result<()> {
+        let field = |k: &str| {
+            parse_quantity(v.get(k)).ok_or_else(|| eyre::eyre!("gas estimate missing {k}"))
+        };
+        self.call_gas_limit = field("callGasLimit")?;
+        self.verification_gas_limit = field("verificationGasLimit")?;
+        self.pre_verification_gas = field("preVerificationGas")?;
+        if let Some(g) = parse_quantity(v.get("paymasterVerificationGasLimit")) {
+            self.paymaster_verification_gas_limit = g;
+        }
+        if let Some(g) = parse_quantity(v.get("paymasterPostOpGasLimit")) {
+            self.paymaster_post_op_gas_limit = g;
+        }
+        Ok(())
+    }
+}
+
+/// `EntryPoint` v0.7 `getUserOpHash`: keccak(abi.encode(keccak(packedUserOp), entryPoint, chainId)).
+/// The signature is not part of the hash.
+pub fn user_op_hash(op: &UserOperation, entry_point: Address, chain_id: u64) -> B256 {
+    let packed = (
+        op.sender,
+        op.nonce,
+        keccak256(op.init_code()),
+        keccak256(&op.call_data),
+        pack_u128_pair(op.verification_gas_limit, op.call_gas_limit),
+        U256::from(op.pre_verification_gas),
+        pack_u128_pair(op.max_priority_fee_per_gas, op.max_fee_per_gas),
+        keccak256(op.paymaster_and_data()),
+    )
+        .abi_encode();
+    keccak256((keccak256(packed), entry_point, U256::from(chain_id)).abi_encode())
+}
+
+/// Endpoints and contracts used to execute through a smart account on one chain.
+#[derive(Debug, Clone)]
+pub struct SmartAccountEndpoints {
+    pub entry_point: Address,
+    pub factory: Address,
+    pub bundler_url: String,
+    pub paymaster_url: Option<String>,
+    pub paymaster_context: Value,
+}
+
+async fn json_rpc(client: &Client, url: &str, method: &str, params: Value) -> eyre::Result<Value> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let v: Value = client
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .with_context(|| format!("{method} request"))?
+        .error_for_status()
+        .with_context(|| format!("{method} status"))?
+        .json()
+        .await
+        .with_context(|| format!("{method} json"))?;
+    if let Some(err) = v.get("error") {
+        let msg = err
+            .get("message")
+            .and_then(Value::as_str)
+            .map_or_else(|| err.to_string(), str::to_owned);
+        eyre::bail!("{method} failed: {msg}");
+    }
+    Ok(v.get("result").cloned().unwrap_or(Value::Null))
+}
+
+/// Counterfactual `SimpleAccount` address for `owner` (salt 0), via the factory's `getAddress`.
+pub async fn smart_account_address(
+    evm: &EvmChain,
+    factory: Address,
+    owner: Address,
+) -> eyre::Result<Address> {
+    let call = ISimpleAccountFactory::getAddressCall {
+        owner,
+        salt: U256::ZERO,
+    };
+    let tx = TransactionRequest::default()
+        .with_to(factory)
+        .with_input(call.abi_encode());
+    let out = evm
+        .simulate_tx_strict(&tx)
+        .await
+        .context("smart account factory getAddress")?;
+    ISimpleAccountFactory::getAddressCall::abi_decode_returns(&out)
+        .context("decode smart account address")
+}
+
+async fn entry_point_nonce(
+    evm: &EvmChain,
+    entry_point: Address,
+    sender: Address,
+) -> eyre::Result<U256> {
+    let call = IEntryPoint::getNonceCall {
+        sender,
+        key: U192::ZERO,
+    };
+    let tx = TransactionRequest::default()
+        .with_to(entry_point)
+        .with_input(call.abi_encode());
+    let out = evm
+        .simulate_tx_strict(&tx)
+        .await
+        .context("entry point getNonce")?;
+    IEntryPoint::getNonceCall::abi_decode_returns(&out).context("decode entry point nonce")
+}
+
+/// Wrap `tx` in a `UserOperation` for the smart account `sender`, sponsor it via the paymaster
+/// (if configured), submit it to the bundler and wait for inclusion.
+///
+/// Returns the hash of the bundle transaction that included the operation.
+pub async fn send_user_operation(
+    evm: &EvmChain,
+    endpoints: &SmartAccountEndpoints,
+    owner: &PrivateKeySigner,
+    sender: Address,
+    tx: &TransactionRequest,
+) -> eyre::Result<B256> {
+    ensure_https_or_loopback(&endpoints.bundler_url, "erc4337 bundler url")?;
+    if let Some(pm) = endpoints.paymaster_url.as_deref() {
+        ensure_https_or_loopback(pm, "erc4337 paymaster url")?;
+    }
+    let client = Client::builder()
+        .timeout(BUNDLER_TIMEOUT)
+        .build()
+        .context("build http client")?;
+
+    let dest = extract_tx_to_address(tx)?;
+    let value = tx.value.unwrap_or_default();
+    let func = tx.input.input().cloned().unwrap_or_default();
+    let mut op = UserOperation {
+        sender,
+        call_data: ISimpleAccount::executeCall { dest, value, func }
+            .abi_encode()
+            .into(),
+        signature: DUMMY_SIGNATURE.parse().context("parse dummy signature")?,
+        ..UserOperation::default()
+    };
+
+    if evm.get_contract_code(sender).await?.is_empty() {
+        op.factory = Some(endpoints.factory);
+        op.factory_data = ISimpleAccountFactory::createAccountCall {
+            owner: owner.address(),
+            salt: U256::ZERO,
+        }
+        .abi_encode()
+        .into();
+    }
+    op.nonce = entry_point_nonce(evm, endpoints.entry_point, sender).await?;
+
+    let gas_price = evm.get_gas_price().await?;
+    let base_fee = evm
+        .get_fee_history(1)
+        .await
+        .ok()
+        .and_then(|h| h.base_fee_per_gas.last().copied())
+        .unwrap_or(gas_price);
+    (op.max_fee_per_gas, op.max_priority_fee_per_gas) = compute_eip1559_fees(base_fee, gas_price);
+
+    let entry_point_s = endpoints.entry_point.to_string();
+    let chain_id_s = format!("{:#x}", evm.chain_id);
+    if let Some(pm) = endpoints.paymaster_url.as_deref() {
+        let stub = json_rpc(
+            &client,
+            pm,
+            "pm_getPaymasterStubData",
+            json!([
+                op.to_rpc_json(),
+                entry_point_s,
+                chain_id_s,
+                endpoints.paymaster_context
+            ]),
+        )
+        .await?;
+        op.apply_paymaster(&stub)?;
+    }
+
+    let est = json_rpc(
+        &client,
+        &endpoints.bundler_url,
+        "eth_estimateUserOperationGas",
+        json!([op.to_rpc_json(), entry_point_s]),
+    )
+    .await?;
+    op.apply_gas_estimate(&est)?;
+
+    if let Some(pm) = endpoints.paymaster_url.as_deref() {
+        let data = json_rpc(
+            &client,
+            pm,
+            "pm_getPaymasterData",
+            json!([
+                op.to_rpc_json(),
+                entry_point_s,
+                chain_id_s,
+                endpoints.paymaster_context
+            ]),
+        )
+        .await?;
+        op.apply_paymaster(&data)?;
+    }
+
+    // SimpleAccount validates an EIP-191 signature over the user operation hash.
+    let hash = user_op_hash(&op, endpoints.entry_point, evm.chain_id);
+    let sig = owner
+        .sign_message_sync(hash.as_slice())
+        .map_err(|e| eyre::eyre!("sign user operation: {e}"))?;
+    op.signature = Bytes::from(sig.as_bytes().to_vec());
+
+    let submitted = json_rpc(
+        &client,
+        &endpoints.bundler_url,
+        "eth_sendUserOperation",
+        json!([op.to_rpc_json(), entry_point_s]),
+    )
+    .await?;
+    let op_hash = submitted
+        .as_str()
+        .map_or_else(|| hash.to_string(), str::to_owned);
+    tracing::info!(
+        user_op_hash = %op_hash,
+        sender = %sender,
+        chain = %evm.name,
+        "user operation submitted"
+    );
+
+    let start = std::time::Instant::now();
+    loop {
+        let r = json_rpc(
+            &client,
+            &endpoints.bundler_url,
+            "eth_getUserOperationReceipt",
+            json!([op_hash]),
+        )
+        .await?;
+        if !r.is_null() {
+            let tx_hash = r
+                .get("receipt")
+                .and_then(|x| x.get("transactionHash"))
+                .and_then(Value::as_str)
+                .ok_or_else(|| eyre::eyre!("user operation receipt missing transactionHash"))?;
+            let tx_hash: B256 = tx_hash.parse().context("parse bundle tx hash")?;
+            if r.get("success").and_then(Value::as_bool) == Some(false) {
+                eyre::bail!("user operation {op_hash} reverted (bundle tx {tx_hash})");
+            }
+            return Ok(tx_hash);
+        }
+        if start.elapsed() > RECEIPT_TIMEOUT {
+            eyre::bail!(
+                "user operation {op_hash} was submitted but not included within {}s; check the bundler before retrying",
+                RECEIPT_TIMEOUT.as_secs()
+            );
+        }
+        sleep(RECEIPT_POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_op() -> UserOperation {
+        UserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(7_u64),
+            call_data: Bytes::from(vec![0xb6, 0x1d, 0x27, 0xf6]),
+            call_gas_limit: 100_000,
+            verification_gas_limit: 200_000,
+            pre_verification_gas: 50_000,
+            max_fee_per_gas: 3_000_000_000,
+            max_priority_fee_per_gas: 1_500_000_000,
+            ..UserOperation::default()
+        }
+    }
+
+    #[test]
+    fn user_op_hash_ignores_signature_but_binds_chain() {
+        let ep = Address::repeat_byte(0x22);
+        let mut op = sample_op();
+        let h = user_op_hash(&op, ep, 1);
+        op.signature = Bytes::from(vec![1_u8; 65]);
+        assert_eq!(user_op_hash(&op, ep, 1), h);
+        assert_ne!(user_op_hash(&op, ep, 8453), h);
+        op.nonce = U256::from(8_u64);
+        assert_ne!(user_op_hash(&op, ep, 1), h);
+    }
+
+    #[test]
+    fn packed_fields_layout() {
+        let mut op = sample_op();
+        assert!(op.init_code().is_empty());
+        assert!(op.paymaster_and_data().is_empty());
+        op.factory = Some(Address::repeat_byte(0x33));
+        op.factory_data = Bytes::from(vec![0xaa, 0xbb]);
+        assert_eq!(op.init_code().len(), 22);
+        op.paymaster = Some(Address::repeat_byte(0x44));
+        op.paymaster_verification_gas_limit = 1;
+        op.paymaster_post_op_gas_limit = 2;
+        op.paymaster_data = Bytes::from(vec![0xcc]);
+        let pd = op.paymaster_and_data();
+        assert_eq!(pd.len(), 20 + 16 + 16 + 1);
+        assert_eq!(pd.get(35), Some(&1_u8));
+        assert_eq!(pd.get(51), Some(&2_u8));
+        let packed = pack_u128_pair(1, 2);
+        assert_eq!(packed.get(15), Some(&1_u8));
+        assert_eq!(packed.get(31), Some(&2_u8));
+    }
+
+    #[test]
+    fn rpc_json_omits_absent_factory_and_paymaster() {
+        let op = sample_op();
+        let v = op.to_rpc_json();
+        assert!(v.get("factory").is_none());
+        assert!(v.get("paymaster").is_none());
+        assert_eq!(
+            v.get("callGasLimit").and_then(Value::as_str),
+            Some("0x186a0")
+        );
+    }
+}
@@ -5,7 +5,11 @@ use alloy::{
     network::TransactionBuilder as _,
     primitives::{keccak256, Address, Bytes, TxKind, B256, U256},
     providers::{Provider as _, RootProvider},
-    rpc::types::{BlockNumberOrTag, TransactionReceipt, TransactionRequest},
+    rpc::types::{
+        simulate::{SimBlock, SimCallResult, SimulatePayload},
+        state::StateOverridesBuilder,
+        BlockNumberOrTag, Filter, TransactionReceipt, TransactionRequest,
+    },
     signers::{local::PrivateKeySigner, SignerSync as _},
     sol,
     sol_types::SolCall as _,
@@ -13,7 +17,7 @@ use alloy::{
 use eyre::Context as _;
 use reqwest::Client;
 use serde::Deserialize;
-use std::{str::FromStr as _, time::Duration};
+use std::{collections::HashSet, str::FromStr as _, time::Duration};
 use tokio::time::sleep;
 
 const ONEINCH_ROUTER: &str = "0x1111111254eeb25477b68fb85ed929f73a960582";
@@ -181,13 +185,96 @@ sol! {
         function multicall(bytes[] data) external payable returns (bytes[] results);
         function unwrapWETH9(uint256 amountMinimum, address recipient) external payable;
         function refundETH() external payable;
+        function factory() external view returns (address);
     }
 }
 
+sol! {
+    #[sol(rpc)]
+    contract IUniswapV3Factory {
+        function getPool(address tokenA, address tokenB, uint24 fee) external view returns (address pool);
+    }
+}
+
+sol! {
+    /// `SwapRouter02`'s deadline-checked multicall overload; reverts once `block.timestamp`
+    /// passes `deadline`.
+    function multicall(uint256 deadline, bytes[] data) external payable returns (bytes[] results);
+}
+
 sol! {
     function safeTransferFrom(address from, address to, uint256 tokenId);
 }
 
+sol! {
+    #[sol(rpc)]
+    contract IERC1155 {
+        function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data);
+        function safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] values, bytes data);
+    }
+}
+
+sol! {
+    #[sol(rpc)]
+    contract IERC165 {
+        function supportsInterface(bytes4 interfaceId) external view returns (bool);
+    }
+}
+
+/// ERC-165 interface ids used to tell NFT standards apart.
+const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+/// EVM NFT token standard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NftStandard {
+    Erc721,
+    Erc1155,
+}
+
+impl NftStandard {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().replace('-', "").as_str() {
+            "erc721" => Some(Self::Erc721),
+            "erc1155" => Some(Self::Erc1155),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Erc721 => "erc721",
+            Self::Erc1155 => "erc1155",
+        }
+    }
+}
+
+sol! {
+    #[sol(rpc)]
+    contract IPermit2 {
+        function allowance(address user, address token, address spender)
+            external view returns (uint160 amount, uint48 expiration, uint48 nonce);
+        function approve(address token, address spender, uint160 amount, uint48 expiration) external;
+    }
+}
+
+/// Uniswap's canonical Permit2 deployment (same address on every supported chain).
+pub const PERMIT2: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+/// A `(token, spender)` pair seen in an owner's `Approval` logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ApprovalPair {
+    pub token: Address,
+    pub spender: Address,
+}
+
+/// Approval pairs found for one owner by [`EvmChain::approval_logs`].
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalLogScan {
+    pub erc20: Vec<ApprovalPair>,
+    pub permit2: Vec<ApprovalPair>,
+}
+
 #[derive(Debug, Clone)]
 pub struct UniswapAddresses {
     pub router02: Address,
@@ -206,8 +293,93 @@ pub struct UniswapSwapRequest {
     pub fee: u32,
     pub native_in: bool,
     pub native_out: bool,
+    /// Unix seconds after which the router rejects the swap.
+    pub deadline: u64,
 }
 
+/// Outcome of a simulated buy-then-sell of a token against its deepest Uniswap v3
+/// wrapped-native pool (see [`EvmChain::probe_token_trade`]).
+#[derive(Debug, Clone)]
+pub struct TradeProbe {
+    pub pool: Address,
+    pub fee: u32,
+    pub probe_in_wei: U256,
+    pub buy_ok: bool,
+    pub buy_tax_bps: Option<u32>,
+    /// A plain transfer into the pool (how every sell delivers tokens) succeeded.
+    pub sell_transfer_ok: bool,
+    pub sell_tax_bps: Option<u32>,
+    /// A router sell of the bought tokens succeeded.
+    pub sell_ok: bool,
+    /// First revert reason hit along the buy/transfer/sell sequence.
+    pub error: Option<String>,
+}
+
+impl TradeProbe {
+    /// Whether bought tokens can be sold back. Uniswap v3 pools reject fee-on-transfer tokens,
+    /// so a reverted router sell only counts against the token when the transfer into the pool
+    /// was untaxed (i.e. nothing but the token itself explains the revert).
+    pub const fn sellable(&self) -> bool {
+        if !self.buy_ok || !self.sell_transfer_ok {
+            return false;
+        }
+        match self.sell_tax_bps {
+            Some(t) if t >= 9_900 => false,
+            Some(t) if t > 0 => true,
+            _ => self.sell_ok,
+        }
+    }
+}
+
+/// Owner-controlled capabilities found in a token's bytecode by selector matching.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenCodeFlags {
+    pub has_blacklist: bool,
+    pub has_pause: bool,
+    pub has_mutable_fee: bool,
+}
+
+const BLACKLIST_SIGNATURES: &[&str] = &[
+    "blacklist(address)",
+    "addToBlacklist(address)",
+    "addBlackList(address)",
+    "setBlacklist(address,bool)",
+    "setBlackList(address,bool)",
+    "blacklistAddress(address,bool)",
+    "isBlacklisted(address)",
+    "isBlackListed(address)",
+    "setBot(address,bool)",
+    "setBots(address[])",
+    "addBots(address[])",
+    "blockBots(address[])",
+    "isBot(address)",
+];
+
+const PAUSE_SIGNATURES: &[&str] = &[
+    "pause()",
+    "unpause()",
+    "paused()",
+    "setTradingEnabled(bool)",
+    "setTrading(bool)",
+    "enableTrading()",
+    "openTrading()",
+];
+
+const MUTABLE_FEE_SIGNATURES: &[&str] = &[
+    "setFee(uint256)",
+    "setFees(uint256,uint256)",
+    "setTaxFee(uint256)",
+    "setTax(uint256,uint256)",
+    "setBuyTax(uint256)",
+    "setSellTax(uint256)",
+    "setBuyFee(uint256)",
+    "setSellFee(uint256)",
+    "updateFees(uint256,uint256)",
+];
+
+/// Uniswap v3 fee tiers searched for a token's wrapped-native pool.
+const PROBE_FEE_TIERS: [u32; 3] = [500, 3_000, 10_000];
+
 #[derive(Debug, Clone)]
 pub struct EvmChain {
     pub name: String,
@@ -311,6 +483,41 @@ impl EvmChain {
         .await
     }
 
+    pub async fn get_block_number(&self) -> eyre::Result<u64> {
+        self.with_fallback_and_backoff("get block number", |p| async move {
+            let v = p.get_block_number().await.context("get block number")?;
+            Ok(v)
+        })
+        .await
+    }
+
+    pub async fn get_gas_price(&self) -> eyre::Result<u128> {
+        self.with_fallback_and_backoff("get gas price", |p| async move {
+            let v = p.get_gas_price().await.context("get gas price")?;
+            Ok(v)
+        })
+        .await
+    }
+
+    /// Fee history for the last `blocks` blocks, with p25/p50/p75 priority-fee rewards.
+    pub async fn get_fee_history(
+        &self,
+        blocks: u64,
+    ) -> eyre::Result<alloy::rpc::types::FeeHistory> {
+        self.with_fallback_and_backoff("get fee history", |p| async move {
+            let v = p
+                .get_fee_history(
+                    blocks,
+                    BlockNumberOrTag::Latest,
+                    &[25.0_f64, 50.0_f64, 75.0_f64],
+                )
+                .await
+                .context("get fee history")?;
+            Ok(v)
+        })
+        .await
+    }
+
     pub async fn get_erc20_balance(
         &self,
         token: Address,
@@ -606,6 +813,74 @@ impl EvmChain {
             .with_input(Bytes::from(calldata))
     }
 
+    /// ERC-1155 `safeTransferFrom` for `value` units of `id` (empty `data`).
+    pub fn build_erc1155_safe_transfer_from(
+        from: Address,
+        contract: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+    ) -> TransactionRequest {
+        let calldata = IERC1155::safeTransferFromCall {
+            from,
+            to,
+            id,
+            value,
+            data: Bytes::new(),
+        }
+        .abi_encode();
+        TransactionRequest::default()
+            .with_from(from)
+            .with_to(contract)
+            .with_input(Bytes::from(calldata))
+    }
+
+    /// ERC-1155 `safeBatchTransferFrom`; `ids` and `values` are parallel arrays.
+    pub fn build_erc1155_safe_batch_transfer_from(
+        from: Address,
+        contract: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+    ) -> TransactionRequest {
+        let calldata = IERC1155::safeBatchTransferFromCall {
+            from,
+            to,
+            ids,
+            values,
+            data: Bytes::new(),
+        }
+        .abi_encode();
+        TransactionRequest::default()
+            .with_from(from)
+            .with_to(contract)
+            .with_input(Bytes::from(calldata))
+    }
+
+    /// Detect an NFT contract's standard via ERC-165. `None` if it advertises neither (or does
+    /// not implement ERC-165 at all); ERC-1155 wins if a contract claims both.
+    pub async fn detect_nft_standard(
+        &self,
+        contract: Address,
+    ) -> eyre::Result<Option<NftStandard>> {
+        self.with_fallback_and_backoff("erc165 supportsInterface", |p| async move {
+            let c = IERC165::new(contract, &p);
+            // A revert means "no ERC-165", not a transport failure worth retrying.
+            let supports = |id: [u8; 4]| {
+                let c = &c;
+                async move { c.supportsInterface(id.into()).call().await.unwrap_or(false) }
+            };
+            if supports(ERC1155_INTERFACE_ID).await {
+                return Ok(Some(NftStandard::Erc1155));
+            }
+            if supports(ERC721_INTERFACE_ID).await {
+                return Ok(Some(NftStandard::Erc721));
+            }
+            Ok(None)
+        })
+        .await
+    }
+
     pub async fn erc20_allowance(
         &self,
         token: Address,
@@ -624,6 +899,84 @@ impl EvmChain {
         .await
     }
 
+    /// Permit2 allowance `owner` granted `spender` for `token`, as `(amount, expiration_unix)`.
+    pub async fn permit2_allowance(
+        &self,
+        owner: Address,
+        token: Address,
+        spender: Address,
+    ) -> eyre::Result<(U256, u64)> {
+        let permit2 = Self::parse_address(PERMIT2)?;
+        self.with_fallback_and_backoff("permit2 allowance", |p| async move {
+            let c = IPermit2::new(permit2, &p);
+            let r = c
+                .allowance(owner, token, spender)
+                .call()
+                .await
+                .context("permit2 allowance")?;
+            Ok((U256::from(r.amount), r.expiration.to::<u64>()))
+        })
+        .await
+    }
+
+    /// ERC-20 and Permit2 `Approval` events emitted for `owner` in `from_block..=to_block`.
+    ///
+    /// ERC-721 approvals share the ERC-20 event signature but index a fourth topic; they are
+    /// skipped. RPCs commonly cap the range per request, so callers should page.
+    pub async fn approval_logs(
+        &self,
+        owner: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> eyre::Result<ApprovalLogScan> {
+        let permit2 = Self::parse_address(PERMIT2)?;
+        let owner_topic = owner.into_word();
+        let erc20_filter = Filter::new()
+            .from_block(from_block)
+            .to_block(to_block)
+            .event_signature(keccak256(b"Approval(address,address,uint256)"))
+            .topic1(owner_topic);
+        let permit2_filter = Filter::new()
+            .address(permit2)
+            .from_block(from_block)
+            .to_block(to_block)
+            .event_signature(keccak256(
+                b"Approval(address,address,address,uint160,uint48)",
+            ))
+            .topic1(owner_topic);
+
+        let mut out = ApprovalLogScan::default();
+        let logs = self
+            .with_fallback_and_backoff("erc20 approval logs", |p| {
+                let f = erc20_filter.clone();
+                async move { p.get_logs(&f).await.context("erc20 approval logs") }
+            })
+            .await?;
+        for log in &logs {
+            if let [_, _, spender] = log.topics() {
+                out.erc20.push(ApprovalPair {
+                    token: log.address(),
+                    spender: Address::from_word(*spender),
+                });
+            }
+        }
+        let logs = self
+            .with_fallback_and_backoff("permit2 approval logs", |p| {
+                let f = permit2_filter.clone();
+                async move { p.get_logs(&f).await.context("permit2 approval logs") }
+            })
+            .await?;
+        for log in &logs {
+            if let [_, _, token, spender] = log.topics() {
+                out.permit2.push(ApprovalPair {
+                    token: Address::from_word(*token),
+                    spender: Address::from_word(*spender),
+                });
+            }
+        }
+        Ok(out)
+    }
+
     pub fn parse_address(s: &str) -> eyre::Result<Address> {
         Address::from_str(s).context("parse evm address")
     }
@@ -817,17 +1170,18 @@ impl EvmChain {
         let exact = router.exactInputSingle(params);
 
         let mut value = U256::ZERO;
-        let data: Bytes = if req.native_out {
+        let mut calls: Vec<Bytes> = vec![exact.calldata().clone()];
+        if req.native_out {
             // exactInputSingle(recipient=router) then unwrapWETH9 to the user
             let unwrap = router.unwrapWETH9(U256::ZERO, req.from);
-            let calldata_exact = exact.calldata().clone();
-            let calldata_unwrap = unwrap.calldata().clone();
-            let calls = vec![calldata_exact, calldata_unwrap];
-            let mc = router.multicall(calls);
-            mc.calldata().clone()
-        } else {
-            exact.calldata().clone()
-        };
+            calls.push(unwrap.calldata().clone());
+        }
+        let data: Bytes = multicallCall {
+            deadline: U256::from(req.deadline),
+            data: calls,
+        }
+        .abi_encode()
+        .into();
 
         if req.native_in {
             value = req.amount_in;
@@ -841,6 +1195,310 @@ impl EvmChain {
         tx.chain_id = Some(self.chain_id);
         Ok(tx)
     }
+
+    async fn erc20_balance_of(&self, token: Address, owner: Address) -> eyre::Result<U256> {
+        self.with_fallback_and_backoff("erc20 balanceOf", |p| async move {
+            let c = IERC20::new(token, &p);
+            let bal = c.balanceOf(owner).call().await.context("erc20 balanceOf")?;
+            Ok(bal)
+        })
+        .await
+    }
+
+    /// The token's Uniswap v3 pool against wrapped native with the most wrapped-native
+    /// liquidity, as `(pool, fee, wrapped_native_balance)`.
+    async fn deepest_native_pool(
+        &self,
+        u: &UniswapAddresses,
+        token: Address,
+    ) -> eyre::Result<Option<(Address, u32, U256)>> {
+        let router02 = u.router02;
+        let factory = self
+            .with_fallback_and_backoff("uniswap factory", |p| async move {
+                let r = ISwapRouter02::new(router02, &p);
+                r.factory().call().await.context("uniswap factory")
+            })
+            .await?;
+        let weth = u.wrapped_native;
+        let mut best: Option<(Address, u32, U256)> = None;
+        for fee in PROBE_FEE_TIERS {
+            let pool = self
+                .with_fallback_and_backoff("uniswap getPool", |p| async move {
+                    let f = IUniswapV3Factory::new(factory, &p);
+                    f.getPool(token, weth, alloy::primitives::Uint::from(fee))
+                        .call()
+                        .await
+                        .context("uniswap getPool")
+                })
+                .await?;
+            if pool == Address::ZERO {
+                continue;
+            }
+            let depth = self.erc20_balance_of(weth, pool).await?;
+            let deeper = match &best {
+                Some((_, _, d)) => depth > *d,
+                None => true,
+            };
+            if deeper {
+                best = Some((pool, fee, depth));
+            }
+        }
+        Ok(best)
+    }
+
+    /// Simulate buying `token` with native currency and selling it back through Uniswap v3,
+    /// read-only, in a single `eth_simulateV1` round trip from a funded throwaway address.
+    ///
+    /// The probe size is 0.1% of the pool's wrapped-native liquidity so price impact does not
+    /// read as tax. Returns `Ok(None)` when the chain has no Uniswap deployment or the token has
+    /// no wrapped-native pool; errors if the RPC cannot simulate.
+    pub async fn probe_token_trade(&self, token: Address) -> eyre::Result<Option<TradeProbe>> {
+        let Some(u) = self.uniswap.clone() else {
+            return Ok(None);
+        };
+        let Some((pool, fee, depth)) = self.deepest_native_pool(&u, token).await? else {
+            return Ok(None);
+        };
+        let probe_in = depth / U256::from(1_000_u32);
+        if probe_in.is_zero() {
+            return Ok(None);
+        }
+        let mut probe = TradeProbe {
+            pool,
+            fee,
+            probe_in_wei: probe_in,
+            buy_ok: false,
+            buy_tax_bps: None,
+            sell_transfer_ok: false,
+            sell_tax_bps: None,
+            sell_ok: false,
+            error: None,
+        };
+        // The quoter reports the pre-tax output; sizing the transfer and sell legs at a quarter
+        // of it each keeps them funded even under heavy buy tax.
+        let Ok(quoted) = self
+            .quote_uniswap_exact_in(u.wrapped_native, token, probe_in, fee)
+            .await
+        else {
+            probe.error = Some("buy quote reverted".to_owned());
+            return Ok(Some(probe));
+        };
+        let leg = quoted / U256::from(4_u32);
+
+        let sim = Address::from_word(keccak256(b"seashail.inspect_token.probe"));
+        let call = |to: Address, data: Vec<u8>, value: U256| {
+            TransactionRequest::default()
+                .with_from(sim)
+                .with_to(to)
+                .with_input(Bytes::from(data))
+                .with_value(value)
+        };
+        let swap = |token_in: Address, token_out: Address, amount_in: U256| {
+            ISwapRouter02::exactInputSingleCall {
+                params: ISwapRouter02::ExactInputSingleParams {
+                    tokenIn: token_in,
+                    tokenOut: token_out,
+                    fee: alloy::primitives::Uint::from(fee),
+                    recipient: sim,
+                    amountIn: amount_in,
+                    amountOutMinimum: U256::ZERO,
+                    sqrtPriceLimitX96: alloy::primitives::Uint::ZERO,
+                },
+            }
+            .abi_encode()
+        };
+        let balance_of = |account: Address| IERC20::balanceOfCall { account }.abi_encode();
+        let calls = vec![
+            // 0: buy with native (the router wraps msg.value)
+            call(
+                u.router02,
+                swap(u.wrapped_native, token, probe_in),
+                probe_in,
+            ),
+            // 1: tokens actually received
+            call(token, balance_of(sim), U256::ZERO),
+            // 2-4: transfer into the pool, as a sell would, and measure what arrives
+            call(token, balance_of(pool), U256::ZERO),
+            call(
+                token,
+                IERC20::transferCall {
+                    to: pool,
+                    value: leg,
+                }
+                .abi_encode(),
+                U256::ZERO,
+            ),
+            call(token, balance_of(pool), U256::ZERO),
+            // 5-6: approve and sell back through the router
+            call(
+                token,
+                IERC20::approveCall {
+                    spender: u.router02,
+                    value: U256::MAX,
+                }
+                .abi_encode(),
+                U256::ZERO,
+            ),
+            call(u.router02, swap(token, u.wrapped_native, leg), U256::ZERO),
+        ];
+        let overrides = StateOverridesBuilder::default()
+            .with_balance(sim, probe_in.saturating_add(U256::from(10_u128.pow(20))))
+            .build();
+        let payload = SimulatePayload::default().extend(
+            SimBlock::default()
+                .with_state_overrides(overrides)
+                .extend_calls(calls),
+        );
+        let blocks = self
+            .with_fallback_and_backoff("eth_simulateV1", |p| {
+                let payload = payload.clone();
+                async move { p.simulate(&payload).await.context("eth_simulateV1") }
+            })
+            .await?;
+        let results = blocks
+            .into_iter()
+            .next()
+            .map(|b| b.calls)
+            .unwrap_or_default();
+        let [buy, received, pool_before, transfer, pool_after, _approve, sell] = results.as_slice()
+        else {
+            eyre::bail!("eth_simulateV1: unexpected call result count");
+        };
+
+        probe.buy_ok = buy.status;
+        if !buy.status {
+            probe.error = Some(format!("buy reverted: {}", sim_call_error(buy)));
+            return Ok(Some(probe));
+        }
+        if let (Some(expected), Some(got)) = (
+            first_word(&buy.return_data),
+            first_word(&received.return_data),
+        ) {
+            probe.buy_tax_bps = tax_bps(expected, got);
+        }
+        probe.sell_transfer_ok = transfer.status;
+        if transfer.status {
+            if let (Some(before), Some(after)) = (
+                first_word(&pool_before.return_data),
+                first_word(&pool_after.return_data),
+            ) {
+                probe.sell_tax_bps = tax_bps(leg, after.saturating_sub(before));
+            }
+        } else {
+            probe.error = Some(format!(
+                "transfer to pool reverted: {}",
+                sim_call_error(transfer)
+            ));
+        }
+        probe.sell_ok = sell.status;
+        if !sell.status && probe.error.is_none() {
+            probe.error = Some(format!("sell reverted: {}", sim_call_error(sell)));
+        }
+        Ok(Some(probe))
+    }
+}
+
+/// Tax in basis points implied by receiving `received` where `expected` was owed.
+pub fn tax_bps(expected: U256, received: U256) -> Option<u32> {
+    if expected.is_zero() {
+        return None;
+    }
+    let lost = expected.saturating_sub(received);
+    let bps = lost.saturating_mul(U256::from(10_000_u32)) / expected;
+    Some(u32::try_from(bps).unwrap_or(10_000))
+}
+
+/// Every PUSH4 immediate in `code`. Opcodes are walked so push data is never read as an
+/// instruction; Solidity dispatchers compare calldata selectors against these constants.
+pub fn bytecode_push4_selectors(code: &[u8]) -> HashSet<[u8; 4]> {
+    const PUSH1: u8 = 0x60;
+    const PUSH4: u8 = 0x63;
+    const PUSH32: u8 = 0x7f;
+    let mut out = HashSet::new();
+    let mut i = 0_usize;
+    while let Some(&op) = code.get(i) {
+        if (PUSH1..=PUSH32).contains(&op) {
+            let n = usize::from(op - PUSH1) + 1;
+            if op == PUSH4 {
+                if let Some(sel) = code
+                    .get(i + 1..i + 5)
+                    .and_then(|b| <[u8; 4]>::try_from(b).ok())
+                {
+                    out.insert(sel);
+                }
+            }
+            i = i.saturating_add(n + 1);
+        } else {
+            i = i.saturating_add(1);
+        }
+    }
+    out
+}
+
+/// The 4-byte function selector for a canonical signature such as `paused()`.
+pub fn function_selector(signature: &str) -> [u8; 4] {
+    let h = keccak256(signature.as_bytes());
+    h.get(..4)
+        .and_then(|b| <[u8; 4]>::try_from(b).ok())
+        .unwrap_or_default()
+}
+
+/// Match well-known blacklist/pause/fee-setter selectors against the given bytecodes (pass the
+/// proxy and its implementation together so either can contribute).
+pub fn scan_token_code_flags(codes: &[&[u8]]) -> TokenCodeFlags {
+    let selectors: HashSet<[u8; 4]> = codes
+        .iter()
+        .flat_map(|c| bytecode_push4_selectors(c))
+        .collect();
+    let any = |sigs: &[&str]| {
+        sigs.iter()
+            .any(|sig| selectors.contains(&function_selector(sig)))
+    };
+    TokenCodeFlags {
+        has_blacklist: any(BLACKLIST_SIGNATURES),
+        has_pause: any(PAUSE_SIGNATURES),
+        has_mutable_fee: any(MUTABLE_FEE_SIGNATURES),
+    }
+}
+
+fn first_word(b: &[u8]) -> Option<U256> {
+    b.get(..32).map(U256::from_be_slice)
+}
+
+fn sim_call_error(c: &SimCallResult) -> String {
+    c.error
+        .as_ref()
+        .map_or_else(|| "reverted".to_owned(), |e| e.message.clone())
+}
+
+/// Whether an allowance is effectively unlimited. Some tokens (UNI, COMP) store allowances as
+/// `uint96` and report `2^96 - 1` for an infinite approval, so anything at or above that counts.
+pub fn is_unlimited_allowance(v: U256) -> bool {
+    v >= (U256::from(1_u8) << 96_usize) - U256::from(1_u8)
+}
+
+/// Calldata that sets an ERC-20 allowance for `spender` back to zero (send to the token).
+pub fn erc20_revoke_calldata(spender: Address) -> Bytes {
+    Bytes::from(
+        IERC20::approveCall {
+            spender,
+            value: U256::ZERO,
+        }
+        .abi_encode(),
+    )
+}
+
+/// Calldata that zeroes a Permit2 allowance for `(token, spender)` (send to [`PERMIT2`]).
+pub fn permit2_revoke_calldata(token: Address, spender: Address) -> Bytes {
+    Bytes::from(
+        IPermit2::approveCall {
+            token,
+            spender,
+            amount: alloy::primitives::Uint::ZERO,
+            expiration: alloy::primitives::Uint::ZERO,
+        }
+        .abi_encode(),
+    )
 }
 
 pub fn parse_u256_dec(s: &str) -> eyre::Result<U256> {
@@ -929,6 +1587,135 @@ fn uniswap_defaults(name: &str) -> Option<UniswapAddresses> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn erc1155_transfer_calldata_uses_standard_selectors() {
+        let from = Address::repeat_byte(0x11);
+        let to = Address::repeat_byte(0x22);
+        let contract = Address::repeat_byte(0x33);
+        let single = EvmChain::build_erc1155_safe_transfer_from(
+            from,
+            contract,
+            to,
+            U256::from(7_u32),
+            U256::from(3_u32),
+        );
+        let input = single.input.input().cloned().unwrap_or_default();
+        assert_eq!(input.get(..4), Some(&[0xf2, 0x42, 0x43, 0x2a][..]));
+
+        let batch = EvmChain::build_erc1155_safe_batch_transfer_from(
+            from,
+            contract,
+            to,
+            vec![U256::from(1_u32), U256::from(2_u32)],
+            vec![U256::from(5_u32), U256::from(1_u32)],
+        );
+        let input = batch.input.input().cloned().unwrap_or_default();
+        assert_eq!(input.get(..4), Some(&[0x2e, 0xb2, 0xc2, 0xd6][..]));
+        let decoded = IERC1155::safeBatchTransferFromCall::abi_decode(&input)
+            .map_err(|e| eyre::eyre!("decode batch calldata: {e}"));
+        assert!(decoded.is_ok_and(|c| c.ids.len() == 2 && c.values.len() == 2 && c.to == to));
+
+        assert_eq!(NftStandard::parse("ERC-1155"), Some(NftStandard::Erc1155));
+        assert_eq!(NftStandard::parse("erc721"), Some(NftStandard::Erc721));
+        assert_eq!(NftStandard::parse("erc20"), None);
+    }
+
+    #[test]
+    fn tax_bps_measures_shortfall() {
+        let hundred = U256::from(100_u32);
+        assert_eq!(tax_bps(hundred, hundred), Some(0));
+        assert_eq!(tax_bps(hundred, U256::from(95_u32)), Some(500));
+        assert_eq!(tax_bps(hundred, U256::ZERO), Some(10_000));
+        assert_eq!(tax_bps(hundred, U256::from(120_u32)), Some(0));
+        assert_eq!(tax_bps(U256::ZERO, hundred), None);
+    }
+
+    #[test]
+    fn push4_scan_skips_push_data_and_flags_known_selectors() {
+        let blacklist = function_selector("isBlacklisted(address)");
+        let pause = function_selector("paused()");
+        assert_eq!(pause, [0x5c, 0x97, 0x5a, 0xbb]);
+        // PUSH4 <blacklist>, PUSH5 <0x63 + pause selector hidden inside data>, STOP
+        let mut code = vec![0x63];
+        code.extend_from_slice(&blacklist);
+        code.push(0x64);
+        code.push(0x63);
+        code.extend_from_slice(&pause);
+        code.push(0x00);
+
+        let found = bytecode_push4_selectors(&code);
+        assert!(found.contains(&blacklist));
+        assert!(!found.contains(&pause), "push data must not be decoded");
+
+        let flags = scan_token_code_flags(&[&code]);
+        assert_eq!(
+            flags,
+            TokenCodeFlags {
+                has_blacklist: true,
+                has_pause: false,
+                has_mutable_fee: false,
+            }
+        );
+
+        let mut impl_code = vec![0x63];
+        impl_code.extend_from_slice(&pause);
+        let flags = scan_token_code_flags(&[&code, &impl_code]);
+        assert!(flags.has_blacklist && flags.has_pause);
+    }
+
+    #[test]
+    fn unlimited_allowance_covers_uint96_max() {
+        assert!(is_unlimited_allowance(U256::MAX));
+        assert!(is_unlimited_allowance(
+            (U256::from(1_u8) << 96_usize) - U256::from(1_u8)
+        ));
+        assert!(!is_unlimited_allowance(U256::from(u64::MAX)));
+        assert!(!is_unlimited_allowance(U256::ZERO));
+    }
+
+    #[test]
+    fn trade_probe_sellable_accounts_for_fee_on_transfer() {
+        let base = TradeProbe {
+            pool: Address::ZERO,
+            fee: 3_000,
+            probe_in_wei: U256::from(1_u32),
+            buy_ok: true,
+            buy_tax_bps: Some(0),
+            sell_transfer_ok: true,
+            sell_tax_bps: Some(0),
+            sell_ok: true,
+            error: None,
+        };
+        assert!(base.sellable());
+
+        // Untaxed transfer but the router sell reverts: the token blocks sells.
+        let blocked = TradeProbe {
+            sell_ok: false,
+            ..base.clone()
+        };
+        assert!(!blocked.sellable());
+
+        // Taxed transfer explains a v3 revert; still sellable (with tax).
+        let taxed = TradeProbe {
+            sell_ok: false,
+            sell_tax_bps: Some(500),
+            ..base.clone()
+        };
+        assert!(taxed.sellable());
+
+        let confiscatory = TradeProbe {
+            sell_tax_bps: Some(10_000),
+            ..base.clone()
+        };
+        assert!(!confiscatory.sellable());
+
+        let no_transfer = TradeProbe {
+            sell_transfer_ok: false,
+            ..base
+        };
+        assert!(!no_transfer.sellable());
+    }
+
     #[test]
     fn eip1559_fee_policy_is_conservative_and_monotonic() {
         let base_fee: u128 = 10_000_000_000; // 10 gwei
@@ -1,3 +1,5 @@
 pub mod bitcoin;
+pub mod erc4337;
 pub mod evm;
+pub mod native;
 pub mod solana;
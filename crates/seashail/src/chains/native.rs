@@ -0,0 +1,120 @@
+//! Native (gas) token of each supported chain.
+//!
+//! Display, "send max" math, and USD valuation should consult this registry rather than
+//! assuming ETH/SOL.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NativeToken {
+    pub symbol: &'static str,
+    pub decimals: u8,
+    /// CoinGecko asset id (empty if unknown).
+    pub coingecko_id: &'static str,
+    /// Binance base symbols tried in order for USD pricing (empty if unpriced).
+    pub price_symbols: &'static [&'static str],
+}
+
+impl NativeToken {
+    pub const fn is_priced(&self) -> bool {
+        !self.price_symbols.is_empty()
+    }
+}
+
+const ETH: NativeToken = NativeToken {
+    symbol: "ETH",
+    decimals: 18,
+    coingecko_id: "ethereum",
+    price_symbols: &["ETH"],
+};
+
+const SOL: NativeToken = NativeToken {
+    symbol: "SOL",
+    decimals: 9,
+    coingecko_id: "solana",
+    price_symbols: &["SOL"],
+};
+
+const BTC: NativeToken = NativeToken {
+    symbol: "BTC",
+    decimals: 8,
+    coingecko_id: "bitcoin",
+    price_symbols: &["BTC"],
+};
+
+// Polygon migrated MATIC -> POL, but MATIC pricing is still commonly available; try both.
+const POL: NativeToken = NativeToken {
+    symbol: "POL",
+    decimals: 18,
+    coingecko_id: "polygon-ecosystem-token",
+    price_symbols: &["POL", "MATIC"],
+};
+
+const BNB: NativeToken = NativeToken {
+    symbol: "BNB",
+    decimals: 18,
+    coingecko_id: "binancecoin",
+    price_symbols: &["BNB"],
+};
+
+const AVAX: NativeToken = NativeToken {
+    symbol: "AVAX",
+    decimals: 18,
+    coingecko_id: "avalanche-2",
+    price_symbols: &["AVAX"],
+};
+
+const MON: NativeToken = NativeToken {
+    symbol: "MON",
+    decimals: 18,
+    coingecko_id: "monad",
+    price_symbols: &["MON"],
+};
+
+/// Fallback for EVM chains we have no entry for: 18 decimals, no price source.
+const UNKNOWN_EVM: NativeToken = NativeToken {
+    symbol: "NATIVE",
+    decimals: 18,
+    coingecko_id: "",
+    price_symbols: &[],
+};
+
+/// Native token for `chain`. Unknown chain names are treated as EVM chains.
+pub fn native_token(chain: &str) -> NativeToken {
+    match chain {
+        "solana" => SOL,
+        "bitcoin" => BTC,
+        "ethereum" | "base" | "arbitrum" | "optimism" | "sepolia" | "base-sepolia"
+        | "arbitrum-sepolia" | "optimism-sepolia" => ETH,
+        "polygon" | "polygon-amoy" => POL,
+        "bnb" | "bnb-testnet" => BNB,
+        "avalanche" | "avalanche-fuji" => AVAX,
+        "monad" | "monad-testnet" => MON,
+        _ => UNKNOWN_EVM,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn testnets_share_their_mainnet_native_token() {
+        for (main, test) in [
+            ("ethereum", "sepolia"),
+            ("polygon", "polygon-amoy"),
+            ("bnb", "bnb-testnet"),
+            ("avalanche", "avalanche-fuji"),
+            ("monad", "monad-testnet"),
+        ] {
+            assert_eq!(native_token(main), native_token(test), "{main} vs {test}");
+        }
+    }
+
+    #[test]
+    fn non_evm_decimals_and_unknown_fallback() {
+        assert_eq!(native_token("solana").decimals, 9, "lamports");
+        assert_eq!(native_token("bitcoin").decimals, 8, "sats");
+        let unknown = native_token("my-rollup");
+        assert_eq!(unknown.decimals, 18, "unknown chains are EVM");
+        assert!(!unknown.is_priced(), "no price source for unknown chains");
+    }
+}
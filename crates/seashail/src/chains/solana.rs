@@ -30,6 +30,146 @@ use crate::retry::{try_all_with_backoff, BackoffConfig};
 
 const MAX_REMOTE_TX_BYTES: u64 = 2 * 1024 * 1024;
 
+#[derive(Debug, Clone)]
+pub struct SignatureConfirmation {
+    pub slot: u64,
+    pub confirmed: bool,
+    pub finalized: bool,
+    /// Execution error, if the transaction landed but failed.
+    pub err: Option<String>,
+}
+
+/// Size of a System-program nonce account (`nonce::state::Versions`, bincode).
+pub const NONCE_ACCOUNT_LENGTH: usize = 80;
+/// `SystemInstruction::AdvanceNonceAccount` (bincode enum tag).
+const ADVANCE_NONCE_IX_TAG: u32 = 4;
+
+/// SPL Memo program (v2): Base58("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr").
+pub const MEMO_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    5, 74, 83, 90, 153, 41, 33, 6, 77, 36, 232, 113, 96, 218, 56, 124, 124, 53, 181, 221, 188, 146,
+    187, 129, 228, 31, 168, 64, 65, 5, 68, 141,
+]);
+/// Largest memo attached on-chain; keeps a transfer plus memo well under the packet limit.
+pub const MAX_MEMO_BYTES: usize = 256;
+
+/// SPL Memo instruction carrying `memo`, signed by `signer` so the memo is attributable.
+/// Memos are public and permanent on-chain.
+pub fn memo_instruction(
+    signer: &Pubkey,
+    memo: &str,
+) -> eyre::Result<solana_sdk::instruction::Instruction> {
+    if memo.is_empty() || memo.len() > MAX_MEMO_BYTES {
+        eyre::bail!("memo must be 1-{MAX_MEMO_BYTES} bytes");
+    }
+    Ok(solana_sdk::instruction::Instruction {
+        program_id: MEMO_PROGRAM_ID,
+        accounts: vec![solana_sdk::instruction::AccountMeta::new_readonly(
+            *signer, true,
+        )],
+        data: memo.as_bytes().to_vec(),
+    })
+}
+
+/// Initialized durable nonce account contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceAccountState {
+    pub authority: Pubkey,
+    /// The stored durable nonce; used in place of a recent blockhash.
+    pub nonce: Hash,
+    pub lamports_per_signature: u64,
+}
+
+/// Decode System-program nonce account data. `Ok(None)` if the account is uninitialized.
+///
+/// Layout: `u32` version tag, `u32` state tag, then (if initialized) authority (32), durable
+/// nonce (32), and `lamports_per_signature` (`u64`), all little-endian.
+pub fn parse_nonce_account_data(data: &[u8]) -> eyre::Result<Option<NonceAccountState>> {
+    let le_u32 = |at: usize| -> eyre::Result<u32> {
+        let b: [u8; 4] = data
+            .get(at..at + 4)
+            .and_then(|s| s.try_into().ok())
+            .ok_or_else(|| eyre::eyre!("nonce account data too short"))?;
+        Ok(u32::from_le_bytes(b))
+    };
+    if data.len() != NONCE_ACCOUNT_LENGTH {
+        eyre::bail!(
+            "not a nonce account (data length {}, expected {NONCE_ACCOUNT_LENGTH})",
+            data.len()
+        );
+    }
+    if le_u32(0)? > 1 {
+        eyre::bail!("unknown nonce account version");
+    }
+    match le_u32(4)? {
+        0 => return Ok(None),
+        1 => {}
+        _ => eyre::bail!("unknown nonce account state"),
+    }
+    let bytes32 = |at: usize| -> eyre::Result<[u8; 32]> {
+        data.get(at..at + 32)
+            .and_then(|s| s.try_into().ok())
+            .ok_or_else(|| eyre::eyre!("nonce account data too short"))
+    };
+    let fee: [u8; 8] = data
+        .get(72..80)
+        .and_then(|s| s.try_into().ok())
+        .ok_or_else(|| eyre::eyre!("nonce account data too short"))?;
+    Ok(Some(NonceAccountState {
+        authority: Pubkey::new_from_array(bytes32(8)?),
+        nonce: Hash::new_from_array(bytes32(40)?),
+        lamports_per_signature: u64::from_le_bytes(fee),
+    }))
+}
+
+/// Seed for the `index`-th durable nonce account derived from a wallet account
+/// (`create_with_seed(authority, seed, system_program)`), so no extra keypair needs storing.
+pub fn nonce_account_seed(index: u32) -> String {
+    format!("seashail-nonce-{index}")
+}
+
+pub fn nonce_account_address(authority: &Pubkey, index: u32) -> eyre::Result<Pubkey> {
+    Pubkey::create_with_seed(
+        authority,
+        &nonce_account_seed(index),
+        &solana_system_interface::program::id(),
+    )
+    .map_err(|e| eyre::eyre!("derive nonce account address: {e}"))
+}
+
+/// The nonce account a durable-nonce transaction advances, if its first instruction is
+/// `AdvanceNonceAccount` (which the runtime requires for durable-nonce transactions).
+pub fn durable_nonce_account_of(tx: &solana_sdk::transaction::Transaction) -> Option<Pubkey> {
+    let msg = &tx.message;
+    let ix = msg.instructions.first()?;
+    let program = msg.account_keys.get(usize::from(ix.program_id_index))?;
+    if *program != solana_system_interface::program::id()
+        || ix.data.get(..4) != Some(ADVANCE_NONCE_IX_TAG.to_le_bytes().as_slice())
+    {
+        return None;
+    }
+    let nonce_idx = ix.accounts.first()?;
+    msg.account_keys.get(usize::from(*nonce_idx)).copied()
+}
+
+/// Base64 wire encoding of a signed legacy transaction (as accepted by `sendTransaction`).
+pub fn encode_transaction_base64(
+    tx: &solana_sdk::transaction::Transaction,
+) -> eyre::Result<String> {
+    let bytes = bincode::serialize(tx).context("serialize tx")?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+pub fn decode_transaction_base64(b64: &str) -> eyre::Result<solana_sdk::transaction::Transaction> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(b64.trim())
+        .context("decode base64 tx")?;
+    bincode::DefaultOptions::new()
+        .with_limit(MAX_REMOTE_TX_BYTES)
+        .with_fixint_encoding()
+        .deserialize(&bytes)
+        .context("deserialize tx")
+}
+
 const fn compute_budget_program_id() -> solana_sdk::pubkey::Pubkey {
     // Base58("ComputeBudget111111111111111111111111111111")
     solana_sdk::pubkey::Pubkey::new_from_array([
@@ -260,6 +400,39 @@ impl SolanaChain {
         .await
     }
 
+    pub async fn get_slot(&self) -> eyre::Result<u64> {
+        self.with_fallback_and_backoff("get slot", |rpc| async move {
+            let slot = rpc.get_slot().await.context("get slot")?;
+            Ok(slot)
+        })
+        .await
+    }
+
+    /// Commitment reached by a signature, or `None` if the cluster has not seen it yet.
+    pub async fn get_signature_confirmation(
+        &self,
+        sig: Signature,
+    ) -> eyre::Result<Option<SignatureConfirmation>> {
+        self.with_fallback_and_backoff("get signature status", |rpc| async move {
+            let statuses = rpc
+                .get_signature_statuses(&[sig])
+                .await
+                .context("get signature statuses")?;
+            Ok(statuses
+                .value
+                .into_iter()
+                .next()
+                .flatten()
+                .map(|st| SignatureConfirmation {
+                    slot: st.slot,
+                    confirmed: st.satisfies_commitment(CommitmentConfig::confirmed()),
+                    finalized: st.satisfies_commitment(CommitmentConfig::finalized()),
+                    err: st.err.map(|e| e.to_string()),
+                }))
+        })
+        .await
+    }
+
     pub async fn get_account(&self, key: &Pubkey) -> eyre::Result<Account> {
         let k = *key;
         self.with_fallback_and_backoff("get account", |rpc| async move {
@@ -293,6 +466,182 @@ impl SolanaChain {
         .await
     }
 
+    pub async fn get_rent_exempt_minimum(&self, data_len: usize) -> eyre::Result<u64> {
+        self.with_fallback_and_backoff("rent exemption", |rpc| async move {
+            rpc.get_minimum_balance_for_rent_exemption(data_len)
+                .await
+                .context("get rent exemption")
+        })
+        .await
+    }
+
+    /// Durable nonce account state. `Ok(None)` if the account does not exist or is uninitialized.
+    pub async fn get_nonce_account(
+        &self,
+        addr: &Pubkey,
+    ) -> eyre::Result<Option<NonceAccountState>> {
+        let Some(acct) = self.get_account_optional(addr).await? else {
+            return Ok(None);
+        };
+        if acct.owner != solana_system_interface::program::id() {
+            eyre::bail!("{addr} is not owned by the system program (not a nonce account)");
+        }
+        parse_nonce_account_data(&acct.data)
+    }
+
+    /// Create (and fund with the rent-exempt minimum) the `index`-th seeded nonce account of
+    /// `keypair`, with `keypair` as nonce authority. Returns (signature, address, lamports).
+    pub async fn create_nonce_account(
+        &self,
+        keypair: &Keypair,
+        index: u32,
+    ) -> eyre::Result<(Signature, Pubkey, u64)> {
+        let authority = keypair.pubkey();
+        let nonce = nonce_account_address(&authority, index)?;
+        let lamports = self.get_rent_exempt_minimum(NONCE_ACCOUNT_LENGTH).await?;
+        let ixs = system_instruction::create_nonce_account_with_seed(
+            &authority,
+            &nonce,
+            &authority,
+            &nonce_account_seed(index),
+            &authority,
+            lamports,
+        );
+        let sig = self.sign_and_send_instructions(keypair, ixs).await?;
+        Ok((sig, nonce, lamports))
+    }
+
+    /// Withdraw a nonce account's full balance back to its authority, closing it.
+    pub async fn close_nonce_account(
+        &self,
+        keypair: &Keypair,
+        nonce: Pubkey,
+    ) -> eyre::Result<(Signature, u64)> {
+        let lamports = self.get_sol_balance(nonce).await?;
+        let ix = system_instruction::withdraw_nonce_account(
+            &nonce,
+            &keypair.pubkey(),
+            &keypair.pubkey(),
+            lamports,
+        );
+        let sig = self.sign_and_send_instructions(keypair, vec![ix]).await?;
+        Ok((sig, lamports))
+    }
+
+    /// Sign `instructions` against the durable nonce stored in `nonce` instead of a recent
+    /// blockhash, so the transaction does not expire until the nonce is advanced. The
+    /// `AdvanceNonceAccount` instruction is placed first, as the runtime requires.
+    pub async fn sign_with_durable_nonce(
+        &self,
+        keypair: &Keypair,
+        nonce: Pubkey,
+        instructions: Vec<solana_sdk::instruction::Instruction>,
+    ) -> eyre::Result<solana_sdk::transaction::Transaction> {
+        let state = self.get_nonce_account(&nonce).await?.ok_or_else(|| {
+            eyre::eyre!("nonce account {nonce} does not exist or is uninitialized")
+        })?;
+        if state.authority != keypair.pubkey() {
+            eyre::bail!(
+                "nonce account {nonce} is controlled by {}, not the signing account",
+                state.authority
+            );
+        }
+        let mut ixs = self.with_compute_budget_defaults(instructions);
+        ixs.insert(
+            0,
+            system_instruction::advance_nonce_account(&nonce, &keypair.pubkey()),
+        );
+        let msg = solana_sdk::message::Message::new(&ixs, Some(&keypair.pubkey()));
+        Ok(solana_sdk::transaction::Transaction::new(
+            &[keypair],
+            msg,
+            state.nonce,
+        ))
+    }
+
+    /// Broadcast a previously signed durable-nonce transaction. Fails with a "consumed" error if
+    /// the nonce has advanced since signing (the transaction landed already, or another
+    /// transaction used the nonce), since the cluster would reject it anyway.
+    pub async fn send_durable_nonce_transaction(
+        &self,
+        tx: &solana_sdk::transaction::Transaction,
+    ) -> eyre::Result<Signature> {
+        let nonce = durable_nonce_account_of(tx).ok_or_else(|| {
+            eyre::eyre!(
+                "not a durable-nonce transaction (first instruction must advance a nonce account)"
+            )
+        })?;
+        let state = self
+            .get_nonce_account(&nonce)
+            .await?
+            .ok_or_else(|| eyre::eyre!("nonce account {nonce} does not exist or was closed"))?;
+        if state.nonce != tx.message.recent_blockhash {
+            eyre::bail!(
+                "durable nonce consumed: nonce account {nonce} has advanced since this transaction was signed; re-sign it"
+            );
+        }
+        let sig = *tx
+            .signatures
+            .first()
+            .ok_or_else(|| eyre::eyre!("missing transaction signature"))?;
+        if sig == Signature::default() {
+            eyre::bail!("transaction is not signed");
+        }
+        self.simulate_send_confirm(tx).await?;
+        Ok(sig)
+    }
+
+    /// Simulate, broadcast, and confirm a fully signed legacy transaction.
+    async fn simulate_send_confirm(
+        &self,
+        tx: &solana_sdk::transaction::Transaction,
+    ) -> eyre::Result<()> {
+        let sig = *tx
+            .signatures
+            .first()
+            .ok_or_else(|| eyre::eyre!("missing transaction signature"))?;
+        self.with_fallback_and_backoff("simulate tx", |rpc| {
+            let tx = tx.clone();
+            async move {
+                let sim: RpcSimulateTransactionResult = rpc
+                    .simulate_transaction(&tx)
+                    .await
+                    .context("simulate tx")?
+                    .value;
+                if sim.err.is_some() {
+                    eyre::bail!("transaction simulation failed");
+                }
+                Ok(())
+            }
+        })
+        .await?;
+
+        self.with_fallback_and_backoff("send tx", |rpc| {
+            let tx = tx.clone();
+            async move {
+                rpc.send_transaction(&tx).await.context("send tx")?;
+                Ok(())
+            }
+        })
+        .await?;
+
+        let confirm_cfg = BackoffConfig {
+            rounds: 12,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(3),
+            jitter_max_ms: 200,
+        };
+        self.with_fallback_and_backoff_cfg(&confirm_cfg, "confirm tx", |rpc| async move {
+            let ok = rpc.confirm_transaction(&sig).await.context("confirm tx")?;
+            if ok {
+                Ok(())
+            } else {
+                eyre::bail!("transaction not yet confirmed")
+            }
+        })
+        .await
+    }
+
     pub async fn sign_and_send_instructions(
         &self,
         keypair: &Keypair,
@@ -492,6 +841,19 @@ impl SolanaChain {
         .await
     }
 
+    /// Recent per-slot prioritization fees (micro-lamports per CU), oldest slot first.
+    pub async fn get_recent_prioritization_fees(&self) -> eyre::Result<Vec<u64>> {
+        self.with_fallback_and_backoff("get recent prioritization fees", |rpc| async move {
+            let mut v = rpc
+                .get_recent_prioritization_fees(&[])
+                .await
+                .context("get recent prioritization fees")?;
+            v.sort_by_key(|f| f.slot);
+            Ok(v.into_iter().map(|f| f.prioritization_fee).collect())
+        })
+        .await
+    }
+
     pub async fn get_spl_balance(&self, owner: Pubkey, mint: Pubkey) -> eyre::Result<(u64, u8)> {
         self.with_fallback_and_backoff("get spl balance", |rpc| async move {
             let ata = get_associated_token_address(&owner, &mint);
@@ -698,52 +1060,10 @@ impl SolanaChain {
         amount: u64,
     ) -> eyre::Result<Signature> {
         let from_owner = keypair.pubkey();
-        let from_ata = get_associated_token_address(&from_owner, &mint);
-        let to_ata = get_associated_token_address(&to_owner, &mint);
-
-        let mut ixs = vec![];
-
-        // Create recipient ATA if missing.
-        let to_ata_exists = self
-            .with_fallback_and_backoff("check recipient ata", |rpc| async move {
-                Ok(rpc.get_account(&to_ata).await.is_ok())
-            })
-            .await
-            .unwrap_or(false);
-        if !to_ata_exists {
-            ixs.push(
-                spl_associated_token_account::instruction::create_associated_token_account(
-                    &from_owner,
-                    &to_owner,
-                    &mint,
-                    &spl_token::id(),
-                ),
-            );
-        }
-
-        // Mint decimals for checked transfer.
-        let m = self
-            .with_fallback_and_backoff("get mint account", |rpc| async move {
-                let mint_acc = rpc.get_account(&mint).await.context("get mint account")?;
-                let m = Mint::unpack(&mint_acc.data).context("unpack mint")?;
-                Ok(m)
-            })
+        let ixs = self
+            .spl_transfer_instructions(from_owner, to_owner, mint, amount)
             .await?;
 
-        ixs.push(
-            spl_token::instruction::transfer_checked(
-                &spl_token::id(),
-                &from_ata,
-                &mint,
-                &to_ata,
-                &from_owner,
-                &[],
-                amount,
-                m.decimals,
-            )
-            .context("build spl transfer")?,
-        );
-
         let bh = self
             .with_fallback_and_backoff("latest blockhash", |rpc| async move {
                 let bh = rpc
@@ -802,6 +1122,63 @@ impl SolanaChain {
         Ok(sig)
     }
 
+    /// Instructions for an SPL transfer, creating the recipient's ATA if it is missing.
+    pub async fn spl_transfer_instructions(
+        &self,
+        from_owner: Pubkey,
+        to_owner: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+    ) -> eyre::Result<Vec<solana_sdk::instruction::Instruction>> {
+        let from_ata = get_associated_token_address(&from_owner, &mint);
+        let to_ata = get_associated_token_address(&to_owner, &mint);
+
+        let mut ixs = vec![];
+
+        // Create recipient ATA if missing.
+        let to_ata_exists = self
+            .with_fallback_and_backoff("check recipient ata", |rpc| async move {
+                Ok(rpc.get_account(&to_ata).await.is_ok())
+            })
+            .await
+            .unwrap_or(false);
+        if !to_ata_exists {
+            ixs.push(
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &from_owner,
+                    &to_owner,
+                    &mint,
+                    &spl_token::id(),
+                ),
+            );
+        }
+
+        // Mint decimals for checked transfer.
+        let m = self
+            .with_fallback_and_backoff("get mint account", |rpc| async move {
+                let mint_acc = rpc.get_account(&mint).await.context("get mint account")?;
+                let m = Mint::unpack(&mint_acc.data).context("unpack mint")?;
+                Ok(m)
+            })
+            .await?;
+
+        ixs.push(
+            spl_token::instruction::transfer_checked(
+                &spl_token::id(),
+                &from_ata,
+                &mint,
+                &to_ata,
+                &from_owner,
+                &[],
+                amount,
+                m.decimals,
+            )
+            .context("build spl transfer")?,
+        );
+
+        Ok(ixs)
+    }
+
     pub async fn jupiter_quote(
         &self,
         input_mint: &str,
@@ -1151,6 +1528,27 @@ mod tests {
     use super::*;
     use solana_sdk::instruction::Instruction;
 
+    #[test]
+    fn memo_instruction_is_signed_by_sender_and_bounded() -> eyre::Result<()> {
+        let signer = Pubkey::new_unique();
+        let ix = memo_instruction(&signer, "rent payment")?;
+        assert_eq!(
+            ix.program_id.to_string(),
+            "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+        );
+        assert_eq!(ix.data, b"rent payment");
+        let meta = ix
+            .accounts
+            .first()
+            .ok_or_else(|| eyre::eyre!("missing signer meta"))?;
+        assert_eq!(meta.pubkey, signer);
+        assert!(meta.is_signer && !meta.is_writable);
+
+        assert!(memo_instruction(&signer, "").is_err());
+        assert!(memo_instruction(&signer, &"x".repeat(MAX_MEMO_BYTES + 1)).is_err());
+        Ok(())
+    }
+
     #[test]
     fn prepends_compute_budget_instructions_when_configured() -> eyre::Result<()> {
         let sol = SolanaChain::new_with_fallbacks(
@@ -1188,4 +1586,52 @@ mod tests {
         // If the caller already provided ComputeBudget, we preserve as-is.
         assert_eq!(out.len(), 2);
     }
+
+    #[test]
+    fn parses_nonce_account_data() -> eyre::Result<()> {
+        let authority = Pubkey::new_unique();
+        let nonce = Hash::new_from_array([7_u8; 32]);
+        let mut data = Vec::with_capacity(NONCE_ACCOUNT_LENGTH);
+        data.extend_from_slice(&1_u32.to_le_bytes());
+        data.extend_from_slice(&1_u32.to_le_bytes());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(nonce.as_ref());
+        data.extend_from_slice(&5000_u64.to_le_bytes());
+        let st = parse_nonce_account_data(&data)?.ok_or_else(|| eyre::eyre!("uninitialized"))?;
+        assert_eq!(st.authority, authority, "authority");
+        assert_eq!(st.nonce, nonce, "durable nonce");
+        assert_eq!(st.lamports_per_signature, 5000, "fee");
+
+        let mut uninit = 1_u32.to_le_bytes().to_vec();
+        uninit.resize(NONCE_ACCOUNT_LENGTH, 0);
+        assert!(
+            parse_nonce_account_data(&uninit)?.is_none(),
+            "uninitialized"
+        );
+        data.truncate(40);
+        assert!(parse_nonce_account_data(&data).is_err(), "short data");
+        Ok(())
+    }
+
+    #[test]
+    fn detects_durable_nonce_transactions() -> eyre::Result<()> {
+        let payer = Keypair::new();
+        let nonce = nonce_account_address(&payer.pubkey(), 0)?;
+        let to = Pubkey::new_unique();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &to, 1);
+        let ixs = vec![
+            system_instruction::advance_nonce_account(&nonce, &payer.pubkey()),
+            transfer.clone(),
+        ];
+        let msg = solana_sdk::message::Message::new(&ixs, Some(&payer.pubkey()));
+        let tx = solana_sdk::transaction::Transaction::new(&[&payer], msg, Hash::default());
+        assert_eq!(durable_nonce_account_of(&tx), Some(nonce), "nonce tx");
+        let decoded = decode_transaction_base64(&encode_transaction_base64(&tx)?)?;
+        assert_eq!(decoded, tx, "base64 round trip");
+
+        let msg = solana_sdk::message::Message::new(&[transfer], Some(&payer.pubkey()));
+        let tx = solana_sdk::transaction::Transaction::new(&[&payer], msg, Hash::default());
+        assert_eq!(durable_nonce_account_of(&tx), None, "blockhash tx");
+        Ok(())
+    }
 }
@@ -2,6 +2,8 @@
 
 use std::io::{BufRead as _, IsTerminal as _, Write as _};
 
+use crate::{amount, config::DisplayConfig};
+
 fn stderr_write(s: &str) {
     let mut stderr = std::io::stderr().lock();
     if stderr.write_all(s.as_bytes()).is_err() {
@@ -21,6 +23,24 @@ fn stderr_writeln(s: &str) {
     let _flush = stderr.flush();
 }
 
+/// Format a USD value for human-facing text per the `[display]` config (e.g. `$1,234.57`).
+pub fn format_usd(usd: f64, display: &DisplayConfig) -> String {
+    amount::format_usd_for_display(usd, display.usd_decimals, display.thousands_separator)
+}
+
+/// Format a base-unit token amount for human-facing text per the `[display]` config.
+///
+/// Falls back to the raw base-unit string if `decimals` is out of range.
+pub fn format_token_amount(base: u128, decimals: u32, display: &DisplayConfig) -> String {
+    amount::format_amount_base_for_display(
+        base,
+        decimals,
+        u32::from(display.max_token_decimals).min(decimals),
+        display.thousands_separator,
+    )
+    .unwrap_or_else(|_e| base.to_string())
+}
+
 /// Print the MCP startup banner to stderr (human-operator info only).
 ///
 /// `network` is a display-friendly string like `"mainnet"`, `"testnet"`, or `"auto"`.
@@ -38,6 +58,15 @@ pub fn print_wallet_created(name: &str) {
     ));
 }
 
+/// Print where `seashail upgrade` installed the verified release binary.
+pub fn print_upgrade_installed(path: &std::path::Path, pin: Option<&str>) {
+    let version = pin.unwrap_or("latest release");
+    stderr_writeln(&format!(
+        "Seashail: installed {version} to {} (signature verified).",
+        path.display()
+    ));
+}
+
 /// Prompt the user on stderr to confirm an upgrade, or bail if non-interactive.
 pub fn confirm_upgrade_or_bail(yes: bool) -> eyre::Result<()> {
     if yes {
@@ -49,7 +78,7 @@ pub fn confirm_upgrade_or_bail(yes: bool) -> eyre::Result<()> {
     }
 
     stderr_writeln(
-        "Seashail upgrade will run the hosted installer to reinstall the latest Seashail.",
+        "Seashail upgrade will download the latest Seashail, verify its signature, and reinstall it.",
     );
     stderr_write("Continue? [y/N] ");
     let mut line = String::new();
@@ -5,6 +5,13 @@ use std::collections::BTreeMap;
 pub const SOLANA_MAINNET_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
 pub const SOLANA_DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
 
+/// Public keyless `DefiLlama` yields dataset used by `get_defi_yield_pools`.
+pub const DEFILLAMA_YIELDS_URL: &str = "https://yields.llama.fi/pools";
+/// `DefiLlama` Pro API host; the API key is a path segment (`/<key>/yields/pools`).
+pub const DEFILLAMA_PRO_API_BASE_URL: &str = "https://pro-api.llama.fi";
+/// Public keyless Wormholescan API.
+pub const WORMHOLESCAN_API_BASE_URL: &str = "https://api.wormholescan.io/v1";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum NetworkMode {
@@ -23,6 +30,8 @@ pub struct HttpConfig {
     /// Optional Jupiter API key (x-api-key). Some tiers/hosts require this; Seashail supports keyless usage
     /// where Jupiter permits it (typically with reduced rate limits).
     pub jupiter_api_key: Option<String>,
+    /// Jupiter Trigger API base URL. Used for spot limit orders on Solana (shares `jupiter_api_key`).
+    pub jupiter_trigger_base_url: String,
     /// 1inch Swap API v6 base URL. Note: 1inch currently requires an API key.
     pub oneinch_base_url: String,
     /// Optional 1inch API key. If unset, 1inch integration is disabled and swaps should use Uniswap.
@@ -80,9 +89,19 @@ pub struct HttpConfig {
 
     /// Wormholescan API base URL.
     ///
-    /// Used to fetch signed VAAs for Wormhole transfers. Keyless by default.
+    /// Used to fetch signed VAAs for Wormhole transfers. Keyless by default; a blank value falls
+    /// back to the public endpoint.
     pub wormholescan_api_base_url: String,
 
+    /// Optional yield-pool dataset URL for `get_defi_yield_pools` (`DefiLlama` `/pools` response
+    /// shape). Use this to point at a mirror or a self-hosted cache.
+    ///
+    /// If unset, Seashail uses the `DefiLlama` Pro endpoint when `defillama_api_key` is set, and
+    /// the public keyless endpoint otherwise.
+    pub defillama_yields_url: Option<String>,
+    /// Optional `DefiLlama` Pro API key for higher rate limits. Never echoed in tool output.
+    pub defillama_api_key: Option<String>,
+
     /// Kamino API base URL (Solana `DeFi`: Kamino Lend).
     ///
     /// Used for data endpoints (market/reserve discovery, obligations) and KTX transaction endpoints.
@@ -118,6 +137,7 @@ impl Default for HttpConfig {
             // Jupiter consolidated quote+swap under /swap/v1. This base URL should end in /swap/v1.
             jupiter_base_url: "https://api.jup.ag/swap/v1".into(),
             jupiter_api_key: None,
+            jupiter_trigger_base_url: "https://api.jup.ag/trigger/v1".into(),
             oneinch_base_url: "https://api.1inch.dev/swap/v6.0".into(),
             oneinch_api_key: None,
 
@@ -140,7 +160,10 @@ impl Default for HttpConfig {
             polymarket_gamma_base_url: "https://gamma-api.polymarket.com".into(),
             polymarket_geoblock_base_url: "https://polymarket.com".into(),
 
-            wormholescan_api_base_url: "https://api.wormholescan.io/v1".into(),
+            wormholescan_api_base_url: WORMHOLESCAN_API_BASE_URL.into(),
+
+            defillama_yields_url: None,
+            defillama_api_key: None,
 
             kamino_api_base_url: "https://api.kamino.finance".into(),
             // Kamino examples/docs commonly use this as the main market.
@@ -159,6 +182,42 @@ impl Default for HttpConfig {
     }
 }
 
+impl HttpConfig {
+    /// Wormholescan base URL with trailing slashes removed, or the public default when blank.
+    pub fn wormholescan_base_url(&self) -> &str {
+        let base = self.wormholescan_api_base_url.trim().trim_end_matches('/');
+        if base.is_empty() {
+            WORMHOLESCAN_API_BASE_URL
+        } else {
+            base
+        }
+    }
+
+    /// Yield-pool dataset URL: explicit override, else `DefiLlama` Pro (if keyed), else public.
+    pub fn yield_pools_url(&self) -> String {
+        if let Some(u) = non_blank(self.defillama_yields_url.as_deref()) {
+            return u.to_owned();
+        }
+        match non_blank(self.defillama_api_key.as_deref()) {
+            Some(key) => format!("{DEFILLAMA_PRO_API_BASE_URL}/{key}/yields/pools"),
+            None => DEFILLAMA_YIELDS_URL.to_owned(),
+        }
+    }
+
+    /// [`Self::yield_pools_url`] with the `DefiLlama` API key masked, for tool output and logs.
+    pub fn yield_pools_url_redacted(&self) -> String {
+        let url = self.yield_pools_url();
+        match non_blank(self.defillama_api_key.as_deref()) {
+            Some(key) => url.replace(key, "<redacted>"),
+            None => url,
+        }
+    }
+}
+
+fn non_blank(s: Option<&str>) -> Option<&str> {
+    s.map(str::trim).filter(|s| !s.is_empty())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RpcConfig {
@@ -405,6 +464,222 @@ impl Default for RpcConfig {
     }
 }
 
+/// ERC-4337 smart-account execution settings.
+///
+/// Smart accounts are opt-in per wallet (`enable_smart_account`) and per call
+/// (`execution=smart_account`); EOA signing stays the default everywhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Erc4337Config {
+    /// Bundler JSON-RPC endpoints keyed by EVM chain name. Chains without a bundler cannot use
+    /// smart-account execution.
+    pub bundler_urls: BTreeMap<String, String>,
+    /// Optional ERC-7677 paymaster endpoints keyed by EVM chain name. Without one, the smart
+    /// account pays its own gas in the native token.
+    pub paymaster_urls: BTreeMap<String, String>,
+    /// Provider-specific paymaster context (e.g. `token = "0x..."` to pay gas in an ERC-20).
+    pub paymaster_context: BTreeMap<String, String>,
+    /// `EntryPoint` v0.7 address.
+    pub entry_point: String,
+    /// `SimpleAccountFactory` (v0.7) address used to derive and deploy smart accounts.
+    pub simple_account_factory: String,
+}
+
+impl Default for Erc4337Config {
+    fn default() -> Self {
+        Self {
+            bundler_urls: BTreeMap::new(),
+            paymaster_urls: BTreeMap::new(),
+            paymaster_context: BTreeMap::new(),
+            entry_point: crate::chains::erc4337::ENTRY_POINT_V07.into(),
+            simple_account_factory: crate::chains::erc4337::SIMPLE_ACCOUNT_FACTORY_V07.into(),
+        }
+    }
+}
+
+/// Upper bound on `hot_account.auto_approve_usd`, whatever the config says.
+pub const HOT_ACCOUNT_AUTO_APPROVE_CEILING_USD: f64 = 100.0;
+
+/// Upper bound on `hot_account.max_daily_usd`, whatever the config says.
+pub const HOT_ACCOUNT_MAX_DAILY_CEILING_USD: f64 = 100.0;
+
+/// A designated low-value "hot" `(wallet, account_index)` whose small writes skip the
+/// confirmation prompt. Config-file only: no MCP tool can set or widen it.
+///
+/// Policy still applies in full (hard blocks, allowlists, per-tx and daily caps); only the
+/// "requires confirmation" tier is bypassed, and never for forced confirmations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotAccountConfig {
+    pub wallet: String,
+    pub account_index: u32,
+    /// Writes up to this USD value execute without a prompt. Clamped to
+    /// [`HOT_ACCOUNT_AUTO_APPROVE_CEILING_USD`].
+    pub auto_approve_usd: f64,
+    /// Stop bypassing once this account's daily (UTC) spend, including this write, would exceed
+    /// this. Clamped to [`HOT_ACCOUNT_MAX_DAILY_CEILING_USD`].
+    pub max_daily_usd: f64,
+}
+
+impl Default for HotAccountConfig {
+    fn default() -> Self {
+        Self {
+            wallet: String::new(),
+            account_index: 0,
+            auto_approve_usd: 0.0,
+            max_daily_usd: 100.0,
+        }
+    }
+}
+
+impl HotAccountConfig {
+    pub fn effective_auto_approve_usd(&self) -> f64 {
+        self.auto_approve_usd
+            .clamp(0.0_f64, HOT_ACCOUNT_AUTO_APPROVE_CEILING_USD)
+    }
+
+    pub fn effective_max_daily_usd(&self) -> f64 {
+        self.max_daily_usd
+            .clamp(0.0_f64, HOT_ACCOUNT_MAX_DAILY_CEILING_USD)
+    }
+
+    pub fn is_account(&self, wallet: Option<&str>, account_index: Option<u32>) -> bool {
+        !self.wallet.trim().is_empty()
+            && wallet.map(str::trim) == Some(self.wallet.trim())
+            && account_index == Some(self.account_index)
+    }
+}
+
+/// Opt-in wait for on-chain confirmation after a write tool broadcasts.
+///
+/// Off by default: write tools return as soon as the transaction is submitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TxConfirmationConfig {
+    /// Wait for confirmation before returning and record `confirmed`/`failed` in tx history.
+    pub wait: bool,
+    /// Blocks (including the inclusion block) required on EVM chains.
+    pub evm_confirmations: u64,
+    /// Blocks (including the inclusion block) required on Bitcoin.
+    pub bitcoin_confirmations: u64,
+    /// Solana commitment level to wait for: `confirmed` or `finalized`.
+    pub solana_commitment: String,
+    /// Give up waiting after this many seconds and return the transaction as `pending`.
+    pub timeout_seconds: u64,
+}
+
+impl Default for TxConfirmationConfig {
+    fn default() -> Self {
+        Self {
+            wait: false,
+            evm_confirmations: 1,
+            bitcoin_confirmations: 1,
+            solana_commitment: "confirmed".into(),
+            timeout_seconds: 120,
+        }
+    }
+}
+
+/// Bounds on `evm_defi.deadline_seconds`, whatever the config says.
+pub const EVM_DEFI_DEADLINE_SECONDS_MIN: u64 = 30;
+pub const EVM_DEFI_DEADLINE_SECONDS_MAX: u64 = 24 * 60 * 60;
+
+/// Defaults for native EVM DeFi transactions (Uniswap swaps and adapter-built lend/stake/LP
+/// envelopes). Tools accept per-call `deadline_seconds` / `slippage_bps` overrides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvmDefiConfig {
+    /// Seconds a transaction stays executable after it is built; past the deadline the
+    /// contract reverts instead of filling at a stale price. Clamped to
+    /// [`EVM_DEFI_DEADLINE_SECONDS_MIN`]..=[`EVM_DEFI_DEADLINE_SECONDS_MAX`].
+    pub deadline_seconds: u64,
+    /// Slippage (basis points) used when a call omits `slippage_bps`. Policy
+    /// `max_slippage_bps` still applies.
+    pub slippage_bps: u32,
+}
+
+impl Default for EvmDefiConfig {
+    fn default() -> Self {
+        Self {
+            deadline_seconds: 20 * 60,
+            slippage_bps: 50,
+        }
+    }
+}
+
+impl EvmDefiConfig {
+    pub fn effective_deadline_seconds(&self) -> u64 {
+        self.deadline_seconds
+            .clamp(EVM_DEFI_DEADLINE_SECONDS_MIN, EVM_DEFI_DEADLINE_SECONDS_MAX)
+    }
+}
+
+/// Human-facing number formatting for CLI text (`doctor`, banners, reports). Tool JSON is never
+/// affected and always carries exact base-unit strings and unrounded floats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Group integer digits with `,` (e.g. `1,234,567.5`).
+    pub thousands_separator: bool,
+    /// Decimal places for USD values (capped at 8).
+    pub usd_decimals: u8,
+    /// Most fractional digits shown for token amounts; extra digits are truncated and trailing
+    /// zeros trimmed. Never exceeds the token's own decimals.
+    pub max_token_decimals: u8,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            thousands_separator: true,
+            usd_decimals: 2,
+            max_token_decimals: 6,
+        }
+    }
+}
+
+/// Self-upgrade behavior. `auto` gates the background upgrade started by `seashail mcp` /
+/// `seashail daemon`; `pinned_version` applies to both that and `seashail upgrade`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpgradeConfig {
+    /// Allow background auto-upgrades. `false` turns them off regardless of
+    /// `SEASHAIL_AUTO_UPGRADE`; `SEASHAIL_DISABLE_AUTO_UPGRADE=1` still turns them off when `true`.
+    pub auto: bool,
+    /// Install exactly this release tag (e.g. `v0.1.0`) instead of the latest build. Auto-upgrade
+    /// is a no-op once the running binary matches it. Not supported for Homebrew installs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_version: Option<String>,
+}
+
+impl Default for UpgradeConfig {
+    fn default() -> Self {
+        Self {
+            auto: true,
+            pinned_version: None,
+        }
+    }
+}
+
+impl UpgradeConfig {
+    /// The configured pin, trimmed, or `None` when unset/blank.
+    ///
+    /// Errors when the value cannot be a git tag (it is handed to the installer as a ref).
+    pub fn effective_pinned_version(&self) -> eyre::Result<Option<&str>> {
+        let Some(v) = non_blank(self.pinned_version.as_deref()) else {
+            return Ok(None);
+        };
+        let ok = !v.starts_with('-')
+            && !v.contains("..")
+            && v.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+'));
+        if !ok {
+            eyre::bail!("upgrade.pinned_version is not a valid release tag: {v:?}");
+        }
+        Ok(Some(v))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SeashailConfig {
@@ -416,6 +691,15 @@ pub struct SeashailConfig {
     pub policy_overrides_by_wallet: BTreeMap<String, Policy>,
     pub rpc: RpcConfig,
     pub http: HttpConfig,
+    pub erc4337: Erc4337Config,
+    pub tx_confirmation: TxConfirmationConfig,
+    pub evm_defi: EvmDefiConfig,
+    pub display: DisplayConfig,
+    pub upgrade: UpgradeConfig,
+
+    /// Optional low-value account whose small writes skip confirmation prompts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hot_account: Option<HotAccountConfig>,
 
     /// Network mode controls which chains are used by default (when a tool omits `chain`/`chains`)
     /// and provides agent-facing guidance. Chains can still be selected explicitly by name.
@@ -437,6 +721,28 @@ pub struct SeashailConfig {
     /// Local price cache TTL for token->USDC quote-based lookups (seconds).
     pub price_cache_ttl_seconds_quote: u64,
 
+    /// Safe mode: if set, only tools in these groups are exposed (e.g. `["network", "policy",
+    /// "read", "wallets", "spot"]`). Groups: network, policy, read, wallets, spot, defi, perps,
+    /// nft, pumpfun, predictions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_tool_groups: Option<Vec<String>>,
+
+    /// Tool groups removed entirely (hidden from `tools/list` and rejected on call).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tool_groups: Vec<String>,
+
+    /// Individual tools removed entirely (hidden from `tools/list` and rejected on call).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tools: Vec<String>,
+
+    /// How long write tools wait for the keystore write lock when another process holds it
+    /// (milliseconds, capped at 30s). 0 (default) fails fast with `keystore_busy`.
+    pub write_lock_wait_ms: u64,
+
+    /// Keep USD values in testnet tool results. By default they are reported as 0 because testnet
+    /// tokens have no real value; enable this if you deliberately price testnet assets.
+    pub price_testnet_assets: bool,
+
     /// Legacy. Prefer `network_mode`.
     #[serde(default, skip_serializing)]
     pub testnet_mode: bool,
@@ -449,11 +755,22 @@ impl Default for SeashailConfig {
             policy_overrides_by_wallet: BTreeMap::new(),
             rpc: RpcConfig::default(),
             http: HttpConfig::default(),
+            erc4337: Erc4337Config::default(),
+            tx_confirmation: TxConfirmationConfig::default(),
+            evm_defi: EvmDefiConfig::default(),
+            display: DisplayConfig::default(),
+            upgrade: UpgradeConfig::default(),
+            hot_account: None,
             network_mode: Some(NetworkMode::Mainnet),
             passphrase_salt_b64: None,
             passphrase_session_seconds: 30 * 60,
             price_cache_ttl_seconds_native: 30,
             price_cache_ttl_seconds_quote: 10,
+            enabled_tool_groups: None,
+            disabled_tool_groups: vec![],
+            disabled_tools: vec![],
+            price_testnet_assets: false,
+            write_lock_wait_ms: 0,
             testnet_mode: false,
         }
     }
@@ -477,6 +794,35 @@ impl SeashailConfig {
         (self.policy.clone(), false)
     }
 
+    /// Repoint config entries that reference wallet `old` by name (the per-wallet policy override
+    /// and the hot account) at `new`. Returns the config keys that changed.
+    pub fn rename_wallet_refs(&mut self, old: &str, new: &str) -> Vec<&'static str> {
+        let mut changed = vec![];
+        if let Some(p) = self.policy_overrides_by_wallet.remove(old) {
+            self.policy_overrides_by_wallet.insert(new.to_owned(), p);
+            changed.push("policy_overrides_by_wallet");
+        }
+        if let Some(hot) = self.hot_account.as_mut().filter(|h| h.wallet.trim() == old) {
+            new.clone_into(&mut hot.wallet);
+            changed.push("hot_account");
+        }
+        changed
+    }
+
+    /// Whether `tool` (belonging to `group`) is exposed under the safe-mode settings.
+    ///
+    /// Unlike policy gating, a disabled tool is not offered to the agent at all.
+    pub fn tool_enabled(&self, tool: &str, group: &str) -> bool {
+        if self.disabled_tools.iter().any(|t| t.trim() == tool)
+            || self.disabled_tool_groups.iter().any(|g| g.trim() == group)
+        {
+            return false;
+        }
+        self.enabled_tool_groups
+            .as_ref()
+            .map_or(true, |groups| groups.iter().any(|g| g.trim() == group))
+    }
+
     pub fn default_chains_for_mode(&self, mode: NetworkMode) -> Vec<String> {
         let mut out = vec!["solana".to_owned()];
 
@@ -543,6 +889,184 @@ mod tests {
         assert!(!testnet.contains(&"ethereum".to_owned()));
     }
 
+    #[test]
+    fn tool_enabled_honors_groups_and_individual_toggles() {
+        assert!(SeashailConfig::default().tool_enabled("open_perp_position", "perps"));
+
+        let no_perps = SeashailConfig {
+            disabled_tool_groups: vec!["perps".to_owned()],
+            disabled_tools: vec!["bid_nft".to_owned()],
+            ..Default::default()
+        };
+        assert!(!no_perps.tool_enabled("open_perp_position", "perps"));
+        assert!(!no_perps.tool_enabled("bid_nft", "nft"));
+        assert!(no_perps.tool_enabled("buy_nft", "nft"));
+
+        let read_only = SeashailConfig {
+            enabled_tool_groups: Some(vec!["read".to_owned(), "wallets".to_owned()]),
+            disabled_tools: vec!["get_portfolio".to_owned()],
+            ..Default::default()
+        };
+        assert!(read_only.tool_enabled("get_balance", "read"));
+        assert!(!read_only.tool_enabled("get_portfolio", "read"));
+        assert!(!read_only.tool_enabled("pumpfun_buy", "pumpfun"));
+    }
+
+    #[test]
+    fn tx_confirmation_is_opt_in_and_partially_configurable() -> eyre::Result<()> {
+        assert!(
+            !SeashailConfig::default().tx_confirmation.wait,
+            "default is fire-and-return"
+        );
+        let cfg: SeashailConfig =
+            toml::from_str("[tx_confirmation]\nwait = true\nevm_confirmations = 3\n")?;
+        assert!(cfg.tx_confirmation.wait, "wait enabled");
+        assert_eq!(cfg.tx_confirmation.evm_confirmations, 3, "evm override");
+        assert_eq!(
+            cfg.tx_confirmation.solana_commitment, "confirmed",
+            "unset fields keep defaults"
+        );
+        assert_eq!(cfg.tx_confirmation.timeout_seconds, 120, "default timeout");
+        Ok(())
+    }
+
+    #[test]
+    fn hot_account_limit_is_capped_and_scoped_to_one_account() -> eyre::Result<()> {
+        assert!(SeashailConfig::default().hot_account.is_none(), "opt-in");
+        let cfg: SeashailConfig = toml::from_str(
+            "[hot_account]\nwallet = \"trader\"\naccount_index = 2\nauto_approve_usd = 5000.0\nmax_daily_usd = 1e9\n",
+        )?;
+        let hot = cfg
+            .hot_account
+            .ok_or_else(|| eyre::eyre!("hot_account parsed"))?;
+        assert!(
+            !crate::financial_math::usd_cap_mismatch(
+                hot.effective_auto_approve_usd(),
+                HOT_ACCOUNT_AUTO_APPROVE_CEILING_USD
+            ),
+            "limit clamped to the ceiling"
+        );
+        assert!(
+            !crate::financial_math::usd_cap_mismatch(
+                hot.effective_max_daily_usd(),
+                HOT_ACCOUNT_MAX_DAILY_CEILING_USD
+            ),
+            "daily budget clamped to the ceiling"
+        );
+        assert!(
+            hot.is_account(Some("trader"), Some(2)),
+            "designated account"
+        );
+        assert!(
+            !hot.is_account(Some("trader"), Some(0)),
+            "other account index"
+        );
+        assert!(!hot.is_account(Some("default"), Some(2)), "other wallet");
+        assert!(
+            !hot.is_account(Some("trader"), None),
+            "unknown account index"
+        );
+        assert!(
+            !HotAccountConfig::default().is_account(Some(""), Some(0)),
+            "empty wallet never matches"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn evm_defi_deadline_defaults_and_is_clamped() -> eyre::Result<()> {
+        let d = EvmDefiConfig::default();
+        assert_eq!(d.effective_deadline_seconds(), 1200, "20 minute default");
+        assert_eq!(d.slippage_bps, 50, "default slippage");
+        let cfg: SeashailConfig = toml::from_str("[evm_defi]\ndeadline_seconds = 0\n")?;
+        assert_eq!(
+            cfg.evm_defi.effective_deadline_seconds(),
+            EVM_DEFI_DEADLINE_SECONDS_MIN,
+            "zero never disables the deadline"
+        );
+        assert_eq!(cfg.evm_defi.slippage_bps, 50, "unset fields keep defaults");
+        let cfg: SeashailConfig = toml::from_str("[evm_defi]\ndeadline_seconds = 604800\n")?;
+        assert_eq!(
+            cfg.evm_defi.effective_deadline_seconds(),
+            EVM_DEFI_DEADLINE_SECONDS_MAX,
+            "capped at a day"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_provider_endpoints_fall_back_to_public_defaults() -> eyre::Result<()> {
+        let h = HttpConfig::default();
+        assert_eq!(h.yield_pools_url(), DEFILLAMA_YIELDS_URL);
+        assert_eq!(h.wormholescan_base_url(), WORMHOLESCAN_API_BASE_URL);
+
+        let cfg: SeashailConfig = toml::from_str(
+            "[http]\ndefillama_api_key = \"k123\"\nwormholescan_api_base_url = \" \"\n",
+        )?;
+        assert_eq!(
+            cfg.http.yield_pools_url(),
+            "https://pro-api.llama.fi/k123/yields/pools",
+            "a key alone switches to the Pro endpoint"
+        );
+        assert!(!cfg.http.yield_pools_url_redacted().contains("k123"));
+        assert_eq!(
+            cfg.http.wormholescan_base_url(),
+            WORMHOLESCAN_API_BASE_URL,
+            "blank base falls back"
+        );
+
+        let cfg: SeashailConfig = toml::from_str(
+            "[http]\ndefillama_yields_url = \"https://mirror.example/pools\"\ndefillama_api_key = \"k123\"\n",
+        )?;
+        assert_eq!(cfg.http.yield_pools_url(), "https://mirror.example/pools");
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_defaults_to_auto_and_validates_the_pin() -> eyre::Result<()> {
+        let cfg = SeashailConfig::default();
+        assert!(cfg.upgrade.auto);
+        assert_eq!(cfg.upgrade.effective_pinned_version()?, None);
+
+        let cfg: SeashailConfig =
+            toml::from_str("[upgrade]\nauto = false\npinned_version = \" v0.1.0 \"\n")?;
+        assert!(!cfg.upgrade.auto);
+        assert_eq!(cfg.upgrade.effective_pinned_version()?, Some("v0.1.0"));
+
+        for bad in ["--upload-pack=x", "v1 .0", "v1/../main"] {
+            let u = UpgradeConfig {
+                auto: true,
+                pinned_version: Some(bad.into()),
+            };
+            assert!(u.effective_pinned_version().is_err(), "{bad}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rename_wallet_refs_moves_override_and_hot_account() {
+        let mut cfg = SeashailConfig {
+            hot_account: Some(HotAccountConfig {
+                wallet: "main".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        cfg.policy_overrides_by_wallet
+            .insert("main".into(), Policy::default());
+        assert_eq!(
+            cfg.rename_wallet_refs("main", "savings"),
+            vec!["policy_overrides_by_wallet", "hot_account"]
+        );
+        assert!(cfg.policy_overrides_by_wallet.contains_key("savings"));
+        assert!(!cfg.policy_overrides_by_wallet.contains_key("main"));
+        assert_eq!(
+            cfg.hot_account.as_ref().map(|h| h.wallet.as_str()),
+            Some("savings")
+        );
+        assert!(cfg.rename_wallet_refs("other", "x").is_empty());
+    }
+
     #[test]
     fn legacy_testnet_mode_is_respected_when_network_mode_unset() {
         let cfg = SeashailConfig {
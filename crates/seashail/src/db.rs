@@ -34,6 +34,15 @@ pub struct PortfolioSnapshotTotalRow {
     pub total_usd: f64,
 }
 
+#[derive(Debug, Clone)]
+pub struct PortfolioSnapshotItemRow {
+    pub wallet: String,
+    pub account_index: i64,
+    pub chain: String,
+    pub usd_value: f64,
+    pub payload_json: String,
+}
+
 pub struct HealthSnapshotInput<'a> {
     pub surface: &'a str,
     pub chain: &'a str,
@@ -457,6 +466,66 @@ impl Db {
         }))
     }
 
+    pub async fn portfolio_snapshot_total_by_id(
+        &self,
+        scope_json: &str,
+        snapshot_id: i64,
+    ) -> eyre::Result<Option<PortfolioSnapshotTotalRow>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT s.snapshot_id, s.fetched_at_ms, s.day, \
+                        COALESCE(SUM(i.usd_value), 0) AS total_usd \
+                   FROM seashail_portfolio_snapshots s \
+                   LEFT JOIN seashail_portfolio_snapshot_items i \
+                     ON i.snapshot_id = s.snapshot_id \
+                  WHERE s.scope_json = ? AND s.snapshot_id = ? \
+                  GROUP BY s.snapshot_id, s.fetched_at_ms, s.day",
+                (scope_json, snapshot_id),
+            )
+            .await
+            .context("query portfolio snapshot total by id")?;
+
+        let Some(row) = rows.next().await.context("next row")? else {
+            return Ok(None);
+        };
+        Ok(Some(PortfolioSnapshotTotalRow {
+            snapshot_id: row.get(0).context("row.snapshot_id")?,
+            fetched_at_ms: row.get(1).context("row.fetched_at_ms")?,
+            day: row.get(2).context("row.day")?,
+            total_usd: row.get(3).context("row.total_usd")?,
+        }))
+    }
+
+    pub async fn list_portfolio_snapshot_items(
+        &self,
+        snapshot_id: i64,
+    ) -> eyre::Result<Vec<PortfolioSnapshotItemRow>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT wallet, account_index, chain, usd_value, payload_json \
+                   FROM seashail_portfolio_snapshot_items \
+                  WHERE snapshot_id = ? \
+                  ORDER BY wallet, account_index, chain",
+                (snapshot_id,),
+            )
+            .await
+            .context("query seashail_portfolio_snapshot_items")?;
+
+        let mut out: Vec<PortfolioSnapshotItemRow> = vec![];
+        while let Some(row) = rows.next().await.context("next row")? {
+            out.push(PortfolioSnapshotItemRow {
+                wallet: row.get(0).context("row.wallet")?,
+                account_index: row.get(1).context("row.account_index")?,
+                chain: row.get(2).context("row.chain")?,
+                usd_value: row.get(3).context("row.usd_value")?,
+                payload_json: row.get(4).context("row.payload_json")?,
+            });
+        }
+        Ok(out)
+    }
+
     pub fn now_ms() -> eyre::Result<i64> {
         let d = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -639,6 +708,22 @@ mod tests {
         let expected_total_usd = 25.0_f64;
         assert!((before.total_usd - expected_total_usd).abs() < 1e-9_f64);
 
+        let by_id = db
+            .portfolio_snapshot_total_by_id(scope, s2)
+            .await?
+            .ok_or_else(|| eyre::eyre!("missing by_id"))?;
+        assert_eq!(by_id.fetched_at_ms, t2);
+        assert!(db
+            .portfolio_snapshot_total_by_id(r#"{"wallets":[]}"#, s2)
+            .await?
+            .is_none());
+
+        let items = db.list_portfolio_snapshot_items(s0).await?;
+        assert_eq!(items.len(), 1);
+        let item = items.first().context("expected one item")?;
+        assert_eq!(item.chain, "solana");
+        assert_eq!(item.payload_json, "{}");
+
         Ok(())
     }
 }
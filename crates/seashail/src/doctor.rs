@@ -1,7 +1,21 @@
-use crate::{config::SeashailConfig, paths::SeashailPaths, wallet::WalletStore};
+use crate::{
+    chains::{bitcoin::BitcoinChain, evm::EvmChain, solana::SolanaChain},
+    cli_output::format_usd,
+    config::{DisplayConfig, NetworkMode, SeashailConfig},
+    paths::SeashailPaths,
+    perps::hyperliquid::HyperliquidClient,
+    policy::Policy,
+    wallet::WalletStore,
+};
 use eyre::Context as _;
 use serde_json::json;
-use std::{fs, path::Path, path::PathBuf};
+use std::{
+    fs,
+    path::Path,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 #[cfg(unix)]
 use tokio::net::UnixStream;
@@ -98,6 +112,376 @@ async fn probe_daemon_running(paths: &SeashailPaths) -> (bool, Option<String>) {
     }
 }
 
+/// HTTPS endpoints whose `Date` header serves as the time reference (first to answer wins).
+const CLOCK_REFERENCE_URLS: &[&str] = &["https://www.cloudflare.com", "https://www.google.com"];
+/// Skew beyond this can move writes across the UTC day boundary used for daily spend limits.
+const CLOCK_SKEW_WARN_SECONDS: u64 = 30;
+
+/// Local clock minus reference clock, taking the local midpoint of the request round-trip.
+fn clock_skew_seconds(
+    sent: chrono::DateTime<chrono::Utc>,
+    received: chrono::DateTime<chrono::Utc>,
+    reference: chrono::DateTime<chrono::Utc>,
+) -> i64 {
+    let local = sent + (received - sent) / 2_i32;
+    (local - reference).num_seconds()
+}
+
+async fn probe_clock_skew() -> ClockReport {
+    let mut last_err = None;
+    let client = match reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(3))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            return ClockReport::skipped(format!("build http client: {e}"));
+        }
+    };
+    for url in CLOCK_REFERENCE_URLS {
+        let sent = chrono::Utc::now();
+        let resp = match client.head(*url).send().await {
+            Ok(r) => r,
+            Err(e) => {
+                last_err = Some(format!("{e}"));
+                continue;
+            }
+        };
+        let received = chrono::Utc::now();
+        let Some(date) = resp
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
+        else {
+            last_err = Some("reference response had no usable Date header".to_owned());
+            continue;
+        };
+        let skew = clock_skew_seconds(sent, received, date.with_timezone(&chrono::Utc));
+        return ClockReport {
+            reference: resp.url().host_str().map(ToOwned::to_owned),
+            skew_seconds: Some(skew),
+            ok: Some(skew.unsigned_abs() <= CLOCK_SKEW_WARN_SECONDS),
+            note: None,
+        };
+    }
+    ClockReport::skipped(last_err.unwrap_or_else(|| "no reference reachable".to_owned()))
+}
+
+/// Per-probe deadline for the connectivity matrix. Probes run concurrently, so the whole matrix
+/// takes about this long in the worst case.
+const PROBE_TIMEOUT_MS: u64 = 5_000;
+
+/// Which reused client performs a connectivity probe. Every probe is a read.
+enum ProbeTarget {
+    Evm {
+        chain: String,
+        chain_id: u64,
+    },
+    Solana,
+    Bitcoin,
+    BinancePrice,
+    Hyperliquid,
+    /// Plain GET; any non-5xx response counts as reachable.
+    Http,
+}
+
+struct ProbeSpec {
+    /// `rpc` or `api`.
+    kind: &'static str,
+    service: String,
+    /// `primary`/`fallback` for RPCs, the feature area for APIs.
+    role: &'static str,
+    /// Full URL. Used for the request only; never reported.
+    url: String,
+    target: ProbeTarget,
+}
+
+struct ProbeResult {
+    kind: &'static str,
+    service: String,
+    role: &'static str,
+    /// Scheme and host only (see `redact_endpoint`).
+    endpoint: String,
+    ok: bool,
+    latency_ms: u64,
+    detail: String,
+}
+
+/// `scheme://host[:port]`, plus `/...` when the URL had a path or query. RPC and API URLs often
+/// carry keys there (`/v2/<key>`, `?api-key=`), so they are never echoed.
+fn redact_endpoint(url: &str) -> String {
+    let Ok(u) = reqwest::Url::parse(url.trim()) else {
+        return "<invalid url>".to_owned();
+    };
+    let host = u.host_str().unwrap_or("?");
+    let port = u.port().map(|p| format!(":{p}")).unwrap_or_default();
+    let more = if u.path().trim_matches('/').is_empty() && u.query().is_none() {
+        ""
+    } else {
+        "/..."
+    };
+    format!("{}://{host}{port}{more}", u.scheme())
+}
+
+/// Strip `url` (and any long path segment, query value or password from it) out of a client
+/// error, since transport errors usually quote the request URL.
+fn scrub_error(msg: &str, url: &str) -> String {
+    let url = url.trim();
+    let mut s = msg.to_owned();
+    let mut whole = vec![url.to_owned(), url.trim_end_matches('/').to_owned()];
+    let mut parts = vec![];
+    if let Ok(u) = reqwest::Url::parse(url) {
+        whole.push(u.as_str().to_owned());
+        parts.extend(u.password().map(str::to_owned));
+        parts.extend(u.path_segments().into_iter().flatten().map(str::to_owned));
+        parts.extend(u.query_pairs().map(|(_k, v)| v.into_owned()));
+    }
+    whole.sort_by_key(|w| std::cmp::Reverse(w.len()));
+    let redacted = redact_endpoint(url);
+    for w in whole.iter().filter(|w| !w.is_empty()) {
+        s = s.replace(w.as_str(), &redacted);
+    }
+    for p in parts.iter().filter(|p| p.len() >= 12) {
+        s = s.replace(p.as_str(), "***");
+    }
+    s.chars().take(240).collect()
+}
+
+/// Primary + fallbacks for one chain, skipping blanks and duplicates (same order the clients try).
+fn push_rpc_specs(
+    specs: &mut Vec<ProbeSpec>,
+    service: &str,
+    primary: &str,
+    fallbacks: &[String],
+    target: impl Fn() -> ProbeTarget,
+) {
+    let mut seen: Vec<&str> = vec![];
+    let urls = std::iter::once(primary).chain(fallbacks.iter().map(String::as_str));
+    for (i, url) in urls.enumerate() {
+        let url = url.trim();
+        if url.is_empty() || seen.contains(&url) {
+            continue;
+        }
+        seen.push(url);
+        specs.push(ProbeSpec {
+            kind: "rpc",
+            service: service.to_owned(),
+            role: if i == 0 { "primary" } else { "fallback" },
+            url: url.to_owned(),
+            target: target(),
+        });
+    }
+}
+
+fn collect_probe_specs(cfg: &SeashailConfig) -> Vec<ProbeSpec> {
+    let mut specs = vec![];
+    let mode = cfg.effective_network_mode();
+    let h = &cfg.http;
+
+    for (chain, url) in &cfg.rpc.evm_rpc_urls {
+        let chain_id = cfg.rpc.evm_chain_ids.get(chain).copied().unwrap_or(0);
+        let mut evm = EvmChain::for_name(chain, chain_id, url, h);
+        if let Some(fb) = cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+            evm.fallback_rpc_urls.clone_from(fb);
+        }
+        push_rpc_specs(&mut specs, chain, url, &evm.fallback_rpc_urls, || {
+            ProbeTarget::Evm {
+                chain: chain.clone(),
+                chain_id,
+            }
+        });
+    }
+
+    let (solana_fallbacks, bitcoin_url, hyperliquid_url) = match mode {
+        NetworkMode::Mainnet => (
+            &cfg.rpc.solana_fallback_rpc_urls_mainnet,
+            &h.bitcoin_api_base_url_mainnet,
+            &h.hyperliquid_base_url_mainnet,
+        ),
+        NetworkMode::Testnet => (
+            &cfg.rpc.solana_fallback_rpc_urls_devnet,
+            &h.bitcoin_api_base_url_testnet,
+            &h.hyperliquid_base_url_testnet,
+        ),
+    };
+    push_rpc_specs(
+        &mut specs,
+        "solana",
+        &cfg.rpc.solana_rpc_url,
+        solana_fallbacks,
+        || ProbeTarget::Solana,
+    );
+    push_rpc_specs(&mut specs, "bitcoin", bitcoin_url, &[], || {
+        ProbeTarget::Bitcoin
+    });
+
+    let mut apis: Vec<(&'static str, &str, String, ProbeTarget)> = vec![
+        (
+            "price",
+            "binance",
+            h.binance_base_url.clone(),
+            ProbeTarget::BinancePrice,
+        ),
+        (
+            "swap",
+            "jupiter",
+            h.jupiter_base_url.clone(),
+            ProbeTarget::Http,
+        ),
+        (
+            "perps",
+            "hyperliquid",
+            hyperliquid_url.clone(),
+            ProbeTarget::Hyperliquid,
+        ),
+        (
+            "prediction",
+            "polymarket_clob",
+            h.polymarket_clob_base_url.clone(),
+            ProbeTarget::Http,
+        ),
+        (
+            "prediction",
+            "polymarket_gamma",
+            h.polymarket_gamma_base_url.clone(),
+            ProbeTarget::Http,
+        ),
+        (
+            "bridge_status",
+            "wormholescan",
+            h.wormholescan_base_url().to_owned(),
+            ProbeTarget::Http,
+        ),
+        (
+            "yields",
+            "defillama",
+            h.yield_pools_url(),
+            ProbeTarget::Http,
+        ),
+    ];
+    if h.oneinch_api_key
+        .as_ref()
+        .is_some_and(|k| !k.trim().is_empty())
+    {
+        apis.push((
+            "swap",
+            "1inch",
+            h.oneinch_base_url.clone(),
+            ProbeTarget::Http,
+        ));
+    }
+    for (role, service, url, target) in apis {
+        if url.trim().is_empty() {
+            continue;
+        }
+        specs.push(ProbeSpec {
+            kind: "api",
+            service: service.to_owned(),
+            role,
+            url: url.trim().to_owned(),
+            target,
+        });
+    }
+    specs
+}
+
+async fn http_reachable(url: &str) -> eyre::Result<String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(PROBE_TIMEOUT_MS))
+        .build()
+        .context("build http client")?;
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)
+        .context("request")?;
+    let status = resp.status();
+    if status.is_server_error() {
+        eyre::bail!("http {}", status.as_u16());
+    }
+    Ok(format!("http {}", status.as_u16()))
+}
+
+async fn probe_once(spec: &ProbeSpec, cfg: &SeashailConfig) -> eyre::Result<String> {
+    match &spec.target {
+        ProbeTarget::Evm { chain, chain_id } => {
+            let mut evm = EvmChain::for_name(chain, *chain_id, &spec.url, &cfg.http);
+            evm.fallback_rpc_urls.clear();
+            Ok(format!("block {}", evm.get_block_number().await?))
+        }
+        ProbeTarget::Solana => {
+            let sol = SolanaChain::new_with_fallbacks(
+                &spec.url,
+                &[],
+                &cfg.http.jupiter_base_url,
+                None,
+                None,
+                None,
+            );
+            Ok(format!("slot {}", sol.get_slot().await?))
+        }
+        ProbeTarget::Bitcoin => Ok(format!(
+            "height {}",
+            BitcoinChain::new(&spec.url)?.tip_height().await?
+        )),
+        ProbeTarget::BinancePrice => {
+            let px = crate::price::binance_price_usd(cfg, "BTC").await?;
+            Ok(format!("BTC {}", format_usd(px, &cfg.display)))
+        }
+        ProbeTarget::Hyperliquid => {
+            HyperliquidClient::new(&spec.url)?
+                .info(json!({ "type": "meta" }))
+                .await?;
+            Ok("meta ok".to_owned())
+        }
+        ProbeTarget::Http => http_reachable(&spec.url).await,
+    }
+}
+
+async fn run_probe(spec: ProbeSpec, cfg: &SeashailConfig) -> ProbeResult {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(
+        Duration::from_millis(PROBE_TIMEOUT_MS),
+        probe_once(&spec, cfg),
+    )
+    .await;
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let (ok, detail) = match outcome {
+        Ok(Ok(detail)) => (true, detail),
+        Ok(Err(e)) => (false, scrub_error(&format!("{e:#}"), &spec.url)),
+        Err(_elapsed) => (false, format!("timed out after {PROBE_TIMEOUT_MS}ms")),
+    };
+    ProbeResult {
+        kind: spec.kind,
+        endpoint: redact_endpoint(&spec.url),
+        service: spec.service,
+        role: spec.role,
+        ok,
+        latency_ms,
+        detail,
+    }
+}
+
+/// Probe every configured RPC (primary + fallbacks per chain) and external API concurrently.
+async fn probe_connectivity(cfg: &SeashailConfig) -> Vec<ProbeResult> {
+    let cfg = Arc::new(cfg.clone());
+    let mut set = tokio::task::JoinSet::new();
+    for (i, spec) in collect_probe_specs(&cfg).into_iter().enumerate() {
+        let cfg = Arc::clone(&cfg);
+        set.spawn(async move { (i, run_probe(spec, &cfg).await) });
+    }
+    let mut results = vec![];
+    while let Some(joined) = set.join_next().await {
+        if let Ok(r) = joined {
+            results.push(r);
+        }
+    }
+    results.sort_by_key(|(i, _r)| *i);
+    results.into_iter().map(|(_i, r)| r).collect()
+}
+
 fn try_parse_config(path: &Path) -> eyre::Result<SeashailConfig> {
     let s = fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
     let cfg: SeashailConfig = toml::from_str(&s).context("parse config.toml")?;
@@ -117,10 +501,15 @@ struct ConfigReport {
     error: Option<String>,
     network_mode_configured: Option<String>,
     network_mode_effective: Option<String>,
+    /// Host-only (see `redact_endpoint`).
     solana_rpc_url: Option<String>,
     evm_chain_count: usize,
     scam_blocklist_configured: Option<bool>,
     scam_blocklist_pubkey_pinned: Option<bool>,
+    /// Global policy in effect (built-in default when config.toml is missing).
+    policy: Option<Policy>,
+    policy_override_count: usize,
+    display: DisplayConfig,
 }
 
 struct WalletsReport {
@@ -138,12 +527,40 @@ struct DaemonReport {
     note: Option<String>,
 }
 
+/// Local clock vs an HTTP `Date` reference. Fields are `None` when the check was skipped.
+struct ClockReport {
+    reference: Option<String>,
+    /// Local minus reference; positive means the local clock is ahead.
+    skew_seconds: Option<i64>,
+    ok: Option<bool>,
+    note: Option<String>,
+}
+
+impl ClockReport {
+    fn skipped(reason: String) -> Self {
+        Self {
+            reference: None,
+            skew_seconds: None,
+            ok: None,
+            note: Some(format!("skipped (offline?): {reason}")),
+        }
+    }
+}
+
+/// Connectivity matrix. `note` explains an empty `probes` (e.g. config.toml failed to parse).
+struct ConnectivityReport {
+    probes: Vec<ProbeResult>,
+    note: Option<String>,
+}
+
 struct DoctorReport {
     version: &'static str,
     paths: PathsReport,
     config: ConfigReport,
     wallets: WalletsReport,
     daemon: DaemonReport,
+    clock: ClockReport,
+    connectivity: ConnectivityReport,
     env: serde_json::Value,
 }
 
@@ -168,6 +585,25 @@ async fn collect(paths: &SeashailPaths) -> eyre::Result<DoctorReport> {
     let daemon_lock_path = daemon_lock_path(paths);
     let daemon_lock_exists = daemon_lock_path.exists();
     let (daemon_reachable, daemon_note) = probe_daemon_running(paths).await;
+    // A missing config.toml means built-in defaults are what tools would use, so probe those.
+    let probe_cfg = if config_exists {
+        cfg.clone()
+    } else {
+        Some(SeashailConfig::default())
+    };
+    let (clock, probes) = tokio::join!(probe_clock_skew(), async {
+        match &probe_cfg {
+            Some(c) => Some(probe_connectivity(c).await),
+            None => None,
+        }
+    });
+    let connectivity = match probes {
+        Some(probes) => ConnectivityReport { probes, note: None },
+        None => ConnectivityReport {
+            probes: vec![],
+            note: Some("skipped: config.toml failed to parse".to_owned()),
+        },
+    };
 
     let network_mode_effective = cfg
         .as_ref()
@@ -178,7 +614,7 @@ async fn collect(paths: &SeashailPaths) -> eyre::Result<DoctorReport> {
         .and_then(|c| c.network_mode)
         .map(|m| format!("{m:?}").to_lowercase());
 
-    let solana_rpc_url = cfg.as_ref().map(|c| c.rpc.solana_rpc_url.clone());
+    let solana_rpc_url = cfg.as_ref().map(|c| redact_endpoint(&c.rpc.solana_rpc_url));
     let evm_chain_count = cfg.as_ref().map_or(0, |c| c.rpc.evm_rpc_urls.len());
     let scam_blocklist_configured = cfg.as_ref().map(|c| {
         c.http
@@ -193,6 +629,16 @@ async fn collect(paths: &SeashailPaths) -> eyre::Result<DoctorReport> {
             .is_some_and(|k| !k.trim().is_empty())
     });
 
+    let policy = if config_exists {
+        cfg.as_ref().map(|c| c.policy.clone())
+    } else {
+        Some(Policy::default())
+    };
+    let policy_override_count = cfg
+        .as_ref()
+        .map_or(0, |c| c.policy_overrides_by_wallet.len());
+    let display = cfg.as_ref().map(|c| c.display.clone()).unwrap_or_default();
+
     let env = json!({
       "SEASHAIL_CONFIG_DIR": env_opt("SEASHAIL_CONFIG_DIR"),
       "SEASHAIL_DATA_DIR": env_opt("SEASHAIL_DATA_DIR"),
@@ -220,6 +666,9 @@ async fn collect(paths: &SeashailPaths) -> eyre::Result<DoctorReport> {
             evm_chain_count,
             scam_blocklist_configured,
             scam_blocklist_pubkey_pinned,
+            policy,
+            policy_override_count,
+            display,
         },
         wallets: WalletsReport {
             index_path: wallet_index_path,
@@ -234,6 +683,8 @@ async fn collect(paths: &SeashailPaths) -> eyre::Result<DoctorReport> {
             reachable: daemon_reachable,
             note: daemon_note,
         },
+        clock,
+        connectivity,
         env,
     })
 }
@@ -279,11 +730,35 @@ fn print_json(out: &mut impl std::io::Write, r: &DoctorReport) -> eyre::Result<(
         "reachable": r.daemon.reachable,
         "note": r.daemon.note,
       },
+      "clock": {
+        "reference": r.clock.reference,
+        "skew_seconds": r.clock.skew_seconds,
+        "warn_threshold_seconds": CLOCK_SKEW_WARN_SECONDS,
+        "ok": r.clock.ok,
+        "note": r.clock.note,
+      },
+      "connectivity": {
+        "timeout_ms": PROBE_TIMEOUT_MS,
+        "passed": r.connectivity.probes.iter().filter(|p| p.ok).count(),
+        "failed": r.connectivity.probes.iter().filter(|p| !p.ok).count(),
+        "note": r.connectivity.note,
+        "probes": r.connectivity.probes.iter().map(|p| json!({
+          "kind": p.kind,
+          "service": p.service,
+          "role": p.role,
+          "endpoint": p.endpoint,
+          "ok": p.ok,
+          "latency_ms": p.latency_ms,
+          "detail": p.detail,
+        })).collect::<Vec<_>>(),
+      },
       "env": r.env,
       "hints": [
         "If your agent can't connect, install an integration (OpenClaw/Claude/Codex) that runs: seashail mcp",
         "If wallets.count == 0, connect via MCP and call list_wallets (or any wallet tool). Seashail will auto-create a machine-local default wallet. For portability/recovery, call export_shares/rotate_shares.",
         "Scam blocklist is opt-in. If you want it, configure http.scam_blocklist_url (and pin http.scam_blocklist_pubkey_b64).",
+        "If clock.ok == false, sync the system clock (NTP). Daily spend limits are keyed by the local UTC day and skew can also break TLS to RPC endpoints.",
+        "If a connectivity probe fails, check that endpoint in config.toml (rpc.* / http.*) and your network or firewall. A failing primary is survivable while a fallback for the same service passes.",
       ]
     }))
     .context("serialize doctor json")?;
@@ -345,6 +820,29 @@ fn print_human(out: &mut impl std::io::Write, r: &DoctorReport) -> eyre::Result<
     }
     writeln!(out).context("write newline")?;
 
+    writeln!(out, "Policy limits:").context("write policy header")?;
+    if let Some(p) = &r.config.policy {
+        let d = &r.config.display;
+        for (name, v) in [
+            ("auto_approve_usd", p.auto_approve_usd),
+            ("confirm_up_to_usd", p.confirm_up_to_usd),
+            ("hard_block_over_usd", p.hard_block_over_usd),
+            ("max_usd_per_tx", p.max_usd_per_tx),
+            ("max_usd_per_day", p.max_usd_per_day),
+        ] {
+            writeln!(out, "  {name}: {}", format_usd(v, d)).context("write policy")?;
+        }
+        writeln!(
+            out,
+            "  wallet_overrides: {}",
+            r.config.policy_override_count
+        )
+        .context("write policy")?;
+    } else {
+        writeln!(out, "  status: unknown (config parse failed)").context("write policy")?;
+    }
+    writeln!(out).context("write newline")?;
+
     writeln!(out, "Wallets:").context("write wallets header")?;
     writeln!(out, "  index.json: {}", r.wallets.index_path.display()).context("write wallets")?;
     writeln!(out, "  index_exists: {}", r.wallets.index_exists).context("write wallets")?;
@@ -362,6 +860,68 @@ fn print_human(out: &mut impl std::io::Write, r: &DoctorReport) -> eyre::Result<
     }
     writeln!(out).context("write newline")?;
 
+    writeln!(out, "Clock:").context("write clock header")?;
+    match (r.clock.skew_seconds, r.clock.ok) {
+        (Some(skew), Some(ok)) => {
+            writeln!(
+                out,
+                "  reference: {}",
+                r.clock.reference.as_deref().unwrap_or("unknown")
+            )
+            .context("write clock")?;
+            writeln!(out, "  skew_seconds: {skew:+}").context("write clock")?;
+            if ok {
+                writeln!(out, "  status: ok").context("write clock")?;
+            } else {
+                writeln!(
+                    out,
+                    "  status: WARNING local clock is off by more than {CLOCK_SKEW_WARN_SECONDS}s; sync it (NTP). Daily spend limits use the local UTC day and TLS may fail."
+                )
+                .context("write clock")?;
+            }
+        }
+        _ => {
+            writeln!(
+                out,
+                "  status: {}",
+                r.clock.note.as_deref().unwrap_or("skipped")
+            )
+            .context("write clock")?;
+        }
+    }
+    writeln!(out).context("write newline")?;
+
+    writeln!(
+        out,
+        "Connectivity (timeout {PROBE_TIMEOUT_MS}ms per probe; endpoints shown host-only):"
+    )
+    .context("write connectivity header")?;
+    if let Some(note) = &r.connectivity.note {
+        writeln!(out, "  status: {note}").context("write connectivity")?;
+    }
+    for p in &r.connectivity.probes {
+        writeln!(
+            out,
+            "  {:<4} {:<3} {:<18} {:<13} {:<42} {:>6}ms  {}",
+            if p.ok { "ok" } else { "FAIL" },
+            p.kind,
+            p.service,
+            p.role,
+            p.endpoint,
+            p.latency_ms,
+            p.detail
+        )
+        .context("write connectivity")?;
+    }
+    let failed = r.connectivity.probes.iter().filter(|p| !p.ok).count();
+    writeln!(
+        out,
+        "  summary: {} ok, {failed} failed",
+        r.connectivity.probes.len().saturating_sub(failed)
+    )
+    .context("write connectivity")?;
+    writeln!(out).context("write newline")?;
+
     writeln!(out, "Env (redacted):").context("write env header")?;
     writeln!(
         out,
@@ -40,6 +40,12 @@ pub enum SeashailError {
 
     #[error("keystore busy")]
     KeystoreBusy,
+
+    #[error("watch-only wallet cannot sign: {0}")]
+    WatchOnlyWallet(String),
+
+    #[error("wallet name already in use: {0}")]
+    WalletNameTaken(String),
     // Add more structured errors as we expand the policy engine + adapters.
 }
 
@@ -62,6 +68,16 @@ impl From<SeashailError> for ToolError {
             SeashailError::KeystoreBusy => {
                 Self::new("keystore_busy", "keystore busy; retry the operation")
             }
+            SeashailError::WatchOnlyWallet(name) => Self::new(
+                "watch_only_wallet",
+                format!("wallet '{name}' is watch-only and cannot sign transactions or messages"),
+            ),
+            SeashailError::WalletNameTaken(name) => Self::new(
+                "wallet_name_taken",
+                format!(
+                    "wallet name '{name}' is already in use (names are compared case-insensitively and include names of renamed wallets)"
+                ),
+            ),
         }
     }
 }
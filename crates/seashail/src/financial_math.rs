@@ -145,3 +145,88 @@ pub fn float_to_wire(x: f64) -> eyre::Result<String> {
 pub fn clamp_fee_rate(fee: f64) -> u64 {
     fee.clamp(1.0_f64, 5000.0_f64) as u64
 }
+
+/// Scale a base-unit balance by `part_usd / whole_usd`, rounding down.
+///
+/// The result never exceeds `base`. Returns 0 for non-finite or non-positive inputs.
+pub fn scale_base_by_usd(base: u128, part_usd: f64, whole_usd: f64) -> u128 {
+    const PPB: u128 = 1_000_000_000;
+    if !part_usd.is_finite()
+        || !whole_usd.is_finite()
+        || part_usd <= 0.0_f64
+        || whole_usd <= 0.0_f64
+    {
+        return 0;
+    }
+    let ratio = (part_usd / whole_usd).min(1.0_f64);
+    let ppb = ((ratio * 1e9_f64).floor() as u128).min(PPB);
+    // Split to avoid overflowing on large 18-decimal balances.
+    (base / PPB)
+        .saturating_mul(ppb)
+        .saturating_add((base % PPB) * ppb / PPB)
+}
+
+/// Scale a USD value by `part_base / whole_base` (e.g. the share of a transfer that arrives).
+///
+/// Returns 0 when `whole_base` is 0.
+pub fn scale_usd_by_base(usd: f64, part_base: u128, whole_base: u128) -> f64 {
+    if whole_base == 0 {
+        return 0.0_f64;
+    }
+    usd * (part_base as f64 / whole_base as f64)
+}
+
+/// Value lost out of `sent_usd` (sent minus received, plus fees) in basis points, rounded up.
+///
+/// Returns `None` when the inputs are not comparable (non-finite, or nothing sent).
+pub fn loss_bps(sent_usd: f64, received_usd: f64, fees_usd: f64) -> Option<u32> {
+    if !sent_usd.is_finite()
+        || !received_usd.is_finite()
+        || !fees_usd.is_finite()
+        || sent_usd <= 0.0_f64
+    {
+        return None;
+    }
+    let loss = (sent_usd - received_usd + fees_usd).max(0.0_f64);
+    Some(
+        ((loss / sent_usd) * 10_000.0_f64)
+            .ceil()
+            .min(f64::from(u32::MAX)) as u32,
+    )
+}
+
+/// Leverage rounded up to a whole multiple (`2.3` -> `3`), for policy fields typed as `u32`.
+///
+/// Returns `None` for non-finite or sub-1x input.
+pub fn leverage_ceil(leverage: f64) -> Option<u32> {
+    if !leverage.is_finite() || leverage < 1.0_f64 {
+        return None;
+    }
+    Some(leverage.ceil().min(f64::from(u32::MAX)) as u32)
+}
+
+/// Normalise a ratio an API may report either as a fraction (`0.8`) or a percentage (`80`).
+pub fn fraction_from_ratio_or_pct(v: f64) -> f64 {
+    if v > 1.0_f64 {
+        v / 100.0_f64
+    } else {
+        v
+    }
+}
+
+/// Health factor of a looped ("multiply") position opened at `leverage`x equity.
+///
+/// Collateral is `leverage` × equity and debt is `leverage - 1` × equity (priced at entry), so
+/// the health factor is `leverage × liquidation_threshold / (leverage - 1)`. Returns `None` for
+/// non-finite inputs, `leverage <= 1` (no debt), or a threshold outside `(0, 1]`.
+pub fn multiply_health_factor(leverage: f64, liquidation_threshold: f64) -> Option<f64> {
+    if !leverage.is_finite()
+        || !liquidation_threshold.is_finite()
+        || leverage <= 1.0_f64
+        || liquidation_threshold <= 0.0_f64
+        || liquidation_threshold > 1.0_f64
+    {
+        return None;
+    }
+    Some(leverage * liquidation_threshold / (leverage - 1.0_f64))
+}
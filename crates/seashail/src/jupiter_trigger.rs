@@ -0,0 +1,308 @@
+use base64::Engine as _;
+use eyre::Context as _;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::config::HttpConfig;
+
+// Jupiter Limit Order v2 program (backs the Trigger API). Every instruction in a remote-built
+// trigger transaction must target this program or one of the common programs below.
+pub const TRIGGER_PROGRAM_ID: &str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
+
+const COMMON_PROGRAM_IDS: [&str; 6] = [
+    "11111111111111111111111111111111", // system
+    "ComputeBudget111111111111111111111111111111",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", // SPL Token
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb", // Token-2022
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL", // ATA
+    "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr", // Memo
+];
+
+fn is_loopback_http(url: &str) -> bool {
+    fn host_prefix_ok(s: &str, prefix: &str) -> bool {
+        if !s.starts_with(prefix) {
+            return false;
+        }
+        matches!(s.as_bytes().get(prefix.len()), None | Some(b':' | b'/'))
+    }
+    let u = url.trim();
+    host_prefix_ok(u, "http://127.0.0.1")
+        || host_prefix_ok(u, "http://localhost")
+        || host_prefix_ok(u, "http://[::1]")
+}
+
+fn ensure_https_or_loopback(url: &str, name: &str) -> eyre::Result<()> {
+    let u = url.trim();
+    if u.starts_with("https://") || is_loopback_http(u) {
+        return Ok(());
+    }
+    eyre::bail!("{name} must use https (or http://localhost for local testing)");
+}
+
+/// Program ids a trigger transaction is allowed to invoke.
+pub fn allowed_program_ids() -> eyre::Result<Vec<solana_sdk::pubkey::Pubkey>> {
+    COMMON_PROGRAM_IDS
+        .iter()
+        .copied()
+        .chain(std::iter::once(TRIGGER_PROGRAM_ID))
+        .map(crate::chains::solana::SolanaChain::parse_pubkey)
+        .collect::<Result<Vec<_>, _>>()
+        .context("parse jupiter trigger allowlist program ids")
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateOrderRequest<'a> {
+    pub input_mint: &'a str,
+    pub output_mint: &'a str,
+    pub maker: &'a str,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    /// Unix seconds. `None` means the order never expires.
+    pub expired_at: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TriggerTx {
+    /// Order account pubkey (only present for create).
+    pub order: Option<String>,
+    pub tx_bytes: Vec<u8>,
+    pub request_id: Option<String>,
+}
+
+fn client() -> eyre::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(20))
+        .build()
+        .context("build http client")
+}
+
+async fn post_json(cfg: &HttpConfig, path: &str, body: &Value) -> eyre::Result<Value> {
+    let base = cfg.jupiter_trigger_base_url.trim();
+    ensure_https_or_loopback(base, "jupiter_trigger_base_url")?;
+    let url = format!("{}/{path}", base.trim_end_matches('/'));
+    let mut req = client()?.post(url).json(body);
+    if let Some(k) = cfg
+        .jupiter_api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        req = req.header("x-api-key", k);
+    }
+    let resp = req
+        .send()
+        .await
+        .with_context(|| format!("jupiter {path}"))?;
+    let status = resp.status();
+    let v: Value = resp
+        .json()
+        .await
+        .with_context(|| format!("jupiter {path} json"))?;
+    if !status.is_success() {
+        eyre::bail!("jupiter {path} http {status}: {v}");
+    }
+    Ok(v)
+}
+
+fn decode_trigger_tx(v: &Value) -> eyre::Result<TriggerTx> {
+    let tx_b64 = v
+        .get("transaction")
+        .and_then(Value::as_str)
+        .ok_or_else(|| eyre::eyre!("missing transaction in jupiter trigger response"))?;
+    let tx_bytes = base64::engine::general_purpose::STANDARD
+        .decode(tx_b64)
+        .context("decode jupiter trigger transaction b64")?;
+    Ok(TriggerTx {
+        order: v.get("order").and_then(Value::as_str).map(str::to_owned),
+        tx_bytes,
+        request_id: v
+            .get("requestId")
+            .and_then(Value::as_str)
+            .map(str::to_owned),
+    })
+}
+
+pub async fn create_order(
+    cfg: &HttpConfig,
+    req: &CreateOrderRequest<'_>,
+) -> eyre::Result<TriggerTx> {
+    let mut params = json!({
+      "makingAmount": req.making_amount.to_string(),
+      "takingAmount": req.taking_amount.to_string(),
+    });
+    if let (Some(ts), Some(obj)) = (req.expired_at, params.as_object_mut()) {
+        obj.insert("expiredAt".to_owned(), json!(ts.to_string()));
+    }
+    let body = json!({
+      "inputMint": req.input_mint,
+      "outputMint": req.output_mint,
+      "maker": req.maker,
+      "payer": req.maker,
+      "params": params,
+      "computeUnitPrice": "auto",
+      "wrapAndUnwrapSol": true,
+    });
+    let v = post_json(cfg, "createOrder", &body).await?;
+    decode_trigger_tx(&v)
+}
+
+pub async fn cancel_order(cfg: &HttpConfig, maker: &str, order: &str) -> eyre::Result<TriggerTx> {
+    let body = json!({
+      "maker": maker,
+      "order": order,
+      "computeUnitPrice": "auto",
+    });
+    let v = post_json(cfg, "cancelOrder", &body).await?;
+    decode_trigger_tx(&v)
+}
+
+/// Fetch trigger orders for `user`. `status` is `active` or `history`.
+pub async fn get_orders(cfg: &HttpConfig, user: &str, status: &str) -> eyre::Result<Vec<Value>> {
+    let base = cfg.jupiter_trigger_base_url.trim();
+    ensure_https_or_loopback(base, "jupiter_trigger_base_url")?;
+    let url = format!("{}/getTriggerOrders", base.trim_end_matches('/'));
+    let mut req = client()?
+        .get(url)
+        .query(&[("user", user), ("orderStatus", status)]);
+    if let Some(k) = cfg
+        .jupiter_api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        req = req.header("x-api-key", k);
+    }
+    let v: Value = req
+        .send()
+        .await
+        .context("jupiter getTriggerOrders")?
+        .error_for_status()
+        .context("jupiter getTriggerOrders status")?
+        .json()
+        .await
+        .context("jupiter getTriggerOrders json")?;
+    Ok(v.get("orders")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default())
+}
+
+fn str_field<'a>(o: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|k| o.get(*k).and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn u128_field(o: &Value, keys: &[&str]) -> Option<u128> {
+    keys.iter().find_map(|k| match o.get(*k) {
+        Some(Value::String(s)) => s.trim().parse::<u128>().ok(),
+        Some(Value::Number(n)) => n.as_u64().map(u128::from),
+        _ => None,
+    })
+}
+
+fn parse_expiry_unix(o: &Value) -> Option<i64> {
+    match o.get("expiredAt")? {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => {
+            let t = s.trim();
+            t.parse::<i64>().ok().or_else(|| {
+                chrono::DateTime::parse_from_rfc3339(t)
+                    .ok()
+                    .map(|d| d.timestamp())
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Normalize a Trigger API order into Seashail's stable shape, including fill progress and expiry.
+///
+/// Upstream field names have shifted between API revisions, so lookups are best-effort.
+pub fn normalize_order(o: &Value, now_unix: i64) -> Value {
+    let making = u128_field(o, &["rawMakingAmount", "makingAmount"]);
+    let remaining = u128_field(o, &["rawRemainingMakingAmount", "remainingMakingAmount"]);
+    let taking = u128_field(o, &["rawTakingAmount", "takingAmount"]);
+    let remaining_taking = u128_field(o, &["rawRemainingTakingAmount", "remainingTakingAmount"]);
+    let filled = match (making, remaining) {
+        (Some(m), Some(r)) => Some(m.saturating_sub(r)),
+        _ => None,
+    };
+    let filled_bps = match (making, filled) {
+        (Some(m), Some(f)) if m > 0 => Some(f.saturating_mul(10_000) / m),
+        _ => None,
+    };
+    let expired_at = parse_expiry_unix(o);
+    let expired = expired_at.is_some_and(|ts| ts <= now_unix);
+    let upstream_status = str_field(o, &["status"]).unwrap_or("").to_lowercase();
+    let status = if expired && upstream_status != "completed" {
+        "expired"
+    } else if upstream_status == "cancelled" || upstream_status == "canceled" {
+        "cancelled"
+    } else if upstream_status == "completed" || remaining == Some(0) {
+        "filled"
+    } else if filled.is_some_and(|f| f > 0) {
+        "partially_filled"
+    } else {
+        "open"
+    };
+    json!({
+      "order": str_field(o, &["orderKey", "order", "publicKey"]),
+      "input_mint": str_field(o, &["inputMint"]),
+      "output_mint": str_field(o, &["outputMint"]),
+      "making_amount_base": making.map(|v| v.to_string()),
+      "taking_amount_base": taking.map(|v| v.to_string()),
+      "remaining_making_amount_base": remaining.map(|v| v.to_string()),
+      "remaining_taking_amount_base": remaining_taking.map(|v| v.to_string()),
+      "filled_making_amount_base": filled.map(|v| v.to_string()),
+      "filled_bps": filled_bps.and_then(|v| u64::try_from(v).ok()),
+      "expired_at": expired_at,
+      "created_at": str_field(o, &["createdAt"]),
+      "status": status,
+      "upstream_status": if upstream_status.is_empty() { Value::Null } else { json!(upstream_status) },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_order_reports_partial_fill() {
+        let o = json!({
+          "orderKey": "Ord1",
+          "inputMint": "So11111111111111111111111111111111111111112",
+          "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+          "rawMakingAmount": "1000",
+          "rawRemainingMakingAmount": "250",
+          "rawTakingAmount": "5000",
+          "status": "Open"
+        });
+        let n = normalize_order(&o, 0);
+        assert_eq!(
+            n.get("status").and_then(Value::as_str),
+            Some("partially_filled")
+        );
+        assert_eq!(n.get("filled_bps").and_then(Value::as_u64), Some(7_500));
+        assert_eq!(
+            n.get("filled_making_amount_base").and_then(Value::as_str),
+            Some("750")
+        );
+    }
+
+    #[test]
+    fn normalize_order_reports_expiry() {
+        let o = json!({
+          "orderKey": "Ord2",
+          "rawMakingAmount": "1000",
+          "rawRemainingMakingAmount": "1000",
+          "expiredAt": "100",
+          "status": "Open"
+        });
+        let n = normalize_order(&o, 200);
+        assert_eq!(n.get("status").and_then(Value::as_str), Some("expired"));
+        let n = normalize_order(&o, 50);
+        assert_eq!(n.get("status").and_then(Value::as_str), Some("open"));
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     ofac::OfacSdnCacheFile,
     paths::SeashailPaths,
     store::ConfigStore,
-    wallet::{WalletInfo, WalletKind, WalletRecord, WalletStore},
+    wallet::{ActiveSelection, WalletInfo, WalletKind, WalletRecord, WalletStore},
 };
 use base64::Engine as _;
 use chrono::{Datelike as _, Utc};
@@ -16,9 +16,11 @@ use eyre::Context as _;
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs::{self, File, OpenOptions},
     io::Write as _,
     path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 use uuid::Uuid;
 use zeroize::Zeroize as _;
@@ -215,46 +217,89 @@ impl Keystore {
         Ok(secret)
     }
 
-    /// Exclusive lock for write operations across multiple `seashail mcp` processes.
-    pub fn acquire_write_lock(&self) -> eyre::Result<File> {
+    fn open_lock_file(&self) -> eyre::Result<File> {
         let p = self.lock_path();
         if let Some(parent) = p.parent() {
             crate::fsutil::ensure_private_dir(parent)?;
         }
-        let f = {
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::OpenOptionsExt as _;
-                OpenOptions::new()
-                    .create(true)
-                    .read(true)
-                    .write(true)
-                    .truncate(false)
-                    .mode(0o600)
-                    .open(&p)
-                    .context("open lock file")?
-            }
-            #[cfg(not(unix))]
-            {
-                OpenOptions::new()
-                    .create(true)
-                    .read(true)
-                    .write(true)
-                    .truncate(false)
-                    .open(&p)
-                    .context("open lock file")?
-            }
-        };
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt as _;
+            OpenOptions::new()
+                .create(true)
+                .read(true)
+                .write(true)
+                .truncate(false)
+                .mode(0o600)
+                .open(&p)
+                .context("open lock file")
+        }
+        #[cfg(not(unix))]
+        {
+            OpenOptions::new()
+                .create(true)
+                .read(true)
+                .write(true)
+                .truncate(false)
+                .open(&p)
+                .context("open lock file")
+        }
+    }
+
+    /// Try the exclusive lock once. `Ok(false)` means another process holds it.
+    fn try_lock_file(f: &File) -> eyre::Result<bool> {
+        match f.try_lock_exclusive() {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(false),
+            Err(e) => Err(eyre::Report::new(e).wrap_err("lock exclusive")),
+        }
+    }
+
+    /// Exclusive lock for write operations across multiple `seashail mcp` processes.
+    pub fn acquire_write_lock(&self) -> eyre::Result<File> {
+        let f = self.open_lock_file()?;
         // Avoid indefinite blocking inside an MCP tool call. If another process is actively
         // writing, fail fast and let the client retry.
-        match f.try_lock_exclusive() {
-            Ok(()) => {}
-            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+        if !Self::try_lock_file(&f)? {
+            return Err(SeashailError::KeystoreBusy.into());
+        }
+        Ok(f)
+    }
+
+    /// Like `acquire_write_lock`, but polls for up to `max_wait` so brief contention between
+    /// processes resolves transparently. Still fails with `KeystoreBusy` once the wait is used up.
+    pub async fn acquire_write_lock_within(&self, max_wait: Duration) -> eyre::Result<File> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+        let f = self.open_lock_file()?;
+        let deadline = Instant::now() + max_wait;
+        loop {
+            if Self::try_lock_file(&f)? {
+                return Ok(f);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
                 return Err(SeashailError::KeystoreBusy.into());
             }
-            Err(e) => return Err(eyre::Report::new(e).wrap_err("lock exclusive")),
+            tokio::time::sleep(POLL_INTERVAL.min(remaining)).await;
+        }
+    }
+
+    /// Blocking variant of `acquire_write_lock_within` for one-shot CLI commands, which run
+    /// outside the async MCP request loop.
+    pub fn acquire_write_lock_blocking_within(&self, max_wait: Duration) -> eyre::Result<File> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+        let f = self.open_lock_file()?;
+        let deadline = Instant::now() + max_wait;
+        loop {
+            if Self::try_lock_file(&f)? {
+                return Ok(f);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(SeashailError::KeystoreBusy.into());
+            }
+            std::thread::sleep(POLL_INTERVAL.min(remaining));
         }
-        Ok(f)
     }
 
     pub fn release_lock(mut f: File) -> eyre::Result<()> {
@@ -268,29 +313,52 @@ impl Keystore {
         self.wallets.list()
     }
 
-    pub fn ensure_default_wallet(&self) -> eyre::Result<()> {
-        if !self.list_wallets()?.is_empty() {
-            return Ok(());
-        }
-
-        // Serialize first-run initialization across competing binaries/agents.
-        let lock = self.acquire_write_lock()?;
+    /// First-run initialization: create the machine-bound generated `default` wallet if there are
+    /// no wallets yet. The caller must hold the write lock, which serializes this across competing
+    /// binaries/agents. Returns the new wallet, or `None` if one already existed.
+    pub fn create_default_wallet_locked(&self) -> eyre::Result<Option<WalletInfo>> {
         if !self.list_wallets()?.is_empty() {
-            Self::release_lock(lock)?;
-            return Ok(());
+            return Ok(None);
         }
-
         let info = self.create_generated_wallet_machine_only("default".to_owned())?;
         // Best-effort: make it active for immediate use.
         let _set_active_wallet = self.set_active_wallet(&info.name, 0);
-        Self::release_lock(lock)?;
-        Ok(())
+        Ok(Some(info))
     }
 
     pub fn get_wallet_by_name(&self, name: &str) -> eyre::Result<Option<WalletRecord>> {
         self.wallets.get_by_name(name)
     }
 
+    /// Fail with [`SeashailError::WalletNameTaken`] if `name` collides with an existing wallet's
+    /// current or previous name (case-insensitive, whitespace-trimmed).
+    pub fn ensure_wallet_name_available(&self, name: &str) -> eyre::Result<()> {
+        if self.wallets.name_conflict(name)?.is_some() {
+            return Err(SeashailError::WalletNameTaken(name.trim().to_owned()).into());
+        }
+        Ok(())
+    }
+
+    /// Rename a wallet. Tx history is left as written; see [`Self::read_tx_history_filtered`].
+    pub fn rename_wallet(&self, old: &str, new: &str) -> eyre::Result<WalletInfo> {
+        let w = self.wallets.rename(old, new)?;
+        let active = self
+            .wallets
+            .get_active()?
+            .and_then(|(aw, idx)| (aw.id == w.id).then_some(idx))
+            .unwrap_or(w.last_active_account);
+        Ok(WalletStore::wallet_info(&w, active))
+    }
+
+    /// `wallet` plus every name it was renamed from, so history filters still match old entries.
+    fn wallet_history_names(&self, wallet: &str) -> eyre::Result<Vec<String>> {
+        let mut names = vec![wallet.to_owned()];
+        if let Some(w) = self.wallets.get_by_name(wallet)? {
+            names.extend(w.previous_names);
+        }
+        Ok(names)
+    }
+
     pub fn get_wallet_info(&self, name: &str) -> eyre::Result<WalletInfo> {
         let w = self
             .wallets
@@ -304,6 +372,10 @@ impl Keystore {
         Ok(WalletStore::wallet_info(&w, active))
     }
 
+    pub fn update_wallet(&self, wallet: &WalletRecord) -> eyre::Result<()> {
+        self.wallets.update(wallet)
+    }
+
     pub fn set_active_wallet(&self, name: &str, account_index: u32) -> eyre::Result<()> {
         self.wallets.set_active(name, account_index)
     }
@@ -312,6 +384,33 @@ impl Keystore {
         self.wallets.get_active()
     }
 
+    pub fn set_active_wallet_for_chain(
+        &self,
+        scope: &str,
+        name: &str,
+        account_index: u32,
+    ) -> eyre::Result<()> {
+        self.wallets
+            .set_active_for_chain(scope, name, account_index)
+    }
+
+    pub fn clear_active_wallet_for_chain(&self, scope: &str) -> eyre::Result<bool> {
+        self.wallets.clear_active_for_chain(scope)
+    }
+
+    /// Per-chain active overrides (chain name or family -> wallet/account).
+    pub fn active_wallet_by_chain(&self) -> eyre::Result<BTreeMap<String, ActiveSelection>> {
+        self.wallets.active_by_chain()
+    }
+
+    /// Active wallet/account for `chain`, honoring per-chain overrides before the global one.
+    pub fn get_active_wallet_for_chain(
+        &self,
+        chain: &str,
+    ) -> eyre::Result<Option<(WalletRecord, u32)>> {
+        self.wallets.get_active_for_chain(chain)
+    }
+
     pub fn add_account(
         &self,
         wallet_name: &str,
@@ -325,19 +424,44 @@ impl Keystore {
         wallet_name: &str,
         passphrase_key: Option<&[u8; 32]>,
     ) -> eyre::Result<(WalletInfo, u32)> {
+        self.add_accounts_auto(wallet_name, passphrase_key, 1)?
+            .pop()
+            .ok_or_else(|| eyre::eyre!("no account derived"))
+    }
+
+    /// Derive `count` new account indexes in one secret-decryption pass.
+    ///
+    /// Addresses and the `accounts` counter are written in a single wallet update, so a failure
+    /// part-way through leaves the record unchanged.
+    pub fn add_accounts_auto(
+        &self,
+        wallet_name: &str,
+        passphrase_key: Option<&[u8; 32]>,
+        count: u32,
+    ) -> eyre::Result<Vec<(WalletInfo, u32)>> {
+        if count == 0 {
+            eyre::bail!("count must be >= 1");
+        }
         let mut w = self
             .get_wallet_by_name(wallet_name)?
             .ok_or_else(|| SeashailError::WalletNotFound(wallet_name.to_owned()))?;
 
-        let new_index = w.accounts;
+        let first_index = w.accounts;
+        let end_index = first_index
+            .checked_add(count)
+            .ok_or_else(|| eyre::eyre!("account index overflow"))?;
+        let new_indexes: Vec<u32> = (first_index..end_index).collect();
 
         match w.kind {
             WalletKind::Generated => {
                 let mut entropy = self.decrypt_generated_entropy_maybe(&w.id, passphrase_key)?;
-                let (evm, sol) = crate::wallet::addresses_from_entropy(&entropy, &[new_index])?;
-                let (btc_main, btc_test) =
-                    crate::wallet::bitcoin_addresses_from_entropy(&entropy, &[new_index])?;
+                let derived = crate::wallet::addresses_from_entropy(&entropy, &new_indexes)
+                    .and_then(|(evm, sol)| {
+                        crate::wallet::bitcoin_addresses_from_entropy(&entropy, &new_indexes)
+                            .map(|btc| (evm, sol, btc))
+                    });
                 entropy.zeroize();
+                let (evm, sol, (btc_main, btc_test)) = derived?;
                 w.evm_addresses.extend(evm);
                 w.solana_addresses.extend(sol);
                 w.bitcoin_addresses_mainnet.extend(btc_main);
@@ -349,16 +473,23 @@ impl Keystore {
                         return Err(SeashailError::PassphraseRequired.into());
                     };
                     let mut secret = self.decrypt_imported_secret(&w.id, passphrase_key)?;
-                    let phrase =
-                        std::str::from_utf8(&secret).context("imported mnemonic must be utf-8")?;
-                    let mnemonic =
-                        bip39::Mnemonic::parse_in_normalized(bip39::Language::English, phrase)
-                            .context("parse imported mnemonic")?;
-                    let (evm, sol) =
-                        crate::wallet::addresses_from_mnemonic(&mnemonic, &[new_index])?;
-                    let (btc_main, btc_test) =
-                        crate::wallet::bitcoin_addresses_from_mnemonic(&mnemonic, &[new_index])?;
+                    let derived = std::str::from_utf8(&secret)
+                        .context("imported mnemonic must be utf-8")
+                        .and_then(|phrase| {
+                            bip39::Mnemonic::parse_in_normalized(bip39::Language::English, phrase)
+                                .context("parse imported mnemonic")
+                        })
+                        .and_then(|mnemonic| {
+                            let (evm, sol) =
+                                crate::wallet::addresses_from_mnemonic(&mnemonic, &new_indexes)?;
+                            let btc = crate::wallet::bitcoin_addresses_from_mnemonic(
+                                &mnemonic,
+                                &new_indexes,
+                            )?;
+                            Ok((evm, sol, btc))
+                        });
                     secret.zeroize();
+                    let (evm, sol, (btc_main, btc_test)) = derived?;
                     w.evm_addresses.extend(evm);
                     w.solana_addresses.extend(sol);
                     w.bitcoin_addresses_mainnet.extend(btc_main);
@@ -366,11 +497,66 @@ impl Keystore {
                 }
                 _ => eyre::bail!("cannot add accounts to imported private key wallets"),
             },
+            WalletKind::Watch => {
+                let Some(xpub) = &w.watch_xpub else {
+                    eyre::bail!(
+                        "cannot add accounts to address-list watch wallets; re-import with the extra addresses"
+                    );
+                };
+                let derived = crate::wallet::watch_addresses_from_xpub(xpub, &new_indexes)?;
+                w.evm_addresses.extend(derived.evm);
+                w.bitcoin_addresses_mainnet.extend(derived.bitcoin_mainnet);
+                w.bitcoin_addresses_testnet.extend(derived.bitcoin_testnet);
+            }
         }
 
-        w.accounts += 1;
+        w.accounts = end_index;
         self.wallets.update(&w)?;
-        Ok((WalletStore::wallet_info(&w, new_index), new_index))
+        Ok(new_indexes
+            .into_iter()
+            .map(|idx| (WalletStore::wallet_info(&w, idx), idx))
+            .collect())
+    }
+
+    /// Account-level extended public keys for a BIP-44 wallet. The secret is zeroized before
+    /// returning and nothing private leaves this function.
+    pub fn public_descriptors(
+        &self,
+        wallet_name: &str,
+        passphrase_key: Option<&[u8; 32]>,
+    ) -> eyre::Result<crate::wallet::PublicDescriptors> {
+        let w = self
+            .get_wallet_by_name(wallet_name)?
+            .ok_or_else(|| SeashailError::WalletNotFound(wallet_name.to_owned()))?;
+        match w.kind {
+            WalletKind::Generated => {
+                let mut entropy = self.decrypt_generated_entropy_maybe(&w.id, passphrase_key)?;
+                let out = crate::wallet::public_descriptors_from_entropy(&entropy);
+                entropy.zeroize();
+                out
+            }
+            WalletKind::Imported => match w.imported_kind {
+                Some(crate::wallet::ImportedKind::Mnemonic) => {
+                    let Some(passphrase_key) = passphrase_key else {
+                        return Err(SeashailError::PassphraseRequired.into());
+                    };
+                    let mut secret = self.decrypt_imported_secret(&w.id, passphrase_key)?;
+                    let out = std::str::from_utf8(&secret)
+                        .context("imported mnemonic must be utf-8")
+                        .and_then(|phrase| {
+                            bip39::Mnemonic::parse_in_normalized(bip39::Language::English, phrase)
+                                .context("parse imported mnemonic")
+                        })
+                        .and_then(|mnemonic| {
+                            crate::wallet::public_descriptors_from_mnemonic(&mnemonic)
+                        });
+                    secret.zeroize();
+                    out
+                }
+                _ => eyre::bail!("imported private key wallets have no extended public key"),
+            },
+            WalletKind::Watch => eyre::bail!("watch-only wallets have no key material to export"),
+        }
     }
 
     pub fn add_account_no_passphrase(&self, wallet_name: &str) -> eyre::Result<(WalletInfo, u32)> {
@@ -434,7 +620,23 @@ impl Keystore {
         eyre::bail!("unknown chain: {chain}");
     }
 
-    pub fn append_tx_history(&self, entry: &serde_json::Value) -> eyre::Result<()> {
+    /// Append one tx history entry. `note` is the caller's bookkeeping note for the tool call
+    /// that produced it; an entry that already carries a `note` keeps its own.
+    pub fn append_tx_history(
+        &self,
+        entry: &serde_json::Value,
+        note: Option<&str>,
+    ) -> eyre::Result<()> {
+        let noted;
+        let entry = match (note, entry.as_object()) {
+            (Some(n), Some(obj)) if !obj.contains_key("note") => {
+                let mut obj = obj.clone();
+                obj.insert("note".into(), serde_json::Value::from(n));
+                noted = serde_json::Value::Object(obj);
+                &noted
+            }
+            _ => entry,
+        };
         let p = self.tx_history_path();
         if let Some(parent) = p.parent() {
             crate::fsutil::ensure_private_dir(parent)?;
@@ -464,6 +666,67 @@ impl Keystore {
         Ok(())
     }
 
+    /// Set `status` on the tx history entries that recorded `tx_id` (as `txid`, `signature`, or
+    /// `tx_hash`), merging `fields` in. Returns how many entries were updated.
+    pub fn update_tx_history_status(
+        &self,
+        tx_id: &str,
+        status: &str,
+        fields: &serde_json::Value,
+    ) -> eyre::Result<usize> {
+        let p = self.tx_history_path();
+        if !p.exists() {
+            return Ok(0);
+        }
+        let contents = fs::read_to_string(&p).context("read tx history")?;
+        let mut updated = 0_usize;
+        let mut out = String::with_capacity(contents.len());
+        for line in contents.lines() {
+            let matched = serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .filter(|v| {
+                    ["txid", "signature", "tx_hash"]
+                        .iter()
+                        .any(|k| v.get(*k).and_then(|x| x.as_str()) == Some(tx_id))
+                });
+            match matched {
+                Some(mut v) => {
+                    if let Some(obj) = v.as_object_mut() {
+                        obj.insert("status".into(), serde_json::Value::from(status));
+                        if let Some(extra) = fields.as_object() {
+                            for (k, val) in extra {
+                                obj.insert(k.clone(), val.clone());
+                            }
+                        }
+                    }
+                    out.push_str(&v.to_string());
+                    updated += 1;
+                }
+                None => out.push_str(line),
+            }
+            out.push('\n');
+        }
+        if updated > 0 {
+            crate::fsutil::write_string_atomic_restrictive(&p, &out, 0o600)?;
+        }
+        Ok(updated)
+    }
+
+    /// Whether tx history holds a `sign_only` entry for `signature` that has not been submitted yet.
+    pub fn has_signed_tx_pending(&self, signature: &str) -> eyre::Result<bool> {
+        let p = self.tx_history_path();
+        if !p.exists() {
+            return Ok(false);
+        }
+        let contents = fs::read_to_string(&p).context("read tx history")?;
+        Ok(contents.lines().any(|line| {
+            serde_json::from_str::<serde_json::Value>(line).is_ok_and(|v| {
+                v.get("signature").and_then(|x| x.as_str()) == Some(signature)
+                    && v.get("status").and_then(|x| x.as_str()) == Some("signed")
+            })
+        }))
+    }
+
     pub fn read_tx_history_filtered(
         &self,
         limit: usize,
@@ -487,18 +750,32 @@ impl Keystore {
         } else {
             None
         };
+        let wallet_names = wallet.map(|w| self.wallet_history_names(w)).transpose()?;
+        // Entries recorded before a rename keep their original `wallet`; expose the current name
+        // alongside it rather than rewriting history.
+        let renamed: BTreeMap<String, String> = self
+            .wallets
+            .list()?
+            .into_iter()
+            .flat_map(|w| {
+                w.previous_names
+                    .into_iter()
+                    .map(move |p| (p, w.name.clone()))
+            })
+            .collect();
         let contents = fs::read_to_string(&p).context("read tx history")?;
         let mut out = vec![];
         for line in contents.lines().rev() {
             if out.len() >= limit {
                 break;
             }
-            let Ok(v) = serde_json::from_str::<serde_json::Value>(line) else {
+            let Ok(mut v) = serde_json::from_str::<serde_json::Value>(line) else {
                 continue;
             };
 
-            if let Some(w) = wallet {
-                if v.get("wallet").and_then(|x| x.as_str()) != Some(w) {
+            let entry_wallet = v.get("wallet").and_then(|x| x.as_str()).map(str::to_owned);
+            if let Some(names) = &wallet_names {
+                if !entry_wallet.as_ref().is_some_and(|ew| names.contains(ew)) {
                     continue;
                 }
             }
@@ -526,6 +803,15 @@ impl Keystore {
                 }
             }
 
+            if let (Some(current), Some(obj)) = (
+                entry_wallet.and_then(|ew| renamed.get(&ew)),
+                v.as_object_mut(),
+            ) {
+                obj.insert(
+                    "wallet_current".into(),
+                    serde_json::Value::from(current.as_str()),
+                );
+            }
             out.push(v);
         }
         out.reverse();
@@ -533,24 +819,58 @@ impl Keystore {
     }
 
     pub fn daily_used_usd_filtered(&self, day: &str, wallet: Option<&str>) -> eyre::Result<f64> {
+        self.daily_used_usd_scoped(day, wallet, None)
+    }
+
+    /// Daily spend of one `(wallet, account_index)`. Entries that do not record an account index
+    /// cannot be attributed and are counted, so this never under-reports.
+    pub fn daily_used_usd_for_account(
+        &self,
+        day: &str,
+        wallet: &str,
+        account_index: u32,
+    ) -> eyre::Result<f64> {
+        self.daily_used_usd_scoped(day, Some(wallet), Some(account_index))
+    }
+
+    fn daily_used_usd_scoped(
+        &self,
+        day: &str,
+        wallet: Option<&str>,
+        account_index: Option<u32>,
+    ) -> eyre::Result<f64> {
         let p = self.tx_history_path();
         if !p.exists() {
             return Ok(0.0_f64);
         }
+        let wallet_names = wallet.map(|w| self.wallet_history_names(w)).transpose()?;
         let s = fs::read_to_string(&p).context("read tx history")?;
         let mut total = 0.0_f64;
         for line in s.lines() {
             let Ok(v) = serde_json::from_str::<serde_json::Value>(line) else {
                 continue;
             };
-            if let Some(w) = wallet {
-                if v.get("wallet").and_then(|x| x.as_str()) != Some(w) {
+            if let Some(names) = &wallet_names {
+                // Renaming a wallet must not reset its daily spend.
+                if !v
+                    .get("wallet")
+                    .and_then(|x| x.as_str())
+                    .is_some_and(|ew| names.iter().any(|n| n == ew))
+                {
                     continue;
                 }
             }
             if v.get("day").and_then(|x| x.as_str()) != Some(day) {
                 continue;
             }
+            if let (Some(want), Some(got)) = (
+                account_index,
+                v.get("account_index").and_then(serde_json::Value::as_u64),
+            ) {
+                if u64::from(want) != got {
+                    continue;
+                }
+            }
             let Some(t) = v.get("type").and_then(|x| x.as_str()) else {
                 continue;
             };
@@ -559,6 +879,7 @@ impl Keystore {
                 t,
                 "send"
                     | "swap"
+                    | "spot_limit_place"
                     | "approve"
                     | "perp_open"
                     | "perp_close"
@@ -580,6 +901,8 @@ impl Keystore {
                     | "unstake"
                     | "provide_liquidity"
                     | "remove_liquidity"
+                    | "multiply_open"
+                    | "multiply_close"
                     | "prediction_place"
                     | "prediction_close"
             ) {
@@ -628,6 +951,8 @@ impl Keystore {
         passphrase_key: [u8; 32],
     ) -> eyre::Result<(WalletInfo, String)> {
         self.ensure_machine_secret()?;
+        // Checked up front so a taken name never leaves an orphaned wallet dir behind.
+        self.ensure_wallet_name_available(&name)?;
 
         let wallet_id = Uuid::new_v4().to_string();
         let wdir = self.wallet_dir(&wallet_id);
@@ -703,6 +1028,8 @@ impl Keystore {
     /// with a passphrase and exporting Share 3.
     pub fn create_generated_wallet_machine_only(&self, name: String) -> eyre::Result<WalletInfo> {
         self.ensure_machine_secret()?;
+        // Checked up front so a taken name never leaves an orphaned wallet dir behind.
+        self.ensure_wallet_name_available(&name)?;
 
         let wallet_id = Uuid::new_v4().to_string();
         let wdir = self.wallet_dir(&wallet_id);
@@ -770,8 +1097,11 @@ impl Keystore {
         kind: crate::wallet::ImportedKind,
         mut secret_bytes: Vec<u8>,
         passphrase_key: [u8; 32],
+        birthday: Option<crate::wallet::WalletBirthday>,
     ) -> eyre::Result<WalletInfo> {
         self.ensure_machine_secret()?;
+        // Checked up front so a taken name never leaves an orphaned wallet dir behind.
+        self.ensure_wallet_name_available(&name)?;
         let wallet_id = Uuid::new_v4().to_string();
         let wdir = self.wallet_dir(&wallet_id);
         fs::create_dir_all(&wdir).context("create wallet dir")?;
@@ -798,6 +1128,7 @@ impl Keystore {
                 bitcoin_mainnet: btc_main,
                 bitcoin_testnet: btc_test,
             },
+            birthday,
         );
         self.wallets.add(wallet.clone())?;
 
@@ -820,6 +1151,26 @@ impl Keystore {
         Ok(WalletStore::wallet_info(&wallet, 0))
     }
 
+    /// Add a watch-only wallet. Only public addresses (and the optional xpub) are stored; there
+    /// is no key material, so no wallet directory or passphrase is involved.
+    pub fn import_watch_wallet(
+        &self,
+        name: String,
+        watch_xpub: Option<crate::wallet::WatchXpub>,
+        addrs: crate::wallet::WalletAddressSets,
+        birthday: Option<crate::wallet::WalletBirthday>,
+    ) -> eyre::Result<WalletInfo> {
+        let wallet = crate::wallet::WalletRecord::new_watch(
+            Uuid::new_v4().to_string(),
+            name,
+            watch_xpub,
+            addrs,
+            birthday,
+        );
+        self.wallets.add(wallet.clone())?;
+        Ok(WalletStore::wallet_info(&wallet, 0))
+    }
+
     fn load_generated_wallet_meta(&self, wallet_id: &str) -> eyre::Result<GeneratedWalletMeta> {
         let p = self.wallet_dir(wallet_id).join("wallet.json");
         Self::read_json(&p)
@@ -981,3 +1332,84 @@ struct ImportedWalletMeta {
 pub fn utc_now_iso() -> String {
     chrono::Utc::now().to_rfc3339()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kamino_multiply_counts_toward_daily_spend() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let ks = Keystore::open(SeashailPaths {
+            config_dir: dir.path().join("config"),
+            data_dir: dir.path().join("data"),
+            log_file: dir.path().join("data").join("seashail.log.jsonl"),
+        })?;
+        let day = Keystore::current_utc_day_key();
+        assert!(ks.daily_used_usd_filtered(&day, None)?.abs() < 1e-9_f64);
+
+        ks.append_tx_history(
+            &serde_json::json!({
+              "day": day, "type": "multiply_open", "usd_value": 300.0_f64,
+            }),
+            None,
+        )?;
+        let used = ks.daily_used_usd_filtered(&day, None)?;
+        assert!((used - 300.0_f64).abs() < 1e-9_f64);
+
+        ks.append_tx_history(
+            &serde_json::json!({
+              "day": day, "type": "multiply_close", "usd_value": 120.0_f64,
+            }),
+            None,
+        )?;
+        let used = ks.daily_used_usd_filtered(&day, None)?;
+        assert!((used - 420.0_f64).abs() < 1e-9_f64);
+        Ok(())
+    }
+
+    #[test]
+    fn account_daily_spend_excludes_other_accounts() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let ks = Keystore::open(SeashailPaths {
+            config_dir: dir.path().join("config"),
+            data_dir: dir.path().join("data"),
+            log_file: dir.path().join("data").join("seashail.log.jsonl"),
+        })?;
+        let day = Keystore::current_utc_day_key();
+        for (idx, usd) in [(0_u32, 500.0_f64), (1_u32, 20.0_f64)] {
+            ks.append_tx_history(&serde_json::json!({
+              "day": day, "type": "send", "wallet": "trader", "account_index": idx, "usd_value": usd,
+            }), None)?;
+        }
+        let hot = ks.daily_used_usd_for_account(&day, "trader", 1)?;
+        assert!((hot - 20.0_f64).abs() < 1e-9_f64);
+        let wallet = ks.daily_used_usd_filtered(&day, Some("trader"))?;
+        assert!((wallet - 520.0_f64).abs() < 1e-9_f64);
+        Ok(())
+    }
+
+    #[test]
+    fn only_unsubmitted_signed_entries_are_pending() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let ks = Keystore::open(SeashailPaths {
+            config_dir: dir.path().join("config"),
+            data_dir: dir.path().join("data"),
+            log_file: dir.path().join("data").join("seashail.log.jsonl"),
+        })?;
+        assert!(!ks.has_signed_tx_pending("sigA")?);
+        ks.append_tx_history(
+            &serde_json::json!({ "type": "send", "signature": "sigA", "status": "signed" }),
+            None,
+        )?;
+        ks.append_tx_history(
+            &serde_json::json!({ "type": "send", "signature": "sigB" }),
+            None,
+        )?;
+        assert!(ks.has_signed_tx_pending("sigA")?);
+        assert!(!ks.has_signed_tx_pending("sigB")?);
+        ks.update_tx_history_status("sigA", "submitted", &serde_json::json!({}))?;
+        assert!(!ks.has_signed_tx_pending("sigA")?);
+        Ok(())
+    }
+}
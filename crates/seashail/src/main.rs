@@ -21,8 +21,10 @@ mod doctor;
 mod errors;
 mod financial_math;
 mod fsutil;
+mod jupiter_trigger;
 mod keystore;
 mod marketplace_adapter;
+mod message_signing;
 mod ofac;
 mod openclaw;
 mod paths;
@@ -30,6 +32,7 @@ mod perps;
 mod policy;
 mod policy_engine;
 mod price;
+mod rebalance;
 mod retry;
 mod rpc;
 mod store;
@@ -45,6 +45,12 @@ pub struct EvmTxEnvelopeResponse {
     pub value_wei: String,
     #[serde(default)]
     pub usd_value: Option<f64>,
+    /// Bridge routes: value expected to arrive on the destination chain.
+    #[serde(default)]
+    pub expected_received_usd: Option<f64>,
+    /// Bridge routes: protocol/relayer fees charged by the route.
+    #[serde(default)]
+    pub bridge_fee_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -54,6 +60,12 @@ pub struct SolanaTxEnvelopeResponse {
     pub allowed_program_ids: Vec<String>,
     #[serde(default)]
     pub usd_value: Option<f64>,
+    /// Bridge routes: value expected to arrive on the destination chain.
+    #[serde(default)]
+    pub expected_received_usd: Option<f64>,
+    /// Bridge routes: protocol/relayer fees charged by the route.
+    #[serde(default)]
+    pub bridge_fee_usd: Option<f64>,
 }
 
 fn base_url_for_marketplace<'a>(cfg: &'a HttpConfig, marketplace: &str) -> Option<&'a str> {
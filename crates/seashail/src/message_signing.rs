@@ -0,0 +1,165 @@
+//! Off-chain message signing helpers (EIP-191 `personal_sign`, Solana ed25519 message signing).
+//!
+//! Pure computation (no I/O): decoding the user-supplied message, rendering it for confirmation,
+//! refusing payloads that are transactions, and flagging ambiguous ones such as digests.
+
+use bincode::Options as _;
+use eyre::Context as _;
+
+/// Upper bound on message size. Login/proof messages are small; anything larger is suspicious.
+pub const MAX_MESSAGE_BYTES: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignerFamily {
+    Evm,
+    Solana,
+}
+
+/// Decode `message` as UTF-8 text (`encoding = "utf8"`) or hex (`encoding = "hex"`, optional `0x`).
+pub fn decode_message(message: &str, encoding: &str) -> eyre::Result<Vec<u8>> {
+    let bytes = match encoding {
+        "utf8" | "utf-8" | "" => message.as_bytes().to_vec(),
+        "hex" => {
+            let h = message.trim();
+            let h = h
+                .strip_prefix("0x")
+                .or_else(|| h.strip_prefix("0X"))
+                .unwrap_or(h);
+            hex::decode(h).context("message is not valid hex")?
+        }
+        other => eyre::bail!("unsupported encoding: {other} (expected utf8 or hex)"),
+    };
+    if bytes.is_empty() {
+        eyre::bail!("message is empty");
+    }
+    if bytes.len() > MAX_MESSAGE_BYTES {
+        eyre::bail!(
+            "message is {} bytes; max supported is {MAX_MESSAGE_BYTES}",
+            bytes.len()
+        );
+    }
+    Ok(bytes)
+}
+
+/// The message as printable text, if it is valid UTF-8 without control characters (other than
+/// common whitespace).
+pub fn as_readable_text(bytes: &[u8]) -> Option<&str> {
+    let s = std::str::from_utf8(bytes).ok()?;
+    s.chars()
+        .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .then_some(s)
+}
+
+/// Exact rendering of the message for confirmation prompts: text as-is, otherwise `0x`-hex.
+pub fn display_message(bytes: &[u8]) -> String {
+    as_readable_text(bytes).map_or_else(|| format!("0x{}", hex::encode(bytes)), str::to_owned)
+}
+
+fn looks_like_solana_message(bytes: &[u8]) -> bool {
+    let opts = || {
+        bincode::DefaultOptions::new()
+            .with_limit(u64::try_from(MAX_MESSAGE_BYTES).unwrap_or(u64::MAX))
+            .reject_trailing_bytes()
+    };
+    opts()
+        .deserialize::<solana_sdk::message::VersionedMessage>(bytes)
+        .is_ok()
+        || opts()
+            .deserialize::<solana_sdk::transaction::VersionedTransaction>(bytes)
+            .is_ok()
+}
+
+/// Why `bytes` must not be signed as a message, if it is a transaction or typed-data payload.
+///
+/// A Solana message signature over a serialized transaction message is a valid transaction
+/// signature, and EVM transaction/EIP-712 payloads authorize more than a login. Those have to go
+/// through the policy-gated write tools instead.
+pub fn transaction_payload_reason(bytes: &[u8], family: SignerFamily) -> Option<&'static str> {
+    match family {
+        SignerFamily::Solana => looks_like_solana_message(bytes).then_some(
+            "message decodes as a Solana transaction; use the write tools to sign transactions",
+        ),
+        SignerFamily::Evm => {
+            if as_readable_text(bytes).is_some() {
+                return None;
+            }
+            match bytes {
+                [0x19, 0x01, ..] => Some("message starts with the EIP-712 typed-data prefix"),
+                [0x01..=0x04, 0xc0..=0xff, ..] => {
+                    Some("message looks like an RLP-encoded typed transaction")
+                }
+                [0xc0..=0xff, ..] => Some("message looks like an RLP-encoded transaction"),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Warnings for ambiguous payloads that may hide what is being authorized (blind signing).
+/// Transaction-shaped payloads are refused by [`transaction_payload_reason`] before this runs.
+pub fn blind_signing_warnings(bytes: &[u8], family: SignerFamily) -> Vec<&'static str> {
+    let mut out = vec![];
+    if as_readable_text(bytes).is_some() {
+        return out;
+    }
+    out.push("message is not human-readable text");
+    if family == SignerFamily::Evm && bytes.len() == 32 {
+        out.push("message is exactly 32 bytes and may be a transaction or typed-data hash");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_message_handles_utf8_hex_and_limits() {
+        assert_eq!(
+            decode_message("hello", "utf8").ok(),
+            Some(b"hello".to_vec())
+        );
+        assert_eq!(decode_message("0x6869", "hex").ok(), Some(b"hi".to_vec()));
+        assert!(decode_message("zz", "hex").is_err());
+        assert!(decode_message("", "utf8").is_err());
+        assert!(decode_message(&"a".repeat(MAX_MESSAGE_BYTES + 1), "utf8").is_err());
+    }
+
+    #[test]
+    fn readable_text_has_no_warnings() {
+        let msg = b"example.com wants you to sign in with your Ethereum account:\n0xabc";
+        assert!(blind_signing_warnings(msg, SignerFamily::Evm).is_empty());
+        assert_eq!(display_message(msg), String::from_utf8_lossy(msg));
+    }
+
+    #[test]
+    fn evm_digests_are_flagged_and_transactions_refused() {
+        let digest = [0xab_u8; 32];
+        let w = blind_signing_warnings(&digest, SignerFamily::Evm);
+        assert!(w.iter().any(|s| s.contains("32 bytes")));
+        assert_eq!(transaction_payload_reason(&digest, SignerFamily::Evm), None);
+
+        let typed_tx = [0x02_u8, 0xf8, 0x6f, 0x01];
+        assert!(transaction_payload_reason(&typed_tx, SignerFamily::Evm)
+            .is_some_and(|r| r.contains("typed transaction")));
+        assert_eq!(display_message(&typed_tx), "0x02f86f01");
+        assert!(transaction_payload_reason(&[0x19_u8, 0x01, 0xaa], SignerFamily::Evm).is_some());
+        assert_eq!(
+            transaction_payload_reason("é".as_bytes(), SignerFamily::Evm),
+            None
+        );
+    }
+
+    #[test]
+    fn solana_transaction_message_is_refused() -> eyre::Result<()> {
+        let payer = solana_sdk::pubkey::Pubkey::new_unique();
+        let msg = solana_sdk::message::Message::new(&[], Some(&payer));
+        let bytes = bincode::serialize(&msg).context("serialize message")?;
+        assert!(transaction_payload_reason(&bytes, SignerFamily::Solana).is_some());
+        assert_eq!(
+            transaction_payload_reason(b"sign in to example.com", SignerFamily::Solana),
+            None
+        );
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use std::{
     io::Write as _,
     path::{Path, PathBuf},
     process::Command,
+    time::Duration,
 };
 
 use crate::agent::AgentNetwork;
@@ -18,26 +19,30 @@ fn ensure_default_wallet_cli() -> eyre::Result<()> {
         return Ok(());
     }
 
-    let lock = ks.acquire_write_lock().context("acquire keystore lock")?;
-    if !ks.list_wallets().context("list wallets")?.is_empty() {
+    // A one-shot command can afford to wait out a daemon that is creating the wallet itself.
+    let lock = ks
+        .acquire_write_lock_blocking_within(Duration::from_secs(10))
+        .context("acquire keystore lock")?;
+    let Some(info) = ks
+        .create_default_wallet_locked()
+        .context("create generated wallet")?
+    else {
         Keystore::release_lock(lock)?;
         return Ok(());
-    }
-
-    let info = ks
-        .create_generated_wallet_machine_only("default".to_owned())
-        .context("create generated wallet")?;
-    let _set_active_wallet = ks.set_active_wallet(&info.name, 0);
+    };
 
     // Record a minimal history entry.
-    ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": "wallet_created",
-      "wallet": info.name,
-      "wallet_kind": "generated",
-      "source": "openclaw_install"
-    }))
+    ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "wallet_created",
+          "wallet": info.name,
+          "wallet_kind": "generated",
+          "source": "openclaw_install"
+        }),
+        None,
+    )
     .ok();
 
     Keystore::release_lock(lock)?;
@@ -266,6 +266,50 @@ impl HyperliquidClient {
         }
         Ok(out)
     }
+
+    /// OHLCV candles for `coin` between `start_ms` and `end_ms` (inclusive), oldest first.
+    pub async fn candle_snapshot(
+        &self,
+        coin: &str,
+        interval: &str,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> eyre::Result<Vec<crate::price::Candle>> {
+        let v = self
+            .info(json!({
+                "type": "candleSnapshot",
+                "req": { "coin": coin, "interval": interval, "startTime": start_ms, "endTime": end_ms }
+            }))
+            .await?;
+        let arr = v.as_array().context("candleSnapshot must be array")?;
+        let num = |o: &serde_json::Map<String, Value>, k: &str| -> eyre::Result<f64> {
+            o.get(k)
+                .and_then(Value::as_str)
+                .and_then(|s| s.trim().parse::<f64>().ok())
+                .with_context(|| format!("candleSnapshot: invalid {k}"))
+        };
+        let mut out = Vec::with_capacity(arr.len());
+        for c in arr {
+            let o = c.as_object().context("candle must be object")?;
+            out.push(crate::price::Candle {
+                open_time_ms: o
+                    .get("t")
+                    .and_then(Value::as_i64)
+                    .context("candleSnapshot: missing t")?,
+                close_time_ms: o
+                    .get("T")
+                    .and_then(Value::as_i64)
+                    .context("candleSnapshot: missing T")?,
+                open: num(o, "o")?,
+                high: num(o, "h")?,
+                low: num(o, "l")?,
+                close: num(o, "c")?,
+                volume: num(o, "v")?,
+                trades: o.get("n").and_then(Value::as_u64),
+            });
+        }
+        Ok(out)
+    }
 }
 
 /// Delegate to [`crate::financial_math::float_to_wire`].
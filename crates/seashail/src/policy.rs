@@ -20,6 +20,52 @@ impl From<bool> for PolicyBool {
     }
 }
 
+/// How internal transfers (between Seashail-managed wallets/accounts) interact with policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InternalTransfersPolicy {
+    /// Skip policy entirely (hard blocks still apply).
+    Exempt,
+    /// Never block or prompt, but evaluate policy and record the would-be decision in the audit log.
+    Audit,
+    /// Full tiered approval, USD caps, and daily limits, like any other write.
+    Enforce,
+}
+
+impl InternalTransfersPolicy {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Exempt => "exempt",
+            Self::Audit => "audit",
+            Self::Enforce => "enforce",
+        }
+    }
+}
+
+/// A "do-not-send" recipient: sends to `address` are refused outright.
+///
+/// With `account_index` set, only sends from that account of the wallet are refused; otherwise
+/// every account is covered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendDenyEntry {
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_index: Option<u32>,
+    /// Free-form reminder of why the address is denied, echoed in the block error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl SendDenyEntry {
+    pub fn applies_to_account(&self, account_index: Option<u32>) -> bool {
+        match (self.account_index, account_index) {
+            (None, _) => true,
+            (Some(scoped), Some(idx)) => scoped == idx,
+            (Some(_), None) => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Policy {
@@ -83,11 +129,21 @@ pub struct Policy {
     /// tiering and USD caps (and will count toward daily limits).
     pub internal_transfers_exempt: PolicyBool,
 
+    /// Internal transfer handling: `exempt`, `audit`, or `enforce`.
+    ///
+    /// When unset, derived from `internal_transfers_exempt` (`true` => `exempt`, `false` =>
+    /// `enforce`). Use [`Policy::internal_transfers_mode`] to read the effective value.
+    pub internal_transfers_policy: Option<InternalTransfersPolicy>,
+
     /// Allow sending to any address (disables allowlisting).
     pub send_allow_any: PolicyBool,
     /// For token transfers, only allow sending to these addresses by default.
     /// If empty and `send_allow_any == false`, all sends are blocked.
     pub send_allowlist: Vec<String>,
+    /// Recipients that must never receive from this wallet, regardless of allowlisting, internal
+    /// transfer exemptions, or approval tier. Scope it to one wallet with a per-wallet policy
+    /// override, and to one account with [`SendDenyEntry::account_index`].
+    pub send_denylist: Vec<SendDenyEntry>,
 
     /// Allow `DeFi` contract interactions with any contract (disables allowlisting).
     pub contract_allow_any: PolicyBool,
@@ -99,6 +155,10 @@ pub struct Policy {
     /// Perpetuals risk controls.
     pub max_leverage: u32,
     pub max_usd_per_position: f64,
+    /// Aggregate perp caps across venues, checked against live positions before opening a new
+    /// one. `0` disables the cap.
+    pub max_total_perp_notional_usd: f64,
+    pub max_open_perp_positions: u32,
 
     /// NFT risk controls.
     pub max_usd_per_nft_tx: f64,
@@ -117,6 +177,15 @@ pub struct Policy {
     pub max_usd_per_stake_tx: f64,
     pub max_usd_per_liquidity_tx: f64,
     pub max_usd_per_prediction_tx: f64,
+    /// Maximum expected value lost on a bridge route (bridge fees, amount not delivered, and
+    /// estimated network fees), in basis points of the value sent. `0` disables the check.
+    pub max_bridge_loss_bps: u32,
+
+    /// Leveraged lending (Kamino multiply) risk controls. `max_lending_leverage` caps the requested
+    /// leverage, and positions whose projected health factor would open below
+    /// `min_lending_health_factor` are rejected (`0` disables the health check).
+    pub max_lending_leverage: f64,
+    pub min_lending_health_factor: f64,
 }
 
 impl Default for Policy {
@@ -147,14 +216,18 @@ impl Default for Policy {
             enable_ofac_sdn: true.into(),
 
             internal_transfers_exempt: true.into(),
+            internal_transfers_policy: None,
 
             send_allow_any: false.into(),
             send_allowlist: vec![],
+            send_denylist: vec![],
             contract_allow_any: false.into(),
             contract_allowlist: vec![],
 
             max_leverage: 3,
             max_usd_per_position: 100.0,
+            max_total_perp_notional_usd: 0.0,
+            max_open_perp_positions: 0,
             max_usd_per_nft_tx: 100.0,
 
             pumpfun_max_sol_per_buy: 0.1,
@@ -165,6 +238,41 @@ impl Default for Policy {
             max_usd_per_stake_tx: 500.0,
             max_usd_per_liquidity_tx: 100.0,
             max_usd_per_prediction_tx: 100.0,
+            max_bridge_loss_bps: 300, // 3.0%
+
+            max_lending_leverage: 3.0,
+            min_lending_health_factor: 1.2,
+        }
+    }
+}
+
+impl Policy {
+    /// Conservative starter policy applied by `onboard`: tighter USD caps than the default, and
+    /// the highest-risk surfaces (perps, pump.fun, prediction markets) off until the user opts in.
+    pub fn starter() -> Self {
+        Self {
+            auto_approve_usd: 5.0,
+            confirm_up_to_usd: 250.0,
+            hard_block_over_usd: 250.0,
+            max_usd_per_tx: 50.0,
+            max_usd_per_day: 200.0,
+            max_slippage_bps: 50, // 0.5%
+            enable_perps: false.into(),
+            enable_pumpfun: false.into(),
+            enable_prediction: false.into(),
+            max_leverage: 2,
+            max_lending_leverage: 2.0,
+            min_lending_health_factor: 1.5,
+            ..Self::default()
+        }
+    }
+
+    /// Effective internal transfer mode (explicit setting wins over the legacy boolean).
+    pub fn internal_transfers_mode(&self) -> InternalTransfersPolicy {
+        match self.internal_transfers_policy {
+            Some(m) => m,
+            None if self.internal_transfers_exempt.get() => InternalTransfersPolicy::Exempt,
+            None => InternalTransfersPolicy::Enforce,
         }
     }
 }
@@ -183,4 +291,53 @@ mod tests {
         assert!(p.enable_liquidity.get());
         assert!(p.enable_prediction.get());
     }
+
+    #[test]
+    fn starter_is_stricter_than_default() {
+        let d = Policy::default();
+        let s = Policy::starter();
+        assert!(s.auto_approve_usd < d.auto_approve_usd);
+        assert!(s.max_usd_per_tx < d.max_usd_per_tx);
+        assert!(s.max_usd_per_day < d.max_usd_per_day);
+        assert!(s.max_slippage_bps < d.max_slippage_bps);
+        assert!(s.max_lending_leverage < d.max_lending_leverage);
+        assert!(s.min_lending_health_factor > d.min_lending_health_factor);
+        assert!(!s.enable_perps.get() && !s.enable_pumpfun.get() && !s.enable_prediction.get());
+        assert!(s.deny_unknown_usd_value.get());
+        assert!(!s.send_allow_any.get() && !s.contract_allow_any.get());
+    }
+
+    #[test]
+    fn internal_transfers_mode_defaults_to_exempt_and_honors_legacy_flag() {
+        let mut p = Policy::default();
+        assert_eq!(p.internal_transfers_mode(), InternalTransfersPolicy::Exempt);
+
+        p.internal_transfers_exempt = false.into();
+        assert_eq!(
+            p.internal_transfers_mode(),
+            InternalTransfersPolicy::Enforce
+        );
+
+        p.internal_transfers_policy = Some(InternalTransfersPolicy::Audit);
+        assert_eq!(p.internal_transfers_mode(), InternalTransfersPolicy::Audit);
+    }
+
+    #[test]
+    fn send_denylist_entries_parse_and_scope_by_account() -> eyre::Result<()> {
+        let p: Policy = serde_json::from_value(serde_json::json!({
+            "send_denylist": [
+                { "address": "0x1111111111111111111111111111111111111111" },
+                { "address": "0x2222222222222222222222222222222222222222", "account_index": 1, "label": "cold storage" }
+            ]
+        }))?;
+        let [all, scoped] = p.send_denylist.as_slice() else {
+            eyre::bail!("expected two entries");
+        };
+        assert!(all.applies_to_account(Some(0)) && all.applies_to_account(None));
+        assert!(scoped.applies_to_account(Some(1)));
+        assert!(!scoped.applies_to_account(Some(0)) && !scoped.applies_to_account(None));
+        assert_eq!(scoped.label.as_deref(), Some("cold storage"));
+        assert!(Policy::default().send_denylist.is_empty());
+        Ok(())
+    }
 }
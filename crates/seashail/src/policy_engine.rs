@@ -1,4 +1,7 @@
-use crate::{errors::ToolError, policy::Policy};
+use crate::{
+    errors::ToolError,
+    policy::{InternalTransfersPolicy, Policy},
+};
 use eyre::Context as _;
 use std::str::FromStr as _;
 
@@ -37,6 +40,12 @@ const COMPOUND_COMET_POLYGON: &str = "0xf25212e676d1f7f89cd72ffee66158f541246445
 const LIDO_STETH: &str = "0xae7ab96520de3a18e5e111b5eaab095312d7fe84";
 const LIDO_WITHDRAWAL_QUEUE: &str = "0x889edc2edab5f40e902b864ad4d7ade8e412f9b1";
 
+// Canonical wrapped native tokens (`wrap_native` / `unwrap_native`).
+const WETH_ETHEREUM: &str = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+const WETH_ARBITRUM: &str = "0x82af49447d8a07e3bd95bd0d56f35241523fbab1";
+const WETH_OP_STACK: &str = "0x4200000000000000000000000000000000000006";
+const WMATIC_POLYGON: &str = "0x0d500b1d8e8ef31e21c99d1db9a6444d3adf1270";
+
 // Testnets (Wormhole).
 const WORMHOLE_TOKEN_BRIDGE_SEPOLIA: &str = "0xdb5492265f6038831e89f495670ff909ade94bd9";
 const WORMHOLE_TOKEN_BRIDGE_ARBITRUM_SEPOLIA: &str = "0xc7a204bdbfe983fcd8d8e61d02b475d4073ff97e";
@@ -54,6 +63,10 @@ pub enum WriteOp {
     ClosePerpPosition,
     ModifyPerpOrder,
     PlaceLimitOrder,
+    /// Resting spot limit (trigger) orders. Evaluated like swaps: the order's full notional counts
+    /// toward USD caps when it is placed.
+    PlaceSpotLimitOrder,
+    CancelSpotLimitOrder,
     BuyNft,
     SellNft,
     TransferNft,
@@ -73,8 +86,9 @@ pub enum WriteOp {
     ClosePrediction,
     /// Transfers between Seashail-managed wallets/accounts.
     ///
-    /// These are exempt by default (`policy.internal_transfers_exempt=true`), but can be made
-    /// subject to global USD caps + tiered approval when the exemption is disabled.
+    /// These are exempt by default (`policy.internal_transfers_policy=exempt`), but can be made
+    /// subject to global USD caps + tiered approval with `enforce` (or evaluated without blocking
+    /// with `audit`).
     InternalTransfer,
 }
 
@@ -85,6 +99,8 @@ pub const ALL_WRITE_OPS: &[WriteOp] = &[
     WriteOp::ClosePerpPosition,
     WriteOp::ModifyPerpOrder,
     WriteOp::PlaceLimitOrder,
+    WriteOp::PlaceSpotLimitOrder,
+    WriteOp::CancelSpotLimitOrder,
     WriteOp::BuyNft,
     WriteOp::SellNft,
     WriteOp::TransferNft,
@@ -105,10 +121,85 @@ pub const ALL_WRITE_OPS: &[WriteOp] = &[
     WriteOp::InternalTransfer,
 ];
 
+impl WriteOp {
+    /// Stable `snake_case` name, as accepted by `simulate_policy`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Send => "send",
+            Self::Swap => "swap",
+            Self::OpenPerpPosition => "open_perp_position",
+            Self::ClosePerpPosition => "close_perp_position",
+            Self::ModifyPerpOrder => "modify_perp_order",
+            Self::PlaceLimitOrder => "place_limit_order",
+            Self::PlaceSpotLimitOrder => "place_spot_limit_order",
+            Self::CancelSpotLimitOrder => "cancel_spot_limit_order",
+            Self::BuyNft => "buy_nft",
+            Self::SellNft => "sell_nft",
+            Self::TransferNft => "transfer_nft",
+            Self::BidNft => "bid_nft",
+            Self::PumpfunBuy => "pumpfun_buy",
+            Self::PumpfunSell => "pumpfun_sell",
+            Self::Bridge => "bridge",
+            Self::Lend => "lend",
+            Self::WithdrawLending => "withdraw_lending",
+            Self::Borrow => "borrow",
+            Self::RepayBorrow => "repay_borrow",
+            Self::Stake => "stake",
+            Self::Unstake => "unstake",
+            Self::ProvideLiquidity => "provide_liquidity",
+            Self::RemoveLiquidity => "remove_liquidity",
+            Self::PlacePrediction => "place_prediction",
+            Self::ClosePrediction => "close_prediction",
+            Self::InternalTransfer => "internal_transfer",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        ALL_WRITE_OPS.iter().copied().find(|op| op.as_str() == name)
+    }
+
+    /// The operation a tx-history entry of `type` was evaluated as, or `None` for entries that are
+    /// not policy-gated on their own (approvals, airdrops, bridge redeems, bookkeeping).
+    pub fn from_history_type(ty: &str) -> Option<Self> {
+        let op = match ty {
+            "send" => Self::Send,
+            "swap" => Self::Swap,
+            "spot_limit_place" => Self::PlaceSpotLimitOrder,
+            "spot_limit_cancel" => Self::CancelSpotLimitOrder,
+            "perp_open" => Self::OpenPerpPosition,
+            "perp_close" => Self::ClosePerpPosition,
+            "perp_modify" => Self::ModifyPerpOrder,
+            "perp_limit" => Self::PlaceLimitOrder,
+            "nft_buy" => Self::BuyNft,
+            "nft_sell" => Self::SellNft,
+            "nft_transfer" => Self::TransferNft,
+            "nft_bid" => Self::BidNft,
+            "pumpfun_buy" => Self::PumpfunBuy,
+            "pumpfun_sell" => Self::PumpfunSell,
+            "bridge" => Self::Bridge,
+            "lend" => Self::Lend,
+            "withdraw_lending" => Self::WithdrawLending,
+            "borrow" => Self::Borrow,
+            "repay_borrow" => Self::RepayBorrow,
+            "stake" => Self::Stake,
+            "unstake" => Self::Unstake,
+            "provide_liquidity" => Self::ProvideLiquidity,
+            "remove_liquidity" => Self::RemoveLiquidity,
+            "prediction_place" => Self::PlacePrediction,
+            "prediction_close" => Self::ClosePrediction,
+            "internal_transfer" | "internal_transfer_strict" => Self::InternalTransfer,
+            _ => return None,
+        };
+        Some(op)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PolicyContext<'a> {
     pub op: WriteOp,
     pub chain: &'a str,
+    pub account_index: Option<u32>,
     pub usd_value: f64,
     pub usd_value_known: bool,
     pub daily_used_usd: f64,
@@ -125,10 +216,15 @@ pub enum Approval {
 }
 
 pub fn evaluate(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<Approval, ToolError> {
+    // The do-not-send list is a hard block, so it runs before the internal transfer exemption.
+    check_recipient_denylist(policy, ctx.op, ctx.chain, ctx.account_index, ctx.to_address)?;
+
     // Internal transfers are "inside the security boundary" and cannot exfiltrate to an external
     // recipient. By default they are policy-exempt (still subject to hard blocks enforced by the
     // tool layer: scam blocklist + OFAC when enabled).
-    if ctx.op == WriteOp::InternalTransfer && policy.internal_transfers_exempt.get() {
+    if ctx.op == WriteOp::InternalTransfer
+        && policy.internal_transfers_mode() == InternalTransfersPolicy::Exempt
+    {
         return Ok(Approval::AutoApprove);
     }
 
@@ -160,6 +256,8 @@ fn check_unknown_usd(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<bool, T
         | WriteOp::ClosePerpPosition
         | WriteOp::ModifyPerpOrder
         | WriteOp::PlaceLimitOrder
+        | WriteOp::PlaceSpotLimitOrder
+        | WriteOp::CancelSpotLimitOrder
         | WriteOp::BuyNft
         | WriteOp::BidNft
         | WriteOp::PumpfunBuy
@@ -191,7 +289,9 @@ fn check_unknown_usd(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<bool, T
 fn check_op_specific(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<(), ToolError> {
     match ctx.op {
         WriteOp::Send => check_send(policy, ctx),
-        WriteOp::Swap => check_swap(policy, ctx),
+        WriteOp::Swap | WriteOp::PlaceSpotLimitOrder | WriteOp::CancelSpotLimitOrder => {
+            check_swap(policy, ctx)
+        }
         WriteOp::OpenPerpPosition
         | WriteOp::ClosePerpPosition
         | WriteOp::ModifyPerpOrder
@@ -338,6 +438,53 @@ fn check_perps(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<(), ToolError
     Ok(())
 }
 
+/// Open perp exposure across venues at the time a new position is requested.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerpExposure {
+    pub open_positions: u32,
+    pub total_notional_usd: f64,
+}
+
+pub const fn perp_exposure_caps_enabled(policy: &Policy) -> bool {
+    policy.max_open_perp_positions > 0 || policy.max_total_perp_notional_usd > 0.0_f64
+}
+
+/// Aggregate perp caps. [`evaluate`] only sees the single order, so perp write paths call this
+/// with live positions before opening another one.
+pub fn check_perp_exposure(
+    policy: &Policy,
+    current: PerpExposure,
+    new_notional_usd: f64,
+) -> Result<(), ToolError> {
+    if policy.max_open_perp_positions > 0
+        && current.open_positions >= policy.max_open_perp_positions
+    {
+        return Err(ToolError::new(
+            "policy_perp_position_count_cap",
+            format!(
+                "{} open perp positions; opening another exceeds max_open_perp_positions {}",
+                current.open_positions, policy.max_open_perp_positions
+            ),
+        ));
+    }
+    if policy.max_total_perp_notional_usd > 0.0_f64 {
+        let after = crate::financial_math::sum_f64(&[current.total_notional_usd, new_notional_usd]);
+        if after > policy.max_total_perp_notional_usd {
+            return Err(ToolError::new(
+                "policy_perp_notional_cap",
+                format!(
+                    "open perp notional {:.2} + {:.2} = {:.2} USD exceeds max_total_perp_notional_usd {:.2}",
+                    current.total_notional_usd,
+                    new_notional_usd,
+                    after,
+                    policy.max_total_perp_notional_usd
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn check_nft(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<(), ToolError> {
     if !policy.enable_nft.get() {
         return Err(ToolError::new(
@@ -398,6 +545,109 @@ fn check_bridge(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<(), ToolErro
     Ok(())
 }
 
+/// Bridge route loss cap. [`evaluate`] only sees the value sent, so bridge paths call this with
+/// the quoted loss (fees plus any amount not delivered) before asking for confirmation.
+pub fn check_bridge_loss(policy: &Policy, loss_bps: u32) -> Result<(), ToolError> {
+    if policy.max_bridge_loss_bps > 0 && loss_bps > policy.max_bridge_loss_bps {
+        return Err(ToolError::new(
+            "policy_bridge_loss_exceeded",
+            format!(
+                "expected bridge loss {loss_bps} bps exceeds max_bridge_loss_bps {}",
+                policy.max_bridge_loss_bps
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Apply the do-not-send list to recipient-bearing ops (sends, NFT transfers and internal
+/// transfers). `DeFi` ops put a protocol contract in `to_address`, so they are skipped.
+pub fn check_recipient_denylist(
+    policy: &Policy,
+    op: WriteOp,
+    chain: &str,
+    account_index: Option<u32>,
+    to: Option<&str>,
+) -> Result<(), ToolError> {
+    if !matches!(
+        op,
+        WriteOp::Send | WriteOp::TransferNft | WriteOp::InternalTransfer
+    ) {
+        return Ok(());
+    }
+    let Some(to) = to else {
+        return Ok(());
+    };
+    check_send_denylist(policy, chain, account_index, to)
+}
+
+/// Per-wallet "do-not-send" guard. Applied by [`evaluate`] for every op that moves funds or NFTs
+/// to a recipient, including policy-exempt internal transfers, and cannot be overridden by
+/// confirmation.
+pub fn check_send_denylist(
+    policy: &Policy,
+    chain: &str,
+    account_index: Option<u32>,
+    to: &str,
+) -> Result<(), ToolError> {
+    let Some(to_norm) = normalize_addr(chain, to) else {
+        return Ok(());
+    };
+    let hit = policy.send_denylist.iter().find(|e| {
+        e.applies_to_account(account_index)
+            && normalize_addr(chain, &e.address).is_some_and(|a| a == to_norm)
+    });
+    let Some(entry) = hit else {
+        return Ok(());
+    };
+    let reason = entry
+        .label
+        .as_deref()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map_or_else(String::new, |l| format!(" ({l})"));
+    Err(ToolError::new(
+        "policy_recipient_denylisted",
+        format!("recipient {to} is on this wallet's do-not-send list{reason}"),
+    ))
+}
+
+/// Leveraged lending guard. [`evaluate`] only sees the deposit, so the Kamino multiply path calls
+/// this with the requested leverage and the projected health factor (`None` when it could not be
+/// computed, which fails closed while the health check is enabled).
+pub fn check_lending_leverage(
+    policy: &Policy,
+    leverage: f64,
+    health_factor: Option<f64>,
+) -> Result<(), ToolError> {
+    if leverage > policy.max_lending_leverage {
+        return Err(ToolError::new(
+            "policy_lending_leverage_too_high",
+            format!(
+                "leverage {leverage:.2}x exceeds max_lending_leverage {:.2}x",
+                policy.max_lending_leverage
+            ),
+        ));
+    }
+    if policy.min_lending_health_factor <= 0.0_f64 {
+        return Ok(());
+    }
+    match health_factor {
+        None => Err(ToolError::new(
+            "policy_health_factor_unknown",
+            "projected health factor is unavailable; refusing leveraged position while min_lending_health_factor is set",
+        )),
+        Some(hf) if hf < policy.min_lending_health_factor => Err(ToolError::new(
+            "policy_health_factor_too_low",
+            format!(
+                "projected health factor {hf:.3} is below min_lending_health_factor {:.3}",
+                policy.min_lending_health_factor
+            ),
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
 fn check_lending(policy: &Policy, ctx: &PolicyContext<'_>) -> Result<(), ToolError> {
     if !policy.enable_lending.get() {
         return Err(ToolError::new(
@@ -559,30 +809,35 @@ fn built_in_allowed_contract(chain: &str, contract: &str) -> bool {
                 || c == WORMHOLE_TOKEN_BRIDGE_ETHEREUM
                 || c == LIDO_STETH
                 || c == LIDO_WITHDRAWAL_QUEUE
+                || c == WETH_ETHEREUM
         }
         "base" => {
             c == UNISWAP_BASE_ROUTER
                 || c == AAVE_V3_POOL_BASE
                 || c == COMPOUND_COMET_BASE
                 || c == WORMHOLE_TOKEN_BRIDGE_BASE
+                || c == WETH_OP_STACK
         }
         "arbitrum" => {
             c == UNISWAP_ROUTER
                 || c == AAVE_V3_POOL_ARBITRUM_OPTIMISM_POLYGON
                 || c == COMPOUND_COMET_ARBITRUM
                 || c == WORMHOLE_TOKEN_BRIDGE_ARBITRUM
+                || c == WETH_ARBITRUM
         }
         "optimism" => {
             c == UNISWAP_ROUTER
                 || c == AAVE_V3_POOL_ARBITRUM_OPTIMISM_POLYGON
                 || c == COMPOUND_COMET_OPTIMISM
                 || c == WORMHOLE_TOKEN_BRIDGE_OPTIMISM
+                || c == WETH_OP_STACK
         }
         "polygon" => {
             c == UNISWAP_ROUTER
                 || c == AAVE_V3_POOL_ARBITRUM_OPTIMISM_POLYGON
                 || c == COMPOUND_COMET_POLYGON
                 || c == WORMHOLE_TOKEN_BRIDGE_POLYGON
+                || c == WMATIC_POLYGON
         }
 
         // Wormhole bridge support on additional chains/testnets.
@@ -642,6 +897,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::Send,
             chain: "ethereum",
+            account_index: None,
             usd_value: 1.0,
             usd_value_known: true,
             daily_used_usd: 0.0,
@@ -666,6 +922,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::Send,
             chain: "ethereum",
+            account_index: None,
             usd_value: 5.0,
             usd_value_known: true,
             daily_used_usd: 0.0,
@@ -688,6 +945,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::Swap,
             chain: "ethereum",
+            account_index: None,
             usd_value: 1.0,
             usd_value_known: true,
             daily_used_usd: 0.0,
@@ -713,6 +971,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::Send,
             chain: "ethereum",
+            account_index: None,
             usd_value: 6.0,
             usd_value_known: true,
             daily_used_usd: 6.0,
@@ -728,6 +987,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn internal_transfer_exempt_skips_caps_but_audit_evaluates_them() -> eyre::Result<()> {
+        let ctx = PolicyContext {
+            op: WriteOp::InternalTransfer,
+            chain: "solana",
+            account_index: None,
+            usd_value: 5_000.0,
+            usd_value_known: true,
+            daily_used_usd: 0.0,
+            slippage_bps: None,
+            to_address: None,
+            contract: None,
+            leverage: None,
+        };
+        let exempt = Policy::default();
+        match evaluate(&exempt, &ctx) {
+            Ok(Approval::AutoApprove) => {}
+            Ok(other) => eyre::bail!("unexpected approval: {other:?}"),
+            Err(e) => eyre::bail!("unexpected error: {}", e.code),
+        }
+
+        let audit = Policy {
+            internal_transfers_policy: Some(InternalTransfersPolicy::Audit),
+            ..Default::default()
+        };
+        assert!(evaluate(&audit, &ctx).is_err());
+        Ok(())
+    }
+
     #[test]
     fn transfer_nft_unknown_usd_requires_confirm_even_when_deny_unknown_is_true() -> eyre::Result<()>
     {
@@ -739,6 +1027,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::TransferNft,
             chain: "ethereum",
+            account_index: None,
             usd_value: 0.0,
             usd_value_known: false,
             daily_used_usd: 0.0,
@@ -765,6 +1054,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::BuyNft,
             chain: "ethereum",
+            account_index: None,
             usd_value: 0.0,
             usd_value_known: false,
             daily_used_usd: 0.0,
@@ -792,6 +1082,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::BuyNft,
             chain: "ethereum",
+            account_index: None,
             usd_value: 1.0,
             usd_value_known: true,
             daily_used_usd: 0.0,
@@ -818,6 +1109,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::BuyNft,
             chain: "ethereum",
+            account_index: None,
             usd_value: 1.0,
             usd_value_known: true,
             daily_used_usd: 0.0,
@@ -839,6 +1131,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::ModifyPerpOrder,
             chain: "hyperliquid",
+            account_index: None,
             usd_value: 101.0,
             usd_value_known: true,
             daily_used_usd: 0.0,
@@ -864,6 +1157,7 @@ mod tests {
         let ctx = PolicyContext {
             op: WriteOp::ModifyPerpOrder,
             chain: "hyperliquid",
+            account_index: None,
             usd_value: 50.0,
             usd_value_known: true,
             daily_used_usd: 0.0,
@@ -879,6 +1173,99 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn perp_exposure_caps_block_count_and_notional() -> eyre::Result<()> {
+        let policy = Policy {
+            max_open_perp_positions: 2,
+            max_total_perp_notional_usd: 500.0,
+            ..Default::default()
+        };
+        let current = PerpExposure {
+            open_positions: 1,
+            total_notional_usd: 400.0,
+        };
+        assert!(check_perp_exposure(&policy, current, 100.0).is_ok());
+        let Err(err) = check_perp_exposure(&policy, current, 100.5) else {
+            eyre::bail!("expected notional cap error");
+        };
+        assert_eq!(err.code, "policy_perp_notional_cap");
+
+        let full = PerpExposure {
+            open_positions: 2,
+            ..current
+        };
+        let Err(err) = check_perp_exposure(&policy, full, 1.0) else {
+            eyre::bail!("expected position count cap error");
+        };
+        assert_eq!(err.code, "policy_perp_position_count_cap");
+
+        // Zero disables both caps.
+        assert!(!perp_exposure_caps_enabled(&Policy::default()));
+        assert!(check_perp_exposure(&Policy::default(), full, 1e9).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn bridge_loss_cap_blocks_above_limit() -> eyre::Result<()> {
+        let policy = Policy {
+            max_bridge_loss_bps: 100,
+            ..Default::default()
+        };
+        assert!(
+            check_bridge_loss(&policy, 100).is_ok(),
+            "at the cap is allowed"
+        );
+        let Err(err) = check_bridge_loss(&policy, 101) else {
+            eyre::bail!("expected bridge loss error");
+        };
+        assert_eq!(err.code, "policy_bridge_loss_exceeded");
+
+        let off = Policy {
+            max_bridge_loss_bps: 0,
+            ..Default::default()
+        };
+        assert!(
+            check_bridge_loss(&off, 10_000).is_ok(),
+            "0 disables the cap"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lending_leverage_guard_checks_leverage_and_health_factor() -> eyre::Result<()> {
+        let policy = Policy {
+            max_lending_leverage: 3.0,
+            min_lending_health_factor: 1.2,
+            ..Default::default()
+        };
+        assert!(check_lending_leverage(&policy, 3.0, Some(1.2)).is_ok());
+
+        let Err(err) = check_lending_leverage(&policy, 3.5, Some(2.0)) else {
+            eyre::bail!("expected leverage error");
+        };
+        assert_eq!(err.code, "policy_lending_leverage_too_high");
+
+        let Err(err) = check_lending_leverage(&policy, 2.0, Some(1.1)) else {
+            eyre::bail!("expected health factor error");
+        };
+        assert_eq!(err.code, "policy_health_factor_too_low");
+
+        let Err(err) = check_lending_leverage(&policy, 2.0, None) else {
+            eyre::bail!("expected unknown health factor error");
+        };
+        assert_eq!(err.code, "policy_health_factor_unknown");
+
+        let off = Policy {
+            min_lending_health_factor: 0.0,
+            ..policy
+        };
+        assert!(
+            check_lending_leverage(&off, 2.0, None).is_ok(),
+            "0 disables the health check"
+        );
+        Ok(())
+    }
+
     #[test]
     fn built_in_allowlist_includes_compound_v3_comet_markets() {
         assert!(built_in_allowed_contract(
@@ -896,4 +1283,122 @@ mod tests {
         ));
         assert!(built_in_allowed_contract("polygon", COMPOUND_COMET_POLYGON));
     }
+
+    #[test]
+    fn built_in_allowlist_includes_wrapped_native_tokens() {
+        assert!(built_in_allowed_contract("ethereum", WETH_ETHEREUM));
+        assert!(built_in_allowed_contract("arbitrum", WETH_ARBITRUM));
+        assert!(built_in_allowed_contract("base", WETH_OP_STACK));
+        assert!(built_in_allowed_contract("optimism", WETH_OP_STACK));
+        assert!(built_in_allowed_contract("polygon", WMATIC_POLYGON));
+        assert!(!built_in_allowed_contract("ethereum", WETH_OP_STACK));
+    }
+
+    #[test]
+    fn write_op_names_round_trip() {
+        for op in ALL_WRITE_OPS {
+            assert_eq!(WriteOp::from_name(op.as_str()), Some(*op));
+        }
+        assert_eq!(WriteOp::from_name("swap"), Some(WriteOp::Swap));
+        assert_eq!(WriteOp::from_name("not_an_op"), None);
+    }
+
+    #[test]
+    fn history_types_map_to_gated_ops() {
+        assert_eq!(WriteOp::from_history_type("swap"), Some(WriteOp::Swap));
+        assert_eq!(
+            WriteOp::from_history_type("perp_limit"),
+            Some(WriteOp::PlaceLimitOrder)
+        );
+        assert_eq!(
+            WriteOp::from_history_type("internal_transfer_strict"),
+            Some(WriteOp::InternalTransfer)
+        );
+        assert_eq!(WriteOp::from_history_type("approve"), None);
+        assert_eq!(WriteOp::from_history_type("bridge_redeem"), None);
+    }
+
+    #[test]
+    fn send_denylist_blocks_matching_recipient_for_scoped_accounts() -> eyre::Result<()> {
+        let cold = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd";
+        let policy = Policy {
+            send_denylist: vec![crate::policy::SendDenyEntry {
+                address: cold.to_owned(),
+                account_index: Some(0),
+                label: Some("cold storage".to_owned()),
+            }],
+            ..Default::default()
+        };
+
+        // Addresses compare case-insensitively on EVM.
+        let Err(err) = check_send_denylist(
+            &policy,
+            "ethereum",
+            Some(0),
+            "0xABCDEFABCDEFABCDEFABCDEFABCDEFABCDEFABCD",
+        ) else {
+            eyre::bail!("expected denylist block");
+        };
+        assert_eq!(err.code, "policy_recipient_denylisted");
+        assert!(err.message.contains("cold storage"));
+
+        assert!(check_send_denylist(&policy, "ethereum", Some(1), cold).is_ok());
+        assert!(check_send_denylist(
+            &policy,
+            "ethereum",
+            Some(0),
+            "0x2222222222222222222222222222222222222222"
+        )
+        .is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_blocks_denylisted_recipient_even_for_exempt_internal_transfers() -> eyre::Result<()>
+    {
+        let cold = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd";
+        let policy = Policy {
+            send_allow_any: true.into(),
+            send_denylist: vec![crate::policy::SendDenyEntry {
+                address: cold.to_owned(),
+                account_index: None,
+                label: None,
+            }],
+            ..Default::default()
+        };
+        for op in [WriteOp::Send, WriteOp::InternalTransfer] {
+            let ctx = PolicyContext {
+                op,
+                chain: "ethereum",
+                account_index: Some(0),
+                usd_value: 1.0,
+                usd_value_known: true,
+                daily_used_usd: 0.0,
+                slippage_bps: None,
+                to_address: Some(cold),
+                contract: None,
+                leverage: None,
+            };
+            let Err(err) = evaluate(&policy, &ctx) else {
+                eyre::bail!("expected denylist block for {}", op.as_str());
+            };
+            assert_eq!(err.code, "policy_recipient_denylisted");
+        }
+
+        // Non-recipient ops carry a protocol contract in `to_address` and are not gated.
+        let swap = PolicyContext {
+            op: WriteOp::Swap,
+            chain: "ethereum",
+            account_index: Some(0),
+            usd_value: 1.0,
+            usd_value_known: true,
+            daily_used_usd: 0.0,
+            slippage_bps: None,
+            to_address: Some(cold),
+            contract: None,
+            leverage: None,
+        };
+        assert!(evaluate(&policy, &swap).is_ok());
+        Ok(())
+    }
 }
@@ -2,9 +2,9 @@ use crate::{
     chains::{evm::EvmChain, solana::SolanaChain},
     config::SeashailConfig,
 };
-use eyre::Context as _;
+use eyre::{Context as _, ContextCompat as _};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tracing::warn;
 
@@ -142,6 +142,84 @@ pub async fn binance_price_usd(cfg: &SeashailConfig, symbol: &str) -> eyre::Resu
     Ok(p)
 }
 
+/// One OHLCV bar, normalized across venues (prices in USD, volume in base units).
+#[derive(Debug, Clone, Serialize)]
+pub struct Candle {
+    pub open_time_ms: i64,
+    pub close_time_ms: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub trades: Option<u64>,
+}
+
+fn kline_f64(row: &[serde_json::Value], idx: usize, label: &str) -> eyre::Result<f64> {
+    row.get(idx)
+        .and_then(serde_json::Value::as_str)
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .with_context(|| format!("binance kline: invalid {label}"))
+}
+
+fn kline_i64(row: &[serde_json::Value], idx: usize, label: &str) -> eyre::Result<i64> {
+    row.get(idx)
+        .and_then(serde_json::Value::as_i64)
+        .with_context(|| format!("binance kline: invalid {label}"))
+}
+
+/// Recent `{symbol}USDT` klines from Binance, oldest first. `interval` uses Binance notation
+/// (`1m`, `1h`, `1d`, ...); `limit` is passed through (Binance caps it at 1000).
+pub async fn binance_klines(
+    cfg: &SeashailConfig,
+    symbol: &str,
+    interval: &str,
+    limit: u32,
+) -> eyre::Result<Vec<Candle>> {
+    let base = cfg.http.binance_base_url.trim();
+    if !base.starts_with("https://") && !is_loopback_http(base) && !allow_insecure_http() {
+        eyre::bail!(
+            "binance_base_url must use https (or loopback); set SEASHAIL_ALLOW_INSECURE_HTTP=1 to override"
+        );
+    }
+
+    let pair = format!("{}USDT", symbol.trim().to_uppercase());
+    let url = format!(
+        "{}/api/v3/klines?symbol={}&interval={}&limit={}",
+        cfg.http.binance_base_url, pair, interval, limit
+    );
+    let client = Client::builder()
+        .timeout(Duration::from_secs(20))
+        .build()
+        .context("build http client")?;
+    let rows: Vec<Vec<serde_json::Value>> = client
+        .get(url)
+        .send()
+        .await
+        .context("binance klines request")?
+        .error_for_status()
+        .context("binance klines status")?
+        .json()
+        .await
+        .context("binance klines json")?;
+
+    // Row layout: [openTime, open, high, low, close, volume, closeTime, quoteVolume, trades, ...]
+    rows.iter()
+        .map(|row| {
+            Ok(Candle {
+                open_time_ms: kline_i64(row, 0, "open time")?,
+                close_time_ms: kline_i64(row, 6, "close time")?,
+                open: kline_f64(row, 1, "open")?,
+                high: kline_f64(row, 2, "high")?,
+                low: kline_f64(row, 3, "low")?,
+                close: kline_f64(row, 4, "close")?,
+                volume: kline_f64(row, 5, "volume")?,
+                trades: row.get(8).and_then(serde_json::Value::as_u64),
+            })
+        })
+        .collect()
+}
+
 async fn binance_price_usd_any(cfg: &SeashailConfig, symbols: &[&str]) -> eyre::Result<f64> {
     let mut last_err: Option<eyre::Report> = None;
     for s in symbols {
@@ -157,25 +235,11 @@ pub async fn native_token_price_usd(
     chain: &str,
     cfg: &SeashailConfig,
 ) -> eyre::Result<TokenPriceUsd> {
-    let symbol = match chain {
-        "solana" => "SOL",
-        "bitcoin" => "BTC",
-        "ethereum" | "base" | "arbitrum" | "optimism" | "sepolia" | "base-sepolia"
-        | "arbitrum-sepolia" | "optimism-sepolia" => "ETH",
-        // Polygon migrated to POL, but MATIC pricing is still commonly available; try both.
-        "polygon" | "polygon-amoy" => {
-            let usd = binance_price_usd_any(cfg, &["POL", "MATIC"]).await?;
-            return Ok(TokenPriceUsd {
-                usd,
-                source: PriceSource::Binance,
-            });
-        }
-        "bnb" | "bnb-testnet" => "BNB",
-        "avalanche" | "avalanche-fuji" => "AVAX",
-        "monad" | "monad-testnet" => "MON",
-        other => eyre::bail!("unknown native token for chain: {other}"),
-    };
-    let usd = binance_price_usd(cfg, symbol).await?;
+    let native = crate::chains::native::native_token(chain);
+    if !native.is_priced() {
+        eyre::bail!("unknown native token for chain: {chain}");
+    }
+    let usd = binance_price_usd_any(cfg, native.price_symbols).await?;
     Ok(TokenPriceUsd {
         usd,
         source: PriceSource::Binance,
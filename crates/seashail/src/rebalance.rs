@@ -0,0 +1,200 @@
+//! Target-weight portfolio rebalancing planner.
+//!
+//! Pure computation (no I/O): given current USD holdings and target weights, produce a small set
+//! of direct token-to-token swaps that brings every holding back inside the tolerance band.
+
+use crate::financial_math::{abs_f64, bps_to_fraction, mul_f64, sub_f64, sum_f64};
+
+/// Legs smaller than this are treated as settled (avoids dust swaps from float residue).
+const MIN_LEG_USD: f64 = 0.01_f64;
+
+#[derive(Debug, Clone)]
+pub struct Holding {
+    pub token: String,
+    pub usd_value: f64,
+    /// Target weight in basis points (all holdings must sum to 10,000).
+    pub target_bps: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Drift {
+    pub token: String,
+    pub usd_value: f64,
+    pub target_usd: f64,
+    /// `target_usd - usd_value` (positive means underweight).
+    pub delta_usd: f64,
+    pub within_tolerance: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedSwap {
+    pub token_in: String,
+    pub token_out: String,
+    pub usd_value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub total_usd: f64,
+    pub drifts: Vec<Drift>,
+    pub swaps: Vec<PlannedSwap>,
+    /// True when `max_swaps` stopped the plan before every leg was settled.
+    pub truncated: bool,
+}
+
+struct Leg {
+    token: String,
+    usd: f64,
+}
+
+fn sort_desc(legs: &mut [Leg]) {
+    legs.sort_by(|a, b| b.usd.total_cmp(&a.usd));
+}
+
+/// Compute the swaps needed to move `holdings` to their target weights.
+///
+/// Nothing is traded unless at least one holding drifts outside `tolerance_bps` of the total.
+/// When that happens, out-of-band holdings are matched greedily (largest surplus against largest
+/// deficit), which settles `n` holdings in at most `n - 1` swaps. If one side has no out-of-band
+/// holdings, in-band holdings with the opposite drift are used as counterparties.
+pub fn plan(holdings: &[Holding], tolerance_bps: u32, max_swaps: usize) -> Plan {
+    let values: Vec<f64> = holdings.iter().map(|h| h.usd_value).collect();
+    let total_usd = sum_f64(&values);
+    let band = mul_f64(total_usd, bps_to_fraction(tolerance_bps));
+
+    let mut drifts = Vec::with_capacity(holdings.len());
+    for h in holdings {
+        let target_usd = mul_f64(total_usd, bps_to_fraction(h.target_bps));
+        let delta_usd = sub_f64(target_usd, h.usd_value);
+        drifts.push(Drift {
+            token: h.token.clone(),
+            usd_value: h.usd_value,
+            target_usd,
+            delta_usd,
+            within_tolerance: abs_f64(delta_usd) <= band,
+        });
+    }
+
+    let legs = |want_surplus: bool, out_of_band_only: bool| -> Vec<Leg> {
+        drifts
+            .iter()
+            .filter(|d| (d.delta_usd < 0.0_f64) == want_surplus)
+            .filter(|d| !out_of_band_only || !d.within_tolerance)
+            .filter(|d| abs_f64(d.delta_usd) > MIN_LEG_USD)
+            .map(|d| Leg {
+                token: d.token.clone(),
+                usd: abs_f64(d.delta_usd),
+            })
+            .collect()
+    };
+    let mut sells = legs(true, true);
+    let mut buys = legs(false, true);
+    if sells.is_empty() && !buys.is_empty() {
+        sells = legs(true, false);
+    } else if buys.is_empty() && !sells.is_empty() {
+        buys = legs(false, false);
+    }
+
+    let mut swaps = vec![];
+    let mut truncated = false;
+    loop {
+        sort_desc(&mut sells);
+        sort_desc(&mut buys);
+        let (Some(s), Some(b)) = (sells.first_mut(), buys.first_mut()) else {
+            break;
+        };
+        if swaps.len() >= max_swaps {
+            truncated = true;
+            break;
+        }
+        let usd_value = s.usd.min(b.usd);
+        swaps.push(PlannedSwap {
+            token_in: s.token.clone(),
+            token_out: b.token.clone(),
+            usd_value,
+        });
+        s.usd = sub_f64(s.usd, usd_value);
+        b.usd = sub_f64(b.usd, usd_value);
+        sells.retain(|l| l.usd > MIN_LEG_USD);
+        buys.retain(|l| l.usd > MIN_LEG_USD);
+    }
+
+    Plan {
+        total_usd,
+        drifts,
+        swaps,
+        truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h(token: &str, usd_value: f64, target_bps: u32) -> Holding {
+        Holding {
+            token: token.to_owned(),
+            usd_value,
+            target_bps,
+        }
+    }
+
+    #[test]
+    fn plan_moves_surplus_into_deficit() {
+        let p = plan(
+            &[h("sol", 700.0_f64, 5_000), h("usdc", 300.0_f64, 5_000)],
+            100,
+            5,
+        );
+        assert_eq!(p.swaps.len(), 1);
+        let s = p.swaps.first();
+        assert_eq!(s.map(|s| s.token_in.as_str()), Some("sol"));
+        assert_eq!(s.map(|s| s.token_out.as_str()), Some("usdc"));
+        assert!(s.is_some_and(|s| abs_f64(sub_f64(s.usd_value, 200.0_f64)) < 1e-9_f64));
+        assert!(!p.truncated);
+    }
+
+    #[test]
+    fn plan_skips_when_within_tolerance() {
+        let p = plan(
+            &[h("sol", 505.0_f64, 5_000), h("usdc", 495.0_f64, 5_000)],
+            100,
+            5,
+        );
+        assert!(p.swaps.is_empty());
+        assert!(p.drifts.iter().all(|d| d.within_tolerance));
+    }
+
+    #[test]
+    fn plan_settles_n_holdings_in_at_most_n_minus_one_swaps() {
+        let p = plan(
+            &[
+                h("a", 600.0_f64, 2_500),
+                h("b", 200.0_f64, 2_500),
+                h("c", 150.0_f64, 2_500),
+                h("d", 50.0_f64, 2_500),
+            ],
+            50,
+            10,
+        );
+        assert!(p.swaps.len() <= 3);
+        let moved: Vec<f64> = p.swaps.iter().map(|s| s.usd_value).collect();
+        assert!(abs_f64(sub_f64(sum_f64(&moved), 350.0_f64)) < 1e-9_f64);
+    }
+
+    #[test]
+    fn plan_respects_max_swaps() {
+        let p = plan(
+            &[
+                h("a", 900.0_f64, 2_500),
+                h("b", 50.0_f64, 2_500),
+                h("c", 50.0_f64, 2_500),
+                h("d", 0.0_f64, 2_500),
+            ],
+            50,
+            1,
+        );
+        assert_eq!(p.swaps.len(), 1);
+        assert!(p.truncated);
+    }
+}
@@ -34,7 +34,7 @@ struct JsonRpcNotification {
     jsonrpc: String,
 }
 
-fn handle_initialize(
+async fn handle_initialize(
     req_id: Value,
     params: &Value,
     shared: &SharedState,
@@ -49,8 +49,8 @@ fn handle_initialize(
     }
     // Ensure a default wallet exists so agents can immediately query addresses/balances.
     shared
-        .ks
         .ensure_default_wallet()
+        .await
         .context("ensure default wallet")?;
     Ok(ok(
         req_id,
@@ -113,9 +113,9 @@ pub async fn run(network_override: Option<NetworkMode>) -> eyre::Result<()> {
         }
 
         let resp = match req.method.as_str() {
-            "initialize" => handle_initialize(req.id, &req.params, &shared, &mut conn)?,
+            "initialize" => handle_initialize(req.id, &req.params, &shared, &mut conn).await?,
             "ping" => ok(req.id, json!({})),
-            "tools/list" => ok(req.id, list_tools_result()),
+            "tools/list" => ok(req.id, list_tools_result(&shared.cfg)),
             "tools/call" => {
                 let name = req
                     .params
@@ -124,6 +124,11 @@ pub async fn run(network_override: Option<NetworkMode>) -> eyre::Result<()> {
                     .unwrap_or("");
                 let args = req.params.get("arguments").cloned().unwrap_or(Value::Null);
                 let id = req.id.clone();
+                conn.progress_token = req
+                    .params
+                    .get("_meta")
+                    .and_then(|m| m.get("progressToken"))
+                    .cloned();
                 match handle_tools_call(
                     id.clone(),
                     name,
@@ -98,6 +98,31 @@ impl SharedState {
         self.db.as_ref()
     }
 
+    /// Acquire the keystore write lock, waiting up to `write_lock_wait_ms` when configured.
+    pub async fn acquire_write_lock(&self) -> eyre::Result<std::fs::File> {
+        const MAX_WAIT_MS: u64 = 30_000;
+        match self.cfg.write_lock_wait_ms {
+            0 => self.ks.acquire_write_lock(),
+            ms => {
+                self.ks
+                    .acquire_write_lock_within(Duration::from_millis(ms.min(MAX_WAIT_MS)))
+                    .await
+            }
+        }
+    }
+
+    /// Ensure a wallet exists, creating the generated `default` wallet on first run under the
+    /// write lock.
+    pub async fn ensure_default_wallet(&self) -> eyre::Result<()> {
+        if !self.ks.list_wallets()?.is_empty() {
+            return Ok(());
+        }
+        let lock = self.acquire_write_lock().await?;
+        self.ks.create_default_wallet_locked()?;
+        Keystore::release_lock(lock)?;
+        Ok(())
+    }
+
     pub async fn ensure_db(&mut self) {
         if self.db.is_some() || self.db_init_attempted {
             return;
@@ -302,6 +327,8 @@ impl SharedState {
 pub struct ConnState {
     pub next_id: i64,
     pub network_override: Option<NetworkMode>,
+    /// `_meta.progressToken` of the tool call being served, if the client asked for progress.
+    pub progress_token: Option<serde_json::Value>,
 }
 
 impl ConnState {
@@ -309,6 +336,7 @@ impl ConnState {
         Self {
             next_id: 1_000_000,
             network_override: None,
+            progress_token: None,
         }
     }
 
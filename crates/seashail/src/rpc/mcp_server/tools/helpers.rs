@@ -1,7 +1,9 @@
 use crate::{
     chains::{evm::EvmChain, solana::SolanaChain},
-    config::NetworkMode,
-    errors::SeashailError,
+    config::{
+        EvmDefiConfig, NetworkMode, EVM_DEFI_DEADLINE_SECONDS_MAX, EVM_DEFI_DEADLINE_SECONDS_MIN,
+    },
+    errors::{SeashailError, ToolError},
     policy::Policy,
     wallet::ImportedKind,
 };
@@ -14,18 +16,6 @@ use super::super::SharedState;
 
 pub const MAX_REMOTE_TX_BYTES: u64 = 2 * 1024 * 1024;
 
-pub fn evm_native_symbol(chain: &str) -> &'static str {
-    match chain {
-        "ethereum" | "base" | "arbitrum" | "optimism" | "sepolia" | "base-sepolia"
-        | "arbitrum-sepolia" | "optimism-sepolia" => "ETH",
-        "polygon" | "polygon-amoy" => "POL",
-        "bnb" | "bnb-testnet" => "BNB",
-        "avalanche" | "avalanche-fuji" => "AVAX",
-        "monad" | "monad-testnet" => "MON",
-        _ => "NATIVE",
-    }
-}
-
 pub fn oneinch_supported_chain(chain: &str) -> bool {
     // 1inch coverage changes over time; keep this conservative and aligned with our defaults.
     matches!(
@@ -76,6 +66,7 @@ pub fn parse_policy(v: Value) -> eyre::Result<Policy> {
         || !p.max_usd_per_tx.is_finite()
         || !p.max_usd_per_day.is_finite()
         || !p.max_usd_per_position.is_finite()
+        || !p.max_total_perp_notional_usd.is_finite()
         || !p.max_usd_per_nft_tx.is_finite()
         || !p.pumpfun_max_sol_per_buy.is_finite()
         || !p.max_usd_per_bridge_tx.is_finite()
@@ -83,6 +74,8 @@ pub fn parse_policy(v: Value) -> eyre::Result<Policy> {
         || !p.max_usd_per_stake_tx.is_finite()
         || !p.max_usd_per_liquidity_tx.is_finite()
         || !p.max_usd_per_prediction_tx.is_finite()
+        || !p.max_lending_leverage.is_finite()
+        || !p.min_lending_health_factor.is_finite()
     {
         eyre::bail!("policy invalid: numeric limits must be finite");
     }
@@ -91,6 +84,7 @@ pub fn parse_policy(v: Value) -> eyre::Result<Policy> {
         || p.max_usd_per_tx < 0.0_f64
         || p.max_usd_per_day < 0.0_f64
         || p.max_usd_per_position < 0.0_f64
+        || p.max_total_perp_notional_usd < 0.0_f64
         || p.max_usd_per_nft_tx < 0.0_f64
         || p.pumpfun_max_sol_per_buy < 0.0_f64
         || p.max_usd_per_bridge_tx < 0.0_f64
@@ -98,6 +92,7 @@ pub fn parse_policy(v: Value) -> eyre::Result<Policy> {
         || p.max_usd_per_stake_tx < 0.0_f64
         || p.max_usd_per_liquidity_tx < 0.0_f64
         || p.max_usd_per_prediction_tx < 0.0_f64
+        || p.min_lending_health_factor < 0.0_f64
     {
         eyre::bail!("policy invalid: numeric limits must be non-negative");
     }
@@ -111,9 +106,18 @@ pub fn parse_policy(v: Value) -> eyre::Result<Policy> {
     if p.max_leverage == 0 {
         eyre::bail!("policy invalid: max_leverage must be >= 1");
     }
+    if p.max_lending_leverage < 1.0_f64 {
+        eyre::bail!("policy invalid: max_lending_leverage must be >= 1");
+    }
+    if p.max_bridge_loss_bps > 10_000 {
+        eyre::bail!("policy invalid: max_bridge_loss_bps must be <= 10000");
+    }
     if p.pumpfun_max_buys_per_hour == 0 {
         eyre::bail!("policy invalid: pumpfun_max_buys_per_hour must be >= 1");
     }
+    if p.send_denylist.iter().any(|e| e.address.trim().is_empty()) {
+        eyre::bail!("policy invalid: send_denylist entries require an address");
+    }
     Ok(p)
 }
 
@@ -149,6 +153,11 @@ pub fn decode_secret(
     }
 }
 
+/// The call's bookkeeping `note` for tx history entries (validated and trimmed before dispatch).
+pub fn tx_note(args: &Value) -> Option<&str> {
+    args.get("note").and_then(Value::as_str)
+}
+
 pub fn resolve_wallet_and_account(
     shared: &SharedState,
     args: &Value,
@@ -168,10 +177,20 @@ pub fn resolve_wallet_and_account(
             let idx = account_index.unwrap_or(w.last_active_account);
             (w, idx)
         }
-        _ => shared
-            .ks
-            .get_active_wallet()?
-            .ok_or_else(|| SeashailError::WalletNotFound("active".into()))?,
+        _ => {
+            // A per-chain active selection (see `set_active_wallet` with `chain`) wins over the
+            // global one for tools that target a chain.
+            let chain = args
+                .get("chain")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|c| !c.is_empty());
+            let active = match chain {
+                Some(c) => shared.ks.get_active_wallet_for_chain(c)?,
+                None => shared.ks.get_active_wallet()?,
+            };
+            active.ok_or_else(|| SeashailError::WalletNotFound("active".into()))?
+        }
     };
 
     let idx = account_index.unwrap_or(active_idx);
@@ -240,3 +259,39 @@ pub fn u256_pow10(exp: u32) -> U256 {
     }
     out
 }
+
+/// How long a native EVM DeFi transaction stays executable: the call's `deadline_seconds`, or
+/// else `evm_defi.deadline_seconds`.
+pub fn evm_defi_deadline_seconds(cfg: &EvmDefiConfig, args: &Value) -> Result<u64, ToolError> {
+    match args.get("deadline_seconds") {
+        None | Some(Value::Null) => Ok(cfg.effective_deadline_seconds()),
+        Some(v) => v
+            .as_u64()
+            .filter(|s| (EVM_DEFI_DEADLINE_SECONDS_MIN..=EVM_DEFI_DEADLINE_SECONDS_MAX).contains(s))
+            .ok_or_else(|| {
+                ToolError::new(
+                    "invalid_request",
+                    format!(
+                        "deadline_seconds must be between {EVM_DEFI_DEADLINE_SECONDS_MIN} and {EVM_DEFI_DEADLINE_SECONDS_MAX}"
+                    ),
+                )
+            }),
+    }
+}
+
+/// Unix-seconds deadline `secs` from now. Take it right before building the transaction so time
+/// spent at the confirmation prompt does not eat into the window.
+pub fn unix_deadline(secs: u64) -> u64 {
+    u64::try_from(chrono::Utc::now().timestamp())
+        .unwrap_or_default()
+        .saturating_add(secs)
+}
+
+/// Slippage (basis points) for a native EVM DeFi call: the call's `slippage_bps`, or else
+/// `evm_defi.slippage_bps`.
+pub fn evm_defi_slippage_bps(cfg: &EvmDefiConfig, args: &Value) -> u32 {
+    args.get("slippage_bps")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(cfg.slippage_bps)
+}
@@ -1,3 +1,4 @@
+use crate::errors::SeashailError;
 use crate::wallet::{ImportedKind, WalletKind};
 use alloy::signers::local::PrivateKeySigner;
 use eyre::Context as _;
@@ -40,6 +41,7 @@ where
             .context("build evm wallet")?;
             Ok(wallet)
         }
+        WalletKind::Watch => Err(SeashailError::WatchOnlyWallet(w.name.clone()).into()),
         WalletKind::Imported => {
             let key = ensure_unlocked(shared, conn, stdin, stdout).await?;
             let kind = w
@@ -104,6 +106,7 @@ where
             .map_err(|e| eyre::eyre!("derive solana keypair: {e}"))?;
             Ok(kp)
         }
+        WalletKind::Watch => Err(SeashailError::WatchOnlyWallet(w.name.clone()).into()),
         WalletKind::Imported => {
             let key = ensure_unlocked(shared, conn, stdin, stdout).await?;
             let kind = w
@@ -183,6 +186,7 @@ where
             entropy.zeroize();
             derive(&mnemonic)
         }
+        WalletKind::Watch => Err(SeashailError::WatchOnlyWallet(w.name.clone()).into()),
         WalletKind::Imported => {
             let key = ensure_unlocked(shared, conn, stdin, stdout).await?;
             let kind = w
@@ -7,6 +7,7 @@ mod policy;
 mod policy_confirm;
 mod read;
 mod schema;
+mod tx_confirmation;
 mod value_helpers;
 mod wallets;
 mod write;
@@ -17,7 +18,9 @@ use serde_json::Value;
 use tokio::io::BufReader;
 
 use super::jsonrpc::{err, ok, tool_err, JsonRpcResponse};
+use super::state::effective_network_mode;
 use super::{ConnState, SharedState};
+use crate::config::{is_evm_testnet_chain_name, NetworkMode};
 use crate::errors::ToolError;
 
 fn tool_triggers_first_run_setup(tool_name: &str) -> bool {
@@ -28,6 +31,7 @@ fn tool_triggers_first_run_setup(tool_name: &str) -> bool {
         "get_balance"
             | "get_portfolio"
             | "get_portfolio_analytics"
+            | "get_allowances_risk_report"
             | "estimate_gas"
             | "get_market_data"
             | "get_positions"
@@ -35,18 +39,33 @@ fn tool_triggers_first_run_setup(tool_name: &str) -> bool {
             | "get_wallet_info"
             | "get_deposit_info"
             | "set_active_wallet"
+            | "rename_wallet"
             | "add_account"
+            | "enable_smart_account"
             | "create_wallet_pool"
             | "export_shares"
             | "rotate_shares"
+            | "export_public_descriptor"
             | "request_airdrop"
             | "send_transaction"
+            | "create_nonce_account"
+            | "close_nonce_account"
+            | "get_nonce_account"
+            | "submit_signed_transaction"
+            | "sign_message"
             | "swap_tokens"
+            | "wrap_native"
+            | "unwrap_native"
+            | "rebalance_portfolio"
+            | "place_spot_limit_order"
+            | "cancel_spot_limit_order"
+            | "get_open_orders"
             | "transfer_between_wallets"
             | "fund_wallets"
             | "pumpfun_buy"
             | "pumpfun_sell"
             | "bridge_tokens"
+            | "attest_token"
             | "lend_tokens"
             | "withdraw_lending"
             | "borrow_tokens"
@@ -61,6 +80,7 @@ fn tool_triggers_first_run_setup(tool_name: &str) -> bool {
             | "get_prediction_positions"
             | "open_perp_position"
             | "close_perp_position"
+            | "close_all_positions"
             | "modify_perp_order"
             | "place_limit_order"
             | "get_nft_inventory"
@@ -71,10 +91,205 @@ fn tool_triggers_first_run_setup(tool_name: &str) -> bool {
     )
 }
 
+/// Group for tools missing from [`tool_group`]; never enabled, so a new tool stays hidden until it
+/// is assigned a group.
+const UNGROUPED_TOOL_GROUP: &str = "ungrouped";
+
+/// Safe-mode group a tool belongs to (see `enabled_tool_groups` / `disabled_tool_groups`).
+///
+/// Product-specific read tools live with their product so disabling e.g. `pumpfun` hides the
+/// whole surface, not just the write side.
+fn tool_group(tool_name: &str) -> &'static str {
+    match tool_name {
+        "get_network_mode"
+        | "set_network_mode"
+        | "get_capabilities"
+        | "get_testnet_faucet_links"
+        | "configure_rpc" => "network",
+        "get_policy" | "update_policy" | "simulate_policy" | "replay_policy" => "policy",
+        "list_wallets"
+        | "get_wallet_info"
+        | "get_deposit_info"
+        | "set_active_wallet"
+        | "rename_wallet"
+        | "add_account"
+        | "enable_smart_account"
+        | "create_wallet_pool"
+        | "create_wallet"
+        | "import_wallet"
+        | "export_shares"
+        | "rotate_shares"
+        | "export_public_descriptor"
+        | "onboard" => "wallets",
+        "get_market_data"
+        | "get_positions"
+        | "open_perp_position"
+        | "close_perp_position"
+        | "close_all_positions"
+        | "modify_perp_order"
+        | "place_limit_order" => "perps",
+        "get_nft_inventory" | "transfer_nft" | "buy_nft" | "sell_nft" | "bid_nft" => "nft",
+        "pumpfun_list_new_coins" | "pumpfun_get_coin_info" | "pumpfun_buy" | "pumpfun_sell" => {
+            "pumpfun"
+        }
+        "get_prediction_positions" | "place_prediction" | "close_prediction" => "predictions",
+        "request_airdrop"
+        | "send_transaction"
+        | "sign_message"
+        | "swap_tokens"
+        | "wrap_native"
+        | "unwrap_native"
+        | "rebalance_portfolio"
+        | "place_spot_limit_order"
+        | "cancel_spot_limit_order"
+        | "transfer_between_wallets"
+        | "fund_wallets"
+        | "create_nonce_account"
+        | "close_nonce_account"
+        | "submit_signed_transaction" => "spot",
+        "bridge_tokens" | "attest_token" | "lend_tokens" | "withdraw_lending" | "borrow_tokens"
+        | "repay_borrow" | "stake_tokens" | "unstake_tokens" | "provide_liquidity"
+        | "remove_liquidity" => "defi",
+        "get_balance"
+        | "get_portfolio"
+        | "get_portfolio_analytics"
+        | "diff_portfolio"
+        | "get_token_price"
+        | "get_supported_tokens"
+        | "inspect_token"
+        | "estimate_gas"
+        | "get_network_fees"
+        | "get_transaction_history"
+        | "get_allowances_risk_report"
+        | "get_nonce_account"
+        | "get_open_orders"
+        | "get_bridge_quote"
+        | "get_bridge_status"
+        | "get_defi_yield_pools"
+        | "get_lending_positions"
+        | "search_prediction_markets"
+        | "get_prediction_orderbook" => "read",
+        _ => UNGROUPED_TOOL_GROUP,
+    }
+}
+
+/// Write tools that record tx history and accept an optional bookkeeping `note`.
+fn tool_accepts_note(tool_name: &str) -> bool {
+    matches!(
+        tool_group(tool_name),
+        "perps" | "nft" | "pumpfun" | "predictions" | "spot" | "defi"
+    ) && !matches!(
+        tool_name,
+        "get_market_data"
+            | "get_positions"
+            | "get_nft_inventory"
+            | "pumpfun_list_new_coins"
+            | "pumpfun_get_coin_info"
+            | "get_prediction_positions"
+            | "sign_message"
+    )
+}
+
+const MAX_NOTE_CHARS: usize = 280;
+
+/// Parse the optional `note` of a write tool call: trimmed, empty treated as absent.
+fn parse_note(args: &Value) -> Result<Option<String>, ToolError> {
+    let note = match args.get("note") {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(s)) => s.trim(),
+        Some(_) => return Err(ToolError::new("invalid_request", "note must be a string")),
+    };
+    if note.chars().count() > MAX_NOTE_CHARS {
+        return Err(ToolError::new(
+            "invalid_request",
+            format!("note must be at most {MAX_NOTE_CHARS} characters"),
+        ));
+    }
+    Ok((!note.is_empty()).then(|| note.to_owned()))
+}
+
+/// Whether a tool is exposed under the configured safe-mode settings.
+pub fn tool_enabled(cfg: &crate::config::SeashailConfig, tool_name: &str) -> bool {
+    let group = tool_group(tool_name);
+    group != UNGROUPED_TOOL_GROUP && cfg.tool_enabled(tool_name, group)
+}
+
+/// Tools whose results carry balances, prices, or USD values.
+fn tool_is_value_bearing(tool_name: &str) -> bool {
+    !matches!(tool_group(tool_name), "network" | "policy" | "wallets")
+}
+
+/// Network a tool result refers to: an explicit EVM chain decides by name; otherwise the
+/// effective network mode applies.
+fn response_network(shared: &SharedState, conn: &ConnState, args: &Value) -> NetworkMode {
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+    if shared.cfg.rpc.evm_rpc_urls.contains_key(chain) {
+        if is_evm_testnet_chain_name(chain) {
+            NetworkMode::Testnet
+        } else {
+            NetworkMode::Mainnet
+        }
+    } else {
+        effective_network_mode(shared, conn)
+    }
+}
+
+/// Tag a successful tool result with `network` and, on testnet, zero its USD values unless
+/// `price_testnet_assets` is set (testnet tokens have no real value).
+fn annotate_network(
+    mut resp: JsonRpcResponse,
+    network: NetworkMode,
+    zero_usd: bool,
+) -> JsonRpcResponse {
+    let Some(content) = resp
+        .result
+        .as_mut()
+        .filter(|r| r.get("isError").and_then(Value::as_bool) == Some(false))
+        .and_then(|r| r.get_mut("content"))
+        .and_then(|c| c.get_mut(0_usize))
+    else {
+        return resp;
+    };
+    let Some(mut payload) = content
+        .get("text")
+        .and_then(Value::as_str)
+        .and_then(|t| serde_json::from_str::<Value>(t).ok())
+    else {
+        return resp;
+    };
+    let testnet = network == NetworkMode::Testnet;
+    let zeroed = if testnet && zero_usd {
+        value_helpers::zero_usd_values(&mut payload)
+    } else {
+        0
+    };
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert(
+            "network".into(),
+            Value::from(if testnet { "testnet" } else { "mainnet" }),
+        );
+        if zeroed > 0 {
+            obj.insert("usd_values_zeroed".into(), Value::Bool(true));
+            obj.insert(
+                "usd_note".into(),
+                Value::from("testnet assets have no real value; USD amounts are reported as 0 (set price_testnet_assets = true to keep them)"),
+            );
+        }
+    }
+    if let Some(c) = content.as_object_mut() {
+        c.insert("text".into(), Value::from(payload.to_string()));
+    }
+    resp
+}
+
 pub async fn handle_tools_call<R, W>(
     req_id: Value,
     tool_name: &str,
-    args: Value,
+    mut args: Value,
     shared: &mut SharedState,
     conn: &mut ConnState,
     stdin: &mut tokio::io::Lines<BufReader<R>>,
@@ -84,18 +299,71 @@ where
     R: tokio::io::AsyncRead + Unpin + Send,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
+    // Safe mode: disabled tools are not listed; reject direct calls too.
+    if !tool_enabled(&shared.cfg, tool_name) {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "tool_disabled",
+                format!("{tool_name} is disabled by configuration (safe mode)"),
+            )),
+        ));
+    }
+
     // On first run, create a generated wallet via elicitation before servicing wallet-dependent tools.
     if tool_triggers_first_run_setup(tool_name) && shared.ks.list_wallets()?.is_empty() {
         // Non-interactive: auto-create a machine-bound default wallet so the agent can immediately
         // show deposit addresses and balances. Users can opt into portable recovery (passphrase +
         // Share 3) later via `export_shares` / `rotate_shares`.
-        shared.ks.ensure_default_wallet()?;
+        shared.ensure_default_wallet().await?;
+    }
+
+    let note = if tool_accepts_note(tool_name) {
+        match parse_note(&args) {
+            Ok(n) => n,
+            Err(te) => return Ok(ok(req_id, tool_err(te))),
+        }
+    } else {
+        None
+    };
+
+    // Handlers read the validated note back via `helpers::tx_note` and pass it to each tx history
+    // append; tools that do not take a note never see one.
+    if let Some(obj) = args.as_object_mut() {
+        match note {
+            Some(n) => obj.insert("note".into(), Value::String(n)),
+            None => obj.remove("note"),
+        };
     }
 
+    let network = tool_is_value_bearing(tool_name).then(|| response_network(shared, conn, &args));
+    let resp = dispatch_tool(req_id, tool_name, args, shared, conn, stdin, stdout).await?;
+    let resp =
+        tx_confirmation::maybe_await_confirmation(resp, tool_name, shared, conn, stdout).await;
+    Ok(match network {
+        Some(n) => annotate_network(resp, n, !shared.cfg.price_testnet_assets),
+        None => resp,
+    })
+}
+
+async fn dispatch_tool<R, W>(
+    req_id: Value,
+    tool_name: &str,
+    args: Value,
+    shared: &mut SharedState,
+    conn: &mut ConnState,
+    stdin: &mut tokio::io::Lines<BufReader<R>>,
+    stdout: &mut W,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin + Send,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
     // Write DeFi tools: require explicit non-empty chain to avoid surprising defaults.
     if matches!(
         tool_name,
         "bridge_tokens"
+            | "attest_token"
             | "lend_tokens"
             | "withdraw_lending"
             | "borrow_tokens"
@@ -125,22 +393,31 @@ where
         | "configure_rpc" => network::handle(req_id, tool_name, args, shared, conn),
 
         // Policy tools
-        "get_policy" | "update_policy" => policy::handle(req_id, tool_name, &args, shared),
+        "get_policy" | "update_policy" | "simulate_policy" | "replay_policy" => {
+            policy::handle(req_id, tool_name, &args, shared)
+        }
 
         // Read-only tools
         "inspect_token"
+        | "get_supported_tokens"
         | "get_defi_yield_pools"
         | "get_balance"
         | "get_token_price"
         | "estimate_gas"
+        | "get_network_fees"
         | "get_portfolio"
         | "get_portfolio_analytics"
+        | "diff_portfolio"
         | "get_transaction_history"
         | "pumpfun_list_new_coins"
         | "pumpfun_get_coin_info"
         | "get_lending_positions"
         | "get_prediction_positions"
-        | "get_bridge_status" => {
+        | "get_open_orders"
+        | "get_bridge_status"
+        | "get_bridge_quote"
+        | "get_nonce_account"
+        | "get_allowances_risk_report" => {
             read::handle(req_id, tool_name, args, shared, conn, stdin, stdout).await
         }
 
@@ -160,21 +437,37 @@ where
         }
 
         // Wallet management tools
-        "list_wallets" | "get_wallet_info" | "get_deposit_info" | "set_active_wallet"
-        | "add_account" | "create_wallet_pool" | "create_wallet" | "import_wallet"
-        | "export_shares" | "rotate_shares" => {
-            wallets::handle(req_id, tool_name, args, shared, conn, stdin, stdout).await
-        }
+        "list_wallets"
+        | "get_wallet_info"
+        | "get_deposit_info"
+        | "set_active_wallet"
+        | "rename_wallet"
+        | "add_account"
+        | "enable_smart_account"
+        | "create_wallet_pool"
+        | "create_wallet"
+        | "import_wallet"
+        | "export_shares"
+        | "rotate_shares"
+        | "export_public_descriptor"
+        | "onboard" => wallets::handle(req_id, tool_name, args, shared, conn, stdin, stdout).await,
 
         // Write/signer tools
         "request_airdrop"
         | "send_transaction"
+        | "sign_message"
         | "swap_tokens"
+        | "wrap_native"
+        | "unwrap_native"
+        | "rebalance_portfolio"
+        | "place_spot_limit_order"
+        | "cancel_spot_limit_order"
         | "transfer_between_wallets"
         | "fund_wallets"
         | "pumpfun_buy"
         | "pumpfun_sell"
         | "bridge_tokens"
+        | "attest_token"
         | "lend_tokens"
         | "withdraw_lending"
         | "borrow_tokens"
@@ -184,7 +477,11 @@ where
         | "provide_liquidity"
         | "remove_liquidity"
         | "place_prediction"
-        | "close_prediction" => {
+        | "close_prediction"
+        | "close_all_positions"
+        | "create_nonce_account"
+        | "close_nonce_account"
+        | "submit_signed_transaction" => {
             write::handle(req_id, tool_name, args, shared, conn, stdin, stdout).await
         }
 
@@ -199,10 +199,17 @@ fn build_services_json(shared: &SharedState, f: &ConfigFlags) -> Value {
           "notes": "pump.fun discovery works via Solana RPC by default. Execution (buy/sell) uses an optional adapter endpoint for tx envelopes (https/loopback only)."
         },
         "wormholescan": {
-          "configured": !shared.cfg.http.wormholescan_api_base_url.trim().is_empty(),
-          "base_url": shared.cfg.http.wormholescan_api_base_url,
+          "configured": true,
+          "base_url": shared.cfg.http.wormholescan_base_url(),
           "notes": "Wormhole bridging uses Wormholescan to fetch signed VAAs (keyless) for status and best-effort redemption."
         },
+        "defillama_yields": {
+          "requires_api_key": false,
+          "optional_api_key_supported": true,
+          "api_key_configured": opt_configured(shared.cfg.http.defillama_api_key.as_ref()),
+          "source": shared.cfg.http.yield_pools_url_redacted(),
+          "notes": "get_defi_yield_pools uses the public DefiLlama yields dataset. Set http.defillama_api_key (Pro) or http.defillama_yields_url (mirror) if you hit rate limits."
+        },
         "defi_adapter": {
           "configured": f.defi.defi_adapter,
           "notes": "DeFi surfaces can execute agent-supplied tx envelopes. Some protocols also have native handlers (Aave v3 lending; Wormhole bridging) that do not require the adapter."
@@ -9,6 +9,7 @@ use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
 use super::super::helpers::{
     evm_addr_for_account, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+    tx_note,
 };
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::common::{
@@ -82,6 +83,7 @@ struct NftRecordCtx<'a> {
     marketplace: &'a str,
     usd_value: f64,
     usd_value_known: bool,
+    note: Option<&'a str>,
 }
 
 /// Record tx history and audit log for a completed Solana NFT trade, then respond.
@@ -94,12 +96,15 @@ fn solana_nft_record_and_respond(
     req_id: Value,
 ) -> eyre::Result<JsonRpcResponse> {
     let ty = nft_history_type(r.tool_name);
-    shared.ks.append_tx_history(&json!({
-        "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": ty,
-        "chain": "solana", "wallet": r.w_name, "account_index": r.idx,
-        "marketplace": r.marketplace, "usd_value": r.usd_value,
-        "usd_value_known": r.usd_value_known, "signature": sig.to_string()
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+            "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": ty,
+            "chain": "solana", "wallet": r.w_name, "account_index": r.idx,
+            "marketplace": r.marketplace, "usd_value": r.usd_value,
+            "usd_value_known": r.usd_value_known, "signature": sig.to_string()
+        }),
+        r.note,
+    )?;
     let _audit_log = shared.ks.append_audit_log(&json!({
         "ts": utc_now_iso(), "tool": r.tool_name, "wallet": r.w_name, "account_index": r.idx,
         "chain": "solana", "marketplace": r.marketplace, "usd_value": r.usd_value,
@@ -141,13 +146,16 @@ fn evm_nft_record_and_respond(
         outcome,
     } = e;
     let ty = nft_history_type(r.tool_name);
-    shared.ks.append_tx_history(&json!({
-        "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": ty,
-        "chain": r.chain, "wallet": r.w_name, "account_index": r.idx,
-        "marketplace": r.marketplace, "to": to_s, "value_wei": value_wei_s,
-        "usd_value": r.usd_value, "usd_value_known": r.usd_value_known,
-        "tx_hash": format!("{:#x}", tx_hash)
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+            "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": ty,
+            "chain": r.chain, "wallet": r.w_name, "account_index": r.idx,
+            "marketplace": r.marketplace, "to": to_s, "value_wei": value_wei_s,
+            "usd_value": r.usd_value, "usd_value_known": r.usd_value_known,
+            "tx_hash": format!("{:#x}", tx_hash)
+        }),
+        r.note,
+    )?;
     let _audit_log = shared.ks.append_audit_log(&json!({
         "ts": utc_now_iso(), "tool": r.tool_name, "wallet": r.w_name, "account_index": r.idx,
         "chain": r.chain, "marketplace": r.marketplace, "usd_value": r.usd_value,
@@ -376,6 +384,7 @@ where
         marketplace: &marketplace,
         usd_value: env.usd_value,
         usd_value_known: env.usd_value_known,
+        note: tx_note(args),
     };
     solana_nft_record_and_respond(shared, lock, &r, &sig, &outcome, req_id)
 }
@@ -575,6 +584,7 @@ where
         marketplace: &marketplace,
         usd_value: env.usd_value,
         usd_value_known: env.usd_value_known,
+        note: tx_note(args),
     };
     if shared.scam_blocklist_contains_evm(*to_addr).await {
         evm_nft_blocklist_audit(shared, &rec, &env.to_s);
@@ -660,7 +670,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(shared, &args)?;
     let chain = args.get("chain").and_then(|v| v.as_str()).unwrap_or("");
     let marketplace = args
@@ -6,14 +6,19 @@ use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
 use super::super::helpers::{
-    evm_addr_for_account, resolve_wallet_and_account, solana_fallback_urls,
+    evm_addr_for_account, resolve_wallet_and_account, solana_fallback_urls, tx_note,
 };
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::common::summarize_sim_error;
-use crate::chains::{evm::EvmChain, solana::SolanaChain};
+use crate::chains::{
+    evm::{EvmChain, NftStandard},
+    solana::SolanaChain,
+};
 use crate::errors::ToolError;
 use crate::keystore::{utc_now_iso, Keystore};
 use crate::policy_engine::WriteOp;
+use alloy::primitives::U256;
+use alloy::rpc::types::TransactionRequest;
 
 const USD_ZERO: f64 = 0.0_f64;
 
@@ -29,7 +34,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
     let res = handle_locked(req_id, args, shared, conn, stdin, stdout).await;
     let release_res = Keystore::release_lock(lock);
 
@@ -177,13 +182,10 @@ where
     .await?;
     let sig = sol.send_spl(&kp, to_pk, mint_pk, 1).await?;
 
-    ctx.shared.ks.append_tx_history(&tx_history_solana(
-        &ctx.wallet.name,
-        ctx.account_index,
-        mint,
-        to,
-        &sig,
-    ))?;
+    ctx.shared.ks.append_tx_history(
+        &tx_history_solana(&ctx.wallet.name, ctx.account_index, mint, to, &sig),
+        tx_note(args),
+    )?;
     let _audit_log = ctx.shared.ks.append_audit_log(&audit_broadcasted_solana(
         &ctx.wallet.name,
         ctx.account_index,
@@ -199,6 +201,156 @@ where
     ))
 }
 
+/// One `token_id` (and unit count) of an EVM NFT transfer. Counts other than 1 are ERC-1155 only.
+struct EvmNftItem {
+    token_id_s: String,
+    token_id: U256,
+    amount: U256,
+}
+
+/// Most token ids accepted in one ERC-1155 `safeBatchTransferFrom`.
+const MAX_BATCH_ITEMS: usize = 100;
+
+fn parse_evm_item(token_id_s: &str, amount_s: &str) -> Result<EvmNftItem, ToolError> {
+    let token_id = crate::chains::evm::parse_u256_dec(token_id_s)
+        .map_err(|e| ToolError::new("invalid_request", format!("invalid token_id: {e:#}")))?;
+    let amount = crate::chains::evm::parse_u256_dec(amount_s)
+        .map_err(|e| ToolError::new("invalid_request", format!("invalid amount: {e:#}")))?;
+    if amount.is_zero() {
+        return Err(ToolError::new("invalid_request", "amount must be >= 1"));
+    }
+    Ok(EvmNftItem {
+        token_id_s: token_id_s.trim().to_owned(),
+        token_id,
+        amount,
+    })
+}
+
+/// `items` (batch) if present, otherwise the single `token_id`/`amount` pair.
+fn parse_evm_items(args: &Value) -> Result<Vec<EvmNftItem>, ToolError> {
+    let str_of = |v: &Value, k: &str| -> String {
+        match v.get(k) {
+            Some(Value::String(s)) => s.trim().to_owned(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => String::new(),
+        }
+    };
+    let Some(items) = args.get("items").and_then(Value::as_array) else {
+        let token_id_s = str_of(args, "token_id");
+        if token_id_s.is_empty() {
+            return Err(ToolError::new(
+                "invalid_request",
+                "missing contract/token_id for EVM NFT transfer",
+            ));
+        }
+        let amount_s = str_of(args, "amount");
+        let amount_s = if amount_s.is_empty() {
+            "1".to_owned()
+        } else {
+            amount_s
+        };
+        return Ok(vec![parse_evm_item(&token_id_s, &amount_s)?]);
+    };
+    if args.get("token_id").is_some() {
+        return Err(ToolError::new(
+            "invalid_request",
+            "pass either token_id or items, not both",
+        ));
+    }
+    if items.is_empty() || items.len() > MAX_BATCH_ITEMS {
+        return Err(ToolError::new(
+            "invalid_request",
+            format!("items must contain 1..={MAX_BATCH_ITEMS} entries"),
+        ));
+    }
+    items
+        .iter()
+        .map(|it| {
+            let amount_s = str_of(it, "amount");
+            let amount_s = if amount_s.is_empty() {
+                "1".to_owned()
+            } else {
+                amount_s
+            };
+            parse_evm_item(&str_of(it, "token_id"), &amount_s)
+        })
+        .collect()
+}
+
+/// The caller's `standard` hint, else ERC-165 detection. Contracts that advertise neither are
+/// treated as ERC-721 only for a plain single-token transfer (the historical behavior).
+async fn resolve_nft_standard(
+    evm: &EvmChain,
+    contract: alloy::primitives::Address,
+    args: &Value,
+    items: &[EvmNftItem],
+) -> Result<NftStandard, ToolError> {
+    let hint = args
+        .get("standard")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let plain_single = matches!(items, [one] if one.amount == U256::from(1_u8));
+    let standard = match hint {
+        Some(h) => NftStandard::parse(h).ok_or_else(|| {
+            ToolError::new("invalid_request", "standard must be erc721 or erc1155")
+        })?,
+        None => match evm.detect_nft_standard(contract).await {
+            Ok(Some(s)) => s,
+            _ if plain_single => NftStandard::Erc721,
+            _ => {
+                return Err(ToolError::new(
+                    "invalid_request",
+                    "could not detect ERC-1155 support via ERC-165; pass standard explicitly",
+                ))
+            }
+        },
+    };
+    if standard == NftStandard::Erc721 && !plain_single {
+        return Err(ToolError::new(
+            "invalid_request",
+            "ERC-721 transfers move exactly one token_id; amounts and batches need standard=erc1155",
+        ));
+    }
+    Ok(standard)
+}
+
+fn build_evm_transfer_tx(
+    standard: NftStandard,
+    from: alloy::primitives::Address,
+    contract: alloy::primitives::Address,
+    to: alloy::primitives::Address,
+    items: &[EvmNftItem],
+) -> TransactionRequest {
+    match (standard, items) {
+        (NftStandard::Erc721, [one, ..]) => {
+            EvmChain::build_erc721_safe_transfer_from(from, contract, to, one.token_id)
+        }
+        (NftStandard::Erc1155, [one]) => {
+            EvmChain::build_erc1155_safe_transfer_from(from, contract, to, one.token_id, one.amount)
+        }
+        _ => EvmChain::build_erc1155_safe_batch_transfer_from(
+            from,
+            contract,
+            to,
+            items.iter().map(|i| i.token_id).collect(),
+            items.iter().map(|i| i.amount).collect(),
+        ),
+    }
+}
+
+/// `7` for ERC-721, `7 x3` per ERC-1155 id; comma-joined for batches.
+fn describe_items(standard: NftStandard, items: &[EvmNftItem]) -> String {
+    items
+        .iter()
+        .map(|i| match standard {
+            NftStandard::Erc721 => i.token_id_s.clone(),
+            NftStandard::Erc1155 => format!("{} x{}", i.token_id_s, i.amount),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 async fn handle_evm<R, W>(
     req_id: Value,
     args: &Value,
@@ -210,10 +362,9 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    // EVM (ERC-721 safeTransferFrom)
+    // EVM (ERC-721 safeTransferFrom; ERC-1155 safeTransferFrom / safeBatchTransferFrom)
     let contract = args.get("contract").and_then(Value::as_str).unwrap_or("");
-    let token_id_s = args.get("token_id").and_then(Value::as_str).unwrap_or("");
-    if contract.is_empty() || token_id_s.is_empty() {
+    if contract.is_empty() {
         return Ok(ok(
             req_id,
             tool_err(ToolError::new(
@@ -222,12 +373,15 @@ where
             )),
         ));
     }
+    let items = match parse_evm_items(args) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
 
     let evm = evm_chain_for(ctx.shared, chain)?;
     let from = evm_addr_for_account(ctx.wallet, ctx.account_index)?;
     let to_addr = EvmChain::parse_address(to)?;
     let contract_addr = EvmChain::parse_address(contract)?;
-    let token_id = crate::chains::evm::parse_u256_dec(token_id_s).context("parse token_id")?;
 
     if let Some(resp) = evm_blocklist_guard(
         req_id.clone(),
@@ -243,13 +397,21 @@ where
         return Ok(resp);
     }
 
+    let standard = match resolve_nft_standard(&evm, contract_addr, args, &items).await {
+        Ok(s) => s,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
+    let items_desc = describe_items(standard, &items);
+
     let outcome =
-        match confirm_transfer_nft_evm(&req_id, ctx, chain, to, contract, token_id_s).await? {
+        match confirm_transfer_nft_evm(&req_id, ctx, chain, to, contract, standard, &items_desc)
+            .await?
+        {
             Ok(v) => v,
             Err(resp) => return Ok(resp),
         };
 
-    let tx = EvmChain::build_erc721_safe_transfer_from(from, contract_addr, to_addr, token_id);
+    let tx = build_evm_transfer_tx(standard, from, contract_addr, to_addr, &items);
     if let Err(e) = evm.simulate_tx_strict(&tx).await {
         let _audit_log = ctx.shared.ks.append_audit_log(&audit_simulation_failed_evm(
             &ctx.wallet.name,
@@ -277,15 +439,19 @@ where
     .await?;
     let tx_hash = evm.send_tx(wallet, tx).await?;
 
-    ctx.shared.ks.append_tx_history(&tx_history_evm(
-        &ctx.wallet.name,
-        ctx.account_index,
-        chain,
-        contract,
-        token_id_s,
-        to,
-        tx_hash,
-    ))?;
+    ctx.shared.ks.append_tx_history(
+        &tx_history_evm(
+            &ctx.wallet.name,
+            ctx.account_index,
+            chain,
+            contract,
+            standard,
+            &items,
+            to,
+            tx_hash,
+        ),
+        tx_note(args),
+    )?;
     let _audit_log = ctx.shared.ks.append_audit_log(&audit_broadcasted_evm(
         &ctx.wallet.name,
         ctx.account_index,
@@ -296,7 +462,11 @@ where
 
     Ok(ok(
         req_id,
-        tool_ok(json!({ "chain": chain, "tx_hash": format!("{tx_hash:#x}") })),
+        tool_ok(json!({
+          "chain": chain,
+          "standard": standard.as_str(),
+          "tx_hash": format!("{tx_hash:#x}")
+        })),
     ))
 }
 
@@ -361,14 +531,17 @@ async fn confirm_transfer_nft_evm<R, W>(
     chain: &str,
     to: &str,
     contract: &str,
-    token_id_s: &str,
+    standard: NftStandard,
+    items_desc: &str,
 ) -> eyre::Result<Result<super::super::policy_confirm::WriteConfirmOutcome, JsonRpcResponse>>
 where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let summary =
-        format!("TRANSFER NFT on {chain} contract {contract} token_id {token_id_s} to {to}");
+    let summary = format!(
+        "TRANSFER NFT ({}) on {chain} contract {contract} token_id {items_desc} to {to}",
+        standard.as_str()
+    );
     match maybe_confirm_write(
         ctx.shared,
         ctx.conn,
@@ -552,11 +725,12 @@ fn tx_history_evm(
     account_index: u32,
     chain: &str,
     contract: &str,
-    token_id_s: &str,
+    standard: NftStandard,
+    items: &[EvmNftItem],
     to: &str,
     tx_hash: alloy::primitives::B256,
 ) -> Value {
-    json!({
+    let mut v = json!({
       "ts": utc_now_iso(),
       "day": Keystore::current_utc_day_key(),
       "type": "nft_transfer",
@@ -564,9 +738,24 @@ fn tx_history_evm(
       "wallet": wallet,
       "account_index": account_index,
       "contract": contract,
-      "token_id": token_id_s,
+      "standard": standard.as_str(),
       "to": to,
       "usd_value": USD_ZERO,
       "tx_hash": format!("{tx_hash:#x}")
-    })
+    });
+    if let Some(obj) = v.as_object_mut() {
+        match items {
+            [one] => {
+                obj.insert("token_id".into(), Value::from(one.token_id_s.as_str()));
+                obj.insert("amount_base".into(), Value::from(one.amount.to_string()));
+            }
+            _ => {
+                let ids: Vec<&str> = items.iter().map(|i| i.token_id_s.as_str()).collect();
+                let amounts: Vec<String> = items.iter().map(|i| i.amount.to_string()).collect();
+                obj.insert("token_ids".into(), json!(ids));
+                obj.insert("amounts_base".into(), json!(amounts));
+            }
+        }
+    }
+    v
 }
@@ -4,7 +4,8 @@ use crate::{
     financial_math,
     keystore::{utc_now_iso, Keystore},
     perps::{hyperliquid, jupiter_perps},
-    policy_engine::WriteOp,
+    policy::Policy,
+    policy_engine::{self, PerpExposure, WriteOp},
 };
 use eyre::Context as _;
 use serde_json::{json, Value};
@@ -17,9 +18,10 @@ use tokio::io::BufReader;
 use super::super::jsonrpc::{err, ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::state::effective_network_mode;
 use super::super::{ConnState, SharedState};
-use super::helpers::{resolve_wallet_and_account, u128_to_u64};
+use super::helpers::{resolve_wallet_and_account, tx_note, u128_to_u64};
 use super::key_loading::{load_evm_signer, load_solana_keypair};
 use super::policy_confirm::{maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest};
+use super::value_helpers::{parse_verbosity, project_response, tool_response_payload};
 
 fn hyperliquid_base_url(shared: &SharedState, conn: &ConnState) -> String {
     let mode = effective_network_mode(shared, conn);
@@ -56,6 +58,22 @@ fn parse_u32(v: Option<u64>, label: &'static str) -> Result<u32, ToolError> {
     u32::try_from(x).map_err(|_e| ToolError::new("invalid_request", format!("invalid {label}")))
 }
 
+/// Reject an explicitly requested leverage above the wallet's policy `max_leverage`, before any
+/// venue call. The policy engine re-checks at confirmation time.
+fn check_policy_leverage(
+    args: &Value,
+    leverage_u32: u32,
+    policy_max_leverage: u32,
+) -> Result<(), ToolError> {
+    if args.get("leverage").is_some() && leverage_u32 > policy_max_leverage {
+        return Err(ToolError::new(
+            "leverage_exceeds_policy",
+            format!("leverage {leverage_u32}x exceeds policy max_leverage {policy_max_leverage}x"),
+        ));
+    }
+    Ok(())
+}
+
 fn parse_provider(args: &Value) -> &str {
     args.get("provider")
         .and_then(|v| v.as_str())
@@ -269,6 +287,200 @@ async fn jupiter_market_data(
     ))
 }
 
+/// Candle intervals accepted by `get_market_data` in `candles` mode. Both Hyperliquid and the
+/// Binance fallback used for Jupiter Perps understand these names.
+const CANDLE_INTERVALS: &[(&str, i64)] = &[
+    ("1m", 60_000),
+    ("5m", 300_000),
+    ("15m", 900_000),
+    ("30m", 1_800_000),
+    ("1h", 3_600_000),
+    ("4h", 14_400_000),
+    ("12h", 43_200_000),
+    ("1d", 86_400_000),
+    ("1w", 604_800_000),
+];
+const DEFAULT_CANDLE_LOOKBACK: u32 = 100;
+const MAX_CANDLE_LOOKBACK: u32 = 500;
+
+struct CandleQuery {
+    market: String,
+    interval: &'static str,
+    interval_ms: i64,
+    lookback: u32,
+}
+
+fn wants_candles(args: &Value) -> Result<bool, ToolError> {
+    match args.get("mode").and_then(Value::as_str).map(str::trim) {
+        None | Some("markets") => Ok(false),
+        Some("candles") => Ok(true),
+        Some(other) => Err(ToolError::new(
+            "invalid_request",
+            format!("unsupported mode: {other} (expected markets or candles)"),
+        )),
+    }
+}
+
+fn parse_candle_query(args: &Value) -> Result<CandleQuery, ToolError> {
+    let market = args
+        .get("market")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    if market.is_empty() {
+        return Err(ToolError::new(
+            "invalid_request",
+            "candles mode requires market",
+        ));
+    }
+    let wanted = args
+        .get("interval")
+        .and_then(Value::as_str)
+        .map_or("1h", str::trim);
+    let Some(&(interval, interval_ms)) = CANDLE_INTERVALS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(wanted))
+    else {
+        let names: Vec<&str> = CANDLE_INTERVALS.iter().map(|(n, _)| *n).collect();
+        return Err(ToolError::new(
+            "invalid_request",
+            format!(
+                "unsupported interval: {wanted} (expected one of {})",
+                names.join(", ")
+            ),
+        ));
+    };
+    let lookback = match args.get("lookback") {
+        None | Some(Value::Null) => DEFAULT_CANDLE_LOOKBACK,
+        Some(v) => {
+            let n = v
+                .as_u64()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| ToolError::new("invalid_request", "lookback must be >= 1"))?;
+            u32::try_from(n)
+                .unwrap_or(MAX_CANDLE_LOOKBACK)
+                .min(MAX_CANDLE_LOOKBACK)
+        }
+    };
+    Ok(CandleQuery {
+        market: market.to_uppercase(),
+        interval,
+        interval_ms,
+        lookback,
+    })
+}
+
+/// Shape a candle fetch into a tool response. Fresh results are cached for 30s; on a fetch error
+/// the last cached set for the same query is served instead, mirroring the markets path.
+async fn candles_response(
+    req_id: Value,
+    provider: &str,
+    source: &str,
+    q: &CandleQuery,
+    shared: &mut SharedState,
+    fetched: eyre::Result<Vec<crate::price::Candle>>,
+) -> JsonRpcResponse {
+    shared.ensure_db().await;
+    let key = format!(
+        "perps:{provider}:candles:{}:{}:{}",
+        q.market, q.interval, q.lookback
+    );
+    let now_ms = crate::db::Db::now_ms().ok();
+    let (candles, cached) = match fetched {
+        Ok(mut candles) => {
+            let keep = usize::try_from(q.lookback).unwrap_or(usize::MAX);
+            if candles.len() > keep {
+                candles.drain(..candles.len() - keep);
+            }
+            let v = serde_json::to_value(&candles).unwrap_or(Value::Null);
+            if let (Some(db), Some(now_ms)) = (shared.db(), now_ms) {
+                let json_s = serde_json::to_string(&v).unwrap_or_default();
+                if !json_s.is_empty() {
+                    let _cache_write = db.upsert_json(&key, &json_s, now_ms, now_ms + 30_000).await;
+                }
+            }
+            (v, false)
+        }
+        Err(e) => {
+            let cached = if let (Some(db), Some(now_ms)) = (shared.db(), now_ms) {
+                match db.get_json_if_fresh(&key, now_ms).await {
+                    Ok(Some(row)) => serde_json::from_str::<Value>(&row.json).ok(),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            let Some(v) = cached else {
+                return ok(
+                    req_id,
+                    tool_err(ToolError::new(
+                        "provider_unavailable",
+                        format!("{provider} candle fetch failed: {e:#}"),
+                    )),
+                );
+            };
+            (v, true)
+        }
+    };
+    ok(
+        req_id,
+        tool_ok(json!({
+          "provider": provider,
+          "source": source,
+          "cached": cached,
+          "market": q.market,
+          "interval": q.interval,
+          "lookback": q.lookback,
+          "candles": candles
+        })),
+    )
+}
+
+async fn hyperliquid_candles(
+    req_id: Value,
+    q: &CandleQuery,
+    shared: &mut SharedState,
+    conn: &ConnState,
+) -> eyre::Result<JsonRpcResponse> {
+    let base_url = hyperliquid_base_url(shared, conn);
+    let client = hyperliquid::HyperliquidClient::new(&base_url)?;
+    let end_ms = crate::db::Db::now_ms()?;
+    let start_ms = end_ms.saturating_sub(q.interval_ms.saturating_mul(i64::from(q.lookback)));
+    let fetched = client
+        .candle_snapshot(&q.market, q.interval, start_ms, end_ms)
+        .await;
+    Ok(candles_response(req_id, "hyperliquid", "hyperliquid", q, shared, fetched).await)
+}
+
+/// Jupiter Perps has no candle API; its oracle prices track the majors, so Binance klines for the
+/// same symbol are served as a best-effort stand-in (`source: "binance"`).
+async fn jupiter_candles(
+    req_id: Value,
+    q: &CandleQuery,
+    shared: &mut SharedState,
+    conn: &ConnState,
+) -> eyre::Result<JsonRpcResponse> {
+    let mode = effective_network_mode(shared, conn);
+    if mode != crate::config::NetworkMode::Mainnet {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "provider_unavailable",
+                "jupiter_perps is only supported on Solana mainnet",
+            )),
+        ));
+    }
+    if jupiter_perps::custody_for_market_mainnet(&q.market).is_none() {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new("unknown_market", "unknown market")),
+        ));
+    }
+    let fetched =
+        crate::price::binance_klines(&shared.cfg, &q.market, q.interval, q.lookback).await;
+    Ok(candles_response(req_id, "jupiter_perps", "binance", q, shared, fetched).await)
+}
+
 async fn handle_get_market_data(
     req_id: Value,
     args: &Value,
@@ -276,6 +488,21 @@ async fn handle_get_market_data(
     conn: &ConnState,
 ) -> eyre::Result<JsonRpcResponse> {
     let provider = parse_provider(args);
+    let candles = match wants_candles(args) {
+        Ok(c) => c,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
+    if candles && matches!(provider, "hyperliquid" | "jupiter_perps") {
+        let q = match parse_candle_query(args) {
+            Ok(q) => q,
+            Err(te) => return Ok(ok(req_id, tool_err(te))),
+        };
+        return if provider == "hyperliquid" {
+            hyperliquid_candles(req_id, &q, shared, conn).await
+        } else {
+            jupiter_candles(req_id, &q, shared, conn).await
+        };
+    }
     match provider {
         "hyperliquid" => hyperliquid_market_data(req_id, args, shared, conn).await,
         "jupiter_perps" => jupiter_market_data(req_id, args, shared, conn).await,
@@ -597,7 +824,7 @@ async fn jupiter_positions_cache(
     }
 }
 
-async fn handle_get_positions(
+pub(super) async fn handle_get_positions(
     req_id: Value,
     args: &Value,
     shared: &mut SharedState,
@@ -617,6 +844,252 @@ async fn handle_get_positions(
     }
 }
 
+/// Open notional (USD) per position from a `get_positions` payload.
+fn position_notionals(provider: &str, payload: &Value) -> Vec<f64> {
+    let num = |v: Option<&Value>| {
+        v.and_then(|x| {
+            x.as_f64()
+                .or_else(|| x.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+        })
+    };
+    if provider == "hyperliquid" {
+        payload
+            .get("state")
+            .and_then(|st| st.get("assetPositions"))
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .filter_map(|p| p.get("position"))
+                    .filter(|p| num(p.get("szi")).is_some_and(|szi| szi != 0.0_f64))
+                    .map(|p| {
+                        financial_math::abs_f64(num(p.get("positionValue")).unwrap_or(0.0_f64))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        payload
+            .get("positions")
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .map(|p| num(p.get("size_usd")).unwrap_or(0.0_f64))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn pick(v: &Value, keys: &[&str]) -> Value {
+    let mut out = serde_json::Map::new();
+    for k in keys {
+        if let Some(x) = v.get(*k) {
+            out.insert((*k).to_owned(), x.clone());
+        }
+    }
+    Value::Object(out)
+}
+
+/// Compact `get_market_data`: price, funding and leverage per market, or bare OHLCV per candle.
+fn compact_market_data(payload: &Value) -> Value {
+    if let Some(candles) = payload.get("candles").and_then(Value::as_array) {
+        let bars: Vec<Value> = candles
+            .iter()
+            .map(|c| {
+                pick(
+                    c,
+                    &["open_time_ms", "open", "high", "low", "close", "volume"],
+                )
+            })
+            .collect();
+        let mut out = pick(
+            payload,
+            &["provider", "source", "cached", "market", "interval"],
+        );
+        if let Some(o) = out.as_object_mut() {
+            o.insert("candles".into(), json!(bars));
+        }
+        return out;
+    }
+    let markets: Vec<Value> = payload
+        .get("markets")
+        .and_then(Value::as_array)
+        .map(|a| {
+            a.iter()
+                .map(|m| {
+                    pick(
+                        m,
+                        &["coin", "mark_px", "funding", "max_leverage", "price_usd"],
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut out = pick(payload, &["provider", "cached"]);
+    if let Some(o) = out.as_object_mut() {
+        o.insert("markets".into(), json!(markets));
+    }
+    out
+}
+
+/// Compact `get_positions`: market/side/size/PnL per open position, plus account totals.
+fn compact_positions(payload: &Value) -> Value {
+    let mut out = pick(payload, &["provider", "cached"]);
+    let positions: Vec<Value> = if let Some(st) = payload.get("state") {
+        let num = |v: Option<&Value>| {
+            v.and_then(Value::as_str)
+                .and_then(|s| s.trim().parse::<f64>().ok())
+        };
+        if let (Some(o), Some(summary)) = (out.as_object_mut(), st.get("marginSummary")) {
+            o.insert(
+                "account_value_usd".into(),
+                summary.get("accountValue").cloned().unwrap_or(Value::Null),
+            );
+            o.insert(
+                "withdrawable_usd".into(),
+                st.get("withdrawable").cloned().unwrap_or(Value::Null),
+            );
+        }
+        st.get("assetPositions")
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .filter_map(|p| p.get("position"))
+                    .filter_map(|p| {
+                        let szi = num(p.get("szi"))?;
+                        if szi == 0.0_f64 {
+                            return None;
+                        }
+                        Some(json!({
+                          "market": p.get("coin"),
+                          "side": if szi > 0.0_f64 { "long" } else { "short" },
+                          "size": p.get("szi"),
+                          "entry_px": p.get("entryPx"),
+                          "size_usd": p.get("positionValue"),
+                          "unrealized_pnl_usd": p.get("unrealizedPnl"),
+                          "liquidation_px": p.get("liquidationPx"),
+                        }))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        payload
+            .get("positions")
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .map(|p| {
+                        pick(
+                            p,
+                            &["market", "side", "size_usd", "collateral_usd", "position"],
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    if let Some(o) = out.as_object_mut() {
+        o.insert("positions".into(), json!(positions));
+    }
+    out
+}
+
+/// Current perp exposure across venues, read through the `get_positions` paths. Fails closed:
+/// while aggregate caps are enabled, an unreadable venue blocks the open.
+async fn perp_exposure(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    args: &Value,
+) -> Result<PerpExposure, ToolError> {
+    let mut providers = vec!["hyperliquid"];
+    if effective_network_mode(shared, conn) == crate::config::NetworkMode::Mainnet {
+        providers.push("jupiter_perps");
+    }
+    let mut exposure = PerpExposure::default();
+    for provider in providers {
+        let mut a = args.clone();
+        if let Some(o) = a.as_object_mut() {
+            o.insert("provider".into(), json!(provider));
+        }
+        let resp = handle_get_positions(Value::Null, &a, shared, conn)
+            .await
+            .map_err(|e| {
+                ToolError::new(
+                    "perp_exposure_unavailable",
+                    format!("{provider} positions: {e:#}"),
+                )
+            })?;
+        let (is_error, payload) = tool_response_payload(&resp);
+        if is_error {
+            // No address on this venue means no positions there.
+            if payload.get("code").and_then(Value::as_str) == Some("missing_address") {
+                continue;
+            }
+            let msg = payload.get("message").and_then(Value::as_str).unwrap_or("");
+            return Err(ToolError::new(
+                "perp_exposure_unavailable",
+                format!(
+                    "cannot verify perp exposure caps; {provider} positions unavailable: {msg}"
+                ),
+            ));
+        }
+        let notionals = position_notionals(provider, &payload);
+        exposure.open_positions = exposure
+            .open_positions
+            .saturating_add(u32::try_from(notionals.len()).unwrap_or(u32::MAX));
+        financial_math::accum(
+            &mut exposure.total_notional_usd,
+            financial_math::sum_f64(&notionals),
+        );
+    }
+    Ok(exposure)
+}
+
+/// Current-vs-cap line for the confirmation summary (only the enabled caps).
+fn perp_exposure_summary(policy: &Policy, current: PerpExposure, new_notional_usd: f64) -> String {
+    let mut parts = vec![];
+    if policy.max_open_perp_positions > 0 {
+        parts.push(format!(
+            "{} -> {} of {} positions",
+            current.open_positions,
+            current.open_positions.saturating_add(1),
+            policy.max_open_perp_positions
+        ));
+    }
+    if policy.max_total_perp_notional_usd > 0.0_f64 {
+        parts.push(format!(
+            "{:.2} -> {:.2} of {:.2} USD notional",
+            current.total_notional_usd,
+            financial_math::sum_f64(&[current.total_notional_usd, new_notional_usd]),
+            policy.max_total_perp_notional_usd
+        ));
+    }
+    format!("\nPerp exposure (all venues): {}", parts.join(", "))
+}
+
+/// Enforce aggregate perp caps for a new position. Returns the summary line to show in the
+/// confirmation, or `None` when no cap is configured.
+async fn check_perp_exposure_caps(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    args: &Value,
+    wallet: &str,
+    new_notional_usd: f64,
+) -> Result<Option<String>, ToolError> {
+    let (policy, _) = shared.cfg.policy_for_wallet(Some(wallet));
+    if !policy_engine::perp_exposure_caps_enabled(&policy) {
+        return Ok(None);
+    }
+    let current = perp_exposure(shared, conn, args).await?;
+    policy_engine::check_perp_exposure(&policy, current, new_notional_usd)?;
+    Ok(Some(perp_exposure_summary(
+        &policy,
+        current,
+        new_notional_usd,
+    )))
+}
+
 // ---------------------------------------------------------------------------
 // write_perp: Jupiter helpers
 // ---------------------------------------------------------------------------
@@ -650,6 +1123,7 @@ struct JupiterPerpExecCtx<'a> {
     keypair: &'a solana_sdk::signer::keypair::Keypair,
     wallet_name: &'a str,
     idx: u32,
+    note: Option<&'a str>,
 }
 
 /// Resolve the side for a Jupiter close operation. If `side_s` is empty,
@@ -757,6 +1231,7 @@ struct JupiterPerpLogEntry<'a> {
     usd_value: f64,
     sig: &'a solana_sdk::signature::Signature,
     outcome: &'a WriteConfirmOutcome,
+    note: Option<&'a str>,
 }
 
 fn jupiter_perp_log(shared: &SharedState, entry: &JupiterPerpLogEntry<'_>) -> eyre::Result<()> {
@@ -769,19 +1244,22 @@ fn jupiter_perp_log(shared: &SharedState, entry: &JupiterPerpLogEntry<'_>) -> ey
         jupiter_perps::Side::Long => "long",
         jupiter_perps::Side::Short => "short",
     };
-    shared.ks.append_tx_history(&json!({
-        "ts": utc_now_iso(),
-        "day": Keystore::current_utc_day_key(),
-        "type": ty,
-        "provider": "jupiter_perps",
-        "chain": "solana",
-        "wallet": entry.wallet_name,
-        "account_index": entry.idx,
-        "market": entry.market,
-        "side": side_label,
-        "usd_value": entry.usd_value,
-        "txid": entry.sig.to_string()
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+            "ts": utc_now_iso(),
+            "day": Keystore::current_utc_day_key(),
+            "type": ty,
+            "provider": "jupiter_perps",
+            "chain": "solana",
+            "wallet": entry.wallet_name,
+            "account_index": entry.idx,
+            "market": entry.market,
+            "side": side_label,
+            "usd_value": entry.usd_value,
+            "txid": entry.sig.to_string()
+        }),
+        entry.note,
+    )?;
     let _audit_log = shared.ks.append_audit_log(&json!({
         "ts": utc_now_iso(),
         "tool": entry.tool_name,
@@ -940,6 +1418,7 @@ fn jupiter_perp_ok_response(
             usd_value: result.usd_value,
             sig: &result.sig,
             outcome: &result.outcome,
+            note: exec.note,
         },
     )?;
     Keystore::release_lock(lock)?;
@@ -974,6 +1453,7 @@ async fn jupiter_open_perp<R, W>(
     stdout: &mut W,
     ctx: PerpWriteCtx<'_>,
     exec: &JupiterPerpExecCtx<'_>,
+    exposure: Option<PerpExposure>,
 ) -> eyre::Result<JsonRpcResponse>
 where
     R: tokio::io::AsyncRead + Unpin,
@@ -999,10 +1479,21 @@ where
         jupiter_perps::Side::Long => "LONG",
         jupiter_perps::Side::Short => "SHORT",
     };
+    let (policy, _) = shared.cfg.policy_for_wallet(Some(exec.wallet_name));
+    let exposure_line = match exposure {
+        Some(current) => {
+            if let Err(te) = policy_engine::check_perp_exposure(&policy, current, usd_value) {
+                Keystore::release_lock(ctx.lock)?;
+                return Ok(ok(ctx.req_id, tool_err(te)));
+            }
+            perp_exposure_summary(&policy, current, usd_value)
+        }
+        None => String::new(),
+    };
     let summary = format!(
-        "OPEN PERP on Jupiter Perps: {side_label} {sym_upper} ({usd_value} USD, {}x)\n\n\
+        "OPEN PERP on Jupiter Perps: {side_label} {sym_upper} ({usd_value} USD, {}x; policy max {}x){exposure_line}\n\n\
          Note: Jupiter Perps uses a request-fulfillment model; this submits a request for keepers to execute.",
-        exec.setup.leverage_u32
+        exec.setup.leverage_u32, policy.max_leverage
     );
 
     let confirm_req = jupiter_confirm_request(
@@ -1316,6 +1807,7 @@ async fn jupiter_resolve_leverage_and_slippage(
     args: &Value,
     market: &str,
     side: jupiter_perps::Side,
+    policy_max_leverage: u32,
 ) -> Result<(u32, u64), ToolError> {
     let leverage_u32 = parse_u32(
         args.get("leverage")
@@ -1323,6 +1815,7 @@ async fn jupiter_resolve_leverage_and_slippage(
             .or(Some(1)),
         "leverage",
     )?;
+    check_policy_leverage(args, leverage_u32, policy_max_leverage)?;
     let slippage_bps = args
         .get("slippage_bps")
         .and_then(serde_json::Value::as_u64)
@@ -1456,14 +1949,38 @@ where
             return Ok(ok(req_id, tool_err(te)));
         }
     };
-    let (leverage_u32, price_slippage_u64) =
-        match jupiter_resolve_leverage_and_slippage(shared, args, market, side).await {
-            Ok(v) => v,
+    let (policy, _) = shared.cfg.policy_for_wallet(Some(&w.name));
+    let (leverage_u32, price_slippage_u64) = match jupiter_resolve_leverage_and_slippage(
+        shared,
+        args,
+        market,
+        side,
+        policy.max_leverage,
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(req_id, tool_err(te)));
+        }
+    };
+
+    // Aggregate caps need live positions (and `&mut` state); the new notional is checked in
+    // `jupiter_open_perp` once the size is known.
+    let exposure = if tool_name == "open_perp_position"
+        && policy_engine::perp_exposure_caps_enabled(&policy)
+    {
+        match perp_exposure(shared, conn, args).await {
+            Ok(v) => Some(v),
             Err(te) => {
                 Keystore::release_lock(lock)?;
                 return Ok(ok(req_id, tool_err(te)));
             }
-        };
+        }
+    } else {
+        None
+    };
 
     let setup = jupiter_build_setup(&pks, &owner, side, leverage_u32, price_slippage_u64);
     let exec = JupiterPerpExecCtx {
@@ -1472,6 +1989,7 @@ where
         keypair: &keypair,
         wallet_name: &w.name,
         idx,
+        note: tx_note(args),
     };
     let write_ctx = PerpWriteCtx {
         req_id,
@@ -1482,7 +2000,7 @@ where
 
     match tool_name {
         "open_perp_position" => {
-            jupiter_open_perp(shared, conn, stdin, stdout, write_ctx, &exec).await
+            jupiter_open_perp(shared, conn, stdin, stdout, write_ctx, &exec, exposure).await
         }
         "close_perp_position" => {
             jupiter_close_perp(shared, conn, stdin, stdout, write_ctx, &exec).await
@@ -1597,22 +2115,25 @@ fn hyperliquid_build_summary(
     coin: &str,
     usd_value: f64,
     leverage_u32: u32,
+    policy_max_leverage: u32,
     limit_px: &str,
 ) -> String {
     match tool_name {
         "open_perp_position" => format!(
-            "OPEN PERP on Hyperliquid: {} {} ({} USD, {}x)",
+            "OPEN PERP on Hyperliquid: {} {} ({} USD, {}x; policy max {}x)",
             if is_buy { "LONG" } else { "SHORT" },
             coin,
             usd_value,
-            leverage_u32
+            leverage_u32,
+            policy_max_leverage
         ),
         "place_limit_order" => format!(
-            "PLACE LIMIT ORDER on Hyperliquid: {} {} ({} USD, {}x) @ {}",
+            "PLACE LIMIT ORDER on Hyperliquid: {} {} ({} USD, {}x; policy max {}x) @ {}",
             if is_buy { "BUY" } else { "SELL" },
             coin,
             usd_value,
             leverage_u32,
+            policy_max_leverage,
             limit_px
         ),
         "close_perp_position" => format!("CLOSE PERP on Hyperliquid: {coin}"),
@@ -1633,6 +2154,7 @@ struct HyperliquidLogEntry<'a> {
     usd_value: f64,
     resp: &'a Value,
     outcome: &'a WriteConfirmOutcome,
+    note: Option<&'a str>,
 }
 
 fn hyperliquid_log_order(
@@ -1646,22 +2168,25 @@ fn hyperliquid_log_order(
         "place_limit_order" => "perp_limit",
         _ => "perp",
     };
-    shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": ty,
-      "chain": "hyperliquid",
-      "wallet": entry.wallet_name,
-      "account_index": entry.idx,
-      "provider": "hyperliquid",
-      "market": entry.coin,
-      "side": if entry.is_buy { "buy" } else { "sell" },
-      "size_asset": entry.sz_wire,
-      "limit_px": entry.limit_px,
-      "leverage": entry.leverage_u32,
-      "usd_value": entry.usd_value,
-      "response": entry.resp
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": ty,
+          "chain": "hyperliquid",
+          "wallet": entry.wallet_name,
+          "account_index": entry.idx,
+          "provider": "hyperliquid",
+          "market": entry.coin,
+          "side": if entry.is_buy { "buy" } else { "sell" },
+          "size_asset": entry.sz_wire,
+          "limit_px": entry.limit_px,
+          "leverage": entry.leverage_u32,
+          "usd_value": entry.usd_value,
+          "response": entry.resp
+        }),
+        entry.note,
+    )?;
     let _audit_log = shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(),
       "tool": entry.tool_name,
@@ -1709,10 +2234,11 @@ fn hyperliquid_parse_side(args: &Value) -> Result<bool, ToolError> {
     }
 }
 
-/// Validate leverage against venue max and parse slippage.
+/// Validate leverage against the policy cap and venue max, and parse slippage.
 fn hyperliquid_parse_leverage_slippage(
     args: &Value,
     max_leverage: u32,
+    policy_max_leverage: u32,
 ) -> Result<(u32, f64), ToolError> {
     let leverage_u32 = parse_u32(
         args.get("leverage")
@@ -1720,6 +2246,7 @@ fn hyperliquid_parse_leverage_slippage(
             .or(Some(1)),
         "leverage",
     )?;
+    check_policy_leverage(args, leverage_u32, policy_max_leverage)?;
     if max_leverage > 0 && leverage_u32 > max_leverage {
         return Err(ToolError::new(
             "invalid_request",
@@ -1805,6 +2332,7 @@ async fn hyperliquid_prepare_order<'a>(
     client: &hyperliquid::HyperliquidClient,
     markets: &'a [hyperliquid::HyperliquidMarket],
     evm_address: &str,
+    policy_max_leverage: u32,
 ) -> Result<HyperliquidPreparedOrder<'a>, ToolError> {
     let market = ctx
         .args
@@ -1815,7 +2343,8 @@ async fn hyperliquid_prepare_order<'a>(
         .ok_or_else(|| ToolError::new("unknown_market", "unknown market"))?;
 
     let is_buy = hyperliquid_parse_side(ctx.args)?;
-    let (leverage_u32, slippage) = hyperliquid_parse_leverage_slippage(ctx.args, m.max_leverage)?;
+    let (leverage_u32, slippage) =
+        hyperliquid_parse_leverage_slippage(ctx.args, m.max_leverage, policy_max_leverage)?;
 
     let (sz_wire, limit_px, tif, usd_value) =
         hyperliquid_resolve_size_and_price(ctx, client, m, evm_address, is_buy, slippage).await?;
@@ -1826,6 +2355,7 @@ async fn hyperliquid_prepare_order<'a>(
         &m.coin,
         usd_value,
         leverage_u32,
+        policy_max_leverage,
         &limit_px,
     );
 
@@ -1938,13 +2468,26 @@ where
         .get(idx as usize)
         .cloned()
         .unwrap_or_default();
-    let prep = match hyperliquid_prepare_order(&ctx, &client, &markets, &evm_address).await {
-        Ok(v) => v,
-        Err(te) => {
-            Keystore::release_lock(ctx.lock)?;
-            return Ok(ok(ctx.req_id, tool_err(te)));
+    let (policy, _) = shared.cfg.policy_for_wallet(Some(&w.name));
+    let mut prep =
+        match hyperliquid_prepare_order(&ctx, &client, &markets, &evm_address, policy.max_leverage)
+            .await
+        {
+            Ok(v) => v,
+            Err(te) => {
+                Keystore::release_lock(ctx.lock)?;
+                return Ok(ok(ctx.req_id, tool_err(te)));
+            }
+        };
+    if ctx.tool_name == "open_perp_position" {
+        match check_perp_exposure_caps(shared, conn, ctx.args, &w.name, prep.usd_value).await {
+            Ok(line) => prep.summary.push_str(&line.unwrap_or_default()),
+            Err(te) => {
+                Keystore::release_lock(ctx.lock)?;
+                return Ok(ok(ctx.req_id, tool_err(te)));
+            }
         }
-    };
+    }
 
     let confirm_req = hyperliquid_confirm_request(ctx.tool_name, &w.name, idx, &prep);
     let outcome = match maybe_confirm_write(shared, conn, stdin, stdout, &confirm_req).await {
@@ -1992,6 +2535,7 @@ where
             usd_value: prep.usd_value,
             resp: &resp,
             outcome: &outcome,
+            note: tx_note(ctx.args),
         },
     )?;
 
@@ -2019,7 +2563,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
 
     let provider = parse_provider(args);
     match provider {
@@ -2054,6 +2598,87 @@ where
     }
 }
 
+/// Cancel every resting Hyperliquid order for an account, returning one result per order.
+///
+/// Used by `close_all_positions`; cancels release margin but move no funds, so the caller's
+/// confirmation covers them.
+pub(super) async fn hyperliquid_cancel_all_orders<R, W>(
+    shared: &mut SharedState,
+    conn: &mut ConnState,
+    stdin: &mut tokio::io::Lines<BufReader<R>>,
+    stdout: &mut W,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+) -> eyre::Result<Vec<Value>>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let Some(user) = w.evm_addresses.get(idx as usize) else {
+        return Ok(vec![]);
+    };
+    let client = hyperliquid::HyperliquidClient::new(&hyperliquid_base_url(shared, conn))?;
+    let open = client
+        .info(json!({ "type": "openOrders", "user": user }))
+        .await
+        .context("hyperliquid openOrders")?;
+    let orders = open.as_array().cloned().unwrap_or_default();
+    if orders.is_empty() {
+        return Ok(vec![]);
+    }
+    let markets = client.meta_and_asset_ctxs().await?;
+    let is_mainnet = effective_network_mode(shared, conn) == crate::config::NetworkMode::Mainnet;
+    let signer = load_evm_signer(shared, conn, stdin, stdout, w, idx).await?;
+
+    let lock = shared.acquire_write_lock().await?;
+    let mut out = Vec::with_capacity(orders.len());
+    for o in &orders {
+        let coin = o.get("coin").and_then(Value::as_str).unwrap_or("");
+        let oid = o.get("oid").and_then(Value::as_u64);
+        let (Some(m), Some(oid)) = (find_market(&markets, coin), oid) else {
+            out.push(
+                json!({ "coin": coin, "oid": oid, "ok": false, "error": "unknown market or oid" }),
+            );
+            continue;
+        };
+        let res =
+            hyperliquid::post_cancel(&client, &signer, is_mainnet, None, None, m.asset, oid).await;
+        let entry = match res {
+            Ok(v) => {
+                let status = v
+                    .get("response")
+                    .and_then(|r| r.get("data"))
+                    .and_then(|d| d.get("statuses"))
+                    .and_then(Value::as_array)
+                    .and_then(|a| a.first());
+                let cancelled = v.get("status").and_then(Value::as_str) == Some("ok")
+                    && status.and_then(Value::as_str) == Some("success");
+                json!({ "coin": coin, "oid": oid, "ok": cancelled, "response": v })
+            }
+            Err(e) => json!({ "coin": coin, "oid": oid, "ok": false, "error": format!("{e:#}") }),
+        };
+        out.push(entry);
+    }
+    let _audit_log = shared.ks.append_audit_log(&json!({
+      "ts": utc_now_iso(),
+      "tool": "close_all_positions",
+      "wallet": w.name,
+      "account_index": idx,
+      "chain": "hyperliquid",
+      "usd_value": 0.0_f64,
+      "usd_value_known": true,
+      "policy_decision": null,
+      "confirm_required": true,
+      "confirm_result": "confirmed",
+      "txid": null,
+      "error_code": null,
+      "result": "orders_cancelled",
+      "cancelled": out.iter().filter(|r| r.get("ok").and_then(Value::as_bool) == Some(true)).count()
+    }));
+    Keystore::release_lock(lock)?;
+    Ok(out)
+}
+
 pub async fn handle<R, W>(
     req_id: Value,
     tool_name: &str,
@@ -2068,8 +2693,24 @@ where
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
     match tool_name {
-        "get_market_data" => handle_get_market_data(req_id, &args, shared, conn).await,
-        "get_positions" => handle_get_positions(req_id, &args, shared, conn).await,
+        "get_market_data" | "get_positions" => {
+            let verbosity = match parse_verbosity(&args) {
+                Ok(v) => v,
+                Err(te) => return Ok(ok(req_id, tool_err(te))),
+            };
+            if tool_name == "get_market_data" {
+                let resp = handle_get_market_data(req_id.clone(), &args, shared, conn).await?;
+                Ok(project_response(
+                    req_id,
+                    resp,
+                    verbosity,
+                    compact_market_data,
+                ))
+            } else {
+                let resp = handle_get_positions(req_id.clone(), &args, shared, conn).await?;
+                Ok(project_response(req_id, resp, verbosity, compact_positions))
+            }
+        }
         "open_perp_position"
         | "place_limit_order"
         | "close_perp_position"
@@ -1,9 +1,395 @@
+use std::collections::BTreeMap;
+
 use serde_json::{json, Value};
 
 use super::super::jsonrpc::{err, ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::SharedState;
 use super::helpers::parse_policy;
+use super::policy_confirm::hot_account_covers;
 use crate::errors::{SeashailError, ToolError};
+use crate::financial_math;
+use crate::keystore::Keystore;
+use crate::policy::{InternalTransfersPolicy, Policy};
+use crate::policy_engine::{self, Approval, PolicyContext, WriteOp, ALL_WRITE_OPS};
+
+/// Why the write tool would ask the user regardless of USD tiering or `hot_account`, mirroring the
+/// `force_confirm` inputs the write tools pass to `maybe_confirm_write`.
+fn forced_confirm_reason(
+    op: WriteOp,
+    chain: &str,
+    remote_tx: bool,
+    policy: &Policy,
+) -> Option<&'static str> {
+    match op {
+        WriteOp::Send if chain == "bitcoin" => Some("bitcoin sends always require confirmation"),
+        WriteOp::BuyNft | WriteOp::SellNft | WriteOp::BidNft => {
+            Some("marketplace-built nft transactions always require confirmation")
+        }
+        _ if remote_tx && policy.require_user_confirm_for_remote_tx.get() => Some(
+            "remotely built transaction requires confirmation (require_user_confirm_for_remote_tx)",
+        ),
+        _ => None,
+    }
+}
+
+/// Evaluate a synthetic operation against the effective policy and today's tx history.
+///
+/// Purely local: no RPC calls, no signing, nothing is written.
+fn simulate_policy(
+    req_id: Value,
+    args: &Value,
+    shared: &SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let op_s = args.get("op").and_then(Value::as_str).unwrap_or("");
+    let Some(op) = WriteOp::from_name(op_s) else {
+        let valid: Vec<&str> = ALL_WRITE_OPS.iter().copied().map(WriteOp::as_str).collect();
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_request",
+                format!("unknown op {op_s:?}; expected one of: {}", valid.join(", ")),
+            )),
+        ));
+    };
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    if chain.is_empty() {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new("invalid_request", "missing chain")),
+        ));
+    }
+    let usd_value = args.get("usd_value").and_then(Value::as_f64);
+    let as_u32 = |k: &str| {
+        args.get(k)
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+    };
+    let as_str = |k: &str| {
+        args.get(k)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+
+    let (wallet_name, default_idx) = match as_str("wallet") {
+        Some(w) => {
+            let Some(rec) = shared.ks.get_wallet_by_name(w)? else {
+                return Ok(ok(
+                    req_id,
+                    tool_err(ToolError::from(SeashailError::WalletNotFound(w.to_owned()))),
+                ));
+            };
+            (Some(w.to_owned()), Some(rec.last_active_account))
+        }
+        None => shared
+            .ks
+            .get_active_wallet()?
+            .map_or((None, None), |(w, idx)| (Some(w.name), Some(idx))),
+    };
+    let account_index = as_u32("account_index").or(default_idx);
+    let remote_tx = args
+        .get("remote_tx")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let (policy, is_override) = shared.cfg.policy_for_wallet(wallet_name.as_deref());
+    let forced = forced_confirm_reason(op, chain, remote_tx, &policy);
+    let day = Keystore::current_utc_day_key();
+    let used = shared
+        .ks
+        .daily_used_usd_filtered(&day, wallet_name.as_deref())?;
+
+    let ctx = PolicyContext {
+        op,
+        chain,
+        account_index,
+        usd_value: usd_value.unwrap_or(0.0_f64),
+        usd_value_known: usd_value.is_some(),
+        daily_used_usd: used,
+        slippage_bps: as_u32("slippage_bps"),
+        to_address: as_str("to"),
+        contract: as_str("contract"),
+        leverage: as_u32("leverage"),
+    };
+
+    let mut reasons: Vec<String> = vec![];
+    let (decision, code) = match policy_engine::evaluate(&policy, &ctx) {
+        Ok(approval) => {
+            if op == WriteOp::InternalTransfer
+                && policy.internal_transfers_mode() == InternalTransfersPolicy::Exempt
+            {
+                reasons.push(
+                    "internal transfers are exempt (internal_transfers_policy=exempt)".to_owned(),
+                );
+            } else if !ctx.usd_value_known {
+                reasons.push("usd value unknown; user confirmation is required".to_owned());
+            } else if approval == Approval::AutoApprove {
+                reasons.push(format!(
+                    "usd_value {:.2} <= auto_approve_usd {:.2}",
+                    ctx.usd_value, policy.auto_approve_usd
+                ));
+            } else {
+                reasons.push(format!(
+                    "usd_value {:.2} > auto_approve_usd {:.2}",
+                    ctx.usd_value, policy.auto_approve_usd
+                ));
+            }
+            if let Some(reason) = forced {
+                reasons.push(reason.to_owned());
+                ("confirm", None)
+            } else if approval == Approval::AutoApprove {
+                ("allow", None)
+            } else if hot_account_covers(
+                shared,
+                wallet_name.as_deref(),
+                account_index,
+                usd_value,
+                &day,
+            ) {
+                reasons.push(
+                    "hot account: within hot_account auto-approve and daily limits".to_owned(),
+                );
+                ("allow", None)
+            } else {
+                ("confirm", None)
+            }
+        }
+        Err(te) => {
+            reasons.push(te.message);
+            ("block", Some(te.code))
+        }
+    };
+
+    let remaining = financial_math::sub_f64(policy.max_usd_per_day, used).max(0.0_f64);
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "decision": decision,
+          "code": code,
+          "reasons": reasons,
+          "op": op.as_str(),
+          "chain": chain,
+          "usd_value": usd_value,
+          "forced_confirm": forced.is_some(),
+          "wallet": wallet_name,
+          "account_index": account_index,
+          "policy_source": if is_override { "wallet_override" } else { "global" },
+          "daily_used_usd": used,
+          "daily_remaining_usd": remaining,
+          "max_usd_per_day": policy.max_usd_per_day,
+          "note": "Simulation only. Hard blocks enforced at execution time (scam blocklist, OFAC, simulation failures) are not evaluated."
+        })),
+    ))
+}
+
+/// Tally of replayed decisions for one policy. Daily usage is rebuilt from the replayed events
+/// themselves: an event the policy would have blocked never counts toward the day's spend.
+#[derive(Default)]
+struct Replay {
+    daily_used: BTreeMap<(String, String), f64>,
+    allow: u64,
+    confirm: u64,
+    block: u64,
+    block_codes: BTreeMap<&'static str, u64>,
+}
+
+impl Replay {
+    fn evaluate(
+        &mut self,
+        policy: &Policy,
+        ctx: &PolicyContext<'_>,
+        day_key: (String, String),
+        counts_daily: bool,
+    ) -> (&'static str, Option<&'static str>) {
+        let used = self.daily_used.get(&day_key).copied().unwrap_or(0.0_f64);
+        let ctx = PolicyContext {
+            daily_used_usd: used,
+            ..ctx.clone()
+        };
+        match policy_engine::evaluate(policy, &ctx) {
+            Ok(approval) => {
+                if counts_daily {
+                    financial_math::accum(
+                        self.daily_used.entry(day_key).or_default(),
+                        ctx.usd_value,
+                    );
+                }
+                if approval == Approval::AutoApprove {
+                    self.allow += 1;
+                    ("allow", None)
+                } else {
+                    self.confirm += 1;
+                    ("confirm", None)
+                }
+            }
+            Err(te) => {
+                self.block += 1;
+                *self.block_codes.entry(te.code).or_default() += 1;
+                ("block", Some(te.code))
+            }
+        }
+    }
+
+    fn summary(&self) -> Value {
+        json!({
+          "allow": self.allow,
+          "confirm": self.confirm,
+          "block": self.block,
+          "block_codes": self.block_codes,
+        })
+    }
+}
+
+/// Replay a proposed policy over the local tx history and report how each past policy-gated
+/// event would have been classified, next to the classification under the current policy.
+///
+/// Purely local: reads `tx_history.jsonl` only; nothing is saved.
+fn replay_policy(
+    req_id: Value,
+    args: &Value,
+    shared: &SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let as_str = |k: &str| {
+        args.get(k)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let policy_v = args.get("policy").cloned().unwrap_or(Value::Null);
+    if policy_v.is_null() {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new("invalid_request", "missing policy")),
+        ));
+    }
+    let proposed = match parse_policy(policy_v) {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new("invalid_policy", format!("{e:#}"))),
+            ));
+        }
+    };
+    let wallet = as_str("wallet");
+    if let Some(w) = wallet {
+        if shared.ks.get_wallet_by_name(w)?.is_none() {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::from(SeashailError::WalletNotFound(w.to_owned()))),
+            ));
+        }
+    }
+    let limit = args
+        .get("limit")
+        .and_then(Value::as_u64)
+        .and_then(|v| usize::try_from(v).ok())
+        .unwrap_or(1000)
+        .clamp(1, 10_000);
+    let history = match shared.ks.read_tx_history_filtered(
+        limit,
+        wallet,
+        as_str("chain"),
+        None,
+        as_str("since_ts"),
+        as_str("until_ts"),
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new("invalid_request", format!("{e:#}"))),
+            ));
+        }
+    };
+
+    let mut proposed_replay = Replay::default();
+    let mut current_replay = Replay::default();
+    let (mut usd_unknown, mut not_gated, mut changed) = (0_u64, 0_u64, 0_u64);
+    let mut events = vec![];
+    for entry in &history {
+        let field = |k: &str| entry.get(k).and_then(Value::as_str).unwrap_or("");
+        let ty = field("type");
+        let Some(op) = WriteOp::from_history_type(ty) else {
+            not_gated += 1;
+            continue;
+        };
+        let (ts, chain, w) = (field("ts"), field("chain"), field("wallet"));
+        let Some(usd_value) = entry.get("usd_value").and_then(Value::as_f64) else {
+            // Older or partial entries without a USD value can't be placed on the USD tiers;
+            // report them instead of guessing.
+            usd_unknown += 1;
+            events.push(json!({
+              "ts": ts, "type": ty, "op": op.as_str(), "chain": chain, "wallet": w,
+              "usd_value": null, "decision": "usd_unknown",
+            }));
+            continue;
+        };
+        let to = entry.get("to").and_then(Value::as_str);
+        let contract = entry
+            .get("contract")
+            .and_then(Value::as_str)
+            .or(if op == WriteOp::Send { None } else { to });
+        let ctx = PolicyContext {
+            op,
+            chain,
+            account_index: entry
+                .get("account_index")
+                .and_then(Value::as_u64)
+                .and_then(|v| u32::try_from(v).ok()),
+            usd_value,
+            usd_value_known: true,
+            daily_used_usd: 0.0_f64,
+            slippage_bps: entry
+                .get("slippage_bps")
+                .and_then(Value::as_u64)
+                .and_then(|v| u32::try_from(v).ok()),
+            to_address: to,
+            contract,
+            leverage: entry
+                .get("leverage")
+                .and_then(Value::as_u64)
+                .and_then(|v| u32::try_from(v).ok()),
+        };
+        // Exempt internal transfers never count toward daily limits (see `daily_used_usd_filtered`).
+        let counts_daily = ty != "internal_transfer";
+        let day_key = (w.to_owned(), field("day").to_owned());
+        let (decision, code) =
+            proposed_replay.evaluate(&proposed, &ctx, day_key.clone(), counts_daily);
+        let (current_policy, _) = shared
+            .cfg
+            .policy_for_wallet(Some(w).filter(|s| !s.is_empty()));
+        let (current_decision, _) =
+            current_replay.evaluate(&current_policy, &ctx, day_key, counts_daily);
+        if decision != current_decision {
+            changed += 1;
+        }
+        events.push(json!({
+          "ts": ts, "type": ty, "op": op.as_str(), "chain": chain, "wallet": w,
+          "usd_value": usd_value, "decision": decision, "code": code,
+          "current_decision": current_decision,
+        }));
+    }
+
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "wallet": wallet,
+          "history_entries": history.len(),
+          "evaluated": proposed_replay.allow + proposed_replay.confirm + proposed_replay.block,
+          "proposed": proposed_replay.summary(),
+          "current": current_replay.summary(),
+          "changed": changed,
+          "usd_unknown": usd_unknown,
+          "not_policy_gated": not_gated,
+          "events": events,
+          "note": "Replay only. Daily limits are rebuilt from the replayed window; hard blocks enforced at execution time (scam blocklist, OFAC, simulation failures) and per-venue caps that need live state are not evaluated."
+        })),
+    ))
+}
 
 pub fn handle(
     req_id: Value,
@@ -12,6 +398,8 @@ pub fn handle(
     shared: &mut SharedState,
 ) -> eyre::Result<JsonRpcResponse> {
     match tool_name {
+        "simulate_policy" => simulate_policy(req_id, args, shared),
+        "replay_policy" => replay_policy(req_id, args, shared),
         "get_policy" => {
             let wallet = args.get("wallet").and_then(|v| v.as_str()).map(str::trim);
             if let Some(w) = wallet.filter(|s| !s.is_empty()) {
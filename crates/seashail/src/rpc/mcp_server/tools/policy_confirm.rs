@@ -1,6 +1,9 @@
 use crate::{
+    cli_output::format_usd,
     errors::{SeashailError, ToolError},
+    financial_math,
     keystore::Keystore,
+    policy::InternalTransfersPolicy,
     policy_engine::{self, PolicyContext, WriteOp},
 };
 use serde_json::json;
@@ -51,9 +54,47 @@ where
         .daily_used_usd_filtered(&day, req.wallet)
         .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
     let (policy, _is_override) = shared.cfg.policy_for_wallet(req.wallet);
-    let ctx = PolicyContext {
+
+    match policy_engine::evaluate(&policy, &policy_context(req, used)) {
+        Ok(policy_engine::Approval::AutoApprove) if !req.force_confirm => Ok(WriteConfirmOutcome {
+            policy_decision: "auto_approve",
+            confirm_required: false,
+            confirm_result: None,
+            forced_confirm: false,
+            daily_used_usd: used,
+        }),
+        Ok(_)
+            if !req.force_confirm
+                && hot_account_covers(
+                    shared,
+                    req.wallet,
+                    req.account_index,
+                    req.usd_value_known.then_some(req.usd_value),
+                    &day,
+                ) =>
+        {
+            audit_hot_account_auto_approved(shared, req, used);
+            Ok(WriteConfirmOutcome {
+                policy_decision: "hot_account_auto_approve",
+                confirm_required: false,
+                confirm_result: None,
+                forced_confirm: false,
+                daily_used_usd: used,
+            })
+        }
+        Ok(_) => confirm_with_user(shared, conn, stdin, stdout, req, used).await,
+        Err(te) => {
+            audit_policy_blocked(shared, req, used, &te);
+            Err(te)
+        }
+    }
+}
+
+fn policy_context<'a>(req: &'a WriteConfirmRequest<'a>, used: f64) -> PolicyContext<'a> {
+    PolicyContext {
         op: req.op,
         chain: req.chain,
+        account_index: req.account_index,
         usd_value: req.usd_value,
         usd_value_known: req.usd_value_known,
         daily_used_usd: used,
@@ -61,17 +102,51 @@ where
         to_address: req.to_address,
         contract: req.contract,
         leverage: req.leverage,
-    };
+    }
+}
 
-    match policy_engine::evaluate(&policy, &ctx) {
-        Ok(policy_engine::Approval::AutoApprove) if !req.force_confirm => Ok(WriteConfirmOutcome {
-            policy_decision: "auto_approve",
-            confirm_required: false,
-            confirm_result: None,
-            forced_confirm: false,
+/// Ask once for a whole multi-step plan (e.g. `rebalance_portfolio`), with `req.usd_value` as the
+/// plan total. The steps then go through [`confirm_plan_step`] instead of prompting one by one, so
+/// a decline can never leave the plan half-applied.
+pub async fn confirm_plan<R, W>(
+    shared: &SharedState,
+    conn: &mut ConnState,
+    stdin: &mut tokio::io::Lines<tokio::io::BufReader<R>>,
+    stdout: &mut W,
+    req: &WriteConfirmRequest<'_>,
+) -> Result<WriteConfirmOutcome, ToolError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let day = Keystore::current_utc_day_key();
+    let used = shared
+        .ks
+        .daily_used_usd_filtered(&day, req.wallet)
+        .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
+    confirm_with_user(shared, conn, stdin, stdout, req, used).await
+}
+
+/// One step of a plan the user already accepted via [`confirm_plan`]. Policy still applies in
+/// full (a blocked step fails as usual), but the user is not prompted again.
+pub fn confirm_plan_step(
+    shared: &SharedState,
+    req: &WriteConfirmRequest<'_>,
+) -> Result<WriteConfirmOutcome, ToolError> {
+    let day = Keystore::current_utc_day_key();
+    let used = shared
+        .ks
+        .daily_used_usd_filtered(&day, req.wallet)
+        .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
+    let (policy, _is_override) = shared.cfg.policy_for_wallet(req.wallet);
+    match policy_engine::evaluate(&policy, &policy_context(req, used)) {
+        Ok(_) => Ok(WriteConfirmOutcome {
+            policy_decision: "plan_confirmed",
+            confirm_required: true,
+            confirm_result: Some("confirmed"),
+            forced_confirm: req.force_confirm,
             daily_used_usd: used,
         }),
-        Ok(_) => confirm_with_user(shared, conn, stdin, stdout, req, used).await,
         Err(te) => {
             audit_policy_blocked(shared, req, used, &te);
             Err(te)
@@ -79,6 +154,98 @@ where
     }
 }
 
+/// Whether the configured hot account may skip the confirmation prompt for this write: it must
+/// be the designated `(wallet, account_index)`, have a known USD value within the (capped)
+/// hot limit, and keep that account's own spend today within the (capped) hot daily budget.
+pub fn hot_account_covers(
+    shared: &SharedState,
+    wallet: Option<&str>,
+    account_index: Option<u32>,
+    usd_value: Option<f64>,
+    day: &str,
+) -> bool {
+    let Some(hot) = shared.cfg.hot_account.as_ref() else {
+        return false;
+    };
+    let Some(usd_value) = usd_value else {
+        return false;
+    };
+    if !hot.is_account(wallet, account_index) || usd_value > hot.effective_auto_approve_usd() {
+        return false;
+    }
+    // Unreadable history means the budget cannot be checked; fall back to prompting.
+    let Ok(hot_used) =
+        shared
+            .ks
+            .daily_used_usd_for_account(day, hot.wallet.trim(), hot.account_index)
+    else {
+        return false;
+    };
+    financial_math::daily_total_usd(hot_used, usd_value) <= hot.effective_max_daily_usd()
+}
+
+/// Apply the wallet's internal transfer mode (`exempt` / `audit` / `enforce`).
+///
+/// Returns `None` when exempt. In `audit` mode policy is evaluated but never blocks or prompts;
+/// the would-be decision is returned as `policy_decision` for the caller's audit log entry.
+pub async fn confirm_internal_transfer<R, W>(
+    shared: &SharedState,
+    conn: &mut ConnState,
+    stdin: &mut tokio::io::Lines<tokio::io::BufReader<R>>,
+    stdout: &mut W,
+    req: &WriteConfirmRequest<'_>,
+) -> Result<Option<WriteConfirmOutcome>, ToolError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let (policy, _is_override) = shared.cfg.policy_for_wallet(req.wallet);
+    // The do-not-send list is a hard block, so it applies even when internal transfers are exempt.
+    if let Err(te) = policy_engine::check_recipient_denylist(
+        &policy,
+        req.op,
+        req.chain,
+        req.account_index,
+        req.to_address,
+    ) {
+        audit_policy_blocked(shared, req, 0.0_f64, &te);
+        return Err(te);
+    }
+    match policy.internal_transfers_mode() {
+        InternalTransfersPolicy::Exempt => Ok(None),
+        InternalTransfersPolicy::Enforce => maybe_confirm_write(shared, conn, stdin, stdout, req)
+            .await
+            .map(Some),
+        InternalTransfersPolicy::Audit => {
+            let day = Keystore::current_utc_day_key();
+            let used = shared
+                .ks
+                .daily_used_usd_filtered(&day, req.wallet)
+                .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
+            let policy_decision = match policy_engine::evaluate(&policy, &policy_context(req, used))
+            {
+                Ok(policy_engine::Approval::AutoApprove) => "audit_would_auto_approve",
+                Ok(policy_engine::Approval::RequiresUserConfirm) => "audit_would_confirm",
+                Err(te) => {
+                    tracing::info!(
+                        code = te.code,
+                        tool = req.tool,
+                        "internal transfer would be blocked by policy (audit mode)"
+                    );
+                    "audit_would_block"
+                }
+            };
+            Ok(Some(WriteConfirmOutcome {
+                policy_decision,
+                confirm_required: false,
+                confirm_result: None,
+                forced_confirm: false,
+                daily_used_usd: used,
+            }))
+        }
+    }
+}
+
 async fn confirm_with_user<R, W>(
     shared: &SharedState,
     conn: &mut ConnState,
@@ -98,14 +265,18 @@ where
       },
       "required": ["confirm"]
     });
+    let display = &shared.cfg.display;
     let usd_s = if req.usd_value_known {
-        format!("{:.2}", req.usd_value)
+        format_usd(req.usd_value, display)
     } else {
         "unknown".to_owned()
     };
     let msg = format!(
-        "Seashail requires confirmation.\n\n{}\n\nUSD value: {}\nDaily used (UTC): {:.2}\nChain: {}\n",
-        req.summary, usd_s, used, req.chain
+        "Seashail requires confirmation.\n\n{}\n\nUSD value: {}\nDaily used (UTC): {}\nChain: {}\n",
+        req.summary,
+        usd_s,
+        format_usd(used, display),
+        req.chain
     );
     let res = elicit_form(
         conn,
@@ -138,6 +309,32 @@ where
     })
 }
 
+fn audit_hot_account_auto_approved(shared: &SharedState, req: &WriteConfirmRequest<'_>, used: f64) {
+    let limit = shared
+        .cfg
+        .hot_account
+        .as_ref()
+        .map(crate::config::HotAccountConfig::effective_auto_approve_usd);
+    let _audit_log = shared.ks.append_audit_log(&json!({
+      "ts": crate::keystore::utc_now_iso(),
+      "tool": req.tool,
+      "wallet": req.wallet,
+      "account_index": req.account_index,
+      "chain": req.chain,
+      "usd_value": req.usd_value,
+      "usd_value_known": req.usd_value_known,
+      "daily_used_usd": used,
+      "policy_decision": "hot_account_auto_approve",
+      "hot_account_auto_approve_usd": limit,
+      "confirm_required": false,
+      "confirm_result": null,
+      "txid": null,
+      "error_code": null,
+      "result": "approved_hot_account",
+      "summary": req.summary
+    }));
+}
+
 fn audit_user_declined(shared: &SharedState, req: &WriteConfirmRequest<'_>, used: f64) {
     let _audit_log = shared.ks.append_audit_log(&json!({
       "ts": crate::keystore::utc_now_iso(),
@@ -0,0 +1,676 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    chains::evm::{
+        erc20_revoke_calldata, is_unlimited_allowance, permit2_revoke_calldata, u256_low_u128,
+        ApprovalPair, EvmChain, PERMIT2,
+    },
+    errors::ToolError,
+    financial_math::token_base_to_usd,
+    price,
+    wallet::{scan_start_height, WalletBirthday},
+};
+use alloy::primitives::{Address, U256};
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+use super::super::helpers::{evm_addr_for_account, resolve_wallet_and_account, u256_pow10};
+
+const DEFAULT_LOOKBACK_BLOCKS: u64 = 50_000;
+const MAX_LOOKBACK_BLOCKS: u64 = 2_000_000;
+/// Blocks per `eth_getLogs` request; most public RPCs reject wider ranges.
+const LOG_PAGE_BLOCKS: u64 = 10_000;
+/// Upper bound on allowance reads per report, to keep the tool responsive.
+const MAX_PAIRS: usize = 200;
+
+/// Spenders deployed at the same address on every chain Seashail ships defaults for.
+const WELL_KNOWN_SPENDERS: &[(&str, &str)] = &[
+    (PERMIT2, "Uniswap Permit2"),
+    (
+        "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD",
+        "Uniswap Universal Router",
+    ),
+    (
+        "0x111111125421cA6dc452d289314280a0f8842A65",
+        "1inch Aggregation Router v6",
+    ),
+    (
+        "0x1111111254EEB25477B68fb85Ed929f73A960582",
+        "1inch Aggregation Router v5",
+    ),
+];
+
+/// Permit2 spenders probed for every token approved to Permit2, on top of those in the logs.
+const PERMIT2_KNOWN_SPENDERS: &[&str] = &["0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl Risk {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApprovalKind {
+    Erc20,
+    Permit2,
+}
+
+/// Balance, decimals, symbol and best-effort USD price of a token the owner has approved.
+#[derive(Debug, Clone)]
+struct TokenInfo {
+    balance: U256,
+    decimals: u8,
+    symbol: String,
+    usd_price: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SpenderInfo {
+    is_contract: Option<bool>,
+    blocklisted: bool,
+    ofac_sdn: bool,
+}
+
+struct Finding {
+    kind: ApprovalKind,
+    pair: ApprovalPair,
+    allowance: U256,
+    expiration: Option<u64>,
+    /// For Permit2, the lower of the Permit2 amount and the token's allowance to Permit2.
+    effective_allowance: U256,
+}
+
+/// Explicit `lookback_blocks`, or `None` to derive the range from the wallet birthday.
+fn parse_lookback(args: &Value) -> Result<Option<u64>, ToolError> {
+    match args.get("lookback_blocks") {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .filter(|n| (1..=MAX_LOOKBACK_BLOCKS).contains(n))
+            .map(Some)
+            .ok_or_else(|| {
+                ToolError::new(
+                    "invalid_request",
+                    format!("lookback_blocks must be between 1 and {MAX_LOOKBACK_BLOCKS}"),
+                )
+            }),
+    }
+}
+
+fn parse_extra_tokens(args: &Value) -> Result<Vec<Address>, ToolError> {
+    let Some(arr) = args.get("tokens").and_then(Value::as_array) else {
+        return Ok(vec![]);
+    };
+    arr.iter()
+        .filter_map(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            EvmChain::parse_address(s)
+                .map_err(|_e| ToolError::new("invalid_request", format!("invalid token: {s}")))
+        })
+        .collect()
+}
+
+fn setup_evm(shared: &SharedState, chain: &str) -> Option<EvmChain> {
+    let rpc_url = shared.cfg.rpc.evm_rpc_urls.get(chain)?;
+    let chain_id = *shared.cfg.rpc.evm_chain_ids.get(chain)?;
+    let mut evm = EvmChain::for_name(chain, chain_id, rpc_url, &shared.cfg.http);
+    if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+        evm.fallback_rpc_urls.clone_from(fb);
+    }
+    Some(evm)
+}
+
+/// `(token, spender)` pairs Seashail itself approved, labelled by the recorded protocol.
+fn history_pairs(
+    shared: &SharedState,
+    wallet: &str,
+    idx: u32,
+    chain: &str,
+) -> eyre::Result<Vec<(ApprovalPair, Option<String>)>> {
+    let items = shared.ks.read_tx_history_filtered(
+        usize::MAX,
+        Some(wallet),
+        Some(chain),
+        Some("approve"),
+        None,
+        None,
+    )?;
+    let mut out = vec![];
+    for it in &items {
+        let same_account = it
+            .get("account_index")
+            .and_then(Value::as_u64)
+            .map_or(true, |i| i == u64::from(idx));
+        if !same_account {
+            continue;
+        }
+        let addr = |k: &str| {
+            it.get(k)
+                .and_then(Value::as_str)
+                .and_then(|s| EvmChain::parse_address(s).ok())
+        };
+        let (Some(token), Some(spender)) = (addr("token"), addr("spender")) else {
+            continue;
+        };
+        let label = it
+            .get("protocol")
+            .or_else(|| it.get("provider"))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned);
+        out.push((ApprovalPair { token, spender }, label));
+    }
+    Ok(out)
+}
+
+#[derive(Default)]
+struct LogScan {
+    erc20: Vec<ApprovalPair>,
+    permit2: Vec<ApprovalPair>,
+    head: u64,
+    /// First block actually covered; `None` when no page succeeded.
+    from_block: Option<u64>,
+    error: Option<String>,
+}
+
+/// First block to scan: an explicit lookback wins; otherwise start at the wallet birthday (no
+/// earlier than `MAX_LOOKBACK_BLOCKS` back), or `DEFAULT_LOOKBACK_BLOCKS` without one.
+fn scan_floor(
+    head: u64,
+    lookback: Option<u64>,
+    birthday: Option<&WalletBirthday>,
+    chain: &str,
+    now_unix: i64,
+) -> (u64, &'static str) {
+    if let Some(n) = lookback {
+        return (head.saturating_sub(n.saturating_sub(1)), "lookback_blocks");
+    }
+    match birthday {
+        Some(b) => {
+            let cap = head.saturating_sub(MAX_LOOKBACK_BLOCKS - 1);
+            (
+                scan_start_height(Some(b), chain, head, now_unix).max(cap),
+                "wallet_birthday",
+            )
+        }
+        None => (head.saturating_sub(DEFAULT_LOOKBACK_BLOCKS - 1), "default"),
+    }
+}
+
+/// Scan the owner's approval logs backwards in pages from `head` down to the floor chosen by
+/// `floor_for`, stopping at the first failing page.
+async fn scan_logs(evm: &EvmChain, owner: Address, floor_for: impl FnOnce(u64) -> u64) -> LogScan {
+    let mut out = LogScan::default();
+    match evm.get_block_number().await {
+        Ok(h) => out.head = h,
+        Err(e) => {
+            out.error = Some(format!("{e:#}"));
+            return out;
+        }
+    }
+    let floor = floor_for(out.head);
+    let mut to = out.head;
+    loop {
+        let from = to.saturating_sub(LOG_PAGE_BLOCKS - 1).max(floor);
+        match evm.approval_logs(owner, from, to).await {
+            Ok(scan) => {
+                out.erc20.extend(scan.erc20);
+                out.permit2.extend(scan.permit2);
+                out.from_block = Some(from);
+            }
+            Err(e) => {
+                out.error = Some(format!("{e:#}"));
+                return out;
+            }
+        }
+        if from <= floor {
+            return out;
+        }
+        to = from - 1;
+    }
+}
+
+fn spender_label(
+    evm: &EvmChain,
+    history_labels: &BTreeMap<Address, String>,
+    spender: Address,
+) -> Option<String> {
+    if let Some(l) = history_labels.get(&spender) {
+        return Some(l.clone());
+    }
+    if let Some((_, l)) = WELL_KNOWN_SPENDERS.iter().find(|(a, _)| {
+        EvmChain::parse_address(a)
+            .ok()
+            .is_some_and(|x| x == spender)
+    }) {
+        return Some((*l).to_owned());
+    }
+    evm.uniswap
+        .as_ref()
+        .filter(|u| u.router02 == spender)
+        .map(|_| "Uniswap SwapRouter02".to_owned())
+}
+
+async fn token_info(
+    evm: &EvmChain,
+    shared: &SharedState,
+    token: Address,
+    owner: Address,
+) -> Option<TokenInfo> {
+    let (balance, decimals, symbol) = evm.get_erc20_balance(token, owner).await.ok()?;
+    let usdc = evm.uniswap.as_ref().map(|u| u.usdc);
+    let usd_price = if usdc == Some(token) {
+        Some(1.0_f64)
+    } else if balance.is_zero() || evm.uniswap.is_none() {
+        None
+    } else {
+        let one = u256_pow10(u32::from(decimals)).max(U256::from(1_u64));
+        price::evm_token_price_usd_cached(evm, &shared.cfg, token, one, 50, shared.db())
+            .await
+            .ok()
+            .map(|p| p.usd)
+    };
+    Some(TokenInfo {
+        balance,
+        decimals,
+        symbol,
+        usd_price,
+    })
+}
+
+fn classify(unlimited: bool, labelled: bool, spender: SpenderInfo) -> (Risk, Vec<&'static str>) {
+    let mut flags = vec![];
+    if unlimited {
+        flags.push("unlimited");
+    }
+    if spender.blocklisted {
+        flags.push("spender_blocklisted");
+    }
+    if spender.ofac_sdn {
+        flags.push("spender_ofac_sdn");
+    }
+    if spender.is_contract == Some(false) {
+        flags.push("spender_not_contract");
+    }
+    if !labelled {
+        flags.push("unrecognized_spender");
+    }
+    let risk = if spender.blocklisted
+        || spender.ofac_sdn
+        || spender.is_contract == Some(false)
+        || (unlimited && !labelled)
+    {
+        Risk::High
+    } else if unlimited || !labelled {
+        Risk::Medium
+    } else {
+        Risk::Low
+    };
+    (risk, flags)
+}
+
+fn suggestion(risk: Risk, flags: &[&str]) -> (&'static str, &'static str) {
+    let listed = flags.contains(&"spender_blocklisted") || flags.contains(&"spender_ofac_sdn");
+    match risk {
+        Risk::High if listed => (
+            "revoke",
+            "spender is on a known-malicious or sanctions list",
+        ),
+        Risk::High if flags.contains(&"spender_not_contract") => (
+            "revoke",
+            "spender is an externally owned account, not a protocol contract",
+        ),
+        Risk::High => ("revoke", "unlimited approval to an unrecognized contract"),
+        Risk::Medium if flags.contains(&"unlimited") => (
+            "reduce",
+            "unlimited approval; revoke it or re-approve only what you need",
+        ),
+        Risk::Medium => ("review", "approval to an unrecognized contract"),
+        Risk::Low => ("keep", "bounded approval to a recognized protocol"),
+    }
+}
+
+/// Every `(token, spender)` pair worth an on-chain allowance read, plus spender labels taken
+/// from Seashail's own approval history.
+fn candidate_pairs(
+    evm: &EvmChain,
+    history: Vec<(ApprovalPair, Option<String>)>,
+    logged: Vec<ApprovalPair>,
+    extra_tokens: Vec<Address>,
+) -> (BTreeSet<ApprovalPair>, BTreeMap<Address, String>) {
+    let mut labels: BTreeMap<Address, String> = BTreeMap::new();
+    let mut pairs: BTreeSet<ApprovalPair> = BTreeSet::new();
+    for (pair, label) in history {
+        if let Some(l) = label {
+            labels.entry(pair.spender).or_insert(l);
+        }
+        pairs.insert(pair);
+    }
+    pairs.extend(logged);
+
+    let mut tokens: BTreeSet<Address> = pairs.iter().map(|p| p.token).collect();
+    tokens.extend(extra_tokens);
+    let mut spenders: Vec<Address> = WELL_KNOWN_SPENDERS
+        .iter()
+        .filter_map(|(a, _)| EvmChain::parse_address(a).ok())
+        .collect();
+    if let Some(u) = &evm.uniswap {
+        tokens.insert(u.usdc);
+        tokens.insert(u.wrapped_native);
+        spenders.push(u.router02);
+    }
+    for token in &tokens {
+        for spender in &spenders {
+            pairs.insert(ApprovalPair {
+                token: *token,
+                spender: *spender,
+            });
+        }
+    }
+    (pairs, labels)
+}
+
+/// Read live ERC-20 allowances, then Permit2 allowances for tokens approved to Permit2.
+/// Returns the non-zero, unexpired approvals and the number of reads that failed.
+async fn read_findings(
+    evm: &EvmChain,
+    owner: Address,
+    pairs: &BTreeSet<ApprovalPair>,
+    logged_permit2: Vec<ApprovalPair>,
+) -> eyre::Result<(Vec<Finding>, u32)> {
+    let permit2 = EvmChain::parse_address(PERMIT2)?;
+    let mut findings: Vec<Finding> = vec![];
+    let mut read_errors = 0_u32;
+    let mut via_permit2: BTreeMap<Address, U256> = BTreeMap::new();
+    for pair in pairs.iter().take(MAX_PAIRS) {
+        match evm.erc20_allowance(pair.token, owner, pair.spender).await {
+            Ok(a) if a.is_zero() => {}
+            Ok(a) => {
+                if pair.spender == permit2 {
+                    via_permit2.insert(pair.token, a);
+                }
+                findings.push(Finding {
+                    kind: ApprovalKind::Erc20,
+                    pair: *pair,
+                    allowance: a,
+                    expiration: None,
+                    effective_allowance: a,
+                });
+            }
+            Err(_e) => read_errors = read_errors.saturating_add(1),
+        }
+    }
+
+    let mut permit2_pairs: BTreeSet<ApprovalPair> = logged_permit2
+        .into_iter()
+        .filter(|p| via_permit2.contains_key(&p.token))
+        .collect();
+    for token in via_permit2.keys() {
+        for spender in PERMIT2_KNOWN_SPENDERS
+            .iter()
+            .filter_map(|s| EvmChain::parse_address(s).ok())
+        {
+            permit2_pairs.insert(ApprovalPair {
+                token: *token,
+                spender,
+            });
+        }
+    }
+    let now_unix = u64::try_from(chrono::Utc::now().timestamp()).unwrap_or_default();
+    for pair in permit2_pairs.iter().take(MAX_PAIRS) {
+        match evm.permit2_allowance(owner, pair.token, pair.spender).await {
+            Ok((amount, expiration)) if !amount.is_zero() && expiration >= now_unix => {
+                let cap = via_permit2.get(&pair.token).copied().unwrap_or_default();
+                findings.push(Finding {
+                    kind: ApprovalKind::Permit2,
+                    pair: *pair,
+                    allowance: amount,
+                    expiration: Some(expiration),
+                    effective_allowance: amount.min(cap),
+                });
+            }
+            Ok(_) => {}
+            Err(_e) => read_errors = read_errors.saturating_add(1),
+        }
+    }
+    Ok((findings, read_errors))
+}
+
+async fn spender_info(evm: &EvmChain, shared: &mut SharedState, spender: Address) -> SpenderInfo {
+    let is_contract = evm
+        .get_contract_code(spender)
+        .await
+        .ok()
+        .map(|c| !c.is_empty());
+    SpenderInfo {
+        is_contract,
+        blocklisted: shared.scam_blocklist_contains_evm(spender).await,
+        ofac_sdn: shared.ofac_sdn_contains_evm(spender).await,
+    }
+}
+
+fn approval_row(
+    f: &Finding,
+    info: Option<&TokenInfo>,
+    spender: SpenderInfo,
+    label: Option<String>,
+) -> eyre::Result<(Risk, Option<f64>, Value)> {
+    let unlimited = is_unlimited_allowance(f.allowance);
+    let (risk, flags) = classify(unlimited, label.is_some(), spender);
+    let (action, reason) = suggestion(risk, &flags);
+    let at_risk_base = info.map(|i| f.effective_allowance.min(i.balance));
+    let at_risk_usd = match (info, at_risk_base) {
+        (Some(_), Some(base)) if base.is_zero() => Some(0.0_f64),
+        (Some(i), Some(base)) => i
+            .usd_price
+            .map(|p| token_base_to_usd(u256_low_u128(base), i.decimals, p)),
+        _ => None,
+    };
+    let (kind, revoke) = match f.kind {
+        ApprovalKind::Erc20 => (
+            "erc20",
+            json!({
+              "to": format!("{:#x}", f.pair.token),
+              "data": erc20_revoke_calldata(f.pair.spender).to_string(),
+            }),
+        ),
+        ApprovalKind::Permit2 => (
+            "permit2",
+            json!({
+              "to": format!("{:#x}", EvmChain::parse_address(PERMIT2)?),
+              "data": permit2_revoke_calldata(f.pair.token, f.pair.spender).to_string(),
+            }),
+        ),
+    };
+    let row = json!({
+      "kind": kind,
+      "token": format!("{:#x}", f.pair.token),
+      "symbol": info.map(|i| i.symbol.clone()),
+      "decimals": info.map(|i| i.decimals),
+      "spender": format!("{:#x}", f.pair.spender),
+      "spender_label": label,
+      "spender_is_contract": spender.is_contract,
+      "allowance_base": f.allowance.to_string(),
+      "unlimited": unlimited,
+      "expiration": f.expiration,
+      "balance_base": info.map(|i| i.balance.to_string()),
+      "at_risk_base": at_risk_base.map(|b| b.to_string()),
+      "at_risk_usd": at_risk_usd,
+      "risk": risk.as_str(),
+      "flags": flags,
+      "suggestion": action,
+      "reason": reason,
+      "revoke": revoke,
+    });
+    Ok((risk, at_risk_usd, row))
+}
+
+fn summarize(rows: &[(Risk, Option<f64>, Value)]) -> Value {
+    let mut total_at_risk_usd = 0.0_f64;
+    let mut unpriced = 0_u32;
+    let (mut high, mut medium) = (0_u32, 0_u32);
+    for (risk, usd, _) in rows {
+        match usd {
+            Some(v) => crate::financial_math::accum(&mut total_at_risk_usd, *v),
+            None => unpriced = unpriced.saturating_add(1),
+        }
+        match risk {
+            Risk::High => high = high.saturating_add(1),
+            Risk::Medium => medium = medium.saturating_add(1),
+            Risk::Low => {}
+        }
+    }
+    json!({
+      "count": rows.len(),
+      "high": high,
+      "medium": medium,
+      "total_at_risk_usd": total_at_risk_usd,
+      "unpriced": unpriced,
+    })
+}
+
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &mut SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    if chain.is_empty() {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new("invalid_request", "missing chain")),
+        ));
+    }
+    let Some(evm) = setup_evm(shared, chain) else {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_request",
+                format!(
+                    "get_allowances_risk_report supports EVM chains only (unknown chain: {chain})"
+                ),
+            )),
+        ));
+    };
+    let (lookback, extra_tokens) = match (parse_lookback(args), parse_extra_tokens(args)) {
+        (Ok(l), Ok(t)) => (l, t),
+        (Err(te), _) | (_, Err(te)) => return Ok(ok(req_id, tool_err(te))),
+    };
+    let (w, idx) = resolve_wallet_and_account(shared, args)?;
+    let owner = evm_addr_for_account(&w, idx)?;
+    shared.ensure_db().await;
+
+    let history = history_pairs(shared, &w.name, idx, chain)?;
+    let now = chrono::Utc::now().timestamp();
+    let mut scan_start = "default";
+    let mut floor = None;
+    let log = scan_logs(&evm, owner, |head| {
+        let (f, start) = scan_floor(head, lookback, w.birthday.as_ref(), chain, now);
+        floor = Some(f);
+        scan_start = start;
+        f
+    })
+    .await;
+    let (pairs, history_labels) = candidate_pairs(&evm, history, log.erc20, extra_tokens);
+    let truncated = pairs.len() > MAX_PAIRS;
+    let (findings, read_errors) = read_findings(&evm, owner, &pairs, log.permit2).await?;
+
+    let mut token_infos: BTreeMap<Address, Option<TokenInfo>> = BTreeMap::new();
+    let mut spender_infos: BTreeMap<Address, SpenderInfo> = BTreeMap::new();
+    for f in &findings {
+        if !token_infos.contains_key(&f.pair.token) {
+            let info = token_info(&evm, shared, f.pair.token, owner).await;
+            token_infos.insert(f.pair.token, info);
+        }
+        if !spender_infos.contains_key(&f.pair.spender) {
+            let info = spender_info(&evm, shared, f.pair.spender).await;
+            spender_infos.insert(f.pair.spender, info);
+        }
+    }
+
+    let mut rows: Vec<(Risk, Option<f64>, Value)> = vec![];
+    for f in &findings {
+        let Some(spender) = spender_infos.get(&f.pair.spender).copied() else {
+            continue;
+        };
+        let info = token_infos.get(&f.pair.token).and_then(Option::as_ref);
+        let label = spender_label(&evm, &history_labels, f.pair.spender);
+        rows.push(approval_row(f, info, spender, label)?);
+    }
+    // Rank by at-risk USD (unpriced last), then by risk level.
+    rows.sort_by(|a, b| {
+        let usd = |x: Option<f64>| x.unwrap_or(-1.0_f64);
+        usd(b.1).total_cmp(&usd(a.1)).then_with(|| b.0.cmp(&a.0))
+    });
+    let summary = summarize(&rows);
+    let suggested_revocations: Vec<Value> = rows
+        .iter()
+        .filter(|(_, _, r)| r.get("suggestion").and_then(Value::as_str) == Some("revoke"))
+        .map(|(_, _, r)| {
+            json!({
+              "kind": r.get("kind"),
+              "token": r.get("token"),
+              "symbol": r.get("symbol"),
+              "spender": r.get("spender"),
+              "reason": r.get("reason"),
+              "revoke": r.get("revoke"),
+            })
+        })
+        .collect();
+    let approvals: Vec<Value> = rows.into_iter().map(|(_, _, r)| r).collect();
+
+    let mut warnings: Vec<String> = vec![];
+    if let Some(e) = &log.error {
+        warnings.push(format!(
+            "approval log scan stopped early ({e}); older approvals to unlisted spenders may be missing"
+        ));
+    }
+    if truncated {
+        warnings.push(format!(
+            "checked the first {MAX_PAIRS} token/spender pairs only"
+        ));
+    }
+    if read_errors > 0 {
+        warnings.push(format!(
+            "{read_errors} allowance reads failed and were skipped"
+        ));
+    }
+
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "wallet": w.name,
+          "account_index": idx,
+          "chain": chain,
+          "owner": format!("{owner:#x}"),
+          "approvals": approvals,
+          "summary": summary,
+          "suggested_revocations": suggested_revocations,
+          "scan": {
+            "head_block": log.head,
+            "from_block": log.from_block,
+            "lookback_blocks": floor.map(|f| log.head.saturating_sub(f).saturating_add(1)),
+            "start": scan_start,
+            "complete": log.error.is_none(),
+          },
+          "warnings": warnings,
+        })),
+    ))
+}
@@ -1,22 +1,24 @@
-use crate::chains::{bitcoin::BitcoinChain, evm::EvmChain, solana::SolanaChain};
+use crate::chains::{
+    bitcoin::BitcoinChain, evm::EvmChain, native::native_token, solana::SolanaChain,
+};
 use serde_json::{json, Value};
 
 use super::super::super::jsonrpc::{ok, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
 use super::super::helpers::{
-    evm_addr_for_account, evm_native_symbol, is_native_token, resolve_wallet_and_account,
-    sol_pubkey_for_account, solana_fallback_urls,
+    evm_addr_for_account, is_native_token, resolve_wallet_and_account, sol_pubkey_for_account,
+    solana_fallback_urls,
 };
+use super::locked_positions;
 
 const SOL_DECIMALS: i32 = 9;
-const EVM_DECIMALS: i32 = 18;
 const BTC_DECIMALS: i32 = 8;
 
 pub async fn handle(
     req_id: Value,
     args: Value,
-    shared: &SharedState,
+    shared: &mut SharedState,
     conn: &ConnState,
 ) -> eyre::Result<JsonRpcResponse> {
     let (w, idx) = resolve_wallet_and_account(shared, &args)?;
@@ -39,33 +41,81 @@ pub async fn handle(
         vec![chain_filter.to_owned()]
     };
 
+    let include_positions = args
+        .get("include_positions")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
     let mut out = vec![];
-    for chain in chains {
-        let v = if chain == "solana" {
-            balance_solana(shared, conn, &w, idx, &tokens).await?
-        } else if chain == "bitcoin" {
-            balance_bitcoin(shared, conn, &w, idx).await?
-        } else {
-            balance_evm(shared, &w, idx, &chain, &tokens).await?
-        };
-        out.push(v);
+    for chain in &chains {
+        out.push(balance_for_chain(shared, conn, &w, idx, chain, &tokens).await?);
+    }
+
+    if !include_positions {
+        return Ok(ok(
+            req_id,
+            tool_ok(json!({ "wallet": w.name, "account_index": idx, "balances": out })),
+        ));
     }
 
+    let positions = locked_positions::collect(shared, conn, &w, idx, &chains).await;
+    let (locked_usd, debt_usd) = locked_positions::totals(&positions);
     Ok(ok(
         req_id,
-        tool_ok(json!({ "wallet": w.name, "account_index": idx, "balances": out })),
+        tool_ok(json!({
+          "wallet": w.name,
+          "account_index": idx,
+          "balances": out,
+          "locked_positions": positions,
+          "locked_usd": locked_usd,
+          "debt_usd": debt_usd,
+          "note": "balances are liquid (spendable). locked_positions are staked, lent or posted as perp margin and must be withdrawn or unstaked before they can be spent."
+        })),
     ))
 }
 
-async fn balance_solana(
+async fn balance_for_chain(
     shared: &SharedState,
     conn: &ConnState,
     w: &crate::wallet::WalletRecord,
     idx: u32,
+    chain: &str,
     tokens: &[String],
 ) -> eyre::Result<Value> {
+    if chain == "solana" {
+        balance_solana(shared, conn, w, idx, tokens).await
+    } else if chain == "bitcoin" {
+        balance_bitcoin(shared, conn, w, idx).await
+    } else {
+        balance_evm(shared, w, idx, chain, tokens).await
+    }
+}
+
+/// Native balances on the network mode's default chains. Fails soft: a chain whose read errors
+/// lands in the second vec as `{chain, error}` instead of failing the whole call.
+pub async fn default_chain_balances(
+    shared: &SharedState,
+    conn: &ConnState,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+) -> (Vec<Value>, Vec<Value>) {
+    let chains = shared
+        .cfg
+        .default_chains_for_mode(effective_network_mode(shared, conn));
+    let mut balances = vec![];
+    let mut errors = vec![];
+    for chain in &chains {
+        match balance_for_chain(shared, conn, w, idx, chain, &[]).await {
+            Ok(v) => balances.push(v),
+            Err(e) => errors.push(json!({ "chain": chain, "error": format!("{e:#}") })),
+        }
+    }
+    (balances, errors)
+}
+
+fn solana_chain(shared: &SharedState, conn: &ConnState) -> SolanaChain {
     let mode = effective_network_mode(shared, conn);
-    let sol = SolanaChain::new_with_fallbacks(
+    SolanaChain::new_with_fallbacks(
         &shared.cfg.rpc.solana_rpc_url,
         solana_fallback_urls(shared, mode),
         &shared.cfg.http.jupiter_base_url,
@@ -75,7 +125,57 @@ async fn balance_solana(
             .cfg
             .rpc
             .solana_default_compute_unit_price_micro_lamports,
-    );
+    )
+}
+
+fn evm_chain(shared: &SharedState, chain: &str) -> eyre::Result<EvmChain> {
+    let rpc_url = shared
+        .cfg
+        .rpc
+        .evm_rpc_urls
+        .get(chain)
+        .ok_or_else(|| eyre::eyre!("unknown evm chain: {chain}"))?
+        .clone();
+    let chain_id = *shared
+        .cfg
+        .rpc
+        .evm_chain_ids
+        .get(chain)
+        .ok_or_else(|| eyre::eyre!("missing evm chain id: {chain}"))?;
+
+    let mut evm = EvmChain::for_name(chain, chain_id, &rpc_url, &shared.cfg.http);
+    if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+        evm.fallback_rpc_urls.clone_from(fb);
+    }
+    Ok(evm)
+}
+
+/// Native balance (lamports or wei) of a wallet account on `chain` (solana or an EVM chain).
+pub async fn native_balance_base(
+    shared: &SharedState,
+    conn: &ConnState,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    chain: &str,
+) -> eyre::Result<u128> {
+    if chain == "solana" {
+        let owner = sol_pubkey_for_account(w, idx)?;
+        let lamports = solana_chain(shared, conn).get_sol_balance(owner).await?;
+        return Ok(u128::from(lamports));
+    }
+    let owner = evm_addr_for_account(w, idx)?;
+    let wei = evm_chain(shared, chain)?.get_native_balance(owner).await?;
+    Ok(crate::chains::evm::u256_low_u128(wei))
+}
+
+async fn balance_solana(
+    shared: &SharedState,
+    conn: &ConnState,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    tokens: &[String],
+) -> eyre::Result<Value> {
+    let sol = solana_chain(shared, conn);
     let owner = sol_pubkey_for_account(w, idx)?;
 
     let lamports = match sol.get_sol_balance(owner).await {
@@ -132,26 +232,9 @@ async fn balance_evm(
     chain: &str,
     tokens: &[String],
 ) -> eyre::Result<Value> {
-    let rpc_url = shared
-        .cfg
-        .rpc
-        .evm_rpc_urls
-        .get(chain)
-        .ok_or_else(|| eyre::eyre!("unknown evm chain: {chain}"))?
-        .clone();
-    let chain_id = *shared
-        .cfg
-        .rpc
-        .evm_chain_ids
-        .get(chain)
-        .ok_or_else(|| eyre::eyre!("missing evm chain id: {chain}"))?;
-
-    let mut evm = EvmChain::for_name(chain, chain_id, &rpc_url, &shared.cfg.http);
-    if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
-        evm.fallback_rpc_urls.clone_from(fb);
-    }
-
+    let evm = evm_chain(shared, chain)?;
     let owner = evm_addr_for_account(w, idx)?;
+    let native = native_token(chain);
 
     let wei = match evm.get_native_balance(owner).await {
         Ok(v) => v,
@@ -159,7 +242,7 @@ async fn balance_evm(
             return Ok(json!({
               "chain": chain,
               "error": format!("{e:#}"),
-              "native": { "symbol": evm_native_symbol(chain), "amount": "0", "decimals": EVM_DECIMALS },
+              "native": { "symbol": native.symbol, "amount": "0", "decimals": native.decimals },
               "tokens": []
             }));
         }
@@ -168,7 +251,7 @@ async fn balance_evm(
     let toks = evm_erc20_balances(&evm, owner, tokens).await?;
     Ok(json!({
       "chain": chain,
-      "native": { "symbol": evm_native_symbol(chain), "amount": wei.to_string(), "decimals": EVM_DECIMALS },
+      "native": { "symbol": native.symbol, "amount": wei.to_string(), "decimals": native.decimals },
       "tokens": toks
     }))
 }
@@ -0,0 +1,58 @@
+use serde_json::Value;
+
+use crate::errors::ToolError;
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+use super::super::write::{quote_bridge_routes, RouteQuoteRequest};
+
+fn arg<'a>(args: &'a Value, key: &str) -> &'a str {
+    args.get(key).and_then(Value::as_str).unwrap_or("").trim()
+}
+
+/// Compare bridge providers for one transfer (fees, amount received, ETA) without executing.
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &mut SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let req = RouteQuoteRequest {
+        chain: arg(args, "chain"),
+        to_chain: arg(args, "to_chain"),
+        token: arg(args, "token"),
+        amount: arg(args, "amount"),
+        units: match arg(args, "amount_units") {
+            "" => "ui",
+            u => u,
+        },
+    };
+    if req.chain.is_empty()
+        || req.to_chain.is_empty()
+        || req.token.is_empty()
+        || req.amount.is_empty()
+    {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_request",
+                "missing chain/to_chain/token/amount",
+            )),
+        ));
+    }
+    if req.chain == req.to_chain {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_request",
+                "chain and to_chain must differ",
+            )),
+        ));
+    }
+
+    let wallet = args.get("wallet").and_then(Value::as_str);
+    let (policy, _) = shared.cfg.policy_for_wallet(wallet);
+    match quote_bridge_routes(shared, &req, &policy).await {
+        Ok(v) => Ok(ok(req_id, tool_ok(v))),
+        Err(te) => Ok(ok(req_id, tool_err(te))),
+    }
+}
@@ -161,7 +161,7 @@ async fn handle_wormhole(
     let mut last_err: Option<eyre::Report> = None;
     for _ in 0..3_u32 {
         match wormholescan_signed_vaa_b64(
-            &shared.cfg.http.wormholescan_api_base_url,
+            shared.cfg.http.wormholescan_base_url(),
             src_chain_id,
             emitter,
             sequence,
@@ -4,6 +4,20 @@ use reqwest::Client;
 use serde_json::{json, Value};
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+
+fn is_loopback_http(url: &str) -> bool {
+    fn host_prefix_ok(s: &str, prefix: &str) -> bool {
+        if !s.starts_with(prefix) {
+            return false;
+        }
+        matches!(s.as_bytes().get(prefix.len()), None | Some(b':' | b'/'))
+    }
+    let u = url.trim();
+    host_prefix_ok(u, "http://127.0.0.1")
+        || host_prefix_ok(u, "http://localhost")
+        || host_prefix_ok(u, "http://[::1]")
+}
 
 struct YieldFilters {
     chains: Vec<String>,
@@ -128,20 +142,36 @@ fn filter_and_sort_pools(data: Vec<Value>, f: &YieldFilters) -> Vec<Value> {
     out
 }
 
-pub async fn handle(req_id: Value, args: Value) -> eyre::Result<JsonRpcResponse> {
+pub async fn handle(
+    req_id: Value,
+    args: Value,
+    shared: &SharedState,
+) -> eyre::Result<JsonRpcResponse> {
     let filters = parse_yield_filters(&args);
 
-    // Public, keyless endpoint. Keep as a constant default to avoid env var reliance.
-    let url = "https://yields.llama.fi/pools";
+    // Defaults to the public keyless dataset; see `http.defillama_yields_url` / `defillama_api_key`.
+    let url = shared.cfg.http.yield_pools_url();
+    let source = shared.cfg.http.yield_pools_url_redacted();
+    if !url.starts_with("https://") && !is_loopback_http(&url) {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_config",
+                "http.defillama_yields_url must use https (or loopback for local testing)",
+            )),
+        ));
+    }
 
     let client = Client::builder()
         .timeout(std::time::Duration::from_secs(10))
         .build()
         .context("build http client")?;
     let resp = client
-        .get(url)
+        .get(&url)
         .send()
         .await
+        // Strip the URL from transport errors: it may embed the DefiLlama Pro key.
+        .map_err(reqwest::Error::without_url)
         .context("fetch yields dataset")?;
     if !resp.status().is_success() {
         return Ok(ok(
@@ -152,7 +182,11 @@ pub async fn handle(req_id: Value, args: Value) -> eyre::Result<JsonRpcResponse>
             )),
         ));
     }
-    let v: Value = resp.json().await.context("parse yields json")?;
+    let v: Value = resp
+        .json()
+        .await
+        .map_err(reqwest::Error::without_url)
+        .context("parse yields json")?;
     let data = v
         .get("data")
         .and_then(|d| d.as_array())
@@ -164,7 +198,7 @@ pub async fn handle(req_id: Value, args: Value) -> eyre::Result<JsonRpcResponse>
     Ok(ok(
         req_id,
         tool_ok(json!({
-          "source": url,
+          "source": source,
           "count": out.len(),
           "pools": out
         })),
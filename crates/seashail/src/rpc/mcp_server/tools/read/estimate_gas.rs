@@ -1,6 +1,6 @@
 use crate::{
     amount,
-    chains::{evm::EvmChain, solana::SolanaChain},
+    chains::{evm::EvmChain, native::native_token, solana::SolanaChain},
     errors::ToolError,
 };
 use alloy::primitives::U256;
@@ -13,8 +13,9 @@ use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
 use super::super::helpers::{
-    evm_addr_for_account, is_native_token, resolve_wallet_and_account, sol_pubkey_for_account,
-    solana_fallback_urls, u128_to_u256, u128_to_u64, MAX_REMOTE_TX_BYTES,
+    evm_addr_for_account, evm_defi_deadline_seconds, evm_defi_slippage_bps, is_native_token,
+    resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls, u128_to_u256,
+    u128_to_u64, unix_deadline, MAX_REMOTE_TX_BYTES,
 };
 
 /// Bundled parameters for send-transaction gas estimation.
@@ -251,8 +252,10 @@ async fn estimate_send_evm(
         let wei: U256 = if units == "base" {
             crate::chains::evm::parse_u256_dec(amount)?
         } else {
-            // native assumes 18 decimals
-            u128_to_u256(amount::parse_amount_ui_to_base_u128(amount, 18)?)
+            u128_to_u256(amount::parse_amount_ui_to_base_u128(
+                amount,
+                u32::from(native_token(chain).decimals),
+            )?)
         };
         tx = EvmChain::build_native_transfer(from, to_addr, wei);
     } else {
@@ -290,11 +293,14 @@ async fn estimate_swap_tokens(
         .get("amount_units")
         .and_then(|v| v.as_str())
         .unwrap_or("ui");
-    let slippage_bps = args
-        .get("slippage_bps")
-        .and_then(serde_json::Value::as_u64)
-        .and_then(|v| u32::try_from(v).ok())
-        .unwrap_or(100);
+    let slippage_bps = if chain == "solana" {
+        args.get("slippage_bps")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(100)
+    } else {
+        evm_defi_slippage_bps(&shared.cfg.evm_defi, args)
+    };
     let provider_raw = args
         .get("provider")
         .and_then(|v| v.as_str())
@@ -478,6 +484,10 @@ async fn estimate_swap_evm(
     let from = evm_addr_for_account(&w, idx)?;
 
     if provider == "uniswap" {
+        let deadline_seconds = match evm_defi_deadline_seconds(&shared.cfg.evm_defi, args) {
+            Ok(v) => v,
+            Err(te) => return Ok(ok(req_id, tool_err(te))),
+        };
         let sp = SwapEstimateParams {
             req_id,
             chain,
@@ -487,7 +497,7 @@ async fn estimate_swap_evm(
             units,
             slippage_bps,
         };
-        return estimate_swap_uniswap(sp, from, &evm).await;
+        return estimate_swap_uniswap(sp, from, &evm, deadline_seconds).await;
     }
 
     if provider == "1inch" {
@@ -516,6 +526,7 @@ async fn estimate_swap_uniswap(
     p: SwapEstimateParams<'_>,
     from: alloy::primitives::Address,
     evm: &EvmChain,
+    deadline_seconds: u64,
 ) -> eyre::Result<JsonRpcResponse> {
     let SwapEstimateParams {
         req_id,
@@ -590,6 +601,7 @@ async fn estimate_swap_uniswap(
         fee,
         native_in,
         native_out,
+        deadline: unix_deadline(deadline_seconds),
     };
     let tx = evm.build_uniswap_swap_tx(&swap_req)?;
     let gas = evm.estimate_tx_gas(&tx).await?;
@@ -1,5 +1,9 @@
 use crate::{
-    chains::{evm::EvmChain, solana::SolanaChain},
+    chains::{
+        evm::{scan_token_code_flags, EvmChain, TokenCodeFlags},
+        native::native_token,
+        solana::SolanaChain,
+    },
     errors::ToolError,
 };
 use serde_json::{json, Value};
@@ -10,7 +14,7 @@ use spl_token::state::Mint;
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
-use super::super::helpers::{evm_native_symbol, is_native_token, solana_fallback_urls};
+use super::super::helpers::{is_native_token, solana_fallback_urls};
 
 pub async fn handle(
     req_id: Value,
@@ -151,14 +155,15 @@ async fn inspect_evm(
     };
 
     if is_native_token(token) {
+        let native = native_token(chain);
         return Ok(ok(
             req_id,
             tool_ok(json!({
               "chain": chain,
               "token": "native",
               "kind": "native",
-              "symbol": evm_native_symbol(chain),
-              "decimals": 18,
+              "symbol": native.symbol,
+              "decimals": native.decimals,
               "warnings": [],
             })),
         ));
@@ -198,18 +203,24 @@ async fn inspect_evm(
     let impl_val: alloy::primitives::B256 = evm.get_storage_at(token_addr, impl_slot).await?;
     let impl_bytes = impl_val.as_slice();
     let proxy_detected = impl_bytes.iter().any(|b| *b != 0);
-    let implementation = proxy_detected.then(|| {
+    let impl_addr = proxy_detected.then(|| {
         let mut addr = [0_u8; 20];
         if let Some(b) = impl_bytes.get(12..32) {
             addr.copy_from_slice(b);
         }
-        let impl_addr = alloy::primitives::Address::from(addr);
         warnings.push("eip1967_proxy_detected");
-        format!("{impl_addr:#x}")
+        alloy::primitives::Address::from(addr)
     });
+    let implementation = impl_addr.map(|a| format!("{a:#x}"));
 
     // Try to read ERC-20 metadata; if it fails, return best-effort contract info.
     if let Ok((decimals, symbol, name, supply)) = evm.get_erc20_details(token_addr).await {
+        let impl_code = match impl_addr {
+            Some(a) => evm.get_contract_code(a).await.unwrap_or_default(),
+            None => alloy::primitives::Bytes::new(),
+        };
+        let flags = scan_token_code_flags(&[code.as_ref(), impl_code.as_ref()]);
+        let risk = evm_trade_risk(&evm, token_addr, flags, &mut warnings).await;
         Ok(ok(
             req_id,
             tool_ok(json!({
@@ -222,6 +233,13 @@ async fn inspect_evm(
               "name": name,
               "total_supply_base": supply.to_string(),
               "proxy": { "eip1967_detected": proxy_detected, "implementation": implementation },
+              "buy_tax_bps": risk.get("buy_tax_bps").cloned().unwrap_or(Value::Null),
+              "sell_tax_bps": risk.get("sell_tax_bps").cloned().unwrap_or(Value::Null),
+              "sellable": risk.get("sellable").cloned().unwrap_or(Value::Null),
+              "has_blacklist": flags.has_blacklist,
+              "has_pause": flags.has_pause,
+              "has_mutable_fee": flags.has_mutable_fee,
+              "trade_simulation": risk,
               "warnings": warnings,
             })),
         ))
@@ -240,3 +258,67 @@ async fn inspect_evm(
         ))
     }
 }
+
+/// Buy-tax/sell-tax above this many basis points is called out as a warning.
+const HIGH_TAX_BPS: u32 = 1_000;
+
+/// Bytecode flags plus a simulated Uniswap v3 buy and sell, summarized for `inspect_token`.
+/// Simulation problems are reported as warnings rather than failing the inspection.
+async fn evm_trade_risk(
+    evm: &EvmChain,
+    token: alloy::primitives::Address,
+    flags: TokenCodeFlags,
+    warnings: &mut Vec<&'static str>,
+) -> Value {
+    if flags.has_blacklist {
+        warnings.push("blacklist_function_present");
+    }
+    if flags.has_pause {
+        warnings.push("pause_function_present");
+    }
+    if flags.has_mutable_fee {
+        warnings.push("mutable_fee_function_present");
+    }
+
+    let probe = match evm.probe_token_trade(token).await {
+        Ok(Some(p)) => p,
+        Ok(None) => {
+            warnings.push("no_uniswap_v3_native_pool");
+            return json!({ "simulated": false, "reason": "no Uniswap v3 wrapped-native pool" });
+        }
+        Err(e) => {
+            warnings.push("trade_simulation_unavailable");
+            return json!({ "simulated": false, "reason": format!("{e:#}") });
+        }
+    };
+
+    let sellable = probe.sellable();
+    if !probe.buy_ok {
+        warnings.push("buy_simulation_reverted");
+    } else if !sellable {
+        warnings.push("honeypot_suspected");
+    }
+    if probe.buy_tax_bps.is_some_and(|t| t > HIGH_TAX_BPS) {
+        warnings.push("high_buy_tax");
+    }
+    if probe.sell_tax_bps.is_some_and(|t| t > HIGH_TAX_BPS) {
+        warnings.push("high_sell_tax");
+    }
+    if sellable && !probe.sell_ok {
+        warnings.push("fee_on_transfer_not_sellable_on_uniswap_v3");
+    }
+
+    json!({
+      "simulated": true,
+      "dex": "uniswap_v3",
+      "pool": format!("{:#x}", probe.pool),
+      "fee": probe.fee,
+      "probe_in_wei": probe.probe_in_wei.to_string(),
+      "buy_ok": probe.buy_ok,
+      "buy_tax_bps": probe.buy_tax_bps,
+      "sell_tax_bps": probe.sell_tax_bps,
+      "sellable": probe.buy_ok.then_some(sellable),
+      "router_sell_ok": probe.sell_ok,
+      "error": probe.error,
+    })
+}
@@ -0,0 +1,527 @@
+use crate::{
+    chains::{
+        evm::{u256_low_u128, EvmChain},
+        solana::SolanaChain,
+    },
+    config::NetworkMode,
+    financial_math::{accum, sub_f64, sum_f64, token_base_to_usd},
+    price,
+    wallet::WalletRecord,
+};
+use alloy::{primitives::Address, sol};
+use eyre::Context as _;
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::JsonRpcResponse;
+use super::super::super::state::effective_network_mode;
+use super::super::super::{ConnState, SharedState};
+use super::super::helpers::{evm_addr_for_account, sol_pubkey_for_account, solana_fallback_urls};
+use super::super::perps::handle_get_positions;
+use super::super::value_helpers::tool_response_payload;
+use super::lending_positions;
+
+const LIDO_STETH: &str = "0xae7ab96520de3a18e5e111b5eaab095312d7fe84";
+const JITOSOL_MINT: &str = "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+// Aave v3 reports account data in its USD base currency (8 decimals); Comet price feeds match.
+const USD_BASE_DECIMALS: u8 = 8;
+
+sol! {
+    #[sol(rpc)]
+    contract ICometV3Balances {
+        struct AssetInfo {
+            uint8 offset;
+            address asset;
+            address priceFeed;
+            uint64 scale;
+            uint64 borrowCollateralFactor;
+            uint64 liquidateCollateralFactor;
+            uint64 liquidationFactor;
+            uint128 supplyCap;
+        }
+        function baseScale() external view returns (uint64);
+        function baseTokenPriceFeed() external view returns (address);
+        function getPrice(address priceFeed) external view returns (uint256);
+        function balanceOf(address account) external view returns (uint256);
+        function borrowBalanceOf(address account) external view returns (uint256);
+        function numAssets() external view returns (uint8);
+        function getAssetInfo(uint8 i) external view returns (AssetInfo);
+        function collateralBalanceOf(address account, address asset) external view returns (uint128);
+    }
+}
+
+fn default_comet_for_chain(chain: &str) -> Option<&'static str> {
+    // Compound v3 Comet addresses (USDC markets). Source: compound-finance/comet deployments.
+    match chain {
+        "ethereum" => Some("0xc3d688B66703497DAA19211EEdff47f25384cdc3"),
+        "base" => Some("0xb125E6687d4313864e53df431d5425969c15Eb2F"),
+        "arbitrum" => Some("0x9c4ec768c28520B50860ea7a15bd7213a9fF58bf"),
+        "optimism" => Some("0x2e44e174f7D53F0212823acC11C01A11d58c5bCB"),
+        "polygon" => Some("0xF25212E676D1F7F89Cd72fFEe66158f541246445"),
+        "sepolia" => Some("0xAec1F48e02Cfb822Be958B68C7957156EB3F0b6e"),
+        _ => None,
+    }
+}
+
+fn has_default_aave_pool(chain: &str) -> bool {
+    matches!(
+        chain,
+        "ethereum" | "base" | "arbitrum" | "optimism" | "polygon"
+    )
+}
+
+/// One locked position. `usd_value` is the gross value held by the protocol; `debt_usd` is owed
+/// against it. Zero positions are dropped.
+fn position(
+    kind: &str,
+    protocol: &str,
+    chain: &str,
+    usd_value: Option<f64>,
+    debt_usd: f64,
+    details: Value,
+) -> Option<Value> {
+    if usd_value.is_some_and(|v| v <= 0.0_f64) && debt_usd <= 0.0_f64 {
+        return None;
+    }
+    Some(json!({
+      "type": kind,
+      "protocol": protocol,
+      "chain": chain,
+      "usd_value": usd_value.unwrap_or(0.0_f64),
+      "usd_value_known": usd_value.is_some(),
+      "debt_usd": debt_usd,
+      "details": details
+    }))
+}
+
+fn position_error(kind: &str, protocol: &str, chain: &str, msg: &str) -> Value {
+    json!({ "type": kind, "protocol": protocol, "chain": chain, "error": msg })
+}
+
+fn num_f64(v: Option<&Value>) -> Option<f64> {
+    v.and_then(|x| {
+        x.as_f64()
+            .or_else(|| x.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+    })
+}
+
+fn base_str_to_usd(v: Option<&Value>) -> Option<f64> {
+    let s = v.and_then(Value::as_str)?;
+    let base = s.trim().parse::<u128>().ok()?;
+    Some(token_base_to_usd(base, USD_BASE_DECIMALS, 1.0_f64))
+}
+
+/// Run one of the existing position readers and return its payload (or its error message).
+fn reader_payload(resp: eyre::Result<JsonRpcResponse>) -> Result<Value, String> {
+    let resp = resp.map_err(|e| format!("{e:#}"))?;
+    let (is_error, payload) = tool_response_payload(&resp);
+    if is_error {
+        return Err(payload
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("position read failed")
+            .to_owned());
+    }
+    Ok(payload)
+}
+
+fn reader_args(w: &WalletRecord, idx: u32, extra: &Value) -> Value {
+    let mut args = json!({ "wallet": w.name, "account_index": idx });
+    if let (Some(a), Some(e)) = (args.as_object_mut(), extra.as_object()) {
+        a.extend(e.clone());
+    }
+    args
+}
+
+async fn aave_position(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    w: &WalletRecord,
+    idx: u32,
+    chain: &str,
+) -> Option<Value> {
+    let args = reader_args(w, idx, &json!({ "chain": chain, "protocol": "aave" }));
+    match reader_payload(lending_positions::handle(Value::Null, args, shared, conn).await) {
+        Ok(p) => {
+            let data = p.get("account_data");
+            let collateral = base_str_to_usd(data.and_then(|d| d.get("total_collateral_base")));
+            let debt = base_str_to_usd(data.and_then(|d| d.get("total_debt_base")));
+            position(
+                "lending",
+                "aave",
+                chain,
+                collateral,
+                debt.unwrap_or(0.0_f64),
+                json!({ "pool": p.get("pool"), "source": p.get("source") }),
+            )
+        }
+        Err(msg) => Some(position_error("lending", "aave", chain, &msg)),
+    }
+}
+
+async fn comet_usd(
+    comet: &ICometV3Balances::ICometV3BalancesInstance<alloy::providers::RootProvider>,
+    amount: u128,
+    scale: u64,
+    price_feed: Address,
+) -> eyre::Result<f64> {
+    if amount == 0 {
+        return Ok(0.0_f64);
+    }
+    let px = comet
+        .getPrice(price_feed)
+        .call()
+        .await
+        .context("comet getPrice")?;
+    let price_usd = token_base_to_usd(u256_low_u128(px), USD_BASE_DECIMALS, 1.0_f64);
+    let decimals = u8::try_from(scale.max(1).ilog10()).unwrap_or(0);
+    Ok(token_base_to_usd(amount, decimals, price_usd))
+}
+
+/// The lending reader only reports Comet borrows, so supply and collateral are read here.
+async fn compound_position_values(
+    evm: &EvmChain,
+    comet_s: &str,
+    user: Address,
+) -> eyre::Result<(f64, f64)> {
+    let comet_addr = EvmChain::parse_address(comet_s).context("parse comet address")?;
+    let comet = ICometV3Balances::new(comet_addr, evm.provider()?);
+    let base_scale = comet.baseScale().call().await.context("comet baseScale")?;
+    let base_feed = comet
+        .baseTokenPriceFeed()
+        .call()
+        .await
+        .context("comet baseTokenPriceFeed")?;
+    let supplied = comet
+        .balanceOf(user)
+        .call()
+        .await
+        .context("comet balanceOf")?;
+    let borrowed = comet
+        .borrowBalanceOf(user)
+        .call()
+        .await
+        .context("comet borrowBalanceOf")?;
+
+    let mut values = vec![comet_usd(&comet, u256_low_u128(supplied), base_scale, base_feed).await?];
+    let n = comet.numAssets().call().await.context("comet numAssets")?;
+    for i in 0..n {
+        let info = comet
+            .getAssetInfo(i)
+            .call()
+            .await
+            .with_context(|| format!("comet getAssetInfo({i})"))?;
+        let bal = comet
+            .collateralBalanceOf(user, info.asset)
+            .call()
+            .await
+            .context("comet collateralBalanceOf")?;
+        values.push(comet_usd(&comet, bal, info.scale, info.priceFeed).await?);
+    }
+    let debt = comet_usd(&comet, u256_low_u128(borrowed), base_scale, base_feed).await?;
+    Ok((sum_f64(&values), debt))
+}
+
+async fn lido_position(
+    shared: &SharedState,
+    evm: &EvmChain,
+    owner: Address,
+) -> eyre::Result<Option<Value>> {
+    let steth = EvmChain::parse_address(LIDO_STETH).context("parse steth address")?;
+    let (bal, decimals, _symbol) = evm.get_erc20_balance(steth, owner).await?;
+    if bal.is_zero() {
+        return Ok(None);
+    }
+    // stETH tracks ETH 1:1.
+    let usd = price::native_token_price_usd_cached("ethereum", &shared.cfg, shared.db())
+        .await
+        .ok()
+        .map(|p| token_base_to_usd(u256_low_u128(bal), decimals, p.usd));
+    Ok(position(
+        "staking",
+        "lido",
+        "ethereum",
+        usd,
+        0.0_f64,
+        json!({ "token": LIDO_STETH, "symbol": "stETH", "amount_base": bal.to_string(), "decimals": decimals }),
+    ))
+}
+
+async fn evm_positions(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    w: &WalletRecord,
+    idx: u32,
+    chain: &str,
+    out: &mut Vec<Value>,
+) {
+    let Ok(owner) = evm_addr_for_account(w, idx) else {
+        return;
+    };
+    let Some(rpc_url) = shared.cfg.rpc.evm_rpc_urls.get(chain) else {
+        return;
+    };
+    let Some(chain_id) = shared.cfg.rpc.evm_chain_ids.get(chain) else {
+        return;
+    };
+    let mut evm = EvmChain::for_name(chain, *chain_id, rpc_url, &shared.cfg.http);
+    if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+        evm.fallback_rpc_urls.clone_from(fb);
+    }
+
+    if has_default_aave_pool(chain) {
+        out.extend(aave_position(shared, conn, w, idx, chain).await);
+    }
+    if let Some(comet) = default_comet_for_chain(chain) {
+        match compound_position_values(&evm, comet, owner).await {
+            Ok((supplied, debt)) => out.extend(position(
+                "lending",
+                "compound",
+                chain,
+                Some(supplied),
+                debt,
+                json!({ "comet": comet }),
+            )),
+            Err(e) => out.push(position_error(
+                "lending",
+                "compound",
+                chain,
+                &format!("{e:#}"),
+            )),
+        }
+    }
+    if chain == "ethereum" {
+        match lido_position(shared, &evm, owner).await {
+            Ok(p) => out.extend(p),
+            Err(e) => out.push(position_error("staking", "lido", chain, &format!("{e:#}"))),
+        }
+    }
+}
+
+async fn kamino_position(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    w: &WalletRecord,
+    idx: u32,
+) -> Option<Value> {
+    let args = reader_args(w, idx, &json!({ "chain": "solana", "protocol": "kamino" }));
+    match reader_payload(lending_positions::handle(Value::Null, args, shared, conn).await) {
+        Ok(p) => {
+            let obligations = p
+                .get("obligations")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            if obligations.is_empty() {
+                return None;
+            }
+            // Kamino's API reports per-obligation USD stats; unknown shapes stay unpriced.
+            let stat = |o: &Value, k: &str| num_f64(o.get("refreshedStats").and_then(|s| s.get(k)));
+            let deposits: Option<Vec<f64>> = obligations
+                .iter()
+                .map(|o| stat(o, "userTotalDeposit"))
+                .collect();
+            let borrows: Vec<f64> = obligations
+                .iter()
+                .filter_map(|o| stat(o, "userTotalBorrow"))
+                .collect();
+            position(
+                "lending",
+                "kamino",
+                "solana",
+                deposits.map(|d| sum_f64(&d)),
+                sum_f64(&borrows),
+                json!({ "market": p.get("market"), "obligations": obligations.len(), "source": p.get("source") }),
+            )
+        }
+        Err(msg) => Some(position_error("lending", "kamino", "solana", &msg)),
+    }
+}
+
+async fn jito_position(
+    shared: &SharedState,
+    sol: &SolanaChain,
+    owner: solana_sdk::pubkey::Pubkey,
+) -> eyre::Result<Option<Value>> {
+    let mint = SolanaChain::parse_pubkey(JITOSOL_MINT)?;
+    // A missing token account simply means no JitoSOL.
+    let Ok((bal, decimals)) = sol.get_spl_balance(owner, mint).await else {
+        return Ok(None);
+    };
+    if bal == 0 {
+        return Ok(None);
+    }
+    let one = 10_u64.checked_pow(u32::from(decimals)).unwrap_or(1);
+    let usd = price::solana_token_price_usd_cached(
+        sol,
+        &shared.cfg,
+        JITOSOL_MINT,
+        USDC_MINT,
+        one.max(1),
+        50,
+        shared.db(),
+    )
+    .await
+    .ok()
+    .map(|p| token_base_to_usd(u128::from(bal), decimals, p.usd));
+    Ok(position(
+        "staking",
+        "jito",
+        "solana",
+        usd,
+        0.0_f64,
+        json!({ "mint": JITOSOL_MINT, "symbol": "JitoSOL", "amount_base": bal.to_string(), "decimals": decimals }),
+    ))
+}
+
+async fn solana_positions(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    w: &WalletRecord,
+    idx: u32,
+    out: &mut Vec<Value>,
+) {
+    let Ok(owner) = sol_pubkey_for_account(w, idx) else {
+        return;
+    };
+    let mode = effective_network_mode(shared, conn);
+    // Kamino, Jito and Jupiter Perps are mainnet-only.
+    if mode != NetworkMode::Mainnet {
+        return;
+    }
+    out.extend(kamino_position(shared, conn, w, idx).await);
+
+    let sol = SolanaChain::new_with_fallbacks(
+        &shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(shared, mode),
+        &shared.cfg.http.jupiter_base_url,
+        shared.cfg.http.jupiter_api_key.as_deref(),
+        shared.cfg.rpc.solana_default_compute_unit_limit,
+        shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    );
+    match jito_position(shared, &sol, owner).await {
+        Ok(p) => out.extend(p),
+        Err(e) => out.push(position_error(
+            "staking",
+            "jito",
+            "solana",
+            &format!("{e:#}"),
+        )),
+    }
+
+    let args = reader_args(w, idx, &json!({ "provider": "jupiter_perps" }));
+    match reader_payload(handle_get_positions(Value::Null, &args, shared, conn).await) {
+        Ok(p) => {
+            let collateral: Vec<f64> = p
+                .get("positions")
+                .and_then(Value::as_array)
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|x| num_f64(x.get("collateral_usd")))
+                        .collect()
+                })
+                .unwrap_or_default();
+            out.extend(position(
+                "perp_collateral",
+                "jupiter_perps",
+                "solana",
+                Some(sum_f64(&collateral)),
+                0.0_f64,
+                json!({ "positions": collateral.len() }),
+            ));
+        }
+        Err(msg) => out.push(position_error(
+            "perp_collateral",
+            "jupiter_perps",
+            "solana",
+            &msg,
+        )),
+    }
+}
+
+async fn hyperliquid_position(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    w: &WalletRecord,
+    idx: u32,
+) -> Option<Value> {
+    let args = reader_args(w, idx, &json!({ "provider": "hyperliquid" }));
+    match reader_payload(handle_get_positions(Value::Null, &args, shared, conn).await) {
+        Ok(p) => {
+            let summary = p.get("state").and_then(|s| s.get("marginSummary"));
+            position(
+                "perp_collateral",
+                "hyperliquid",
+                "hyperliquid",
+                num_f64(summary.and_then(|s| s.get("accountValue"))),
+                0.0_f64,
+                json!({
+                  "total_margin_used": summary.and_then(|s| s.get("totalMarginUsed")),
+                  "cached": p.get("cached").and_then(Value::as_bool).unwrap_or(false)
+                }),
+            )
+        }
+        Err(msg) => Some(position_error(
+            "perp_collateral",
+            "hyperliquid",
+            "hyperliquid",
+            &msg,
+        )),
+    }
+}
+
+/// Staked, lent and perp-margin positions for one account across `chains`, best-effort: a failing
+/// protocol yields an `error` entry instead of failing the whole read.
+pub(super) async fn collect(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    w: &WalletRecord,
+    idx: u32,
+    chains: &[String],
+) -> Vec<Value> {
+    shared.ensure_db().await;
+    let mut out = vec![];
+    let mut any_evm = false;
+    for chain in chains {
+        match chain.as_str() {
+            "solana" => solana_positions(shared, conn, w, idx, &mut out).await,
+            "bitcoin" => {}
+            evm_chain => {
+                any_evm = true;
+                evm_positions(shared, conn, w, idx, evm_chain, &mut out).await;
+            }
+        }
+    }
+    // Hyperliquid margin is held by the account's EVM address, independent of any one chain.
+    if any_evm && w.evm_addresses.get(idx as usize).is_some() {
+        out.extend(hyperliquid_position(shared, conn, w, idx).await);
+    }
+    out
+}
+
+/// `(locked_usd, debt_usd)` across positions; error entries contribute nothing.
+pub(super) fn totals(positions: &[Value]) -> (f64, f64) {
+    let mut locked = 0.0_f64;
+    let mut debt = 0.0_f64;
+    for p in positions {
+        accum(
+            &mut locked,
+            p.get("usd_value")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0_f64),
+        );
+        accum(
+            &mut debt,
+            p.get("debt_usd").and_then(Value::as_f64).unwrap_or(0.0_f64),
+        );
+    }
+    (locked, debt)
+}
+
+/// Net value including positions: liquid + locked - debt.
+pub(super) fn net_total(liquid_usd: f64, locked_usd: f64, debt_usd: f64) -> f64 {
+    sub_f64(sum_f64(&[liquid_usd, locked_usd]), debt_usd)
+}
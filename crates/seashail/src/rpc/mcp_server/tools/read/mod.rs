@@ -1,14 +1,22 @@
+mod allowances_risk;
 mod balance;
+mod bridge_quote;
 mod bridge_status;
 mod defi_yield_pools;
 mod estimate_gas;
 mod inspect_token;
 mod lending_positions;
+mod locked_positions;
+mod network_fees;
+mod nonce_account;
+mod open_orders;
 mod portfolio;
 mod portfolio_analytics;
+mod portfolio_diff;
 mod prediction_markets;
 mod prediction_positions;
 mod pumpfun;
+mod supported_tokens;
 mod token_price;
 mod tx_history;
 
@@ -18,6 +26,8 @@ use tokio::io::BufReader;
 use super::super::jsonrpc::{err, JsonRpcResponse};
 use super::super::{ConnState, SharedState};
 
+pub(super) use balance::{default_chain_balances, native_balance_base};
+
 pub async fn handle<R, W>(
     req_id: Value,
     tool_name: &str,
@@ -32,13 +42,16 @@ where
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
     match tool_name {
-        "get_defi_yield_pools" => defi_yield_pools::handle(req_id, args).await,
+        "get_defi_yield_pools" => defi_yield_pools::handle(req_id, args, shared).await,
         "inspect_token" => inspect_token::handle(req_id, args, shared, conn).await,
+        "get_supported_tokens" => supported_tokens::handle(req_id, &args, shared).await,
         "get_balance" => balance::handle(req_id, args, shared, conn).await,
         "get_token_price" => token_price::handle(req_id, args, shared, conn).await,
         "estimate_gas" => estimate_gas::handle(req_id, args, shared, conn).await,
+        "get_network_fees" => network_fees::handle(req_id, &args, shared, conn).await,
         "get_portfolio" => portfolio::handle(req_id, args, shared, conn).await,
         "get_portfolio_analytics" => portfolio_analytics::handle(req_id, &args, shared).await,
+        "diff_portfolio" => portfolio_diff::handle(req_id, &args, shared).await,
         "get_transaction_history" => tx_history::handle(req_id, &args, shared),
         "pumpfun_list_new_coins" | "pumpfun_get_coin_info" => {
             pumpfun::handle(req_id, tool_name, args, shared).await
@@ -50,7 +63,11 @@ where
         "search_prediction_markets" | "get_prediction_orderbook" => {
             prediction_markets::handle(req_id, tool_name, args, shared, conn).await
         }
+        "get_open_orders" => open_orders::handle(req_id, &args, shared).await,
         "get_bridge_status" => bridge_status::handle(req_id, args, shared).await,
+        "get_bridge_quote" => bridge_quote::handle(req_id, &args, shared).await,
+        "get_nonce_account" => nonce_account::handle(req_id, &args, shared, conn).await,
+        "get_allowances_risk_report" => allowances_risk::handle(req_id, &args, shared).await,
         _ => Ok(err(req_id, -32601, "unknown tool")),
     }
 }
@@ -0,0 +1,288 @@
+use crate::{
+    chains::{bitcoin::BitcoinChain, evm::EvmChain, solana::SolanaChain},
+    config::NetworkMode,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tokio::task::{Id, JoinSet};
+
+use super::super::super::jsonrpc::{ok, tool_ok, JsonRpcResponse};
+use super::super::super::state::effective_network_mode;
+use super::super::super::{ConnState, SharedState};
+use super::super::helpers::solana_fallback_urls;
+
+const CACHE_TTL_MS: i64 = 15_000;
+const EVM_HISTORY_BLOCKS: u64 = 20;
+// Solana returns up to 150 recent slots; the newest slots are compared against the full window.
+const SOLANA_RECENT_SLOTS: usize = 20;
+
+/// Value at percentile `p` (0-100) of an ascending-sorted slice.
+fn percentile(sorted: &[u128], p: usize) -> Option<u128> {
+    let last = sorted.len().checked_sub(1)?;
+    sorted.get(last.saturating_mul(p.min(100)) / 100).copied()
+}
+
+fn median_of(values: &[u128]) -> Option<u128> {
+    let mut v = values.to_vec();
+    v.sort_unstable();
+    percentile(&v, 50)
+}
+
+/// Classify `current` against `baseline`: at or below `cheap_pct`% is cheap, at or above
+/// `expensive_pct`% is expensive.
+fn classify(current: u128, baseline: u128, cheap_pct: u128, expensive_pct: u128) -> &'static str {
+    if baseline == 0 {
+        return if current == 0 { "cheap" } else { "expensive" };
+    }
+    let ratio_pct = current.saturating_mul(100) / baseline;
+    if ratio_pct <= cheap_pct {
+        "cheap"
+    } else if ratio_pct >= expensive_pct {
+        "expensive"
+    } else {
+        "normal"
+    }
+}
+
+async fn evm_fees(evm: EvmChain) -> eyre::Result<Value> {
+    let gas_price = evm.get_gas_price().await?;
+    let hist = evm.get_fee_history(EVM_HISTORY_BLOCKS).await?;
+    // The final entry is the base fee for the next (pending) block.
+    let next_base_fee = hist.base_fee_per_gas.last().copied().unwrap_or(0);
+    let window = hist
+        .base_fee_per_gas
+        .split_last()
+        .map_or(&[][..], |(_, rest)| rest);
+    let baseline = median_of(window).unwrap_or(0);
+    let rewards = hist.reward.unwrap_or_default();
+    let reward_median = |i: usize| -> Option<String> {
+        let col: Vec<u128> = rewards.iter().filter_map(|r| r.get(i).copied()).collect();
+        median_of(&col).map(|v| v.to_string())
+    };
+    let eip1559 = next_base_fee > 0;
+    let level = if eip1559 {
+        classify(next_base_fee, baseline, 80, 125)
+    } else {
+        "unknown"
+    };
+    Ok(json!({
+      "chain": evm.name,
+      "kind": "evm",
+      "eip1559": eip1559,
+      "gas_price_wei": gas_price.to_string(),
+      "base_fee_wei": next_base_fee.to_string(),
+      "baseline_base_fee_wei": baseline.to_string(),
+      "priority_fee_wei": {
+        "p25": reward_median(0),
+        "p50": reward_median(1),
+        "p75": reward_median(2)
+      },
+      "history_blocks": EVM_HISTORY_BLOCKS,
+      "level": level
+    }))
+}
+
+async fn solana_fees(sol: SolanaChain) -> eyre::Result<Value> {
+    let fees: Vec<u128> = sol
+        .get_recent_prioritization_fees()
+        .await?
+        .into_iter()
+        .map(u128::from)
+        .collect();
+    let recent = fees
+        .len()
+        .checked_sub(SOLANA_RECENT_SLOTS)
+        .and_then(|start| fees.get(start..))
+        .unwrap_or(&fees);
+    let current = median_of(recent).unwrap_or(0);
+    let baseline = median_of(&fees).unwrap_or(0);
+    let mut sorted = fees.clone();
+    sorted.sort_unstable();
+    let pct = |p: usize| percentile(&sorted, p).map(|v| v.to_string());
+    Ok(json!({
+      "chain": "solana",
+      "kind": "solana",
+      "priority_fee_micro_lamports_per_cu": {
+        "p25": pct(25),
+        "p50": pct(50),
+        "p75": pct(75),
+        "p90": pct(90),
+        "max": pct(100),
+        "recent_median": current.to_string()
+      },
+      "history_slots": fees.len(),
+      "level": classify(current, baseline, 80, 125)
+    }))
+}
+
+async fn bitcoin_fees(btc: BitcoinChain) -> eyre::Result<Value> {
+    let est = btc.fee_estimates_sats_per_vb().await?;
+    let at = |target: u32| est.get(&target).copied();
+    let next_block = at(1).or_else(|| est.values().next().copied()).unwrap_or(1);
+    let day = at(144)
+        .or_else(|| est.values().next_back().copied())
+        .unwrap_or(1);
+    // Blockstream has no fee history; the urgency premium over the ~1 day target is the best
+    // available congestion signal. A next-block premium under 2x is quiet, 5x+ is congested.
+    Ok(json!({
+      "chain": "bitcoin",
+      "kind": "bitcoin",
+      "fee_rate_sats_per_vb": {
+        "next_block": next_block,
+        "3_blocks": at(3),
+        "6_blocks": at(6),
+        "144_blocks": at(144)
+      },
+      "level": classify(u128::from(next_block), u128::from(day), 200, 500)
+    }))
+}
+
+enum FeeSource {
+    Solana(SolanaChain),
+    Evm(EvmChain),
+    Bitcoin(BitcoinChain),
+}
+
+fn fee_source(shared: &SharedState, mode: NetworkMode, chain: &str) -> eyre::Result<FeeSource> {
+    if chain == "solana" {
+        return Ok(FeeSource::Solana(SolanaChain::new_with_fallbacks(
+            &shared.cfg.rpc.solana_rpc_url,
+            solana_fallback_urls(shared, mode),
+            &shared.cfg.http.jupiter_base_url,
+            shared.cfg.http.jupiter_api_key.as_deref(),
+            shared.cfg.rpc.solana_default_compute_unit_limit,
+            shared
+                .cfg
+                .rpc
+                .solana_default_compute_unit_price_micro_lamports,
+        )));
+    }
+    if chain == "bitcoin" {
+        let base = if mode == NetworkMode::Testnet {
+            &shared.cfg.http.bitcoin_api_base_url_testnet
+        } else {
+            &shared.cfg.http.bitcoin_api_base_url_mainnet
+        };
+        return Ok(FeeSource::Bitcoin(BitcoinChain::new(base)?));
+    }
+    let rpc_url = shared
+        .cfg
+        .rpc
+        .evm_rpc_urls
+        .get(chain)
+        .ok_or_else(|| eyre::eyre!("unknown evm chain: {chain}"))?;
+    let chain_id = *shared
+        .cfg
+        .rpc
+        .evm_chain_ids
+        .get(chain)
+        .ok_or_else(|| eyre::eyre!("missing evm chain id: {chain}"))?;
+    let mut evm = EvmChain::for_name(chain, chain_id, rpc_url, &shared.cfg.http);
+    if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+        evm.fallback_rpc_urls.clone_from(fb);
+    }
+    Ok(FeeSource::Evm(evm))
+}
+
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &mut SharedState,
+    conn: &ConnState,
+) -> eyre::Result<JsonRpcResponse> {
+    let mode = effective_network_mode(shared, conn);
+    let chains: Vec<String> = match args.get("chains").and_then(Value::as_array) {
+        Some(a) if !a.is_empty() => a
+            .iter()
+            .filter_map(|x| x.as_str().map(|s| s.trim().to_owned()))
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => {
+            let mut c = shared.cfg.default_chains_for_mode(mode);
+            c.push("bitcoin".to_owned());
+            c
+        }
+    };
+
+    shared.ensure_db().await;
+    let now_ms = crate::db::Db::now_ms()?;
+    let mode_s = if mode == NetworkMode::Testnet {
+        "testnet"
+    } else {
+        "mainnet"
+    };
+
+    let mut items: Vec<(usize, Value)> = Vec::with_capacity(chains.len());
+    let mut tasks = JoinSet::new();
+    let mut task_chain: HashMap<Id, usize> = HashMap::new();
+    for (i, chain) in chains.iter().enumerate() {
+        let cache_key = format!("fees:{mode_s}:{chain}");
+        if let Some(db) = shared.db() {
+            if let Ok(Some(row)) = db.get_json_if_fresh(&cache_key, now_ms).await {
+                if let Ok(v) = serde_json::from_str::<Value>(&row.json) {
+                    items.push((i, v));
+                    continue;
+                }
+            }
+        }
+        let source = match fee_source(shared, mode, chain) {
+            Ok(s) => s,
+            Err(e) => {
+                items.push((i, json!({ "chain": chain, "error": format!("{e:#}") })));
+                continue;
+            }
+        };
+        let handle = tasks.spawn(async move {
+            let r = match source {
+                FeeSource::Solana(sol) => solana_fees(sol).await,
+                FeeSource::Evm(evm) => evm_fees(evm).await,
+                FeeSource::Bitcoin(btc) => bitcoin_fees(btc).await,
+            };
+            (i, cache_key, r)
+        });
+        task_chain.insert(handle.id(), i);
+    }
+
+    while let Some(joined) = tasks.join_next_with_id().await {
+        let (i, cache_key, r) = match joined {
+            Ok((_, v)) => v,
+            Err(e) => {
+                // A panicked task still gets an entry, so no requested chain silently disappears.
+                if let Some(i) = task_chain.get(&e.id()).copied() {
+                    let chain = chains.get(i).cloned().unwrap_or_default();
+                    items.push((
+                        i,
+                        json!({ "chain": chain, "error": format!("fee lookup failed: {e}") }),
+                    ));
+                }
+                continue;
+            }
+        };
+        let chain = chains.get(i).cloned().unwrap_or_default();
+        match r {
+            Ok(v) => {
+                if let Some(db) = shared.db() {
+                    let _cache_write = db
+                        .upsert_json(
+                            &cache_key,
+                            &v.to_string(),
+                            now_ms,
+                            now_ms.saturating_add(CACHE_TTL_MS),
+                        )
+                        .await;
+                }
+                items.push((i, v));
+            }
+            Err(e) => items.push((i, json!({ "chain": chain, "error": format!("{e:#}") }))),
+        }
+    }
+    items.sort_by_key(|(i, _)| *i);
+
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "network_mode": mode_s,
+          "chains": items.into_iter().map(|(_, v)| v).collect::<Vec<_>>()
+        })),
+    ))
+}
@@ -0,0 +1,83 @@
+use serde_json::{json, Value};
+
+use crate::{
+    chains::solana::{self as sol_chain, SolanaChain},
+    errors::ToolError,
+};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::state::effective_network_mode;
+use super::super::super::{ConnState, SharedState};
+use super::super::helpers::{
+    resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+};
+
+/// State of the wallet account's `nonce_index`-th durable nonce account (Solana).
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &SharedState,
+    conn: &ConnState,
+) -> eyre::Result<JsonRpcResponse> {
+    let nonce_index = match args.get("nonce_index") {
+        None | Some(Value::Null) => 0,
+        Some(v) => {
+            let Some(n) = v.as_u64().and_then(|n| u32::try_from(n).ok()) else {
+                return Ok(ok(
+                    req_id,
+                    tool_err(ToolError::new(
+                        "invalid_request",
+                        "nonce_index must be a non-negative integer",
+                    )),
+                ));
+            };
+            n
+        }
+    };
+    let (w, idx) = resolve_wallet_and_account(shared, args)?;
+    let authority = sol_pubkey_for_account(&w, idx)?;
+    let nonce = sol_chain::nonce_account_address(&authority, nonce_index)?;
+
+    let mode = effective_network_mode(shared, conn);
+    let sol = SolanaChain::new_with_fallbacks(
+        &shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(shared, mode),
+        &shared.cfg.http.jupiter_base_url,
+        shared.cfg.http.jupiter_api_key.as_deref(),
+        shared.cfg.rpc.solana_default_compute_unit_limit,
+        shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    );
+    let Some(acct) = sol.get_account_optional(&nonce).await? else {
+        return Ok(ok(
+            req_id,
+            tool_ok(json!({
+              "chain": "solana",
+              "wallet": w.name,
+              "account_index": idx,
+              "nonce_index": nonce_index,
+              "nonce_account": nonce.to_string(),
+              "exists": false,
+            })),
+        ));
+    };
+    let state = sol.get_nonce_account(&nonce).await?;
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "chain": "solana",
+          "wallet": w.name,
+          "account_index": idx,
+          "nonce_index": nonce_index,
+          "nonce_account": nonce.to_string(),
+          "exists": true,
+          "initialized": state.is_some(),
+          "lamports": acct.lamports.to_string(),
+          "authority": state.as_ref().map(|s| s.authority.to_string()),
+          "durable_nonce": state.as_ref().map(|s| s.nonce.to_string()),
+          "lamports_per_signature": state.as_ref().map(|s| s.lamports_per_signature),
+        })),
+    ))
+}
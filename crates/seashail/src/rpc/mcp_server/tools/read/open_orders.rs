@@ -0,0 +1,72 @@
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+use super::super::helpers::{resolve_wallet_and_account, sol_pubkey_for_account};
+use crate::{errors::ToolError, jupiter_trigger};
+
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("solana");
+    if chain != "solana" {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "unsupported_chain",
+                "spot limit orders are currently supported on solana only (Jupiter Trigger API)",
+            )),
+        ));
+    }
+    let include_history = args
+        .get("include_history")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let (w, idx) = resolve_wallet_and_account(shared, args)?;
+    let owner = sol_pubkey_for_account(&w, idx)?.to_string();
+    let now = chrono::Utc::now().timestamp();
+
+    let active = match jupiter_trigger::get_orders(&shared.cfg.http, &owner, "active").await {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new("jupiter_trigger_error", format!("{e:#}"))),
+            ))
+        }
+    };
+    let orders: Vec<Value> = active
+        .iter()
+        .map(|o| jupiter_trigger::normalize_order(o, now))
+        .collect();
+
+    let mut out = json!({
+      "chain": "solana",
+      "provider": "jupiter",
+      "wallet": w.name,
+      "account_index": idx,
+      "owner": owner,
+      "orders": orders,
+    });
+    if include_history {
+        // History is informational; don't fail the whole read if it is unavailable.
+        let history: Vec<Value> = jupiter_trigger::get_orders(&shared.cfg.http, &owner, "history")
+            .await
+            .unwrap_or_default()
+            .iter()
+            .map(|o| jupiter_trigger::normalize_order(o, now))
+            .collect();
+        if let Some(obj) = out.as_object_mut() {
+            obj.insert("history".to_owned(), json!(history));
+        }
+    }
+    Ok(ok(req_id, tool_ok(out)))
+}
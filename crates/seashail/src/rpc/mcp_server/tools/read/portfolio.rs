@@ -1,5 +1,5 @@
 use crate::{
-    chains::{evm::EvmChain, solana::SolanaChain},
+    chains::{evm::EvmChain, native::native_token, solana::SolanaChain},
     financial_math::{lamports_to_usd, token_base_to_usd},
     keystore::Keystore,
     price,
@@ -15,6 +15,7 @@ use super::super::helpers::{
     evm_addr_for_account, parse_portfolio_tokens_map, sol_pubkey_for_account, solana_fallback_urls,
     u256_pow10,
 };
+use super::locked_positions;
 
 async fn solana_chain_item(
     shared: &SharedState,
@@ -135,15 +136,23 @@ async fn evm_chain_item(
     }
     let owner = evm_addr_for_account(w, account_index)?;
     let wei = evm.get_native_balance(owner).await.unwrap_or_default();
+    let native = native_token(chain);
     let p = price::native_token_price_usd_cached(chain, &shared.cfg, db).await;
     let usd = p
-        .map(|pp| token_base_to_usd(crate::chains::evm::u256_low_u128(wei), 18, pp.usd))
+        .map(|pp| {
+            token_base_to_usd(
+                crate::chains::evm::u256_low_u128(wei),
+                native.decimals,
+                pp.usd,
+            )
+        })
         .unwrap_or(0.0_f64);
 
     let mut chain_tokens = vec![json!({
       "token": "native",
+      "symbol": native.symbol,
       "amount_base": wei.to_string(),
-      "decimals": 18_i32,
+      "decimals": native.decimals,
       "usd_value": usd
     })];
 
@@ -190,7 +199,7 @@ async fn evm_chain_item(
       "wallet": w.name,
       "account_index": account_index,
       "chain": chain,
-      "native": { "amount_wei": wei.to_string() },
+      "native": { "symbol": native.symbol, "amount_wei": wei.to_string() },
       "tokens": chain_tokens,
       "usd_value": chain_total_usd
     });
@@ -211,6 +220,10 @@ pub async fn handle(
         .get("include_health")
         .and_then(Value::as_bool)
         .unwrap_or(false);
+    let include_positions = args
+        .get("include_positions")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
     let history_limit = args
         .get("history_limit")
         .and_then(Value::as_u64)
@@ -229,7 +242,6 @@ pub async fn handle(
     });
     let tokens_map = parse_portfolio_tokens_map(&args);
     shared.ensure_db().await;
-    let db = shared.db();
 
     let wallets = shared.ks.list_wallets()?;
     // Use `as_ref()` so we don't move `wallet_filter`; we may include it in the history scope below.
@@ -250,6 +262,25 @@ pub async fn handle(
         }
     }
 
+    // Position readers need `&mut SharedState`, so collect them before borrowing the DB.
+    let mut positions = vec![];
+    if include_positions {
+        for w in &selected_wallets {
+            for account_index in 0..w.accounts {
+                for mut p in
+                    locked_positions::collect(shared, conn, w, account_index, &chains).await
+                {
+                    if let Some(o) = p.as_object_mut() {
+                        o.insert("wallet".into(), json!(w.name));
+                        o.insert("account_index".into(), json!(account_index));
+                    }
+                    positions.push(p);
+                }
+            }
+        }
+    }
+
+    let db = shared.db();
     let mut items = vec![];
     let mut total_usd = 0.0_f64;
     for w in selected_wallets {
@@ -291,11 +322,29 @@ pub async fn handle(
         None
     };
 
+    if !include_positions {
+        return Ok(ok(
+            req_id,
+            tool_ok(json!({
+              "items": items,
+              "total_usd": total_usd,
+              "pnl": pnl_out,
+              "history": history_out,
+              "health": health_out
+            })),
+        ));
+    }
+
+    let (locked_usd, debt_usd) = locked_positions::totals(&positions);
     Ok(ok(
         req_id,
         tool_ok(json!({
           "items": items,
-          "total_usd": total_usd,
+          "locked_positions": positions,
+          "liquid_usd": total_usd,
+          "locked_usd": locked_usd,
+          "debt_usd": debt_usd,
+          "total_usd": locked_positions::net_total(total_usd, locked_usd, debt_usd),
           "pnl": pnl_out,
           "history": history_out,
           "health": health_out
@@ -62,7 +62,7 @@ fn aggregate_items(items: &[Value]) -> (f64, ByType, ByType, ByDay) {
     (total_usd, by_type, by_chain, by_day)
 }
 
-fn parse_scope(args: &Value, shared: &SharedState) -> Option<(Value, String)> {
+pub(super) fn parse_scope(args: &Value, shared: &SharedState) -> Option<(Value, String)> {
     // This scope JSON is shared with `get_portfolio(include_history=true)` so callers can
     // query snapshot-based P&L from the same persisted history.
     let scope_in = args.get("snapshot_scope").and_then(Value::as_object);
@@ -0,0 +1,421 @@
+use serde_json::{json, Value};
+
+use std::collections::BTreeMap;
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+use super::portfolio_analytics::parse_scope;
+use crate::db::{Db, PortfolioSnapshotItemRow, PortfolioSnapshotTotalRow};
+use crate::errors::ToolError;
+use crate::financial_math::{abs_f64, accum, div_f64, mul_f64, sub_f64, token_base_to_usd};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// (wallet, account_index, chain, token)
+type HoldingKey = (String, i64, String, String);
+
+/// One token line from a persisted `get_portfolio` snapshot item.
+#[derive(Debug, Clone)]
+struct Holding {
+    symbol: Option<String>,
+    amount_base: u128,
+    decimals: u8,
+    usd_value: f64,
+}
+
+impl Holding {
+    fn amount_ui(&self) -> f64 {
+        token_base_to_usd(self.amount_base, self.decimals, 1.0_f64)
+    }
+
+    /// USD price per whole token implied by the snapshot (`None` when it was not priced).
+    fn price(&self) -> Option<f64> {
+        let amount = self.amount_ui();
+        (amount > 0.0_f64 && self.usd_value > 0.0_f64).then(|| div_f64(self.usd_value, amount))
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+          "amount_base": self.amount_base.to_string(),
+          "amount": self.amount_ui(),
+          "usd_value": self.usd_value,
+          "price_usd": self.price()
+        })
+    }
+}
+
+/// USD change for one holding (or a group of holdings), split by cause.
+#[derive(Debug, Clone, Copy, Default)]
+struct Effects {
+    from_usd: f64,
+    to_usd: f64,
+    delta: f64,
+    price: f64,
+    quantity: f64,
+    unattributed: f64,
+}
+
+impl Effects {
+    fn add(&mut self, other: &Self) {
+        accum(&mut self.from_usd, other.from_usd);
+        accum(&mut self.to_usd, other.to_usd);
+        accum(&mut self.delta, other.delta);
+        accum(&mut self.price, other.price);
+        accum(&mut self.quantity, other.quantity);
+        accum(&mut self.unattributed, other.unattributed);
+    }
+
+    fn to_json(self) -> Value {
+        let delta_pct = (self.from_usd > 0.0_f64)
+            .then(|| mul_f64(div_f64(self.delta, self.from_usd), 100.0_f64));
+        json!({
+          "from_usd": self.from_usd,
+          "to_usd": self.to_usd,
+          "delta_usd": self.delta,
+          "delta_pct": delta_pct,
+          "price_effect_usd": self.price,
+          "quantity_effect_usd": self.quantity,
+          "unattributed_usd": self.unattributed
+        })
+    }
+}
+
+/// Split a holding's USD change into a quantity-driven part (valued at the old price) and a
+/// price-driven part (applied to the new quantity), so the two always sum to the total delta.
+/// When only one side was priced, the quantity part uses that price and any remainder is left
+/// unattributed rather than guessed.
+fn decompose(from: Option<&Holding>, to: Option<&Holding>) -> Effects {
+    let from_usd = from.map_or(0.0_f64, |h| h.usd_value);
+    let to_usd = to.map_or(0.0_f64, |h| h.usd_value);
+    let delta = sub_f64(to_usd, from_usd);
+    let mut e = Effects {
+        from_usd,
+        to_usd,
+        delta,
+        ..Effects::default()
+    };
+    let (Some(f), Some(t)) = (from, to) else {
+        // Added or removed entirely: the whole change is a quantity change.
+        e.quantity = delta;
+        return e;
+    };
+    let qty_delta = sub_f64(t.amount_ui(), f.amount_ui());
+    match (f.price(), t.price()) {
+        (Some(p1), Some(p2)) => {
+            e.quantity = mul_f64(qty_delta, p1);
+            e.price = mul_f64(sub_f64(p2, p1), t.amount_ui());
+        }
+        (Some(p), None) | (None, Some(p)) => {
+            e.quantity = mul_f64(qty_delta, p);
+            e.unattributed = sub_f64(delta, e.quantity);
+        }
+        (None, None) => e.unattributed = delta,
+    }
+    e
+}
+
+fn signed_base_delta(from: u128, to: u128) -> String {
+    if to >= from {
+        to.saturating_sub(from).to_string()
+    } else {
+        format!("-{}", from.saturating_sub(to))
+    }
+}
+
+fn holdings_from_items(items: &[PortfolioSnapshotItemRow]) -> BTreeMap<HoldingKey, Holding> {
+    let mut out = BTreeMap::new();
+    for item in items {
+        let Ok(payload) = serde_json::from_str::<Value>(&item.payload_json) else {
+            continue;
+        };
+        let Some(tokens) = payload.get("tokens").and_then(Value::as_array) else {
+            continue;
+        };
+        for t in tokens {
+            let Some(token) = t.get("token").and_then(Value::as_str) else {
+                continue;
+            };
+            let usd_value = t
+                .get("usd_value")
+                .and_then(Value::as_f64)
+                .filter(|v| v.is_finite() && *v >= 0.0_f64)
+                .unwrap_or(0.0_f64);
+            let holding = Holding {
+                symbol: t
+                    .get("symbol")
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned),
+                amount_base: t
+                    .get("amount_base")
+                    .and_then(Value::as_str)
+                    .and_then(|s| s.parse::<u128>().ok())
+                    .unwrap_or(0),
+                decimals: t
+                    .get("decimals")
+                    .and_then(Value::as_u64)
+                    .and_then(|d| u8::try_from(d).ok())
+                    .unwrap_or(0),
+                usd_value,
+            };
+            out.insert(
+                (
+                    item.wallet.clone(),
+                    item.account_index,
+                    item.chain.clone(),
+                    token.to_owned(),
+                ),
+                holding,
+            );
+        }
+    }
+    out
+}
+
+enum Selector {
+    Id(i64),
+    AtOrBefore(i64),
+}
+
+fn parse_ts_ms(args: &Value, key: &str) -> Result<Option<i64>, ToolError> {
+    let s = args
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    if s.is_empty() {
+        return Ok(None);
+    }
+    chrono::DateTime::parse_from_rfc3339(s)
+        .map(|ts| Some(ts.timestamp_millis()))
+        .map_err(|_e| ToolError::new("invalid_request", format!("{key} must be RFC3339")))
+}
+
+fn parse_selectors(
+    args: &Value,
+    now_ms: i64,
+) -> Result<(Option<Selector>, Option<Selector>), ToolError> {
+    let to = match args.get("to_snapshot_id").and_then(Value::as_i64) {
+        Some(id) => Some(Selector::Id(id)),
+        None => parse_ts_ms(args, "to_ts")?.map(Selector::AtOrBefore),
+    };
+    let from = if let Some(id) = args.get("from_snapshot_id").and_then(Value::as_i64) {
+        Some(Selector::Id(id))
+    } else if let Some(ms) = parse_ts_ms(args, "from_ts")? {
+        Some(Selector::AtOrBefore(ms))
+    } else if let Some(days) = args.get("days_ago").and_then(Value::as_u64) {
+        if days == 0 {
+            return Err(ToolError::new("invalid_request", "days_ago must be >= 1"));
+        }
+        let back_ms = i64::try_from(days)
+            .ok()
+            .and_then(|d| d.checked_mul(DAY_MS))
+            .ok_or_else(|| ToolError::new("invalid_request", "days_ago is too large"))?;
+        Some(Selector::AtOrBefore(now_ms.saturating_sub(back_ms)))
+    } else {
+        None
+    };
+    Ok((from, to))
+}
+
+async fn resolve(
+    db: &Db,
+    scope_json: &str,
+    sel: &Selector,
+) -> Result<Option<PortfolioSnapshotTotalRow>, ToolError> {
+    let res = match *sel {
+        Selector::Id(id) => db.portfolio_snapshot_total_by_id(scope_json, id).await,
+        Selector::AtOrBefore(ms) => {
+            db.portfolio_snapshot_total_at_or_before(scope_json, ms)
+                .await
+        }
+    };
+    res.map_err(|e| ToolError::new("internal_error", format!("read portfolio snapshot: {e:#}")))
+}
+
+/// Resolve the (from, to) snapshot pair. `to` defaults to the latest snapshot and `from` to the
+/// one immediately before it.
+async fn select_snapshots(
+    db: &Db,
+    scope_json: &str,
+    args: &Value,
+) -> Result<(PortfolioSnapshotTotalRow, PortfolioSnapshotTotalRow), ToolError> {
+    let now_ms = Db::now_ms().unwrap_or(0);
+    let (from_sel, to_sel) = parse_selectors(args, now_ms)?;
+    let to = match to_sel {
+        Some(sel) => resolve(db, scope_json, &sel).await?,
+        None => resolve(db, scope_json, &Selector::AtOrBefore(i64::MAX)).await?,
+    }
+    .ok_or_else(|| {
+        ToolError::new(
+            "no_snapshot",
+            "no matching `to` snapshot for this scope; call get_portfolio(include_history=true) first",
+        )
+    })?;
+    let from_sel =
+        from_sel.unwrap_or_else(|| Selector::AtOrBefore(to.fetched_at_ms.saturating_sub(1)));
+    let from = resolve(db, scope_json, &from_sel).await?.ok_or_else(|| {
+        ToolError::new(
+            "no_snapshot",
+            "no matching `from` snapshot for this scope (history may not reach back that far)",
+        )
+    })?;
+    if from.snapshot_id == to.snapshot_id {
+        return Err(ToolError::new(
+            "no_snapshot",
+            "from and to resolve to the same snapshot; capture more history with get_portfolio(include_history=true)",
+        ));
+    }
+    if from.fetched_at_ms > to.fetched_at_ms {
+        return Err(ToolError::new(
+            "invalid_request",
+            "from snapshot must be older than to snapshot",
+        ));
+    }
+    Ok((from, to))
+}
+
+fn snapshot_json(r: &PortfolioSnapshotTotalRow) -> Value {
+    json!({
+      "snapshot_id": r.snapshot_id,
+      "fetched_at_ms": r.fetched_at_ms,
+      "day": r.day,
+      "total_usd": r.total_usd
+    })
+}
+
+/// Diff two holding sets into per-token rows plus per-chain and overall totals.
+fn diff_holdings(
+    from: &BTreeMap<HoldingKey, Holding>,
+    to: &BTreeMap<HoldingKey, Holding>,
+    include_unchanged: bool,
+) -> (Vec<Value>, BTreeMap<String, Effects>, Effects) {
+    let mut keys: Vec<&HoldingKey> = from.keys().chain(to.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut tokens_out = vec![];
+    let mut by_chain: BTreeMap<String, Effects> = BTreeMap::new();
+    let mut total = Effects::default();
+    for key in keys {
+        let f = from.get(key);
+        let t = to.get(key);
+        let status = match (f, t) {
+            (None, Some(_)) => "added",
+            (Some(_), None) => "removed",
+            (Some(a), Some(b))
+                if a.amount_base == b.amount_base
+                    && abs_f64(sub_f64(a.usd_value, b.usd_value)) < 1e-9_f64 =>
+            {
+                "unchanged"
+            }
+            _ => "changed",
+        };
+        let e = decompose(f, t);
+        by_chain.entry(key.2.clone()).or_default().add(&e);
+        total.add(&e);
+        if status == "unchanged" && !include_unchanged {
+            continue;
+        }
+
+        let from_base = f.map_or(0, |h| h.amount_base);
+        let to_base = t.map_or(0, |h| h.amount_base);
+        let from_ui = f.map_or(0.0_f64, Holding::amount_ui);
+        let to_ui = t.map_or(0.0_f64, Holding::amount_ui);
+        let (wallet, account_index, chain, token) = key;
+        tokens_out.push(json!({
+          "wallet": wallet,
+          "account_index": account_index,
+          "chain": chain,
+          "token": token,
+          "symbol": t.or(f).and_then(|h| h.symbol.clone()),
+          "decimals": t.or(f).map(|h| h.decimals),
+          "status": status,
+          "from": f.map(Holding::to_json),
+          "to": t.map(Holding::to_json),
+          "amount_delta_base": signed_base_delta(from_base, to_base),
+          "amount_delta": sub_f64(to_ui, from_ui),
+          "usd_delta": e.delta,
+          "price_effect_usd": e.price,
+          "quantity_effect_usd": e.quantity,
+          "unattributed_usd": e.unattributed
+        }));
+    }
+    (tokens_out, by_chain, total)
+}
+
+/// Compare two persisted portfolio snapshots (same scope as `get_portfolio(include_history=true)`).
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &mut SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let Some((scope, scope_json)) = parse_scope(args, shared) else {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new("invalid_request", "no chains in scope")),
+        ));
+    };
+    let include_unchanged = args
+        .get("include_unchanged")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    shared.ensure_db().await;
+    let Some(db) = shared.db() else {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "internal_error",
+                "portfolio history database is unavailable",
+            )),
+        ));
+    };
+    let (from, to) = match select_snapshots(db, &scope_json, args).await {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
+    let (from_items, to_items) = match (
+        db.list_portfolio_snapshot_items(from.snapshot_id).await,
+        db.list_portfolio_snapshot_items(to.snapshot_id).await,
+    ) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new(
+                    "internal_error",
+                    format!("read portfolio snapshot items: {e:#}"),
+                )),
+            ));
+        }
+    };
+
+    let (tokens_out, by_chain, total) = diff_holdings(
+        &holdings_from_items(&from_items),
+        &holdings_from_items(&to_items),
+        include_unchanged,
+    );
+    let by_chain_out: Vec<Value> = by_chain
+        .into_iter()
+        .map(|(chain, e)| {
+            let mut v = e.to_json();
+            if let Some(o) = v.as_object_mut() {
+                o.insert("chain".into(), Value::String(chain));
+            }
+            v
+        })
+        .collect();
+
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "scope": scope,
+          "from": snapshot_json(&from),
+          "to": snapshot_json(&to),
+          "elapsed_ms": to.fetched_at_ms.saturating_sub(from.fetched_at_ms),
+          "pnl": total.to_json(),
+          "by_chain": by_chain_out,
+          "tokens": tokens_out
+        })),
+    ))
+}
@@ -4,6 +4,7 @@ use crate::errors::ToolError;
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::{ConnState, SharedState};
+use super::super::value_helpers::{parse_verbosity, Verbosity};
 
 fn arg_str<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
     args.get(key)
@@ -103,10 +104,10 @@ fn coerce_vec_str(v: &Value) -> Vec<String> {
         .collect()
 }
 
-fn normalize_search_results(v: &Value) -> Value {
+fn normalize_search_results(v: &Value, verbosity: Verbosity) -> Value {
     let mut markets_flat: Vec<Value> = vec![];
     let Some(events) = v.get("events").and_then(Value::as_array) else {
-        return json!({ "raw": v, "markets": markets_flat });
+        return search_results(v, markets_flat, verbosity);
     };
     for ev in events {
         let ev_title = ev.get("title").and_then(Value::as_str).unwrap_or("").trim();
@@ -149,21 +150,39 @@ fn normalize_search_results(v: &Value) -> Value {
                 }));
             }
 
+            let market = if verbosity == Verbosity::Full {
+                json!({
+                  "question": question,
+                  "slug": slug,
+                  "condition_id": condition_id,
+                  "outcomes": outcomes,
+                  "clob_token_ids": token_ids,
+                  "outcome_prices": prices,
+                  "outcome_tokens": outcome_tokens
+                })
+            } else {
+                // `outcome_tokens` already zips outcomes, token ids and prices.
+                json!({
+                  "question": question,
+                  "slug": slug,
+                  "condition_id": condition_id,
+                  "outcome_tokens": outcome_tokens
+                })
+            };
             markets_flat.push(json!({
               "event": { "id": ev_id, "title": ev_title, "slug": ev_slug },
-              "market": {
-                "question": question,
-                "slug": slug,
-                "condition_id": condition_id,
-                "outcomes": outcomes,
-                "clob_token_ids": token_ids,
-                "outcome_prices": prices,
-                "outcome_tokens": outcome_tokens
-              }
+              "market": market
             }));
         }
     }
-    json!({ "raw": v, "markets": markets_flat })
+    search_results(v, markets_flat, verbosity)
+}
+
+fn search_results(raw: &Value, markets: Vec<Value>, verbosity: Verbosity) -> Value {
+    match verbosity {
+        Verbosity::Full => json!({ "raw": raw, "markets": markets }),
+        Verbosity::Compact => json!({ "markets": markets }),
+    }
 }
 
 pub async fn handle(
@@ -233,6 +252,10 @@ async fn handle_search(
             tool_err(ToolError::new("invalid_request", "missing query")),
         ));
     };
+    let verbosity = match parse_verbosity(args) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
     let limit = arg_u64(args, "limit").unwrap_or(10).clamp(1, 100);
     let page = arg_u64(args, "page").unwrap_or(1).max(1);
     let include_closed = arg_bool(args, "include_closed").unwrap_or(false);
@@ -303,7 +326,7 @@ async fn handle_search(
         }
     };
 
-    let normalized = normalize_search_results(&v);
+    let normalized = normalize_search_results(&v, verbosity);
     Ok(ok(
         req_id,
         tool_ok(json!({
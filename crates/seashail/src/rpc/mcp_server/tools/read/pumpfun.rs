@@ -8,6 +8,7 @@ use std::str::FromStr as _;
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::SharedState;
+use super::super::value_helpers::{parse_verbosity, project_response};
 
 // pump.fun program id (mainnet). Used only for RPC-only discovery mode.
 const PUMPFUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
@@ -571,6 +572,47 @@ async fn handle_get_coin_info(
     ))
 }
 
+/// Compact `pumpfun_list_new_coins`: mint, curve address, age and curve progress per coin.
+/// Adapter payloads that are not an item array pass through unchanged.
+fn compact_new_coins(payload: &Value) -> Value {
+    let Some(items) = payload.get("items").and_then(Value::as_array) else {
+        return payload.clone();
+    };
+    let compact: Vec<Value> = items
+        .iter()
+        .map(|it| {
+            let Some(o) = it.as_object() else {
+                return it.clone();
+            };
+            let mut out = serde_json::Map::new();
+            for k in ["mint", "bonding_curve", "block_time"] {
+                if let Some(v) = o.get(k) {
+                    out.insert(k.to_owned(), v.clone());
+                }
+            }
+            if let Some(curve) = o.get("curve").and_then(Value::as_object) {
+                let kept: serde_json::Map<String, Value> = curve
+                    .iter()
+                    .filter(|(k, _)| {
+                        matches!(
+                            k.as_str(),
+                            "complete" | "virtual_sol_reserves" | "virtual_token_reserves"
+                        )
+                    })
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                out.insert("curve".into(), Value::Object(kept));
+            }
+            Value::Object(out)
+        })
+        .collect();
+    let mut out = payload.clone();
+    if let Some(o) = out.as_object_mut() {
+        o.insert("items".into(), Value::Array(compact));
+    }
+    out
+}
+
 pub async fn handle(
     req_id: Value,
     tool_name: &str,
@@ -578,7 +620,14 @@ pub async fn handle(
     shared: &SharedState,
 ) -> eyre::Result<JsonRpcResponse> {
     match tool_name {
-        "pumpfun_list_new_coins" => handle_list_new_coins(req_id, &args, shared).await,
+        "pumpfun_list_new_coins" => {
+            let verbosity = match parse_verbosity(&args) {
+                Ok(v) => v,
+                Err(te) => return Ok(ok(req_id, tool_err(te))),
+            };
+            let resp = handle_list_new_coins(req_id.clone(), &args, shared).await?;
+            Ok(project_response(req_id, resp, verbosity, compact_new_coins))
+        }
         "pumpfun_get_coin_info" => handle_get_coin_info(req_id, &args, shared).await,
         _ => Ok(ok(
             req_id,
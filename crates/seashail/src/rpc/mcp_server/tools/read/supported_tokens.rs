@@ -0,0 +1,336 @@
+use alloy::{primitives::Address, sol};
+use eyre::Context as _;
+use serde_json::{json, Value};
+use tokio::task::JoinSet;
+
+use crate::{
+    chains::{evm::EvmChain, native::native_token},
+    errors::ToolError,
+};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+
+// Reserve/market lists change rarely (governance); keep them for an hour.
+const CACHE_TTL_MS: i64 = 60 * 60 * 1000;
+
+sol! {
+    #[sol(rpc)]
+    contract IAavePoolV3Reserves {
+        function getReservesList() external view returns (address[]);
+    }
+}
+
+sol! {
+    #[sol(rpc)]
+    contract ICometV3Assets {
+        struct AssetInfo {
+            uint8 offset;
+            address asset;
+            address priceFeed;
+            uint64 scale;
+            uint64 borrowCollateralFactor;
+            uint64 liquidateCollateralFactor;
+            uint64 liquidationFactor;
+            uint128 supplyCap;
+        }
+        function baseToken() external view returns (address);
+        function numAssets() external view returns (uint8);
+        function getAssetInfo(uint8 i) external view returns (AssetInfo);
+    }
+}
+
+/// Canonical Solana mints Seashail routes natively (Jupiter accepts any mint; these are the
+/// identifiers agents most often need).
+const SOLANA_TOKENS: &[(&str, &str, u8)] = &[
+    ("native", "SOL", 9),
+    ("So11111111111111111111111111111111111111112", "WSOL", 9),
+    ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "USDC", 6),
+    ("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", "USDT", 6),
+];
+
+fn default_aave_pool_for_chain(chain: &str) -> Option<&'static str> {
+    // Aave v3 Pool (mainnets). Sources: Aave docs / explorers.
+    match chain {
+        "ethereum" => Some("0x87870Bca3F3fD6335C3F4ce8392D69350B4fA4E2"),
+        "base" => Some("0xA238Dd80C259a72e81d7e4664a9801593F98d1c5"),
+        "arbitrum" | "optimism" | "polygon" => Some("0x794a61358D6845594F94dc1DB02A252b5b4814aD"),
+        _ => None,
+    }
+}
+
+fn default_comet_for_chain(chain: &str) -> Option<&'static str> {
+    // Compound v3 Comet addresses (USDC markets). Source: compound-finance/comet deployments.
+    match chain {
+        "ethereum" => Some("0xc3d688B66703497DAA19211EEdff47f25384cdc3"),
+        "base" => Some("0xb125E6687d4313864e53df431d5425969c15Eb2F"),
+        "arbitrum" => Some("0x9c4ec768c28520B50860ea7a15bd7213a9fF58bf"),
+        "optimism" => Some("0x2e44e174f7D53F0212823acC11C01A11d58c5bCB"),
+        "polygon" => Some("0xF25212E676D1F7F89Cd72fFEe66158f541246445"),
+        "sepolia" => Some("0xAec1F48e02Cfb822Be958B68C7957156EB3F0b6e"),
+        _ => None,
+    }
+}
+
+fn build_evm_chain(shared: &SharedState, chain: &str) -> eyre::Result<EvmChain> {
+    let rpc_url = shared
+        .cfg
+        .rpc
+        .evm_rpc_urls
+        .get(chain)
+        .ok_or_else(|| eyre::eyre!("unknown evm chain: {chain}"))?;
+    let chain_id = *shared
+        .cfg
+        .rpc
+        .evm_chain_ids
+        .get(chain)
+        .ok_or_else(|| eyre::eyre!("missing evm chain id: {chain}"))?;
+    let mut evm = EvmChain::for_name(chain, chain_id, rpc_url, &shared.cfg.http);
+    if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+        evm.fallback_rpc_urls.clone_from(fb);
+    }
+    Ok(evm)
+}
+
+/// Resolve symbol/decimals for `(address, role)` pairs concurrently, preserving input order.
+async fn evm_token_entries(evm: &EvmChain, tokens: Vec<(Address, &'static str)>) -> Vec<Value> {
+    let mut tasks = JoinSet::new();
+    for (i, (addr, role)) in tokens.into_iter().enumerate() {
+        let evm = evm.clone();
+        tasks.spawn(async move {
+            let entry = match evm.get_erc20_metadata(addr).await {
+                Ok((decimals, symbol)) => json!({
+                  "address": format!("{addr:#x}"), "symbol": symbol, "decimals": decimals, "role": role
+                }),
+                Err(e) => json!({
+                  "address": format!("{addr:#x}"), "role": role, "error": format!("{e:#}")
+                }),
+            };
+            (i, entry)
+        });
+    }
+    let mut out = vec![];
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(item) = joined {
+            out.push(item);
+        }
+    }
+    out.sort_by_key(|(i, _)| *i);
+    out.into_iter().map(|(_, v)| v).collect()
+}
+
+async fn evm_swap_tokens(evm: &EvmChain) -> eyre::Result<Value> {
+    let u = evm
+        .uniswap
+        .as_ref()
+        .ok_or_else(|| eyre::eyre!("no swap routing configured for {}", evm.name))?;
+    let native = native_token(&evm.name);
+    let mut tokens = vec![json!({
+      "address": "native", "symbol": native.symbol, "decimals": native.decimals, "role": "native"
+    })];
+    tokens.extend(
+        evm_token_entries(
+            evm,
+            vec![(u.wrapped_native, "wrapped_native"), (u.usdc, "stable")],
+        )
+        .await,
+    );
+    Ok(json!({
+      "protocol": "swap",
+      "tokens": tokens,
+      "note": "Uniswap/1inch can route any ERC-20 with liquidity; these are the canonical identifiers Seashail uses for pricing and routing."
+    }))
+}
+
+async fn aave_tokens(evm: &EvmChain, pool: &str) -> eyre::Result<Value> {
+    let pool_addr = EvmChain::parse_address(pool).context("parse aave pool address")?;
+    let reserves = IAavePoolV3Reserves::new(pool_addr, evm.provider()?)
+        .getReservesList()
+        .call()
+        .await
+        .context("aave getReservesList")?;
+    let tokens =
+        evm_token_entries(evm, reserves.into_iter().map(|a| (a, "reserve")).collect()).await;
+    Ok(json!({ "protocol": "aave", "pool": pool, "tokens": tokens }))
+}
+
+async fn compound_tokens(evm: &EvmChain, comet: &str) -> eyre::Result<Value> {
+    let comet_addr = EvmChain::parse_address(comet).context("parse compound comet address")?;
+    let c = ICometV3Assets::new(comet_addr, evm.provider()?);
+    let base = c.baseToken().call().await.context("comet baseToken")?;
+    let n = c.numAssets().call().await.context("comet numAssets")?;
+    let mut assets = vec![(base, "base")];
+    for i in 0..n {
+        let info = c
+            .getAssetInfo(i)
+            .call()
+            .await
+            .with_context(|| format!("comet getAssetInfo({i})"))?;
+        assets.push((info.asset, "collateral"));
+    }
+    let tokens = evm_token_entries(evm, assets).await;
+    Ok(json!({ "protocol": "compound", "comet": comet, "tokens": tokens }))
+}
+
+fn solana_swap_tokens() -> Value {
+    let tokens: Vec<Value> = SOLANA_TOKENS
+        .iter()
+        .map(|(address, symbol, decimals)| {
+            json!({ "address": address, "symbol": symbol, "decimals": decimals })
+        })
+        .collect();
+    json!({
+      "protocol": "swap",
+      "tokens": tokens,
+      "note": "Jupiter can route any SPL mint with liquidity; these are the canonical mints Seashail uses."
+    })
+}
+
+async fn evm_protocol_tokens(
+    evm: &EvmChain,
+    protocol: &str,
+    args: &Value,
+) -> Result<Value, ToolError> {
+    let arg = |k: &str| {
+        args.get(k)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let r = match protocol {
+        "swap" => evm_swap_tokens(evm).await,
+        "aave" => {
+            let Some(pool) = arg("pool_address").or_else(|| default_aave_pool_for_chain(&evm.name))
+            else {
+                return Err(ToolError::new(
+                    "unsupported_chain",
+                    "no default Aave pool for this chain (provide pool_address)",
+                ));
+            };
+            aave_tokens(evm, pool).await
+        }
+        "compound" => {
+            let Some(comet) = arg("comet_address").or_else(|| default_comet_for_chain(&evm.name))
+            else {
+                return Err(ToolError::new(
+                    "unsupported_chain",
+                    "no default Compound market for this chain (provide comet_address)",
+                ));
+            };
+            compound_tokens(evm, comet).await
+        }
+        other => {
+            return Err(ToolError::new(
+                "invalid_request",
+                format!("unsupported protocol for EVM chains: {other} (expected swap, aave or compound)"),
+            ));
+        }
+    };
+    r.map_err(|e| ToolError::new("rpc_error", format!("{e:#}")))
+}
+
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &mut SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim()
+        .to_owned();
+    let protocol = args
+        .get("protocol")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    if chain == "solana" || chain == "bitcoin" {
+        if protocol.is_some_and(|p| p != "swap") {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new(
+                    "invalid_request",
+                    "only protocol=swap is supported on this chain",
+                )),
+            ));
+        }
+        let entry = if chain == "solana" {
+            solana_swap_tokens()
+        } else {
+            json!({
+              "protocol": "native",
+              "tokens": [{ "address": "native", "symbol": "BTC", "decimals": 8_u8 }]
+            })
+        };
+        return Ok(ok(
+            req_id,
+            tool_ok(json!({ "chain": chain, "protocols": [entry] })),
+        ));
+    }
+
+    let evm = match build_evm_chain(shared, &chain) {
+        Ok(e) => e,
+        Err(e) => {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new("invalid_request", format!("{e:#}"))),
+            ));
+        }
+    };
+    // Without an explicit protocol, list everything with a known deployment on this chain.
+    let protocols: Vec<&str> = protocol.map_or_else(
+        || {
+            let mut v = vec!["swap"];
+            if default_aave_pool_for_chain(&chain).is_some() {
+                v.push("aave");
+            }
+            if default_comet_for_chain(&chain).is_some() {
+                v.push("compound");
+            }
+            v
+        },
+        |p| vec![p],
+    );
+
+    shared.ensure_db().await;
+    let now_ms = crate::db::Db::now_ms()?;
+    let mut entries = vec![];
+    for p in protocols {
+        // Custom pool/comet overrides bypass the cache (keys only cover the defaults).
+        let cache_key = (args.get("pool_address").is_none() && args.get("comet_address").is_none())
+            .then(|| format!("supported_tokens:{chain}:{p}"));
+        if let (Some(db), Some(key)) = (shared.db(), cache_key.as_deref()) {
+            if let Ok(Some(row)) = db.get_json_if_fresh(key, now_ms).await {
+                if let Ok(v) = serde_json::from_str::<Value>(&row.json) {
+                    entries.push(v);
+                    continue;
+                }
+            }
+        }
+        match evm_protocol_tokens(&evm, p, args).await {
+            Ok(v) => {
+                if let (Some(db), Some(key)) = (shared.db(), cache_key.as_deref()) {
+                    let _cache_write = db
+                        .upsert_json(
+                            key,
+                            &v.to_string(),
+                            now_ms,
+                            now_ms.saturating_add(CACHE_TTL_MS),
+                        )
+                        .await;
+                }
+                entries.push(v);
+            }
+            // A single explicitly requested protocol surfaces its error directly.
+            Err(te) if protocol.is_some() => return Ok(ok(req_id, tool_err(te))),
+            Err(te) => entries.push(json!({ "protocol": p, "error": te.message })),
+        }
+    }
+
+    Ok(ok(
+        req_id,
+        tool_ok(json!({ "chain": chain, "protocols": entries })),
+    ))
+}
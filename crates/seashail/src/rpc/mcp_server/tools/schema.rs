@@ -38,7 +38,7 @@ fn network_tool_schemas() -> Vec<Value> {
 
 fn read_token_schemas() -> Vec<Value> {
     vec![
-        json!({ "name": "inspect_token", "description": "Read-only token inspection (authorities/decimals/supply) to help evaluate risk for newly launched tokens.", "inputSchema": {
+        json!({ "name": "inspect_token", "description": "Read-only token inspection (authorities/decimals/supply) to help evaluate risk for newly launched tokens. On EVM, also simulates a buy and sell (buy_tax_bps, sell_tax_bps, sellable) and flags blacklist/pause/fee-setter functions in bytecode.", "inputSchema": {
           "type": "object",
           "properties": {
             "chain": { "type": "string", "description": "solana or an EVM chain name." },
@@ -47,6 +47,17 @@ fn read_token_schemas() -> Vec<Value> {
           "required": ["chain", "token"],
           "additionalProperties": false
         }}),
+        json!({ "name": "get_supported_tokens", "description": "Canonical token identifiers (address/mint, symbol, decimals) Seashail operates with on a chain: swap routing tokens, Aave v3 reserves, and Compound v3 base/collateral assets. Use these instead of guessing addresses.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "chain": { "type": "string", "description": "solana, bitcoin, or an EVM chain name." },
+            "protocol": { "type": "string", "enum": ["swap", "aave", "compound"], "description": "If omitted, lists every protocol with a known deployment on the chain." },
+            "pool_address": { "type": "string", "description": "Optional Aave v3 Pool override." },
+            "comet_address": { "type": "string", "description": "Optional Compound v3 Comet override." }
+          },
+          "required": ["chain"],
+          "additionalProperties": false
+        }}),
         json!({ "name": "get_defi_yield_pools", "description": "Fetch and filter DeFi yield pool metadata (best-effort) for agent research. Read-only; does not execute transactions.", "inputSchema": {
           "type": "object",
           "properties": {
@@ -80,7 +91,8 @@ fn read_portfolio_schemas() -> Vec<Value> {
             "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
             "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
             "chain": { "type": "string", "description": "solana, bitcoin, or an EVM chain name (ethereum, base, arbitrum, optimism, polygon, bnb, avalanche, monad, sepolia, base-sepolia, arbitrum-sepolia, optimism-sepolia, polygon-amoy, bnb-testnet, avalanche-fuji, monad-testnet). If omitted, returns default chains based on network mode." },
-            "tokens": { "type": "array", "items": { "type": "string" }, "description": "Optional token addresses/mints to query. If omitted, returns native + a small default set." }
+            "tokens": { "type": "array", "items": { "type": "string" }, "description": "Optional token addresses/mints to query. If omitted, returns native + a small default set." },
+            "include_positions": { "type": "boolean", "default": false, "description": "If true, also return locked_positions: lending (Aave, Compound, Kamino), staking (Lido stETH, JitoSOL) and perp margin (Hyperliquid, Jupiter Perps), labeled by type and kept separate from liquid balances." }
           },
           "additionalProperties": false
         }}),
@@ -92,7 +104,8 @@ fn read_portfolio_schemas() -> Vec<Value> {
             "tokens": { "type": "object", "description": "Optional per-chain token list to include in addition to native. Keys are chain names. Values are arrays of token identifiers (Solana mint or EVM contract address).", "additionalProperties": { "type": "array", "items": { "type": "string" } } },
             "include_history": { "type": "boolean", "default": false, "description": "If true, persist a portfolio snapshot and return recent snapshot totals + simple P&L deltas." },
             "history_limit": { "type": "integer", "minimum": 1, "maximum": 365, "default": 30, "description": "Number of snapshot totals to return when include_history=true." },
-            "include_health": { "type": "boolean", "default": false, "description": "If true, attach latest persisted position/health snapshots from monitoring surfaces (perps/lending/prediction) when available." }
+            "include_health": { "type": "boolean", "default": false, "description": "If true, attach latest persisted position/health snapshots from monitoring surfaces (perps/lending/prediction) when available." },
+            "include_positions": { "type": "boolean", "default": false, "description": "If true, fold lending, staking and perp-margin positions into the view: returns locked_positions plus liquid_usd, locked_usd and debt_usd, and total_usd becomes liquid + locked - debt." }
           },
           "additionalProperties": false
         }}),
@@ -108,12 +121,20 @@ fn read_portfolio_schemas() -> Vec<Value> {
             "token_in": { "type": "string" },
             "token_out": { "type": "string" },
             "amount_in": { "type": "string" },
-            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 5000, "default": 100 },
+            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 5000, "description": "Defaults to 100 on Solana and evm_defi.slippage_bps (50) on EVM." },
+            "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM Uniswap only: swap deadline window (default evm_defi.deadline_seconds)." },
             "provider": { "type": "string", "enum": ["auto", "jupiter", "uniswap", "1inch"], "default": "auto" }
           },
           "required": ["op", "chain"],
           "additionalProperties": false
         }}),
+        json!({ "name": "get_network_fees", "description": "Snapshot of current network fee conditions per chain: EVM base fee and priority fee percentiles, Solana priority fee percentiles, and Bitcoin fee rates, each classified as cheap/normal/expensive relative to recent history. Read-only; cached briefly.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "chains": { "type": "array", "items": { "type": "string" }, "description": "If omitted, uses the default chains for the current network mode plus bitcoin." }
+          },
+          "additionalProperties": false
+        }}),
         json!({ "name": "get_transaction_history", "description": "Return locally tracked transaction history (with optional filtering).", "inputSchema": {
           "type": "object",
           "properties": {
@@ -142,6 +163,24 @@ fn read_portfolio_schemas() -> Vec<Value> {
           },
           "additionalProperties": false
         }}),
+        json!({ "name": "diff_portfolio", "description": "Compare two persisted portfolio snapshots (from get_portfolio(include_history=true)): per-token and per-chain amount/USD deltas plus overall P&L, split into price-driven vs quantity-driven changes. Defaults to the latest snapshot vs the one before it.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "snapshot_scope": { "type": "object", "description": "Optional snapshot scope, matching get_portfolio(include_history=true). If omitted, derived from wallet/chain and default chains for the current network mode.", "properties": {
+              "wallets": { "type": "array", "items": { "type": "string" } },
+              "chains": { "type": "array", "items": { "type": "string" } }
+            }, "additionalProperties": false },
+            "wallet": { "type": "string", "description": "Optional wallet name (used when snapshot_scope is omitted)." },
+            "chain": { "type": "string", "description": "Optional chain name (used when snapshot_scope is omitted)." },
+            "from_snapshot_id": { "type": "integer", "description": "Baseline snapshot id." },
+            "from_ts": { "type": "string", "description": "Baseline: latest snapshot at or before this RFC3339 timestamp." },
+            "days_ago": { "type": "integer", "minimum": 1, "description": "Baseline: latest snapshot at or before now minus N days." },
+            "to_snapshot_id": { "type": "integer", "description": "Comparison snapshot id (default: latest)." },
+            "to_ts": { "type": "string", "description": "Comparison: latest snapshot at or before this RFC3339 timestamp." },
+            "include_unchanged": { "type": "boolean", "default": false, "description": "Include tokens whose amount and USD value did not change." }
+          },
+          "additionalProperties": false
+        }}),
     ]
 }
 
@@ -173,11 +212,56 @@ fn read_defi_schemas() -> Vec<Value> {
           "required": ["bridge_id"],
           "additionalProperties": false
         }}),
+        json!({ "name": "get_bridge_quote", "description": "Read-only comparison of bridge routes for one transfer: expected amount received, fees (bridge, source network, destination redeem), loss vs max_bridge_loss_bps, and ETA per provider. Each route is marked execution=native (bridge_tokens builds and signs it), envelope (needs the DeFi adapter), or unsupported (comparison only). Nothing is signed or sent.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "Optional: evaluate routes against this wallet's policy (max_bridge_loss_bps). Defaults to the global policy." },
+            "chain": { "type": "string", "description": "Source chain (solana or an EVM chain name)." },
+            "to_chain": { "type": "string", "description": "Destination chain (solana or an EVM chain name)." },
+            "token": { "type": "string", "description": "Token on the source chain. EVM: ERC-20 contract address. Solana: SPL mint pubkey." },
+            "amount": { "type": "string", "description": "Amount to bridge (string)." },
+            "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui", "description": "Amount units (ui uses token decimals; base uses raw base units)." }
+          },
+          "required": ["chain", "to_chain", "token", "amount"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "get_nonce_account", "description": "Read-only state of a Solana durable nonce account owned by the wallet account: address, whether it exists, authority, and the current durable nonce.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "nonce_index": { "type": "integer", "minimum": 0, "default": 0, "description": "Which of the account's nonce accounts (each index derives a distinct address)." }
+          },
+          "additionalProperties": false
+        }}),
+        json!({ "name": "get_allowances_risk_report", "description": "Read-only report of a wallet's standing ERC-20 and Permit2 approvals on an EVM chain. Flags unlimited approvals and spenders that are not contracts or match the scam blocklist / OFAC SDN list, estimates at-risk USD (min of allowance and balance) per approval, ranks by it, and suggests revocations with ready-to-send calldata.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "chain": { "type": "string", "description": "EVM chain name." },
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "lookback_blocks": { "type": "integer", "minimum": 1, "maximum": 2_000_000, "description": "How far back to scan Approval logs for spenders Seashail did not record. If omitted, scans from the wallet birthday (capped at 2,000,000 blocks), or the last 50,000 blocks for wallets without one. Approvals from local history and well-known routers are always checked." },
+            "tokens": { "type": "array", "items": { "type": "string" }, "description": "Extra token contract addresses to check against well-known spenders." }
+          },
+          "required": ["chain"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "get_open_orders", "description": "Read-only spot limit orders placed via the Jupiter Trigger API (Solana), with fill progress and expiry status.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "enum": ["solana"], "default": "solana" },
+            "include_history": { "type": "boolean", "default": false, "description": "If true, also return filled/cancelled/expired orders." }
+          },
+          "additionalProperties": false
+        }}),
         json!({ "name": "pumpfun_list_new_coins", "description": "List recent pump.fun launches. Read-only.", "inputSchema": {
           "type": "object",
           "properties": {
             "limit": { "type": "integer", "minimum": 1, "maximum": 200, "default": 20 },
-            "program_id": { "type": "string", "minLength": 1, "description": "Optional Solana program id to scan when using the RPC fallback. Defaults to the pump.fun program id." }
+            "program_id": { "type": "string", "minLength": 1, "description": "Optional Solana program id to scan when using the RPC fallback. Defaults to the pump.fun program id." },
+            "verbosity": { "type": "string", "enum": ["compact", "full"], "default": "compact", "description": "compact keeps mint, bonding curve, block time and curve progress; full returns every decoded field." }
           },
           "additionalProperties": false
         }}),
@@ -216,13 +300,53 @@ fn wallet_tool_schemas() -> Vec<Value> {
           },
           "additionalProperties": false
         }}),
+        json!({ "name": "simulate_policy", "description": "Check whether a hypothetical operation would be allowed, need user confirmation, or be blocked by the effective policy, with reasons and today's daily-used USD. Purely local: no RPC calls and nothing is signed.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "op": { "type": "string", "description": "Operation kind, e.g. send, swap, bridge, open_perp_position, internal_transfer." },
+            "chain": { "type": "string" },
+            "usd_value": { "type": "number", "minimum": 0, "description": "If omitted, the USD value is treated as unknown." },
+            "to": { "type": "string", "description": "Destination address (checked against send_allowlist and send_denylist for send)." },
+            "contract": { "type": "string", "description": "Target contract/program (checked against contract allowlists)." },
+            "slippage_bps": { "type": "integer", "minimum": 0 },
+            "leverage": { "type": "integer", "minimum": 1 },
+            "remote_tx": { "type": "boolean", "default": false, "description": "Whether the transaction is built by a remote API (Jupiter, 1inch, Jupiter Trigger, pump.fun or DeFi adapters). With policy.require_user_confirm_for_remote_tx this always needs confirmation." },
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet's effective policy and daily usage." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "Account whose send_denylist scope and hot_account status apply. Defaults to the wallet's active account." }
+          },
+          "required": ["op", "chain"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "replay_policy", "description": "Replay a proposed policy over the local tx history: how each past policy-gated transaction would have been classified (allow/confirm/block) next to the current policy, with aggregate counts. Entries without a USD value are counted separately as usd_unknown. Purely local: nothing is saved or signed.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "policy": { "type": "object", "description": "Proposed policy object (same shape as update_policy's policy)." },
+            "wallet": { "type": "string", "description": "Only replay this wallet's history. If omitted, replays all wallets, comparing against each wallet's current effective policy." },
+            "chain": { "type": "string" },
+            "since_ts": { "type": "string", "description": "RFC3339 lower bound (inclusive)." },
+            "until_ts": { "type": "string", "description": "RFC3339 upper bound (inclusive)." },
+            "limit": { "type": "integer", "minimum": 1, "maximum": 10000, "default": 1000, "description": "Most recent history entries to replay." }
+          },
+          "required": ["policy"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "onboard", "description": "One-call, idempotent first-run setup: ensures the generated 'default' wallet exists and is active, persists the network mode (so default chains are stable), applies a conservative starter policy if the policy is still the untouched built-in default, and returns deposit addresses. Re-running when already set up changes nothing (already_onboarded=true).", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "network": { "type": "string", "enum": ["mainnet", "testnet"], "description": "Network mode to persist if none is configured yet. Ignored (with a note) when a mode is already configured. If omitted, uses the effective mode." }
+          },
+          "additionalProperties": false
+        }}),
         json!({ "name": "list_wallets", "description": "List all wallets with names, types, accounts, and cached addresses. Seashail maintains a generated 'default' wallet; on first run it may be created the first time you call a wallet-dependent tool.", "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false } }),
-        json!({ "name": "get_wallet_info", "description": "Get details for a specific wallet (cached public addresses only). Seashail maintains a generated 'default' wallet; on first run it may be created on-demand. For funding, prefer get_deposit_info (deposit address).", "inputSchema": {
+        json!({ "name": "get_wallet_info", "description": "Get details for a specific wallet (cached public addresses only unless include_balances is set). Seashail maintains a generated 'default' wallet; on first run it may be created on-demand. For funding, prefer get_deposit_info (deposit address).", "inputSchema": {
           "type": "object",
-          "properties": { "wallet": { "type": "string", "description": "If omitted, returns the active wallet." } },
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, returns the active wallet." },
+            "include_balances": { "type": "boolean", "default": false, "description": "If true, also fetch live native balances for the network mode's default chains (active account). Best-effort: chains that fail are listed in balance_errors and addresses are still returned." }
+          },
           "additionalProperties": false
         }}),
-        json!({ "name": "get_deposit_info", "description": "Get a deposit address for a wallet on a specific chain (address-only; no QR). If the generated 'default' wallet does not exist yet (fresh install), Seashail may create it on-demand before returning an address.", "inputSchema": {
+        json!({ "name": "get_deposit_info", "description": "Get a deposit address for a wallet on a specific chain (address-only; no QR). If the generated 'default' wallet does not exist yet (fresh install), Seashail may create it on-demand before returning an address. The result states whether a memo/tag is required (none of the supported chains need one) and includes asset_warnings when the requested token only exists on this chain as a bridged/wrapped variant.", "inputSchema": {
           "type": "object",
           "properties": {
             "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
@@ -232,15 +356,41 @@ fn wallet_tool_schemas() -> Vec<Value> {
           },
           "additionalProperties": false
         }}),
-        json!({ "name": "set_active_wallet", "description": "Set the active wallet and account index.", "inputSchema": {
+        json!({ "name": "set_active_wallet", "description": "Set the active wallet and account index. With chain, pins the selection to that chain (or family: evm/solana/bitcoin) only; tools targeting that chain use it instead of the global active wallet. list_wallets reports the per-chain mapping.", "inputSchema": {
           "type": "object",
-          "properties": { "wallet": { "type": "string" }, "account_index": { "type": "integer", "minimum": 0 } },
-          "required": ["wallet", "account_index"],
+          "properties": {
+            "wallet": { "type": "string", "description": "Required unless clearing a chain override." },
+            "account_index": { "type": "integer", "minimum": 0, "default": 0 },
+            "chain": { "type": "string", "description": "Optional scope: a chain name (e.g. base, solana) or family (evm, solana, bitcoin). If omitted, sets the global active wallet." },
+            "clear": { "type": "boolean", "default": false, "description": "If true (requires chain), removes the override for that scope so it falls back to the family/global selection." }
+          },
           "additionalProperties": false
         }}),
-        json!({ "name": "add_account", "description": "Add a new account index to an existing BIP-44 wallet.", "inputSchema": {
+        json!({ "name": "rename_wallet", "description": "Rename a wallet. Active selections (global and per-chain), the per-wallet policy override and the hot account follow the new name. Tx history is never rewritten: older entries keep the name they were recorded under and get_transaction_history adds wallet_current to them. Old names stay reserved so history remains unambiguous.", "inputSchema": {
           "type": "object",
-          "properties": { "wallet": { "type": "string" } },
+          "properties": {
+            "wallet": { "type": "string", "minLength": 1, "description": "Current wallet name." },
+            "new_name": { "type": "string", "minLength": 1, "description": "New name. Must not collide (case-insensitively) with another wallet's current or previous name." }
+          },
+          "required": ["wallet", "new_name"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "add_account", "description": "Add new account indexes to an existing BIP-44 wallet. Pass count to derive several indexes in one pass.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string" },
+            "count": { "type": "integer", "minimum": 1, "maximum": 100, "default": 1, "description": "Number of account indexes to add." }
+          },
+          "required": ["wallet"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "enable_smart_account", "description": "Opt a wallet into ERC-4337 smart-account execution (SimpleAccount, EntryPoint v0.7) owned by its EVM keys. Computes counterfactual account addresses; the account deploys on first use. EOA signing stays the default; pass execution=smart_account to send_transaction/swap_tokens. Requires erc4337.bundler_urls (and optionally erc4337.paymaster_urls for gas sponsorship or ERC-20 gas) in config. Re-run after add_account.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string" },
+            "chain": { "type": "string", "description": "EVM chain used to derive addresses (the factory must be deployed there)." },
+            "enabled": { "type": "boolean", "default": true, "description": "false removes the smart account binding." }
+          },
           "required": ["wallet"],
           "additionalProperties": false
         }}),
@@ -253,18 +403,24 @@ fn wallet_tool_schemas() -> Vec<Value> {
           "required": ["count"],
           "additionalProperties": false
         }}),
-        json!({ "name": "create_wallet", "description": "Create a generated wallet (Shamir 2-of-3). Requires user confirmation.", "inputSchema": {
+        json!({ "name": "create_wallet", "description": "Create a generated wallet (Shamir 2-of-3). Requires user confirmation. Names must be unique (case-insensitive, including names of renamed wallets).", "inputSchema": {
           "type": "object",
           "properties": { "name": { "type": "string", "minLength": 1 } },
           "required": ["name"],
           "additionalProperties": false
         }}),
-        json!({ "name": "import_wallet", "description": "Import an existing private key or mnemonic. Requires user confirmation. The secret is always requested via an interactive prompt (not via tool arguments).", "inputSchema": {
+        json!({ "name": "import_wallet", "description": "Import an existing private key or mnemonic, or create a watch-only wallet from an xpub or address list. Key imports require user confirmation and the secret is always requested via an interactive prompt (not via tool arguments). Watch-only wallets (kind=xpub/addresses) hold public data only: they work in balance/portfolio views but can never sign.", "inputSchema": {
           "type": "object",
           "properties": {
             "name": { "type": "string", "minLength": 1 },
-            "kind": { "type": "string", "enum": ["private_key", "mnemonic"] },
+            "kind": { "type": "string", "enum": ["private_key", "mnemonic", "xpub", "addresses"] },
             "private_key_chain": { "type": "string", "enum": ["evm", "solana"] },
+            "xpub": { "type": "string", "description": "kind=xpub: account-level extended public key (xpub/tpub, or zpub/vpub for Bitcoin BIP84), e.g. from export_public_descriptor." },
+            "xpub_chain": { "type": "string", "enum": ["evm", "bitcoin"], "description": "kind=xpub: which chain the xpub belongs to. Required for plain xpub/tpub; zpub/vpub imply bitcoin." },
+            "address_count": { "type": "integer", "minimum": 1, "maximum": 100, "default": 5, "description": "kind=xpub: how many receive addresses (0/0..0/n-1) to derive; each becomes an account. add_account derives more later." },
+            "addresses": { "type": "array", "items": { "type": "string" }, "description": "kind=addresses: EVM, Solana and/or Bitcoin addresses (up to 100 per chain). Account i holds the i-th address of each chain." },
+            "birthday": { "type": "string", "description": "Optional approximate date the wallet was first used (YYYY-MM-DD or RFC 3339). On-chain scans start from here instead of a default lookback." },
+            "birthday_heights": { "type": "object", "description": "Optional explicit scan start heights per chain (EVM block, Solana slot, Bitcoin block), e.g. {\"ethereum\": 18000000}.", "additionalProperties": { "type": "integer", "minimum": 0 } },
             "secret": { "type": "string", "description": "Deprecated. Leave unset; Seashail will prompt for the secret via an elicitation form.", "minLength": 1 }
           },
           "required": ["name", "kind"],
@@ -276,6 +432,11 @@ fn wallet_tool_schemas() -> Vec<Value> {
           "required": ["wallet"],
           "additionalProperties": false
         }}),
+        json!({ "name": "export_public_descriptor", "description": "Export account-level extended public keys for watch-only use: Bitcoin BIP84 xpub/zpub plus wpkh output descriptors, and the EVM BIP44 xpub. Contains no private material, but reveals every address of the account (a privacy consideration). Solana has no xpub (hardened-only derivation); its addresses are listed instead.", "inputSchema": {
+          "type": "object",
+          "properties": { "wallet": { "type": "string", "description": "If omitted, uses the active wallet." } },
+          "additionalProperties": false
+        }}),
         json!({ "name": "rotate_shares", "description": "Regenerate all Shamir shares for a generated wallet. Requires passphrase.", "inputSchema": {
           "type": "object",
           "properties": { "wallet": { "type": "string" } },
@@ -305,7 +466,8 @@ fn prediction_tool_schemas() -> Vec<Value> {
             "query": { "type": "string", "minLength": 1, "description": "Search query." },
             "limit": { "type": "integer", "minimum": 1, "maximum": 100, "default": 10, "description": "Max results per type." },
             "page": { "type": "integer", "minimum": 1, "default": 1 },
-            "include_closed": { "type": "boolean", "default": false, "description": "If true, include closed markets in results." }
+            "include_closed": { "type": "boolean", "default": false, "description": "If true, include closed markets in results." },
+            "verbosity": { "type": "string", "enum": ["compact", "full"], "default": "compact", "description": "compact returns normalized markets only; full also includes the raw Gamma API response." }
           },
           "required": ["query"],
           "additionalProperties": false
@@ -325,11 +487,15 @@ fn prediction_tool_schemas() -> Vec<Value> {
 
 fn perp_read_schemas() -> Vec<Value> {
     vec![
-        json!({ "name": "get_market_data", "description": "Read-only market data for perpetual venues (Hyperliquid and Jupiter Perps, best-effort).", "inputSchema": {
+        json!({ "name": "get_market_data", "description": "Read-only market data for perpetual venues (Hyperliquid and Jupiter Perps, best-effort). Set mode=candles for recent OHLCV candles.", "inputSchema": {
           "type": "object",
           "properties": {
             "provider": { "type": "string", "enum": ["hyperliquid", "jupiter_perps"], "default": "hyperliquid" },
-            "market": { "type": "string", "description": "Optional market/coin symbol (e.g. BTC). If omitted, returns all markets." }
+            "mode": { "type": "string", "enum": ["markets", "candles"], "default": "markets", "description": "markets returns current market info; candles returns recent OHLCV for one market (Jupiter Perps candles are best-effort from Binance)." },
+            "market": { "type": "string", "description": "Optional market/coin symbol (e.g. BTC). If omitted, returns all markets. Required in candles mode." },
+            "interval": { "type": "string", "enum": ["1m", "5m", "15m", "30m", "1h", "4h", "12h", "1d", "1w"], "default": "1h", "description": "Candle interval (candles mode only)." },
+            "lookback": { "type": "integer", "minimum": 1, "maximum": 500, "default": 100, "description": "Number of most recent candles to return (candles mode only; capped at 500)." },
+            "verbosity": { "type": "string", "enum": ["compact", "full"], "default": "compact", "description": "compact keeps price, funding and max leverage per market (or OHLCV per candle); full returns the venue payload." }
           },
           "additionalProperties": false
         }}),
@@ -338,7 +504,8 @@ fn perp_read_schemas() -> Vec<Value> {
           "properties": {
             "provider": { "type": "string", "enum": ["hyperliquid", "jupiter_perps"], "default": "hyperliquid" },
             "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
-            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." }
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "verbosity": { "type": "string", "enum": ["compact", "full"], "default": "compact", "description": "compact keeps market, side, size and PnL per position; full returns the venue payload." }
           },
           "additionalProperties": false
         }}),
@@ -380,6 +547,20 @@ fn perp_write_schemas() -> Vec<Value> {
           "required": ["market"],
           "additionalProperties": false
         }}),
+        json!({ "name": "close_all_positions", "description": "Emergency flatten: cancel open Hyperliquid orders, market-close every open perp position (Hyperliquid + Jupiter Perps) and optionally repay Aave/Compound borrows. Requires typing CLOSE ALL to confirm; each close still goes through policy. Reports per-position results and what remains open.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "providers": { "type": "array", "items": { "type": "string", "enum": ["hyperliquid", "jupiter_perps"] }, "description": "Perp venues to flatten. Default: hyperliquid, plus jupiter_perps on mainnet." },
+            "cancel_orders": { "type": "boolean", "default": true, "description": "Cancel resting Hyperliquid orders first." },
+            "unwind_borrows": { "type": "boolean", "default": false, "description": "Also repay outstanding Aave v3 / Compound v3 borrows from wallet balances." },
+            "chains": { "type": "array", "items": { "type": "string" }, "description": "EVM chains to scan for borrows. Default: configured chains with a known Aave pool or Comet for the current network mode." },
+            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 5000, "default": 100 },
+            "dry_run": { "type": "boolean", "default": false, "description": "Return the plan without confirming or executing." }
+          },
+          "additionalProperties": false
+        }}),
         json!({ "name": "modify_perp_order", "description": "Modify a perp order on Hyperliquid (implemented as cancel + new order). Requires policy approval.", "inputSchema": {
           "type": "object",
           "properties": {
@@ -476,7 +657,7 @@ fn nft_tool_schemas() -> Vec<Value> {
           "required": ["chain"],
           "additionalProperties": false
         }}),
-        json!({ "name": "transfer_nft", "description": "Transfer an NFT (Solana mints; EVM ERC-721 safeTransferFrom). Requires policy approval.", "inputSchema": {
+        json!({ "name": "transfer_nft", "description": "Transfer an NFT (Solana mints; EVM ERC-721 safeTransferFrom; EVM ERC-1155 safeTransferFrom with an amount, or safeBatchTransferFrom via items). The EVM standard is detected via ERC-165 unless standard is given. Requires policy approval.", "inputSchema": {
           "type": "object",
           "properties": {
             "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
@@ -485,7 +666,18 @@ fn nft_tool_schemas() -> Vec<Value> {
             "to": { "type": "string" },
             "mint": { "type": "string", "description": "Solana mint (required when chain=solana)." },
             "contract": { "type": "string", "description": "EVM contract address (required for EVM chains)." },
-            "token_id": { "type": "string", "description": "EVM token id (required for EVM chains). Decimal string." }
+            "token_id": { "type": "string", "description": "EVM token id (required for EVM chains unless items is given). Decimal string." },
+            "standard": { "type": "string", "enum": ["erc721", "erc1155"], "description": "EVM token standard. If omitted, detected via ERC-165 (contracts advertising neither are treated as ERC-721 for a single-token transfer)." },
+            "amount": { "type": "string", "default": "1", "description": "ERC-1155 only: units of token_id to transfer. Decimal string." },
+            "items": { "type": "array", "minItems": 1, "maxItems": 100, "description": "ERC-1155 batch: several ids in one safeBatchTransferFrom. Use instead of token_id/amount.", "items": {
+              "type": "object",
+              "properties": {
+                "token_id": { "type": "string" },
+                "amount": { "type": "string", "default": "1" }
+              },
+              "required": ["token_id"],
+              "additionalProperties": false
+            } }
           },
           "required": ["chain", "to"],
           "additionalProperties": false
@@ -574,11 +766,27 @@ fn write_spot_schemas() -> Vec<Value> {
             "to": { "type": "string" },
             "token": { "type": "string", "description": "native (default) or token mint/contract address." },
             "amount": { "type": "string" },
-            "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" }
+            "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" },
+            "execution": { "type": "string", "enum": ["eoa", "smart_account"], "default": "eoa", "description": "EVM only. smart_account routes through the wallet's ERC-4337 account (see enable_smart_account) via the configured bundler/paymaster." },
+            "durable_nonce_index": { "type": "integer", "minimum": 0, "description": "Solana only. Sign against this durable nonce account (see create_nonce_account) instead of a recent blockhash, so the transaction does not expire." },
+            "sign_only": { "type": "boolean", "default": false, "description": "Solana only; requires durable_nonce_index. Sign without broadcasting and return the base64 signed_transaction for submit_signed_transaction." },
+            "memo_onchain": { "type": "boolean", "default": false, "description": "Solana only; requires note (max 256 bytes). Also attach the note as an SPL Memo instruction. On-chain memos are public and permanent." }
           },
           "required": ["chain", "to", "amount"],
           "additionalProperties": false
         }}),
+        json!({ "name": "sign_message", "description": "Sign an off-chain message (EIP-191 personal_sign on EVM, ed25519 on Solana) for sign-in/proof-of-ownership. Always shows the exact message for confirmation and never broadcasts. Refuses payloads that are transactions (EIP-712/RLP on EVM, transaction messages on Solana) and warns on other non-text payloads or digests.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "description": "solana, or any EVM chain name (EVM signatures are chain-agnostic)." },
+            "message": { "type": "string", "minLength": 1 },
+            "encoding": { "type": "string", "enum": ["utf8", "hex"], "default": "utf8" }
+          },
+          "required": ["chain", "message"],
+          "additionalProperties": false
+        }}),
         json!({ "name": "swap_tokens", "description": "Execute a token swap via Jupiter (Solana) or Uniswap/1inch (EVM). Requires policy approval.", "inputSchema": {
           "type": "object",
           "properties": {
@@ -589,13 +797,85 @@ fn write_spot_schemas() -> Vec<Value> {
             "token_out": { "type": "string" },
             "amount_in": { "type": "string" },
             "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" },
-            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 5000, "default": 100 },
-            "provider": { "type": "string", "enum": ["auto", "jupiter", "uniswap", "1inch"], "default": "auto" }
+            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 5000, "description": "Defaults to 100 on Solana and evm_defi.slippage_bps (50) on EVM; capped by policy.max_slippage_bps." },
+            "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM Uniswap only: seconds the swap stays executable once built; the router reverts it afterwards. Defaults to evm_defi.deadline_seconds (1200). 1inch swaps carry no deadline." },
+            "provider": { "type": "string", "enum": ["auto", "jupiter", "uniswap", "1inch"], "default": "auto" },
+            "execution": { "type": "string", "enum": ["eoa", "smart_account"], "default": "eoa", "description": "EVM only. smart_account routes through the wallet's ERC-4337 account (see enable_smart_account) via the configured bundler/paymaster." }
           },
           "required": ["chain", "token_in", "token_out", "amount_in"],
           "additionalProperties": false
         }}),
-        json!({ "name": "transfer_between_wallets", "description": "Transfer tokens between Seashail-managed wallets/accounts. Internal transfers are policy-exempt by default.", "inputSchema": {
+        json!({ "name": "wrap_native", "description": "Wrap the chain's native asset: ETH/MATIC -> WETH/WMATIC on EVM via deposit(), SOL -> wSOL on Solana (creates the wSOL token account if needed, transfers, then SyncNative). Returns the resulting wrapped balance. Requires policy approval.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "description": "solana or an EVM chain with a known wrapped native token (ethereum, base, arbitrum, optimism, polygon)." },
+            "amount": { "type": "string" },
+            "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" }
+          },
+          "required": ["chain", "amount"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "unwrap_native", "description": "Unwrap back to the native asset: WETH/WMATIC -> ETH/MATIC on EVM via withdraw(), wSOL -> SOL on Solana by closing the wSOL token account (always the full balance; the account's rent deposit is reclaimed and reported). Requires policy approval.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "description": "solana or an EVM chain with a known wrapped native token (ethereum, base, arbitrum, optimism, polygon)." },
+            "amount": { "type": "string", "description": "Amount to unwrap, or max. If omitted, unwraps the full wrapped balance. On Solana only the full balance can be unwrapped." },
+            "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" }
+          },
+          "required": ["chain"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "rebalance_portfolio", "description": "Rebalance one wallet/account on a single chain to target weights. Reads balances and prices, plans the minimal set of direct swaps that brings every token within tolerance, and previews total slippage and fees. Unless dry_run is set, executes the swaps via swap_tokens. The whole batch is policy-checked before the first swap, and if any swap would need confirmation the user confirms the full plan (with its total USD) once, instead of swap by swap.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "description": "solana or an EVM chain name." },
+            "targets": { "type": "array", "minItems": 1, "maxItems": 20, "description": "Target allocation. Weights are percentages (max 2 decimals) and must sum to 100.", "items": { "type": "object", "properties": { "token": { "type": "string", "description": "native or token mint/contract address." }, "weight": { "type": ["string", "number"] } }, "required": ["token", "weight"], "additionalProperties": false } },
+            "tolerance_bps": { "type": "integer", "minimum": 0, "maximum": 5000, "default": 100, "description": "Skip rebalancing while every token is within this drift of its target (basis points of total value)." },
+            "max_swaps": { "type": "integer", "minimum": 1, "maximum": 20, "default": 5 },
+            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 5000, "default": 100 },
+            "provider": { "type": "string", "enum": ["auto", "jupiter", "uniswap", "1inch"], "default": "auto" },
+            "execution": { "type": "string", "enum": ["eoa", "smart_account"], "default": "eoa", "description": "EVM only. smart_account reads balances from and swaps through the wallet's ERC-4337 account (see enable_smart_account)." },
+            "dry_run": { "type": "boolean", "default": false, "description": "If true, return the plan and preview without executing." }
+          },
+          "required": ["chain", "targets"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "place_spot_limit_order", "description": "Place a spot limit order via the Jupiter Trigger API (Solana only). The order fills when the market reaches the limit price. The full input notional counts toward policy limits at placement. Requires policy approval.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "enum": ["solana"], "default": "solana" },
+            "token_in": { "type": "string", "description": "Token to sell (native/sol or mint)." },
+            "token_out": { "type": "string", "description": "Token to buy (native/sol or mint)." },
+            "amount_in": { "type": "string" },
+            "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" },
+            "amount_out": { "type": "string", "description": "Minimum amount of token_out to receive (same units as amount_in). Provide this or limit_price." },
+            "limit_price": { "type": "string", "description": "Limit price as token_out per 1 token_in (UI units). Provide this or amount_out." },
+            "expires_in_seconds": { "type": "integer", "minimum": 1, "description": "Optional expiry relative to now." },
+            "expires_at": { "type": "string", "description": "Optional absolute expiry (RFC3339)." }
+          },
+          "required": ["token_in", "token_out", "amount_in"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "cancel_spot_limit_order", "description": "Cancel an open Jupiter Trigger spot limit order (Solana only). Unfilled input is returned to the wallet. Requires policy approval.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "enum": ["solana"], "default": "solana" },
+            "order": { "type": "string", "minLength": 1, "description": "Order account pubkey (from place_spot_limit_order or get_open_orders)." }
+          },
+          "required": ["order"],
+          "additionalProperties": false
+        }}),
+        json!({ "name": "transfer_between_wallets", "description": "Transfer tokens between Seashail-managed wallets/accounts. Internal transfers are policy-exempt by default (see policy.internal_transfers_policy: exempt|audit|enforce).", "inputSchema": {
           "type": "object",
           "properties": {
             "chain": { "type": "string", "description": "solana or an EVM chain name." },
@@ -610,7 +890,7 @@ fn write_spot_schemas() -> Vec<Value> {
           "required": ["chain", "amount", "from_wallet", "from_account_index", "to_wallet", "to_account_index"],
           "additionalProperties": false
         }}),
-        json!({ "name": "fund_wallets", "description": "Distribute funds from one managed wallet/account to many managed wallets/accounts. Internal transfers are policy-exempt by default.", "inputSchema": {
+        json!({ "name": "fund_wallets", "description": "Distribute funds from one managed wallet/account to many managed wallets/accounts. Internal transfers are policy-exempt by default (see policy.internal_transfers_policy: exempt|audit|enforce).", "inputSchema": {
           "type": "object",
           "properties": {
             "chain": { "type": "string", "description": "solana or an EVM chain name." },
@@ -624,6 +904,37 @@ fn write_spot_schemas() -> Vec<Value> {
           "required": ["chain", "amount_each", "destinations"],
           "additionalProperties": false
         }}),
+        json!({ "name": "create_nonce_account", "description": "Create a Solana durable nonce account (derived from the wallet account, which is its authority) so transactions can be signed now and submitted later. Funds it with the rent-exempt minimum; treated as an internal transfer for policy.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "enum": ["solana"], "default": "solana" },
+            "nonce_index": { "type": "integer", "minimum": 0, "default": 0, "description": "Which nonce account to create (each index derives a distinct address)." }
+          },
+          "additionalProperties": false
+        }}),
+        json!({ "name": "close_nonce_account", "description": "Close a Solana durable nonce account, withdrawing its full balance back to the wallet account. Any transaction still signed against it can no longer land.", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "enum": ["solana"], "default": "solana" },
+            "nonce_index": { "type": "integer", "minimum": 0, "default": 0 }
+          },
+          "additionalProperties": false
+        }}),
+        json!({ "name": "submit_signed_transaction", "description": "Broadcast a Solana durable-nonce transaction produced by send_transaction with sign_only. Fails with nonce_consumed if the nonce has advanced since signing (re-sign in that case).", "inputSchema": {
+          "type": "object",
+          "properties": {
+            "wallet": { "type": "string", "description": "If omitted, uses the active wallet. Must be the wallet account that signed the transaction." },
+            "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+            "chain": { "type": "string", "enum": ["solana"], "default": "solana" },
+            "transaction": { "type": "string", "description": "Base64 signed transaction (signed_transaction from send_transaction)." }
+          },
+          "required": ["transaction"],
+          "additionalProperties": false
+        }}),
         json!({ "name": "pumpfun_buy", "description": "Buy a pump.fun coin. Requires policy approval.", "inputSchema": {
           "type": "object",
           "properties": {
@@ -664,10 +975,13 @@ fn write_defi_bridge_schema() -> Value {
         "recipient": { "type": "string", "description": "Optional recipient. EVM->EVM or Solana->EVM: EVM address. EVM->Solana: Solana owner pubkey (Seashail derives the ATA for the wrapped mint). Defaults to the same Seashail wallet on the destination chain." },
         "recipient_token_account": { "type": "string", "description": "EVM->Solana only: optional override for the exact destination SPL token account pubkey (must match the token account address embedded in the VAA). If omitted, Seashail uses the derived ATA." },
         "redeem": { "type": "boolean", "default": true, "description": "If true (default), Seashail will best-effort fetch the signed VAA and redeem on destination (requires destination fees: EVM gas or SOL)." },
+        "allow_insufficient_destination_gas": { "type": "boolean", "default": false, "description": "When redeem=true, Seashail checks the destination wallet's native balance against the estimated redeem cost and fails with insufficient_destination_gas if it is short. Set true to bridge anyway (the shortfall is returned as a warning); fund the destination before redemption." },
         "token_bridge_address": { "type": "string", "description": "Optional override for source Wormhole token bridge contract (useful for local testing/mocks)." },
         "to_token_bridge_address": { "type": "string", "description": "Optional override for destination Wormhole token bridge contract (useful for local testing/mocks)." },
         "usd_value": { "type": "number", "description": "Best-effort USD value for policy enforcement. If omitted, treated as unknown." },
         "usd_value_known": { "type": "boolean", "default": false, "description": "Optional override. If usd_value is provided, this is treated as true." },
+        "expected_received_usd": { "type": "number", "description": "Tx envelope only: USD value expected to arrive on the destination chain (used for the loss disclosure and max_bridge_loss_bps). The adapter response may also provide it." },
+        "bridge_fee_usd": { "type": "number", "description": "Tx envelope only: bridge/relayer fee in USD charged by the route (disclosed in the confirmation)." },
         "to": { "type": "string", "description": "EVM: transaction recipient/contract address." },
         "data": { "type": "string", "description": "EVM: 0x-prefixed calldata. Use 0x for empty." },
         "value_wei": { "type": "string", "description": "EVM: value in wei as a decimal string.", "default": "0" },
@@ -694,6 +1008,12 @@ fn schema_lend_tokens() -> Value {
         "pool_address": { "type": "string", "description": "Optional override for Aave v3 Pool address (useful for local testing/mocks)." },
         "comet_address": { "type": "string", "description": "Compound v3: optional override for the Comet market address. If omitted, Seashail uses a per-chain default for the USDC market when available." },
         "market": { "type": "string", "description": "Kamino: optional market pubkey override (defaults to http.kamino_default_lend_market)." },
+        "reserve": { "type": "string", "description": "Kamino: optional reserve pubkey override for `token` (resolved from the market when omitted)." },
+        "position_type": { "type": "string", "enum": ["lend", "multiply"], "default": "lend", "description": "Kamino: lend supplies `token` to a single reserve; multiply opens a leveraged position that borrows `debt_token` and loops it into `token` as collateral." },
+        "debt_token": { "type": "string", "description": "Kamino multiply: SPL mint (or native) borrowed against the collateral." },
+        "debt_reserve": { "type": "string", "description": "Kamino multiply: optional debt reserve pubkey override." },
+        "leverage": { "type": "number", "exclusiveMinimum": 1, "description": "Kamino multiply: target leverage (collateral / equity). Capped by policy.max_lending_leverage; the projected health factor must stay >= policy.min_lending_health_factor." },
+        "slippage_bps": { "type": "integer", "minimum": 0, "description": "Kamino multiply: swap slippage for the loop (default 50, capped by policy.max_slippage_bps). EVM adapter envelopes (asset): sent to the adapter, default evm_defi.slippage_bps." },
         "group": { "type": "string", "description": "Marginfi: optional group pubkey override (defaults to http.marginfi_default_group)." },
         "usd_value": { "type": "number" },
         "usd_value_known": { "type": "boolean", "default": false },
@@ -702,6 +1022,7 @@ fn schema_lend_tokens() -> Value {
         "value_wei": { "type": "string", "default": "0" },
         "tx_b64": { "type": "string" },
         "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+        "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
         "asset": { "type": "object" }
       },
       "required": ["chain"],
@@ -723,6 +1044,11 @@ fn schema_withdraw_lending() -> Value {
         "pool_address": { "type": "string", "description": "Optional override for Aave v3 Pool address (useful for local testing/mocks)." },
         "comet_address": { "type": "string", "description": "Compound v3: optional override for the Comet market address. If omitted, Seashail uses a per-chain default for the USDC market when available." },
         "market": { "type": "string", "description": "Kamino: optional market pubkey override (defaults to http.kamino_default_lend_market)." },
+        "reserve": { "type": "string", "description": "Kamino: optional reserve pubkey override for `token` (resolved from the market when omitted)." },
+        "position_type": { "type": "string", "enum": ["lend", "multiply"], "default": "lend", "description": "Kamino: lend withdraws a simple supply; multiply unwinds a leveraged position (repays debt_token from collateral). amount=max closes the whole multiply position (pass usd_value for policy)." },
+        "debt_token": { "type": "string", "description": "Kamino multiply: SPL mint (or native) borrowed by the position." },
+        "debt_reserve": { "type": "string", "description": "Kamino multiply: optional debt reserve pubkey override." },
+        "slippage_bps": { "type": "integer", "minimum": 0, "description": "Kamino multiply: swap slippage for the unwind (default 50, capped by policy.max_slippage_bps). EVM adapter envelopes (asset): sent to the adapter, default evm_defi.slippage_bps." },
         "group": { "type": "string", "description": "Marginfi: optional group pubkey override (defaults to http.marginfi_default_group)." },
         "usd_value": { "type": "number" },
         "usd_value_known": { "type": "boolean", "default": false },
@@ -731,6 +1057,7 @@ fn schema_withdraw_lending() -> Value {
         "value_wei": { "type": "string", "default": "0" },
         "tx_b64": { "type": "string" },
         "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+        "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
         "asset": { "type": "object" }
       },
       "required": ["chain"],
@@ -761,6 +1088,7 @@ fn schema_borrow_tokens() -> Value {
         "value_wei": { "type": "string", "default": "0" },
         "tx_b64": { "type": "string" },
         "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+        "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
         "asset": { "type": "object" }
       },
       "required": ["chain"],
@@ -791,6 +1119,7 @@ fn schema_repay_borrow() -> Value {
         "value_wei": { "type": "string", "default": "0" },
         "tx_b64": { "type": "string" },
         "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+        "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
         "asset": { "type": "object" }
       },
       "required": ["chain"],
@@ -819,7 +1148,7 @@ fn write_defi_staking_schemas() -> Vec<Value> {
             "token": { "type": "string", "description": "Token to stake. EVM Lido: native ETH (use token=native). Solana Jito: native SOL (token=native)." },
             "amount": { "type": "string", "description": "Amount to stake (string). Native staking paths require this." },
             "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" },
-            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 2000, "default": 100, "description": "Solana only (Jito via Jupiter swap): slippage in basis points." },
+            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 2000, "description": "Slippage in basis points. Solana Jito (via Jupiter swap): default 100. EVM adapter envelopes (asset): sent to the adapter, default evm_defi.slippage_bps (50)." },
             "usd_value": { "type": "number" },
             "usd_value_known": { "type": "boolean", "default": false },
             "to": { "type": "string" },
@@ -827,6 +1156,7 @@ fn write_defi_staking_schemas() -> Vec<Value> {
             "value_wei": { "type": "string", "default": "0" },
             "tx_b64": { "type": "string" },
             "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+            "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
             "asset": { "type": "object" }
           },
           "required": ["chain"],
@@ -843,7 +1173,7 @@ fn write_defi_staking_schemas() -> Vec<Value> {
             "amount": { "type": "string", "description": "Amount to unstake (string). Native unstaking paths require this." },
             "amount_units": { "type": "string", "enum": ["ui", "base"], "default": "ui" },
             "request_id": { "type": "string", "description": "Optional async request id for Lido withdrawal claims. Native Lido unstake returns request_ids that can be used to claim once the withdrawal is finalized." },
-            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 2000, "default": 100, "description": "Solana only (Jito via Jupiter swap): slippage in basis points." },
+            "slippage_bps": { "type": "integer", "minimum": 0, "maximum": 2000, "description": "Slippage in basis points. Solana Jito (via Jupiter swap): default 100. EVM adapter envelopes (asset): sent to the adapter, default evm_defi.slippage_bps (50)." },
             "usd_value": { "type": "number" },
             "usd_value_known": { "type": "boolean", "default": false },
             "to": { "type": "string" },
@@ -851,6 +1181,7 @@ fn write_defi_staking_schemas() -> Vec<Value> {
             "value_wei": { "type": "string", "default": "0" },
             "tx_b64": { "type": "string" },
             "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+            "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
             "asset": { "type": "object" }
           },
           "required": ["chain"],
@@ -875,6 +1206,8 @@ fn write_defi_liquidity_schemas() -> Vec<Value> {
             "value_wei": { "type": "string", "default": "0" },
             "tx_b64": { "type": "string" },
             "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+            "slippage_bps": { "type": "integer", "minimum": 0, "description": "EVM adapter envelopes (asset): slippage sent to the adapter. Defaults to evm_defi.slippage_bps (50); capped by policy.max_slippage_bps." },
+            "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
             "asset": { "type": "object" }
           },
           "required": ["chain"],
@@ -894,6 +1227,8 @@ fn write_defi_liquidity_schemas() -> Vec<Value> {
             "value_wei": { "type": "string", "default": "0" },
             "tx_b64": { "type": "string" },
             "allowed_program_ids": { "type": "array", "items": { "type": "string" } },
+            "slippage_bps": { "type": "integer", "minimum": 0, "description": "EVM adapter envelopes (asset): slippage sent to the adapter. Defaults to evm_defi.slippage_bps (50); capped by policy.max_slippage_bps." },
+            "deadline_seconds": { "type": "integer", "minimum": 30, "maximum": 86400, "description": "EVM adapter envelopes (asset): seconds the transaction stays executable; sent to the adapter as an absolute unix `deadline`. Defaults to evm_defi.deadline_seconds (1200)." },
             "asset": { "type": "object" }
           },
           "required": ["chain"],
@@ -902,15 +1237,31 @@ fn write_defi_liquidity_schemas() -> Vec<Value> {
     ]
 }
 
+fn write_defi_attest_schema() -> Value {
+    json!({ "name": "attest_token", "description": "One-time Wormhole attestation of an EVM token to Solana: calls attestToken on the source chain, waits for the signed VAA, then creates the wrapped mint on Solana. Run it when bridge_tokens fails with token_not_attested. Costs gas on the source chain plus Solana rent, and always requires confirmation. Returns already_attested=true without sending anything if the wrapped mint exists.", "inputSchema": {
+      "type": "object",
+      "properties": {
+        "wallet": { "type": "string", "description": "If omitted, uses the active wallet." },
+        "account_index": { "type": "integer", "minimum": 0, "description": "If omitted, uses the active account index." },
+        "chain": { "type": "string", "description": "EVM chain the token is native to (must have a Wormhole token bridge)." },
+        "token": { "type": "string", "description": "ERC-20 contract address on chain." },
+        "to_chain": { "type": "string", "enum": ["solana"], "default": "solana", "description": "Chain to create the wrapped asset on." },
+        "attest_txid": { "type": "string", "description": "Resume: an earlier attestToken tx hash whose VAA was not yet signed. Skips the source-chain tx and only creates the wrapped mint." }
+      },
+      "required": ["chain", "token"],
+      "additionalProperties": false
+    }})
+}
+
 fn write_defi_schemas() -> Vec<Value> {
-    let mut schemas = vec![write_defi_bridge_schema()];
+    let mut schemas = vec![write_defi_bridge_schema(), write_defi_attest_schema()];
     schemas.extend(write_defi_lending_schemas());
     schemas.extend(write_defi_staking_schemas());
     schemas.extend(write_defi_liquidity_schemas());
     schemas
 }
 
-pub fn list_tools_result() -> Value {
+pub fn list_tools_result(cfg: &crate::config::SeashailConfig) -> Value {
     // Tool surface served via MCP.
     let mut tools = network_tool_schemas();
     tools.extend(read_tool_schemas());
@@ -920,5 +1271,27 @@ pub fn list_tools_result() -> Value {
     tools.extend(nft_tool_schemas());
     tools.extend(write_spot_schemas());
     tools.extend(write_defi_schemas());
+    for t in &mut tools {
+        let accepts_note = t
+            .get("name")
+            .and_then(Value::as_str)
+            .is_some_and(super::tool_accepts_note);
+        if let Some(props) = t
+            .pointer_mut("/inputSchema/properties")
+            .and_then(Value::as_object_mut)
+            .filter(|_| accepts_note)
+        {
+            props.insert(
+                "note".into(),
+                json!({ "type": "string", "maxLength": 280, "description": "Optional bookkeeping note (e.g. \"rent payment\") stored on this call's tx history entries. Stored locally, never on-chain unless a tool says otherwise (see send_transaction memo_onchain)." }),
+            );
+        }
+    }
+    // Safe mode: tools disabled by config are hidden so agents never attempt them.
+    tools.retain(|t| {
+        t.get("name")
+            .and_then(Value::as_str)
+            .is_some_and(|name| super::tool_enabled(cfg, name))
+    });
     json!({ "tools": tools })
 }
@@ -0,0 +1,371 @@
+//! Optional wait for a broadcast transaction to reach the configured confirmation depth
+//! (`[tx_confirmation]` in config) before the tool result is returned.
+
+use std::str::FromStr as _;
+use std::time::Duration;
+
+use alloy::primitives::B256;
+use serde_json::{json, Value};
+use solana_sdk::signature::Signature;
+
+use super::super::jsonrpc::JsonRpcResponse;
+use super::super::state::effective_network_mode;
+use super::super::transport::write_frame;
+use super::super::{ConnState, SharedState};
+use super::helpers::solana_fallback_urls;
+use super::tool_group;
+use crate::chains::{bitcoin::BitcoinChain, evm::EvmChain, solana::SolanaChain};
+use crate::config::NetworkMode;
+use crate::keystore::Keystore;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Where a transaction stands after polling.
+struct Confirmation {
+    /// `confirmed`, `failed`, or `pending` (timed out / not yet seen).
+    status: &'static str,
+    confirmations: u64,
+    required: u64,
+    error: Option<String>,
+}
+
+impl Confirmation {
+    const fn pending(confirmations: u64, required: u64) -> Self {
+        Self {
+            status: "pending",
+            confirmations,
+            required,
+            error: None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut v = json!({
+          "status": self.status,
+          "confirmations": self.confirmations,
+          "required": self.required,
+        });
+        if let (Some(obj), Some(e)) = (v.as_object_mut(), self.error.as_ref()) {
+            obj.insert("error".into(), Value::from(e.as_str()));
+        }
+        v
+    }
+}
+
+enum Target {
+    Evm(EvmChain, B256),
+    Solana(SolanaChain, Signature, bool),
+    Bitcoin(BitcoinChain, String),
+}
+
+fn tool_broadcasts(tool_name: &str) -> bool {
+    !tool_name.starts_with("get_")
+        && !matches!(
+            tool_name,
+            "pumpfun_list_new_coins" | "pumpfun_get_coin_info" | "inspect_token"
+        )
+        && !matches!(tool_group(tool_name), "network" | "policy" | "wallets")
+}
+
+fn success_payload(resp: &JsonRpcResponse) -> Option<Value> {
+    resp.result
+        .as_ref()
+        .filter(|r| r.get("isError").and_then(Value::as_bool) == Some(false))
+        .and_then(|r| r.get("content"))
+        .and_then(|c| c.get(0_usize))
+        .and_then(|c| c.get("text"))
+        .and_then(Value::as_str)
+        .and_then(|t| serde_json::from_str::<Value>(t).ok())
+}
+
+fn replace_payload(resp: &mut JsonRpcResponse, payload: &Value) {
+    if let Some(c) = resp
+        .result
+        .as_mut()
+        .and_then(|r| r.get_mut("content"))
+        .and_then(|c| c.get_mut(0_usize))
+        .and_then(Value::as_object_mut)
+    {
+        c.insert("text".into(), Value::String(payload.to_string()));
+    }
+}
+
+fn tx_id(payload: &Value) -> Option<String> {
+    ["txid", "signature", "tx_hash"]
+        .iter()
+        .find_map(|k| payload.get(*k).and_then(Value::as_str))
+        .map(str::to_owned)
+}
+
+fn target(
+    shared: &SharedState,
+    conn: &ConnState,
+    chain: &str,
+    id: &str,
+) -> eyre::Result<Option<Target>> {
+    let mode = effective_network_mode(shared, conn);
+    Ok(match chain {
+        "solana" => {
+            let Ok(sig) = Signature::from_str(id) else {
+                return Ok(None);
+            };
+            let sol = SolanaChain::new_with_fallbacks(
+                &shared.cfg.rpc.solana_rpc_url,
+                solana_fallback_urls(shared, mode),
+                &shared.cfg.http.jupiter_base_url,
+                shared.cfg.http.jupiter_api_key.as_deref(),
+                shared.cfg.rpc.solana_default_compute_unit_limit,
+                shared
+                    .cfg
+                    .rpc
+                    .solana_default_compute_unit_price_micro_lamports,
+            );
+            let finalized = shared
+                .cfg
+                .tx_confirmation
+                .solana_commitment
+                .eq_ignore_ascii_case("finalized");
+            Some(Target::Solana(sol, sig, finalized))
+        }
+        "bitcoin" => {
+            let base = if mode == NetworkMode::Testnet {
+                &shared.cfg.http.bitcoin_api_base_url_testnet
+            } else {
+                &shared.cfg.http.bitcoin_api_base_url_mainnet
+            };
+            Some(Target::Bitcoin(BitcoinChain::new(base)?, id.to_owned()))
+        }
+        _ => {
+            let (Some(rpc_url), Some(chain_id), Ok(hash)) = (
+                shared.cfg.rpc.evm_rpc_urls.get(chain),
+                shared.cfg.rpc.evm_chain_ids.get(chain),
+                B256::from_str(id),
+            ) else {
+                return Ok(None);
+            };
+            let mut evm = EvmChain::for_name(chain, *chain_id, rpc_url, &shared.cfg.http);
+            if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+                evm.fallback_rpc_urls.clone_from(fb);
+            }
+            Some(Target::Evm(evm, hash))
+        }
+    })
+}
+
+/// One status check. Transient upstream errors are treated as "not yet seen".
+async fn poll(target: &Target, evm_required: u64, btc_required: u64) -> Confirmation {
+    match target {
+        Target::Evm(evm, hash) => {
+            let Ok(Some(receipt)) = evm.get_tx_receipt(*hash).await else {
+                return Confirmation::pending(0, evm_required);
+            };
+            let tip = evm.get_block_number().await.unwrap_or(0);
+            let depth = receipt
+                .block_number
+                .map_or(0, |b| tip.saturating_sub(b).saturating_add(1));
+            if !receipt.status() {
+                return Confirmation {
+                    status: "failed",
+                    confirmations: depth,
+                    required: evm_required,
+                    error: Some("transaction reverted".into()),
+                };
+            }
+            if depth >= evm_required {
+                Confirmation {
+                    status: "confirmed",
+                    confirmations: depth,
+                    required: evm_required,
+                    error: None,
+                }
+            } else {
+                Confirmation::pending(depth, evm_required)
+            }
+        }
+        Target::Solana(sol, sig, finalized) => {
+            let required = 1_u64;
+            let Ok(Some(st)) = sol.get_signature_confirmation(*sig).await else {
+                return Confirmation::pending(0, required);
+            };
+            if let Some(e) = st.err {
+                return Confirmation {
+                    status: "failed",
+                    confirmations: 1,
+                    required,
+                    error: Some(e),
+                };
+            }
+            if (*finalized && st.finalized) || (!*finalized && st.confirmed) {
+                Confirmation {
+                    status: "confirmed",
+                    confirmations: 1,
+                    required,
+                    error: None,
+                }
+            } else {
+                Confirmation::pending(0, required)
+            }
+        }
+        Target::Bitcoin(btc, txid) => {
+            let Ok(Some(st)) = btc.tx_status(txid).await else {
+                return Confirmation::pending(0, btc_required);
+            };
+            let depth = match (st.confirmed, st.block_height) {
+                (true, Some(h)) => btc
+                    .tip_height()
+                    .await
+                    .map_or(1, |tip| tip.saturating_sub(h).saturating_add(1)),
+                (true, None) => 1,
+                (false, _) => 0,
+            };
+            if depth >= btc_required {
+                Confirmation {
+                    status: "confirmed",
+                    confirmations: depth,
+                    required: btc_required,
+                    error: None,
+                }
+            } else {
+                Confirmation::pending(depth, btc_required)
+            }
+        }
+    }
+}
+
+async fn notify_progress<W>(conn: &ConnState, stdout: &mut W, c: &Confirmation, message: &str)
+where
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let Some(token) = conn.progress_token.as_ref() else {
+        return;
+    };
+    let frame = json!({
+      "jsonrpc": "2.0",
+      "method": "notifications/progress",
+      "params": {
+        "progressToken": token,
+        "progress": c.confirmations,
+        "total": c.required,
+        "message": message,
+      }
+    });
+    // Progress is best-effort; a failed write must not fail the (already broadcast) transaction.
+    let _progress_sent = write_frame(stdout, &frame).await;
+}
+
+/// When `tx_confirmation.wait` is enabled, block a successful write tool result until its
+/// transaction reaches the configured depth (or times out), mark the tx history entry
+/// `confirmed`/`failed`, and attach a `confirmation` object to the result.
+///
+/// The transaction is already broadcast, so this never fails the tool call: anything that goes
+/// wrong is logged and reported as `confirmation_error` on the success payload instead (an error
+/// result would invite a retry, i.e. a double spend).
+pub(super) async fn maybe_await_confirmation<W>(
+    resp: JsonRpcResponse,
+    tool_name: &str,
+    shared: &SharedState,
+    conn: &ConnState,
+    stdout: &mut W,
+) -> JsonRpcResponse
+where
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let cfg = &shared.cfg.tx_confirmation;
+    if !cfg.wait || !tool_broadcasts(tool_name) {
+        return resp;
+    }
+    let Some(mut payload) = success_payload(&resp) else {
+        return resp;
+    };
+    // Signed-only (durable nonce) transactions have not been broadcast; nothing to wait for.
+    if payload.get("broadcast").and_then(Value::as_bool) == Some(false) {
+        return resp;
+    }
+    let (Some(chain), Some(id)) = (
+        payload
+            .get("chain")
+            .and_then(Value::as_str)
+            .map(str::to_owned),
+        tx_id(&payload),
+    ) else {
+        return resp;
+    };
+    let target = match target(shared, conn, &chain, &id) {
+        Ok(Some(t)) => t,
+        Ok(None) => return resp,
+        Err(e) => {
+            tracing::warn!(chain = %chain, tx = %id, error = %e, "tx confirmation: cannot poll");
+            return with_confirmation(resp, &mut payload, None, Some(&e));
+        }
+    };
+
+    let evm_required = cfg.evm_confirmations.max(1);
+    let btc_required = cfg.bitcoin_confirmations.max(1);
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(cfg.timeout_seconds);
+    let mut last_reported = None;
+    let outcome = loop {
+        let c = poll(&target, evm_required, btc_required).await;
+        if c.status != "pending" {
+            break c;
+        }
+        if last_reported != Some(c.confirmations) {
+            last_reported = Some(c.confirmations);
+            let message = format!(
+                "{chain} {id}: {}/{} confirmations",
+                c.confirmations, c.required
+            );
+            notify_progress(conn, stdout, &c, &message).await;
+        }
+        if tokio::time::Instant::now() + POLL_INTERVAL > deadline {
+            break c;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    };
+
+    let recorded = if outcome.status == "pending" {
+        Ok(())
+    } else {
+        record_outcome(shared, &id, &outcome).await
+    };
+    if let Err(e) = &recorded {
+        tracing::warn!(chain = %chain, tx = %id, error = %e, "tx confirmation: history update failed");
+    }
+    with_confirmation(resp, &mut payload, Some(&outcome), recorded.err().as_ref())
+}
+
+/// Mark the tx history entry `confirmed`/`failed`.
+async fn record_outcome(
+    shared: &SharedState,
+    id: &str,
+    outcome: &Confirmation,
+) -> eyre::Result<()> {
+    let mut fields = json!({ "confirmations": outcome.confirmations });
+    if let (Some(obj), Some(e)) = (fields.as_object_mut(), outcome.error.as_ref()) {
+        obj.insert("error".into(), Value::from(e.as_str()));
+    }
+    // Tx history rewrites must not interleave with appends from other processes.
+    let lock = shared.acquire_write_lock().await?;
+    let updated = shared
+        .ks
+        .update_tx_history_status(id, outcome.status, &fields);
+    Keystore::release_lock(lock)?;
+    updated
+}
+
+fn with_confirmation(
+    mut resp: JsonRpcResponse,
+    payload: &mut Value,
+    outcome: Option<&Confirmation>,
+    error: Option<&eyre::Report>,
+) -> JsonRpcResponse {
+    if let Some(obj) = payload.as_object_mut() {
+        if let Some(c) = outcome {
+            obj.insert("confirmation".into(), c.to_json());
+        }
+        if let Some(e) = error {
+            obj.insert("confirmation_error".into(), Value::from(format!("{e:#}")));
+        }
+    }
+    replace_payload(&mut resp, payload);
+    resp
+}
@@ -1,4 +1,8 @@
-use serde_json::Value;
+use serde_json::{json, Value};
+
+use crate::errors::ToolError;
+
+use super::super::jsonrpc::{ok, tool_ok, JsonRpcResponse};
 
 #[must_use]
 pub(super) fn parse_usd_value(args: &Value) -> (f64, bool) {
@@ -106,3 +110,87 @@ pub(super) fn summarize_sim_error(e: &eyre::Report, label: &str) -> String {
         format!("{label}: {s}")
     }
 }
+
+/// Zero every USD amount in `v` (numbers or numeric strings under keys containing `usd`).
+/// Returns how many values were zeroed.
+pub(super) fn zero_usd_values(v: &mut Value) -> usize {
+    match v {
+        Value::Object(map) => map
+            .iter_mut()
+            .map(|(k, child)| {
+                if !k.to_ascii_lowercase().contains("usd") {
+                    return zero_usd_values(child);
+                }
+                match child {
+                    Value::Number(_) => *child = Value::from(0.0_f64),
+                    Value::String(s) if s.trim().parse::<f64>().is_ok() => {
+                        *child = Value::from("0");
+                    }
+                    Value::Null | Value::Bool(_) | Value::String(_) => return 0,
+                    Value::Array(_) | Value::Object(_) => return zero_usd_values(child),
+                }
+                1
+            })
+            .sum(),
+        Value::Array(items) => items.iter_mut().map(zero_usd_values).sum(),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => 0,
+    }
+}
+
+/// Split a nested tool response into `(is_error, payload)`.
+pub(super) fn tool_response_payload(resp: &JsonRpcResponse) -> (bool, Value) {
+    let Some(result) = resp.result.as_ref() else {
+        let msg = resp.error.as_ref().map(|e| e.message.clone());
+        return (true, json!({ "code": "internal_error", "message": msg }));
+    };
+    let is_error = result
+        .get("isError")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let payload = result
+        .get("content")
+        .and_then(Value::as_array)
+        .and_then(|c| c.first())
+        .and_then(|c| c.get("text"))
+        .and_then(Value::as_str)
+        .and_then(|t| serde_json::from_str::<Value>(t).ok())
+        .unwrap_or(Value::Null);
+    (is_error, payload)
+}
+
+/// Output shape for heavy read tools: `compact` (the default) keeps the fields agents act on,
+/// `full` returns the complete upstream payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Verbosity {
+    Compact,
+    Full,
+}
+
+pub(super) fn parse_verbosity(args: &Value) -> Result<Verbosity, ToolError> {
+    match args.get("verbosity").and_then(Value::as_str).map(str::trim) {
+        None | Some("" | "compact") => Ok(Verbosity::Compact),
+        Some("full") => Ok(Verbosity::Full),
+        Some(other) => Err(ToolError::new(
+            "invalid_request",
+            format!("verbosity must be compact or full (got {other})"),
+        )),
+    }
+}
+
+/// Re-emit a successful tool response through `project` when `verbosity` is compact. Errors and
+/// `full` responses pass through untouched.
+pub(super) fn project_response(
+    req_id: Value,
+    resp: JsonRpcResponse,
+    verbosity: Verbosity,
+    project: impl FnOnce(&Value) -> Value,
+) -> JsonRpcResponse {
+    if verbosity == Verbosity::Full {
+        return resp;
+    }
+    let (is_error, payload) = tool_response_payload(&resp);
+    if is_error || payload.is_null() {
+        return resp;
+    }
+    ok(req_id, tool_ok(project(&payload)))
+}
@@ -1,7 +1,12 @@
 use serde_json::{json, Value};
 
-use super::super::super::jsonrpc::{ok, tool_ok, JsonRpcResponse};
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::SharedState;
+use crate::errors::ToolError;
+
+fn valid_scope(shared: &SharedState, scope: &str) -> bool {
+    matches!(scope, "evm" | "solana" | "bitcoin") || shared.cfg.rpc.evm_rpc_urls.contains_key(scope)
+}
 
 pub fn handle(req_id: Value, args: &Value, shared: &SharedState) -> eyre::Result<JsonRpcResponse> {
     let name = args.get("wallet").and_then(|v| v.as_str()).unwrap_or("");
@@ -10,6 +15,61 @@ pub fn handle(req_id: Value, args: &Value, shared: &SharedState) -> eyre::Result
         .and_then(serde_json::Value::as_u64)
         .and_then(|v| u32::try_from(v).ok())
         .unwrap_or(0);
-    shared.ks.set_active_wallet(name, idx)?;
-    Ok(ok(req_id, tool_ok(json!({ "ok": true }))))
+    let scope = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    let clear = args.get("clear").and_then(Value::as_bool).unwrap_or(false);
+
+    if name.is_empty() && !clear {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new("invalid_request", "missing wallet")),
+        ));
+    }
+
+    if scope.is_empty() {
+        if clear {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new(
+                    "invalid_request",
+                    "clear requires chain (the global active wallet cannot be cleared)",
+                )),
+            ));
+        }
+        shared.ks.set_active_wallet(name, idx)?;
+        return Ok(ok(req_id, tool_ok(json!({ "ok": true }))));
+    }
+
+    if !valid_scope(shared, scope) {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_request",
+                format!("unknown chain scope: {scope} (use a chain name or evm/solana/bitcoin)"),
+            )),
+        ));
+    }
+    let cleared = if clear {
+        shared.ks.clear_active_wallet_for_chain(scope)?
+    } else {
+        shared.ks.set_active_wallet_for_chain(scope, name, idx)?;
+        false
+    };
+    let global = shared
+        .ks
+        .get_active_wallet()?
+        .map(|(w, i)| json!({ "wallet": w.name, "account_index": i }));
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "ok": true,
+          "chain": scope,
+          "cleared": cleared,
+          "active": global,
+          "active_by_chain": shared.ks.active_wallet_by_chain()?,
+        })),
+    ))
 }
@@ -1,8 +1,9 @@
-use serde_json::json;
+use serde_json::{json, Value};
 
 use super::super::super::elicitation::ensure_unlocked;
-use super::super::super::jsonrpc::{ok, tool_ok, JsonRpcResponse};
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::WalletHandlerCtx;
+use crate::errors::ToolError;
 use crate::keystore::Keystore;
 use crate::wallet::WalletKind;
 
@@ -11,28 +12,52 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let count = ctx.args.get("count").and_then(Value::as_u64).unwrap_or(1);
+    let Some(count) = u32::try_from(count).ok().filter(|c| (1..=100).contains(c)) else {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "count must be between 1 and 100",
+            )),
+        ));
+    };
+
+    let lock = ctx.shared.acquire_write_lock().await?;
     let name = ctx
         .args
         .get("wallet")
         .and_then(|v| v.as_str())
         .unwrap_or("");
     let w = ctx.shared.ks.get_wallet_by_name(name)?;
-    let (info, new_index) = match w {
+    let added = match w {
         Some(w)
             if w.kind == WalletKind::Generated
                 && !ctx.shared.ks.generated_wallet_needs_passphrase(&w.id) =>
         {
-            ctx.shared.ks.add_account_no_passphrase(name)?
+            ctx.shared.ks.add_accounts_auto(name, None, count)?
+        }
+        Some(w) if w.kind == WalletKind::Watch => {
+            ctx.shared.ks.add_accounts_auto(name, None, count)?
         }
         _ => {
             let key = ensure_unlocked(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout).await?;
-            ctx.shared.ks.add_account(name, &key)?
+            ctx.shared.ks.add_accounts_auto(name, Some(&key), count)?
         }
     };
     Keystore::release_lock(lock)?;
+
+    let new_indexes: Vec<u32> = added.iter().map(|(_, idx)| *idx).collect();
+    let Some((info, _)) = added.into_iter().last() else {
+        eyre::bail!("no account derived");
+    };
     Ok(ok(
         ctx.req_id.clone(),
-        tool_ok(json!({ "wallet": info, "new_account_index": new_index })),
+        tool_ok(json!({
+          "wallet": info,
+          "new_account_index": new_indexes.first(),
+          "new_account_indexes": new_indexes,
+          "added": new_indexes.len()
+        })),
     ))
 }
@@ -3,7 +3,7 @@ use serde_json::{json, Value};
 use super::super::super::elicitation::elicit_form;
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::WalletHandlerCtx;
-use crate::errors::SeashailError;
+use crate::errors::{SeashailError, ToolError};
 use crate::keystore::Keystore;
 
 pub async fn handle<R, W>(ctx: &mut WalletHandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
@@ -12,13 +12,25 @@ where
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
     // Serialize creates/imports across competing binaries.
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let name = ctx
         .args
         .get("name")
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_owned();
+    if name.trim().is_empty() {
+        Keystore::release_lock(lock)?;
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new("invalid_request", "missing name")),
+        ));
+    }
+    // Reject duplicates before any prompt so the user never types a passphrase for nothing.
+    if let Err(e) = ctx.shared.ks.ensure_wallet_name_available(&name) {
+        Keystore::release_lock(lock)?;
+        return Err(e);
+    }
 
     // Passphrase entry
     let pass_schema = json!({
@@ -14,7 +14,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let args = &ctx.args;
 
     // Default to the active wallet if `wallet` is omitted.
@@ -63,39 +63,43 @@ where
         None
     };
 
-    // Create N new accounts under the selected wallet root.
-    let mut created: Vec<Value> = vec![];
-    for _ in 0..count_u32 {
-        let result = match key.as_ref() {
-            Some(k) => ctx.shared.ks.add_account(&w.name, k),
-            None => ctx.shared.ks.add_account_no_passphrase(&w.name),
-        };
-        let (info, new_index) = match result {
-            Ok(v) => v,
-            Err(e) => {
-                Keystore::release_lock(lock)?;
-                return Ok(ok(
-                    ctx.req_id.clone(),
-                    tool_err(ToolError::new("wallet_pool_failed", e.to_string())),
-                ));
-            }
-        };
-
-        created.push(json!({
-          "account_index": new_index,
-          "evm_address": info.addresses.evm.get(new_index as usize).cloned().unwrap_or_default(),
-          "solana_address": info.addresses.solana.get(new_index as usize).cloned().unwrap_or_default()
-        }));
-    }
+    // Create N new accounts under the selected wallet root (one secret-decryption pass).
+    let added = match ctx
+        .shared
+        .ks
+        .add_accounts_auto(&w.name, key.as_ref(), count_u32)
+    {
+        Ok(v) => v,
+        Err(e) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new("wallet_pool_failed", e.to_string())),
+            ));
+        }
+    };
+    let created: Vec<Value> = added
+        .iter()
+        .map(|(info, new_index)| {
+            json!({
+              "account_index": new_index,
+              "evm_address": info.addresses.evm.get(*new_index as usize).cloned().unwrap_or_default(),
+              "solana_address": info.addresses.solana.get(*new_index as usize).cloned().unwrap_or_default()
+            })
+        })
+        .collect();
 
     // Record a history event so strategies can reason about pools.
-    ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": "wallet_pool_created",
-      "wallet": w.name,
-      "count": count_u32
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "wallet_pool_created",
+          "wallet": w.name,
+          "count": count_u32
+        }),
+        None,
+    )?;
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(),
       "tool": "create_wallet_pool",
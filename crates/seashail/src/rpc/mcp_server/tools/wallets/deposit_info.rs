@@ -5,7 +5,7 @@ use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
 use super::super::helpers::{is_native_token, resolve_wallet_and_account};
 use crate::{
-    chains::{evm::EvmChain, solana::SolanaChain},
+    chains::{evm::EvmChain, native::native_token, solana::SolanaChain},
     config::NetworkMode,
     errors::ToolError,
 };
@@ -14,6 +14,8 @@ struct DepositAddr {
     address: String,
     chain_kind: &'static str,
     chain_id: Option<u64>,
+    /// Memo / destination tag the sender must attach. None of the supported chains use one.
+    memo: Option<String>,
 }
 
 fn resolve_deposit_address(
@@ -34,6 +36,7 @@ fn resolve_deposit_address(
             address: addr.clone(),
             chain_kind: "solana",
             chain_id: None,
+            memo: None,
         })
     } else if chain == "bitcoin" {
         let mode = effective_network_mode(shared, conn);
@@ -52,6 +55,7 @@ fn resolve_deposit_address(
             address: addr.clone(),
             chain_kind: "bitcoin",
             chain_id: None,
+            memo: None,
         })
     } else {
         if !shared.cfg.rpc.evm_rpc_urls.contains_key(chain) {
@@ -71,6 +75,7 @@ fn resolve_deposit_address(
             address: addr.clone(),
             chain_kind: "evm",
             chain_id,
+            memo: None,
         })
     }
 }
@@ -127,18 +132,8 @@ fn resolve_token_info(
         } else {
             kind = "unknown";
         }
-    } else if chain_kind == "bitcoin" {
-        symbol = Some("BTC".to_owned());
-    } else if chain_kind == "solana" {
-        symbol = Some("SOL".to_owned());
-    } else if chain == "polygon" {
-        symbol = Some("POL".to_owned());
-    } else if chain == "bnb" || chain == "bnb-testnet" {
-        symbol = Some("BNB".to_owned());
-    } else if chain == "avalanche" {
-        symbol = Some("AVAX".to_owned());
     } else {
-        symbol = Some("ETH".to_owned());
+        symbol = Some(native_token(chain).symbol.to_owned());
     }
 
     TokenInfo {
@@ -148,6 +143,63 @@ fn resolve_token_info(
     }
 }
 
+/// Chains where Circle-issued USDC coexists with an older bridged variant (USDC.e) or is replaced by
+/// a pegged token; exchanges often default to the wrong one.
+fn usdc_variant_note(chain: &str) -> Option<&'static str> {
+    match chain {
+        "arbitrum" | "optimism" | "polygon" | "avalanche" => Some(
+            "Native USDC and bridged USDC.e are different tokens on this chain. Make sure the sender withdraws native (Circle-issued) USDC; USDC.e arrives at the same address but is a separate asset.",
+        ),
+        "bnb" => Some(
+            "USDC on BNB Chain is Binance-Peg USDC (18 decimals), not Circle-issued USDC. It cannot be redeemed with Circle directly.",
+        ),
+        _ => None,
+    }
+}
+
+/// Cross-chain pitfalls for depositing `token_hint` on `chain`: the asset a user expects may only
+/// exist there as a bridged/wrapped variant, or not at all.
+fn asset_warnings(chain: &str, chain_kind: &str, token_hint: &str) -> Vec<String> {
+    let mut out = vec![];
+    let native = native_token(chain);
+    if is_native_token(token_hint) || token_hint.eq_ignore_ascii_case(native.symbol) {
+        return out;
+    }
+    if chain_kind == "bitcoin" {
+        out.push("Bitcoin deposits support BTC only. Tokens from other chains sent to this address are lost.".to_owned());
+        return out;
+    }
+    if token_hint.eq_ignore_ascii_case("usdc") {
+        if let Some(note) = usdc_variant_note(chain) {
+            out.push(note.to_owned());
+        }
+        return out;
+    }
+    let foreign_home = [
+        "bitcoin",
+        "solana",
+        "ethereum",
+        "bnb",
+        "avalanche",
+        "polygon",
+    ]
+    .into_iter()
+    .find(|home| {
+        let n = native_token(home);
+        n.symbol != native.symbol
+            && (token_hint.eq_ignore_ascii_case(n.symbol)
+                || (n.symbol == "POL" && token_hint.eq_ignore_ascii_case("MATIC")))
+    });
+    if let Some(home) = foreign_home {
+        out.push(format!(
+            "{} is not the native asset of {chain} ({}); there it only exists as a bridged/wrapped token with its own contract. Make sure the sender withdraws on the {chain} network, or deposit on {home} instead.",
+            token_hint.to_uppercase(),
+            native.symbol,
+        ));
+    }
+    out
+}
+
 pub fn handle(
     req_id: Value,
     args: &Value,
@@ -186,6 +238,12 @@ pub fn handle(
         "Only send assets on the selected chain/network. Sending from the wrong chain may be unrecoverable.".to_owned(),
         "Always verify the address on both sides before sending.".to_owned(),
     ];
+    let asset_warnings = asset_warnings(&chain, deposit.chain_kind, token_hint);
+    let memo_note = if deposit.memo.is_some() {
+        format!("A memo/tag is required on {chain}. Deposits sent without it may be lost.")
+    } else {
+        format!("No memo/tag required on {chain}. If the sending exchange asks for one, leave it empty.")
+    };
     if token_hint.eq_ignore_ascii_case("usdc") && token.identifier.is_none() {
         warnings.push("USDC contract/mint is chain-specific. If in doubt, paste the address into a trusted explorer and confirm the USDC token contract/mint before sending.".to_owned());
     }
@@ -205,6 +263,10 @@ pub fn handle(
             "identifier": token.identifier,
           },
           "address": deposit.address,
+          "memo_required": deposit.memo.is_some(),
+          "memo": deposit.memo,
+          "memo_note": memo_note,
+          "asset_warnings": asset_warnings,
           "warnings": warnings
         })),
     ))
@@ -2,11 +2,22 @@ use serde_json::{json, Value};
 
 use super::super::super::elicitation::{elicit_form, ensure_unlocked};
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
-use super::super::helpers::decode_secret;
+use super::super::super::state::effective_network_mode;
+use super::super::super::SharedState;
+use super::super::helpers::{decode_secret, solana_fallback_urls};
 use super::WalletHandlerCtx;
+use crate::chains::{bitcoin::BitcoinChain, evm::EvmChain, solana::SolanaChain};
+use crate::config::NetworkMode;
 use crate::errors::{SeashailError, ToolError};
 use crate::keystore::{utc_now_iso, Keystore};
-use crate::wallet::ImportedKind;
+use crate::wallet::{
+    parse_account_xpub, parse_birthday_date, scan_start_height, watch_addresses_from_list,
+    watch_addresses_from_xpub, ImportedKind, WalletAddressSets, WalletBirthday, WatchXpub,
+    WatchXpubChain, WATCH_MAX_ADDRESSES,
+};
+
+/// Upper bound on the best-effort tip lookups used to map a birthday date to heights.
+const BIRTHDAY_RESOLVE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
 fn parse_import_kind(kind_s: &str) -> Result<ImportedKind, ToolError> {
     match kind_s {
@@ -16,6 +27,246 @@ fn parse_import_kind(kind_s: &str) -> Result<ImportedKind, ToolError> {
     }
 }
 
+/// Optional user-supplied birthday: an approximate first-use date and/or explicit per-chain heights.
+fn parse_birthday(args: &Value) -> Result<Option<WalletBirthday>, ToolError> {
+    let created_at = match args.get("birthday").and_then(Value::as_str) {
+        Some(s) if !s.trim().is_empty() => Some(
+            parse_birthday_date(s)
+                .map_err(|e| ToolError::new("invalid_request", format!("{e:#}")))?,
+        ),
+        _ => None,
+    };
+    let mut heights = std::collections::BTreeMap::new();
+    if let Some(obj) = args.get("birthday_heights").and_then(Value::as_object) {
+        for (chain, h) in obj {
+            let h = h.as_u64().ok_or_else(|| {
+                ToolError::new(
+                    "invalid_request",
+                    format!("birthday_heights.{chain} must be a non-negative integer"),
+                )
+            })?;
+            heights.insert(chain.clone(), h);
+        }
+    }
+    if created_at.is_none() && heights.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(WalletBirthday {
+        created_at,
+        heights,
+    }))
+}
+
+/// Addresses derived from an xpub when `address_count` is omitted.
+const DEFAULT_WATCH_ADDRESS_COUNT: u32 = 5;
+
+/// Public material for a watch-only import (`kind` = `xpub` or `addresses`).
+fn parse_watch_source(
+    args: &Value,
+    kind_s: &str,
+) -> Result<(Option<WatchXpub>, WalletAddressSets), ToolError> {
+    let invalid = |e: eyre::Report| ToolError::new("invalid_request", format!("{e:#}"));
+    if kind_s == "addresses" {
+        let list: Vec<String> = args
+            .get("addresses")
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .filter_map(Value::as_str)
+                    .map(ToOwned::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let addrs = watch_addresses_from_list(&list).map_err(invalid)?;
+        return Ok((None, addrs));
+    }
+
+    let xpub_s = args
+        .get("xpub")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+    if xpub_s.is_empty() {
+        return Err(ToolError::new(
+            "invalid_request",
+            "xpub is required when kind=xpub",
+        ));
+    }
+    let chain = match args.get("xpub_chain").and_then(Value::as_str) {
+        Some("evm") => WatchXpubChain::Evm,
+        Some("bitcoin") => WatchXpubChain::Bitcoin,
+        // SLIP-132 prefixes are Bitcoin-only; a plain xpub could be either.
+        None if xpub_s.starts_with("zpub") || xpub_s.starts_with("vpub") => WatchXpubChain::Bitcoin,
+        _ => {
+            return Err(ToolError::new(
+                "invalid_request",
+                "xpub_chain must be evm or bitcoin",
+            ))
+        }
+    };
+    let count = match args.get("address_count") {
+        None | Some(Value::Null) => DEFAULT_WATCH_ADDRESS_COUNT,
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| (1..=WATCH_MAX_ADDRESSES).contains(n))
+            .ok_or_else(|| {
+                ToolError::new(
+                    "invalid_request",
+                    format!("address_count must be between 1 and {WATCH_MAX_ADDRESSES}"),
+                )
+            })?,
+    };
+    let watch = WatchXpub {
+        chain,
+        xpub: parse_account_xpub(xpub_s).map_err(invalid)?.to_string(),
+    };
+    let indexes: Vec<u32> = (0..count).collect();
+    let addrs = watch_addresses_from_xpub(&watch, &indexes).map_err(invalid)?;
+    Ok((Some(watch), addrs))
+}
+
+async fn chain_tip_height(shared: &SharedState, chain: &str) -> eyre::Result<u64> {
+    match chain {
+        "solana" => {
+            let sol = SolanaChain::new_with_fallbacks(
+                &shared.cfg.rpc.solana_rpc_url,
+                solana_fallback_urls(shared, NetworkMode::Mainnet),
+                &shared.cfg.http.jupiter_base_url,
+                shared.cfg.http.jupiter_api_key.as_deref(),
+                None,
+                None,
+            );
+            sol.get_slot().await
+        }
+        "bitcoin" => {
+            BitcoinChain::new(&shared.cfg.http.bitcoin_api_base_url_mainnet)?
+                .tip_height()
+                .await
+        }
+        _ => {
+            let rpc_url = shared
+                .cfg
+                .rpc
+                .evm_rpc_urls
+                .get(chain)
+                .ok_or_else(|| eyre::eyre!("unknown evm chain: {chain}"))?;
+            let chain_id = *shared
+                .cfg
+                .rpc
+                .evm_chain_ids
+                .get(chain)
+                .ok_or_else(|| eyre::eyre!("missing evm chain id: {chain}"))?;
+            let mut evm = EvmChain::for_name(chain, chain_id, rpc_url, &shared.cfg.http);
+            if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+                evm.fallback_rpc_urls.clone_from(fb);
+            }
+            evm.get_block_number().await
+        }
+    }
+}
+
+/// Map a date-only birthday to mainnet start heights for the chains this key can hold funds on.
+///
+/// Best effort: chains whose tip lookup fails (or times out) keep just the date, which scans
+/// map to a height on their own.
+async fn resolve_birthday_heights(
+    shared: &SharedState,
+    mut chains: Vec<String>,
+    birthday: &mut WalletBirthday,
+) {
+    let Some(created_at) = birthday.created_at else {
+        return;
+    };
+    chains.retain(|c| !birthday.heights.contains_key(c));
+
+    let date_only = WalletBirthday::at(created_at);
+    let now = chrono::Utc::now().timestamp();
+    let lookups = async {
+        for chain in chains {
+            match chain_tip_height(shared, &chain).await {
+                Ok(tip) => {
+                    let h = scan_start_height(Some(&date_only), &chain, tip, now);
+                    birthday.heights.insert(chain, h);
+                }
+                Err(e) => {
+                    tracing::debug!(
+                        chain = %chain,
+                        error = %format!("{e:#}"),
+                        "birthday height lookup failed"
+                    );
+                }
+            }
+        }
+    };
+    let _timed_out = tokio::time::timeout(BIRTHDAY_RESOLVE_TIMEOUT, lookups).await;
+}
+
+/// Mainnet chains an imported key can hold funds on.
+fn import_scan_chains(
+    shared: &SharedState,
+    kind: ImportedKind,
+    private_key_chain: Option<&str>,
+) -> Vec<String> {
+    let mut chains = shared.cfg.default_chains_for_mode(NetworkMode::Mainnet);
+    match (kind, private_key_chain) {
+        (ImportedKind::Mnemonic, _) => chains.push("bitcoin".to_owned()),
+        (ImportedKind::PrivateKey, Some("solana")) => chains.retain(|c| c == "solana"),
+        (ImportedKind::PrivateKey, _) => chains.retain(|c| c != "solana"),
+    }
+    chains
+}
+
+/// Mainnet chains a watch-only wallet has addresses for.
+fn watch_scan_chains(shared: &SharedState, addrs: &WalletAddressSets) -> Vec<String> {
+    let mut chains = shared.cfg.default_chains_for_mode(NetworkMode::Mainnet);
+    chains.retain(|c| {
+        if c == "solana" {
+            !addrs.solana.is_empty()
+        } else {
+            !addrs.evm.is_empty()
+        }
+    });
+    if !addrs.bitcoin_mainnet.is_empty() {
+        chains.push("bitcoin".to_owned());
+    }
+    chains
+}
+
+/// What an import installs, parsed from the tool arguments.
+enum ImportSource {
+    Watch(Option<WatchXpub>, WalletAddressSets),
+    Secret(ImportedKind),
+}
+
+/// Parse the import source and birthday, resolving birthday heights on mainnet.
+///
+/// The height lookups hit the network (up to `BIRTHDAY_RESOLVE_TIMEOUT`), so this runs before the
+/// keystore write lock is taken.
+async fn prepare_import(
+    shared: &SharedState,
+    mainnet: bool,
+    args: &Value,
+    kind_s: &str,
+    private_key_chain: Option<&str>,
+) -> Result<(ImportSource, Option<WalletBirthday>), ToolError> {
+    let mut birthday = parse_birthday(args)?;
+    let (source, chains) = if matches!(kind_s, "xpub" | "addresses") {
+        let (watch_xpub, addrs) = parse_watch_source(args, kind_s)?;
+        let chains = watch_scan_chains(shared, &addrs);
+        (ImportSource::Watch(watch_xpub, addrs), chains)
+    } else {
+        let kind = parse_import_kind(kind_s)?;
+        let chains = import_scan_chains(shared, kind, private_key_chain);
+        (ImportSource::Secret(kind), chains)
+    };
+    // Heights are only resolved on mainnet; testnet imports keep the date alone.
+    if let Some(b) = birthday.as_mut().filter(|_| mainnet) {
+        resolve_birthday_heights(shared, chains, b).await;
+    }
+    Ok((source, birthday))
+}
+
 const fn secret_prompt_msg(kind: ImportedKind) -> &'static str {
     match kind {
         ImportedKind::Mnemonic => {
@@ -99,12 +350,45 @@ where
     }
 }
 
+/// Watch-only import. Only public material is involved, so there is no confirmation, passphrase
+/// or secret prompt.
+async fn handle_watch<R, W>(
+    ctx: &mut WalletHandlerCtx<'_, R, W>,
+    lock: std::fs::File,
+    name: String,
+    watch_xpub: Option<WatchXpub>,
+    addrs: WalletAddressSets,
+    birthday: Option<WalletBirthday>,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let info = ctx
+        .shared
+        .ks
+        .import_watch_wallet(name, watch_xpub, addrs, birthday)?;
+
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "wallet_imported",
+          "wallet": info.name,
+          "wallet_kind": "watch"
+        }),
+        None,
+    )?;
+
+    Keystore::release_lock(lock)?;
+    Ok(ok(ctx.req_id.clone(), tool_ok(json!({ "wallet": info }))))
+}
+
 pub async fn handle<R, W>(ctx: &mut WalletHandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
 where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
     let args = ctx.args.clone();
     let name = args
         .get("name")
@@ -115,19 +399,30 @@ where
     let chain_s = args.get("private_key_chain").and_then(|v| v.as_str());
 
     if name.trim().is_empty() {
-        Keystore::release_lock(lock)?;
         return Ok(ok(
             ctx.req_id.clone(),
             tool_err(ToolError::new("invalid_request", "missing name")),
         ));
     }
 
-    let kind = match parse_import_kind(kind_s) {
-        Ok(k) => k,
-        Err(e) => {
-            Keystore::release_lock(lock)?;
-            return Ok(ok(ctx.req_id.clone(), tool_err(e)));
+    let mainnet = effective_network_mode(ctx.shared, ctx.conn) == NetworkMode::Mainnet;
+    let (source, birthday) = match prepare_import(ctx.shared, mainnet, &args, kind_s, chain_s).await
+    {
+        Ok(x) => x,
+        Err(e) => return Ok(ok(ctx.req_id.clone(), tool_err(e))),
+    };
+
+    let lock = ctx.shared.acquire_write_lock().await?;
+    if let Err(e) = ctx.shared.ks.ensure_wallet_name_available(&name) {
+        Keystore::release_lock(lock)?;
+        return Err(e);
+    }
+
+    let kind = match source {
+        ImportSource::Watch(watch_xpub, addrs) => {
+            return handle_watch(ctx, lock, name, watch_xpub, addrs, birthday).await;
         }
+        ImportSource::Secret(kind) => kind,
     };
 
     // Never accept secrets via tool arguments: those routinely end up in agent logs.
@@ -173,15 +468,21 @@ where
     };
 
     let decoded = decode_secret(kind, chain_s, secret_s.as_str())?;
-    let info = ctx.shared.ks.import_wallet(name, kind, decoded, pass_key)?;
-
-    ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": "wallet_imported",
-      "wallet": info.name,
-      "wallet_kind": "imported"
-    }))?;
+    let info = ctx
+        .shared
+        .ks
+        .import_wallet(name, kind, decoded, pass_key, birthday)?;
+
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "wallet_imported",
+          "wallet": info.name,
+          "wallet_kind": "imported"
+        }),
+        None,
+    )?;
 
     Keystore::release_lock(lock)?;
     Ok(ok(ctx.req_id.clone(), tool_ok(json!({ "wallet": info }))))
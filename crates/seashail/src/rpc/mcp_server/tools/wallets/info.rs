@@ -1,10 +1,17 @@
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use super::super::super::jsonrpc::{ok, tool_ok, JsonRpcResponse};
-use super::super::super::SharedState;
+use super::super::super::{ConnState, SharedState};
+use super::super::helpers::resolve_wallet_and_account;
+use super::super::read::default_chain_balances;
 use crate::errors::SeashailError;
 
-pub fn handle(req_id: Value, args: &Value, shared: &SharedState) -> eyre::Result<JsonRpcResponse> {
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &SharedState,
+    conn: &ConnState,
+) -> eyre::Result<JsonRpcResponse> {
     let name = args.get("wallet").and_then(|v| v.as_str()).unwrap_or("");
     let info = if name.is_empty() {
         let (w, _idx) = shared
@@ -15,5 +22,29 @@ pub fn handle(req_id: Value, args: &Value, shared: &SharedState) -> eyre::Result
     } else {
         shared.ks.get_wallet_info(name)?
     };
-    Ok(ok(req_id, tool_ok(serde_json::json!(info))))
+
+    let include_balances = args
+        .get("include_balances")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if !include_balances {
+        return Ok(ok(req_id, tool_ok(json!(info))));
+    }
+
+    // Addresses are the contract; balances are best-effort (for `active_account`) and never
+    // fail the call.
+    let (balances, errors) =
+        match resolve_wallet_and_account(shared, &json!({ "wallet": info.name })) {
+            Ok((w, idx)) => default_chain_balances(shared, conn, &w, idx).await,
+            Err(e) => (
+                vec![],
+                vec![json!({ "chain": null, "error": format!("{e:#}") })],
+            ),
+        };
+    let mut out = json!(info);
+    if let Some(o) = out.as_object_mut() {
+        o.insert("balances".into(), json!(balances));
+        o.insert("balance_errors".into(), json!(errors));
+    }
+    Ok(ok(req_id, tool_ok(out)))
 }
@@ -5,5 +5,16 @@ use super::super::super::SharedState;
 
 pub fn handle(req_id: Value, shared: &SharedState) -> eyre::Result<JsonRpcResponse> {
     let wallets = shared.ks.list_wallets()?;
-    Ok(ok(req_id, tool_ok(json!({ "wallets": wallets }))))
+    let active = shared
+        .ks
+        .get_active_wallet()?
+        .map(|(w, i)| json!({ "wallet": w.name, "account_index": i }));
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "wallets": wallets,
+          "active": active,
+          "active_by_chain": shared.ks.active_wallet_by_chain()?,
+        })),
+    ))
 }
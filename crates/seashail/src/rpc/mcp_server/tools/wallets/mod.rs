@@ -6,7 +6,11 @@ mod deposit_info;
 mod import_wallet;
 mod info;
 mod list;
+mod onboard;
+mod public_descriptor;
+mod rename;
 mod shares;
+mod smart_account;
 
 use crate::{
     chains::evm::EvmChain,
@@ -141,13 +145,16 @@ where
         return Err(SeashailError::UserDeclined.into());
     }
 
-    shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": "wallet_created",
-      "wallet": info.name,
-      "wallet_kind": "generated"
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "wallet_created",
+          "wallet": info.name,
+          "wallet_kind": "generated"
+        }),
+        None,
+    )?;
 
     Keystore::release_lock(lock)?;
     Ok(info)
@@ -168,9 +175,12 @@ where
 {
     match tool_name {
         "list_wallets" => list::handle(req_id, shared),
-        "get_wallet_info" => info::handle(req_id, &args, shared),
+        "get_wallet_info" => info::handle(req_id, &args, shared, conn).await,
         "get_deposit_info" => deposit_info::handle(req_id, &args, shared, conn),
+        "onboard" => onboard::handle(req_id, &args, shared, conn).await,
         "set_active_wallet" => active::handle(req_id, &args, shared),
+        "rename_wallet" => rename::handle(req_id, &args, shared).await,
+        "enable_smart_account" => smart_account::handle(req_id, &args, shared).await,
         "add_account" => {
             let mut ctx = WalletHandlerCtx {
                 req_id,
@@ -226,6 +236,17 @@ where
             };
             shares::handle(tool_name, &mut ctx).await
         }
+        "export_public_descriptor" => {
+            let mut ctx = WalletHandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            public_descriptor::handle(&mut ctx).await
+        }
         _ => Ok(err(req_id, -32601, "unknown tool")),
     }
 }
@@ -0,0 +1,133 @@
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::state::{effective_network_mode, network_mode_str, parse_network_mode};
+use super::super::super::{ConnState, SharedState};
+use crate::{
+    config::{NetworkMode, SOLANA_DEVNET_RPC_URL, SOLANA_MAINNET_RPC_URL},
+    errors::ToolError,
+    policy::Policy,
+};
+
+/// Idempotent first-run setup: default wallet, starter policy, persisted network mode, and the
+/// deposit addresses to fund. Each step only acts when it has not been done (or customized)
+/// already, so re-running reports `already_onboarded: true` and changes nothing.
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &mut SharedState,
+    conn: &ConnState,
+) -> eyre::Result<JsonRpcResponse> {
+    let requested_mode = match args
+        .get("network")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        None => None,
+        Some(s) => match parse_network_mode(s) {
+            Some(m) => Some(m),
+            None => {
+                return Ok(ok(
+                    req_id,
+                    tool_err(ToolError::new(
+                        "invalid_request",
+                        "network must be one of: mainnet, testnet",
+                    )),
+                ));
+            }
+        },
+    };
+
+    let mut changes: Vec<&str> = vec![];
+    let mut notes: Vec<String> = vec![];
+
+    // 1) Wallet: machine-bound generated `default` wallet on first run.
+    if shared.ks.list_wallets()?.is_empty() {
+        shared.ensure_default_wallet().await?;
+        changes.push("wallet_created");
+    }
+    let (w, idx) = if let Some(active) = shared.ks.get_active_wallet()? {
+        active
+    } else {
+        let wallets = shared.ks.list_wallets()?;
+        let Some(w) = wallets
+            .iter()
+            .find(|w| w.name == "default")
+            .or_else(|| wallets.first())
+            .cloned()
+        else {
+            eyre::bail!("no wallet available after onboarding");
+        };
+        shared.ks.set_active_wallet(&w.name, 0)?;
+        changes.push("active_wallet_set");
+        (w, 0)
+    };
+
+    // 2) Network mode: persist once so default chains stop depending on session flags.
+    let mut save_config = false;
+    let mode = if let Some(configured) = shared.cfg.network_mode {
+        if requested_mode.is_some_and(|m| m != configured) {
+            notes.push(format!(
+                "network_mode is already {}; use set_network_mode to switch",
+                network_mode_str(configured)
+            ));
+        }
+        configured
+    } else {
+        let m = requested_mode.unwrap_or_else(|| effective_network_mode(shared, conn));
+        shared.cfg.network_mode = Some(m);
+        shared.cfg.testnet_mode = m == NetworkMode::Testnet;
+        if m == NetworkMode::Testnet && shared.cfg.rpc.solana_rpc_url == SOLANA_MAINNET_RPC_URL {
+            shared.cfg.rpc.solana_rpc_url = SOLANA_DEVNET_RPC_URL.into();
+        }
+        save_config = true;
+        changes.push("network_mode_set");
+        m
+    };
+
+    // 3) Policy: only replace the untouched built-in default; never overwrite user edits.
+    let policy_is_default =
+        serde_json::to_value(&shared.cfg.policy)? == serde_json::to_value(Policy::default())?;
+    if policy_is_default {
+        shared.cfg.policy = Policy::starter();
+        save_config = true;
+        changes.push("starter_policy_applied");
+    }
+
+    if save_config {
+        shared.ks.save_config(&shared.cfg)?;
+    }
+
+    let i = idx as usize;
+    let bitcoin = if mode == NetworkMode::Testnet {
+        w.bitcoin_addresses_testnet.get(i)
+    } else {
+        w.bitcoin_addresses_mainnet.get(i)
+    };
+    let default_chains = shared.cfg.default_chains_for_mode(mode);
+
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "already_onboarded": changes.is_empty(),
+          "changes": changes,
+          "wallet": w.name,
+          "account_index": idx,
+          "network_mode": network_mode_str(mode),
+          "default_chains": default_chains,
+          "policy": if policy_is_default { "starter" } else { "existing" },
+          "deposit_addresses": {
+            "solana": w.solana_addresses.get(i),
+            "evm": w.evm_addresses.get(i),
+            "bitcoin": bitcoin,
+          },
+          "notes": notes,
+          "next_steps": [
+            "Fund one of the deposit addresses (get_deposit_info gives per-chain details).",
+            "Review limits with get_policy; raise them or re-enable perps/pump.fun/predictions with update_policy.",
+            "Back up the wallet for recovery with export_shares (the default wallet is machine-bound)."
+          ],
+        })),
+    ))
+}
@@ -0,0 +1,88 @@
+use serde_json::json;
+
+use super::super::super::elicitation::ensure_unlocked;
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::WalletHandlerCtx;
+use crate::errors::{SeashailError, ToolError};
+use crate::keystore::utc_now_iso;
+use crate::wallet::{ImportedKind, WalletKind};
+
+const PRIVACY_NOTICE: &str = "An extended public key reveals every address of this account (past and future) and therefore its full balance and transaction history to whoever holds it. It cannot sign or spend funds.";
+
+pub async fn handle<R, W>(ctx: &mut WalletHandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let name = ctx
+        .args
+        .get("wallet")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let w = match name {
+        Some(n) => ctx
+            .shared
+            .ks
+            .get_wallet_by_name(n)?
+            .ok_or_else(|| SeashailError::WalletNotFound(n.to_owned()))?,
+        None => {
+            ctx.shared
+                .ks
+                .get_active_wallet()?
+                .ok_or_else(|| SeashailError::WalletNotFound("active".into()))?
+                .0
+        }
+    };
+    if w.kind == WalletKind::Watch {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "not_hd_wallet",
+                "watch-only wallets hold no key material; get_wallet_info shows the xpub they were imported from",
+            )),
+        ));
+    }
+    if w.kind == WalletKind::Imported && w.imported_kind != Some(ImportedKind::Mnemonic) {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "not_hd_wallet",
+                "imported private key wallets have no extended public key",
+            )),
+        ));
+    }
+
+    let descriptors = if w.kind == WalletKind::Generated
+        && !ctx.shared.ks.generated_wallet_needs_passphrase(&w.id)
+    {
+        ctx.shared.ks.public_descriptors(&w.name, None)?
+    } else {
+        let key = ensure_unlocked(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout).await?;
+        ctx.shared.ks.public_descriptors(&w.name, Some(&key))?
+    };
+
+    let _audit_log = ctx.shared.ks.append_audit_log(&json!({
+      "ts": utc_now_iso(),
+      "tool": "export_public_descriptor",
+      "wallet": w.name,
+      "result": "exported"
+    }));
+
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "wallet": w.name,
+          "master_fingerprint": descriptors.master_fingerprint,
+          "bitcoin": descriptors.bitcoin,
+          "evm": descriptors.evm,
+          "solana": {
+            "supported": false,
+            "reason": "Solana uses hardened-only ed25519 derivation (m/44'/501'/i'/0'), so no extended public key exists; watch the listed addresses instead.",
+            "addresses": w.solana_addresses
+          },
+          "privacy_notice": PRIVACY_NOTICE,
+          "contains_private_material": false
+        })),
+    ))
+}
@@ -0,0 +1,71 @@
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+use crate::errors::ToolError;
+use crate::keystore::{utc_now_iso, Keystore};
+
+fn rename_locked(shared: &mut SharedState, old: &str, new: &str) -> eyre::Result<Value> {
+    let was_active = shared
+        .ks
+        .get_active_wallet()?
+        .is_some_and(|(w, _)| w.name == old);
+    let info = shared.ks.rename_wallet(old, new)?;
+    let config_updated = shared.cfg.rename_wallet_refs(old, new);
+    if !config_updated.is_empty() {
+        shared.ks.save_config(&shared.cfg)?;
+    }
+    if old != new {
+        // History stays immutable: past entries keep the old name and are annotated with
+        // `wallet_current` on read; this entry records the rename itself.
+        shared.ks.append_tx_history(
+            &json!({
+              "ts": utc_now_iso(),
+              "day": Keystore::current_utc_day_key(),
+              "type": "wallet_renamed",
+              "wallet": new,
+              "previous_name": old
+            }),
+            None,
+        )?;
+    }
+    Ok(json!({
+      "wallet": info,
+      "previous_name": old,
+      "was_active": was_active,
+      "config_updated": config_updated,
+    }))
+}
+
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &mut SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let old = args
+        .get("wallet")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("")
+        .to_owned();
+    let new = args
+        .get("new_name")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("")
+        .to_owned();
+    if old.is_empty() || new.is_empty() {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_request",
+                "missing wallet or new_name",
+            )),
+        ));
+    }
+
+    let lock = shared.acquire_write_lock().await?;
+    let res = rename_locked(shared, &old, &new);
+    Keystore::release_lock(lock)?;
+    Ok(ok(req_id, tool_ok(res?)))
+}
@@ -59,7 +59,7 @@ where
     }
 
     let key = ensure_unlocked(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout).await?;
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let plan = match ctx.shared.ks.plan_rotate_shares(&w.id, &key) {
         Ok(p) => p,
         Err(e) => {
@@ -151,7 +151,7 @@ where
     }
 
     let key = ensure_unlocked(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout).await?;
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let plan = match ctx.shared.ks.plan_rotate_shares(&w.id, &key) {
         Ok(p) => p,
         Err(e) => {
@@ -0,0 +1,99 @@
+use crate::{
+    chains::{erc4337, evm::EvmChain},
+    errors::{SeashailError, ToolError},
+    keystore::Keystore,
+    wallet::{SmartAccountKind, SmartAccountRecord},
+};
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::SharedState;
+
+/// Bind (or unbind) an ERC-4337 `SimpleAccount` to a wallet.
+///
+/// Addresses are counterfactual: nothing is deployed here. The account is created by the first
+/// user operation sent with `execution=smart_account`.
+pub async fn handle(
+    req_id: Value,
+    args: &Value,
+    shared: &SharedState,
+) -> eyre::Result<JsonRpcResponse> {
+    let name = args.get("wallet").and_then(Value::as_str).unwrap_or("");
+    let mut w = shared
+        .ks
+        .get_wallet_by_name(name)?
+        .ok_or_else(|| SeashailError::WalletNotFound(name.to_owned()))?;
+    let enabled = args.get("enabled").and_then(Value::as_bool).unwrap_or(true);
+
+    if !enabled {
+        let lock = shared.acquire_write_lock().await?;
+        w.smart_account = None;
+        shared.ks.update_wallet(&w)?;
+        Keystore::release_lock(lock)?;
+        return Ok(ok(
+            req_id,
+            tool_ok(json!({ "wallet": w.name, "smart_account": null })),
+        ));
+    }
+
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+    let (Some(rpc_url), Some(chain_id)) = (
+        shared.cfg.rpc.evm_rpc_urls.get(chain),
+        shared.cfg.rpc.evm_chain_ids.get(chain),
+    ) else {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "invalid_request",
+                "chain must be a configured EVM chain",
+            )),
+        ));
+    };
+    let mut evm = EvmChain::for_name(chain, *chain_id, rpc_url, &shared.cfg.http);
+    if let Some(fb) = shared.cfg.rpc.evm_fallback_rpc_urls.get(chain) {
+        evm.fallback_rpc_urls.clone_from(fb);
+    }
+
+    let cfg = &shared.cfg.erc4337;
+    let factory = EvmChain::parse_address(&cfg.simple_account_factory)?;
+    if evm.get_contract_code(factory).await?.is_empty() {
+        return Ok(ok(
+            req_id,
+            tool_err(ToolError::new(
+                "unsupported_chain",
+                format!("SimpleAccountFactory is not deployed on {chain}"),
+            )),
+        ));
+    }
+    let mut addresses = Vec::with_capacity(w.evm_addresses.len());
+    for owner in &w.evm_addresses {
+        let owner = EvmChain::parse_address(owner)?;
+        let sa = erc4337::smart_account_address(&evm, factory, owner).await?;
+        addresses.push(format!("{sa:#x}"));
+    }
+
+    let lock = shared.acquire_write_lock().await?;
+    w.smart_account = Some(SmartAccountRecord {
+        kind: SmartAccountKind::SimpleAccountV07,
+        factory: cfg.simple_account_factory.clone(),
+        entry_point: cfg.entry_point.clone(),
+        addresses,
+    });
+    shared.ks.update_wallet(&w)?;
+    Keystore::release_lock(lock)?;
+
+    Ok(ok(
+        req_id,
+        tool_ok(json!({
+          "wallet": w.name,
+          "smart_account": w.smart_account,
+          "bundler_configured": cfg.bundler_urls.contains_key(chain),
+          "paymaster_configured": cfg.paymaster_urls.contains_key(chain),
+          "note": "Smart accounts are deployed on first use. Pass execution=smart_account to send_transaction or swap_tokens; fund the smart account address (or configure a paymaster) to cover gas."
+        })),
+    ))
+}
@@ -14,7 +14,7 @@ use crate::{
 };
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
-use super::super::helpers::{evm_addr_for_account, resolve_wallet_and_account};
+use super::super::helpers::{evm_addr_for_account, resolve_wallet_and_account, tx_note};
 use super::super::key_loading::load_evm_signer;
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::super::value_helpers::{parse_usd_value, summarize_sim_error};
@@ -429,12 +429,15 @@ where
     })?;
     let tx_hash_s = format!("{tx_hash:#x}");
 
-    let _tx_log = ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": "approve",
-      "chain": ac.chain, "wallet": ac.wallet_name, "account_index": ac.idx, "protocol": "aave",
-      "token": format!("{:#x}", ac.token_addr), "spender": format!("{:#x}", ac.pool_addr),
-      "amount_base": ac.amount_base.to_string(), "usd_value": 0.0_f64, "txid": tx_hash_s,
-    }));
+    let _tx_log = ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": "approve",
+          "chain": ac.chain, "wallet": ac.wallet_name, "account_index": ac.idx, "protocol": "aave",
+          "token": format!("{:#x}", ac.token_addr), "spender": format!("{:#x}", ac.pool_addr),
+          "amount_base": ac.amount_base.to_string(), "usd_value": 0.0_f64, "txid": tx_hash_s,
+        }),
+        tx_note(&ctx.args),
+    );
     log_aave_approve_audit(
         &ctx.shared.ks,
         ac,
@@ -600,7 +603,7 @@ where
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
     let args = ctx.args.clone();
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     handle_inner(tool_name, ctx, &args, lock).await
 }
 
@@ -802,7 +805,7 @@ where
       "pool": format!("{:#x}", stx.pool_addr), "token": format!("{:#x}", stx.token_addr),
       "amount_base": if stx.amount_base == U256::MAX { "max".to_owned() } else { stx.amount_base.to_string() },
       "amount_units": stx.parsed.units, "interest_rate_mode": stx.parsed.irm, "usd_value": stx.audit.usd_value, "txid": format!("{txid:#x}"),
-    }))?;
+    }), tx_note(&ctx.args))?;
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": stx.tool_name, "wallet": w.name, "account_index": stx.audit.idx, "chain": stx.parsed.chain,
       "usd_value": stx.audit.usd_value, "usd_value_known": stx.audit.usd_value_known,
@@ -0,0 +1,705 @@
+use alloy::primitives::U256;
+use serde_json::{json, Value};
+
+use crate::{
+    amount,
+    chains::{evm::EvmChain, native::native_token, solana::SolanaChain},
+    errors::ToolError,
+    financial_math,
+    policy::Policy,
+    policy_engine, price,
+};
+
+use super::super::super::{ConnState, SharedState};
+use super::super::helpers::solana_fallback_urls;
+use super::super::read::native_balance_base;
+use super::super::value_helpers::defi_adapter_fetch;
+use super::swap_tokens::setup_evm_chain;
+use super::wormhole::{
+    default_token_bridge_for_chain, evm_address_to_bytes32, sol_token_bridge_program_id,
+    sol_wrapped_mint_pda, usdc_base_to_usd_f64, wormhole_chain_id,
+};
+use super::wormhole_solana::USDC_MINT_MAINNET;
+
+/// Wormhole normalizes token amounts to 8 decimals; anything below that stays in the wallet.
+const WORMHOLE_MAX_DECIMALS: u8 = 8;
+// Preview-only gas budgets (actual gas is estimated at execution).
+const EVM_APPROVE_GAS_ESTIMATE: u128 = 60_000;
+const EVM_WORMHOLE_TRANSFER_GAS_ESTIMATE: u128 = 150_000;
+const EVM_WORMHOLE_REDEEM_GAS_ESTIMATE: u128 = 300_000;
+// Two signatures plus rent for the posted message account.
+const SOLANA_WORMHOLE_TRANSFER_FEE_LAMPORTS: u64 = 2_510_000;
+// Signature verification, posted VAA rent and the redeem transaction.
+const SOLANA_WORMHOLE_REDEEM_FEE_LAMPORTS: u64 = 5_000_000;
+
+/// Expected value delivered by a bridge route, shown in the confirmation prompt and checked
+/// against `max_bridge_loss_bps`. Unknown parts stay `None` and are reported as such.
+#[derive(Debug, Clone, Default)]
+pub(super) struct BridgeQuote {
+    pub symbol: String,
+    pub decimals: u8,
+    pub sent_base: Option<u128>,
+    pub received_base: Option<u128>,
+    /// Base units below the bridge's precision; not sent, stays with the sender.
+    pub dust_base: u128,
+    pub sent_usd: Option<f64>,
+    /// Explicit destination value (remote routes); otherwise derived from `received_base`.
+    pub received_usd: Option<f64>,
+    pub bridge_fee_usd: Option<f64>,
+    pub source_fee_usd: Option<f64>,
+    pub destination_fee_usd: Option<f64>,
+    pub notes: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl BridgeQuote {
+    /// Quote a Wormhole token bridge transfer: no relayer fee, amount truncated to 8 decimals.
+    pub fn wormhole(symbol: &str, decimals: u8, amount_base: u128, sent_usd: Option<f64>) -> Self {
+        let dust_base = decimals
+            .checked_sub(WORMHOLE_MAX_DECIMALS)
+            .and_then(|extra| 10_u128.checked_pow(u32::from(extra)))
+            .map_or(0, |unit| amount_base % unit);
+        let bridged = amount_base.saturating_sub(dust_base);
+        Self {
+            symbol: symbol.to_owned(),
+            decimals,
+            sent_base: Some(bridged),
+            received_base: Some(bridged),
+            dust_base,
+            sent_usd: sent_usd.map(|u| financial_math::scale_usd_by_base(u, bridged, amount_base)),
+            received_usd: None,
+            bridge_fee_usd: Some(0.0_f64),
+            source_fee_usd: None,
+            destination_fee_usd: None,
+            notes: vec![
+                "arrives as the Wormhole-wrapped token unless the destination is the token's origin chain; swapping a wrapped token back to a native asset can cost extra".to_owned(),
+            ],
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Quote a remote route (tx envelope / adapter) from the values it discloses.
+    pub fn remote(sent_usd: Option<f64>, values: RemoteBridgeValues) -> Self {
+        Self {
+            sent_usd,
+            received_usd: values.expected_received_usd,
+            bridge_fee_usd: values.bridge_fee_usd,
+            ..Self::default()
+        }
+    }
+
+    fn expected_received_usd(&self) -> Option<f64> {
+        self.received_usd.or_else(|| {
+            let (sent, recv, usd) = (self.sent_base?, self.received_base?, self.sent_usd?);
+            Some(financial_math::scale_usd_by_base(usd, recv, sent))
+        })
+    }
+
+    /// Sum of the fees that could be estimated.
+    fn fees_usd(&self) -> f64 {
+        financial_math::sum_f64(
+            &[
+                self.bridge_fee_usd,
+                self.source_fee_usd,
+                self.destination_fee_usd,
+            ]
+            .map(|f| f.unwrap_or(0.0_f64)),
+        )
+    }
+
+    pub fn loss_bps(&self) -> Option<u32> {
+        financial_math::loss_bps(
+            self.sent_usd?,
+            self.expected_received_usd()?,
+            self.fees_usd(),
+        )
+    }
+
+    /// Enforce `max_bridge_loss_bps` when the loss can be computed.
+    pub fn check(&self, policy: &Policy) -> Result<(), ToolError> {
+        match self.loss_bps() {
+            Some(bps) => policy_engine::check_bridge_loss(policy, bps).map_err(|te| {
+                ToolError::new(
+                    te.code,
+                    format!("{}\n\n{}", te.message, self.summary(policy)),
+                )
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn amount_ui(&self, base: u128) -> String {
+        amount::format_amount_base_to_ui_string(base, u32::from(self.decimals))
+            .map_or_else(|_| base.to_string(), |s| format!("{s} {}", self.symbol))
+    }
+
+    fn value_line(&self, base: Option<u128>, usd: Option<f64>) -> String {
+        let usd_s = fmt_usd(usd);
+        base.map_or_else(
+            || usd_s.clone(),
+            |b| format!("{} (~{usd_s})", self.amount_ui(b)),
+        )
+    }
+
+    /// Human-readable breakdown appended to the confirmation summary.
+    pub fn summary(&self, policy: &Policy) -> String {
+        let loss = self.loss_bps().map_or_else(
+            || "unknown".to_owned(),
+            |bps| {
+                format!(
+                    "{:.2}%",
+                    financial_math::mul_f64(financial_math::bps_to_fraction(bps), 100.0_f64)
+                )
+            },
+        );
+        let max = if policy.max_bridge_loss_bps == 0 {
+            "no cap".to_owned()
+        } else {
+            format!(
+                "max {:.2}%",
+                financial_math::mul_f64(
+                    financial_math::bps_to_fraction(policy.max_bridge_loss_bps),
+                    100.0_f64
+                )
+            )
+        };
+        let mut out = format!(
+            "Sent: {}\nExpected received: {}\nFees (estimated): bridge {}, source network {}, destination redeem {}\nExpected loss: {loss} ({max})",
+            self.value_line(self.sent_base, self.sent_usd),
+            self.value_line(self.received_base, self.expected_received_usd()),
+            fmt_usd(self.bridge_fee_usd),
+            fmt_usd(self.source_fee_usd),
+            fmt_usd(self.destination_fee_usd),
+        );
+        if self.dust_base > 0 {
+            out.push_str("\nNot bridged (below 8 decimals, stays in wallet): ");
+            out.push_str(&self.amount_ui(self.dust_base));
+        }
+        for n in &self.notes {
+            out.push_str("\nNote: ");
+            out.push_str(n);
+        }
+        for w in &self.warnings {
+            out.push_str("\nWarning: ");
+            out.push_str(w);
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+          "symbol": self.symbol,
+          "decimals": self.decimals,
+          "sent_base": self.sent_base.map(|b| b.to_string()),
+          "expected_received_base": self.received_base.map(|b| b.to_string()),
+          "dust_base": self.dust_base.to_string(),
+          "sent_usd": self.sent_usd,
+          "expected_received_usd": self.expected_received_usd(),
+          "fees": {
+            "bridge_usd": self.bridge_fee_usd,
+            "source_network_usd": self.source_fee_usd,
+            "destination_redeem_usd": self.destination_fee_usd
+          },
+          "loss_bps": self.loss_bps(),
+          "notes": self.notes,
+          "warnings": self.warnings
+        })
+    }
+}
+
+fn fmt_usd(v: Option<f64>) -> String {
+    v.map_or_else(|| "unknown".to_owned(), |u| format!("${u:.2}"))
+}
+
+/// Value disclosure supplied with a remote bridge route, inline or by the adapter envelope.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct RemoteBridgeValues {
+    pub expected_received_usd: Option<f64>,
+    pub bridge_fee_usd: Option<f64>,
+}
+
+impl RemoteBridgeValues {
+    /// Read `expected_received_usd` / `bridge_fee_usd` from args, falling back to `asset`.
+    pub fn from_args(args: &Value) -> Self {
+        Self {
+            expected_received_usd: usd_in_args_or_asset(args, "expected_received_usd"),
+            bridge_fee_usd: usd_in_args_or_asset(args, "bridge_fee_usd"),
+        }
+    }
+
+    /// Prefer values from `self`, filling gaps from `fallback`.
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            expected_received_usd: self
+                .expected_received_usd
+                .or(fallback.expected_received_usd),
+            bridge_fee_usd: self.bridge_fee_usd.or(fallback.bridge_fee_usd),
+        }
+    }
+}
+
+fn usd_in_args_or_asset(args: &Value, key: &str) -> Option<f64> {
+    let parse = |v: &Value| {
+        v.as_f64()
+            .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+            .filter(|u| u.is_finite())
+    };
+    args.get(key)
+        .and_then(parse)
+        .or_else(|| args.get("asset")?.get(key).and_then(parse))
+}
+
+/// USD value of `base` native units (wei or lamports) on `chain`.
+async fn native_base_to_usd(shared: &mut SharedState, chain: &str, base: u128) -> Option<f64> {
+    shared.ensure_db().await;
+    let px = price::native_token_price_usd_cached(chain, &shared.cfg, shared.db())
+        .await
+        .ok()?
+        .usd;
+    Some(financial_math::token_base_to_usd(
+        base,
+        native_token(chain).decimals,
+        px,
+    ))
+}
+
+/// Estimated native cost of `gas` units on an EVM chain at the current gas price.
+async fn evm_gas_cost_base(shared: &SharedState, chain: &str, gas: u128) -> Option<u128> {
+    let evm = setup_evm_chain(shared, chain).ok()?;
+    let gas_price = evm.get_gas_price().await.ok()?;
+    Some(gas_price.saturating_mul(gas))
+}
+
+/// Estimated USD cost of `gas` units on an EVM chain at the current gas price.
+pub(super) async fn evm_gas_usd(shared: &mut SharedState, chain: &str, gas: u128) -> Option<f64> {
+    let wei = evm_gas_cost_base(shared, chain, gas).await?;
+    native_base_to_usd(shared, chain, wei).await
+}
+
+/// Source-chain network fee for a Wormhole transfer.
+pub(super) async fn wormhole_source_fee_usd(
+    shared: &mut SharedState,
+    chain: &str,
+    needs_approval: bool,
+) -> Option<f64> {
+    if chain == "solana" {
+        let lamports = u128::from(SOLANA_WORMHOLE_TRANSFER_FEE_LAMPORTS);
+        return native_base_to_usd(shared, chain, lamports).await;
+    }
+    let gas = if needs_approval {
+        EVM_WORMHOLE_TRANSFER_GAS_ESTIMATE.saturating_add(EVM_APPROVE_GAS_ESTIMATE)
+    } else {
+        EVM_WORMHOLE_TRANSFER_GAS_ESTIMATE
+    };
+    evm_gas_usd(shared, chain, gas).await
+}
+
+/// Estimated native cost (wei or lamports) of redeeming a Wormhole transfer on `to_chain`.
+async fn wormhole_redeem_cost_base(shared: &SharedState, to_chain: &str) -> Option<u128> {
+    if to_chain == "solana" {
+        Some(u128::from(SOLANA_WORMHOLE_REDEEM_FEE_LAMPORTS))
+    } else {
+        evm_gas_cost_base(shared, to_chain, EVM_WORMHOLE_REDEEM_GAS_ESTIMATE).await
+    }
+}
+
+/// Destination-chain fee for auto-redeeming a Wormhole transfer (paid by the same wallet).
+pub(super) async fn wormhole_redeem_fee_usd(
+    shared: &mut SharedState,
+    to_chain: &str,
+) -> Option<f64> {
+    let base = wormhole_redeem_cost_base(shared, to_chain).await?;
+    native_base_to_usd(shared, to_chain, base).await
+}
+
+/// Before initiating a bridge with auto-redeem, compare the wallet's native balance on
+/// `to_chain` with the estimated redeem cost. A shortfall fails with
+/// `insufficient_destination_gas` unless `allow_insufficient_destination_gas=true`, in which
+/// case it is recorded as a quote warning (the destination can be funded while in flight).
+pub(super) async fn check_destination_gas(
+    shared: &SharedState,
+    conn: &ConnState,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    to_chain: &str,
+    args: &Value,
+    quote: &mut BridgeQuote,
+) -> Result<(), ToolError> {
+    let allow = args
+        .get("allow_insufficient_destination_gas")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let Some(required) = wormhole_redeem_cost_base(shared, to_chain).await else {
+        return Ok(());
+    };
+    let balance = match native_balance_base(shared, conn, w, idx, to_chain).await {
+        Ok(v) => v,
+        Err(e) => {
+            quote.warnings.push(format!(
+                "could not read destination balance on {to_chain} to verify redeem gas: {e:#}"
+            ));
+            return Ok(());
+        }
+    };
+    if balance >= required {
+        return Ok(());
+    }
+    let fmt = |base: u128| {
+        amount::format_amount_base_to_ui_string(base, u32::from(native_token(to_chain).decimals))
+            .map_or_else(
+                |_| base.to_string(),
+                |s| format!("{s} {}", native_token(to_chain).symbol),
+            )
+    };
+    let msg = format!(
+        "destination wallet on {to_chain} has {} but auto-redeem needs about {}; fund it before or while the transfer is in flight, or set redeem=false and redeem later",
+        fmt(balance),
+        fmt(required)
+    );
+    if allow {
+        quote.warnings.push(msg);
+        return Ok(());
+    }
+    let mut te = ToolError::new(
+        "insufficient_destination_gas",
+        format!("{msg} (pass allow_insufficient_destination_gas=true to bridge anyway)"),
+    );
+    te.data = json!({
+      "to_chain": to_chain,
+      "balance_base": balance.to_string(),
+      "estimated_redeem_cost_base": required.to_string(),
+      "decimals": native_token(to_chain).decimals
+    });
+    Err(te)
+}
+
+/// Source-chain finality window (seconds) before Wormhole guardians sign a VAA. Preview-only;
+/// the destination redeem adds about a minute.
+fn wormhole_eta_secs(chain: &str) -> (u32, u32) {
+    match chain {
+        "solana" | "bnb" | "bnb-testnet" => (30, 120),
+        "avalanche" | "avalanche-fuji" => (10, 60),
+        "polygon" | "polygon-amoy" => (60, 300),
+        // Ethereum and rollups settle on Ethereum finality.
+        _ => (960, 1_500),
+    }
+}
+
+/// Inputs for `get_bridge_quote`.
+pub struct RouteQuoteRequest<'a> {
+    pub chain: &'a str,
+    pub to_chain: &'a str,
+    pub token: &'a str,
+    pub amount: &'a str,
+    pub units: &'a str,
+}
+
+/// The bridged token as priced on the source chain (shared by every provider).
+struct SourceToken {
+    symbol: String,
+    decimals: u8,
+    amount_base: u128,
+    sent_usd: Option<f64>,
+    is_usdc: bool,
+    evm_addr: Option<alloy::primitives::Address>,
+}
+
+fn parse_quote_amount(amount_s: &str, units: &str, decimals: u8) -> Result<u128, ToolError> {
+    if amount_s.trim().eq_ignore_ascii_case("max") {
+        return Err(ToolError::new(
+            "invalid_request",
+            "amount=max is not supported for bridge quotes",
+        ));
+    }
+    let parsed = if units.trim() == "base" {
+        amount::parse_amount_base_u128(amount_s)
+    } else {
+        amount::parse_amount_ui_to_base_u128(amount_s, u32::from(decimals))
+    };
+    parsed.map_err(|e| ToolError::new("invalid_request", format!("invalid amount: {e:#}")))
+}
+
+async fn resolve_source_token(
+    shared: &mut SharedState,
+    req: &RouteQuoteRequest<'_>,
+) -> Result<SourceToken, ToolError> {
+    if req.chain == "solana" {
+        let mode = shared.cfg.effective_network_mode();
+        let sol = SolanaChain::new_with_fallbacks(
+            &shared.cfg.rpc.solana_rpc_url,
+            solana_fallback_urls(shared, mode),
+            &shared.cfg.http.jupiter_base_url,
+            shared.cfg.http.jupiter_api_key.as_deref(),
+            shared.cfg.rpc.solana_default_compute_unit_limit,
+            shared
+                .cfg
+                .rpc
+                .solana_default_compute_unit_price_micro_lamports,
+        );
+        let mint = SolanaChain::parse_pubkey(req.token)
+            .map_err(|_| ToolError::new("invalid_request", "token must be an SPL mint pubkey"))?;
+        let decimals = sol
+            .get_mint_decimals(mint)
+            .await
+            .map_err(|e| ToolError::new("rpc_error", format!("read mint decimals: {e:#}")))?;
+        let amount_base = parse_quote_amount(req.amount, req.units, decimals)?;
+        shared.ensure_db().await;
+        let sent_usd = match u64::try_from(amount_base) {
+            Ok(base) => price::solana_token_price_usd_cached(
+                &sol,
+                &shared.cfg,
+                req.token,
+                USDC_MINT_MAINNET,
+                base,
+                50,
+                shared.db(),
+            )
+            .await
+            .ok()
+            .map(|p| p.usd)
+            .filter(|u| u.is_finite()),
+            Err(_) => None,
+        };
+        return Ok(SourceToken {
+            symbol: req.token.to_owned(),
+            decimals,
+            amount_base,
+            sent_usd,
+            is_usdc: req.token == USDC_MINT_MAINNET,
+            evm_addr: None,
+        });
+    }
+
+    let evm = setup_evm_chain(shared, req.chain)
+        .map_err(|e| ToolError::new("invalid_request", format!("{e:#}")))?;
+    let token_addr = EvmChain::parse_address(req.token)
+        .map_err(|_| ToolError::new("invalid_request", "token must be an ERC-20 address"))?;
+    let (decimals, symbol) = evm
+        .get_erc20_metadata(token_addr)
+        .await
+        .unwrap_or_else(|_| (18, "ERC20".into()));
+    let amount_base = parse_quote_amount(req.amount, req.units, decimals)?;
+    let is_usdc = evm.uniswap.as_ref().is_some_and(|u| u.usdc == token_addr);
+    let priced = if is_usdc {
+        usdc_base_to_usd_f64(&U256::from(amount_base)).ok()
+    } else {
+        shared.ensure_db().await;
+        price::evm_token_price_usd_cached(
+            &evm,
+            &shared.cfg,
+            token_addr,
+            U256::from(amount_base),
+            50,
+            shared.db(),
+        )
+        .await
+        .ok()
+        .map(|p| p.usd)
+    };
+    let sent_usd = priced.filter(|u| u.is_finite());
+    Ok(SourceToken {
+        symbol,
+        decimals,
+        amount_base,
+        sent_usd,
+        is_usdc,
+        evm_addr: Some(token_addr),
+    })
+}
+
+fn unavailable_route(provider: &str, execution: &str, reason: &str) -> Value {
+    json!({
+      "provider": provider,
+      "execution": execution,
+      "available": false,
+      "reason": reason,
+    })
+}
+
+fn route_json(
+    provider: &str,
+    execution: &str,
+    quote: &BridgeQuote,
+    policy: &Policy,
+    eta: Option<(u32, u32)>,
+) -> Value {
+    json!({
+      "provider": provider,
+      "execution": execution,
+      "available": true,
+      "quote": quote.to_json(),
+      "eta_seconds": eta.map(|(min, max)| json!({ "min": min, "max": max })),
+      "within_policy": quote.check(policy).is_ok(),
+    })
+}
+
+/// Native Wormhole token bridge route: no relayer fee, 8-decimal truncation, both network fees
+/// (the source estimate includes an ERC-20 approval) and auto-redeem on the destination.
+async fn wormhole_route(
+    shared: &mut SharedState,
+    req: &RouteQuoteRequest<'_>,
+    token: &SourceToken,
+    policy: &Policy,
+) -> Value {
+    if wormhole_chain_id(req.chain).is_none() || wormhole_chain_id(req.to_chain).is_none() {
+        return unavailable_route(
+            "wormhole",
+            "native",
+            "wormhole does not support this chain pair",
+        );
+    }
+    let evm_source = req.chain != "solana";
+    if evm_source && default_token_bridge_for_chain(req.chain).is_none() {
+        return unavailable_route(
+            "wormhole",
+            "native",
+            "no Wormhole token bridge known for the source chain",
+        );
+    }
+    if req.chain == "solana" && default_token_bridge_for_chain(req.to_chain).is_none() {
+        return unavailable_route(
+            "wormhole",
+            "native",
+            "no Wormhole token bridge known for the destination chain",
+        );
+    }
+
+    let mut quote = BridgeQuote::wormhole(
+        &token.symbol,
+        token.decimals,
+        token.amount_base,
+        token.sent_usd,
+    );
+    quote.source_fee_usd = wormhole_source_fee_usd(shared, req.chain, evm_source).await;
+    quote.destination_fee_usd = wormhole_redeem_fee_usd(shared, req.to_chain).await;
+
+    let mut requires_attestation = false;
+    if let (Some(addr), "solana", Some(src_id)) =
+        (token.evm_addr, req.to_chain, wormhole_chain_id(req.chain))
+    {
+        let mode = shared.cfg.effective_network_mode();
+        if let Ok(tb) = sol_token_bridge_program_id(mode) {
+            let mint = sol_wrapped_mint_pda(&tb, src_id, evm_address_to_bytes32(addr));
+            let sol = SolanaChain::new_with_fallbacks(
+                &shared.cfg.rpc.solana_rpc_url,
+                solana_fallback_urls(shared, mode),
+                &shared.cfg.http.jupiter_base_url,
+                shared.cfg.http.jupiter_api_key.as_deref(),
+                shared.cfg.rpc.solana_default_compute_unit_limit,
+                shared
+                    .cfg
+                    .rpc
+                    .solana_default_compute_unit_price_micro_lamports,
+            );
+            if matches!(sol.get_account_optional(&mint).await, Ok(None)) {
+                requires_attestation = true;
+                quote.warnings.push(
+                    "token is not attested to Solana yet; run attest_token once before bridging"
+                        .to_owned(),
+                );
+            }
+        }
+    }
+
+    let mut v = route_json(
+        "wormhole",
+        "native",
+        &quote,
+        policy,
+        Some(wormhole_eta_secs(req.chain)),
+    );
+    if let Some(o) = v.as_object_mut() {
+        o.insert(
+            "requires_attestation".to_owned(),
+            Value::Bool(requires_attestation),
+        );
+    }
+    v
+}
+
+/// Adapter-built route (`bridge_tokens` executes it as a tx envelope). Quoted by the DeFi
+/// adapter's `bridge/quote` endpoint when one is configured.
+async fn adapter_route(
+    shared: &SharedState,
+    provider: &str,
+    req: &RouteQuoteRequest<'_>,
+    token: &SourceToken,
+    policy: &Policy,
+) -> Value {
+    let amount_base = token.amount_base.to_string();
+    let v = match defi_adapter_fetch(
+        shared.cfg.http.defi_adapter_base_url.as_ref(),
+        "bridge/quote",
+        &[
+            ("provider", provider),
+            ("chain", req.chain),
+            ("to_chain", req.to_chain),
+            ("token", req.token),
+            ("amount_base", amount_base.as_str()),
+        ],
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(te) => return unavailable_route(provider, "envelope", &te.message),
+    };
+    let mut quote = BridgeQuote::remote(token.sent_usd, RemoteBridgeValues::from_args(&v));
+    quote.symbol.clone_from(&token.symbol);
+    quote.decimals = token.decimals;
+    quote.sent_base = Some(token.amount_base);
+    let eta = v
+        .get("eta_seconds")
+        .and_then(Value::as_u64)
+        .and_then(|s| u32::try_from(s).ok())
+        .map(|s| (s, s));
+    route_json(provider, "envelope", &quote, policy, eta)
+}
+
+/// Quote every bridge provider for one transfer without building or sending anything.
+/// `execution` tells the caller how `bridge_tokens` would run each route: `native` (Seashail
+/// builds and signs the transfer), `envelope` (an adapter supplies the transaction), or
+/// `unsupported` (shown for comparison only).
+pub async fn quote_bridge_routes(
+    shared: &mut SharedState,
+    req: &RouteQuoteRequest<'_>,
+    policy: &Policy,
+) -> Result<Value, ToolError> {
+    let token = resolve_source_token(shared, req).await?;
+    let mut routes = vec![wormhole_route(shared, req, &token, policy).await];
+    if req.chain == "solana" || req.to_chain == "solana" {
+        routes.push(unavailable_route(
+            "layerzero",
+            "envelope",
+            "LayerZero OFT routes are EVM-only",
+        ));
+    } else {
+        routes.push(adapter_route(shared, "layerzero", req, &token, policy).await);
+    }
+    if token.is_usdc {
+        routes.push(unavailable_route(
+            "cctp",
+            "unsupported",
+            "Circle CCTP is not integrated; bridge_tokens cannot execute it",
+        ));
+    }
+
+    let best = routes
+        .iter()
+        .filter(|r| r.get("available").and_then(Value::as_bool) == Some(true))
+        .filter_map(|r| {
+            let loss = r.get("quote")?.get("loss_bps")?.as_u64()?;
+            Some((loss, r.get("provider")?.as_str()?))
+        })
+        .min_by_key(|(loss, _)| *loss)
+        .map(|(_, p)| p.to_owned());
+    Ok(json!({
+      "chain": req.chain,
+      "to_chain": req.to_chain,
+      "token": req.token,
+      "symbol": token.symbol,
+      "amount_base": token.amount_base.to_string(),
+      "sent_usd": token.sent_usd,
+      "routes": routes,
+      "lowest_loss_provider": best,
+    }))
+}
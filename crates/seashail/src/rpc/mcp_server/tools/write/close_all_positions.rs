@@ -0,0 +1,695 @@
+use crate::{
+    chains::evm::EvmChain,
+    config::{is_evm_testnet_chain_name, NetworkMode},
+    errors::{SeashailError, ToolError},
+    financial_math,
+    keystore::utc_now_iso,
+};
+use alloy::{primitives::U256, sol};
+use eyre::Context as _;
+use serde_json::{json, Value};
+
+use super::super::super::elicitation::elicit_form;
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::state::effective_network_mode;
+use super::super::super::{ConnState, SharedState};
+use super::super::helpers::{evm_addr_for_account, resolve_wallet_and_account};
+use super::super::perps;
+use super::super::value_helpers::tool_response_payload;
+use super::swap_tokens::setup_evm_chain;
+use super::{route_lending, HandlerCtx};
+
+const CONFIRM_PHRASE: &str = "CLOSE ALL";
+const DEFAULT_SLIPPAGE_BPS: u32 = 100;
+const MAX_SLIPPAGE_BPS: u32 = 5_000;
+
+sol! {
+    #[sol(rpc)]
+    contract IAavePoolV3Reserves {
+        struct ReserveConfigurationMap {
+            uint256 data;
+        }
+        struct ReserveData {
+            ReserveConfigurationMap configuration;
+            uint128 liquidityIndex;
+            uint128 currentLiquidityRate;
+            uint128 variableBorrowIndex;
+            uint128 currentVariableBorrowRate;
+            uint128 currentStableBorrowRate;
+            uint40 lastUpdateTimestamp;
+            uint16 id;
+            address aTokenAddress;
+            address stableDebtTokenAddress;
+            address variableDebtTokenAddress;
+            address interestRateStrategyAddress;
+            uint128 accruedToTreasury;
+            uint128 unbacked;
+            uint128 isolationModeTotalDebt;
+        }
+        function getReservesList() external view returns (address[] memory);
+        function getReserveData(address asset) external view returns (ReserveData memory);
+    }
+}
+
+sol! {
+    #[sol(rpc)]
+    contract ICometV3Debt {
+        function baseToken() external view returns (address);
+        function borrowBalanceOf(address) external view returns (uint256);
+    }
+}
+
+fn default_aave_pool_for_chain(chain: &str) -> Option<&'static str> {
+    match chain {
+        "ethereum" => Some("0x87870Bca3F3fD6335C3F4ce8392D69350B4fA4E2"),
+        "base" => Some("0xA238Dd80C259a72e81d7e4664a9801593F98d1c5"),
+        "arbitrum" | "optimism" | "polygon" => Some("0x794a61358D6845594F94dc1DB02A252b5b4814aD"),
+        _ => None,
+    }
+}
+
+fn default_comet_for_chain(chain: &str) -> Option<&'static str> {
+    // Compound v3 Comet addresses (USDC markets). Source: compound-finance/comet deployments.
+    match chain {
+        "ethereum" => Some("0xc3d688B66703497DAA19211EEdff47f25384cdc3"),
+        "base" => Some("0xb125E6687d4313864e53df431d5425969c15Eb2F"),
+        "arbitrum" => Some("0x9c4ec768c28520B50860ea7a15bd7213a9fF58bf"),
+        "optimism" => Some("0x2e44e174f7D53F0212823acC11C01A11d58c5bCB"),
+        "polygon" => Some("0xF25212E676D1F7F89Cd72fFEe66158f541246445"),
+        "sepolia" => Some("0xAec1F48e02Cfb822Be958B68C7957156EB3F0b6e"),
+        _ => None,
+    }
+}
+
+struct CloseAllArgs {
+    providers: Vec<String>,
+    cancel_orders: bool,
+    unwind_borrows: bool,
+    chains: Vec<String>,
+    slippage_bps: u32,
+    dry_run: bool,
+}
+
+struct OpenPosition {
+    provider: String,
+    market: String,
+    side: &'static str,
+    notional_usd: f64,
+}
+
+struct OpenBorrow {
+    chain: String,
+    protocol: &'static str,
+    token: String,
+    symbol: String,
+    decimals: u8,
+    debt_base: U256,
+}
+
+fn str_list(args: &Value, key: &str) -> Option<Vec<String>> {
+    args.get(key).and_then(Value::as_array).map(|a| {
+        a.iter()
+            .filter_map(Value::as_str)
+            .map(|s| s.trim().to_owned())
+            .filter(|s| !s.is_empty())
+            .collect()
+    })
+}
+
+fn parse_args(
+    args: &Value,
+    evm_chains: &[String],
+    mode: NetworkMode,
+) -> Result<CloseAllArgs, ToolError> {
+    let providers = str_list(args, "providers").unwrap_or_else(|| {
+        if mode == NetworkMode::Mainnet {
+            vec!["hyperliquid".to_owned(), "jupiter_perps".to_owned()]
+        } else {
+            vec!["hyperliquid".to_owned()]
+        }
+    });
+    if let Some(p) = providers
+        .iter()
+        .find(|p| !matches!(p.as_str(), "hyperliquid" | "jupiter_perps"))
+    {
+        return Err(ToolError::new(
+            "invalid_request",
+            format!("unsupported provider: {p} (supported: hyperliquid, jupiter_perps)"),
+        ));
+    }
+    let testnet = mode == NetworkMode::Testnet;
+    let chains = str_list(args, "chains").unwrap_or_else(|| {
+        evm_chains
+            .iter()
+            .filter(|c| is_evm_testnet_chain_name(c.as_str()) == testnet)
+            .filter(|c| {
+                default_aave_pool_for_chain(c).is_some() || default_comet_for_chain(c).is_some()
+            })
+            .cloned()
+            .collect()
+    });
+    if let Some(c) = chains.iter().find(|c| !evm_chains.contains(c)) {
+        return Err(ToolError::new(
+            "invalid_request",
+            format!("chains must be configured EVM chains (got {c})"),
+        ));
+    }
+    let slippage_bps =
+        args.get("slippage_bps")
+            .and_then(Value::as_u64)
+            .map_or(Ok(DEFAULT_SLIPPAGE_BPS), |v| {
+                u32::try_from(v)
+                    .ok()
+                    .filter(|v| *v <= MAX_SLIPPAGE_BPS)
+                    .ok_or_else(|| {
+                        ToolError::new(
+                            "invalid_request",
+                            format!("slippage_bps must be <= {MAX_SLIPPAGE_BPS}"),
+                        )
+                    })
+            })?;
+    Ok(CloseAllArgs {
+        providers,
+        cancel_orders: args
+            .get("cancel_orders")
+            .and_then(Value::as_bool)
+            .unwrap_or(true),
+        unwind_borrows: args
+            .get("unwind_borrows")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        chains,
+        slippage_bps,
+        dry_run: args
+            .get("dry_run")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    })
+}
+
+fn num_f64(v: Option<&Value>) -> Option<f64> {
+    v.and_then(|x| {
+        x.as_f64()
+            .or_else(|| x.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+    })
+}
+
+/// Open positions from a `get_positions` payload.
+fn parse_positions(provider: &str, payload: &Value) -> Vec<OpenPosition> {
+    if provider == "hyperliquid" {
+        payload
+            .get("state")
+            .and_then(|st| st.get("assetPositions"))
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .filter_map(|p| p.get("position"))
+                    .filter_map(|p| {
+                        let szi = num_f64(p.get("szi")).filter(|s| *s != 0.0_f64)?;
+                        Some(OpenPosition {
+                            provider: provider.to_owned(),
+                            market: p.get("coin").and_then(Value::as_str)?.to_owned(),
+                            side: if szi > 0.0_f64 { "long" } else { "short" },
+                            notional_usd: financial_math::abs_f64(
+                                num_f64(p.get("positionValue")).unwrap_or(0.0_f64),
+                            ),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        payload
+            .get("positions")
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .filter_map(|p| {
+                        Some(OpenPosition {
+                            provider: provider.to_owned(),
+                            market: p.get("market").and_then(Value::as_str)?.to_owned(),
+                            side: match p.get("side").and_then(Value::as_str)? {
+                                "short" => "short",
+                                _ => "long",
+                            },
+                            notional_usd: num_f64(p.get("size_usd")).unwrap_or(0.0_f64),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Read open perp positions on every requested venue. Venues that cannot be read are reported
+/// as errors rather than treated as flat.
+async fn read_positions(
+    shared: &mut SharedState,
+    conn: &ConnState,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    providers: &[String],
+) -> (Vec<OpenPosition>, Vec<Value>) {
+    let mut out = Vec::new();
+    let mut errors = Vec::new();
+    for provider in providers {
+        let args = json!({ "wallet": w.name, "account_index": idx, "provider": provider });
+        let resp = perps::handle_get_positions(Value::Null, &args, shared, conn).await;
+        let (is_error, payload) = match resp {
+            Ok(r) => tool_response_payload(&r),
+            Err(e) => (true, json!({ "message": format!("{e:#}") })),
+        };
+        if !is_error {
+            out.extend(parse_positions(provider, &payload));
+        } else if payload.get("code").and_then(Value::as_str) != Some("missing_address") {
+            errors.push(json!({ "provider": provider, "error": payload }));
+        }
+    }
+    (out, errors)
+}
+
+async fn aave_borrows(
+    evm: &EvmChain,
+    chain: &str,
+    pool_s: &str,
+    owner: alloy::primitives::Address,
+) -> eyre::Result<Vec<OpenBorrow>> {
+    let pool = IAavePoolV3Reserves::new(EvmChain::parse_address(pool_s)?, evm.provider()?);
+    let reserves = pool
+        .getReservesList()
+        .call()
+        .await
+        .context("aave getReservesList")?;
+    let mut out = Vec::new();
+    for asset in reserves {
+        let data = pool
+            .getReserveData(asset)
+            .call()
+            .await
+            .context("aave getReserveData")?;
+        let (debt, decimals, symbol) = evm
+            .get_erc20_balance(data.variableDebtTokenAddress, owner)
+            .await?;
+        if !debt.is_zero() {
+            out.push(OpenBorrow {
+                chain: chain.to_owned(),
+                protocol: "aave",
+                token: format!("{asset:#x}"),
+                symbol,
+                decimals,
+                debt_base: debt,
+            });
+        }
+    }
+    Ok(out)
+}
+
+async fn compound_borrows(
+    evm: &EvmChain,
+    chain: &str,
+    comet_s: &str,
+    owner: alloy::primitives::Address,
+) -> eyre::Result<Vec<OpenBorrow>> {
+    let comet = ICometV3Debt::new(EvmChain::parse_address(comet_s)?, evm.provider()?);
+    let debt = comet
+        .borrowBalanceOf(owner)
+        .call()
+        .await
+        .context("comet borrowBalanceOf")?;
+    if debt.is_zero() {
+        return Ok(vec![]);
+    }
+    let base = comet.baseToken().call().await.context("comet baseToken")?;
+    let (decimals, symbol) = evm.get_erc20_metadata(base).await?;
+    Ok(vec![OpenBorrow {
+        chain: chain.to_owned(),
+        protocol: "compound",
+        token: format!("{base:#x}"),
+        symbol,
+        decimals,
+        debt_base: debt,
+    }])
+}
+
+/// Read outstanding Aave v3 (variable) and Compound v3 borrows on the given EVM chains.
+async fn read_borrows(
+    shared: &SharedState,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    chains: &[String],
+) -> (Vec<OpenBorrow>, Vec<Value>) {
+    let mut out = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(owner) = evm_addr_for_account(w, idx) else {
+        return (out, errors);
+    };
+    for chain in chains {
+        let evm = match setup_evm_chain(shared, chain) {
+            Ok(v) => v,
+            Err(e) => {
+                errors.push(json!({ "chain": chain, "error": format!("{e:#}") }));
+                continue;
+            }
+        };
+        let protocols = [
+            ("aave", default_aave_pool_for_chain(chain)),
+            ("compound", default_comet_for_chain(chain)),
+        ];
+        for (protocol, addr) in protocols {
+            let Some(addr) = addr else {
+                continue;
+            };
+            let res = if protocol == "aave" {
+                aave_borrows(&evm, chain, addr, owner).await
+            } else {
+                compound_borrows(&evm, chain, addr, owner).await
+            };
+            match res {
+                Ok(v) => out.extend(v),
+                Err(e) => errors.push(json!({
+                  "chain": chain, "protocol": protocol, "error": format!("{e:#}")
+                })),
+            }
+        }
+    }
+    (out, errors)
+}
+
+fn positions_json(positions: &[OpenPosition]) -> Vec<Value> {
+    positions
+        .iter()
+        .map(|p| {
+            json!({
+              "provider": p.provider,
+              "market": p.market,
+              "side": p.side,
+              "notional_usd": p.notional_usd
+            })
+        })
+        .collect()
+}
+
+fn borrows_json(borrows: &[OpenBorrow]) -> Vec<Value> {
+    borrows
+        .iter()
+        .map(|b| {
+            json!({
+              "chain": b.chain,
+              "protocol": b.protocol,
+              "token": b.token,
+              "symbol": b.symbol,
+              "decimals": b.decimals,
+              "debt_base": b.debt_base.to_string()
+            })
+        })
+        .collect()
+}
+
+fn confirm_message(a: &CloseAllArgs, positions: &[OpenPosition], borrows: &[OpenBorrow]) -> String {
+    let mut lines = Vec::new();
+    if a.cancel_orders && a.providers.iter().any(|p| p == "hyperliquid") {
+        lines.push("- cancel every resting Hyperliquid order".to_owned());
+    }
+    for p in positions {
+        lines.push(format!(
+            "- market-close {} {} on {} (~{:.2} USD)",
+            p.side, p.market, p.provider, p.notional_usd
+        ));
+    }
+    for b in borrows {
+        lines.push(format!(
+            "- repay {} {} borrow on {} (debt {} base units)",
+            b.protocol, b.symbol, b.chain, b.debt_base
+        ));
+    }
+    format!(
+        "EMERGENCY CLOSE ALL POSITIONS\n\n{}\n\nMarket closes use up to {} bps slippage. Type {CONFIRM_PHRASE} to proceed.\n",
+        lines.join("\n"),
+        a.slippage_bps
+    )
+}
+
+async fn confirm_close_all<R, W>(ctx: &mut HandlerCtx<'_, R, W>, msg: &str) -> Result<(), ToolError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let schema = json!({
+      "type": "object",
+      "properties": {
+        "confirm_phrase": { "type": "string", "title": format!("Type {CONFIRM_PHRASE} to confirm") }
+      },
+      "required": ["confirm_phrase"]
+    });
+    let res = elicit_form(
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        msg,
+        schema,
+        std::time::Duration::from_secs(5 * 60),
+    )
+    .await
+    .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
+    let confirmed = res.action == "accept"
+        && res
+            .content
+            .get("confirm_phrase")
+            .and_then(Value::as_str)
+            .is_some_and(|s| s.trim() == CONFIRM_PHRASE);
+    if confirmed {
+        Ok(())
+    } else {
+        Err(SeashailError::UserDeclined.into())
+    }
+}
+
+fn audit(
+    shared: &SharedState,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    confirm_result: &str,
+    result: &str,
+    extra: &Value,
+) {
+    let _audit_log = shared.ks.append_audit_log(&json!({
+      "ts": utc_now_iso(),
+      "tool": "close_all_positions",
+      "wallet": w.name,
+      "account_index": idx,
+      "chain": "multi",
+      "usd_value": 0.0_f64,
+      "usd_value_known": false,
+      "policy_decision": null,
+      "confirm_required": true,
+      "confirm_result": confirm_result,
+      "txid": null,
+      "error_code": null,
+      "result": result,
+      "details": extra
+    }));
+}
+
+fn step_result(resp: eyre::Result<JsonRpcResponse>) -> (bool, Value) {
+    match resp {
+        Ok(r) => {
+            let (is_error, payload) = tool_response_payload(&r);
+            (!is_error, payload)
+        }
+        Err(e) => (false, json!({ "message": format!("{e:#}") })),
+    }
+}
+
+/// Repay amount with a small buffer for interest accrued between the read and the repay. Aave
+/// caps repayment at the outstanding debt; on Compound the excess is supplied.
+fn repay_amount(debt: U256) -> U256 {
+    debt.saturating_add(debt / U256::from(1_000_u64))
+        .saturating_add(U256::from(1_u64))
+}
+
+pub async fn handle<R, W>(ctx: &mut HandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin + Send,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let mode = effective_network_mode(ctx.shared, ctx.conn);
+    let evm_chains: Vec<String> = ctx.shared.cfg.rpc.evm_rpc_urls.keys().cloned().collect();
+    let a = match parse_args(&ctx.args, &evm_chains, mode) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+
+    let (positions, mut read_errors) =
+        read_positions(ctx.shared, ctx.conn, &w, idx, &a.providers).await;
+    let borrows = if a.unwind_borrows {
+        let (b, errs) = read_borrows(ctx.shared, &w, idx, &a.chains).await;
+        read_errors.extend(errs);
+        b
+    } else {
+        vec![]
+    };
+    let plan = json!({
+      "wallet": w.name,
+      "account_index": idx,
+      "providers": a.providers,
+      "cancel_orders": a.cancel_orders,
+      "unwind_borrows": a.unwind_borrows,
+      "chains": if a.unwind_borrows { json!(a.chains) } else { Value::Null },
+      "slippage_bps": a.slippage_bps,
+      "positions": positions_json(&positions),
+      "borrows": borrows_json(&borrows),
+      "read_errors": read_errors
+    });
+
+    let cancel_hl = a.cancel_orders && a.providers.iter().any(|p| p == "hyperliquid");
+    if a.dry_run || (positions.is_empty() && borrows.is_empty() && !cancel_hl) {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_ok(json!({ "dry_run": a.dry_run, "plan": plan, "results": [] })),
+        ));
+    }
+
+    let msg = confirm_message(&a, &positions, &borrows);
+    if let Err(te) = confirm_close_all(ctx, &msg).await {
+        audit(ctx.shared, &w, idx, "declined", "user_declined", &plan);
+        return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+    }
+    audit(ctx.shared, &w, idx, "confirmed", "started", &plan);
+
+    // Each step runs through the regular tool path (policy, history, audit). A failed step is
+    // recorded and the run continues so one stuck venue does not leave everything else open.
+    let mut order_cancels = Vec::new();
+    if cancel_hl {
+        match perps::hyperliquid_cancel_all_orders(
+            ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, &w, idx,
+        )
+        .await
+        {
+            Ok(v) => order_cancels = v,
+            Err(e) => order_cancels.push(json!({ "ok": false, "error": format!("{e:#}") })),
+        }
+    }
+
+    let mut results = Vec::with_capacity(positions.len().saturating_add(borrows.len()));
+    for p in &positions {
+        // Hyperliquid takes the order direction; Jupiter takes the position side.
+        let side = match (p.provider.as_str(), p.side) {
+            ("hyperliquid", "long") => "sell",
+            ("hyperliquid", _) => "buy",
+            (_, s) => s,
+        };
+        let args = json!({
+          "provider": p.provider,
+          "wallet": w.name,
+          "account_index": idx,
+          "market": p.market,
+          "side": side,
+          "slippage_bps": a.slippage_bps
+        });
+        let resp = perps::handle(
+            ctx.req_id.clone(),
+            "close_perp_position",
+            args,
+            ctx.shared,
+            ctx.conn,
+            ctx.stdin,
+            ctx.stdout,
+        )
+        .await;
+        let (success, payload) = step_result(resp);
+        results.push(json!({
+          "kind": "perp_position",
+          "provider": p.provider,
+          "market": p.market,
+          "side": p.side,
+          "notional_usd": p.notional_usd,
+          "ok": success,
+          "result": if success { payload.clone() } else { Value::Null },
+          "error": if success { Value::Null } else { payload }
+        }));
+    }
+
+    for b in &borrows {
+        let amount = repay_amount(b.debt_base);
+        let args = json!({
+          "wallet": w.name,
+          "account_index": idx,
+          "chain": b.chain,
+          "protocol": b.protocol,
+          "token": b.token,
+          "amount": amount.to_string(),
+          "amount_units": "base"
+        });
+        let resp = route_lending(
+            ctx.req_id.clone(),
+            "repay_borrow",
+            args,
+            ctx.shared,
+            ctx.conn,
+            ctx.stdin,
+            ctx.stdout,
+        )
+        .await;
+        let (success, payload) = step_result(resp);
+        results.push(json!({
+          "kind": "borrow",
+          "chain": b.chain,
+          "protocol": b.protocol,
+          "token": b.token,
+          "symbol": b.symbol,
+          "debt_base": b.debt_base.to_string(),
+          "repay_base": amount.to_string(),
+          "ok": success,
+          "result": if success { payload.clone() } else { Value::Null },
+          "error": if success { Value::Null } else { payload }
+        }));
+    }
+
+    // Re-read so the caller sees what is actually still open, not what we think we closed.
+    let (left_positions, mut left_errors) =
+        read_positions(ctx.shared, ctx.conn, &w, idx, &a.providers).await;
+    let left_borrows = if a.unwind_borrows {
+        let (b, errs) = read_borrows(ctx.shared, &w, idx, &a.chains).await;
+        left_errors.extend(errs);
+        b
+    } else {
+        vec![]
+    };
+    let cancels_ok = order_cancels
+        .iter()
+        .all(|c| c.get("ok").and_then(Value::as_bool) == Some(true));
+    let complete = left_positions.is_empty()
+        && left_borrows.is_empty()
+        && left_errors.is_empty()
+        && cancels_ok;
+    let remaining = json!({
+      "positions": positions_json(&left_positions),
+      "borrows": borrows_json(&left_borrows),
+      "read_errors": left_errors
+    });
+    audit(
+        ctx.shared,
+        &w,
+        idx,
+        "confirmed",
+        if complete { "completed" } else { "partial" },
+        &json!({
+          "closed": results.iter().filter(|r| r.get("ok").and_then(Value::as_bool) == Some(true)).count(),
+          "failed": results.iter().filter(|r| r.get("ok").and_then(Value::as_bool) != Some(true)).count(),
+          "remaining": remaining
+        }),
+    );
+
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "dry_run": false,
+          "complete": complete,
+          "plan": plan,
+          "order_cancels": order_cancels,
+          "results": results,
+          "remaining": remaining
+        })),
+    ))
+}
@@ -16,7 +16,7 @@ use crate::{
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::{ConnState, SharedState};
-use super::super::helpers::{evm_addr_for_account, resolve_wallet_and_account};
+use super::super::helpers::{evm_addr_for_account, resolve_wallet_and_account, tx_note};
 use super::super::key_loading::load_evm_signer;
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::super::value_helpers::{parse_usd_value, summarize_sim_error};
@@ -84,6 +84,7 @@ struct CompoundApprovalCtx<'a> {
     idx: u32,
     chain: &'a str,
     outcome: &'a super::super::policy_confirm::WriteConfirmOutcome,
+    note: Option<&'a str>,
 }
 
 /// Audit log for a failed approval simulation.
@@ -141,6 +142,7 @@ async fn handle_compound_approval(
         idx,
         chain,
         outcome,
+        note,
     } = ctx;
     let tx_hash = evm
         .send_tx(signer, approve_tx)
@@ -149,13 +151,16 @@ async fn handle_compound_approval(
     let tx_hash_s = format!("{tx_hash:#x}");
     shared
         .ks
-        .append_tx_history(&json!({
-          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
-          "type": "approve", "chain": chain, "wallet": w_name,
-          "account_index": idx, "protocol": "compound",
-          "token": format!("{token_addr:#x}"), "spender": format!("{comet_addr:#x}"),
-          "amount_base": amount_base.to_string(), "usd_value": 0.0_f64, "txid": tx_hash_s,
-        }))
+        .append_tx_history(
+            &json!({
+              "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+              "type": "approve", "chain": chain, "wallet": w_name,
+              "account_index": idx, "protocol": "compound",
+              "token": format!("{token_addr:#x}"), "spender": format!("{comet_addr:#x}"),
+              "amount_base": amount_base.to_string(), "usd_value": 0.0_f64, "txid": tx_hash_s,
+            }),
+            note,
+        )
         .map_err(|e| {
             (
                 ToolError::new("internal_error", format!("{e:#}")),
@@ -203,6 +208,7 @@ struct CompoundParams<'a> {
     symbol: String,
     usd_value: f64,
     usd_value_known: bool,
+    note: Option<&'a str>,
 }
 
 struct CompoundAction<'a> {
@@ -246,15 +252,18 @@ fn compound_record_and_respond(
     txid: alloy::primitives::B256,
     req_id: Value,
 ) -> eyre::Result<JsonRpcResponse> {
-    shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
-      "type": history_type, "chain": p.chain, "wallet": p.w.name,
-      "account_index": p.idx, "protocol": "compound",
-      "comet": format!("{:#x}", p.comet_addr),
-      "token": format!("{:#x}", p.token_addr),
-      "amount_base": p.amount_base.to_string(), "amount_units": p.units,
-      "usd_value": p.usd_value, "txid": format!("{txid:#x}"),
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+          "type": history_type, "chain": p.chain, "wallet": p.w.name,
+          "account_index": p.idx, "protocol": "compound",
+          "comet": format!("{:#x}", p.comet_addr),
+          "token": format!("{:#x}", p.token_addr),
+          "amount_base": p.amount_base.to_string(), "amount_units": p.units,
+          "usd_value": p.usd_value, "txid": format!("{txid:#x}"),
+        }),
+        p.note,
+    )?;
     let _audit_log = shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": tool_name, "wallet": p.w.name,
       "account_index": p.idx, "chain": p.chain, "usd_value": p.usd_value,
@@ -357,6 +366,7 @@ where
             idx: p.idx,
             chain: p.chain,
             outcome: &outcome,
+            note: p.note,
         })
         .await
         {
@@ -402,12 +412,12 @@ struct CompoundValidatedArgs<'a> {
 }
 
 /// Parse and validate all compound handler arguments (chain, protocol, token, amount, comet).
-fn validate_compound_args<'a>(
+async fn validate_compound_args<'a>(
     args: &'a Value,
     shared: &SharedState,
     req_id: &Value,
 ) -> eyre::Result<Result<CompoundValidatedArgs<'a>, JsonRpcResponse>> {
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(shared, args)?;
 
     let chain = arg_str(args, "chain").unwrap_or("");
@@ -652,7 +662,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let va = match validate_compound_args(&args, shared, &req_id)? {
+    let va = match validate_compound_args(&args, shared, &req_id).await? {
         Ok(v) => v,
         Err(resp) => return Ok(resp),
     };
@@ -730,6 +740,7 @@ where
         symbol,
         usd_value,
         usd_value_known,
+        note: tx_note(&args),
     };
 
     compound_execute_and_respond(
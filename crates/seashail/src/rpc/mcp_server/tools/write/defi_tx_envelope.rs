@@ -13,13 +13,16 @@ use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
 use super::super::helpers::{
-    evm_addr_for_account, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+    evm_addr_for_account, evm_defi_deadline_seconds, evm_defi_slippage_bps,
+    resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls, tx_note,
+    unix_deadline,
 };
 use super::super::key_loading::{load_evm_signer, load_solana_keypair};
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::super::value_helpers::{
     get_asset_obj, get_str_in_args_or_asset, parse_usd_value, summarize_sim_error,
 };
+use super::bridge_quote::{self, BridgeQuote, RemoteBridgeValues};
 
 fn arg_str<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
     args.get(key)
@@ -177,6 +180,7 @@ struct SolanaTxResolved {
     allowed: Vec<solana_sdk::pubkey::Pubkey>,
     usd_value: f64,
     usd_value_known: bool,
+    bridge_values: RemoteBridgeValues,
 }
 
 type SolanaTxResolvedResult = Result<SolanaTxResolved, Box<JsonRpcResponse>>;
@@ -193,6 +197,7 @@ fn resolve_solana_tx_inline(params: &EnvelopeParams<'_>) -> SolanaTxResolvedResu
         allowed_program_ids,
         params.usd_value,
         params.usd_value_known,
+        RemoteBridgeValues::from_args(params.args),
     )
 }
 
@@ -203,6 +208,7 @@ fn finish_program_id_resolution(
     allowed_program_ids: Vec<String>,
     usd_value: f64,
     usd_value_known: bool,
+    bridge_values: RemoteBridgeValues,
 ) -> SolanaTxResolvedResult {
     if allowed_program_ids.is_empty() {
         return Err(Box::new(ok(
@@ -231,6 +237,7 @@ fn finish_program_id_resolution(
         allowed,
         usd_value,
         usd_value_known,
+        bridge_values,
     })
 }
 
@@ -276,6 +283,11 @@ async fn resolve_solana_tx_via_adapter(
         }
     }
 
+    let bridge_values = RemoteBridgeValues {
+        expected_received_usd: env.expected_received_usd,
+        bridge_fee_usd: env.bridge_fee_usd,
+    }
+    .or(RemoteBridgeValues::from_args(params.args));
     let allowed_program_ids = if env.allowed_program_ids.is_empty() {
         collect_allowed_program_ids(params.args)
     } else {
@@ -288,6 +300,7 @@ async fn resolve_solana_tx_via_adapter(
         allowed_program_ids,
         usd_value,
         usd_value_known,
+        bridge_values,
     ))
 }
 
@@ -326,10 +339,22 @@ where
     };
 
     let (effective_policy, _) = shared.cfg.policy_for_wallet(Some(params.w.name.as_str()));
-    let summary = format!(
+    let mut summary = format!(
         "{} on Solana via tx envelope ({})",
         params.tool_name, params.marketplace
     );
+    if params.tool_name == "bridge_tokens" {
+        let quote = BridgeQuote::remote(
+            resolved.usd_value_known.then_some(resolved.usd_value),
+            resolved.bridge_values,
+        );
+        if let Err(te) = quote.check(&effective_policy) {
+            Keystore::release_lock(params.lock)?;
+            return Ok(ok(params.req_id, tool_err(te)));
+        }
+        summary.push('\n');
+        summary.push_str(&quote.summary(&effective_policy));
+    }
     let outcome = match maybe_confirm_write(
         shared,
         conn,
@@ -380,7 +405,7 @@ where
         .sign_and_send_versioned_allowlist(&kp, &tx_bytes, &resolved.allowed)
         .await?;
 
-    shared.ks.append_tx_history(&json!({ "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": params.history_type, "chain": "solana", "wallet": params.w.name, "account_index": params.idx, "usd_value": resolved.usd_value, "signature": sig.to_string(), "protocol": params.marketplace }))?;
+    shared.ks.append_tx_history(&json!({ "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": params.history_type, "chain": "solana", "wallet": params.w.name, "account_index": params.idx, "usd_value": resolved.usd_value, "signature": sig.to_string(), "protocol": params.marketplace }), tx_note(params.args))?;
     let _audit_log = shared.ks.append_audit_log(&json!({ "ts": utc_now_iso(), "tool": params.tool_name, "wallet": params.w.name, "account_index": params.idx, "chain": "solana", "usd_value": resolved.usd_value, "usd_value_known": resolved.usd_value_known, "policy_decision": outcome.policy_decision, "confirm_required": outcome.confirm_required, "confirm_result": outcome.confirm_result, "daily_used_usd": outcome.daily_used_usd, "forced_confirm": outcome.forced_confirm, "txid": sig.to_string(), "error_code": null, "result": "broadcasted" }));
 
     Keystore::release_lock(params.lock)?;
@@ -399,6 +424,36 @@ struct EvmTxResolved {
     value_wei: String,
     usd_value: f64,
     usd_value_known: bool,
+    bridge_values: RemoteBridgeValues,
+    /// Slippage the adapter was asked to build with; `None` for caller-supplied calldata.
+    slippage_bps: Option<u32>,
+}
+
+/// Add the EVM DeFi `deadline` (unix seconds) and `slippage_bps` to an adapter request.
+///
+/// Per-call `deadline_seconds` / `slippage_bps` win, then values already in `asset`, then
+/// `[evm_defi]` config. Returns the slippage sent.
+fn apply_evm_defi_limits(
+    cfg: &crate::config::EvmDefiConfig,
+    args: &Value,
+    asset: &mut Value,
+) -> Result<u32, ToolError> {
+    let deadline_seconds = evm_defi_deadline_seconds(cfg, args)?;
+    let slippage_bps = if args.get("slippage_bps").is_some() {
+        evm_defi_slippage_bps(cfg, args)
+    } else {
+        evm_defi_slippage_bps(cfg, asset)
+    };
+    if let Some(obj) = asset.as_object_mut() {
+        if args.get("deadline_seconds").is_some() || !obj.contains_key("deadline") {
+            obj.insert(
+                "deadline".to_owned(),
+                json!(unix_deadline(deadline_seconds)),
+            );
+        }
+        obj.insert("slippage_bps".to_owned(), json!(slippage_bps));
+    }
+    Ok(slippage_bps)
 }
 
 /// Resolve EVM tx parameters from args or via the marketplace adapter.
@@ -419,11 +474,17 @@ async fn resolve_evm_tx_params(
         .to_owned();
     let mut usd_value = params.usd_value;
     let mut usd_value_known = params.usd_value_known;
+    let mut bridge_values = RemoteBridgeValues::from_args(params.args);
+    let mut slippage_bps = None;
 
     if to.trim().is_empty() && get_asset_obj(params.args).is_some() {
-        let asset = get_asset_obj(params.args)
+        let mut asset = get_asset_obj(params.args)
             .cloned()
             .unwrap_or_else(|| json!({}));
+        match apply_evm_defi_limits(&shared.cfg.evm_defi, params.args, &mut asset) {
+            Ok(v) => slippage_bps = Some(v),
+            Err(te) => return Ok(Err(ok(params.req_id.clone(), tool_err(te)))),
+        }
         let env = match crate::marketplace_adapter::fetch_evm_tx_envelope(
             &shared.cfg.http,
             params.marketplace,
@@ -445,6 +506,11 @@ async fn resolve_evm_tx_params(
         to = env.to;
         data = env.data;
         value_wei = env.value_wei;
+        bridge_values = RemoteBridgeValues {
+            expected_received_usd: env.expected_received_usd,
+            bridge_fee_usd: env.bridge_fee_usd,
+        }
+        .or(bridge_values);
         if !usd_value_known {
             if let Some(v) = env.usd_value {
                 usd_value = v;
@@ -469,6 +535,8 @@ async fn resolve_evm_tx_params(
         value_wei,
         usd_value,
         usd_value_known,
+        bridge_values,
+        slippage_bps,
     }))
 }
 
@@ -584,10 +652,31 @@ where
 
     let tx = build_evm_typed_tx(from, to_addr, &resolved.data, &resolved.value_wei);
     let (effective_policy, _) = shared.cfg.policy_for_wallet(Some(params.w.name.as_str()));
-    let summary = format!(
+    let mut summary = format!(
         "{} on {chain} via tx envelope ({})",
         params.tool_name, params.marketplace
     );
+    if params.tool_name == "bridge_tokens" {
+        let mut quote = BridgeQuote::remote(
+            resolved.usd_value_known.then_some(resolved.usd_value),
+            resolved.bridge_values,
+        );
+        if let Ok(gas) = evm.estimate_tx_gas_strict(&tx).await {
+            quote.source_fee_usd = bridge_quote::evm_gas_usd(shared, chain, u128::from(gas)).await;
+        }
+        let value_wei = resolved.value_wei.trim();
+        if !value_wei.is_empty() && value_wei != "0" {
+            quote.notes.push(format!(
+                "attaches {value_wei} wei of native token (messaging fee and/or bridged native amount)"
+            ));
+        }
+        if let Err(te) = quote.check(&effective_policy) {
+            Keystore::release_lock(params.lock)?;
+            return Ok(ok(params.req_id, tool_err(te)));
+        }
+        summary.push('\n');
+        summary.push_str(&quote.summary(&effective_policy));
+    }
     let outcome = match maybe_confirm_write(
         shared,
         conn,
@@ -602,7 +691,7 @@ where
             usd_value: resolved.usd_value,
             usd_value_known: resolved.usd_value_known,
             force_confirm: effective_policy.require_user_confirm_for_remote_tx.get(),
-            slippage_bps: None,
+            slippage_bps: resolved.slippage_bps,
             to_address: Some(&resolved.to),
             contract: Some(&resolved.to),
             leverage: None,
@@ -666,7 +755,7 @@ where
         }
     };
 
-    shared.ks.append_tx_history(&json!({ "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": params.history_type, "chain": chain, "wallet": params.w.name, "account_index": params.idx, "usd_value": resolved.usd_value, "txid": format!("{txid:#x}"), "protocol": params.marketplace, "to": resolved.to }))?;
+    shared.ks.append_tx_history(&json!({ "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": params.history_type, "chain": chain, "wallet": params.w.name, "account_index": params.idx, "usd_value": resolved.usd_value, "txid": format!("{txid:#x}"), "protocol": params.marketplace, "to": resolved.to }), tx_note(params.args))?;
     let _audit_log = shared.ks.append_audit_log(&json!({ "ts": utc_now_iso(), "tool": params.tool_name, "wallet": params.w.name, "account_index": params.idx, "chain": chain, "usd_value": resolved.usd_value, "usd_value_known": resolved.usd_value_known, "policy_decision": outcome.policy_decision, "confirm_required": outcome.confirm_required, "confirm_result": outcome.confirm_result, "daily_used_usd": outcome.daily_used_usd, "forced_confirm": outcome.forced_confirm, "txid": format!("{txid:#x}"), "error_code": null, "result": "broadcasted" }));
 
     Keystore::release_lock(params.lock)?;
@@ -691,7 +780,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(shared, &args)?;
 
     let chain = args.get("chain").and_then(|v| v.as_str()).unwrap_or("");
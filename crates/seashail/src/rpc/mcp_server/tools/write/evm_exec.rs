@@ -0,0 +1,127 @@
+use crate::{
+    chains::{
+        erc4337::{self, SmartAccountEndpoints},
+        evm::EvmChain,
+    },
+    errors::ToolError,
+    wallet::WalletRecord,
+};
+use alloy::{
+    primitives::{Address, B256},
+    rpc::types::TransactionRequest,
+};
+use serde_json::{json, Value};
+
+use super::super::super::SharedState;
+use super::super::helpers::evm_addr_for_account;
+use super::super::key_loading::load_evm_signer;
+use super::HandlerCtx;
+
+/// How an EVM write is executed: signed by the account's EOA (default), or wrapped in an
+/// ERC-4337 user operation from the wallet's smart account.
+pub enum EvmExecution {
+    Eoa(Address),
+    SmartAccount {
+        sender: Address,
+        endpoints: SmartAccountEndpoints,
+    },
+}
+
+impl EvmExecution {
+    /// The address the transaction acts as (`from` for building and simulating calls).
+    pub const fn from_address(&self) -> Address {
+        match self {
+            Self::Eoa(a) | Self::SmartAccount { sender: a, .. } => *a,
+        }
+    }
+}
+
+/// Resolve the `execution` argument (`eoa` | `smart_account`) for `chain`.
+pub fn resolve(
+    shared: &SharedState,
+    args: &Value,
+    w: &WalletRecord,
+    idx: u32,
+    chain: &str,
+) -> Result<EvmExecution, ToolError> {
+    let eoa = evm_addr_for_account(w, idx)
+        .map_err(|e| ToolError::new("wallet_error", format!("{e:#}")))?;
+    let mode = args
+        .get("execution")
+        .and_then(Value::as_str)
+        .unwrap_or("eoa")
+        .trim();
+    match mode {
+        "" | "eoa" => Ok(EvmExecution::Eoa(eoa)),
+        "smart_account" => {
+            let sa = w.smart_account.as_ref().ok_or_else(|| {
+                ToolError::new(
+                    "invalid_request",
+                    "wallet has no smart account; call enable_smart_account first",
+                )
+            })?;
+            let sender = sa
+                .addresses
+                .get(idx as usize)
+                .and_then(|s| EvmChain::parse_address(s).ok())
+                .ok_or_else(|| {
+                    ToolError::new(
+                        "invalid_request",
+                        "smart account address missing for this account index; re-run enable_smart_account",
+                    )
+                })?;
+            let cfg = &shared.cfg.erc4337;
+            let bundler_url = cfg.bundler_urls.get(chain).cloned().ok_or_else(|| {
+                ToolError::new(
+                    "invalid_config",
+                    format!("no erc4337 bundler configured for {chain} (erc4337.bundler_urls)"),
+                )
+            })?;
+            let parse = |s: &str| {
+                EvmChain::parse_address(s)
+                    .map_err(|e| ToolError::new("invalid_config", format!("{e:#}")))
+            };
+            let paymaster_context: serde_json::Map<String, Value> = cfg
+                .paymaster_context
+                .iter()
+                .map(|(k, v)| (k.clone(), json!(v)))
+                .collect();
+            Ok(EvmExecution::SmartAccount {
+                sender,
+                endpoints: SmartAccountEndpoints {
+                    entry_point: parse(&sa.entry_point)?,
+                    factory: parse(&sa.factory)?,
+                    bundler_url,
+                    paymaster_url: cfg.paymaster_urls.get(chain).cloned(),
+                    paymaster_context: Value::Object(paymaster_context),
+                },
+            })
+        }
+        other => Err(ToolError::new(
+            "invalid_request",
+            format!("unknown execution mode: {other} (expected eoa or smart_account)"),
+        )),
+    }
+}
+
+/// Sign and submit `tx` using the resolved execution mode. Returns the on-chain tx hash.
+pub async fn submit<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    w: &WalletRecord,
+    idx: u32,
+    evm: &EvmChain,
+    exec: &EvmExecution,
+    tx: TransactionRequest,
+) -> eyre::Result<B256>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let signer = load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
+    match exec {
+        EvmExecution::Eoa(_) => evm.send_tx(signer, tx).await,
+        EvmExecution::SmartAccount { sender, endpoints } => {
+            erc4337::send_user_operation(evm, endpoints, &signer, *sender, &tx).await
+        }
+    }
+}
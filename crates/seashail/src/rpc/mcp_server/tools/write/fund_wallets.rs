@@ -4,7 +4,8 @@ use crate::{
     errors::ToolError,
     financial_math,
     keystore::{utc_now_iso, Keystore},
-    policy_engine::WriteOp,
+    policy::InternalTransfersPolicy,
+    policy_engine::{self, WriteOp},
     price,
 };
 use alloy::primitives::U256;
@@ -15,11 +16,13 @@ use serde_json::{json, Value};
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::helpers::{
-    evm_addr_for_account, is_native_token, sol_pubkey_for_account, solana_fallback_urls,
+    evm_addr_for_account, is_native_token, sol_pubkey_for_account, solana_fallback_urls, tx_note,
     u128_to_u256, u128_to_u64,
 };
 use super::super::key_loading::{load_evm_signer, load_solana_keypair};
-use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest};
+use super::super::policy_confirm::{
+    confirm_internal_transfer, WriteConfirmOutcome, WriteConfirmRequest,
+};
 use super::common::summarize_sim_error;
 use super::HandlerCtx;
 
@@ -71,7 +74,7 @@ where
     R: tokio::io::AsyncRead + Unpin + Send,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let parsed = match parse_fund_args(ctx, lock)? {
         Ok(v) => v,
         Err(resp) => return Ok(resp),
@@ -237,10 +240,7 @@ where
 
     let usd_value_each = sol_usd_value_each(ctx, &sol, &p.token, amount_base).await?;
 
-    let outcome: Option<WriteConfirmOutcome> = if p.effective_policy.internal_transfers_exempt.get()
-    {
-        None
-    } else {
+    let outcome: Option<WriteConfirmOutcome> = {
         let usd_total = financial_math::mul_f64(
             usd_value_each,
             f64::from(u32::try_from(p.destinations.len()).unwrap_or(0)),
@@ -252,7 +252,7 @@ where
             p.from_idx,
             usd_total
         );
-        match maybe_confirm_write(
+        match confirm_internal_transfer(
             ctx.shared,
             ctx.conn,
             ctx.stdin,
@@ -275,7 +275,7 @@ where
         )
         .await
         {
-            Ok(o) => Some(o),
+            Ok(o) => o,
             Err(te) => {
                 Keystore::release_lock(p.lock)?;
                 return Ok(ok(req_id, tool_err(te)));
@@ -386,6 +386,17 @@ where
             "ok": false, "error_code": "scam_address_blocked"
         }));
     }
+    if let Err(te) = policy_engine::check_send_denylist(
+        &p.effective_policy,
+        "solana",
+        Some(p.from_idx),
+        &to_pk.to_string(),
+    ) {
+        return Ok(json!({
+            "wallet": to_w.name, "account_index": d.account_index,
+            "ok": false, "error_code": te.code, "error": te.message
+        }));
+    }
 
     let sig = match if is_native_token(&p.token) {
         sol.send_sol(kp, to_pk, amount_base).await
@@ -402,26 +413,29 @@ where
         }
     };
 
-    let ty = if p.effective_policy.internal_transfers_exempt.get() {
-        "internal_transfer"
-    } else {
+    let ty = if p.effective_policy.internal_transfers_mode() == InternalTransfersPolicy::Enforce {
         "internal_transfer_strict"
+    } else {
+        "internal_transfer"
     };
 
-    ctx.shared.ks.append_tx_history(&json!({
-        "ts": utc_now_iso(),
-        "day": Keystore::current_utc_day_key(),
-        "type": ty,
-        "chain": "solana",
-        "wallet": p.from_w.name,
-        "account_index": p.from_idx,
-        "to_wallet": to_w.name,
-        "to_account_index": d.account_index,
-        "token": if is_native_token(&p.token) { "native" } else { &p.token },
-        "amount_base": amount_base.to_string(),
-        "usd_value": usd_value_each,
-        "signature": sig.to_string()
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+            "ts": utc_now_iso(),
+            "day": Keystore::current_utc_day_key(),
+            "type": ty,
+            "chain": "solana",
+            "wallet": p.from_w.name,
+            "account_index": p.from_idx,
+            "to_wallet": to_w.name,
+            "to_account_index": d.account_index,
+            "token": if is_native_token(&p.token) { "native" } else { &p.token },
+            "amount_base": amount_base.to_string(),
+            "usd_value": usd_value_each,
+            "signature": sig.to_string()
+        }),
+        tx_note(&ctx.args),
+    )?;
 
     Ok(json!({
         "wallet": to_w.name, "account_index": d.account_index,
@@ -515,10 +529,7 @@ where
 
     let (usd_value_each, amount_base, token_addr_opt) = evm_resolve_amount(ctx, &evm, &p).await?;
 
-    let outcome: Option<WriteConfirmOutcome> = if p.effective_policy.internal_transfers_exempt.get()
-    {
-        None
-    } else {
+    let outcome: Option<WriteConfirmOutcome> = {
         let usd_total = financial_math::mul_f64(
             usd_value_each,
             f64::from(u32::try_from(p.destinations.len()).unwrap_or(0)),
@@ -530,7 +541,7 @@ where
             p.from_idx,
             usd_total
         );
-        match maybe_confirm_write(
+        match confirm_internal_transfer(
             ctx.shared,
             ctx.conn,
             ctx.stdin,
@@ -553,7 +564,7 @@ where
         )
         .await
         {
-            Ok(o) => Some(o),
+            Ok(o) => o,
             Err(te) => {
                 Keystore::release_lock(p.lock)?;
                 return Ok(ok(req_id, tool_err(te)));
@@ -687,6 +698,17 @@ where
             "ok": false, "error_code": "scam_address_blocked"
         }));
     }
+    if let Err(te) = policy_engine::check_send_denylist(
+        &p.effective_policy,
+        &p.chain,
+        Some(p.from_idx),
+        &format!("{to_addr:#x}"),
+    ) {
+        return Ok(json!({
+            "wallet": to_w.name, "account_index": d.account_index,
+            "ok": false, "error_code": te.code, "error": te.message
+        }));
+    }
 
     let tx: TransactionRequest = match fc.token_addr_opt {
         None => EvmChain::build_native_transfer(fc.from, to_addr, fc.amount_base),
@@ -724,26 +746,29 @@ where
         }
     };
 
-    let ty = if p.effective_policy.internal_transfers_exempt.get() {
-        "internal_transfer"
-    } else {
+    let ty = if p.effective_policy.internal_transfers_mode() == InternalTransfersPolicy::Enforce {
         "internal_transfer_strict"
+    } else {
+        "internal_transfer"
     };
 
-    ctx.shared.ks.append_tx_history(&json!({
-        "ts": utc_now_iso(),
-        "day": Keystore::current_utc_day_key(),
-        "type": ty,
-        "chain": p.chain,
-        "wallet": p.from_w.name,
-        "account_index": p.from_idx,
-        "to_wallet": to_w.name,
-        "to_account_index": d.account_index,
-        "token": if is_native_token(&p.token) { "native" } else { &p.token },
-        "amount_base": fc.amount_base.to_string(),
-        "usd_value": fc.usd_value_each,
-        "txid": format!("{txid:#x}")
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+            "ts": utc_now_iso(),
+            "day": Keystore::current_utc_day_key(),
+            "type": ty,
+            "chain": p.chain,
+            "wallet": p.from_w.name,
+            "account_index": p.from_idx,
+            "to_wallet": to_w.name,
+            "to_account_index": d.account_index,
+            "token": if is_native_token(&p.token) { "native" } else { &p.token },
+            "amount_base": fc.amount_base.to_string(),
+            "usd_value": fc.usd_value_each,
+            "txid": format!("{txid:#x}")
+        }),
+        tx_note(&ctx.args),
+    )?;
 
     Ok(json!({
         "wallet": to_w.name, "account_index": d.account_index,
@@ -8,14 +8,16 @@ use crate::{
     chains::solana::SolanaChain,
     errors::ToolError,
     keystore::{utc_now_iso, Keystore},
-    policy_engine::WriteOp,
+    policy_engine::{check_lending_leverage, WriteOp},
     price,
 };
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::super::{ConnState, SharedState};
-use super::super::helpers::{is_native_token, resolve_wallet_and_account, sol_pubkey_for_account};
+use super::super::helpers::{
+    is_native_token, resolve_wallet_and_account, sol_pubkey_for_account, tx_note,
+};
 use super::super::helpers::{solana_fallback_urls, u128_to_u64};
 use super::super::key_loading::load_solana_keypair;
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
@@ -27,6 +29,9 @@ const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
 
 const KAMINO_LEND_PROGRAM: &str = "KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD";
 const KAMINO_SCOPE_PROGRAM: &str = "HFn8GnPADiny6XqUoWE8uRPPxb29ikn4yTuPa9MF2fWJ";
+// Multiply transactions swap borrowed liquidity into collateral via Jupiter.
+const JUPITER_V6_PROGRAM: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+const DEFAULT_MULTIPLY_SLIPPAGE_BPS: u32 = 50;
 
 fn arg_str<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
     args.get(key)
@@ -81,6 +86,12 @@ fn default_allowed_program_ids() -> eyre::Result<Vec<solana_sdk::pubkey::Pubkey>
         .context("parse kamino allowlist program ids")
 }
 
+fn multiply_allowed_program_ids() -> eyre::Result<Vec<solana_sdk::pubkey::Pubkey>> {
+    let mut ids = default_allowed_program_ids()?;
+    ids.push(SolanaChain::parse_pubkey(JUPITER_V6_PROGRAM).context("parse jupiter program id")?);
+    Ok(ids)
+}
+
 fn base58_32_bytes(s: &str) -> eyre::Result<[u8; 32]> {
     let v = bs58::decode(s)
         .into_vec()
@@ -276,12 +287,10 @@ async fn kamino_fetch_sign_broadcast<R, W>(
     w: &crate::wallet::WalletRecord,
     idx: u32,
     sol: &SolanaChain,
-    owner: &solana_sdk::pubkey::Pubkey,
     base_url: &str,
     endpoint: &str,
-    market: &str,
-    reserve: &str,
-    amount_ui: &str,
+    body: &Value,
+    allowed: &[solana_sdk::pubkey::Pubkey],
 ) -> eyre::Result<solana_sdk::signature::Signature>
 where
     R: tokio::io::AsyncRead + Unpin,
@@ -290,19 +299,13 @@ where
     ensure_https_or_loopback(base_url, "kamino_api_base_url")?;
 
     let url = format!("{}/ktx/klend/{endpoint}", base_url.trim_end_matches('/'));
-    let body = json!({
-      "wallet": owner.to_string(),
-      "market": market,
-      "reserve": reserve,
-      "amount": amount_ui,
-    });
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .context("build http client")?;
     let resp = client
         .post(url)
-        .json(&body)
+        .json(body)
         .send()
         .await
         .context("kamino ktx request")?;
@@ -322,9 +325,8 @@ where
         .decode(tx_b64)
         .context("decode kamino transaction b64")?;
 
-    let allowed = default_allowed_program_ids()?;
     let kp = load_solana_keypair(shared, conn, stdin, stdout, w, idx).await?;
-    sol.sign_and_send_versioned_allowlist(&kp, &tx_bytes, &allowed)
+    sol.sign_and_send_versioned_allowlist(&kp, &tx_bytes, allowed)
         .await
         .map_err(|e| eyre::eyre!(summarize_sim_error(&e, "kamino tx")))
 }
@@ -357,6 +359,7 @@ async fn kamino_resolve_reserve(
 fn validate_kamino_args<'a>(
     args: &'a Value,
     default_market: &str,
+    allow_max: bool,
 ) -> Result<(&'a str, Option<&'a str>, &'a str, &'a str, String), ToolError> {
     let chain = arg_str(args, "chain").unwrap_or("");
     if chain != "solana" {
@@ -380,7 +383,7 @@ fn validate_kamino_args<'a>(
     if token_s.is_empty() || amount_s.is_empty() {
         return Err(ToolError::new("invalid_request", "missing token/amount"));
     }
-    if amount_s.eq_ignore_ascii_case("max") {
+    if amount_s.eq_ignore_ascii_case("max") && !allow_max {
         return Err(ToolError::new(
             "invalid_request",
             "kamino native path does not support amount=max (provide an explicit amount, or use tx envelope fallback)",
@@ -458,14 +461,18 @@ fn kamino_record_and_respond(
     mint: &str,
     amount_ui: &str,
     outcome: &super::super::policy_confirm::WriteConfirmOutcome,
+    note: Option<&str>,
 ) -> eyre::Result<JsonRpcResponse> {
-    ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
-      "type": history_type, "chain": "solana", "wallet": w.name,
-      "account_index": idx, "usd_value": usd_value,
-      "signature": sig.to_string(), "protocol": "kamino",
-      "market": market, "reserve": reserve, "mint": mint
-    }))?;
+    ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+          "type": history_type, "chain": "solana", "wallet": w.name,
+          "account_index": idx, "usd_value": usd_value,
+          "signature": sig.to_string(), "protocol": "kamino", "position_type": "lend",
+          "market": market, "reserve": reserve, "mint": mint
+        }),
+        note,
+    )?;
     let _audit_log = ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": tool_name, "wallet": w.name,
       "account_index": idx, "chain": "solana", "usd_value": usd_value,
@@ -477,13 +484,448 @@ fn kamino_record_and_respond(
     Ok(ok(
         req_id,
         tool_ok(json!({
-          "chain": "solana", "protocol": "kamino", "action": action_label,
-          "signature": sig.to_string(), "usd_value": usd_value,
+          "chain": "solana", "protocol": "kamino", "position_type": "lend",
+          "action": action_label, "signature": sig.to_string(), "usd_value": usd_value,
           "market": market, "reserve": reserve, "mint": mint, "amount_ui": amount_ui
         })),
     ))
 }
 
+/// Kamino position type: a simple single-reserve supply, or a "multiply" position that loops a
+/// borrow of `debt_token` back into collateral `token` up to a target leverage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PositionType {
+    Lend,
+    Multiply,
+}
+
+fn parse_position_type(args: &Value, tool_name: &str) -> Result<PositionType, ToolError> {
+    match arg_str(args, "position_type").unwrap_or("lend") {
+        "lend" => Ok(PositionType::Lend),
+        "multiply" if matches!(tool_name, "lend_tokens" | "withdraw_lending") => {
+            Ok(PositionType::Multiply)
+        }
+        "multiply" => Err(ToolError::new(
+            "invalid_request",
+            "position_type=multiply is only supported by lend_tokens (open) and withdraw_lending (close)",
+        )),
+        _ => Err(ToolError::new(
+            "invalid_request",
+            "position_type must be lend or multiply",
+        )),
+    }
+}
+
+fn parse_multiply_leverage(args: &Value) -> Result<f64, ToolError> {
+    let v = args.get("leverage");
+    let leverage = v.and_then(Value::as_f64).or_else(|| {
+        v.and_then(Value::as_str)
+            .and_then(|s| s.trim().parse::<f64>().ok())
+    });
+    match leverage {
+        Some(l) if l.is_finite() && l > 1.0_f64 => Ok(l),
+        Some(_) => Err(ToolError::new(
+            "invalid_request",
+            "leverage must be greater than 1",
+        )),
+        None => Err(ToolError::new(
+            "invalid_request",
+            "missing leverage (required to open a multiply position)",
+        )),
+    }
+}
+
+fn parse_multiply_slippage(args: &Value, max_slippage_bps: u32) -> Result<u32, ToolError> {
+    let slippage_bps = match args.get("slippage_bps").and_then(Value::as_u64) {
+        Some(v) => u32::try_from(v)
+            .map_err(|_e| ToolError::new("invalid_request", "invalid slippage_bps"))?,
+        None => DEFAULT_MULTIPLY_SLIPPAGE_BPS.min(max_slippage_bps),
+    };
+    if slippage_bps > max_slippage_bps {
+        return Err(ToolError::new(
+            "policy_slippage_too_high",
+            format!("slippage_bps {slippage_bps} exceeds max_slippage_bps {max_slippage_bps}"),
+        ));
+    }
+    Ok(slippage_bps)
+}
+
+fn json_f64(v: &Value, key: &str) -> Option<f64> {
+    let f = v.get(key)?;
+    f.as_f64()
+        .or_else(|| f.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+        .filter(|x| x.is_finite() && *x > 0.0_f64)
+}
+
+/// Liquidation threshold (as a fraction) for a reserve, from Kamino's reserve metrics.
+///
+/// Falls back to the reserve's max LTV when the liquidation threshold is not reported, which
+/// understates the health factor (conservative). Cached per market like the reserve map.
+async fn kamino_liquidation_threshold(
+    shared: &mut SharedState,
+    base_url: &str,
+    market: &str,
+    reserve: &str,
+) -> eyre::Result<Option<f64>> {
+    shared.ensure_db().await;
+    let cache_key = format!("kamino:reserve_liq_threshold:{market}");
+    if let Some(db) = shared.db() {
+        if let Ok(now) = crate::db::Db::now_ms() {
+            if let Ok(Some(row)) = db.get_json_if_fresh(&cache_key, now).await {
+                if let Ok(v) = serde_json::from_str::<Value>(&row.json) {
+                    if let Some(t) = v.get(reserve).and_then(Value::as_f64) {
+                        return Ok(Some(t));
+                    }
+                }
+            }
+        }
+    }
+
+    ensure_https_or_loopback(base_url, "kamino_api_base_url")?;
+    let url = format!(
+        "{}/kamino-market/{market}/reserves/metrics",
+        base_url.trim_end_matches('/')
+    );
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(20))
+        .build()
+        .context("build http client")?;
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .context("kamino reserve metrics request")?;
+    if !resp.status().is_success() {
+        eyre::bail!("kamino reserve metrics http {}", resp.status());
+    }
+    let v: Value = resp.json().await.context("kamino reserve metrics json")?;
+
+    let mut map = serde_json::Map::new();
+    for it in v.as_array().map(Vec::as_slice).unwrap_or_default() {
+        let Some(pk) = it.get("reserve").and_then(Value::as_str) else {
+            continue;
+        };
+        let Some(t) = json_f64(it, "liquidationThreshold")
+            .or_else(|| json_f64(it, "liquidationLtv"))
+            .or_else(|| json_f64(it, "maxLtv"))
+        else {
+            continue;
+        };
+        map.insert(
+            pk.to_owned(),
+            json!(crate::financial_math::fraction_from_ratio_or_pct(t)),
+        );
+    }
+
+    if let Some(db) = shared.db() {
+        if let Ok(now) = crate::db::Db::now_ms() {
+            let stale_at = now.saturating_add(15 * 60 * 1000);
+            drop(
+                db.upsert_json(
+                    &cache_key,
+                    &Value::Object(map.clone()).to_string(),
+                    now,
+                    stale_at,
+                )
+                .await,
+            );
+        }
+    }
+
+    Ok(map.get(reserve).and_then(Value::as_f64))
+}
+
+/// Resolved collateral/debt legs of a multiply position.
+struct MultiplyLegs {
+    market: String,
+    mint: String,
+    reserve: String,
+    debt_mint: String,
+    debt_reserve: String,
+}
+
+async fn resolve_multiply_legs(
+    shared: &mut SharedState,
+    args: &Value,
+    token_s: &str,
+    reserve_s: Option<&str>,
+    market: String,
+) -> Result<MultiplyLegs, ToolError> {
+    let debt_s = arg_str(args, "debt_token").ok_or_else(|| {
+        ToolError::new(
+            "invalid_request",
+            "missing debt_token (required for position_type=multiply)",
+        )
+    })?;
+    let mint = if is_native_token(token_s) {
+        WSOL_MINT
+    } else {
+        token_s
+    };
+    let debt_mint = if is_native_token(debt_s) {
+        WSOL_MINT
+    } else {
+        debt_s
+    };
+    if mint == debt_mint {
+        return Err(ToolError::new(
+            "invalid_request",
+            "token and debt_token must differ for a multiply position",
+        ));
+    }
+    let reserve = kamino_resolve_reserve(shared, mint, &market, reserve_s).await?;
+    let debt_reserve =
+        kamino_resolve_reserve(shared, debt_mint, &market, arg_str(args, "debt_reserve")).await?;
+    Ok(MultiplyLegs {
+        market,
+        mint: mint.to_owned(),
+        reserve,
+        debt_mint: debt_mint.to_owned(),
+        debt_reserve,
+    })
+}
+
+/// Leverage and projected health factor for opening a multiply position.
+struct MultiplyRisk {
+    leverage: f64,
+    liquidation_threshold: Option<f64>,
+    health_factor: Option<f64>,
+}
+
+async fn multiply_open_risk(
+    shared: &mut SharedState,
+    args: &Value,
+    legs: &MultiplyLegs,
+    policy: &crate::policy::Policy,
+) -> Result<MultiplyRisk, ToolError> {
+    let leverage = parse_multiply_leverage(args)?;
+    let base_url = shared.cfg.http.kamino_api_base_url.trim().to_owned();
+    // A failed lookup leaves the health factor unknown; the policy guard decides whether that
+    // blocks the open.
+    let liquidation_threshold =
+        kamino_liquidation_threshold(shared, &base_url, &legs.market, &legs.reserve)
+            .await
+            .ok()
+            .flatten();
+    let health_factor = liquidation_threshold
+        .and_then(|t| crate::financial_math::multiply_health_factor(leverage, t));
+    check_lending_leverage(policy, leverage, health_factor)?;
+    Ok(MultiplyRisk {
+        leverage,
+        liquidation_threshold,
+        health_factor,
+    })
+}
+
+/// Parse an explicit multiply collateral amount into (base units, UI string).
+fn parse_multiply_amount(
+    amount_s: &str,
+    units: &str,
+    decimals: u8,
+) -> Result<(u64, String), ToolError> {
+    let base = parse_kamino_amount(amount_s, units, decimals)?;
+    let base_u64 = kamino_u128_to_u64(base)?;
+    let ui = if units == "base" {
+        kamino_format_amount_ui(base, decimals)?
+    } else {
+        amount_s.to_owned()
+    };
+    Ok((base_u64, ui))
+}
+
+/// Open (`lend_tokens`) or unwind (`withdraw_lending`) a Kamino multiply position.
+///
+/// Opening enforces the policy leverage cap and projected health factor before confirmation, and
+/// counts the full leveraged notional (equity × leverage) toward USD caps.
+async fn handle_multiply<R, W>(
+    req_id: Value,
+    tool_name: &str,
+    args: &Value,
+    shared: &mut SharedState,
+    conn: &mut ConnState,
+    stdin: &mut tokio::io::Lines<BufReader<R>>,
+    stdout: &mut W,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    policy: &crate::policy::Policy,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let opening = tool_name == "lend_tokens";
+    let (token_s, reserve_s, amount_s, units, market) =
+        match validate_kamino_args(args, &shared.cfg.http.kamino_default_lend_market, !opening) {
+            Ok(v) => v,
+            Err(te) => return Ok(ok(req_id, tool_err(te))),
+        };
+    let legs = match resolve_multiply_legs(shared, args, token_s, reserve_s, market).await {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
+    // Both directions swap through Jupiter (borrowed liquidity into collateral, or back to repay).
+    let slippage_bps = match parse_multiply_slippage(args, policy.max_slippage_bps) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
+    let risk = if opening {
+        match multiply_open_risk(shared, args, &legs, policy).await {
+            Ok(v) => Some(v),
+            Err(te) => return Ok(ok(req_id, tool_err(te))),
+        }
+    } else {
+        None
+    };
+
+    let mode = effective_network_mode(shared, conn);
+    let sol = SolanaChain::new_with_fallbacks(
+        &shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(shared, mode),
+        &shared.cfg.http.jupiter_base_url,
+        shared.cfg.http.jupiter_api_key.as_deref(),
+        shared.cfg.rpc.solana_default_compute_unit_limit,
+        shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    );
+    let owner = sol_pubkey_for_account(w, idx)?;
+    // Closing accepts amount=max (unwind the whole position).
+    let amount = if amount_s.eq_ignore_ascii_case("max") {
+        None
+    } else {
+        let decimals = sol
+            .get_mint_decimals(SolanaChain::parse_pubkey(&legs.mint)?)
+            .await?;
+        match parse_multiply_amount(amount_s, units, decimals) {
+            Ok(v) => Some(v),
+            Err(te) => return Ok(ok(req_id, tool_err(te))),
+        }
+    };
+
+    // Equity moved by this call; a full close can only be valued from the caller's usd_value.
+    let (equity_usd, usd_value_known) = match &amount {
+        Some((base_u64, _)) => {
+            kamino_resolve_usd(shared, &sol, token_s, &legs.mint, *base_u64, args).await?
+        }
+        None => parse_usd_value(args),
+    };
+    let usd_value = risk.as_ref().map_or(equity_usd, |r| {
+        crate::financial_math::mul_f64(equity_usd, r.leverage)
+    });
+    let amount_ui = amount.as_ref().map_or("max", |(_, ui)| ui.as_str());
+
+    let summary = risk.as_ref().map_or_else(
+        || {
+            format!(
+                "Kamino Multiply close on Solana: collateral={} debt={} amount={amount_ui} market={}",
+                legs.mint, legs.debt_mint, legs.market
+            )
+        },
+        |r| {
+            format!(
+                "Kamino Multiply open on Solana: collateral={} debt={} deposit={amount_ui} leverage={:.2}x projected_health_factor={} notional_usd={usd_value:.2} market={}",
+                legs.mint,
+                legs.debt_mint,
+                r.leverage,
+                r.health_factor
+                    .map_or_else(|| "unknown".to_owned(), |hf| format!("{hf:.3}")),
+                legs.market
+            )
+        },
+    );
+    let (op, history_type, endpoint) = if opening {
+        (WriteOp::Lend, "multiply_open", "multiply/open")
+    } else {
+        (WriteOp::WithdrawLending, "multiply_close", "multiply/close")
+    };
+    let outcome = match maybe_confirm_write(
+        shared,
+        conn,
+        stdin,
+        stdout,
+        &WriteConfirmRequest {
+            tool: tool_name,
+            wallet: Some(w.name.as_str()),
+            account_index: Some(idx),
+            op,
+            chain: "solana",
+            usd_value,
+            usd_value_known,
+            force_confirm: policy.require_user_confirm_for_remote_tx.get(),
+            slippage_bps: Some(slippage_bps),
+            to_address: None,
+            contract: Some("kamino"),
+            leverage: risk
+                .as_ref()
+                .and_then(|r| crate::financial_math::leverage_ceil(r.leverage)),
+            summary: &summary,
+        },
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(req_id, tool_err(te))),
+    };
+
+    let body = json!({
+      "wallet": owner.to_string(),
+      "market": legs.market,
+      "collateralReserve": legs.reserve,
+      "debtReserve": legs.debt_reserve,
+      "amount": amount.as_ref().map(|(_, ui)| ui.as_str()),
+      "closeAll": amount.is_none(),
+      "leverage": risk.as_ref().map(|r| r.leverage),
+      "slippageBps": slippage_bps,
+    });
+    let base_url = shared.cfg.http.kamino_api_base_url.trim().to_owned();
+    let allowed = multiply_allowed_program_ids()?;
+    let sig = match kamino_fetch_sign_broadcast(
+        shared, conn, stdin, stdout, w, idx, &sol, &base_url, endpoint, &body, &allowed,
+    )
+    .await
+    {
+        Ok(s) => s,
+        Err(e) => {
+            return Ok(ok(
+                req_id,
+                tool_err(ToolError::new("kamino_tx_failed", format!("{e:#}"))),
+            ));
+        }
+    };
+
+    let details = json!({
+      "chain": "solana", "protocol": "kamino", "position_type": "multiply",
+      "action": if opening { "open" } else { "close" },
+      "signature": sig.to_string(), "usd_value": usd_value, "equity_usd": equity_usd,
+      "market": legs.market, "reserve": legs.reserve, "mint": legs.mint,
+      "debt_reserve": legs.debt_reserve, "debt_mint": legs.debt_mint, "amount_ui": amount_ui,
+      "leverage": risk.as_ref().map(|r| r.leverage),
+      "projected_health_factor": risk.as_ref().and_then(|r| r.health_factor),
+      "liquidation_threshold": risk.as_ref().and_then(|r| r.liquidation_threshold),
+      "slippage_bps": slippage_bps
+    });
+    let mut history = details.clone();
+    if let Some(o) = history.as_object_mut() {
+        o.insert("ts".into(), Value::String(utc_now_iso()));
+        o.insert("day".into(), Value::String(Keystore::current_utc_day_key()));
+        o.insert("type".into(), Value::String(history_type.to_owned()));
+        o.insert("wallet".into(), Value::String(w.name.clone()));
+        o.insert("account_index".into(), json!(idx));
+        o.remove("action");
+    }
+    shared.ks.append_tx_history(&history, tx_note(args))?;
+    let _audit_log = shared.ks.append_audit_log(&json!({
+      "ts": utc_now_iso(), "tool": tool_name, "wallet": w.name,
+      "account_index": idx, "chain": "solana", "usd_value": usd_value,
+      "usd_value_known": usd_value_known, "policy_decision": outcome.policy_decision,
+      "confirm_required": outcome.confirm_required, "confirm_result": outcome.confirm_result,
+      "daily_used_usd": outcome.daily_used_usd, "forced_confirm": outcome.forced_confirm,
+      "txid": sig.to_string(), "error_code": null, "result": "broadcasted"
+    }));
+    Ok(ok(req_id, tool_ok(details)))
+}
+
 pub async fn handle<R, W>(
     req_id: Value,
     tool_name: &str,
@@ -497,12 +939,37 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(shared, &args)?;
     let (effective_policy, _) = shared.cfg.policy_for_wallet(Some(w.name.as_str()));
 
+    match parse_position_type(&args, tool_name) {
+        Ok(PositionType::Lend) => {}
+        Ok(PositionType::Multiply) => {
+            let resp = handle_multiply(
+                req_id,
+                tool_name,
+                &args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+                &w,
+                idx,
+                &effective_policy,
+            )
+            .await;
+            Keystore::release_lock(lock)?;
+            return resp;
+        }
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(req_id, tool_err(te)));
+        }
+    }
+
     let (token_s, reserve_s, amount_s, units, market) =
-        match validate_kamino_args(&args, &shared.cfg.http.kamino_default_lend_market) {
+        match validate_kamino_args(&args, &shared.cfg.http.kamino_default_lend_market, false) {
             Ok(v) => v,
             Err(te) => {
                 Keystore::release_lock(lock)?;
@@ -609,9 +1076,15 @@ where
     };
 
     let base_url = shared.cfg.http.kamino_api_base_url.trim().to_owned();
+    let body = json!({
+      "wallet": owner.to_string(),
+      "market": market,
+      "reserve": reserve,
+      "amount": amount_ui,
+    });
+    let allowed = default_allowed_program_ids()?;
     let sig = match kamino_fetch_sign_broadcast(
-        shared, conn, stdin, stdout, &w, idx, &sol, &owner, &base_url, endpoint, &market, &reserve,
-        &amount_ui,
+        shared, conn, stdin, stdout, &w, idx, &sol, &base_url, endpoint, &body, &allowed,
     )
     .await
     {
@@ -641,6 +1114,7 @@ where
         mint,
         &amount_ui,
         &outcome,
+        tx_note(&args),
     )?;
     Keystore::release_lock(lock)?;
     Ok(resp)
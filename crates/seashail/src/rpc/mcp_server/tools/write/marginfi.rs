@@ -19,7 +19,9 @@ use crate::{
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
-use super::super::helpers::{is_native_token, resolve_wallet_and_account, sol_pubkey_for_account};
+use super::super::helpers::{
+    is_native_token, resolve_wallet_and_account, sol_pubkey_for_account, tx_note,
+};
 use super::super::helpers::{solana_fallback_urls, u128_to_u64};
 use super::super::key_loading::load_solana_keypair;
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
@@ -161,6 +163,7 @@ struct ParsedMarginfi {
     history_type: &'static str,
     disc: [u8; 8],
     needs_vault_auth: bool,
+    note: Option<String>,
 }
 
 /// Map tool name to (`WriteOp`, `history_type`, instruction discriminator, `needs_vault_auth`).
@@ -228,7 +231,7 @@ fn marginfi_validate_args(
     Ok((group_s, group_pk, mint_s.to_owned(), mint_pk))
 }
 
-fn parse_marginfi_args<R, W>(
+async fn parse_marginfi_args<R, W>(
     tool_name: &str,
     ctx: &HandlerCtx<'_, R, W>,
 ) -> eyre::Result<Result<ParsedMarginfi, JsonRpcResponse>>
@@ -242,7 +245,7 @@ where
             Err(te) => return Ok(Err(ok(ctx.req_id.clone(), tool_err(te)))),
         };
 
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
 
     let Some((op, history_type, disc, needs_vault_auth)) = marginfi_op_for_tool(tool_name) else {
@@ -268,6 +271,7 @@ where
         history_type,
         disc,
         needs_vault_auth,
+        note: tx_note(&ctx.args).map(str::to_owned),
     }))
 }
 
@@ -571,19 +575,22 @@ async fn marginfi_init_account(
         .sign_and_send_instructions_multi(kp, &[marginfi_kp], vec![ix])
         .await
         .context("send marginfi init")?;
-    ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": "marginfi_init",
-      "chain": "solana",
-      "wallet": pm.w.name,
-      "account_index": pm.idx,
-      "usd_value": 0.0_f64,
-      "signature": sig.to_string(),
-      "protocol": "marginfi",
-      "group": pm.group_s,
-      "marginfi_account": marginfi_account.to_string(),
-    }))?;
+    ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "marginfi_init",
+          "chain": "solana",
+          "wallet": pm.w.name,
+          "account_index": pm.idx,
+          "usd_value": 0.0_f64,
+          "signature": sig.to_string(),
+          "protocol": "marginfi",
+          "group": pm.group_s,
+          "marginfi_account": marginfi_account.to_string(),
+        }),
+        pm.note.as_deref(),
+    )?;
     Ok(())
 }
 
@@ -701,22 +708,25 @@ where
     R: tokio::io::AsyncRead + Unpin + Send,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": pm.history_type,
-      "chain": "solana",
-      "wallet": pm.w.name,
-      "account_index": pm.idx,
-      "usd_value": pm.usd_value,
-      "signature": sig.to_string(),
-      "protocol": "marginfi",
-      "group": pm.group_s,
-      "marginfi_account": marginfi_account.to_string(),
-      "bank": bank_pk.to_string(),
-      "mint": pm.mint_s,
-      "amount_base": pm.base_u64.to_string()
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": pm.history_type,
+          "chain": "solana",
+          "wallet": pm.w.name,
+          "account_index": pm.idx,
+          "usd_value": pm.usd_value,
+          "signature": sig.to_string(),
+          "protocol": "marginfi",
+          "group": pm.group_s,
+          "marginfi_account": marginfi_account.to_string(),
+          "bank": bank_pk.to_string(),
+          "mint": pm.mint_s,
+          "amount_base": pm.base_u64.to_string()
+        }),
+        pm.note.as_deref(),
+    )?;
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(),
       "tool": tool_name,
@@ -760,7 +770,7 @@ where
     R: tokio::io::AsyncRead + Unpin + Send,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let mut pm = match parse_marginfi_args(tool_name, ctx)? {
+    let mut pm = match parse_marginfi_args(tool_name, ctx).await? {
         Ok(v) => v,
         Err(resp) => return Ok(resp),
     };
@@ -1,25 +1,38 @@
 mod aave;
+mod bridge_quote;
+mod close_all_positions;
 mod common;
 mod compound;
 mod defi_tx_envelope;
+mod evm_exec;
 mod fund_wallets;
 mod kamino;
 mod marginfi;
 mod polymarket;
 mod pumpfun;
+mod rebalance;
 mod request_airdrop;
 mod send_transaction;
+mod sign_message;
+mod solana_nonce;
+mod spot_limit;
 mod staking;
 mod swap_tokens;
 mod transfer_between_wallets;
 mod wormhole;
+mod wormhole_attest;
 mod wormhole_solana;
+mod wrap_native;
 
 use serde_json::Value;
 use tokio::io::BufReader;
 
-use super::super::jsonrpc::{err, JsonRpcResponse};
+use super::super::jsonrpc::{err, ok, tool_err, JsonRpcResponse};
 use super::super::{ConnState, SharedState};
+use super::helpers::resolve_wallet_and_account;
+use crate::{errors::SeashailError, wallet::WalletKind};
+
+pub(super) use bridge_quote::{quote_bridge_routes, RouteQuoteRequest};
 
 pub struct HandlerCtx<'a, R, W> {
     pub req_id: Value,
@@ -197,6 +210,18 @@ where
     R: tokio::io::AsyncRead + Unpin + Send,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
+    // Fail before any quote or confirmation prompt; key loading refuses watch wallets as well.
+    if tool_name != "request_airdrop" {
+        if let Ok((w, _)) = resolve_wallet_and_account(shared, &args) {
+            if w.kind == WalletKind::Watch {
+                return Ok(ok(
+                    req_id,
+                    tool_err(SeashailError::WatchOnlyWallet(w.name).into()),
+                ));
+            }
+        }
+    }
+
     match tool_name {
         "request_airdrop" => request_airdrop::handle(req_id, args, shared, conn).await,
         "send_transaction" => {
@@ -210,6 +235,17 @@ where
             };
             send_transaction::handle_ctx(&mut ctx).await
         }
+        "sign_message" => {
+            let mut ctx = HandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            sign_message::handle(&mut ctx).await
+        }
         "swap_tokens" => {
             let mut ctx = HandlerCtx {
                 req_id,
@@ -221,6 +257,39 @@ where
             };
             swap_tokens::handle_ctx(&mut ctx).await
         }
+        "wrap_native" | "unwrap_native" => {
+            let mut ctx = HandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            wrap_native::handle(tool_name, &mut ctx).await
+        }
+        "rebalance_portfolio" => {
+            let mut ctx = HandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            rebalance::handle(&mut ctx).await
+        }
+        "close_all_positions" => {
+            let mut ctx = HandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            close_all_positions::handle(&mut ctx).await
+        }
         "transfer_between_wallets" => {
             let mut ctx = HandlerCtx {
                 req_id,
@@ -254,6 +323,17 @@ where
             };
             pumpfun::handle(tool_name, &mut ctx).await
         }
+        "place_spot_limit_order" | "cancel_spot_limit_order" => {
+            let mut ctx = HandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            spot_limit::handle(tool_name, &mut ctx).await
+        }
         "place_prediction" | "close_prediction" => {
             let mut ctx = HandlerCtx {
                 req_id,
@@ -265,7 +345,29 @@ where
             };
             polymarket::handle(tool_name, &mut ctx).await
         }
+        "create_nonce_account" | "close_nonce_account" | "submit_signed_transaction" => {
+            let mut ctx = HandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            solana_nonce::handle(tool_name, &mut ctx).await
+        }
         "bridge_tokens" => route_bridge(req_id, tool_name, args, shared, conn, stdin, stdout).await,
+        "attest_token" => {
+            let mut ctx = HandlerCtx {
+                req_id,
+                args,
+                shared,
+                conn,
+                stdin,
+                stdout,
+            };
+            wormhole_attest::handle(&mut ctx).await
+        }
         "lend_tokens" | "withdraw_lending" | "borrow_tokens" | "repay_borrow" => {
             route_lending(req_id, tool_name, args, shared, conn, stdin, stdout).await
         }
@@ -8,7 +8,7 @@ use polymarket_client_sdk::clob::types::response::GeoblockResponse;
 use rust_decimal::prelude::ToPrimitive as _;
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
-use super::super::helpers::resolve_wallet_and_account;
+use super::super::helpers::{resolve_wallet_and_account, tx_note};
 use super::super::key_loading::load_evm_signer;
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::super::value_helpers::parse_usd_value;
@@ -415,13 +415,16 @@ where
             Err(resp) => return Ok(resp),
         };
 
-    let _history = ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "type": "prediction_place", "wallet": pc.w.name,
-      "account_index": pc.idx, "chain": pc.chain, "protocol": "polymarket",
-      "token_id": parsed.token_id.to_string(), "order_id": resp.order_id,
-      "status": format!("{:?}", resp.status), "success": resp.success,
-      "usd_value": parsed.usd_value, "usd_value_known": parsed.usd_value_known,
-    }));
+    let _history = ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "type": "prediction_place", "wallet": pc.w.name,
+          "account_index": pc.idx, "chain": pc.chain, "protocol": "polymarket",
+          "token_id": parsed.token_id.to_string(), "order_id": resp.order_id,
+          "status": format!("{:?}", resp.status), "success": resp.success,
+          "usd_value": parsed.usd_value, "usd_value_known": parsed.usd_value_known,
+        }),
+        tx_note(&ctx.args),
+    );
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": "place_prediction", "wallet": pc.w.name,
       "account_index": pc.idx, "chain": pc.chain, "protocol": "polymarket",
@@ -774,12 +777,15 @@ where
             ));
         }
     };
-    let _history = ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "type": "prediction_close", "wallet": pc.w.name,
-      "account_index": pc.idx, "chain": pc.chain, "protocol": "polymarket",
-      "order_id": order_id, "usd_value": usd_value, "usd_value_known": usd_value_known,
-      "canceled": cancel.canceled,
-    }));
+    let _history = ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "type": "prediction_close", "wallet": pc.w.name,
+          "account_index": pc.idx, "chain": pc.chain, "protocol": "polymarket",
+          "order_id": order_id, "usd_value": usd_value, "usd_value_known": usd_value_known,
+          "canceled": cancel.canceled,
+        }),
+        tx_note(&ctx.args),
+    );
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": "close_prediction", "wallet": pc.w.name,
       "account_index": pc.idx, "chain": pc.chain, "protocol": "polymarket",
@@ -887,7 +893,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let pc = match parse_polymarket_common(ctx, lock)? {
         Ok(v) => v,
         Err(resp) => return Ok(resp),
@@ -11,7 +11,7 @@ use serde_json::{json, Value};
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
-use super::super::helpers::{resolve_wallet_and_account, solana_fallback_urls};
+use super::super::helpers::{resolve_wallet_and_account, solana_fallback_urls, tx_note};
 use super::super::key_loading::load_solana_keypair;
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::HandlerCtx;
@@ -112,6 +112,8 @@ fn pumpfun_op_str(op: WriteOp) -> Result<&'static str, ToolError> {
         | WriteOp::ClosePerpPosition
         | WriteOp::ModifyPerpOrder
         | WriteOp::PlaceLimitOrder
+        | WriteOp::PlaceSpotLimitOrder
+        | WriteOp::CancelSpotLimitOrder
         | WriteOp::BuyNft
         | WriteOp::SellNft
         | WriteOp::TransferNft
@@ -144,6 +146,8 @@ const fn pumpfun_history_type(op: WriteOp) -> &'static str {
         | WriteOp::ClosePerpPosition
         | WriteOp::ModifyPerpOrder
         | WriteOp::PlaceLimitOrder
+        | WriteOp::PlaceSpotLimitOrder
+        | WriteOp::CancelSpotLimitOrder
         | WriteOp::BuyNft
         | WriteOp::SellNft
         | WriteOp::TransferNft
@@ -257,6 +261,7 @@ struct PumpfunResult<'a> {
     usd_value_known: bool,
     sig: &'a solana_sdk::signature::Signature,
     outcome: &'a super::super::policy_confirm::WriteConfirmOutcome,
+    note: Option<&'a str>,
 }
 
 /// Record transaction history, audit log, release lock, and build response.
@@ -267,15 +272,18 @@ fn pumpfun_record_and_respond(
     req_id: &Value,
 ) -> eyre::Result<JsonRpcResponse> {
     let ty = pumpfun_history_type(r.op);
-    shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": ty, "chain": "solana", "wallet": r.w.name,
-      "account_index": r.idx, "mint": r.mint,
-      "amount_sol": (r.op == WriteOp::PumpfunBuy).then_some(r.amount_sol),
-      "percent": (r.op == WriteOp::PumpfunSell).then_some(r.percent),
-      "usd_value": r.usd_value, "signature": r.sig.to_string()
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": ty, "chain": "solana", "wallet": r.w.name,
+          "account_index": r.idx, "mint": r.mint,
+          "amount_sol": (r.op == WriteOp::PumpfunBuy).then_some(r.amount_sol),
+          "percent": (r.op == WriteOp::PumpfunSell).then_some(r.percent),
+          "usd_value": r.usd_value, "signature": r.sig.to_string()
+        }),
+        r.note,
+    )?;
     let _audit_log = shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": r.tool_name, "wallet": r.w.name,
       "account_index": r.idx, "chain": "solana",
@@ -394,7 +402,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let v = match pumpfun_validate(tool_name, ctx, &lock)? {
         Ok(v) => v,
         Err(resp) => return Ok(resp),
@@ -470,6 +478,7 @@ where
             usd_value_known,
             sig: &sig,
             outcome: &outcome,
+            note: tx_note(&ctx.args),
         },
         &ctx.req_id,
     )
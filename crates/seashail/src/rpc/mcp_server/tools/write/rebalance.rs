@@ -0,0 +1,697 @@
+use crate::{
+    amount,
+    chains::{evm::EvmChain, native::native_token, solana::SolanaChain},
+    errors::ToolError,
+    financial_math,
+    keystore::Keystore,
+    policy_engine::{self, PolicyContext, WriteOp},
+    price, rebalance,
+};
+use alloy::primitives::U256;
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::state::effective_network_mode;
+use super::super::super::SharedState;
+use super::super::helpers::{
+    is_native_token, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+    u128_to_u64, u256_pow10,
+};
+use super::super::policy_confirm::{confirm_plan, WriteConfirmRequest};
+use super::super::value_helpers::tool_response_payload;
+use super::evm_exec;
+use super::swap_tokens::{self, setup_evm_chain};
+use super::HandlerCtx;
+
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+// Native balance kept back for fees; never counted as rebalanceable.
+const SOLANA_NATIVE_RESERVE_LAMPORTS: u64 = 10_000_000;
+const EVM_NATIVE_RESERVE_WEI: u128 = 1_000_000_000_000_000;
+// Preview-only gas budget for a router swap (actual gas is estimated at execution).
+const EVM_SWAP_GAS_ESTIMATE: u128 = 250_000;
+const SOLANA_SIGNATURE_FEE_LAMPORTS: u64 = 5_000;
+const MAX_TARGETS: usize = 20;
+const MAX_SWAPS_CAP: u64 = 20;
+
+struct Target {
+    token: String,
+    bps: u32,
+}
+
+/// Spendable balance and USD valuation for one target token.
+struct Position {
+    token: String,
+    amount_base: u128,
+    decimals: u8,
+    usd_value: f64,
+}
+
+struct RebalanceArgs {
+    chain: String,
+    targets: Vec<Target>,
+    tolerance_bps: u32,
+    max_swaps: usize,
+    slippage_bps: u32,
+    provider: String,
+    dry_run: bool,
+}
+
+fn canonical_token(s: &str) -> String {
+    let t = s.trim();
+    if is_native_token(t) {
+        "native".to_owned()
+    } else {
+        t.to_owned()
+    }
+}
+
+fn parse_targets(args: &Value) -> Result<Vec<Target>, ToolError> {
+    let Some(arr) = args.get("targets").and_then(Value::as_array) else {
+        return Err(ToolError::new("invalid_request", "missing targets"));
+    };
+    if arr.is_empty() || arr.len() > MAX_TARGETS {
+        return Err(ToolError::new(
+            "invalid_request",
+            format!("targets must contain 1..={MAX_TARGETS} entries"),
+        ));
+    }
+    let mut out: Vec<Target> = Vec::with_capacity(arr.len());
+    let mut sum_bps = 0_u32;
+    for t in arr {
+        let token = canonical_token(t.get("token").and_then(Value::as_str).unwrap_or(""));
+        // Weights are percentages with at most two decimals; parse exactly into basis points.
+        let weight_s = match t.get("weight") {
+            Some(Value::String(s)) => s.trim().to_owned(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => String::new(),
+        };
+        let bps = amount::parse_amount_ui_to_base_u128(&weight_s, 2)
+            .ok()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v <= 10_000)
+            .ok_or_else(|| {
+                ToolError::new(
+                    "invalid_request",
+                    format!(
+                        "invalid weight for {token}: expected a percentage (0-100, max 2 decimals)"
+                    ),
+                )
+            })?;
+        if out.iter().any(|x| x.token == token) {
+            return Err(ToolError::new(
+                "invalid_request",
+                format!("duplicate target token: {token}"),
+            ));
+        }
+        sum_bps = sum_bps.saturating_add(bps);
+        out.push(Target { token, bps });
+    }
+    if sum_bps != 10_000 {
+        return Err(ToolError::new(
+            "invalid_request",
+            "target weights must sum to 100",
+        ));
+    }
+    Ok(out)
+}
+
+fn parse_args(args: &Value) -> Result<RebalanceArgs, ToolError> {
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim()
+        .to_owned();
+    if chain.is_empty() {
+        return Err(ToolError::new("invalid_request", "missing chain"));
+    }
+    let targets = parse_targets(args)?;
+    let tolerance_bps = args
+        .get("tolerance_bps")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(100)
+        .min(5_000);
+    let max_swaps = args
+        .get("max_swaps")
+        .and_then(Value::as_u64)
+        .unwrap_or(5)
+        .clamp(1, MAX_SWAPS_CAP);
+    let slippage_bps = args
+        .get("slippage_bps")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(100);
+    let provider = args
+        .get("provider")
+        .and_then(Value::as_str)
+        .unwrap_or("auto")
+        .to_owned();
+    let dry_run = args
+        .get("dry_run")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    Ok(RebalanceArgs {
+        chain,
+        targets,
+        tolerance_bps,
+        max_swaps: usize::try_from(max_swaps).unwrap_or(5),
+        slippage_bps,
+        provider,
+        dry_run,
+    })
+}
+
+async fn solana_positions(
+    shared: &mut SharedState,
+    sol: &SolanaChain,
+    owner: solana_sdk::pubkey::Pubkey,
+    targets: &[Target],
+) -> eyre::Result<Vec<Position>> {
+    shared.ensure_db().await;
+    let db = shared.db();
+    let mut out = Vec::with_capacity(targets.len());
+    for t in targets {
+        let (amount_base, decimals, usd_value) = if t.token == "native" {
+            let lamports = sol
+                .get_sol_balance(owner)
+                .await?
+                .saturating_sub(SOLANA_NATIVE_RESERVE_LAMPORTS);
+            let px = price::native_token_price_usd_cached("solana", &shared.cfg, db)
+                .await?
+                .usd;
+            (
+                u128::from(lamports),
+                9_u8,
+                financial_math::lamports_to_usd(lamports, px),
+            )
+        } else {
+            let mint = SolanaChain::parse_pubkey(&t.token)?;
+            // A missing token account just means a zero balance.
+            let (bal, decimals) = match sol.get_spl_balance(owner, mint).await {
+                Ok(v) => v,
+                Err(_e) => (0, sol.get_mint_decimals(mint).await?),
+            };
+            let usd_value = if bal == 0 {
+                0.0_f64
+            } else if t.token == USDC_MINT {
+                financial_math::token_base_to_usd(u128::from(bal), decimals, 1.0_f64)
+            } else {
+                let one = 10_u64.checked_pow(u32::from(decimals)).unwrap_or(1);
+                let px = price::solana_token_price_usd_cached(
+                    sol,
+                    &shared.cfg,
+                    &t.token,
+                    USDC_MINT,
+                    one.max(1),
+                    50,
+                    db,
+                )
+                .await?
+                .usd;
+                financial_math::token_base_to_usd(u128::from(bal), decimals, px)
+            };
+            (u128::from(bal), decimals, usd_value)
+        };
+        out.push(Position {
+            token: t.token.clone(),
+            amount_base,
+            decimals,
+            usd_value,
+        });
+    }
+    Ok(out)
+}
+
+async fn evm_positions(
+    shared: &mut SharedState,
+    evm: &EvmChain,
+    chain: &str,
+    owner: alloy::primitives::Address,
+    targets: &[Target],
+) -> eyre::Result<Vec<Position>> {
+    shared.ensure_db().await;
+    let db = shared.db();
+    let usdc = evm.uniswap.as_ref().map(|u| u.usdc);
+    let mut out = Vec::with_capacity(targets.len());
+    for t in targets {
+        let (amount_base, decimals, usd_value) = if t.token == "native" {
+            let wei = crate::chains::evm::u256_low_u128(evm.get_native_balance(owner).await?)
+                .saturating_sub(EVM_NATIVE_RESERVE_WEI);
+            let px = price::native_token_price_usd_cached(chain, &shared.cfg, db)
+                .await?
+                .usd;
+            let decimals = native_token(chain).decimals;
+            (
+                wei,
+                decimals,
+                financial_math::token_base_to_usd(wei, decimals, px),
+            )
+        } else {
+            let tok = EvmChain::parse_address(&t.token)?;
+            let (bal, decimals, _symbol) = evm.get_erc20_balance(tok, owner).await?;
+            let bal = crate::chains::evm::u256_low_u128(bal);
+            let usd_value = if bal == 0 {
+                0.0_f64
+            } else if usdc.is_some_and(|u| u == tok) {
+                financial_math::token_base_to_usd(bal, decimals, 1.0_f64)
+            } else {
+                let one = u256_pow10(u32::from(decimals)).max(U256::from(1_u64));
+                let px = price::evm_token_price_usd_cached(evm, &shared.cfg, tok, one, 50, db)
+                    .await?
+                    .usd;
+                financial_math::token_base_to_usd(bal, decimals, px)
+            };
+            (bal, decimals, usd_value)
+        };
+        out.push(Position {
+            token: t.token.clone(),
+            amount_base,
+            decimals,
+            usd_value,
+        });
+    }
+    Ok(out)
+}
+
+/// A planned swap resolved to a concrete input amount.
+struct SwapLeg {
+    token_in: String,
+    token_out: String,
+    amount_in_base: u128,
+    usd_value: f64,
+}
+
+fn resolve_legs(plan: &rebalance::Plan, positions: &[Position]) -> Vec<SwapLeg> {
+    plan.swaps
+        .iter()
+        .filter_map(|s| {
+            let p = positions.iter().find(|p| p.token == s.token_in)?;
+            let amount_in_base =
+                financial_math::scale_base_by_usd(p.amount_base, s.usd_value, p.usd_value);
+            (amount_in_base > 0).then(|| SwapLeg {
+                token_in: s.token_in.clone(),
+                token_out: s.token_out.clone(),
+                amount_in_base,
+                usd_value: s.usd_value,
+            })
+        })
+        .collect()
+}
+
+/// Best-effort Jupiter quote for a preview leg: `(expected_out_base, price_impact_usd)`.
+async fn solana_leg_quote(
+    sol: &SolanaChain,
+    leg: &SwapLeg,
+    slippage_bps: u32,
+) -> Option<(String, f64)> {
+    let mint = |t: &str| {
+        if t == "native" {
+            WSOL_MINT.to_owned()
+        } else {
+            t.to_owned()
+        }
+    };
+    let amt = u128_to_u64(leg.amount_in_base).ok()?;
+    let q = sol
+        .jupiter_quote(
+            &mint(&leg.token_in),
+            &mint(&leg.token_out),
+            amt,
+            slippage_bps,
+        )
+        .await
+        .ok()?;
+    let out = q.get("outAmount").and_then(Value::as_str)?.to_owned();
+    let impact = q
+        .get("priceImpactPct")
+        .and_then(Value::as_str)
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+        .unwrap_or(0.0_f64);
+    Some((
+        out,
+        financial_math::mul_f64(leg.usd_value, financial_math::abs_f64(impact)),
+    ))
+}
+
+/// Per-swap network fee estimate in USD (preview only).
+async fn estimate_fee_usd_per_swap(
+    shared: &mut SharedState,
+    chain: &str,
+    evm: Option<&EvmChain>,
+) -> Option<f64> {
+    shared.ensure_db().await;
+    let db = shared.db();
+    let px = price::native_token_price_usd_cached(chain, &shared.cfg, db)
+        .await
+        .ok()?
+        .usd;
+    if chain == "solana" {
+        let cu_limit = u64::from(
+            shared
+                .cfg
+                .rpc
+                .solana_default_compute_unit_limit
+                .unwrap_or(200_000),
+        );
+        let cu_price = shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports
+            .unwrap_or(0);
+        let priority = cu_limit.saturating_mul(cu_price) / 1_000_000;
+        let lamports = SOLANA_SIGNATURE_FEE_LAMPORTS.saturating_add(priority);
+        return Some(financial_math::lamports_to_usd(lamports, px));
+    }
+    let gas_price = evm?.get_gas_price().await.ok()?;
+    let wei = gas_price.saturating_mul(EVM_SWAP_GAS_ESTIMATE);
+    Some(financial_math::token_base_to_usd(
+        wei,
+        native_token(chain).decimals,
+        px,
+    ))
+}
+
+fn drifts_json(plan: &rebalance::Plan, targets: &[Target], positions: &[Position]) -> Vec<Value> {
+    plan.drifts
+        .iter()
+        .map(|d| {
+            let target_bps = targets
+                .iter()
+                .find(|t| t.token == d.token)
+                .map_or(0, |t| t.bps);
+            let p = positions.iter().find(|p| p.token == d.token);
+            let weight_pct = if plan.total_usd > 0.0_f64 {
+                financial_math::div_f64(
+                    financial_math::mul_f64(d.usd_value, 100.0_f64),
+                    plan.total_usd,
+                )
+            } else {
+                0.0_f64
+            };
+            json!({
+              "token": d.token,
+              "amount_base": p.map(|p| p.amount_base.to_string()),
+              "decimals": p.map(|p| p.decimals),
+              "usd_value": d.usd_value,
+              "current_weight_pct": weight_pct,
+              "target_weight_pct": financial_math::div_f64(f64::from(target_bps), 100.0_f64),
+              "target_usd": d.target_usd,
+              "delta_usd": d.delta_usd,
+              "within_tolerance": d.within_tolerance
+            })
+        })
+        .collect()
+}
+
+/// Run every leg through policy before anything executes, counting each swap's value once.
+/// Returns whether any leg would need the user's confirmation.
+fn preflight_policy<R, W>(
+    ctx: &HandlerCtx<'_, R, W>,
+    w: &crate::wallet::WalletRecord,
+    chain: &str,
+    a: &RebalanceArgs,
+    contract: &str,
+    legs: &[SwapLeg],
+) -> Result<bool, ToolError> {
+    let day = Keystore::current_utc_day_key();
+    let mut used = ctx
+        .shared
+        .ks
+        .daily_used_usd_filtered(&day, Some(w.name.as_str()))
+        .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
+    let (policy, _) = ctx.shared.cfg.policy_for_wallet(Some(w.name.as_str()));
+    // Jupiter and 1inch build the transaction remotely; `swap_tokens` always confirms those when
+    // `require_user_confirm_for_remote_tx` is set.
+    let mut needs_confirm =
+        contract != "uniswap" && policy.require_user_confirm_for_remote_tx.get();
+    for leg in legs {
+        let approval = policy_engine::evaluate(
+            &policy,
+            &PolicyContext {
+                op: WriteOp::Swap,
+                chain,
+                account_index: None,
+                usd_value: leg.usd_value,
+                usd_value_known: true,
+                daily_used_usd: used,
+                slippage_bps: Some(a.slippage_bps),
+                to_address: None,
+                contract: Some(contract),
+                leverage: None,
+            },
+        )
+        .map_err(|te| {
+            ToolError::new(
+                te.code,
+                format!(
+                    "rebalance blocked before execution ({} -> {}): {}",
+                    leg.token_in, leg.token_out, te.message
+                ),
+            )
+        })?;
+        needs_confirm |= approval != policy_engine::Approval::AutoApprove;
+        financial_math::accum(&mut used, leg.usd_value);
+    }
+    Ok(needs_confirm)
+}
+
+fn plan_summary(chain: &str, provider: &str, legs: &[SwapLeg]) -> String {
+    let lines: Vec<String> = legs
+        .iter()
+        .map(|l| {
+            format!(
+                "- swap {} -> {} (~{:.2} USD)",
+                l.token_in, l.token_out, l.usd_value
+            )
+        })
+        .collect();
+    format!(
+        "REBALANCE on {chain} via {provider}: {} swaps, run in order\n{}",
+        legs.len(),
+        lines.join("\n")
+    )
+}
+
+pub async fn handle<R, W>(ctx: &mut HandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let a = match parse_args(&ctx.args) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+    let chain = a.chain.as_str();
+    let provider = match (a.provider.as_str(), chain == "solana") {
+        ("auto", true) => "jupiter",
+        ("auto", false) => "uniswap",
+        (p, _) => p,
+    };
+
+    let (positions, sol, evm) = if chain == "solana" {
+        let mode = effective_network_mode(ctx.shared, ctx.conn);
+        let sol = SolanaChain::new_with_fallbacks(
+            &ctx.shared.cfg.rpc.solana_rpc_url,
+            solana_fallback_urls(ctx.shared, mode),
+            &ctx.shared.cfg.http.jupiter_base_url,
+            ctx.shared.cfg.http.jupiter_api_key.as_deref(),
+            ctx.shared.cfg.rpc.solana_default_compute_unit_limit,
+            ctx.shared
+                .cfg
+                .rpc
+                .solana_default_compute_unit_price_micro_lamports,
+        );
+        let owner = sol_pubkey_for_account(&w, idx)?;
+        let positions = solana_positions(ctx.shared, &sol, owner, &a.targets).await?;
+        (positions, Some(sol), None)
+    } else {
+        let evm = setup_evm_chain(ctx.shared, chain)?;
+        // Read balances of the address the swaps execute from (the smart account, if selected).
+        let owner = match evm_exec::resolve(ctx.shared, &ctx.args, &w, idx, chain) {
+            Ok(exec) => exec.from_address(),
+            Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+        };
+        let positions = evm_positions(ctx.shared, &evm, chain, owner, &a.targets).await?;
+        (positions, None, Some(evm))
+    };
+
+    let holdings: Vec<rebalance::Holding> = positions
+        .iter()
+        .zip(&a.targets)
+        .map(|(p, t)| rebalance::Holding {
+            token: p.token.clone(),
+            usd_value: p.usd_value,
+            target_bps: t.bps,
+        })
+        .collect();
+    let plan = rebalance::plan(&holdings, a.tolerance_bps, a.max_swaps);
+    let legs = resolve_legs(&plan, &positions);
+
+    let fee_each = estimate_fee_usd_per_swap(ctx.shared, chain, evm.as_ref()).await;
+    let mut preview = Vec::with_capacity(legs.len());
+    let mut total_moved = 0.0_f64;
+    let mut total_max_slippage = 0.0_f64;
+    let mut total_impact: Option<f64> = sol.as_ref().map(|_| 0.0_f64);
+    for leg in &legs {
+        let max_slippage_usd = financial_math::mul_f64(
+            leg.usd_value,
+            financial_math::bps_to_fraction(a.slippage_bps),
+        );
+        financial_math::accum(&mut total_moved, leg.usd_value);
+        financial_math::accum(&mut total_max_slippage, max_slippage_usd);
+        let quote = match sol.as_ref() {
+            Some(s) => solana_leg_quote(s, leg, a.slippage_bps).await,
+            None => None,
+        };
+        match quote.as_ref() {
+            Some((_, impact)) => {
+                if let Some(t) = total_impact.as_mut() {
+                    financial_math::accum(t, *impact);
+                }
+            }
+            None => total_impact = None,
+        }
+        preview.push(json!({
+          "token_in": leg.token_in,
+          "token_out": leg.token_out,
+          "amount_in_base": leg.amount_in_base.to_string(),
+          "usd_value": leg.usd_value,
+          "max_slippage_usd": max_slippage_usd,
+          "expected_out_base": quote.as_ref().map(|q| q.0.clone()),
+          "price_impact_usd": quote.as_ref().map(|q| q.1)
+        }));
+    }
+    let n_legs = f64::from(u32::try_from(legs.len()).unwrap_or(u32::MAX));
+    let plan_json = json!({
+      "chain": chain,
+      "wallet": w.name,
+      "account_index": idx,
+      "provider": provider,
+      "total_usd": plan.total_usd,
+      "tolerance_bps": a.tolerance_bps,
+      "max_swaps": a.max_swaps,
+      "slippage_bps": a.slippage_bps,
+      "holdings": drifts_json(&plan, &a.targets, &positions),
+      "swaps": preview,
+      "truncated": plan.truncated,
+      "totals": {
+        "usd_moved": total_moved,
+        "max_slippage_usd": total_max_slippage,
+        "expected_price_impact_usd": total_impact,
+        "estimated_network_fees_usd": fee_each.map(|f| financial_math::mul_f64(f, n_legs))
+      }
+    });
+
+    if a.dry_run || legs.is_empty() {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_ok(json!({ "dry_run": a.dry_run, "plan": plan_json, "results": [] })),
+        ));
+    }
+
+    let needs_confirm = match preflight_policy(ctx, &w, chain, &a, provider, &legs) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+
+    // One prompt for the whole plan, with the total value moved, before the first leg runs. The
+    // legs then skip their own prompts, so a decline can never leave the rebalance half-applied.
+    if needs_confirm {
+        let summary = plan_summary(chain, provider, &legs);
+        if let Err(te) = confirm_plan(
+            ctx.shared,
+            ctx.conn,
+            ctx.stdin,
+            ctx.stdout,
+            &WriteConfirmRequest {
+                tool: "rebalance_portfolio",
+                wallet: Some(w.name.as_str()),
+                account_index: Some(idx),
+                op: WriteOp::Swap,
+                chain,
+                usd_value: total_moved,
+                usd_value_known: true,
+                force_confirm: false,
+                slippage_bps: Some(a.slippage_bps),
+                to_address: None,
+                contract: Some(provider),
+                leverage: None,
+                summary: &summary,
+            },
+        )
+        .await
+        {
+            return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+        }
+    }
+
+    // Each leg goes through `swap_tokens`, which applies per-swap policy and records history, so
+    // every swap's value is counted exactly once.
+    let mut results = Vec::with_capacity(legs.len());
+    let mut completed = true;
+    for leg in &legs {
+        if !completed {
+            results.push(json!({
+              "token_in": leg.token_in, "token_out": leg.token_out, "ok": false, "skipped": true
+            }));
+            continue;
+        }
+        let mut sub = HandlerCtx {
+            req_id: ctx.req_id.clone(),
+            args: json!({
+              "wallet": w.name,
+              "account_index": idx,
+              "chain": chain,
+              "token_in": leg.token_in,
+              "token_out": leg.token_out,
+              "amount_in": leg.amount_in_base.to_string(),
+              "amount_units": "base",
+              "slippage_bps": a.slippage_bps,
+              "provider": provider,
+              "execution": ctx.args.get("execution").and_then(Value::as_str).unwrap_or("eoa")
+            }),
+            shared: &mut *ctx.shared,
+            conn: &mut *ctx.conn,
+            stdin: &mut *ctx.stdin,
+            stdout: &mut *ctx.stdout,
+        };
+        let resp = if needs_confirm {
+            swap_tokens::handle_plan_step(&mut sub).await
+        } else {
+            swap_tokens::handle_ctx(&mut sub).await
+        };
+        let (is_error, payload) = match resp {
+            Ok(resp) => tool_response_payload(&resp),
+            Err(e) => (
+                true,
+                json!({ "code": "swap_failed", "message": format!("{e:#}") }),
+            ),
+        };
+        if is_error {
+            completed = false;
+        }
+        results.push(json!({
+          "token_in": leg.token_in,
+          "token_out": leg.token_out,
+          "amount_in_base": leg.amount_in_base.to_string(),
+          "usd_value": leg.usd_value,
+          "ok": !is_error,
+          "result": if is_error { Value::Null } else { payload.clone() },
+          "error": if is_error { payload } else { Value::Null }
+        }));
+    }
+
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "dry_run": false,
+          "completed": completed,
+          "plan": plan_json,
+          "results": results
+        })),
+    ))
+}
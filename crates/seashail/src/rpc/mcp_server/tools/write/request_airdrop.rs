@@ -10,7 +10,8 @@ use serde_json::{json, Value};
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::helpers::{
-    resolve_wallet_and_account, solana_airdrop_is_allowed, solana_fallback_urls, u128_to_u64,
+    resolve_wallet_and_account, solana_airdrop_is_allowed, solana_fallback_urls, tx_note,
+    u128_to_u64,
 };
 
 pub async fn handle(
@@ -19,7 +20,7 @@ pub async fn handle(
     shared: &SharedState,
     conn: &ConnState,
 ) -> eyre::Result<JsonRpcResponse> {
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(shared, &args)?;
     let chain = args
         .get("chain")
@@ -95,17 +96,20 @@ pub async fn handle(
 
     let sig = sol.request_airdrop(to_pk, lamports).await?;
 
-    shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": "airdrop",
-      "chain": "solana",
-      "wallet": w.name,
-      "account_index": idx,
-      "to": address,
-      "amount_base": lamports.to_string(),
-      "signature": sig.to_string()
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "airdrop",
+          "chain": "solana",
+          "wallet": w.name,
+          "account_index": idx,
+          "to": address,
+          "amount_base": lamports.to_string(),
+          "signature": sig.to_string()
+        }),
+        tx_note(&args),
+    )?;
 
     Keystore::release_lock(lock)?;
     Ok(ok(
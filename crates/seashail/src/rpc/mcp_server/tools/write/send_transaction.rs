@@ -1,6 +1,12 @@
 use crate::{
     amount,
-    chains::{bitcoin as btc_chain, evm::EvmChain, solana::SolanaChain},
+    chains::{
+        bitcoin as btc_chain,
+        evm::EvmChain,
+        native::native_token,
+        solana::{self as sol_chain, SolanaChain},
+    },
+    cli_output::format_token_amount,
     errors::ToolError,
     financial_math,
     keystore::{utc_now_iso, Keystore},
@@ -9,18 +15,20 @@ use crate::{
 };
 use alloy::{primitives::U256, rpc::types::TransactionRequest};
 use eyre::Context as _;
-use serde_json::json;
+use serde_json::{json, Value};
+use solana_sdk::signer::Signer as _;
 use std::str::FromStr as _;
 
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::helpers::{
-    evm_addr_for_account, is_native_token, resolve_wallet_and_account, solana_fallback_urls,
-    u128_to_u256, u128_to_u64,
+    is_native_token, resolve_wallet_and_account, solana_fallback_urls, tx_note, u128_to_u256,
+    u128_to_u64,
 };
-use super::super::key_loading::{load_bitcoin_privkey, load_evm_signer, load_solana_keypair};
+use super::super::key_loading::{load_bitcoin_privkey, load_solana_keypair};
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::common::summarize_sim_error;
+use super::evm_exec;
 use super::HandlerCtx;
 
 /// Parsed send transaction parameters common across chains.
@@ -162,6 +170,7 @@ where
 struct BitcoinSendRecord<'a> {
     ctx_req_id: &'a serde_json::Value,
     ks: &'a Keystore,
+    note: Option<&'a str>,
     w: &'a crate::wallet::WalletRecord,
     idx: u32,
     to: &'a str,
@@ -174,13 +183,16 @@ struct BitcoinSendRecord<'a> {
 }
 
 fn bitcoin_record_and_respond(r: &BitcoinSendRecord<'_>) -> eyre::Result<JsonRpcResponse> {
-    r.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
-      "type": "send", "chain": "bitcoin", "wallet": r.w.name,
-      "account_index": r.idx, "token": "native",
-      "amount_sats": r.amount_sats.to_string(), "usd_value": r.usd_value,
-      "txid": r.txid, "fee_sats": r.fee_sats, "to": r.to,
-    }))?;
+    r.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+          "type": "send", "chain": "bitcoin", "wallet": r.w.name,
+          "account_index": r.idx, "token": "native",
+          "amount_sats": r.amount_sats.to_string(), "usd_value": r.usd_value,
+          "txid": r.txid, "fee_sats": r.fee_sats, "to": r.to,
+        }),
+        r.note,
+    )?;
     let _audit_log = r.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": "send_transaction", "wallet": r.w.name,
       "account_index": r.idx, "chain": "bitcoin", "usd_value": r.usd_value,
@@ -244,7 +256,10 @@ where
         return Ok(blocked);
     }
 
-    let summary = format!("SEND BTC: {amount_sats} sats to {to}");
+    let summary = format!(
+        "SEND BTC: {} BTC ({amount_sats} sats) to {to}",
+        format_token_amount(u128::from(amount_sats), 8, &ctx.shared.cfg.display)
+    );
     let outcome = match maybe_confirm_write(
         ctx.shared,
         ctx.conn,
@@ -279,6 +294,7 @@ where
     let resp = bitcoin_record_and_respond(&BitcoinSendRecord {
         ctx_req_id: &ctx.req_id,
         ks: &ctx.shared.ks,
+        note: tx_note(&ctx.args),
         w,
         idx,
         to,
@@ -380,10 +396,70 @@ where
     }
 }
 
+/// The note to attach on-chain as an SPL Memo, if `memo_onchain` was requested. Opt-in because
+/// on-chain memos are public; the local history note is recorded either way.
+fn solana_send_memo(args: &Value) -> Result<Option<String>, ToolError> {
+    if !args
+        .get("memo_onchain")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        return Ok(None);
+    }
+    let note = args
+        .get("note")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    if note.is_empty() {
+        return Err(ToolError::new(
+            "invalid_request",
+            "memo_onchain requires a non-empty note",
+        ));
+    }
+    if note.len() > sol_chain::MAX_MEMO_BYTES {
+        return Err(ToolError::new(
+            "invalid_request",
+            format!(
+                "note is too long for an on-chain memo (max {} bytes)",
+                sol_chain::MAX_MEMO_BYTES
+            ),
+        ));
+    }
+    Ok(Some(note.to_owned()))
+}
+
+/// Native or SPL transfer instructions, followed by an SPL Memo when `memo` is set.
+async fn solana_transfer_instructions(
+    sol: &SolanaChain,
+    from: solana_sdk::pubkey::Pubkey,
+    to: solana_sdk::pubkey::Pubkey,
+    token: &str,
+    amount_base: u64,
+    memo: Option<&str>,
+) -> eyre::Result<Vec<solana_sdk::instruction::Instruction>> {
+    let mut ixs = if is_native_token(token) {
+        vec![solana_system_interface::instruction::transfer(
+            &from,
+            &to,
+            amount_base,
+        )]
+    } else {
+        let mint = SolanaChain::parse_pubkey(token)?;
+        sol.spl_transfer_instructions(from, to, mint, amount_base)
+            .await?
+    };
+    if let Some(m) = memo {
+        ixs.push(sol_chain::memo_instruction(&from, m)?);
+    }
+    Ok(ixs)
+}
+
 /// Record tx history and audit log for a solana send, then build the success response.
 struct SolanaSendRecord<'a> {
     ctx_req_id: &'a serde_json::Value,
     ks: &'a Keystore,
+    note: Option<&'a str>,
     w: &'a crate::wallet::WalletRecord,
     idx: u32,
     to: &'a str,
@@ -391,33 +467,64 @@ struct SolanaSendRecord<'a> {
     amount_base: u64,
     usd_value: f64,
     sig: &'a solana_sdk::signature::Signature,
+    durable: Option<&'a SolanaDurableSend>,
+    memo_onchain: bool,
     outcome: &'a super::super::policy_confirm::WriteConfirmOutcome,
 }
 
+/// Durable-nonce details of a Solana send (`durable_nonce_index` was given).
+struct SolanaDurableSend {
+    nonce_account: String,
+    /// Base64 signed transaction when `sign_only` (not broadcast).
+    signed_transaction: Option<String>,
+}
+
 fn solana_send_record_and_respond(r: &SolanaSendRecord<'_>) -> eyre::Result<JsonRpcResponse> {
-    r.ks.append_tx_history(&json!({
+    let signed_only = r.durable.and_then(|d| d.signed_transaction.as_deref());
+    let mut history = json!({
       "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
       "type": "send", "chain": "solana", "wallet": r.w.name,
       "account_index": r.idx, "to": r.to,
       "token": if is_native_token(r.token) { "native" } else { r.token },
       "amount_base": r.amount_base.to_string(), "usd_value": r.usd_value,
       "signature": r.sig.to_string()
-    }))?;
+    });
+    if let (Some(obj), Some(d)) = (history.as_object_mut(), r.durable) {
+        obj.insert(
+            "nonce_account".into(),
+            Value::from(d.nonce_account.as_str()),
+        );
+        if signed_only.is_some() {
+            obj.insert("status".into(), Value::from("signed"));
+        }
+    }
+    if let Some(obj) = history.as_object_mut().filter(|_| r.memo_onchain) {
+        obj.insert("memo_onchain".into(), Value::Bool(true));
+    }
+    r.ks.append_tx_history(&history, r.note)?;
     let _audit_log = r.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": "send_transaction", "wallet": r.w.name,
       "account_index": r.idx, "chain": "solana", "usd_value": r.usd_value,
       "usd_value_known": true, "policy_decision": r.outcome.policy_decision,
       "confirm_required": r.outcome.confirm_required, "confirm_result": r.outcome.confirm_result,
       "daily_used_usd": r.outcome.daily_used_usd, "forced_confirm": r.outcome.forced_confirm,
-      "txid": r.sig.to_string(), "error_code": null, "result": "broadcasted",
+      "txid": r.sig.to_string(), "error_code": null,
+      "result": if signed_only.is_some() { "signed" } else { "broadcasted" },
       "signature": r.sig.to_string()
     }));
-    Ok(ok(
-        r.ctx_req_id.clone(),
-        tool_ok(
-            json!({ "chain": "solana", "signature": r.sig.to_string(), "usd_value": r.usd_value }),
-        ),
-    ))
+    let mut resp =
+        json!({ "chain": "solana", "signature": r.sig.to_string(), "usd_value": r.usd_value });
+    if let (Some(obj), Some(d)) = (resp.as_object_mut(), r.durable) {
+        obj.insert(
+            "nonce_account".into(),
+            Value::from(d.nonce_account.as_str()),
+        );
+        if let Some(tx) = signed_only {
+            obj.insert("broadcast".into(), Value::Bool(false));
+            obj.insert("signed_transaction".into(), Value::from(tx));
+        }
+    }
+    Ok(ok(r.ctx_req_id.clone(), tool_ok(resp)))
 }
 
 async fn handle_solana_send<R, W>(
@@ -439,6 +546,44 @@ where
         amount,
         units,
     } = p;
+    let nonce_index = match ctx.args.get("durable_nonce_index") {
+        None | Some(Value::Null) => None,
+        Some(v) => match v.as_u64().and_then(|n| u32::try_from(n).ok()) {
+            Some(n) => Some(n),
+            None => {
+                Keystore::release_lock(lock)?;
+                return Ok(ok(
+                    ctx.req_id.clone(),
+                    tool_err(ToolError::new(
+                        "invalid_request",
+                        "durable_nonce_index must be a non-negative integer",
+                    )),
+                ));
+            }
+        },
+    };
+    let sign_only = ctx
+        .args
+        .get("sign_only")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if sign_only && nonce_index.is_none() {
+        Keystore::release_lock(lock)?;
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "sign_only requires durable_nonce_index (a blockhash-signed transaction expires in ~60-90s)",
+            )),
+        ));
+    }
+    let memo = match solana_send_memo(&ctx.args) {
+        Ok(m) => m,
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+        }
+    };
     let mode = effective_network_mode(ctx.shared, ctx.conn);
     let sol = SolanaChain::new_with_fallbacks(
         &ctx.shared.cfg.rpc.solana_rpc_url,
@@ -518,16 +663,56 @@ where
     };
 
     let kp = load_solana_keypair(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
-    let sig = if is_native_token(token) {
-        sol.send_sol(&kp, to_pk, amount_base).await?
+    let (sig, durable) = if let Some(nonce_index) = nonce_index {
+        let nonce = sol_chain::nonce_account_address(&kp.pubkey(), nonce_index)?;
+        let ixs = solana_transfer_instructions(
+            &sol,
+            kp.pubkey(),
+            to_pk,
+            token,
+            amount_base,
+            memo.as_deref(),
+        )
+        .await?;
+        let tx = sol.sign_with_durable_nonce(&kp, nonce, ixs).await?;
+        let (sig, signed_transaction) = if sign_only {
+            let sig = *tx
+                .signatures
+                .first()
+                .ok_or_else(|| eyre::eyre!("missing transaction signature"))?;
+            (sig, Some(sol_chain::encode_transaction_base64(&tx)?))
+        } else {
+            (sol.send_durable_nonce_transaction(&tx).await?, None)
+        };
+        (
+            sig,
+            Some(SolanaDurableSend {
+                nonce_account: nonce.to_string(),
+                signed_transaction,
+            }),
+        )
+    } else if memo.is_some() {
+        let ixs = solana_transfer_instructions(
+            &sol,
+            kp.pubkey(),
+            to_pk,
+            token,
+            amount_base,
+            memo.as_deref(),
+        )
+        .await?;
+        (sol.sign_and_send_instructions(&kp, ixs).await?, None)
+    } else if is_native_token(token) {
+        (sol.send_sol(&kp, to_pk, amount_base).await?, None)
     } else {
         let mint = SolanaChain::parse_pubkey(token)?;
-        sol.send_spl(&kp, to_pk, mint, amount_base).await?
+        (sol.send_spl(&kp, to_pk, mint, amount_base).await?, None)
     };
 
     let resp = solana_send_record_and_respond(&SolanaSendRecord {
         ctx_req_id: &ctx.req_id,
         ks: &ctx.shared.ks,
+        note: tx_note(&ctx.args),
         w,
         idx,
         to,
@@ -535,6 +720,8 @@ where
         amount_base,
         usd_value,
         sig: &sig,
+        durable: durable.as_ref(),
+        memo_onchain: memo.is_some(),
         outcome: &outcome,
     })?;
     Keystore::release_lock(lock)?;
@@ -620,10 +807,14 @@ where
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
     if is_native_token(a.token) {
+        let decimals = native_token(chain).decimals;
         let amount_base = if a.units == "base" {
             crate::chains::evm::parse_u256_dec(a.amount)?
         } else {
-            u128_to_u256(amount::parse_amount_ui_to_base_u128(a.amount, 18)?)
+            u128_to_u256(amount::parse_amount_ui_to_base_u128(
+                a.amount,
+                u32::from(decimals),
+            )?)
         };
         let usd = {
             ctx.shared.ensure_db().await;
@@ -634,7 +825,7 @@ where
         };
         let usd_value = financial_math::token_base_to_usd(
             crate::chains::evm::u256_low_u128(amount_base),
-            18,
+            decimals,
             usd,
         );
         let tx = EvmChain::build_native_transfer(from, to_addr, amount_base);
@@ -685,6 +876,7 @@ where
 struct EvmSendRecord<'a> {
     ctx_req_id: &'a serde_json::Value,
     ks: &'a Keystore,
+    note: Option<&'a str>,
     w: &'a crate::wallet::WalletRecord,
     idx: u32,
     chain: &'a str,
@@ -698,13 +890,16 @@ struct EvmSendRecord<'a> {
 }
 
 fn evm_send_record_and_respond(r: &EvmSendRecord<'_>) -> eyre::Result<JsonRpcResponse> {
-    r.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": "send",
-      "chain": r.chain, "wallet": r.w.name, "account_index": r.idx, "to": r.to,
-      "token": if is_native_token(r.token) { "native" } else { r.token },
-      "amount_base": r.amount_base.to_string(), "usd_value": r.usd_value,
-      "tx_hash": format!("{:#x}", r.tx_hash)
-    }))?;
+    r.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": "send",
+          "chain": r.chain, "wallet": r.w.name, "account_index": r.idx, "to": r.to,
+          "token": if is_native_token(r.token) { "native" } else { r.token },
+          "amount_base": r.amount_base.to_string(), "usd_value": r.usd_value,
+          "tx_hash": format!("{:#x}", r.tx_hash)
+        }),
+        r.note,
+    )?;
     let _audit_log = r.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": "send_transaction", "wallet": r.w.name,
       "account_index": r.idx, "chain": r.chain, "usd_value": r.usd_value,
@@ -761,7 +956,14 @@ where
     if let Some(fb) = ctx.shared.cfg.rpc.evm_fallback_rpc_urls.get(chain.as_str()) {
         evm.fallback_rpc_urls.clone_from(fb);
     }
-    let from = evm_addr_for_account(w, idx)?;
+    let exec = match evm_exec::resolve(ctx.shared, &ctx.args, w, idx, &chain) {
+        Ok(x) => x,
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+        }
+    };
+    let from = exec.from_address();
     let to_addr = EvmChain::parse_address(&to)?;
 
     if let Some(blocked) = evm_send_check_blocklists(
@@ -843,11 +1045,11 @@ where
         ));
     }
 
-    let wallet = load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
-    let tx_hash = evm.send_tx(wallet, tx).await?;
+    let tx_hash = evm_exec::submit(ctx, w, idx, &evm, &exec, tx).await?;
     let resp = evm_send_record_and_respond(&EvmSendRecord {
         ctx_req_id: &ctx.req_id,
         ks: &ctx.shared.ks,
+        note: tx_note(&ctx.args),
         w,
         idx,
         chain: &chain,
@@ -868,7 +1070,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
     let (effective_policy, _) = ctx.shared.cfg.policy_for_wallet(Some(w.name.as_str()));
     let chain = ctx
@@ -909,6 +1111,22 @@ where
             tool_err(ToolError::new("invalid_request", "missing chain/to/amount")),
         ));
     }
+    if chain != "solana"
+        && ctx
+            .args
+            .get("memo_onchain")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    {
+        Keystore::release_lock(lock)?;
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "memo_onchain is only supported on solana",
+            )),
+        ));
+    }
 
     if chain == "bitcoin" {
         return handle_bitcoin_send(
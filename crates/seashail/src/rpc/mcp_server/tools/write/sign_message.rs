@@ -0,0 +1,213 @@
+use crate::{
+    errors::{SeashailError, ToolError},
+    keystore::utc_now_iso,
+    message_signing::{self, SignerFamily},
+};
+use alloy::signers::SignerSync as _;
+use serde_json::{json, Value};
+use solana_signer::Signer as _;
+
+use super::super::super::elicitation::elicit_form;
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::helpers::resolve_wallet_and_account;
+use super::super::key_loading::{load_evm_signer, load_solana_keypair};
+use super::HandlerCtx;
+
+/// Always prompt: the user must see the exact bytes being signed, regardless of policy.
+async fn confirm_message<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    signer: &str,
+    chain: &str,
+    display: &str,
+    warnings: &[&str],
+) -> Result<(), ToolError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let warning_block = if warnings.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "WARNING: possible blind-signing request:\n- {}\nDo not sign unless you know exactly what this authorizes.\n\n",
+            warnings.join("\n- ")
+        )
+    };
+    let msg = format!(
+        "{warning_block}Sign message (off-chain, never broadcast).\n\nSigner: {signer}\nChain: {chain}\n\nMessage:\n{display}\n"
+    );
+    let schema = json!({
+      "type": "object",
+      "properties": {
+        "confirm": { "type": "boolean", "title": "Sign this exact message", "default": false }
+      },
+      "required": ["confirm"]
+    });
+    let res = elicit_form(
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        &msg,
+        schema,
+        std::time::Duration::from_secs(5 * 60),
+    )
+    .await
+    .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
+    let confirmed =
+        res.action == "accept" && res.content.get("confirm").and_then(Value::as_bool) == Some(true);
+    if confirmed {
+        Ok(())
+    } else {
+        Err(SeashailError::UserDeclined.into())
+    }
+}
+
+pub async fn handle<R, W>(ctx: &mut HandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+    let chain = ctx
+        .args
+        .get("chain")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim()
+        .to_owned();
+    let family = if chain == "solana" {
+        SignerFamily::Solana
+    } else if ctx.shared.cfg.rpc.evm_rpc_urls.contains_key(&chain) {
+        SignerFamily::Evm
+    } else {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "chain must be solana or a configured EVM chain",
+            )),
+        ));
+    };
+    let message = ctx
+        .args
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let encoding = ctx
+        .args
+        .get("encoding")
+        .and_then(Value::as_str)
+        .unwrap_or("utf8");
+    let bytes = match message_signing::decode_message(message, encoding) {
+        Ok(b) => b,
+        Err(e) => {
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new("invalid_request", format!("{e:#}"))),
+            ));
+        }
+    };
+    if let Some(reason) = message_signing::transaction_payload_reason(&bytes, family) {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new("invalid_request", reason)),
+        ));
+    }
+    let display = message_signing::display_message(&bytes);
+    let warnings = message_signing::blind_signing_warnings(&bytes, family);
+
+    let signer_addr = match family {
+        SignerFamily::Evm => w.evm_addresses.get(idx as usize),
+        SignerFamily::Solana => w.solana_addresses.get(idx as usize),
+    }
+    .cloned()
+    .unwrap_or_default();
+
+    if let Err(te) = confirm_message(ctx, &signer_addr, &chain, &display, &warnings).await {
+        let _audit_log = ctx.shared.ks.append_audit_log(&json!({
+          "ts": utc_now_iso(),
+          "tool": "sign_message",
+          "wallet": w.name,
+          "account_index": idx,
+          "chain": chain,
+          "usd_value": 0.0_f64,
+          "usd_value_known": true,
+          "policy_decision": "user_declined",
+          "confirm_required": true,
+          "confirm_result": "declined",
+          "txid": null,
+          "error_code": te.code,
+          "result": "blocked_user_declined"
+        }));
+        return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+    }
+
+    let (signature, recovered, verified) = match family {
+        SignerFamily::Evm => {
+            let signer =
+                load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, &w, idx).await?;
+            let sig = signer
+                .sign_message_sync(&bytes)
+                .map_err(|e| eyre::eyre!("sign message: {e}"))?;
+            let recovered = sig
+                .recover_address_from_msg(&bytes)
+                .map_err(|e| eyre::eyre!("recover signer: {e}"))?;
+            (
+                format!("0x{}", hex::encode(sig.as_bytes())),
+                recovered.to_string(),
+                recovered == signer.address(),
+            )
+        }
+        SignerFamily::Solana => {
+            let kp =
+                load_solana_keypair(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, &w, idx).await?;
+            let pk = kp.pubkey();
+            let sig = kp.sign_message(&bytes);
+            (
+                sig.to_string(),
+                pk.to_string(),
+                sig.verify(pk.as_ref(), &bytes),
+            )
+        }
+    };
+
+    let _audit_log = ctx.shared.ks.append_audit_log(&json!({
+      "ts": utc_now_iso(),
+      "tool": "sign_message",
+      "wallet": w.name,
+      "account_index": idx,
+      "chain": chain,
+      "usd_value": 0.0_f64,
+      "usd_value_known": true,
+      "policy_decision": "user_confirmed",
+      "confirm_required": true,
+      "confirm_result": "confirmed",
+      "txid": null,
+      "error_code": null,
+      "result": "signed",
+      "blind_signing_warnings": warnings
+    }));
+
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "wallet": w.name,
+          "account_index": idx,
+          "chain": chain,
+          "scheme": match family {
+              SignerFamily::Evm => "eip191_personal_sign",
+              SignerFamily::Solana => "ed25519",
+          },
+          "message": display,
+          "signature": signature,
+          "signature_encoding": match family {
+              SignerFamily::Evm => "hex",
+              SignerFamily::Solana => "base58",
+          },
+          "signer": recovered,
+          "verified": verified,
+          "warnings": warnings,
+          "broadcast": false
+        })),
+    ))
+}
@@ -0,0 +1,380 @@
+use serde_json::{json, Value};
+
+use crate::{
+    chains::solana::{self as sol_chain, SolanaChain},
+    errors::ToolError,
+    financial_math,
+    keystore::{utc_now_iso, Keystore},
+    policy_engine::WriteOp,
+    price,
+    wallet::WalletRecord,
+};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::state::effective_network_mode;
+use super::super::helpers::{
+    resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls, tx_note,
+};
+use super::super::key_loading::load_solana_keypair;
+use super::super::policy_confirm::{
+    confirm_internal_transfer, WriteConfirmOutcome, WriteConfirmRequest,
+};
+use super::HandlerCtx;
+
+fn setup_solana<R, W>(ctx: &HandlerCtx<'_, R, W>) -> SolanaChain {
+    let mode = effective_network_mode(ctx.shared, ctx.conn);
+    SolanaChain::new_with_fallbacks(
+        &ctx.shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(ctx.shared, mode),
+        &ctx.shared.cfg.http.jupiter_base_url,
+        ctx.shared.cfg.http.jupiter_api_key.as_deref(),
+        ctx.shared.cfg.rpc.solana_default_compute_unit_limit,
+        ctx.shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    )
+}
+
+fn nonce_index(args: &Value) -> Result<u32, ToolError> {
+    match args.get("nonce_index") {
+        None | Some(Value::Null) => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| {
+                ToolError::new(
+                    "invalid_request",
+                    "nonce_index must be a non-negative integer",
+                )
+            }),
+    }
+}
+
+fn audit_log(
+    ks: &Keystore,
+    tool_name: &str,
+    w: &WalletRecord,
+    idx: u32,
+    usd_value: f64,
+    outcome: Option<&WriteConfirmOutcome>,
+    extra: &Value,
+) {
+    let mut entry = json!({
+      "ts": utc_now_iso(),
+      "tool": tool_name,
+      "wallet": w.name,
+      "account_index": idx,
+      "chain": "solana",
+      "usd_value": usd_value,
+      "usd_value_known": true,
+      "policy_decision": outcome.map_or("auto_approve_internal", |o| o.policy_decision),
+      "confirm_required": outcome.is_some_and(|o| o.confirm_required),
+      "confirm_result": outcome.and_then(|o| o.confirm_result),
+      "daily_used_usd": outcome.map_or(0.0_f64, |o| o.daily_used_usd),
+      "forced_confirm": outcome.is_some_and(|o| o.forced_confirm),
+    });
+    if let (Some(b), Some(e)) = (entry.as_object_mut(), extra.as_object()) {
+        for (k, v) in e {
+            b.insert(k.clone(), v.clone());
+        }
+    }
+    let _audit_log = ks.append_audit_log(&entry);
+}
+
+/// Create or close a seeded nonce account. Rent moves between the wallet account and an
+/// account it controls, so this is confirmed as an internal transfer.
+async fn handle_create_or_close<R, W>(
+    tool_name: &str,
+    ctx: &mut HandlerCtx<'_, R, W>,
+    w: &WalletRecord,
+    idx: u32,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let create = tool_name == "create_nonce_account";
+    let nonce_index = match nonce_index(&ctx.args) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let sol = setup_solana(ctx);
+    let authority = sol_pubkey_for_account(w, idx)?;
+    let nonce = sol_chain::nonce_account_address(&authority, nonce_index)?;
+    let existing = sol.get_account_optional(&nonce).await?;
+    let lamports = match (create, existing.as_ref()) {
+        (true, Some(_)) => {
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new(
+                    "already_exists",
+                    format!("nonce account {nonce} (nonce_index {nonce_index}) already exists"),
+                )),
+            ));
+        }
+        (false, None) => {
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new(
+                    "not_found",
+                    format!("nonce account {nonce} (nonce_index {nonce_index}) does not exist"),
+                )),
+            ));
+        }
+        (true, None) => {
+            sol.get_rent_exempt_minimum(sol_chain::NONCE_ACCOUNT_LENGTH)
+                .await?
+        }
+        (false, Some(acct)) => acct.lamports,
+    };
+
+    let usd_value = {
+        ctx.shared.ensure_db().await;
+        let db = ctx.shared.db();
+        let sol_usd = price::native_token_price_usd_cached("solana", &ctx.shared.cfg, db)
+            .await?
+            .usd;
+        financial_math::lamports_to_usd(lamports, sol_usd)
+    };
+    let summary = if create {
+        format!("Create durable nonce account {nonce} (nonce_index {nonce_index}), depositing {lamports} lamports rent")
+    } else {
+        format!("Close durable nonce account {nonce} (nonce_index {nonce_index}), withdrawing {lamports} lamports")
+    };
+    let nonce_s = nonce.to_string();
+    let outcome = match confirm_internal_transfer(
+        ctx.shared,
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        &WriteConfirmRequest {
+            tool: tool_name,
+            wallet: Some(w.name.as_str()),
+            account_index: Some(idx),
+            op: WriteOp::InternalTransfer,
+            chain: "solana",
+            usd_value,
+            usd_value_known: true,
+            force_confirm: false,
+            slippage_bps: None,
+            to_address: Some(nonce_s.as_str()),
+            contract: None,
+            leverage: None,
+            summary: &summary,
+        },
+    )
+    .await
+    {
+        Ok(o) => o,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+
+    let kp = load_solana_keypair(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
+    let sig = if create {
+        sol.create_nonce_account(&kp, nonce_index).await?.0
+    } else {
+        sol.close_nonce_account(&kp, nonce).await?.0
+    };
+    let sig_s = sig.to_string();
+
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": if create { "create_nonce_account" } else { "close_nonce_account" },
+          "chain": "solana",
+          "wallet": w.name,
+          "account_index": idx,
+          "token": "native",
+          "amount_base": lamports.to_string(),
+          "usd_value": usd_value,
+          "nonce_account": nonce_s,
+          "signature": sig_s
+        }),
+        tx_note(&ctx.args),
+    )?;
+    audit_log(
+        &ctx.shared.ks,
+        tool_name,
+        w,
+        idx,
+        usd_value,
+        outcome.as_ref(),
+        &json!({ "txid": sig_s, "error_code": null, "result": "broadcasted", "nonce_account": nonce_s }),
+    );
+
+    let nonce_value = if create {
+        sol.get_nonce_account(&nonce)
+            .await
+            .ok()
+            .flatten()
+            .map(|st| st.nonce.to_string())
+    } else {
+        None
+    };
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "chain": "solana",
+          "signature": sig_s,
+          "nonce_index": nonce_index,
+          "nonce_account": nonce_s,
+          "authority": authority.to_string(),
+          "lamports": lamports.to_string(),
+          "usd_value": usd_value,
+          "durable_nonce": nonce_value,
+        })),
+    ))
+}
+
+/// Broadcast a transaction produced by `send_transaction` with `sign_only`. Policy was applied
+/// when it was signed, so only transactions recorded in tx history as `signed` (and not yet
+/// submitted) are accepted; bytes signed any other way are refused. We also check it belongs to
+/// this wallet account and that its nonce has not been consumed.
+async fn handle_submit<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    w: &WalletRecord,
+    idx: u32,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let raw = ctx
+        .args
+        .get("transaction")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+    if raw.is_empty() {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new("invalid_request", "missing transaction")),
+        ));
+    }
+    let Ok(tx) = sol_chain::decode_transaction_base64(raw) else {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "transaction must be a base64-encoded signed Solana transaction",
+            )),
+        ));
+    };
+    let Some(nonce) = sol_chain::durable_nonce_account_of(&tx) else {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "not a durable-nonce transaction (sign it with send_transaction sign_only + durable_nonce_index)",
+            )),
+        ));
+    };
+    let authority = sol_pubkey_for_account(w, idx)?;
+    if tx.message.account_keys.first() != Some(&authority) {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "transaction fee payer is not the selected wallet account",
+            )),
+        ));
+    }
+
+    let tracked = match tx.signatures.first() {
+        Some(sig) => ctx.shared.ks.has_signed_tx_pending(&sig.to_string())?,
+        None => false,
+    };
+    if !tracked {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                "transaction was not signed by send_transaction sign_only (or was already submitted); only policy-approved durable-nonce transactions can be submitted",
+            )),
+        ));
+    }
+
+    let sol = setup_solana(ctx);
+    let current = sol.get_nonce_account(&nonce).await?;
+    if !current.is_some_and(|st| st.nonce == tx.message.recent_blockhash) {
+        audit_log(
+            &ctx.shared.ks,
+            "submit_signed_transaction",
+            w,
+            idx,
+            0.0_f64,
+            None,
+            &json!({ "txid": null, "error_code": "nonce_consumed", "result": "nonce_consumed", "nonce_account": nonce.to_string() }),
+        );
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "nonce_consumed",
+                format!("nonce account {nonce} has advanced or been closed since this transaction was signed; it can no longer land. Sign it again."),
+            )),
+        ));
+    }
+
+    let sig = sol.send_durable_nonce_transaction(&tx).await?;
+    let sig_s = sig.to_string();
+    ctx.shared.ks.update_tx_history_status(
+        &sig_s,
+        "submitted",
+        &json!({ "submitted_ts": utc_now_iso() }),
+    )?;
+    audit_log(
+        &ctx.shared.ks,
+        "submit_signed_transaction",
+        w,
+        idx,
+        0.0_f64,
+        None,
+        &json!({ "txid": sig_s, "error_code": null, "result": "broadcasted", "nonce_account": nonce.to_string() }),
+    );
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "chain": "solana",
+          "signature": sig_s,
+          "nonce_account": nonce.to_string(),
+          "signer": authority.to_string(),
+        })),
+    ))
+}
+
+/// Durable nonce tools: `create_nonce_account`, `close_nonce_account`,
+/// `submit_signed_transaction` (Solana only).
+pub async fn handle<R, W>(
+    tool_name: &str,
+    ctx: &mut HandlerCtx<'_, R, W>,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let chain = ctx
+        .args
+        .get("chain")
+        .and_then(Value::as_str)
+        .unwrap_or("solana");
+    if chain != "solana" {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_chain",
+                "durable nonces are only supported on solana",
+            )),
+        ));
+    }
+    let lock = ctx.shared.acquire_write_lock().await?;
+    let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+    let resp = if tool_name == "submit_signed_transaction" {
+        handle_submit(ctx, &w, idx).await
+    } else {
+        handle_create_or_close(tool_name, ctx, &w, idx).await
+    };
+    Keystore::release_lock(lock)?;
+    resp
+}
@@ -0,0 +1,534 @@
+use crate::{
+    amount,
+    chains::solana::SolanaChain,
+    errors::ToolError,
+    jupiter_trigger,
+    keystore::{utc_now_iso, Keystore},
+    policy_engine::WriteOp,
+};
+use eyre::Context as _;
+use serde_json::{json, Value};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::state::effective_network_mode;
+use super::super::super::SharedState;
+use super::super::helpers::{
+    is_native_token, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+    tx_note, u128_to_u64,
+};
+use super::super::key_loading::load_solana_keypair;
+use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest};
+use super::super::value_helpers::summarize_sim_error;
+use super::swap_tokens::{resolve_solana_amount, solana_usd_value};
+use super::HandlerCtx;
+
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+fn arg_str<'a>(args: &'a Value, key: &str) -> &'a str {
+    args.get(key).and_then(Value::as_str).unwrap_or("").trim()
+}
+
+fn mint_for(token: &str) -> &str {
+    if is_native_token(token) {
+        WSOL_MINT
+    } else {
+        token
+    }
+}
+
+fn setup_solana<R, W>(ctx: &HandlerCtx<'_, R, W>) -> SolanaChain {
+    let mode = effective_network_mode(ctx.shared, ctx.conn);
+    SolanaChain::new_with_fallbacks(
+        &ctx.shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(ctx.shared, mode),
+        &ctx.shared.cfg.http.jupiter_base_url,
+        ctx.shared.cfg.http.jupiter_api_key.as_deref(),
+        ctx.shared.cfg.rpc.solana_default_compute_unit_limit,
+        ctx.shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    )
+}
+
+/// Resolve the order expiry (unix seconds) from `expires_in_seconds` or `expires_at` (RFC3339).
+fn parse_expiry(args: &Value, now_unix: i64) -> Result<Option<i64>, ToolError> {
+    if let Some(secs) = args.get("expires_in_seconds").and_then(Value::as_u64) {
+        if secs == 0 {
+            return Err(ToolError::new(
+                "invalid_request",
+                "expires_in_seconds must be > 0",
+            ));
+        }
+        let secs = i64::try_from(secs)
+            .map_err(|_e| ToolError::new("invalid_request", "invalid expires_in_seconds"))?;
+        return Ok(Some(now_unix.saturating_add(secs)));
+    }
+    let at = arg_str(args, "expires_at");
+    if at.is_empty() {
+        return Ok(None);
+    }
+    let ts = chrono::DateTime::parse_from_rfc3339(at)
+        .map_err(|_e| ToolError::new("invalid_request", "expires_at must be RFC3339"))?
+        .timestamp();
+    if ts <= now_unix {
+        return Err(ToolError::new(
+            "invalid_request",
+            "expires_at must be in the future",
+        ));
+    }
+    Ok(Some(ts))
+}
+
+/// Compute the taking amount (output base units) from either `amount_out` or `limit_price`.
+///
+/// `limit_price` is quoted as `token_out` per 1 `token_in` (UI units) and is converted using
+/// integer math only.
+fn resolve_taking_amount(
+    args: &Value,
+    units: &str,
+    making_base: u64,
+    decimals_in: u8,
+    decimals_out: u8,
+) -> eyre::Result<u64> {
+    let amount_out = arg_str(args, "amount_out");
+    if !amount_out.is_empty() {
+        return if units == "base" {
+            u128_to_u64(amount::parse_amount_base_u128(amount_out)?)
+        } else {
+            u128_to_u64(amount::parse_amount_ui_to_base_u128(
+                amount_out,
+                u32::from(decimals_out),
+            )?)
+        };
+    }
+    let price = arg_str(args, "limit_price");
+    if price.is_empty() {
+        eyre::bail!("missing amount_out or limit_price");
+    }
+    let taking = amount::apply_ui_price_to_base_u128(
+        u128::from(making_base),
+        price,
+        u32::from(decimals_in),
+        u32::from(decimals_out),
+    )
+    .context("limit_price")?;
+    u128_to_u64(taking)
+}
+
+/// Parsed, validated order parameters for `place_spot_limit_order`.
+struct PlaceOrder {
+    token_in: String,
+    token_out: String,
+    making: u64,
+    taking: u64,
+    decimals_in: u8,
+    decimals_out: u8,
+    expired_at: Option<i64>,
+    usd_value: f64,
+}
+
+/// UI amount for confirmation text; falls back to base units if the decimals are unusable.
+fn ui(base: u64, decimals: u8) -> String {
+    amount::format_amount_base_to_ui_string(u128::from(base), u32::from(decimals))
+        .unwrap_or_else(|_e| format!("{base} base"))
+}
+
+/// Read the mint's decimals. A bad mint is the caller's input error, so it maps to
+/// `invalid_request`.
+async fn mint_decimals(sol: &SolanaChain, which: &str, token: &str) -> Result<u8, ToolError> {
+    let mint = SolanaChain::parse_pubkey(mint_for(token))
+        .map_err(|e| ToolError::new("invalid_request", format!("{which}: {e:#}")))?;
+    sol.get_mint_decimals(mint).await.map_err(|e| {
+        ToolError::new(
+            "invalid_request",
+            format!("{which}: cannot read mint decimals: {e:#}"),
+        )
+    })
+}
+
+/// Validate and resolve the order before the write lock is taken, so bad input fails fast as
+/// `invalid_request`.
+async fn prepare_place<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    sol: &SolanaChain,
+) -> eyre::Result<Result<PlaceOrder, ToolError>>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let token_in = arg_str(&ctx.args, "token_in").to_owned();
+    let token_out = arg_str(&ctx.args, "token_out").to_owned();
+    let amount_in = arg_str(&ctx.args, "amount_in").to_owned();
+    let units = match arg_str(&ctx.args, "amount_units") {
+        "" => "ui".to_owned(),
+        u => u.to_owned(),
+    };
+    if token_in.is_empty() || token_out.is_empty() || amount_in.is_empty() {
+        return Ok(Err(ToolError::new(
+            "invalid_request",
+            "missing token_in/token_out/amount_in",
+        )));
+    }
+    if mint_for(&token_in) == mint_for(&token_out) {
+        return Ok(Err(ToolError::new(
+            "invalid_request",
+            "token_in and token_out must differ",
+        )));
+    }
+    let expired_at = match parse_expiry(&ctx.args, chrono::Utc::now().timestamp()) {
+        Ok(v) => v,
+        Err(te) => return Ok(Err(te)),
+    };
+
+    let decimals_in = match mint_decimals(sol, "token_in", &token_in).await {
+        Ok(v) => v,
+        Err(te) => return Ok(Err(te)),
+    };
+    let decimals_out = match mint_decimals(sol, "token_out", &token_out).await {
+        Ok(v) => v,
+        Err(te) => return Ok(Err(te)),
+    };
+    let making = match resolve_solana_amount(&amount_in, &units, decimals_in) {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(Err(ToolError::new(
+                "invalid_request",
+                format!("amount_in: {e:#}"),
+            )))
+        }
+    };
+    let taking = match resolve_taking_amount(&ctx.args, &units, making, decimals_in, decimals_out) {
+        Ok(v) => v,
+        Err(e) => return Ok(Err(ToolError::new("invalid_request", format!("{e:#}")))),
+    };
+    if making == 0 || taking == 0 {
+        return Ok(Err(ToolError::new(
+            "invalid_request",
+            "order amounts must be > 0",
+        )));
+    }
+    // The full input notional is committed up front, so it is what counts toward policy limits.
+    let usd_value =
+        solana_usd_value(ctx.shared, sol, &token_in, mint_for(&token_in), making).await?;
+    Ok(Ok(PlaceOrder {
+        token_in,
+        token_out,
+        making,
+        taking,
+        decimals_in,
+        decimals_out,
+        expired_at,
+        usd_value,
+    }))
+}
+
+/// Audit-log context shared by every exit path after policy confirmation.
+struct OrderAudit<'a> {
+    tool: &'a str,
+    w: &'a crate::wallet::WalletRecord,
+    idx: u32,
+    usd_value: f64,
+    outcome: &'a WriteConfirmOutcome,
+}
+
+fn audit(shared: &SharedState, a: &OrderAudit<'_>, sig: Option<&str>, error_code: Option<&str>) {
+    let result = if error_code.is_some() {
+        "failed"
+    } else {
+        "broadcasted"
+    };
+    let _audit_log = shared.ks.append_audit_log(&json!({
+      "ts": utc_now_iso(), "tool": a.tool, "wallet": a.w.name,
+      "account_index": a.idx, "chain": "solana", "usd_value": a.usd_value,
+      "usd_value_known": true, "policy_decision": a.outcome.policy_decision,
+      "confirm_required": a.outcome.confirm_required,
+      "confirm_result": a.outcome.confirm_result,
+      "daily_used_usd": a.outcome.daily_used_usd,
+      "forced_confirm": a.outcome.forced_confirm,
+      "txid": sig, "error_code": error_code,
+      "result": result, "signature": sig,
+      "provider": "jupiter"
+    }));
+}
+
+async fn handle_place<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    lock: std::fs::File,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+    sol: &SolanaChain,
+    o: &PlaceOrder,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let owner = sol_pubkey_for_account(w, idx)?;
+
+    let (effective_policy, _) = ctx.shared.cfg.policy_for_wallet(Some(w.name.as_str()));
+    let expiry_s = o
+        .expired_at
+        .map_or_else(|| "never".to_owned(), |ts| ts.to_string());
+    let summary = format!(
+        "SPOT LIMIT ORDER on Solana via Jupiter: sell {} {} for at least {} {} (expires: {expiry_s})",
+        ui(o.making, o.decimals_in),
+        o.token_in,
+        ui(o.taking, o.decimals_out),
+        o.token_out
+    );
+    let outcome = match maybe_confirm_write(
+        ctx.shared,
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        &WriteConfirmRequest {
+            tool: "place_spot_limit_order",
+            wallet: Some(w.name.as_str()),
+            account_index: Some(idx),
+            op: WriteOp::PlaceSpotLimitOrder,
+            chain: "solana",
+            usd_value: o.usd_value,
+            usd_value_known: true,
+            force_confirm: effective_policy.require_user_confirm_for_remote_tx.get(),
+            slippage_bps: None,
+            to_address: None,
+            contract: Some("jupiter"),
+            leverage: None,
+            summary: &summary,
+        },
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+        }
+    };
+
+    let a = OrderAudit {
+        tool: "place_spot_limit_order",
+        w,
+        idx,
+        usd_value: o.usd_value,
+        outcome: &outcome,
+    };
+    let owner_s = owner.to_string();
+    let trigger = match jupiter_trigger::create_order(
+        &ctx.shared.cfg.http,
+        &jupiter_trigger::CreateOrderRequest {
+            input_mint: mint_for(&o.token_in),
+            output_mint: mint_for(&o.token_out),
+            maker: &owner_s,
+            making_amount: o.making,
+            taking_amount: o.taking,
+            expired_at: o.expired_at,
+        },
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            audit(ctx.shared, &a, None, Some("jupiter_trigger_error"));
+            Keystore::release_lock(lock)?;
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new("jupiter_trigger_error", format!("{e:#}"))),
+            ));
+        }
+    };
+
+    let allowed = jupiter_trigger::allowed_program_ids()?;
+    let kp = load_solana_keypair(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
+    let sig = match sol
+        .sign_and_send_versioned_allowlist(&kp, &trigger.tx_bytes, &allowed)
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            audit(ctx.shared, &a, None, Some("tx_failed"));
+            Keystore::release_lock(lock)?;
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new(
+                    "tx_failed",
+                    summarize_sim_error(&e, "jupiter trigger createOrder"),
+                )),
+            ));
+        }
+    };
+    let sig_s = sig.to_string();
+
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+          "type": "spot_limit_place", "chain": "solana", "wallet": w.name,
+          "account_index": idx, "provider": "jupiter", "order": trigger.order,
+          "token_in": o.token_in, "token_out": o.token_out,
+          "making_amount_base": o.making.to_string(), "taking_amount_base": o.taking.to_string(),
+          "expired_at": o.expired_at, "usd_value": o.usd_value, "signature": sig_s
+        }),
+        tx_note(&ctx.args),
+    )?;
+    audit(ctx.shared, &a, Some(&sig_s), None);
+    Keystore::release_lock(lock)?;
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "chain": "solana", "provider": "jupiter", "order": trigger.order,
+          "signature": sig_s, "making_amount_base": o.making.to_string(),
+          "taking_amount_base": o.taking.to_string(), "expired_at": o.expired_at,
+          "usd_value": o.usd_value, "status": "open"
+        })),
+    ))
+}
+
+async fn handle_cancel<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    lock: std::fs::File,
+    w: &crate::wallet::WalletRecord,
+    idx: u32,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let order = arg_str(&ctx.args, "order").to_owned();
+    if order.is_empty() {
+        Keystore::release_lock(lock)?;
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new("invalid_request", "missing order")),
+        ));
+    }
+    let sol = setup_solana(ctx);
+    let owner_s = sol_pubkey_for_account(w, idx)?.to_string();
+
+    // Cancelling returns the unfilled input to the maker, so it carries no outbound USD value.
+    let summary = format!("CANCEL SPOT LIMIT ORDER on Solana via Jupiter: {order}");
+    let outcome = match maybe_confirm_write(
+        ctx.shared,
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        &WriteConfirmRequest {
+            tool: "cancel_spot_limit_order",
+            wallet: Some(w.name.as_str()),
+            account_index: Some(idx),
+            op: WriteOp::CancelSpotLimitOrder,
+            chain: "solana",
+            usd_value: 0.0_f64,
+            usd_value_known: true,
+            force_confirm: false,
+            slippage_bps: None,
+            to_address: None,
+            contract: Some("jupiter"),
+            leverage: None,
+            summary: &summary,
+        },
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+        }
+    };
+
+    let a = OrderAudit {
+        tool: "cancel_spot_limit_order",
+        w,
+        idx,
+        usd_value: 0.0_f64,
+        outcome: &outcome,
+    };
+    let trigger = match jupiter_trigger::cancel_order(&ctx.shared.cfg.http, &owner_s, &order).await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            audit(ctx.shared, &a, None, Some("jupiter_trigger_error"));
+            Keystore::release_lock(lock)?;
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new("jupiter_trigger_error", format!("{e:#}"))),
+            ));
+        }
+    };
+    let allowed = jupiter_trigger::allowed_program_ids()?;
+    let kp = load_solana_keypair(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
+    let sig = match sol
+        .sign_and_send_versioned_allowlist(&kp, &trigger.tx_bytes, &allowed)
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            audit(ctx.shared, &a, None, Some("tx_failed"));
+            Keystore::release_lock(lock)?;
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new(
+                    "tx_failed",
+                    summarize_sim_error(&e, "jupiter trigger cancelOrder"),
+                )),
+            ));
+        }
+    };
+    let sig_s = sig.to_string();
+
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+          "type": "spot_limit_cancel", "chain": "solana", "wallet": w.name,
+          "account_index": idx, "provider": "jupiter", "order": order,
+          "usd_value": 0.0_f64, "signature": sig_s
+        }),
+        tx_note(&ctx.args),
+    )?;
+    audit(ctx.shared, &a, Some(&sig_s), None);
+    Keystore::release_lock(lock)?;
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "chain": "solana", "provider": "jupiter", "order": order,
+          "signature": sig_s, "status": "cancelled"
+        })),
+    ))
+}
+
+pub async fn handle<R, W>(
+    tool_name: &str,
+    ctx: &mut HandlerCtx<'_, R, W>,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let chain = arg_str(&ctx.args, "chain");
+    let chain = if chain.is_empty() { "solana" } else { chain };
+    if chain != "solana" {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "unsupported_chain",
+                "spot limit orders are currently supported on solana only (Jupiter Trigger API)",
+            )),
+        ));
+    }
+
+    if tool_name == "cancel_spot_limit_order" {
+        let lock = ctx.shared.acquire_write_lock().await?;
+        let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+        return handle_cancel(ctx, lock, &w, idx).await;
+    }
+
+    let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+    let sol = setup_solana(ctx);
+    let o = match prepare_place(ctx, &sol).await? {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let lock = ctx.shared.acquire_write_lock().await?;
+    handle_place(ctx, lock, &w, idx, &sol, &o).await
+}
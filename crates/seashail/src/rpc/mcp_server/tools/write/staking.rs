@@ -18,7 +18,7 @@ use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::helpers::{
     evm_addr_for_account, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
-    u128_to_u256, u128_to_u64,
+    tx_note, u128_to_u256, u128_to_u64,
 };
 use super::super::key_loading::{load_evm_signer, load_solana_keypair};
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
@@ -201,12 +201,15 @@ where
     let kp = load_solana_keypair(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
     let sig = sol.sign_and_send_versioned(&kp, &tx_bytes).await?;
 
-    ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": history_type,
-      "chain": "solana", "wallet": w.name, "account_index": idx, "usd_value": usd_value,
-      "signature": sig.to_string(), "protocol": "jito", "mint_in": mint_in,
-      "mint_out": mint_out, "amount_in_base": amt_in.to_string(),
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": history_type,
+          "chain": "solana", "wallet": w.name, "account_index": idx, "usd_value": usd_value,
+          "signature": sig.to_string(), "protocol": "jito", "mint_in": mint_in,
+          "mint_out": mint_out, "amount_in_base": amt_in.to_string(),
+        }),
+        tx_note(&ctx.args),
+    )?;
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": tool_name, "wallet": w.name, "account_index": idx,
       "chain": "solana", "usd_value": usd_value, "usd_value_known": true,
@@ -233,7 +236,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
     let (effective_policy, _) = ctx.shared.cfg.policy_for_wallet(Some(w.name.as_str()));
 
@@ -423,19 +426,22 @@ where
             let txid = evm.send_tx(signer, tx).await.context("send lido tx")?;
             let txid_s = format!("{txid:#x}");
 
-            ctx.shared.ks.append_tx_history(&json!({
-              "ts": utc_now_iso(),
-              "day": Keystore::current_utc_day_key(),
-              "type": history_type,
-              "chain": "ethereum",
-              "wallet": w.name,
-              "account_index": idx,
-              "protocol": "lido",
-              "contract": LIDO_STETH,
-              "amount_wei": amount_wei.to_string(),
-              "usd_value": usd_value,
-              "txid": txid_s,
-            }))?;
+            ctx.shared.ks.append_tx_history(
+                &json!({
+                  "ts": utc_now_iso(),
+                  "day": Keystore::current_utc_day_key(),
+                  "type": history_type,
+                  "chain": "ethereum",
+                  "wallet": w.name,
+                  "account_index": idx,
+                  "protocol": "lido",
+                  "contract": LIDO_STETH,
+                  "amount_wei": amount_wei.to_string(),
+                  "usd_value": usd_value,
+                  "txid": txid_s,
+                }),
+                tx_note(&ctx.args),
+            )?;
             let _audit_log = ctx.shared.ks.append_audit_log(&json!({
               "ts": utc_now_iso(),
               "tool": tool_name,
@@ -504,20 +510,23 @@ where
                     )),
                 ));
             }
-            ctx.shared.ks.append_tx_history(&json!({
-              "ts": utc_now_iso(),
-              "day": Keystore::current_utc_day_key(),
-              "type": "approve",
-              "chain": "ethereum",
-              "wallet": w.name,
-              "account_index": idx,
-              "protocol": "lido",
-              "token": LIDO_STETH,
-              "spender": LIDO_WITHDRAWAL_QUEUE,
-              "amount_base": amount_wei.to_string(),
-              "usd_value": 0.0_f64,
-              "txid": format!("{approve_txid:#x}"),
-            }))?;
+            ctx.shared.ks.append_tx_history(
+                &json!({
+                  "ts": utc_now_iso(),
+                  "day": Keystore::current_utc_day_key(),
+                  "type": "approve",
+                  "chain": "ethereum",
+                  "wallet": w.name,
+                  "account_index": idx,
+                  "protocol": "lido",
+                  "token": LIDO_STETH,
+                  "spender": LIDO_WITHDRAWAL_QUEUE,
+                  "amount_base": amount_wei.to_string(),
+                  "usd_value": 0.0_f64,
+                  "txid": format!("{approve_txid:#x}"),
+                }),
+                tx_note(&ctx.args),
+            )?;
         }
 
         let queue = ILidoWithdrawalQueue::new(queue_addr, evm.provider()?);
@@ -581,19 +590,22 @@ where
         let txid = evm.send_tx(signer, tx).await.context("send withdraw")?;
         let txid_s = format!("{txid:#x}");
 
-        ctx.shared.ks.append_tx_history(&json!({
-          "ts": utc_now_iso(),
-          "day": Keystore::current_utc_day_key(),
-          "type": history_type,
-          "chain": "ethereum",
-          "wallet": w.name,
-          "account_index": idx,
-          "protocol": "lido",
-          "contract": LIDO_WITHDRAWAL_QUEUE,
-          "amount_base": amount_wei.to_string(),
-          "usd_value": usd_value,
-          "txid": txid_s,
-        }))?;
+        ctx.shared.ks.append_tx_history(
+            &json!({
+              "ts": utc_now_iso(),
+              "day": Keystore::current_utc_day_key(),
+              "type": history_type,
+              "chain": "ethereum",
+              "wallet": w.name,
+              "account_index": idx,
+              "protocol": "lido",
+              "contract": LIDO_WITHDRAWAL_QUEUE,
+              "amount_base": amount_wei.to_string(),
+              "usd_value": usd_value,
+              "txid": txid_s,
+            }),
+            tx_note(&ctx.args),
+        )?;
         let _audit_log = ctx.shared.ks.append_audit_log(&json!({
           "ts": utc_now_iso(),
           "tool": tool_name,
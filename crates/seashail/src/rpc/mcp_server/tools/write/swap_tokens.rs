@@ -15,12 +15,16 @@ use serde_json::json;
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::helpers::{
-    evm_addr_for_account, is_native_token, resolve_wallet_and_account, sol_pubkey_for_account,
-    solana_fallback_urls, u128_to_u256, u128_to_u64,
+    evm_defi_deadline_seconds, evm_defi_slippage_bps, is_native_token, resolve_wallet_and_account,
+    sol_pubkey_for_account, solana_fallback_urls, tx_note, u128_to_u256, u128_to_u64,
+    unix_deadline,
+};
+use super::super::key_loading::load_solana_keypair;
+use super::super::policy_confirm::{
+    confirm_plan_step, maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest,
 };
-use super::super::key_loading::{load_evm_signer, load_solana_keypair};
-use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest};
 use super::common::{summarize_sim_error, wait_for_allowance};
+use super::evm_exec::{self, EvmExecution};
 use super::HandlerCtx;
 
 /// Parameters for a Solana swap via Jupiter.
@@ -34,6 +38,7 @@ struct SolanaSwapParams<'a> {
     amount_in_s: &'a str,
     units: &'a str,
     slippage_bps: u32,
+    plan_confirmed: bool,
 }
 
 /// Bundled data for recording and responding to a completed Solana swap.
@@ -51,18 +56,22 @@ struct SolanaSwapResult<'a> {
     sig: &'a solana_sdk::signature::Signature,
     outcome: &'a WriteConfirmOutcome,
     req_id: &'a serde_json::Value,
+    note: Option<&'a str>,
 }
 
 /// Record swap history/audit and build the success response for a Solana swap.
 fn solana_swap_record_and_respond(r: SolanaSwapResult<'_>) -> eyre::Result<JsonRpcResponse> {
-    r.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
-      "type": "swap", "chain": "solana", "wallet": r.w.name,
-      "account_index": r.idx, "token_in": r.token_in, "token_out": r.token_out,
-      "amount_in_base": r.amt_in.to_string(), "expected_out_base": r.expected_out,
-      "slippage_bps": r.slippage_bps, "usd_value": r.usd_value,
-      "signature": r.sig.to_string()
-    }))?;
+    r.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+          "type": "swap", "chain": "solana", "wallet": r.w.name,
+          "account_index": r.idx, "token_in": r.token_in, "token_out": r.token_out,
+          "amount_in_base": r.amt_in.to_string(), "expected_out_base": r.expected_out,
+          "slippage_bps": r.slippage_bps, "usd_value": r.usd_value,
+          "signature": r.sig.to_string()
+        }),
+        r.note,
+    )?;
     let _audit_log = r.shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(), "tool": "swap_tokens", "wallet": r.w.name,
       "account_index": r.idx, "chain": "solana", "usd_value": r.usd_value,
@@ -86,7 +95,11 @@ fn solana_swap_record_and_respond(r: SolanaSwapResult<'_>) -> eyre::Result<JsonR
 }
 
 /// Resolve the Solana input amount to base units.
-fn resolve_solana_amount(amount_in_s: &str, units: &str, decimals_in: u8) -> eyre::Result<u64> {
+pub(super) fn resolve_solana_amount(
+    amount_in_s: &str,
+    units: &str,
+    decimals_in: u8,
+) -> eyre::Result<u64> {
     if units == "base" {
         u128_to_u64(amount::parse_amount_base_u128(amount_in_s)?)
     } else {
@@ -98,7 +111,7 @@ fn resolve_solana_amount(amount_in_s: &str, units: &str, decimals_in: u8) -> eyr
 }
 
 /// Compute USD value for a Solana token amount.
-async fn solana_usd_value(
+pub(super) async fn solana_usd_value(
     shared: &mut SharedState,
     sol: &SolanaChain,
     token_in: &str,
@@ -150,6 +163,7 @@ where
         amount_in_s,
         units,
         slippage_bps,
+        plan_confirmed,
     } = p;
     let mode = effective_network_mode(ctx.shared, ctx.conn);
     let sol = SolanaChain::new_with_fallbacks(
@@ -182,11 +196,9 @@ where
     let usd_value = solana_usd_value(ctx.shared, &sol, token_in, mint_in, amt_in).await?;
 
     let summary = format!("SWAP on Solana via Jupiter: {token_in} -> {token_out}");
-    let outcome = match maybe_confirm_write(
-        ctx.shared,
-        ctx.conn,
-        ctx.stdin,
-        ctx.stdout,
+    let outcome = match confirm_swap(
+        ctx,
+        plan_confirmed,
         &WriteConfirmRequest {
             tool: "swap_tokens",
             wallet: Some(w.name.as_str()),
@@ -238,6 +250,7 @@ where
         sig: &sig,
         outcome: &outcome,
         req_id: &ctx.req_id,
+        note: tx_note(&ctx.args),
     })
 }
 
@@ -253,8 +266,13 @@ struct EvmSwapParams<'a> {
     amount_in_s: &'a str,
     units: &'a str,
     slippage_bps: u32,
+    /// Part of a plan the user already confirmed as a whole (see [`handle_plan_step`]).
+    plan_confirmed: bool,
+    /// Seconds the swap stays executable once built (Uniswap only; 1inch has no deadline).
+    deadline_seconds: u64,
     evm: EvmChain,
     from_addr: alloy::primitives::Address,
+    exec: EvmExecution,
 }
 
 /// Input parameters for resolving EVM amount and price.
@@ -317,6 +335,7 @@ struct EvmApprovalParams<'a> {
     amt_in: U256,
     outcome: &'a WriteConfirmOutcome,
     provider: &'a str,
+    exec: &'a EvmExecution,
 }
 
 /// Handle ERC-20 approval for an EVM swap if needed. Returns the approval tx hash if one was sent.
@@ -348,10 +367,9 @@ where
             )),
         )));
     }
-    let wallet = load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, ap.w, ap.idx).await?;
-    let tx_hash = ap.evm.send_tx(wallet.clone(), approve_tx).await?;
+    let tx_hash = evm_exec::submit(ctx, ap.w, ap.idx, ap.evm, ap.exec, approve_tx).await?;
     let tx_hash_s = format!("{tx_hash:#x}");
-    ctx.shared.ks.append_tx_history(&json!({ "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": "approve", "chain": ap.chain, "wallet": ap.w.name, "account_index": ap.idx, "provider": ap.provider, "token": format!("{:#x}", ap.token_in_addr), "spender": format!("{:#x}", ap.spender), "amount_base": ap.amt_in.to_string(), "usd_value": 0.0_f64, "tx_hash": tx_hash_s }))?;
+    ctx.shared.ks.append_tx_history(&json!({ "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(), "type": "approve", "chain": ap.chain, "wallet": ap.w.name, "account_index": ap.idx, "provider": ap.provider, "token": format!("{:#x}", ap.token_in_addr), "spender": format!("{:#x}", ap.spender), "amount_base": ap.amt_in.to_string(), "usd_value": 0.0_f64, "tx_hash": tx_hash_s }), tx_note(&ctx.args))?;
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({ "ts": utc_now_iso(), "tool": "swap_tokens", "wallet": ap.w.name, "account_index": ap.idx, "chain": ap.chain, "usd_value": 0.0_f64, "usd_value_known": false, "policy_decision": ap.outcome.policy_decision, "confirm_required": ap.outcome.confirm_required, "confirm_result": ap.outcome.confirm_result, "daily_used_usd": ap.outcome.daily_used_usd, "forced_confirm": ap.outcome.forced_confirm, "txid": tx_hash_s, "error_code": null, "result": "broadcasted", "tx_hash": tx_hash_s, "type": "approve", "provider": ap.provider }));
     if !wait_for_allowance(
         ap.evm,
@@ -391,6 +409,7 @@ struct EvmSwapResult<'a> {
     tx_hash: alloy::primitives::B256,
     outcome: &'a WriteConfirmOutcome,
     req_id: &'a serde_json::Value,
+    note: Option<&'a str>,
 }
 
 /// Record an EVM swap to history + audit log and build the success response.
@@ -402,7 +421,7 @@ fn record_evm_swap_and_respond(r: EvmSwapResult<'_>) -> eyre::Result<JsonRpcResp
             obj.insert("min_out_base".to_owned(), json!(mo.to_string()));
         }
     }
-    r.shared.ks.append_tx_history(&hist)?;
+    r.shared.ks.append_tx_history(&hist, r.note)?;
     let _audit_log = r.shared.ks.append_audit_log(&json!({ "ts": utc_now_iso(), "tool": "swap_tokens", "wallet": r.w.name, "account_index": r.idx, "chain": r.chain, "usd_value": r.usd_value, "usd_value_known": true, "policy_decision": r.outcome.policy_decision, "confirm_required": r.outcome.confirm_required, "confirm_result": r.outcome.confirm_result, "daily_used_usd": r.outcome.daily_used_usd, "forced_confirm": r.outcome.forced_confirm, "txid": tx_hash_s, "error_code": null, "result": "broadcasted", "tx_hash": tx_hash_s, "provider": r.provider }));
     Keystore::release_lock(r.lock)?;
     let mut resp = json!({ "chain": r.chain, "provider": r.provider, "tx_hash": tx_hash_s, "usd_value": r.usd_value, "expected_out_base": r.out.to_string() });
@@ -432,8 +451,11 @@ where
         amount_in_s,
         units,
         slippage_bps,
+        plan_confirmed,
+        deadline_seconds,
         evm,
         from_addr,
+        exec,
         ..
     } = p;
     let u = evm
@@ -465,11 +487,9 @@ where
     .await?;
     let router_s = format!("{router02:#x}");
     let summary = format!("SWAP on {chain} via Uniswap: {token_in} -> {token_out}");
-    let outcome = match maybe_confirm_write(
-        ctx.shared,
-        ctx.conn,
-        ctx.stdin,
-        ctx.stdout,
+    let outcome = match confirm_swap(
+        ctx,
+        plan_confirmed,
         &WriteConfirmRequest {
             tool: "swap_tokens",
             wallet: Some(w.name.as_str()),
@@ -511,6 +531,7 @@ where
                 amt_in,
                 outcome: &outcome,
                 provider: "uniswap",
+                exec: &exec,
             },
         )
         .await?
@@ -532,6 +553,7 @@ where
         fee,
         native_in,
         native_out,
+        deadline: unix_deadline(deadline_seconds),
     };
     let swap_tx = evm.build_uniswap_swap_tx(&swap_req)?;
     if let Err(e) = evm.simulate_tx_strict(&swap_tx).await {
@@ -545,8 +567,7 @@ where
             )),
         ));
     }
-    let wallet = load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
-    let tx_hash = evm.send_tx(wallet, swap_tx).await?;
+    let tx_hash = evm_exec::submit(ctx, w, idx, &evm, &exec, swap_tx).await?;
     record_evm_swap_and_respond(EvmSwapResult {
         shared: ctx.shared,
         lock,
@@ -564,6 +585,7 @@ where
         tx_hash,
         outcome: &outcome,
         req_id: &ctx.req_id,
+        note: tx_note(&ctx.args),
     })
 }
 
@@ -617,8 +639,11 @@ where
         amount_in_s,
         units,
         slippage_bps,
+        plan_confirmed,
         evm,
         from_addr,
+        exec,
+        ..
     } = p;
     if ctx
         .shared
@@ -678,11 +703,9 @@ where
     let contract_addr = extract_tx_to_address(&swap_tx)?;
     let router_s = format!("{contract_addr:#x}");
     let summary = format!("SWAP on {chain} via 1inch: {token_in} -> {token_out}");
-    let outcome = match maybe_confirm_write(
-        ctx.shared,
-        ctx.conn,
-        ctx.stdin,
-        ctx.stdout,
+    let outcome = match confirm_swap(
+        ctx,
+        plan_confirmed,
         &WriteConfirmRequest {
             tool: "swap_tokens",
             wallet: Some(w.name.as_str()),
@@ -722,6 +745,7 @@ where
                 amt_in,
                 outcome: &outcome,
                 provider: "1inch",
+                exec: &exec,
             },
         )
         .await?
@@ -744,8 +768,7 @@ where
             )),
         ));
     }
-    let wallet = load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
-    let tx_hash = evm.send_tx(wallet, swap_tx).await?;
+    let tx_hash = evm_exec::submit(ctx, w, idx, &evm, &exec, swap_tx).await?;
     record_evm_swap_and_respond(EvmSwapResult {
         shared: ctx.shared,
         lock,
@@ -763,6 +786,7 @@ where
         tx_hash,
         outcome: &outcome,
         req_id: &ctx.req_id,
+        note: tx_note(&ctx.args),
     })
 }
 
@@ -805,7 +829,9 @@ struct SwapArgs {
 }
 
 /// Parse and validate swap arguments from the request args.
-fn parse_swap_args(args: &serde_json::Value) -> SwapArgs {
+///
+/// EVM swaps default `slippage_bps` to `evm_defi.slippage_bps`; Solana keeps 100.
+fn parse_swap_args(args: &serde_json::Value, evm_defi: &crate::config::EvmDefiConfig) -> SwapArgs {
     let chain = args
         .get("chain")
         .and_then(|v| v.as_str())
@@ -831,11 +857,14 @@ fn parse_swap_args(args: &serde_json::Value) -> SwapArgs {
         .and_then(|v| v.as_str())
         .unwrap_or("ui")
         .to_owned();
-    let slippage_bps = args
-        .get("slippage_bps")
-        .and_then(serde_json::Value::as_u64)
-        .and_then(|v| u32::try_from(v).ok())
-        .unwrap_or(100);
+    let slippage_bps = if chain == "solana" {
+        args.get("slippage_bps")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(100)
+    } else {
+        evm_defi_slippage_bps(evm_defi, args)
+    };
     let provider_raw = args
         .get("provider")
         .and_then(|v| v.as_str())
@@ -853,7 +882,7 @@ fn parse_swap_args(args: &serde_json::Value) -> SwapArgs {
 }
 
 /// Set up an EVM chain instance from config.
-fn setup_evm_chain(shared: &SharedState, chain: &str) -> eyre::Result<EvmChain> {
+pub(super) fn setup_evm_chain(shared: &SharedState, chain: &str) -> eyre::Result<EvmChain> {
     let rpc_url = shared
         .cfg
         .rpc
@@ -874,15 +903,53 @@ fn setup_evm_chain(shared: &SharedState, chain: &str) -> eyre::Result<EvmChain>
     Ok(evm)
 }
 
+/// Prompt for one swap, or for a step of an already-confirmed plan only apply policy.
+async fn confirm_swap<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    plan_confirmed: bool,
+    req: &WriteConfirmRequest<'_>,
+) -> Result<WriteConfirmOutcome, ToolError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    if plan_confirmed {
+        confirm_plan_step(ctx.shared, req)
+    } else {
+        maybe_confirm_write(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, req).await
+    }
+}
+
 pub async fn handle_ctx<R, W>(ctx: &mut HandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
 where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    handle_swap(ctx, false).await
+}
+
+/// Run one swap of a multi-step plan (e.g. `rebalance_portfolio`) the user already confirmed via
+/// `confirm_plan`: policy, history and audit apply as usual, but there is no per-swap prompt.
+pub async fn handle_plan_step<R, W>(ctx: &mut HandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    handle_swap(ctx, true).await
+}
+
+async fn handle_swap<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    plan_confirmed: bool,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let lock = ctx.shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
     let (effective_policy, _) = ctx.shared.cfg.policy_for_wallet(Some(w.name.as_str()));
-    let a = parse_swap_args(&ctx.args);
+    let a = parse_swap_args(&ctx.args, &ctx.shared.cfg.evm_defi);
 
     if a.chain.is_empty()
         || a.token_in.is_empty()
@@ -910,10 +977,30 @@ where
     };
 
     if a.chain == "solana" {
-        return handle_solana_branch(ctx, lock, &w, idx, &effective_policy, &a, provider).await;
+        return handle_solana_branch(
+            ctx,
+            lock,
+            &w,
+            idx,
+            &effective_policy,
+            &a,
+            provider,
+            plan_confirmed,
+        )
+        .await;
     }
 
-    handle_evm_branch(ctx, lock, &w, idx, &effective_policy, &a, provider).await
+    handle_evm_branch(
+        ctx,
+        lock,
+        &w,
+        idx,
+        &effective_policy,
+        &a,
+        provider,
+        plan_confirmed,
+    )
+    .await
 }
 
 /// Handle the Solana swap branch of `handle_ctx`.
@@ -925,6 +1012,7 @@ async fn handle_solana_branch<R, W>(
     effective_policy: &crate::policy::Policy,
     a: &SwapArgs,
     provider: &str,
+    plan_confirmed: bool,
 ) -> eyre::Result<JsonRpcResponse>
 where
     R: tokio::io::AsyncRead + Unpin,
@@ -952,6 +1040,7 @@ where
             amount_in_s: a.amount_in_s.as_str(),
             units: a.units.as_str(),
             slippage_bps: a.slippage_bps,
+            plan_confirmed,
         },
     )
     .await
@@ -966,6 +1055,7 @@ async fn handle_evm_branch<R, W>(
     effective_policy: &crate::policy::Policy,
     a: &SwapArgs,
     provider: &str,
+    plan_confirmed: bool,
 ) -> eyre::Result<JsonRpcResponse>
 where
     R: tokio::io::AsyncRead + Unpin,
@@ -982,7 +1072,21 @@ where
             )),
         ));
     }
-    let from_addr = evm_addr_for_account(w, idx)?;
+    let exec = match evm_exec::resolve(ctx.shared, &ctx.args, w, idx, &a.chain) {
+        Ok(x) => x,
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+        }
+    };
+    let from_addr = exec.from_address();
+    let deadline_seconds = match evm_defi_deadline_seconds(&ctx.shared.cfg.evm_defi, &ctx.args) {
+        Ok(v) => v,
+        Err(te) => {
+            Keystore::release_lock(lock)?;
+            return Ok(ok(ctx.req_id.clone(), tool_err(te)));
+        }
+    };
 
     if provider == "uniswap" {
         return handle_evm_uniswap_swap(
@@ -998,8 +1102,11 @@ where
                 amount_in_s: &a.amount_in_s,
                 units: &a.units,
                 slippage_bps: a.slippage_bps,
+                plan_confirmed,
+                deadline_seconds,
                 evm,
                 from_addr,
+                exec,
             },
         )
         .await;
@@ -1019,8 +1126,11 @@ where
                 amount_in_s: &a.amount_in_s,
                 units: &a.units,
                 slippage_bps: a.slippage_bps,
+                plan_confirmed,
+                deadline_seconds,
                 evm,
                 from_addr,
+                exec,
             },
         )
         .await;
@@ -4,6 +4,7 @@ use crate::{
     errors::ToolError,
     financial_math,
     keystore::{utc_now_iso, Keystore},
+    policy::InternalTransfersPolicy,
     price,
 };
 use alloy::primitives::U256;
@@ -14,11 +15,13 @@ use serde_json::{json, Value};
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::state::effective_network_mode;
 use super::super::helpers::{
-    evm_addr_for_account, is_native_token, sol_pubkey_for_account, solana_fallback_urls,
+    evm_addr_for_account, is_native_token, sol_pubkey_for_account, solana_fallback_urls, tx_note,
     u128_to_u256, u128_to_u64,
 };
 use super::super::key_loading::{load_evm_signer, load_solana_keypair};
-use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest};
+use super::super::policy_confirm::{
+    confirm_internal_transfer, WriteConfirmOutcome, WriteConfirmRequest,
+};
 use super::common::summarize_sim_error;
 use super::HandlerCtx;
 use crate::policy_engine::WriteOp;
@@ -68,15 +71,13 @@ where
         return resp;
     }
 
-    let outcome: Option<WriteConfirmOutcome> = if p.effective_policy.internal_transfers_exempt.get()
-    {
-        None
-    } else {
+    let to_s = to_pk.to_string();
+    let outcome: Option<WriteConfirmOutcome> = {
         let summary = format!(
             "Internal transfer (Solana): {}:{} -> {}:{} ({:.2} USD)",
             p.from_w.name, p.from_idx, p.to_w.name, p.to_idx, usd_value
         );
-        match maybe_confirm_write(
+        match confirm_internal_transfer(
             ctx.shared,
             ctx.conn,
             ctx.stdin,
@@ -91,7 +92,7 @@ where
                 usd_value_known,
                 force_confirm: false,
                 slippage_bps: None,
-                to_address: None,
+                to_address: Some(to_s.as_str()),
                 contract: None,
                 leverage: None,
                 summary: &summary,
@@ -99,7 +100,7 @@ where
         )
         .await
         {
-            Ok(o) => Some(o),
+            Ok(o) => o,
             Err(te) => {
                 Keystore::release_lock(p.lock)?;
                 return Ok(ok(ctx.req_id.clone(), tool_err(te)));
@@ -280,25 +281,28 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let ty = if p.effective_policy.internal_transfers_exempt.get() {
-        "internal_transfer"
-    } else {
+    let ty = if p.effective_policy.internal_transfers_mode() == InternalTransfersPolicy::Enforce {
         "internal_transfer_strict"
+    } else {
+        "internal_transfer"
     };
-    ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": ty,
-      "chain": "solana",
-      "wallet": p.from_w.name,
-      "account_index": p.from_idx,
-      "to_wallet": p.to_w.name,
-      "to_account_index": p.to_idx,
-      "token": if is_native_token(p.token) { "native" } else { p.token },
-      "amount_base": amount_base.to_string(),
-      "usd_value": usd_value,
-      "signature": sig.to_string()
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": ty,
+          "chain": "solana",
+          "wallet": p.from_w.name,
+          "account_index": p.from_idx,
+          "to_wallet": p.to_w.name,
+          "to_account_index": p.to_idx,
+          "token": if is_native_token(p.token) { "native" } else { p.token },
+          "amount_base": amount_base.to_string(),
+          "usd_value": usd_value,
+          "signature": sig.to_string()
+        }),
+        tx_note(&ctx.args),
+    )?;
 
     let (policy_decision, confirm_required, confirm_result, daily_used_usd, forced_confirm) =
         if let Some(o) = outcome {
@@ -340,7 +344,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let parsed = match parse_internal_transfer_args(ctx, lock)? {
         Ok(v) => v,
         Err(resp) => return Ok(resp),
@@ -539,15 +543,13 @@ where
     let (usd_value, amount_base, tx) = evm_internal_build_tx(ctx, &btx).await?;
     let usd_value_known = true;
 
-    let outcome: Option<WriteConfirmOutcome> = if p.effective_policy.internal_transfers_exempt.get()
-    {
-        None
-    } else {
+    let to_s = format!("{to_addr:#x}");
+    let outcome: Option<WriteConfirmOutcome> = {
         let summary = format!(
             "Internal transfer (EVM): {}:{} -> {}:{} ({:.2} USD)",
             p.from_w.name, p.from_idx, p.to_w.name, p.to_idx, usd_value
         );
-        match maybe_confirm_write(
+        match confirm_internal_transfer(
             ctx.shared,
             ctx.conn,
             ctx.stdin,
@@ -562,7 +564,7 @@ where
                 usd_value_known,
                 force_confirm: false,
                 slippage_bps: None,
-                to_address: None,
+                to_address: Some(to_s.as_str()),
                 contract: None,
                 leverage: None,
                 summary: &summary,
@@ -570,7 +572,7 @@ where
         )
         .await
         {
-            Ok(o) => Some(o),
+            Ok(o) => o,
             Err(te) => {
                 Keystore::release_lock(p.lock)?;
                 return Ok(ok(ctx.req_id.clone(), tool_err(te)));
@@ -796,23 +798,26 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let ty = if p.effective_policy.internal_transfers_exempt.get() {
-        "internal_transfer"
-    } else {
+    let ty = if p.effective_policy.internal_transfers_mode() == InternalTransfersPolicy::Enforce {
         "internal_transfer_strict"
+    } else {
+        "internal_transfer"
     };
-    ctx.shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": ty,
-      "chain": r.chain,
-      "wallet": p.from_w.name, "account_index": p.from_idx,
-      "to_wallet": p.to_w.name, "to_account_index": p.to_idx,
-      "token": if is_native_token(p.token) { "native" } else { p.token },
-      "amount_base": r.amount_base.to_string(),
-      "usd_value": r.usd_value,
-      "txid": format!("{:#x}", r.txid)
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": ty,
+          "chain": r.chain,
+          "wallet": p.from_w.name, "account_index": p.from_idx,
+          "to_wallet": p.to_w.name, "to_account_index": p.to_idx,
+          "token": if is_native_token(p.token) { "native" } else { p.token },
+          "amount_base": r.amount_base.to_string(),
+          "usd_value": r.usd_value,
+          "txid": format!("{:#x}", r.txid)
+        }),
+        tx_note(&ctx.args),
+    )?;
 
     let (policy_decision, confirm_required, confirm_result, daily_used_usd, forced_confirm) =
         if let Some(o) = outcome {
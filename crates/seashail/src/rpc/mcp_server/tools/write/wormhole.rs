@@ -21,10 +21,12 @@ use crate::{
 use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::helpers::{
     evm_addr_for_account, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+    tx_note,
 };
 use super::super::key_loading::load_evm_signer;
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::super::value_helpers::{parse_usd_value, summarize_sim_error};
+use super::bridge_quote::{self, BridgeQuote};
 use super::common::wait_for_allowance;
 use super::wormhole_solana;
 use super::HandlerCtx;
@@ -45,7 +47,7 @@ sol! {
 const SOL_TOKEN_BRIDGE_MAINNET: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
 const SOL_TOKEN_BRIDGE_DEVNET: &str = "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE";
 
-fn sol_token_bridge_program_id(mode: NetworkMode) -> eyre::Result<Pubkey> {
+pub(super) fn sol_token_bridge_program_id(mode: NetworkMode) -> eyre::Result<Pubkey> {
     let s = match mode {
         NetworkMode::Mainnet => SOL_TOKEN_BRIDGE_MAINNET,
         NetworkMode::Testnet => SOL_TOKEN_BRIDGE_DEVNET,
@@ -58,7 +60,7 @@ fn sol_pda(program_id: &Pubkey, seeds: &[&[u8]]) -> Pubkey {
     pk
 }
 
-fn sol_wrapped_mint_pda(
+pub(super) fn sol_wrapped_mint_pda(
     token_bridge: &Pubkey,
     token_chain: u16,
     token_address: [u8; 32],
@@ -69,7 +71,7 @@ fn sol_wrapped_mint_pda(
     )
 }
 
-fn arg_str<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+pub(super) fn arg_str<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
     args.get(key)
         .and_then(Value::as_str)
         .map(str::trim)
@@ -112,7 +114,7 @@ pub(super) fn default_token_bridge_for_chain(chain: &str) -> Option<&'static str
     }
 }
 
-fn wormhole_chain_id(chain: &str) -> Option<u16> {
+pub(super) fn wormhole_chain_id(chain: &str) -> Option<u16> {
     // Wormhole chain IDs for supported chains.
     match chain.trim() {
         "solana" => Some(1),
@@ -153,7 +155,7 @@ fn parse_amount_base(amount_s: &str, units: &str, decimals: u8) -> Result<U256,
     Ok(U256::from(base_u128))
 }
 
-fn usdc_base_to_usd_f64(base_amount: &U256) -> eyre::Result<f64> {
+pub(super) fn usdc_base_to_usd_f64(base_amount: &U256) -> eyre::Result<f64> {
     let s = base_amount.to_string();
     let s = s.trim();
     if s.is_empty() {
@@ -185,13 +187,13 @@ fn usdc_base_to_usd_f64(base_amount: &U256) -> eyre::Result<f64> {
     dec.parse::<f64>().context("parse usdc amount")
 }
 
-fn evm_address_to_bytes32(a: Address) -> [u8; 32] {
+pub(super) fn evm_address_to_bytes32(a: Address) -> [u8; 32] {
     let mut out = [0_u8; 32];
     out[12..].copy_from_slice(a.as_slice());
     out
 }
 
-fn bytes32_hex(b: [u8; 32]) -> String {
+pub(super) fn bytes32_hex(b: [u8; 32]) -> String {
     hex::encode(b)
 }
 
@@ -199,7 +201,7 @@ fn log_message_published_sig() -> B256 {
     keccak256("LogMessagePublished(address,uint64,uint32,bytes,uint8)".as_bytes())
 }
 
-fn extract_wormhole_message(
+pub(super) fn extract_wormhole_message(
     receipt: &alloy::rpc::types::TransactionReceipt,
 ) -> Option<(Address, u64)> {
     type LogData = alloy::sol! { tuple(uint64, uint32, bytes, uint8) };
@@ -262,7 +264,7 @@ async fn fetch_signed_vaa_bytes_b64(
 
 /// Polls Wormholescan up to 60 times (500ms apart) for the signed VAA. Returns `Some(b64)` on
 /// success or `None` if the VAA was not available. Sets `*redeem_error` on fetch failure.
-async fn poll_signed_vaa(
+pub(super) async fn poll_signed_vaa(
     base_url: &str,
     src_chain_id: u16,
     emitter_hex: &str,
@@ -321,7 +323,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let dst_lock = ctx.shared.ks.acquire_write_lock()?;
+    let dst_lock = ctx.shared.acquire_write_lock().await?;
     let result = if let Some(owner) = rp.recipient_sol_owner {
         match wormhole_solana::redeem_transfer_vaa_to_solana(
             ctx.shared,
@@ -338,18 +340,21 @@ where
         .await
         {
             Ok(sig) => {
-                ctx.shared.ks.append_tx_history(&json!({
-                  "ts": utc_now_iso(),
-                  "day": Keystore::current_utc_day_key(),
-                  "type": "bridge_redeem",
-                  "chain": "solana",
-                  "wallet": rp.w.name,
-                  "account_index": rp.idx,
-                  "provider": "wormhole",
-                  "bridge_id": rp.bridge_id,
-                  "usd_value": rp.usd_value,
-                  "txid": sig
-                }))?;
+                ctx.shared.ks.append_tx_history(
+                    &json!({
+                      "ts": utc_now_iso(),
+                      "day": Keystore::current_utc_day_key(),
+                      "type": "bridge_redeem",
+                      "chain": "solana",
+                      "wallet": rp.w.name,
+                      "account_index": rp.idx,
+                      "provider": "wormhole",
+                      "bridge_id": rp.bridge_id,
+                      "usd_value": rp.usd_value,
+                      "txid": sig
+                    }),
+                    tx_note(&ctx.args),
+                )?;
                 (Some(sig), None)
             }
             Err(e) => (None, Some(format!("{e:#}"))),
@@ -372,7 +377,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let dst_lock = ctx.shared.ks.acquire_write_lock()?;
+    let dst_lock = ctx.shared.acquire_write_lock().await?;
     let dst_rpc_url = ctx
         .shared
         .cfg
@@ -456,18 +461,21 @@ where
         load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, rp.w, rp.idx).await?;
     match dst.send_tx(dst_signer, redeem_tx).await {
         Ok(h) => {
-            ctx.shared.ks.append_tx_history(&json!({
-              "ts": utc_now_iso(),
-              "day": Keystore::current_utc_day_key(),
-              "type": "bridge_redeem",
-              "chain": rp.to_chain,
-              "wallet": rp.w.name,
-              "account_index": rp.idx,
-              "provider": "wormhole",
-              "bridge_id": rp.bridge_id,
-              "usd_value": rp.usd_value,
-              "txid": format!("{h:#x}")
-            }))?;
+            ctx.shared.ks.append_tx_history(
+                &json!({
+                  "ts": utc_now_iso(),
+                  "day": Keystore::current_utc_day_key(),
+                  "type": "bridge_redeem",
+                  "chain": rp.to_chain,
+                  "wallet": rp.w.name,
+                  "account_index": rp.idx,
+                  "provider": "wormhole",
+                  "bridge_id": rp.bridge_id,
+                  "usd_value": rp.usd_value,
+                  "txid": format!("{h:#x}")
+                }),
+                tx_note(&ctx.args),
+            )?;
             Ok((Some(format!("{h:#x}")), None))
         }
         Err(e) => Ok((None, Some(format!("{e:#}")))),
@@ -490,7 +498,7 @@ struct ParsedBridge {
     effective_policy: crate::policy::Policy,
 }
 
-fn parse_bridge_args<R, W>(
+async fn parse_bridge_args<R, W>(
     ctx: &HandlerCtx<'_, R, W>,
 ) -> eyre::Result<Result<(ParsedBridge, std::fs::File), JsonRpcResponse>>
 where
@@ -562,7 +570,7 @@ where
         .unwrap_or("")
         .to_owned();
 
-    let lock = ctx.shared.ks.acquire_write_lock()?;
+    let lock = ctx.shared.acquire_write_lock().await?;
     let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
     let (effective_policy, _) = ctx.shared.cfg.policy_for_wallet(Some(w.name.as_str()));
 
@@ -650,21 +658,35 @@ where
         )));
     }
 
+    let sol = SolanaChain::new_with_fallbacks(
+        &ctx.shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(ctx.shared, mode),
+        &ctx.shared.cfg.http.jupiter_base_url,
+        ctx.shared.cfg.http.jupiter_api_key.as_deref(),
+        ctx.shared.cfg.rpc.solana_default_compute_unit_limit,
+        ctx.shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    );
+    // Only a definite "account missing" blocks the transfer; RPC errors fall through to the
+    // redeem step, which reports them.
+    let mint_acct = sol.get_account_optional(&wrapped_mint).await;
+    if matches!(mint_acct, Ok(None)) {
+        return Ok(Err(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "token_not_attested",
+                format!(
+                    "{} on {} has not been attested to Solana (wrapped mint {wrapped_mint} does not exist), so a transfer now could not be redeemed. Run attest_token with chain={} and token={} first (one-time; costs {} gas plus Solana rent and requires confirmation), then retry bridge_tokens",
+                    pb.token_s, pb.chain, pb.chain, pb.token_s, pb.chain
+                ),
+            )),
+        )));
+    }
+
     let token_account = if recipient_ta.is_empty() {
-        let sol = SolanaChain::new_with_fallbacks(
-            &ctx.shared.cfg.rpc.solana_rpc_url,
-            solana_fallback_urls(ctx.shared, mode),
-            &ctx.shared.cfg.http.jupiter_base_url,
-            ctx.shared.cfg.http.jupiter_api_key.as_deref(),
-            ctx.shared.cfg.rpc.solana_default_compute_unit_limit,
-            ctx.shared
-                .cfg
-                .rpc
-                .solana_default_compute_unit_price_micro_lamports,
-        );
-        let token_program = sol
-            .get_account_optional(&wrapped_mint)
-            .await
+        let token_program = mint_acct
             .ok()
             .flatten()
             .map_or_else(spl_token::id, |a| a.owner);
@@ -682,6 +704,15 @@ where
 struct BridgeSendResult {
     txid: B256,
     usd_value: f64,
+    quote: BridgeQuote,
+}
+
+struct BridgeToken<'a> {
+    addr: Address,
+    amount_base: U256,
+    decimals: u8,
+    symbol: &'a str,
+    needs_approval: bool,
 }
 
 async fn validate_bridge_addr<R, W>(
@@ -725,16 +756,23 @@ async fn resolve_usd_and_confirm<R, W>(
     ctx: &mut HandlerCtx<'_, R, W>,
     pb: &ParsedBridge,
     evm: &EvmChain,
-    token_addr: Address,
-    amount_base: U256,
-    symbol: &str,
+    token: &BridgeToken<'_>,
 ) -> eyre::Result<
-    Result<(f64, bool, super::super::policy_confirm::WriteConfirmOutcome), JsonRpcResponse>,
+    Result<
+        (
+            f64,
+            bool,
+            super::super::policy_confirm::WriteConfirmOutcome,
+            BridgeQuote,
+        ),
+        JsonRpcResponse,
+    >,
 >
 where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
+    let (token_addr, amount_base) = (token.addr, token.amount_base);
     let (mut usd_value, mut usd_value_known) = parse_usd_value(&ctx.args);
     if !usd_value_known {
         let usd = if evm.uniswap.as_ref().is_some_and(|u| u.usdc == token_addr) {
@@ -751,12 +789,48 @@ where
         usd_value_known = usd_value.is_finite();
     }
 
+    let mut quote = u128::try_from(amount_base).map_or_else(
+        |_| BridgeQuote::default(),
+        |base| {
+            BridgeQuote::wormhole(
+                token.symbol,
+                token.decimals,
+                base,
+                usd_value_known.then_some(usd_value),
+            )
+        },
+    );
+    quote.source_fee_usd =
+        bridge_quote::wormhole_source_fee_usd(ctx.shared, &pb.chain, token.needs_approval).await;
+    if pb.redeem {
+        quote.destination_fee_usd =
+            bridge_quote::wormhole_redeem_fee_usd(ctx.shared, &pb.to_chain).await;
+        if let Err(te) = bridge_quote::check_destination_gas(
+            ctx.shared,
+            ctx.conn,
+            &pb.w,
+            pb.idx,
+            &pb.to_chain,
+            &ctx.args,
+            &mut quote,
+        )
+        .await
+        {
+            return Ok(Err(ok(ctx.req_id.clone(), tool_err(te))));
+        }
+    }
+    if let Err(te) = quote.check(&pb.effective_policy) {
+        return Ok(Err(ok(ctx.req_id.clone(), tool_err(te))));
+    }
+
     let summary = format!(
-        "Wormhole bridge on {} -> {}: {symbol} amount={} (units={})",
+        "Wormhole bridge on {} -> {}: {} amount={} (units={})\n{}",
         pb.chain,
         pb.to_chain,
+        token.symbol,
         pb.amount_s.trim(),
-        pb.units
+        pb.units,
+        quote.summary(&pb.effective_policy)
     );
     let outcome = match maybe_confirm_write(
         ctx.shared,
@@ -785,7 +859,7 @@ where
         Err(te) => return Ok(Err(ok(ctx.req_id.clone(), tool_err(te)))),
     };
 
-    Ok(Ok((usd_value, usd_value_known, outcome)))
+    Ok(Ok((usd_value, usd_value_known, outcome, quote)))
 }
 
 async fn approve_and_send_bridge<R, W>(
@@ -814,18 +888,25 @@ where
         Err(te) => return Ok(Err(ok(ctx.req_id.clone(), tool_err(te)))),
     };
 
-    let (usd_value, usd_value_known, outcome) =
-        match resolve_usd_and_confirm(ctx, pb, evm, token_addr, amount_base, &symbol).await? {
+    let allowance = evm
+        .erc20_allowance(token_addr, from, token_bridge_addr)
+        .await
+        .context("read erc20 allowance")?;
+    let token = BridgeToken {
+        addr: token_addr,
+        amount_base,
+        decimals,
+        symbol: &symbol,
+        needs_approval: allowance < amount_base,
+    };
+    let (usd_value, usd_value_known, outcome, quote) =
+        match resolve_usd_and_confirm(ctx, pb, evm, &token).await? {
             Ok(v) => v,
             Err(resp) => return Ok(Err(resp)),
         };
 
     // Approve the token bridge to spend tokens.
-    let allowance = evm
-        .erc20_allowance(token_addr, from, token_bridge_addr)
-        .await
-        .context("read erc20 allowance")?;
-    if allowance < amount_base {
+    if token.needs_approval {
         let ac = ApprovalCtx {
             evm,
             from,
@@ -876,14 +957,17 @@ where
         load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, &pb.w, pb.idx).await?;
     let txid = evm.send_tx(signer, tx).await?;
 
-    ctx.shared.ks.append_tx_history(&json!({
-        "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
-        "type": "bridge", "chain": pb.chain, "wallet": pb.w.name, "account_index": pb.idx,
-        "provider": "wormhole", "to_chain": pb.to_chain, "token": format!("{token_addr:#x}"),
-        "amount_base": amount_base.to_string(), "amount_units": pb.units,
-        "recipient": recipient.display, "token_bridge": format!("{token_bridge_addr:#x}"),
-        "usd_value": usd_value, "txid": format!("{txid:#x}")
-    }))?;
+    ctx.shared.ks.append_tx_history(
+        &json!({
+            "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+            "type": "bridge", "chain": pb.chain, "wallet": pb.w.name, "account_index": pb.idx,
+            "provider": "wormhole", "to_chain": pb.to_chain, "token": format!("{token_addr:#x}"),
+            "amount_base": amount_base.to_string(), "amount_units": pb.units,
+            "recipient": recipient.display, "token_bridge": format!("{token_bridge_addr:#x}"),
+            "usd_value": usd_value, "txid": format!("{txid:#x}")
+        }),
+        tx_note(&ctx.args),
+    )?;
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
         "ts": utc_now_iso(), "tool": "bridge_tokens", "wallet": pb.w.name, "account_index": pb.idx,
         "chain": pb.chain, "usd_value": usd_value, "usd_value_known": usd_value_known,
@@ -893,7 +977,11 @@ where
         "error_code": null, "result": "broadcasted", "provider": "wormhole"
     }));
 
-    Ok(Ok(BridgeSendResult { txid, usd_value }))
+    Ok(Ok(BridgeSendResult {
+        txid,
+        usd_value,
+        quote,
+    }))
 }
 
 struct ApprovalCtx<'a> {
@@ -937,7 +1025,7 @@ where
         "provider": "wormhole", "token": format!("{:#x}", ac.token_addr),
         "spender": format!("{:#x}", ac.token_bridge_addr), "amount_base": ac.amount_base.to_string(),
         "usd_value": 0.0_f64, "txid": tx_hash_s
-    }))?;
+    }), tx_note(&ctx.args))?;
     let _audit_log = ctx.shared.ks.append_audit_log(&json!({
         "ts": utc_now_iso(), "tool": "bridge_tokens", "wallet": pb.w.name, "account_index": pb.idx,
         "chain": pb.chain, "usd_value": 0.0_f64, "usd_value_known": false,
@@ -988,7 +1076,7 @@ where
             ctx.req_id.clone(),
             tool_ok(json!({
                 "chain": pb.chain, "bridge_provider": "wormhole", "to_chain": pb.to_chain,
-                "txid": txid_s, "usd_value": bsr.usd_value, "bridge_id": null,
+                "txid": txid_s, "usd_value": bsr.usd_value, "quote": bsr.quote.to_json(), "bridge_id": null,
                 "notes": "bridge tx broadcasted, but receipt was not observed in time; retry later and derive a wormhole bridge_id from the tx receipt logs"
             })),
         ));
@@ -999,7 +1087,7 @@ where
             ctx.req_id.clone(),
             tool_ok(json!({
                 "chain": pb.chain, "bridge_provider": "wormhole", "to_chain": pb.to_chain,
-                "txid": txid_s, "usd_value": bsr.usd_value, "bridge_id": null,
+                "txid": txid_s, "usd_value": bsr.usd_value, "quote": bsr.quote.to_json(), "bridge_id": null,
                 "notes": "bridge tx confirmed, but wormhole LogMessagePublished was not found in logs"
             })),
         ));
@@ -1024,6 +1112,7 @@ where
         tool_ok(json!({
             "chain": pb.chain, "bridge_provider": "wormhole", "to_chain": pb.to_chain,
             "token": token_s, "amount_base": "", "usd_value": bsr.usd_value,
+            "quote": bsr.quote.to_json(),
             "txid": txid_s, "bridge_id": bridge_id,
             "wormhole": {
                 "source_chain_id": pb.src_wh_chain_id, "destination_chain_id": pb.dst_wh_chain_id,
@@ -1072,7 +1161,7 @@ where
         && (dest_is_solana || !pb.dst_token_bridge_s.trim().is_empty())
     {
         let vaa_b64 = poll_signed_vaa(
-            &ctx.shared.cfg.http.wormholescan_api_base_url,
+            ctx.shared.cfg.http.wormholescan_base_url(),
             pb.src_wh_chain_id,
             emitter_hex,
             sequence,
@@ -1120,7 +1209,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let (pb, lock) = match parse_bridge_args(ctx)? {
+    let (pb, lock) = match parse_bridge_args(ctx).await? {
         Ok(v) => v,
         Err(resp) => return Ok(resp),
     };
@@ -0,0 +1,400 @@
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use base64::Engine as _;
+use eyre::Context as _;
+use serde_json::{json, Value};
+use tokio::time::Duration;
+
+use crate::{
+    chains::{evm::EvmChain, solana::SolanaChain},
+    errors::ToolError,
+    keystore::{utc_now_iso, Keystore},
+    policy_engine::WriteOp,
+    wallet::WalletRecord,
+};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::helpers::{
+    evm_addr_for_account, resolve_wallet_and_account, solana_fallback_urls, tx_note,
+};
+use super::super::key_loading::load_evm_signer;
+use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest};
+use super::super::value_helpers::summarize_sim_error;
+use super::swap_tokens::setup_evm_chain;
+use super::wormhole::{
+    arg_str, bytes32_hex, default_token_bridge_for_chain, evm_address_to_bytes32,
+    extract_wormhole_message, poll_signed_vaa, sol_token_bridge_program_id, sol_wrapped_mint_pda,
+    wormhole_chain_id,
+};
+use super::wormhole_solana;
+use super::HandlerCtx;
+
+sol! {
+    #[sol(rpc)]
+    contract IWormholeTokenBridgeAttest {
+        function attestToken(address tokenAddress, uint32 nonce) external payable returns (uint64 sequence);
+        function wormhole() external view returns (address);
+    }
+
+    #[sol(rpc)]
+    contract IWormholeCore {
+        function messageFee() external view returns (uint256);
+    }
+}
+
+struct AttestArgs {
+    chain: String,
+    token_addr: Address,
+    token_bridge_addr: Address,
+    src_wh_chain_id: u16,
+    attest_txid: Option<B256>,
+}
+
+fn parse_attest_args(args: &Value) -> Result<AttestArgs, ToolError> {
+    let chain = arg_str(args, "chain").unwrap_or("").to_owned();
+    let to_chain = arg_str(args, "to_chain").unwrap_or("solana");
+    if to_chain != "solana" {
+        return Err(ToolError::new(
+            "invalid_request",
+            "attest_token currently only creates wrapped assets on solana (to_chain=solana)",
+        ));
+    }
+    let (Some(src_wh_chain_id), Some(token_bridge_s)) = (
+        wormhole_chain_id(&chain).filter(|id| *id != 1),
+        default_token_bridge_for_chain(&chain),
+    ) else {
+        return Err(ToolError::new(
+            "invalid_request",
+            "attest_token requires an EVM source chain with a Wormhole token bridge",
+        ));
+    };
+    let token_addr = arg_str(args, "token")
+        .and_then(|s| EvmChain::parse_address(s).ok())
+        .ok_or_else(|| ToolError::new("invalid_request", "token must be an ERC-20 address"))?;
+    let token_bridge_addr = EvmChain::parse_address(token_bridge_s)
+        .map_err(|e| ToolError::new("internal_error", format!("{e:#}")))?;
+    let attest_txid = match arg_str(args, "attest_txid") {
+        None => None,
+        Some(s) => Some(s.parse::<B256>().map_err(|_| {
+            ToolError::new(
+                "invalid_request",
+                "attest_txid must be a 0x-prefixed tx hash",
+            )
+        })?),
+    };
+    Ok(AttestArgs {
+        chain,
+        token_addr,
+        token_bridge_addr,
+        src_wh_chain_id,
+        attest_txid,
+    })
+}
+
+fn audit_log(
+    ks: &Keystore,
+    w: &WalletRecord,
+    idx: u32,
+    chain: &str,
+    outcome: &WriteConfirmOutcome,
+    extra: &Value,
+) {
+    let mut entry = json!({
+      "ts": utc_now_iso(),
+      "tool": "attest_token",
+      "wallet": w.name,
+      "account_index": idx,
+      "chain": chain,
+      "usd_value": 0.0_f64,
+      "usd_value_known": false,
+      "policy_decision": outcome.policy_decision,
+      "confirm_required": outcome.confirm_required,
+      "confirm_result": outcome.confirm_result,
+      "daily_used_usd": outcome.daily_used_usd,
+      "forced_confirm": outcome.forced_confirm,
+      "provider": "wormhole",
+    });
+    if let (Some(b), Some(e)) = (entry.as_object_mut(), extra.as_object()) {
+        for (k, v) in e {
+            b.insert(k.clone(), v.clone());
+        }
+    }
+    let _audit_log = ks.append_audit_log(&entry);
+}
+
+/// Send `attestToken` on the source chain, paying the core bridge message fee.
+async fn send_attestation<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    evm: &EvmChain,
+    aa: &AttestArgs,
+    w: &WalletRecord,
+    idx: u32,
+) -> eyre::Result<Result<B256, ToolError>>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let from = evm_addr_for_account(w, idx)?;
+    let bridge = IWormholeTokenBridgeAttest::new(aa.token_bridge_addr, evm.provider()?);
+    let core: Address = bridge
+        .wormhole()
+        .call()
+        .await
+        .context("token bridge wormhole()")?;
+    let message_fee: U256 = IWormholeCore::new(core, evm.provider()?)
+        .messageFee()
+        .call()
+        .await
+        .context("wormhole messageFee")?;
+    let nonce = crate::db::Db::now_ms()
+        .ok()
+        .and_then(|ms| u32::try_from((ms / 1000).rem_euclid(i64::from(u32::MAX))).ok())
+        .unwrap_or(1_u32);
+    let call = bridge.attestToken(aa.token_addr, nonce);
+    let tx = TransactionRequest {
+        from: Some(from),
+        to: Some(aa.token_bridge_addr.into()),
+        input: Bytes::from(call.calldata().to_vec()).into(),
+        value: Some(message_fee),
+        ..Default::default()
+    };
+    if let Err(e) = evm.simulate_tx_strict(&tx).await {
+        return Ok(Err(ToolError::new(
+            "simulation_failed",
+            summarize_sim_error(&e, "attest token (wormhole)"),
+        )));
+    }
+    let signer = load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
+    let txid = evm.send_tx(signer, tx).await?;
+    ctx.shared.ks.append_tx_history(&json!({
+        "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+        "type": "attest_token", "chain": aa.chain, "wallet": w.name, "account_index": idx,
+        "provider": "wormhole", "to_chain": "solana", "token": format!("{:#x}", aa.token_addr),
+        "token_bridge": format!("{:#x}", aa.token_bridge_addr),
+        "message_fee_wei": message_fee.to_string(), "usd_value": 0.0_f64, "txid": format!("{txid:#x}")
+    }), tx_note(&ctx.args))?;
+    Ok(Ok(txid))
+}
+
+async fn attest<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    aa: &AttestArgs,
+    w: &WalletRecord,
+    idx: u32,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let mode = ctx.shared.cfg.effective_network_mode();
+    let token_s = format!("{:#x}", aa.token_addr);
+    let wrapped_mint = sol_wrapped_mint_pda(
+        &sol_token_bridge_program_id(mode)?,
+        aa.src_wh_chain_id,
+        evm_address_to_bytes32(aa.token_addr),
+    );
+    let sol = SolanaChain::new_with_fallbacks(
+        &ctx.shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(ctx.shared, mode),
+        &ctx.shared.cfg.http.jupiter_base_url,
+        ctx.shared.cfg.http.jupiter_api_key.as_deref(),
+        ctx.shared.cfg.rpc.solana_default_compute_unit_limit,
+        ctx.shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    );
+    if sol.get_account_optional(&wrapped_mint).await?.is_some() {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_ok(json!({
+              "chain": aa.chain, "to_chain": "solana", "token": token_s,
+              "already_attested": true, "wrapped_mint": wrapped_mint.to_string(),
+            })),
+        ));
+    }
+
+    let evm = setup_evm_chain(ctx.shared, &aa.chain)?;
+    let token_bridge_s = format!("{:#x}", aa.token_bridge_addr);
+    let summary = match aa.attest_txid {
+        None => format!(
+            "Wormhole attest {token_s} on {} -> solana: calls attestToken on the {} token bridge (gas + core message fee), then creates wrapped mint {wrapped_mint} on Solana (rent paid by this wallet). One-time per token.",
+            aa.chain, aa.chain
+        ),
+        Some(txid) => format!(
+            "Wormhole attest {token_s} on {} -> solana: completes attestation {txid:#x} by creating wrapped mint {wrapped_mint} on Solana (rent paid by this wallet).",
+            aa.chain
+        ),
+    };
+    let outcome = match maybe_confirm_write(
+        ctx.shared,
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        &WriteConfirmRequest {
+            tool: "attest_token",
+            wallet: Some(w.name.as_str()),
+            account_index: Some(idx),
+            op: WriteOp::Bridge,
+            chain: &aa.chain,
+            usd_value: 0.0_f64,
+            usd_value_known: false,
+            force_confirm: true,
+            slippage_bps: None,
+            to_address: Some(&token_bridge_s),
+            contract: Some(&token_bridge_s),
+            leverage: None,
+            summary: &summary,
+        },
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+
+    let txid = match aa.attest_txid {
+        Some(txid) => txid,
+        None => match send_attestation(ctx, &evm, aa, w, idx).await? {
+            Ok(txid) => txid,
+            Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+        },
+    };
+    let txid_s = format!("{txid:#x}");
+    let pending = |notes: &str| {
+        json!({
+          "chain": aa.chain, "to_chain": "solana", "token": token_s,
+          "already_attested": false, "wrapped_mint": wrapped_mint.to_string(),
+          "attest_txid": txid_s, "create_wrapped_signature": null,
+          "notes": format!("{notes}; re-run attest_token with attest_txid={txid_s} to finish"),
+        })
+    };
+
+    let Ok(receipt) = evm.wait_for_tx_receipt(txid, Duration::from_secs(60)).await else {
+        audit_log(
+            &ctx.shared.ks,
+            w,
+            idx,
+            &aa.chain,
+            &outcome,
+            &json!({ "txid": txid_s, "error_code": null, "result": "broadcasted" }),
+        );
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_ok(pending(
+                "attestation tx broadcasted, but receipt was not observed in time",
+            )),
+        ));
+    };
+    let Some((emitter_addr, sequence)) = extract_wormhole_message(&receipt) else {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "invalid_request",
+                format!("{txid_s} did not publish a wormhole message (not an attestToken tx?)"),
+            )),
+        ));
+    };
+    let emitter_hex = bytes32_hex(evm_address_to_bytes32(emitter_addr));
+    let mut vaa_error: Option<String> = None;
+    let vaa_bytes = poll_signed_vaa(
+        &ctx.shared.cfg.http.wormholescan_api_base_url,
+        aa.src_wh_chain_id,
+        &emitter_hex,
+        sequence,
+        &mut vaa_error,
+    )
+    .await
+    .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok());
+    let Some(vaa_bytes) = vaa_bytes else {
+        audit_log(
+            &ctx.shared.ks,
+            w,
+            idx,
+            &aa.chain,
+            &outcome,
+            &json!({ "txid": txid_s, "error_code": null, "result": "broadcasted" }),
+        );
+        let why = vaa_error.unwrap_or_else(|| {
+            "signed VAA not available yet (guardians sign after source-chain finality)".to_owned()
+        });
+        return Ok(ok(ctx.req_id.clone(), tool_ok(pending(&why))));
+    };
+
+    let sig = match wormhole_solana::create_wrapped_on_solana(
+        ctx.shared,
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        wormhole_solana::CreateWrappedParams {
+            wallet: w,
+            account_index: idx,
+            vaa_bytes: &vaa_bytes,
+        },
+    )
+    .await
+    {
+        Ok((sig, _mint)) => sig,
+        Err(e) => {
+            audit_log(
+                &ctx.shared.ks,
+                w,
+                idx,
+                &aa.chain,
+                &outcome,
+                &json!({ "txid": txid_s, "error_code": "create_wrapped_failed", "result": "partial" }),
+            );
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_ok(pending(&format!("create_wrapped on solana failed: {e:#}"))),
+            ));
+        }
+    };
+    ctx.shared.ks.append_tx_history(
+        &json!({
+            "ts": utc_now_iso(), "day": Keystore::current_utc_day_key(),
+            "type": "attest_token_create_wrapped", "chain": "solana", "wallet": w.name,
+            "account_index": idx, "provider": "wormhole", "token": token_s,
+            "wrapped_mint": wrapped_mint.to_string(), "attest_txid": txid_s, "usd_value": 0.0_f64,
+            "txid": sig
+        }),
+        tx_note(&ctx.args),
+    )?;
+    audit_log(
+        &ctx.shared.ks,
+        w,
+        idx,
+        &aa.chain,
+        &outcome,
+        &json!({ "txid": txid_s, "create_wrapped_signature": sig, "error_code": null, "result": "broadcasted" }),
+    );
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "chain": aa.chain, "to_chain": "solana", "token": token_s,
+          "already_attested": false, "wrapped_mint": wrapped_mint.to_string(),
+          "attest_txid": txid_s, "create_wrapped_signature": sig,
+          "wormhole": { "source_chain_id": aa.src_wh_chain_id, "emitter": emitter_hex, "sequence": sequence },
+        })),
+    ))
+}
+
+/// `attest_token`: register an EVM token with the Wormhole token bridge and create its wrapped
+/// mint on Solana, so `bridge_tokens` can deliver it there. Always confirmed: it costs fees on
+/// both chains and creates permanent on-chain state.
+pub async fn handle<R, W>(ctx: &mut HandlerCtx<'_, R, W>) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let aa = match parse_attest_args(&ctx.args) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let lock = ctx.shared.acquire_write_lock().await?;
+    let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+    let resp = attest(ctx, &aa, &w, idx).await;
+    Keystore::release_lock(lock)?;
+    resp
+}
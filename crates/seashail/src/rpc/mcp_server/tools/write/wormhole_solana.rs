@@ -1,4 +1,5 @@
-// VAA redemption flow (redeem_transfer_vaa_to_solana).
+// VAA redemption flow (redeem_transfer_vaa_to_solana) and wrapped-asset creation
+// (create_wrapped_on_solana).
 
 use alloy::network::TransactionBuilder as _;
 use alloy::primitives::{keccak256, U256};
@@ -33,10 +34,12 @@ use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
 use super::super::super::{ConnState, SharedState};
 use super::super::helpers::{
     evm_addr_for_account, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+    tx_note,
 };
 use super::super::key_loading::{load_evm_signer, load_solana_keypair};
 use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmRequest};
 use super::super::value_helpers::{parse_usd_value, summarize_sim_error};
+use super::bridge_quote::{self, BridgeQuote};
 
 sol! {
     contract IWormholeTokenBridge {
@@ -76,7 +79,11 @@ const SOL_TOKEN_BRIDGE_MAINNET: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBC
 const SOL_CORE_BRIDGE_DEVNET: &str = "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o";
 const SOL_TOKEN_BRIDGE_DEVNET: &str = "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE";
 
-const USDC_MINT_MAINNET: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub(super) const USDC_MINT_MAINNET: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+// Metaplex token metadata program (same ID on mainnet and devnet). `create_wrapped` writes the
+// wrapped mint's name/symbol there.
+const METAPLEX_TOKEN_METADATA: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
 
 fn arg_str<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
     args.get(key)
@@ -207,6 +214,13 @@ fn token_bridge_wrapped_meta(token_bridge: &Pubkey, mint: &Pubkey) -> Pubkey {
     pda(token_bridge, &[b"meta", mint.as_ref()])
 }
 
+fn metaplex_metadata(metadata_program: &Pubkey, mint: &Pubkey) -> Pubkey {
+    pda(
+        metadata_program,
+        &[b"metadata", metadata_program.as_ref(), mint.as_ref()],
+    )
+}
+
 #[derive(Debug, Clone)]
 struct VaaSig {
     guardian_index: u8,
@@ -393,6 +407,7 @@ enum TokenBridgeIx {
     CompleteWrapped = 3,
     TransferWrapped = 4,
     TransferNative = 5,
+    CreateWrapped = 7,
 }
 
 fn borsh_ix<T: BorshSerialize>(tag: u8, data: &T) -> eyre::Result<Vec<u8>> {
@@ -793,7 +808,8 @@ where
         .context("get wrapped mint (optional)")?;
     let Some(mint_acc) = mint_acc else {
         eyre::bail!(
-            "wrapped mint not found on solana; token may not be attested yet (create wrapped mint first)"
+            "wrapped mint {wrapped_mint} not found on solana: token 0x{} (wormhole chain {token_chain}) has not been attested to Solana yet. Run attest_token for it on its source chain (one-time), then redeem this VAA again",
+            hex::encode(token_address.get(12..).unwrap_or_default())
         );
     };
     let token_program = mint_acc.owner;
@@ -841,6 +857,129 @@ where
     Ok(sig.to_string())
 }
 
+fn parse_attest_payload(payload: &[u8]) -> eyre::Result<(u16, [u8; 32])> {
+    // Wormhole token bridge attestation payload id 2:
+    // 2 | token_address[32] | token_chain[2] | decimals | symbol[32] | name[32]
+    if payload.len() < 1 + 32 + 2 + 1 + 32 + 32 {
+        eyre::bail!("attestation payload too short");
+    }
+    if payload.first() != Some(&2) {
+        eyre::bail!("unexpected attestation payload id");
+    }
+    let token_address: [u8; 32] = payload
+        .get(1..33)
+        .ok_or_else(|| eyre::eyre!("token_address out of bounds"))?
+        .try_into()
+        .context("token_address conversion")?;
+    let token_chain_bytes: [u8; 2] = payload
+        .get(33..35)
+        .ok_or_else(|| eyre::eyre!("token_chain out of bounds"))?
+        .try_into()
+        .context("token_chain conversion")?;
+    Ok((u16::from_be_bytes(token_chain_bytes), token_address))
+}
+
+pub struct CreateWrappedParams<'a> {
+    pub wallet: &'a crate::wallet::WalletRecord,
+    pub account_index: u32,
+    pub vaa_bytes: &'a [u8],
+}
+
+/// Post an attestation VAA and run the token bridge's `create_wrapped`, creating the wrapped
+/// mint (plus its meta and Metaplex metadata accounts) on Solana. The wallet account pays the
+/// rent. Returns `(signature, wrapped_mint)`.
+pub async fn create_wrapped_on_solana<R, W>(
+    shared: &mut SharedState,
+    conn: &mut ConnState,
+    stdin: &mut tokio::io::Lines<BufReader<R>>,
+    stdout: &mut W,
+    params: CreateWrappedParams<'_>,
+) -> eyre::Result<(String, Pubkey)>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let CreateWrappedParams {
+        wallet,
+        account_index,
+        vaa_bytes,
+    } = params;
+    let mode = shared.cfg.effective_network_mode();
+    let (core_bridge, token_bridge) = sol_wormhole_program_ids(mode)?;
+    let metadata_program = SolanaChain::parse_pubkey(METAPLEX_TOKEN_METADATA)
+        .context("parse metaplex token metadata program id")?;
+    let sol = SolanaChain::new_with_fallbacks(
+        &shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(shared, mode),
+        &shared.cfg.http.jupiter_base_url,
+        shared.cfg.http.jupiter_api_key.as_deref(),
+        shared.cfg.rpc.solana_default_compute_unit_limit,
+        shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    );
+
+    let parsed = parse_vaa(vaa_bytes).context("parse vaa")?;
+    let (token_chain, token_address) =
+        parse_attest_payload(&parsed.payload).context("parse attestation payload")?;
+    let wrapped_mint = token_bridge_wrapped_mint(&token_bridge, token_chain, token_address);
+    if sol
+        .get_account_optional(&wrapped_mint)
+        .await
+        .context("get wrapped mint (optional)")?
+        .is_some()
+    {
+        eyre::bail!("wrapped mint {wrapped_mint} already exists on solana");
+    }
+
+    let ix = Instruction {
+        program_id: token_bridge,
+        accounts: vec![
+            AccountMeta::new(sol_pubkey_for_account(wallet, account_index)?, true),
+            AccountMeta::new_readonly(token_bridge_config(&token_bridge), false),
+            AccountMeta::new_readonly(
+                token_bridge_endpoint(&token_bridge, parsed.emitter_chain, parsed.emitter_address),
+                false,
+            ),
+            AccountMeta::new_readonly(
+                core_bridge_posted_vaa(
+                    &core_bridge,
+                    core_body_hash(&core_post_vaa_from_parsed(&parsed)),
+                ),
+                false,
+            ),
+            AccountMeta::new(
+                core_bridge_claim(
+                    &core_bridge,
+                    parsed.emitter_chain,
+                    parsed.emitter_address,
+                    parsed.sequence,
+                ),
+                false,
+            ),
+            AccountMeta::new(wrapped_mint, false),
+            AccountMeta::new(
+                token_bridge_wrapped_meta(&token_bridge, &wrapped_mint),
+                false,
+            ),
+            AccountMeta::new(metaplex_metadata(&metadata_program, &wrapped_mint), false),
+            AccountMeta::new_readonly(token_bridge_mint_signer(&token_bridge), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+            AccountMeta::new_readonly(core_bridge, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(metadata_program, false),
+        ],
+        data: borsh_ix(TokenBridgeIx::CreateWrapped as u8, &TokenBridgeEmpty)
+            .context("encode create_wrapped")?,
+    };
+
+    let kp = load_solana_keypair(shared, conn, stdin, stdout, wallet, account_index).await?;
+    let sig = post_vaa_and_run_token_bridge_ix(&sol, &kp, core_bridge, &parsed, vec![], ix).await?;
+    Ok((sig.to_string(), wrapped_mint))
+}
+
 // Validated and parsed arguments for the handle function.
 struct HandleArgs<'a> {
     to_chain: &'a str,
@@ -1081,25 +1220,29 @@ struct BridgeHistoryParams<'a> {
     usd_value: f64,
     usd_value_known: bool,
     outcome: &'a super::super::policy_confirm::WriteConfirmOutcome,
+    note: Option<&'a str>,
 }
 
 fn record_bridge_history(shared: &SharedState, p: &BridgeHistoryParams<'_>) -> eyre::Result<()> {
-    shared.ks.append_tx_history(&json!({
-      "ts": utc_now_iso(),
-      "day": Keystore::current_utc_day_key(),
-      "type": "bridge",
-      "chain": "solana",
-      "wallet": p.w.name,
-      "account_index": p.idx,
-      "provider": "wormhole",
-      "to_chain": p.to_chain,
-      "token": p.token_mint_s,
-      "amount_base": p.amount_base_u64.to_string(),
-      "amount_units": p.units,
-      "recipient": format!("{:#x}", p.recipient_evm),
-      "txid": p.sig.to_string(),
-      "bridge_id": p.bridge_id
-    }))?;
+    shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": "bridge",
+          "chain": "solana",
+          "wallet": p.w.name,
+          "account_index": p.idx,
+          "provider": "wormhole",
+          "to_chain": p.to_chain,
+          "token": p.token_mint_s,
+          "amount_base": p.amount_base_u64.to_string(),
+          "amount_units": p.units,
+          "recipient": format!("{:#x}", p.recipient_evm),
+          "txid": p.sig.to_string(),
+          "bridge_id": p.bridge_id
+        }),
+        p.note,
+    )?;
     let _audit_log = shared.ks.append_audit_log(&json!({
       "ts": utc_now_iso(),
       "tool": "bridge_tokens",
@@ -1132,7 +1275,7 @@ async fn poll_vaa_b64(
     }
     for _ in 0..60_u32 {
         match fetch_signed_vaa_bytes_b64(
-            &shared.cfg.http.wormholescan_api_base_url,
+            shared.cfg.http.wormholescan_base_url(),
             1,
             emitter_hex,
             sequence,
@@ -1172,7 +1315,7 @@ where
     R: tokio::io::AsyncRead + Unpin,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    let dst_lock = shared.ks.acquire_write_lock()?;
+    let dst_lock = shared.acquire_write_lock().await?;
     let dst_rpc_url = shared
         .cfg
         .rpc
@@ -1300,6 +1443,7 @@ struct HandlePrepared {
     usd_value: f64,
     usd_value_known: bool,
     outcome: super::super::policy_confirm::WriteConfirmOutcome,
+    quote: BridgeQuote,
     recipient_evm: alloy::primitives::Address,
     core_bridge: Pubkey,
     token_bridge: Pubkey,
@@ -1355,12 +1499,39 @@ where
         .map_err(|e| rpc_err(req_id, "invalid_request", &e))?;
     let (usd_value, usd_value_known) =
         compute_bridge_usd_value(shared, &sol, validated.token_mint_s, amount_base_u64, args).await;
+    let (policy, _) = shared.cfg.policy_for_wallet(Some(w.name.as_str()));
+    let mut quote = BridgeQuote::wormhole(
+        validated.token_mint_s,
+        mint_decimals,
+        u128::from(amount_base_u64),
+        usd_value_known.then_some(usd_value),
+    );
+    quote.source_fee_usd = bridge_quote::wormhole_source_fee_usd(shared, "solana", false).await;
+    if validated.redeem {
+        quote.destination_fee_usd =
+            bridge_quote::wormhole_redeem_fee_usd(shared, validated.to_chain).await;
+        bridge_quote::check_destination_gas(
+            shared,
+            conn,
+            &w,
+            idx,
+            validated.to_chain,
+            args,
+            &mut quote,
+        )
+        .await
+        .map_err(|te| ok(req_id.clone(), tool_err(te)))?;
+    }
+    quote
+        .check(&policy)
+        .map_err(|te| ok(req_id.clone(), tool_err(te)))?;
     let summary = format!(
-        "Wormhole bridge on solana -> {}: mint={} amount={} (units={})",
+        "Wormhole bridge on solana -> {}: mint={} amount={} (units={})\n{}",
         validated.to_chain,
         validated.token_mint_s,
         validated.amount_s.trim(),
         validated.units,
+        quote.summary(&policy),
     );
     let outcome = maybe_confirm_write(
         shared,
@@ -1405,6 +1576,7 @@ where
         usd_value,
         usd_value_known,
         outcome,
+        quote,
         recipient_evm,
         core_bridge,
         token_bridge,
@@ -1523,7 +1695,7 @@ where
         Err(resp) => return Ok(*resp),
     };
 
-    let lock = shared.ks.acquire_write_lock()?;
+    let lock = shared.acquire_write_lock().await?;
 
     let prepared =
         match prepare_handle(&req_id, &args, &validated, shared, conn, stdin, stdout).await {
@@ -1566,6 +1738,7 @@ where
             usd_value: prepared.usd_value,
             usd_value_known: prepared.usd_value_known,
             outcome: &prepared.outcome,
+            note: tx_note(&args),
         },
     )?;
 
@@ -1596,6 +1769,7 @@ where
           "token": validated.token_mint_s,
           "amount_base": prepared.amount_base_u64.to_string(),
           "usd_value": prepared.usd_value,
+          "quote": prepared.quote.to_json(),
           "txid": sig.to_string(),
           "bridge_id": bridge_id,
           "wormhole": {
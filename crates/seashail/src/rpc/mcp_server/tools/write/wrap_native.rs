@@ -0,0 +1,564 @@
+use alloy::primitives::U256;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use eyre::Context as _;
+use serde_json::{json, Value};
+use solana_sdk::{program_pack::Pack as _, pubkey::Pubkey};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use tokio::time::Duration;
+
+use crate::{
+    amount,
+    chains::{evm::u256_low_u128, native::native_token, solana::SolanaChain},
+    errors::ToolError,
+    financial_math,
+    keystore::{utc_now_iso, Keystore},
+    policy_engine::WriteOp,
+    price,
+    wallet::WalletRecord,
+};
+
+use super::super::super::jsonrpc::{ok, tool_err, tool_ok, JsonRpcResponse};
+use super::super::super::state::effective_network_mode;
+use super::super::super::SharedState;
+use super::super::helpers::{
+    evm_addr_for_account, resolve_wallet_and_account, sol_pubkey_for_account, solana_fallback_urls,
+    tx_note, u128_to_u256, u128_to_u64,
+};
+use super::super::key_loading::{load_evm_signer, load_solana_keypair};
+use super::super::policy_confirm::{maybe_confirm_write, WriteConfirmOutcome, WriteConfirmRequest};
+use super::common::summarize_sim_error;
+use super::swap_tokens::setup_evm_chain;
+use super::HandlerCtx;
+
+sol! {
+    #[sol(rpc)]
+    contract IWrappedNative {
+        function deposit() external payable;
+        function withdraw(uint256 wad) external;
+    }
+}
+
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const SOL_DECIMALS: u8 = 9;
+const EVM_NATIVE_DECIMALS: u8 = 18;
+
+struct WrapArgs {
+    chain: String,
+    /// `None` means the full wrapped balance (unwrap only).
+    amount_s: Option<String>,
+    units: String,
+    wrap: bool,
+}
+
+impl WrapArgs {
+    const fn action(&self) -> &'static str {
+        if self.wrap {
+            "wrap"
+        } else {
+            "unwrap"
+        }
+    }
+
+    const fn tool(&self) -> &'static str {
+        if self.wrap {
+            "wrap_native"
+        } else {
+            "unwrap_native"
+        }
+    }
+
+    fn parse_amount(&self, decimals: u8) -> Result<Option<u128>, ToolError> {
+        let Some(s) = self.amount_s.as_deref() else {
+            return Ok(None);
+        };
+        let parsed = if self.units == "base" {
+            amount::parse_amount_base_u128(s)
+        } else {
+            amount::parse_amount_ui_to_base_u128(s, u32::from(decimals))
+        }
+        .map_err(|e| ToolError::new("invalid_request", format!("invalid amount: {e:#}")))?;
+        if parsed == 0 {
+            return Err(ToolError::new("invalid_request", "amount must be > 0"));
+        }
+        Ok(Some(parsed))
+    }
+}
+
+fn parse_args(tool_name: &str, args: &Value) -> Result<WrapArgs, ToolError> {
+    let chain = args
+        .get("chain")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("")
+        .to_owned();
+    if chain.is_empty() || chain == "bitcoin" {
+        return Err(ToolError::new(
+            "invalid_request",
+            "chain must be solana or an EVM chain",
+        ));
+    }
+    let wrap = tool_name == "wrap_native";
+    let amount_s = args
+        .get("amount")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case("max"))
+        .map(ToOwned::to_owned);
+    if wrap && amount_s.is_none() {
+        return Err(ToolError::new(
+            "invalid_request",
+            "wrap_native requires an explicit amount (max is not supported; keep native for fees)",
+        ));
+    }
+    let units = args
+        .get("amount_units")
+        .and_then(Value::as_str)
+        .unwrap_or("ui")
+        .to_owned();
+    Ok(WrapArgs {
+        chain,
+        amount_s,
+        units,
+        wrap,
+    })
+}
+
+async fn native_usd(shared: &mut SharedState, chain: &str, base: u128) -> (f64, bool) {
+    shared.ensure_db().await;
+    let decimals = if chain == "solana" {
+        SOL_DECIMALS
+    } else {
+        EVM_NATIVE_DECIMALS
+    };
+    match price::native_token_price_usd_cached(chain, &shared.cfg, shared.db()).await {
+        Ok(p) => (
+            financial_math::token_base_to_usd(base, decimals, p.usd),
+            true,
+        ),
+        Err(_e) => (0.0_f64, false),
+    }
+}
+
+fn ui(base: u128, decimals: u8) -> String {
+    amount::format_amount_base_to_ui_string(base, u32::from(decimals))
+        .unwrap_or_else(|_| base.to_string())
+}
+
+/// Common audit fields for a wrap/unwrap attempt.
+fn audit_base(
+    tool_name: &str,
+    w: &WalletRecord,
+    idx: u32,
+    chain: &str,
+    usd: (f64, bool),
+    outcome: &WriteConfirmOutcome,
+) -> Value {
+    json!({
+      "ts": utc_now_iso(),
+      "tool": tool_name,
+      "wallet": w.name,
+      "account_index": idx,
+      "chain": chain,
+      "usd_value": usd.0,
+      "usd_value_known": usd.1,
+      "policy_decision": outcome.policy_decision,
+      "confirm_required": outcome.confirm_required,
+      "confirm_result": outcome.confirm_result,
+      "daily_used_usd": outcome.daily_used_usd,
+      "forced_confirm": outcome.forced_confirm
+    })
+}
+
+fn with_fields(mut base: Value, extra: &Value) -> Value {
+    if let (Some(b), Some(e)) = (base.as_object_mut(), extra.as_object()) {
+        for (k, v) in e {
+            b.insert(k.clone(), v.clone());
+        }
+    }
+    base
+}
+
+async fn confirm<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    a: &WrapArgs,
+    w: &WalletRecord,
+    idx: u32,
+    usd: (f64, bool),
+    contract: Option<&str>,
+    summary: &str,
+) -> Result<WriteConfirmOutcome, ToolError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    maybe_confirm_write(
+        ctx.shared,
+        ctx.conn,
+        ctx.stdin,
+        ctx.stdout,
+        &WriteConfirmRequest {
+            tool: a.tool(),
+            wallet: Some(w.name.as_str()),
+            account_index: Some(idx),
+            op: WriteOp::Swap,
+            chain: &a.chain,
+            usd_value: usd.0,
+            usd_value_known: usd.1,
+            force_confirm: false,
+            slippage_bps: None,
+            to_address: contract,
+            contract,
+            leverage: None,
+            summary,
+        },
+    )
+    .await
+}
+
+async fn handle_evm<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    a: &WrapArgs,
+    w: &WalletRecord,
+    idx: u32,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let evm = setup_evm_chain(ctx.shared, &a.chain)?;
+    let Some(weth) = evm.uniswap.as_ref().map(|u| u.wrapped_native) else {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "unsupported_chain",
+                format!("no known wrapped native token on {}", a.chain),
+            )),
+        ));
+    };
+    let weth_s = format!("{weth:#x}");
+    let owner = evm_addr_for_account(w, idx)?;
+    let native_sym = native_token(&a.chain).symbol;
+    let (wrapped_bal, _dec, wrapped_sym) = evm
+        .get_erc20_balance(weth, owner)
+        .await
+        .context("read wrapped balance")?;
+    let wrapped_bal = u256_low_u128(wrapped_bal);
+
+    let requested = match a.parse_amount(EVM_NATIVE_DECIMALS) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    // Unwrap defaults to the full wrapped balance.
+    let amount_base = requested.unwrap_or(wrapped_bal);
+    if !a.wrap && (amount_base == 0 || amount_base > wrapped_bal) {
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "insufficient_balance",
+                format!(
+                    "wrapped balance is {} {wrapped_sym}",
+                    ui(wrapped_bal, EVM_NATIVE_DECIMALS)
+                ),
+            )),
+        ));
+    }
+    let amount_wei = u128_to_u256(amount_base);
+
+    let contract = IWrappedNative::new(weth, evm.provider()?);
+    let (input, value) = if a.wrap {
+        (contract.deposit().calldata().clone(), amount_wei)
+    } else {
+        (contract.withdraw(amount_wei).calldata().clone(), U256::ZERO)
+    };
+    let tx = TransactionRequest {
+        from: Some(owner),
+        to: Some(weth.into()),
+        value: Some(value),
+        input: input.into(),
+        ..Default::default()
+    };
+
+    let usd = native_usd(ctx.shared, &a.chain, amount_base).await;
+    let (from_sym, to_sym) = if a.wrap {
+        (native_sym, wrapped_sym.as_str())
+    } else {
+        (wrapped_sym.as_str(), native_sym)
+    };
+    let summary = format!(
+        "{} {} {from_sym} -> {to_sym} on {} ({weth_s})",
+        if a.wrap { "Wrap" } else { "Unwrap" },
+        ui(amount_base, EVM_NATIVE_DECIMALS),
+        a.chain
+    );
+    let outcome = match confirm(ctx, a, w, idx, usd, Some(&weth_s), &summary).await {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let audit = audit_base(a.tool(), w, idx, &a.chain, usd, &outcome);
+
+    if let Err(e) = evm.simulate_tx_strict(&tx).await {
+        let _audit_log = ctx.shared.ks.append_audit_log(&with_fields(
+            audit,
+            &json!({ "txid": null, "error_code": "simulation_failed", "result": "simulation_failed" }),
+        ));
+        return Ok(ok(
+            ctx.req_id.clone(),
+            tool_err(ToolError::new(
+                "simulation_failed",
+                summarize_sim_error(&e, a.tool()),
+            )),
+        ));
+    }
+
+    let signer = load_evm_signer(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
+    let txid = evm.send_tx(signer, tx).await?;
+    let txid_s = format!("{txid:#x}");
+    let confirmed = evm
+        .wait_for_tx_receipt(txid, Duration::from_secs(60))
+        .await
+        .is_ok();
+    let wrapped_after = evm
+        .get_erc20_balance(weth, owner)
+        .await
+        .ok()
+        .map(|(b, _d, _s)| u256_low_u128(b));
+
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": a.action(),
+          "chain": a.chain,
+          "wallet": w.name,
+          "account_index": idx,
+          "token": weth_s,
+          "amount_base": amount_base.to_string(),
+          "usd_value": usd.0,
+          "txid": txid_s
+        }),
+        tx_note(&ctx.args),
+    )?;
+    let _audit_log = ctx.shared.ks.append_audit_log(&with_fields(
+        audit,
+        &json!({ "txid": txid_s, "error_code": null, "result": "broadcasted", "contract": weth_s }),
+    ));
+
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "chain": a.chain,
+          "action": a.action(),
+          "wrapped_token": weth_s,
+          "amount_base": amount_base.to_string(),
+          "amount": ui(amount_base, EVM_NATIVE_DECIMALS),
+          "usd_value": usd.0,
+          "txid": txid_s,
+          "confirmed": confirmed,
+          "wrapped_balance": {
+            "symbol": wrapped_sym,
+            "decimals": EVM_NATIVE_DECIMALS,
+            "amount_base": wrapped_after.map(|b| b.to_string()),
+            "amount": wrapped_after.map(|b| ui(b, EVM_NATIVE_DECIMALS))
+          }
+        })),
+    ))
+}
+
+fn setup_solana<R, W>(ctx: &HandlerCtx<'_, R, W>) -> SolanaChain {
+    let mode = effective_network_mode(ctx.shared, ctx.conn);
+    SolanaChain::new_with_fallbacks(
+        &ctx.shared.cfg.rpc.solana_rpc_url,
+        solana_fallback_urls(ctx.shared, mode),
+        &ctx.shared.cfg.http.jupiter_base_url,
+        ctx.shared.cfg.http.jupiter_api_key.as_deref(),
+        ctx.shared.cfg.rpc.solana_default_compute_unit_limit,
+        ctx.shared
+            .cfg
+            .rpc
+            .solana_default_compute_unit_price_micro_lamports,
+    )
+}
+
+/// Current wSOL token account state: (token amount, account lamports). `None` if it doesn't exist.
+async fn wsol_account(sol: &SolanaChain, ata: &Pubkey) -> eyre::Result<Option<(u64, u64)>> {
+    let Some(acc) = sol.get_account_optional(ata).await? else {
+        return Ok(None);
+    };
+    let token = spl_token::state::Account::unpack(&acc.data).context("unpack wSOL account")?;
+    Ok(Some((token.amount, acc.lamports)))
+}
+
+async fn handle_solana<R, W>(
+    ctx: &mut HandlerCtx<'_, R, W>,
+    a: &WrapArgs,
+    w: &WalletRecord,
+    idx: u32,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let sol = setup_solana(ctx);
+    let owner = sol_pubkey_for_account(w, idx)?;
+    let mint = SolanaChain::parse_pubkey(WSOL_MINT)?;
+    let ata = get_associated_token_address(&owner, &mint);
+    let before = wsol_account(&sol, &ata).await?;
+
+    let requested = match a.parse_amount(SOL_DECIMALS) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+
+    let (lamports, ixs) = if a.wrap {
+        let lamports = u128_to_u64(requested.unwrap_or_default())?;
+        let ixs = vec![
+            create_associated_token_account_idempotent(&owner, &owner, &mint, &spl_token::id()),
+            solana_system_interface::instruction::transfer(&owner, &ata, lamports),
+            spl_token::instruction::sync_native(&spl_token::id(), &ata)
+                .context("build sync_native")?,
+        ];
+        (lamports, ixs)
+    } else {
+        let Some((balance, _)) = before.filter(|(bal, _)| *bal > 0) else {
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new("insufficient_balance", "no wSOL to unwrap")),
+            ));
+        };
+        // Closing the token account is the only way to unwrap; it releases the whole balance.
+        if requested.is_some_and(|r| r != u128::from(balance)) {
+            return Ok(ok(
+                ctx.req_id.clone(),
+                tool_err(ToolError::new(
+                    "invalid_request",
+                    format!(
+                        "Solana unwrap closes the wSOL account and unwraps the full balance ({} wSOL); omit amount or pass max",
+                        ui(u128::from(balance), SOL_DECIMALS)
+                    ),
+                )),
+            ));
+        }
+        let ixs = vec![spl_token::instruction::close_account(
+            &spl_token::id(),
+            &ata,
+            &owner,
+            &owner,
+            &[],
+        )
+        .context("build close_account")?];
+        (balance, ixs)
+    };
+
+    let usd = native_usd(ctx.shared, "solana", u128::from(lamports)).await;
+    let summary = if a.wrap {
+        format!(
+            "Wrap {} SOL -> wSOL on solana{}",
+            ui(u128::from(lamports), SOL_DECIMALS),
+            if before.is_none() {
+                " (creates the wSOL token account; its rent deposit is returned on unwrap)"
+            } else {
+                ""
+            }
+        )
+    } else {
+        format!(
+            "Unwrap {} wSOL -> SOL on solana (closes the wSOL token account and reclaims its rent)",
+            ui(u128::from(lamports), SOL_DECIMALS)
+        )
+    };
+    let outcome = match confirm(ctx, a, w, idx, usd, None, &summary).await {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let audit = audit_base(a.tool(), w, idx, "solana", usd, &outcome);
+
+    let kp = load_solana_keypair(ctx.shared, ctx.conn, ctx.stdin, ctx.stdout, w, idx).await?;
+    let sig = sol
+        .sign_and_send_instructions(&kp, ixs)
+        .await
+        .context("send wSOL tx")?;
+    let sig_s = sig.to_string();
+
+    // Token accounts hold a rent-exempt reserve on top of the wrapped amount.
+    let after = wsol_account(&sol, &ata).await.ok().flatten();
+    let rent = if a.wrap {
+        json!({
+          "account_created": before.is_none(),
+          "rent_deposit_lamports": after.map(|(amt, lamports)| lamports.saturating_sub(amt))
+        })
+    } else {
+        json!({
+          "account_closed": true,
+          "rent_reclaimed_lamports": before.map(|(amt, lamports)| lamports.saturating_sub(amt))
+        })
+    };
+    let wrapped_after = after.map_or(0, |(amt, _)| amt);
+
+    ctx.shared.ks.append_tx_history(
+        &json!({
+          "ts": utc_now_iso(),
+          "day": Keystore::current_utc_day_key(),
+          "type": a.action(),
+          "chain": "solana",
+          "wallet": w.name,
+          "account_index": idx,
+          "token": WSOL_MINT,
+          "amount_base": lamports.to_string(),
+          "usd_value": usd.0,
+          "signature": sig_s
+        }),
+        tx_note(&ctx.args),
+    )?;
+    let _audit_log = ctx.shared.ks.append_audit_log(&with_fields(
+        audit,
+        &json!({ "txid": sig_s, "error_code": null, "result": "broadcasted" }),
+    ));
+
+    Ok(ok(
+        ctx.req_id.clone(),
+        tool_ok(json!({
+          "chain": "solana",
+          "action": a.action(),
+          "wrapped_token": WSOL_MINT,
+          "amount_base": lamports.to_string(),
+          "amount": ui(u128::from(lamports), SOL_DECIMALS),
+          "usd_value": usd.0,
+          "signature": sig_s,
+          "rent": rent,
+          "wrapped_balance": {
+            "symbol": "wSOL",
+            "decimals": SOL_DECIMALS,
+            "amount_base": wrapped_after.to_string(),
+            "amount": ui(u128::from(wrapped_after), SOL_DECIMALS)
+          }
+        })),
+    ))
+}
+
+/// `wrap_native` / `unwrap_native`: ETH<->WETH (and other EVM wrapped natives) via
+/// `deposit`/`withdraw`, SOL<->wSOL via transfer + `SyncNative` / `CloseAccount`.
+pub async fn handle<R, W>(
+    tool_name: &str,
+    ctx: &mut HandlerCtx<'_, R, W>,
+) -> eyre::Result<JsonRpcResponse>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send,
+{
+    let a = match parse_args(tool_name, &ctx.args) {
+        Ok(v) => v,
+        Err(te) => return Ok(ok(ctx.req_id.clone(), tool_err(te))),
+    };
+    let lock = ctx.shared.acquire_write_lock().await?;
+    let (w, idx) = resolve_wallet_and_account(ctx.shared, &ctx.args)?;
+    let resp = if a.chain == "solana" {
+        handle_solana(ctx, &a, &w, idx).await
+    } else {
+        handle_evm(ctx, &a, &w, idx).await
+    };
+    Keystore::release_lock(lock)?;
+    resp
+}
@@ -150,7 +150,7 @@ where
             // immediately after startup, before any wallet-dependent tool call.
             {
                 let guard = shared.lock().await;
-                if let Err(e) = guard.ks.ensure_default_wallet() {
+                if let Err(e) = guard.ensure_default_wallet().await {
                     warn!(error = %e, "ensure default wallet failed during initialize");
                 }
             }
@@ -164,7 +164,10 @@ where
             )
         }
         "ping" => mcp_server::ok(req.id, json!({})),
-        "tools/list" => mcp_server::ok(req.id, mcp_server::list_tools_result()),
+        "tools/list" => {
+            let guard = shared.lock().await;
+            mcp_server::ok(req.id, mcp_server::list_tools_result(&guard.cfg))
+        }
         "tools/call" => {
             let name = req
                 .params
@@ -173,6 +176,11 @@ where
                 .unwrap_or("");
             let args = req.params.get("arguments").cloned().unwrap_or(Value::Null);
             let id = req.id.clone();
+            conn.progress_token = req
+                .params
+                .get("_meta")
+                .and_then(|m| m.get("progressToken"))
+                .cloned();
 
             let mut guard = shared.lock().await;
             match mcp_server::handle_tools_call(id.clone(), name, args, &mut guard, conn, lines, w)
@@ -1,5 +1,7 @@
-use crate::paths::SeashailPaths;
+use crate::{config::UpgradeConfig, paths::SeashailPaths, store::ConfigStore};
+use base64::Engine as _;
 use chrono::Utc;
+use ed25519_dalek::{Signature, VerifyingKey};
 use eyre::Context as _;
 use fs2::FileExt as _;
 use rand::Rng as _;
@@ -10,6 +12,25 @@ use std::{
     process::Stdio,
 };
 
+/// Base64 Ed25519 public keys allowed to sign upgrade artifacts: release archives
+/// (`<asset>.ed25519`) and the hosted installer scripts (`<url>.sig`).
+///
+/// Compiled in so neither a compromised host nor `SEASHAIL_RELEASE_BASE_URL` /
+/// `SEASHAIL_INSTALL_URL` can swap what gets installed. List the next key alongside the current
+/// one to rotate.
+///
+/// Only the public half of the maintainers' key (the `SEASHAIL_RELEASE_SIGNING_KEY` CI secret)
+/// belongs here. Until it is pinned, every signature check fails and upgrades refuse to install.
+const RELEASE_SIGNING_PUBKEYS_B64: &[&str] = &[];
+
+/// Largest detached signature file we accept (a base64 signature is 88 bytes).
+const SIGNATURE_MAX_BYTES: usize = 4 * 1024;
+
+/// Largest release archive we download.
+const RELEASE_ARCHIVE_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+const RELEASE_BASE_URL: &str = "https://github.com/seashail/seashail/releases";
+
 #[derive(Debug, Clone, Copy)]
 pub struct UpgradeOpts {
     pub yes: bool,
@@ -60,7 +81,11 @@ fn should_auto_upgrade_by_default() -> bool {
     !is_probably_dev_binary()
 }
 
-fn auto_upgrade_enabled() -> bool {
+fn auto_upgrade_enabled(cfg: &UpgradeConfig) -> bool {
+    // The config opt-out is authoritative; env vars can only narrow it further.
+    if !cfg.auto {
+        return false;
+    }
     if let Some(v) = env_boolish("SEASHAIL_DISABLE_AUTO_UPGRADE") {
         if v {
             return false;
@@ -73,6 +98,19 @@ fn auto_upgrade_enabled() -> bool {
     }
 }
 
+/// Whether the running binary already is the pinned release. A leading `v` is ignored.
+fn pin_matches(pin: &str, current: &str) -> bool {
+    let strip = |s: &str| {
+        let s = s.trim();
+        s.strip_prefix('v').unwrap_or(s).to_owned()
+    };
+    strip(pin) == strip(current)
+}
+
+fn load_upgrade_config(paths: &SeashailPaths) -> eyre::Result<UpgradeConfig> {
+    Ok(ConfigStore::new(paths).load_or_init_default()?.upgrade)
+}
+
 fn state_path(paths: &SeashailPaths) -> PathBuf {
     paths.data_dir.join("auto-upgrade.json")
 }
@@ -97,31 +135,252 @@ fn write_state(path: &Path, st: &AutoUpgradeState) -> eyre::Result<()> {
     crate::fsutil::write_string_atomic_restrictive(path, &s, crate::fsutil::MODE_FILE_PRIVATE)
 }
 
-fn temp_script_path(parent: &Path, ext: &str) -> PathBuf {
+fn temp_path(parent: &Path, stem: &str, ext: &str) -> PathBuf {
     let mut rand_bytes = [0_u8; 8];
     rand::rng().fill_bytes(&mut rand_bytes);
     let suffix = hex::encode(rand_bytes);
-    parent.join(format!("seashail-installer-{suffix}.{ext}"))
+    parent.join(format!("{stem}-{suffix}.{ext}"))
 }
 
-async fn fetch_installer_bytes(url: &str) -> eyre::Result<Vec<u8>> {
+async fn fetch_bytes_capped(url: &str, what: &str, max_len: usize) -> eyre::Result<Vec<u8>> {
     let client = reqwest::Client::new();
-    let resp = client.get(url).send().await.context("fetch installer")?;
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("fetch {what}"))?;
     let status = resp.status();
     if !status.is_success() {
-        eyre::bail!("installer fetch failed with HTTP status {status}");
+        eyre::bail!("{what} fetch failed with HTTP status {status}");
     }
-    let bytes = resp.bytes().await.context("read installer bytes")?;
+    let bytes = resp
+        .bytes()
+        .await
+        .with_context(|| format!("read {what} bytes"))?;
     // Safety valve: refuse anything too large.
-    if bytes.len() > 2 * 1024 * 1024 {
-        eyre::bail!(
-            "installer script unexpectedly large ({} bytes)",
-            bytes.len()
-        );
+    if bytes.len() > max_len {
+        eyre::bail!("{what} unexpectedly large ({} bytes)", bytes.len());
     }
     Ok(bytes.to_vec())
 }
 
+async fn fetch_installer_bytes(url: &str) -> eyre::Result<Vec<u8>> {
+    fetch_bytes_capped(url, "installer script", 2 * 1024 * 1024).await
+}
+
+fn installer_signature_url(installer_url: &str) -> String {
+    format!("{installer_url}.sig")
+}
+
+/// Check `bytes` against a detached Ed25519 signature (base64, whitespace ignored) made by one of
+/// `pubkeys_b64`. Anything else — missing, malformed, or signed by another key — is an error.
+fn verify_release_signature(
+    what: &str,
+    bytes: &[u8],
+    sig_file: &[u8],
+    pubkeys_b64: &[&str],
+) -> eyre::Result<()> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let sig_text =
+        std::str::from_utf8(sig_file).with_context(|| format!("{what} signature is not text"))?;
+    let sig_text: String = sig_text.split_whitespace().collect();
+    let sig_bytes = b64
+        .decode(sig_text)
+        .with_context(|| format!("decode {what} signature"))?;
+    let sig_arr: [u8; 64] = sig_bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_e| eyre::eyre!("{what} signature must decode to 64 bytes"))?;
+    let sig = Signature::from_bytes(&sig_arr);
+
+    if pubkeys_b64.is_empty() {
+        eyre::bail!("no release signing key is pinned in this build; refusing to install {what}");
+    }
+    for pk in pubkeys_b64 {
+        let Ok(pk_bytes) = b64.decode(pk) else {
+            continue;
+        };
+        let Ok(pk_arr) = <[u8; 32]>::try_from(pk_bytes.as_slice()) else {
+            continue;
+        };
+        let Ok(vk) = VerifyingKey::from_bytes(&pk_arr) else {
+            continue;
+        };
+        if vk.verify_strict(bytes, &sig).is_ok() {
+            return Ok(());
+        }
+    }
+    eyre::bail!(
+        "{what} signature does not match any pinned release key ({} pinned); refusing to install it",
+        pubkeys_b64.len()
+    )
+}
+
+/// The `dist` target triple of the prebuilt archive for this platform, if one is published.
+fn release_target() -> Option<&'static str> {
+    match (std::env::consts::ARCH, std::env::consts::OS) {
+        ("x86_64", "linux") => Some("x86_64-unknown-linux-gnu"),
+        ("aarch64", "linux") => Some("aarch64-unknown-linux-gnu"),
+        ("x86_64", "macos") => Some("x86_64-apple-darwin"),
+        ("aarch64", "macos") => Some("aarch64-apple-darwin"),
+        ("x86_64", "windows") => Some("x86_64-pc-windows-msvc"),
+        ("aarch64", "windows") => Some("aarch64-pc-windows-msvc"),
+        _ => None,
+    }
+}
+
+fn release_asset_name(target: &str) -> String {
+    if target.contains("windows") {
+        format!("seashail-{target}.zip")
+    } else {
+        format!("seashail-{target}.tar.xz")
+    }
+}
+
+/// `<base>/latest/download/<asset>`, or `<base>/download/v<version>/<asset>` when pinned (the pin
+/// may be written with or without the leading `v`).
+fn release_asset_url(base: &str, pin: Option<&str>, asset: &str) -> String {
+    let base = base.trim().trim_end_matches('/');
+    match pin {
+        Some(v) => {
+            let v = v.trim();
+            let v = v.strip_prefix('v').unwrap_or(v);
+            format!("{base}/download/v{v}/{asset}")
+        }
+        None => format!("{base}/latest/download/{asset}"),
+    }
+}
+
+fn resolve_release_base_url() -> String {
+    std::env::var("SEASHAIL_RELEASE_BASE_URL")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| RELEASE_BASE_URL.into())
+}
+
+/// Depth-limited search for the binary inside an extracted archive (`dist` nests it one level).
+fn find_file_named(dir: &Path, name: &str, depth: u8) -> Option<PathBuf> {
+    let entries = fs::read_dir(dir).ok()?;
+    let mut subdirs = vec![];
+    for e in entries.flatten() {
+        let p = e.path();
+        if p.is_dir() {
+            subdirs.push(p);
+        } else if p.file_name().is_some_and(|f| f == name) {
+            return Some(p);
+        }
+    }
+    if depth == 0 {
+        return None;
+    }
+    subdirs
+        .iter()
+        .find_map(|d| find_file_named(d, name, depth - 1))
+}
+
+/// Swap `new_bin` in for the running executable: stage next to it, move the old one aside, then
+/// rename into place (rolling back if that fails).
+fn replace_current_exe(new_bin: &Path) -> eyre::Result<PathBuf> {
+    let exe = std::env::current_exe().context("resolve current executable")?;
+    let exe = fs::canonicalize(&exe).unwrap_or(exe);
+    let dir = exe
+        .parent()
+        .ok_or_else(|| eyre::eyre!("executable has no parent dir: {}", exe.display()))?;
+    let name = exe
+        .file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or("seashail");
+
+    let staged = temp_path(dir, ".seashail-upgrade", "new");
+    fs::copy(new_bin, &staged).with_context(|| format!("stage {}", staged.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("chmod {}", staged.display()))?;
+    }
+
+    let old = dir.join(format!(".{name}.old"));
+    drop(fs::remove_file(&old));
+    fs::rename(&exe, &old).with_context(|| format!("move aside {}", exe.display()))?;
+    if let Err(e) = fs::rename(&staged, &exe) {
+        drop(fs::rename(&old, &exe));
+        drop(fs::remove_file(&staged));
+        return Err(e).with_context(|| format!("install {}", exe.display()));
+    }
+    // Windows keeps the running image locked; the leftover is removed on the next upgrade.
+    drop(fs::remove_file(&old));
+    Ok(exe)
+}
+
+/// Download the prebuilt release archive, verify its signature against the pinned keys, and only
+/// then extract it and replace the running binary.
+async fn run_release_upgrade(
+    opts: UpgradeOpts,
+    tmp_parent: &Path,
+    pin: Option<&str>,
+) -> eyre::Result<()> {
+    let target =
+        release_target().ok_or_else(|| eyre::eyre!("no prebuilt release for this platform"))?;
+    let asset = release_asset_name(target);
+    let url = release_asset_url(&resolve_release_base_url(), pin, &asset);
+    let archive = fetch_bytes_capped(&url, "release archive", RELEASE_ARCHIVE_MAX_BYTES).await?;
+    let sig = fetch_bytes_capped(
+        &format!("{url}.ed25519"),
+        "release signature",
+        SIGNATURE_MAX_BYTES,
+    )
+    .await?;
+    verify_release_signature(
+        "release archive",
+        &archive,
+        &sig,
+        RELEASE_SIGNING_PUBKEYS_B64,
+    )?;
+
+    let work = temp_path(tmp_parent, "seashail-release", "d");
+    crate::fsutil::ensure_private_dir(&work)?;
+    let result = async {
+        let archive_path = work.join(&asset);
+        crate::fsutil::write_atomic_restrictive(
+            &archive_path,
+            &archive,
+            crate::fsutil::MODE_FILE_PRIVATE,
+        )?;
+        // `tar` ships with macOS, Linux and Windows 10+ and reads both .tar.xz and .zip.
+        let mut cmd = tokio::process::Command::new("tar");
+        cmd.arg("-xf").arg(&archive_path).arg("-C").arg(&work);
+        cmd.stdin(Stdio::null()).stdout(Stdio::null());
+        if opts.quiet {
+            cmd.stderr(Stdio::null());
+        } else {
+            cmd.stderr(Stdio::inherit());
+        }
+        let status = cmd.status().await.context("run tar")?;
+        if !status.success() {
+            eyre::bail!(
+                "extracting {asset} failed with exit code {:?}",
+                status.code()
+            );
+        }
+        let bin_name = if cfg!(windows) {
+            "seashail.exe"
+        } else {
+            "seashail"
+        };
+        let bin = find_file_named(&work, bin_name, 2)
+            .ok_or_else(|| eyre::eyre!("{asset} does not contain {bin_name}"))?;
+        let installed = replace_current_exe(&bin)?;
+        if !opts.quiet {
+            crate::cli_output::print_upgrade_installed(&installed, pin);
+        }
+        Ok(())
+    }
+    .await;
+    drop(fs::remove_dir_all(&work));
+    result
+}
+
 fn resolve_installer_url() -> String {
     if cfg!(windows) {
         std::env::var("SEASHAIL_INSTALL_URL")
@@ -153,12 +412,15 @@ fn is_homebrew_cellar_binary() -> bool {
 
 fn resolve_upgrade_method() -> &'static str {
     // Override knob:
-    // - SEASHAIL_UPGRADE_METHOD=brew|installer
+    // - SEASHAIL_UPGRADE_METHOD=brew|release|installer
     if let Ok(v) = std::env::var("SEASHAIL_UPGRADE_METHOD") {
         let v = v.trim().to_ascii_lowercase();
         if v == "brew" {
             return "brew";
         }
+        if v == "release" {
+            return "release";
+        }
         if v == "installer" {
             return "installer";
         }
@@ -168,6 +430,8 @@ fn resolve_upgrade_method() -> &'static str {
         return "brew";
     }
 
+    // Releases do not publish `<asset>.ed25519` signatures yet, so the signed prebuilt path stays
+    // opt-in (SEASHAIL_UPGRADE_METHOD=release) until they do.
     "installer"
 }
 
@@ -194,12 +458,29 @@ async fn run_brew_upgrade(opts: UpgradeOpts) -> eyre::Result<()> {
     Ok(())
 }
 
-async fn run_installer(opts: UpgradeOpts, tmp_parent: &Path) -> eyre::Result<()> {
+async fn run_installer(
+    opts: UpgradeOpts,
+    tmp_parent: &Path,
+    pin: Option<&str>,
+) -> eyre::Result<()> {
     let url = resolve_installer_url();
     let bytes = fetch_installer_bytes(&url).await?;
+    // Verify before anything touches disk: the script replaces the running binary.
+    let sig = fetch_bytes_capped(
+        &installer_signature_url(&url),
+        "installer signature",
+        SIGNATURE_MAX_BYTES,
+    )
+    .await?;
+    verify_release_signature(
+        "installer script",
+        &bytes,
+        &sig,
+        RELEASE_SIGNING_PUBKEYS_B64,
+    )?;
 
     if cfg!(windows) {
-        let p = temp_script_path(tmp_parent, "ps1");
+        let p = temp_path(tmp_parent, "seashail-installer", "ps1");
         // Best-effort restrictive write; Windows perms are different but we still use atomic pathing.
         crate::fsutil::write_atomic_restrictive(&p, &bytes, crate::fsutil::MODE_FILE_PRIVATE)
             .with_context(|| format!("write {}", p.display()))?;
@@ -212,6 +493,9 @@ async fn run_installer(opts: UpgradeOpts, tmp_parent: &Path) -> eyre::Result<()>
                 .arg("Bypass")
                 .arg("-File")
                 .arg(&p);
+            if let Some(v) = pin {
+                cmd.env("SEASHAIL_VERSION", v);
+            }
 
             if opts.quiet {
                 cmd.stdin(Stdio::null())
@@ -244,7 +528,7 @@ async fn run_installer(opts: UpgradeOpts, tmp_parent: &Path) -> eyre::Result<()>
         eyre::bail!("missing dependency: powershell (or pwsh)");
     }
 
-    let p = temp_script_path(tmp_parent, "sh");
+    let p = temp_path(tmp_parent, "seashail-installer", "sh");
     crate::fsutil::write_atomic_restrictive(&p, &bytes, 0o700_u32)
         .with_context(|| format!("write {}", p.display()))?;
 
@@ -256,6 +540,9 @@ async fn run_installer(opts: UpgradeOpts, tmp_parent: &Path) -> eyre::Result<()>
     };
     let mut cmd = tokio::process::Command::new(shell);
     cmd.arg(&p);
+    if let Some(v) = pin {
+        cmd.env("SEASHAIL_VERSION", v);
+    }
 
     if opts.quiet {
         cmd.stdin(Stdio::null())
@@ -275,10 +562,19 @@ async fn run_installer(opts: UpgradeOpts, tmp_parent: &Path) -> eyre::Result<()>
     Ok(())
 }
 
-async fn run_upgrade(opts: UpgradeOpts, paths: &SeashailPaths) -> eyre::Result<()> {
-    match resolve_upgrade_method() {
-        "brew" => run_brew_upgrade(opts).await,
-        _ => run_installer(opts, &paths.data_dir).await,
+async fn run_upgrade(
+    opts: UpgradeOpts,
+    paths: &SeashailPaths,
+    pin: Option<&str>,
+) -> eyre::Result<()> {
+    match (resolve_upgrade_method(), pin) {
+        ("brew", Some(v)) => eyre::bail!(
+            "upgrade.pinned_version ({v}) is not supported for Homebrew installs; \
+             use `brew pin seashail` or SEASHAIL_UPGRADE_METHOD=release"
+        ),
+        ("brew", None) => run_brew_upgrade(opts).await,
+        ("release", _) => run_release_upgrade(opts, &paths.data_dir, pin).await,
+        _ => run_installer(opts, &paths.data_dir, pin).await,
     }
 }
 
@@ -291,11 +587,33 @@ pub async fn run(opts: UpgradeOpts) -> eyre::Result<()> {
     let paths = SeashailPaths::discover().context("discover paths")?;
     // Use the private data dir as our temp parent to avoid /tmp races and to keep permissions strict.
     crate::fsutil::ensure_private_dir(&paths.data_dir)?;
-    run_upgrade(opts, &paths).await
+    let cfg = load_upgrade_config(&paths)?;
+    run_upgrade(opts, &paths, cfg.effective_pinned_version()?).await
 }
 
 pub fn maybe_auto_upgrade(paths: &SeashailPaths) {
-    if !auto_upgrade_enabled() {
+    // An unreadable config must not be mistaken for consent: skip rather than fall back to defaults.
+    let cfg = match load_upgrade_config(paths) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            tracing::debug!(error = %e, "auto-upgrade: config unavailable; skipped");
+            return;
+        }
+    };
+    if !auto_upgrade_enabled(&cfg) {
+        return;
+    }
+    let pin = match cfg.effective_pinned_version() {
+        Ok(pin) => pin.map(str::to_owned),
+        Err(e) => {
+            tracing::warn!(error = %e, "auto-upgrade: skipped");
+            return;
+        }
+    };
+    if pin
+        .as_deref()
+        .is_some_and(|v| pin_matches(v, env!("CARGO_PKG_VERSION")))
+    {
         return;
     }
 
@@ -306,13 +624,13 @@ pub fn maybe_auto_upgrade(paths: &SeashailPaths) {
     }
 
     tokio::spawn(async move {
-        if let Err(e) = auto_upgrade_task(&paths).await {
+        if let Err(e) = auto_upgrade_task(&paths, pin.as_deref()).await {
             tracing::debug!(error = %e, "auto-upgrade: skipped/failed");
         }
     });
 }
 
-async fn auto_upgrade_task(paths: &SeashailPaths) -> eyre::Result<()> {
+async fn auto_upgrade_task(paths: &SeashailPaths, pin: Option<&str>) -> eyre::Result<()> {
     crate::fsutil::ensure_private_dir(&paths.data_dir)?;
 
     let lock_p = lock_path(paths);
@@ -351,6 +669,7 @@ async fn auto_upgrade_task(paths: &SeashailPaths) -> eyre::Result<()> {
             quiet: true,
         },
         paths,
+        pin,
     )
     .await
     {
@@ -411,4 +730,97 @@ mod tests {
         std::env::remove_var("SEASHAIL_TEST_BOOL");
         assert_eq!(env_boolish("SEASHAIL_TEST_BOOL"), None);
     }
+
+    #[test]
+    fn config_opt_out_disables_auto_upgrade() {
+        let cfg = UpgradeConfig {
+            auto: false,
+            pinned_version: None,
+        };
+        assert!(!auto_upgrade_enabled(&cfg));
+    }
+
+    #[test]
+    fn release_signature_must_come_from_a_pinned_key() -> eyre::Result<()> {
+        use ed25519_dalek::{Signer as _, SigningKey};
+
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let release = SigningKey::from_bytes(&[7_u8; 32]);
+        let other = SigningKey::from_bytes(&[9_u8; 32]);
+        let release_pk = b64.encode(release.verifying_key().to_bytes());
+        let other_pk = b64.encode(other.verifying_key().to_bytes());
+
+        let script = b"#!/usr/bin/env bash\necho hi\n";
+        let sig = b64.encode(release.sign(script).to_bytes());
+        // Wrapped like `openssl base64` output, with a trailing newline.
+        let (head, tail) = sig.split_at(40);
+        let wrapped = format!("{head}\n{tail}\n");
+
+        verify_release_signature(
+            "installer script",
+            script,
+            wrapped.as_bytes(),
+            &[&other_pk, &release_pk],
+        )?;
+        assert!(
+            verify_release_signature("installer script", script, sig.as_bytes(), &[&other_pk])
+                .is_err()
+        );
+        assert!(verify_release_signature(
+            "installer script",
+            b"echo pwned",
+            sig.as_bytes(),
+            &[&release_pk]
+        )
+        .is_err());
+        assert!(verify_release_signature(
+            "installer script",
+            script,
+            b"not base64!",
+            &[&release_pk]
+        )
+        .is_err());
+        assert!(verify_release_signature("installer script", script, sig.as_bytes(), &[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn pinned_release_keys_are_valid_ed25519() -> eyre::Result<()> {
+        for pk in RELEASE_SIGNING_PUBKEYS_B64 {
+            let bytes = base64::engine::general_purpose::STANDARD.decode(pk)?;
+            let arr = <[u8; 32]>::try_from(bytes.as_slice())
+                .map_err(|_e| eyre::eyre!("{pk}: not 32 bytes"))?;
+            VerifyingKey::from_bytes(&arr)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn release_asset_urls_follow_dist_layout() {
+        let asset = release_asset_name("x86_64-unknown-linux-gnu");
+        assert_eq!(asset, "seashail-x86_64-unknown-linux-gnu.tar.xz");
+        assert_eq!(
+            release_asset_name("x86_64-pc-windows-msvc"),
+            "seashail-x86_64-pc-windows-msvc.zip"
+        );
+        assert_eq!(
+            release_asset_url(RELEASE_BASE_URL, None, &asset),
+            "https://github.com/seashail/seashail/releases/latest/download/seashail-x86_64-unknown-linux-gnu.tar.xz"
+        );
+        assert_eq!(
+            release_asset_url("https://mirror.example/releases/", Some("v1.2.0"), &asset),
+            "https://mirror.example/releases/download/v1.2.0/seashail-x86_64-unknown-linux-gnu.tar.xz"
+        );
+        assert_eq!(
+            release_asset_url(RELEASE_BASE_URL, Some("1.2.0"), &asset),
+            release_asset_url(RELEASE_BASE_URL, Some("v1.2.0"), &asset)
+        );
+    }
+
+    #[test]
+    fn pin_matches_ignores_v_prefix() {
+        assert!(pin_matches("v0.1.0", "0.1.0"));
+        assert!(pin_matches("0.1.0", "v0.1.0"));
+        assert!(!pin_matches("v0.2.0", "0.1.0"));
+    }
 }
@@ -1,12 +1,12 @@
 use crate::{errors::SeashailError, paths::SeashailPaths};
 use alloy::signers::local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner};
 use bip39::{Language, Mnemonic};
-use bitcoin::bip32::{DerivationPath as BtcDerivationPath, Xpriv as BtcXpriv};
+use bitcoin::bip32::{DerivationPath as BtcDerivationPath, Xpriv as BtcXpriv, Xpub as BtcXpub};
 use bitcoin::secp256k1::Secp256k1 as BtcSecp256k1;
 use bitcoin::{
     address::KnownHrp as BtcKnownHrp, Address as BtcAddress,
     CompressedPublicKey as BtcCompressedPublicKey, Network as BtcNetwork,
-    PrivateKey as BtcPrivateKey,
+    NetworkKind as BtcNetworkKind, PrivateKey as BtcPrivateKey,
 };
 use eyre::Context as _;
 use serde::{Deserialize, Serialize};
@@ -14,7 +14,7 @@ use solana_derivation_path::DerivationPath as SolanaDerivationPath;
 use solana_keypair::seed_derivable::keypair_from_seed_and_derivation_path;
 use solana_seed_phrase::generate_seed_from_seed_phrase_and_passphrase;
 use solana_signer::Signer as _;
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf};
 use zeroize::Zeroizing;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -22,6 +22,8 @@ use zeroize::Zeroizing;
 pub enum WalletKind {
     Generated,
     Imported,
+    /// Public addresses only (from an xpub or an address list); can never sign.
+    Watch,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -60,6 +62,150 @@ pub struct WalletRecord {
     pub bitcoin_addresses_mainnet: Vec<String>,
     #[serde(default)]
     pub bitcoin_addresses_testnet: Vec<String>,
+
+    /// Optional "wallet birthday": where on-chain history/balance scans can start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub birthday: Option<WalletBirthday>,
+
+    /// Optional ERC-4337 smart account owned by this wallet's EVM keys.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_account: Option<SmartAccountRecord>,
+
+    /// Extended public key a watch-only wallet derives its addresses from (absent for
+    /// address-list watch wallets).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch_xpub: Option<WatchXpub>,
+
+    /// Names this wallet had before `rename_wallet`, oldest first. Tx history is never rewritten,
+    /// so these keep matching entries recorded under an old name.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_names: Vec<String>,
+}
+
+/// Chain family an imported watch-only xpub belongs to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchXpubChain {
+    /// BIP44 account xpub (`m/44'/60'/0'`); account `i` is `0/i`.
+    Evm,
+    /// BIP84 account xpub/zpub (`m/84'/0'/0'`); account `i` is `0/i`.
+    Bitcoin,
+}
+
+/// Account-level extended public key backing a watch-only wallet. Public material only.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WatchXpub {
+    pub chain: WatchXpubChain,
+    /// Normalized `xpub`/`tpub` encoding (SLIP-132 `zpub`/`vpub` input is converted).
+    pub xpub: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmartAccountKind {
+    /// eth-infinitism `SimpleAccount` for `EntryPoint` v0.7 (single ECDSA owner).
+    SimpleAccountV07,
+}
+
+/// ERC-4337 smart account bound to a wallet (opt-in; the EOA remains the default signer).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SmartAccountRecord {
+    pub kind: SmartAccountKind,
+    pub factory: String,
+    pub entry_point: String,
+    /// Counterfactual account addresses by account index (owner = that index's EVM address,
+    /// salt 0). Identical on every chain where the factory is deployed at the same address.
+    pub addresses: Vec<String>,
+}
+
+/// Earliest point at which a wallet can have on-chain activity.
+///
+/// Generated wallets record their creation time. Imported wallets only carry a birthday when the
+/// user supplies one, since the key may predate Seashail by years.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WalletBirthday {
+    /// Unix seconds (creation time, or the user's approximate first-use date for imports).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    /// Explicit start heights keyed by chain name (EVM block, Solana slot, Bitcoin block).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub heights: BTreeMap<String, u64>,
+}
+
+/// Lookback used for chains where a wallet has no birthday.
+pub const DEFAULT_SCAN_LOOKBACK_SECS: i64 = 90 * 24 * 60 * 60;
+
+/// Slack subtracted from date-derived birthdays (approximate dates, clock skew).
+const BIRTHDAY_MARGIN_SECS: i64 = 24 * 60 * 60;
+
+/// Lower bound on the average block (slot) time per chain, in milliseconds.
+///
+/// Underestimating block time maps a timestamp to an earlier height, which is the safe direction:
+/// a scan may start a little early but never skips activity.
+fn min_block_time_ms(chain: &str) -> u64 {
+    match chain {
+        "bitcoin" => 480_000,
+        "ethereum" | "sepolia" => 12_000,
+        "base" | "base-sepolia" | "optimism" | "optimism-sepolia" | "polygon" | "polygon-amoy" => {
+            1_000
+        }
+        "avalanche" | "avalanche-fuji" => 500,
+        "bnb" | "bnb-testnet" => 400,
+        "solana" | "monad" | "monad-testnet" => 300,
+        // Arbitrum and unknown chains: assume very fast blocks.
+        _ => 100,
+    }
+}
+
+impl WalletBirthday {
+    pub const fn at(created_at: i64) -> Self {
+        Self {
+            created_at: Some(created_at),
+            heights: BTreeMap::new(),
+        }
+    }
+}
+
+/// Height at which an on-chain scan for `chain` should start.
+///
+/// Prefers an explicit per-chain birthday height, then maps the birthday timestamp to a height
+/// using a conservative block time, and otherwise falls back to [`DEFAULT_SCAN_LOOKBACK_SECS`].
+pub fn scan_start_height(
+    birthday: Option<&WalletBirthday>,
+    chain: &str,
+    current_height: u64,
+    now_unix: i64,
+) -> u64 {
+    if let Some(h) = birthday.and_then(|b| b.heights.get(chain)) {
+        return (*h).min(current_height);
+    }
+    let lookback_secs =
+        birthday
+            .and_then(|b| b.created_at)
+            .map_or(DEFAULT_SCAN_LOOKBACK_SECS, |ts| {
+                now_unix
+                    .saturating_sub(ts)
+                    .saturating_add(BIRTHDAY_MARGIN_SECS)
+            });
+    let lookback_ms = u64::try_from(lookback_secs.max(0))
+        .unwrap_or(0)
+        .saturating_mul(1_000);
+    let blocks = lookback_ms / min_block_time_ms(chain);
+    current_height.saturating_sub(blocks)
+}
+
+/// Parse a user-supplied birthday (`YYYY-MM-DD` or RFC 3339) into unix seconds.
+pub fn parse_birthday_date(s: &str) -> eyre::Result<i64> {
+    let s = s.trim();
+    if let Ok(d) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let dt = d
+            .and_hms_opt(0, 0, 0)
+            .ok_or_else(|| eyre::eyre!("invalid date"))?;
+        return Ok(dt.and_utc().timestamp());
+    }
+    let dt = chrono::DateTime::parse_from_rfc3339(s)
+        .context("birthday must be YYYY-MM-DD or an RFC 3339 timestamp")?;
+    Ok(dt.timestamp())
 }
 
 pub struct WalletAddressSets {
@@ -97,6 +243,10 @@ impl WalletRecord {
             solana_addresses: addrs.solana,
             bitcoin_addresses_mainnet: addrs.bitcoin_mainnet,
             bitcoin_addresses_testnet: addrs.bitcoin_testnet,
+            birthday: Some(WalletBirthday::at(chrono::Utc::now().timestamp())),
+            smart_account: None,
+            watch_xpub: None,
+            previous_names: Vec::new(),
         }
     }
 
@@ -106,6 +256,7 @@ impl WalletRecord {
         imported_kind: ImportedKind,
         imported_private_key_chain: Option<ImportedPrivateKeyChain>,
         addrs: WalletAddressSets,
+        birthday: Option<WalletBirthday>,
     ) -> Self {
         let accounts = addrs.account_count();
         Self {
@@ -120,10 +271,48 @@ impl WalletRecord {
             solana_addresses: addrs.solana,
             bitcoin_addresses_mainnet: addrs.bitcoin_mainnet,
             bitcoin_addresses_testnet: addrs.bitcoin_testnet,
+            birthday,
+            smart_account: None,
+            watch_xpub: None,
+            previous_names: Vec::new(),
+        }
+    }
+
+    pub fn new_watch(
+        id: String,
+        name: String,
+        watch_xpub: Option<WatchXpub>,
+        addrs: WalletAddressSets,
+        birthday: Option<WalletBirthday>,
+    ) -> Self {
+        let accounts = addrs.account_count();
+        Self {
+            id,
+            name,
+            kind: WalletKind::Watch,
+            accounts,
+            last_active_account: 0,
+            imported_kind: None,
+            imported_private_key_chain: None,
+            evm_addresses: addrs.evm,
+            solana_addresses: addrs.solana,
+            bitcoin_addresses_mainnet: addrs.bitcoin_mainnet,
+            bitcoin_addresses_testnet: addrs.bitcoin_testnet,
+            birthday,
+            smart_account: None,
+            watch_xpub,
+            previous_names: Vec::new(),
         }
     }
 }
 
+/// A (wallet, account) selection pinned to one chain scope.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActiveSelection {
+    pub wallet: String,
+    pub account_index: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WalletIndex {
     pub wallets: Vec<WalletRecord>,
@@ -131,6 +320,19 @@ pub struct WalletIndex {
     pub active_wallet_name: Option<String>,
     #[serde(default)]
     pub active_account: u32,
+    /// Per-chain overrides of the global active selection, keyed by chain name (`base`) or
+    /// family (`evm`, `solana`, `bitcoin`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub active_by_chain: BTreeMap<String, ActiveSelection>,
+}
+
+/// Scope family for a chain name: `solana`, `bitcoin`, or `evm` for everything else.
+pub fn chain_family(chain: &str) -> &'static str {
+    match chain {
+        "solana" => "solana",
+        "bitcoin" => "bitcoin",
+        _ => "evm",
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -140,6 +342,14 @@ pub struct WalletInfo {
     pub accounts: u32,
     pub active_account: u32,
     pub addresses: WalletAddresses,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub birthday: Option<WalletBirthday>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_account: Option<SmartAccountRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch_xpub: Option<WatchXpub>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_names: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,6 +360,23 @@ pub struct WalletAddresses {
     pub bitcoin_testnet: Vec<String>,
 }
 
+/// Canonical form for name collision checks: names that differ only by case or surrounding
+/// whitespace are the same name.
+pub fn normalize_wallet_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// The wallet that owns `name`, either as its current name or as one it was renamed from.
+fn name_owner<'a>(idx: &'a WalletIndex, name: &str) -> Option<&'a WalletRecord> {
+    let want = normalize_wallet_name(name);
+    idx.wallets.iter().find(|w| {
+        normalize_wallet_name(&w.name) == want
+            || w.previous_names
+                .iter()
+                .any(|p| normalize_wallet_name(p) == want)
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct WalletStore {
     index_path: PathBuf,
@@ -194,10 +421,16 @@ impl WalletStore {
         Ok(idx.wallets.into_iter().find(|w| w.name == name))
     }
 
+    /// Current name of the wallet that already owns `name` (see [`normalize_wallet_name`]), if any.
+    pub fn name_conflict(&self, name: &str) -> eyre::Result<Option<String>> {
+        let idx = self.load_index()?;
+        Ok(name_owner(&idx, name).map(|w| w.name.clone()))
+    }
+
     pub fn add(&self, wallet: WalletRecord) -> eyre::Result<()> {
         let mut idx = self.load_index()?;
-        if idx.wallets.iter().any(|w| w.name == wallet.name) {
-            eyre::bail!("wallet name already exists");
+        if name_owner(&idx, &wallet.name).is_some() {
+            return Err(SeashailError::WalletNameTaken(wallet.name).into());
         }
         let wallet_name = wallet.name.clone();
         idx.wallets.push(wallet);
@@ -225,6 +458,47 @@ impl WalletStore {
         Ok(())
     }
 
+    /// Rename `old` to `new`, keeping `old` in `previous_names` and repointing the global and
+    /// per-chain active selections. A wallet may take back one of its own previous names or
+    /// change only the case of its name; every other collision is rejected.
+    pub fn rename(&self, old: &str, new: &str) -> eyre::Result<WalletRecord> {
+        let mut idx = self.load_index()?;
+        let pos = idx
+            .wallets
+            .iter()
+            .position(|w| w.name == old)
+            .ok_or_else(|| SeashailError::WalletNotFound(old.to_owned()))?;
+        let id = idx
+            .wallets
+            .get(pos)
+            .map(|w| w.id.clone())
+            .unwrap_or_default();
+        if name_owner(&idx, new).is_some_and(|w| w.id != id) {
+            return Err(SeashailError::WalletNameTaken(new.to_owned()).into());
+        }
+        let Some(w) = idx.wallets.get_mut(pos) else {
+            return Err(SeashailError::WalletNotFound(old.to_owned()).into());
+        };
+        if w.name == new {
+            return Ok(w.clone());
+        }
+        w.previous_names.retain(|p| p != new && p != old);
+        w.previous_names.push(old.to_owned());
+        new.clone_into(&mut w.name);
+        let renamed = w.clone();
+
+        if idx.active_wallet_name.as_deref() == Some(old) {
+            idx.active_wallet_name = Some(new.to_owned());
+        }
+        for sel in idx.active_by_chain.values_mut() {
+            if sel.wallet == old {
+                new.clone_into(&mut sel.wallet);
+            }
+        }
+        self.save_index(&idx)?;
+        Ok(renamed)
+    }
+
     pub fn set_active(&self, name: &str, account_index: u32) -> eyre::Result<()> {
         let mut idx = self.load_index()?;
         let w = idx
@@ -250,6 +524,66 @@ impl WalletStore {
         Ok(w.map(|w| (w, idx.active_account)))
     }
 
+    /// Pin `name`/`account_index` as the active selection for `scope` (a chain name or family).
+    pub fn set_active_for_chain(
+        &self,
+        scope: &str,
+        name: &str,
+        account_index: u32,
+    ) -> eyre::Result<()> {
+        let mut idx = self.load_index()?;
+        let w = idx
+            .wallets
+            .iter()
+            .find(|w| w.name == name)
+            .ok_or_else(|| SeashailError::WalletNotFound(name.to_owned()))?;
+        if account_index >= w.accounts {
+            return Err(SeashailError::AccountIndexOutOfRange.into());
+        }
+        idx.active_by_chain.insert(
+            scope.to_owned(),
+            ActiveSelection {
+                wallet: name.to_owned(),
+                account_index,
+            },
+        );
+        self.save_index(&idx)?;
+        Ok(())
+    }
+
+    /// Drop the override for `scope`. Returns whether one existed.
+    pub fn clear_active_for_chain(&self, scope: &str) -> eyre::Result<bool> {
+        let mut idx = self.load_index()?;
+        let removed = idx.active_by_chain.remove(scope).is_some();
+        if removed {
+            self.save_index(&idx)?;
+        }
+        Ok(removed)
+    }
+
+    pub fn active_by_chain(&self) -> eyre::Result<BTreeMap<String, ActiveSelection>> {
+        Ok(self.load_index()?.active_by_chain)
+    }
+
+    /// Active selection for `chain`: an override for the exact chain, then for its family, then
+    /// the global selection. Overrides pointing at a missing wallet are skipped.
+    pub fn get_active_for_chain(&self, chain: &str) -> eyre::Result<Option<(WalletRecord, u32)>> {
+        let idx = self.load_index()?;
+        for scope in [chain, chain_family(chain)] {
+            let Some(sel) = idx.active_by_chain.get(scope) else {
+                continue;
+            };
+            if let Some(w) = idx.wallets.iter().find(|w| w.name == sel.wallet) {
+                return Ok(Some((w.clone(), sel.account_index)));
+            }
+        }
+        let Some(name) = idx.active_wallet_name else {
+            return Ok(None);
+        };
+        let w = idx.wallets.into_iter().find(|w| w.name == name);
+        Ok(w.map(|w| (w, idx.active_account)))
+    }
+
     pub fn wallet_info(w: &WalletRecord, active_account: u32) -> WalletInfo {
         WalletInfo {
             name: w.name.clone(),
@@ -262,6 +596,10 @@ impl WalletStore {
                 bitcoin_mainnet: w.bitcoin_addresses_mainnet.clone(),
                 bitcoin_testnet: w.bitcoin_addresses_testnet.clone(),
             },
+            birthday: w.birthday.clone(),
+            smart_account: w.smart_account.clone(),
+            watch_xpub: w.watch_xpub.clone(),
+            previous_names: w.previous_names.clone(),
         }
     }
 }
@@ -347,6 +685,280 @@ pub fn bitcoin_addresses_from_mnemonic(
     Ok((mainnet, testnet))
 }
 
+/// BIP84 account-level path; Seashail account index `i` is the receive address `0/i` below it.
+const BTC_ACCOUNT_PATH: &str = "m/84'/0'/0'";
+/// BIP44 account-level path; EVM account index `i` is `0/i` below it.
+const EVM_ACCOUNT_PATH: &str = "m/44'/60'/0'";
+/// SLIP-132 version bytes for BIP84 extended public keys.
+const ZPUB_VERSION: [u8; 4] = [0x04, 0xb2, 0x47, 0x46];
+const VPUB_VERSION: [u8; 4] = [0x04, 0x5f, 0x1c, 0xf6];
+
+/// Account-level extended public keys for watch-only wallets. Contains no private material.
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicDescriptors {
+    pub master_fingerprint: String,
+    pub bitcoin: BitcoinPublicDescriptor,
+    pub evm: EvmPublicDescriptor,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BitcoinPublicDescriptor {
+    pub path: String,
+    pub xpub: String,
+    pub zpub: String,
+    pub vpub: String,
+    pub descriptor_mainnet: String,
+    pub descriptor_testnet: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvmPublicDescriptor {
+    pub path: String,
+    pub xpub: String,
+    pub address_path: String,
+}
+
+fn xpub_with_version(xpub: &BtcXpub, version: [u8; 4]) -> String {
+    let mut data = xpub.encode();
+    if let Some(prefix) = data.get_mut(..4) {
+        prefix.copy_from_slice(&version);
+    }
+    bitcoin::base58::encode_check(&data)
+}
+
+/// Output descriptor checksum (BIP-380), as required by `importdescriptors`.
+fn descriptor_checksum(desc: &str) -> eyre::Result<String> {
+    const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+    const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    const GENERATORS: [u64; 5] = [
+        0xf5_dee5_1989,
+        0xa9_fdca_3312,
+        0x1b_ab10_e32d,
+        0x37_06b1_677a,
+        0x64_4d62_6ffd,
+    ];
+    fn polymod(c: u64, val: u64) -> u64 {
+        let c0 = c >> 35;
+        let mut c = ((c & 0x7_ffff_ffff) << 5) ^ val;
+        for (i, g) in GENERATORS.iter().enumerate() {
+            if (c0 >> i) & 1 == 1 {
+                c ^= g;
+            }
+        }
+        c
+    }
+
+    let mut c = 1_u64;
+    let mut cls = 0_u64;
+    let mut cls_count = 0_u8;
+    for ch in desc.chars() {
+        let pos = INPUT_CHARSET
+            .find(ch)
+            .ok_or_else(|| eyre::eyre!("invalid descriptor character: {ch:?}"))?;
+        let pos = u64::try_from(pos).context("descriptor charset position")?;
+        c = polymod(c, pos & 31);
+        cls = cls * 3 + (pos >> 5);
+        cls_count += 1;
+        if cls_count == 3 {
+            c = polymod(c, cls);
+            cls = 0;
+            cls_count = 0;
+        }
+    }
+    if cls_count > 0 {
+        c = polymod(c, cls);
+    }
+    for _ in 0..8 {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+    let mut out = String::with_capacity(8);
+    for j in 0..8_u64 {
+        let idx = usize::try_from((c >> (5 * (7 - j))) & 31).context("checksum index")?;
+        let ch = CHECKSUM_CHARSET
+            .get(idx)
+            .ok_or_else(|| eyre::eyre!("checksum index out of range"))?;
+        out.push(char::from(*ch));
+    }
+    Ok(out)
+}
+
+fn with_checksum(desc: &str) -> eyre::Result<String> {
+    Ok(format!("{desc}#{}", descriptor_checksum(desc)?))
+}
+
+pub fn public_descriptors_from_entropy(entropy: &[u8]) -> eyre::Result<PublicDescriptors> {
+    let mnemonic =
+        Mnemonic::from_entropy_in(Language::English, entropy).context("mnemonic from entropy")?;
+    public_descriptors_from_mnemonic(&mnemonic)
+}
+
+/// Derive account-level xpubs for Bitcoin (BIP84) and EVM (BIP44).
+///
+/// Solana uses hardened-only ed25519 derivation, so it has no extended public key.
+pub fn public_descriptors_from_mnemonic(mnemonic: &Mnemonic) -> eyre::Result<PublicDescriptors> {
+    let seed = Zeroizing::new(mnemonic.to_seed_normalized(""));
+    let secp = BtcSecp256k1::new();
+    let master =
+        BtcXpriv::new_master(BtcNetwork::Bitcoin, seed.as_slice()).context("btc master xpriv")?;
+    let fingerprint = master.fingerprint(&secp).to_string();
+
+    let account_xpub = |path_s: &str| -> eyre::Result<BtcXpub> {
+        let path: BtcDerivationPath = path_s.parse().context("parse account derivation path")?;
+        let xpriv = master
+            .derive_priv(&secp, &path)
+            .context("derive account xpriv")?;
+        Ok(BtcXpub::from_priv(&secp, &xpriv))
+    };
+
+    let btc = account_xpub(BTC_ACCOUNT_PATH)?;
+    let mut btc_test = btc;
+    btc_test.network = BtcNetworkKind::Test;
+    let origin = format!("[{fingerprint}/84h/0h/0h]");
+    let evm = account_xpub(EVM_ACCOUNT_PATH)?;
+
+    Ok(PublicDescriptors {
+        master_fingerprint: fingerprint.clone(),
+        bitcoin: BitcoinPublicDescriptor {
+            path: BTC_ACCOUNT_PATH.to_owned(),
+            xpub: btc.to_string(),
+            zpub: xpub_with_version(&btc, ZPUB_VERSION),
+            vpub: xpub_with_version(&btc_test, VPUB_VERSION),
+            descriptor_mainnet: with_checksum(&format!("wpkh({origin}{btc}/0/*)"))?,
+            descriptor_testnet: with_checksum(&format!("wpkh({origin}{btc_test}/0/*)"))?,
+        },
+        evm: EvmPublicDescriptor {
+            path: EVM_ACCOUNT_PATH.to_owned(),
+            xpub: evm.to_string(),
+            address_path: "0/{account_index}".to_owned(),
+        },
+    })
+}
+
+/// Most addresses one watch-only import may derive or list.
+pub const WATCH_MAX_ADDRESSES: u32 = 100;
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+const TPUB_VERSION: [u8; 4] = [0x04, 0x35, 0x87, 0xcf];
+
+/// Parse an account-level extended public key, accepting SLIP-132 `zpub`/`vpub` as well as
+/// `xpub`/`tpub`. Rejects keys that are not at account depth (3), since deriving `0/i` from any
+/// other level would silently produce addresses the owner never uses.
+pub fn parse_account_xpub(s: &str) -> eyre::Result<BtcXpub> {
+    let s = s.trim();
+    let xpub = if let Ok(x) = s.parse::<BtcXpub>() {
+        x
+    } else {
+        let mut data =
+            bitcoin::base58::decode_check(s).context("invalid extended public key encoding")?;
+        let target = match data.get(..4) {
+            Some(v) if v == ZPUB_VERSION => XPUB_VERSION,
+            Some(v) if v == VPUB_VERSION => TPUB_VERSION,
+            _ => eyre::bail!(
+                "unsupported extended public key version (expected xpub, tpub, zpub or vpub)"
+            ),
+        };
+        if let Some(prefix) = data.get_mut(..4) {
+            prefix.copy_from_slice(&target);
+        }
+        BtcXpub::decode(&data).context("invalid extended public key")?
+    };
+    if xpub.depth != 3 {
+        eyre::bail!(
+            "expected an account-level extended public key (depth 3, e.g. {BTC_ACCOUNT_PATH} or {EVM_ACCOUNT_PATH}), got depth {}",
+            xpub.depth
+        );
+    }
+    Ok(xpub)
+}
+
+/// Derive receive addresses `0/i` for `account_indices` from a watch-only xpub, using the same
+/// scheme Seashail uses for its own wallets. Needs no private material.
+pub fn watch_addresses_from_xpub(
+    watch: &WatchXpub,
+    account_indices: &[u32],
+) -> eyre::Result<WalletAddressSets> {
+    let xpub = parse_account_xpub(&watch.xpub)?;
+    let secp = BtcSecp256k1::verification_only();
+    let mut out = WalletAddressSets {
+        evm: vec![],
+        solana: vec![],
+        bitcoin_mainnet: vec![],
+        bitcoin_testnet: vec![],
+    };
+    for &i in account_indices {
+        let path = [
+            bitcoin::bip32::ChildNumber::from_normal_idx(0).context("receive chain")?,
+            bitcoin::bip32::ChildNumber::from_normal_idx(i).context("address index")?,
+        ];
+        let child = xpub
+            .derive_pub(&secp, &path)
+            .context("derive child public key")?;
+        match watch.chain {
+            WatchXpubChain::Evm => {
+                let uncompressed = child.public_key.serialize_uncompressed();
+                let raw = uncompressed
+                    .get(1..)
+                    .ok_or_else(|| eyre::eyre!("invalid public key"))?;
+                out.evm
+                    .push(alloy::primitives::Address::from_raw_public_key(raw).to_checksum(None));
+            }
+            WatchXpubChain::Bitcoin => {
+                let cpk = child.to_pub();
+                out.bitcoin_mainnet
+                    .push(BtcAddress::p2wpkh(&cpk, BtcKnownHrp::Mainnet).to_string());
+                out.bitcoin_testnet
+                    .push(BtcAddress::p2wpkh(&cpk, BtcKnownHrp::Testnets).to_string());
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Sort a user-supplied address list into per-chain watch addresses (EVM `0x…`, Bitcoin
+/// mainnet/testnet, Solana base58). Account `i` is the `i`-th address of each chain.
+pub fn watch_addresses_from_list(list: &[String]) -> eyre::Result<WalletAddressSets> {
+    let mut out = WalletAddressSets {
+        evm: vec![],
+        solana: vec![],
+        bitcoin_mainnet: vec![],
+        bitcoin_testnet: vec![],
+    };
+    for raw in list {
+        let a = raw.trim();
+        if a.is_empty() {
+            continue;
+        }
+        let (bucket, normalized) = if a.starts_with("0x") || a.starts_with("0X") {
+            let addr: alloy::primitives::Address = a
+                .parse()
+                .with_context(|| format!("invalid EVM address: {a}"))?;
+            (&mut out.evm, addr.to_checksum(None))
+        } else if let Ok(btc) = a.parse::<BtcAddress<bitcoin::address::NetworkUnchecked>>() {
+            if btc.is_valid_for_network(BtcNetwork::Bitcoin) {
+                (&mut out.bitcoin_mainnet, btc.assume_checked().to_string())
+            } else {
+                (&mut out.bitcoin_testnet, btc.assume_checked().to_string())
+            }
+        } else {
+            let pk: solana_sdk::pubkey::Pubkey = a
+                .parse()
+                .map_err(|_e| eyre::eyre!("unrecognized address: {a}"))?;
+            (&mut out.solana, pk.to_string())
+        };
+        if !bucket.contains(&normalized) {
+            bucket.push(normalized);
+        }
+    }
+    let accounts = out.account_count();
+    if accounts == 0 {
+        eyre::bail!("no addresses provided");
+    }
+    if accounts > WATCH_MAX_ADDRESSES {
+        eyre::bail!("too many addresses per chain (max {WATCH_MAX_ADDRESSES})");
+    }
+    Ok(out)
+}
+
 pub fn addresses_from_import(
     kind: ImportedKind,
     secret_bytes: &[u8],
@@ -390,6 +1002,104 @@ pub fn addresses_from_import(
 mod tests {
     use super::*;
 
+    fn test_wallet(name: &str, accounts: usize) -> WalletRecord {
+        let addrs = WalletAddressSets {
+            evm: vec![String::new(); accounts],
+            solana: vec![String::new(); accounts],
+            bitcoin_mainnet: vec![String::new(); accounts],
+            bitcoin_testnet: vec![String::new(); accounts],
+        };
+        WalletRecord::new_generated(format!("id-{name}"), name.to_owned(), addrs)
+    }
+
+    #[test]
+    fn per_chain_active_selection_falls_back_to_family_then_global() -> eyre::Result<()> {
+        let td = tempfile::tempdir().context("create tempdir")?;
+        let paths = SeashailPaths {
+            config_dir: td.path().join("cfg"),
+            data_dir: td.path().join("data"),
+            log_file: td.path().join("data").join("seashail.log.jsonl"),
+        };
+        let store = WalletStore::new(&paths);
+        store.add(test_wallet("main", 4))?;
+        store.add(test_wallet("trading", 2))?;
+
+        store.set_active_for_chain("solana", "main", 3)?;
+        store.set_active_for_chain("evm", "trading", 1)?;
+        store.set_active_for_chain("base", "main", 2)?;
+
+        let pick = |chain: &str| -> eyre::Result<(String, u32)> {
+            let (w, i) = store
+                .get_active_for_chain(chain)?
+                .ok_or_else(|| eyre::eyre!("no active wallet"))?;
+            Ok((w.name, i))
+        };
+        assert_eq!(pick("solana")?, ("main".to_owned(), 3));
+        assert_eq!(pick("ethereum")?, ("trading".to_owned(), 1));
+        assert_eq!(pick("base")?, ("main".to_owned(), 2));
+        assert_eq!(pick("bitcoin")?, ("main".to_owned(), 0));
+
+        assert!(store.clear_active_for_chain("base")?);
+        assert!(!store.clear_active_for_chain("base")?);
+        assert_eq!(pick("base")?, ("trading".to_owned(), 1));
+
+        assert!(store.set_active_for_chain("evm", "trading", 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn names_collide_case_insensitively_and_renames_keep_history_names() -> eyre::Result<()> {
+        let td = tempfile::tempdir().context("create tempdir")?;
+        let paths = SeashailPaths {
+            config_dir: td.path().join("cfg"),
+            data_dir: td.path().join("data"),
+            log_file: td.path().join("data").join("seashail.log.jsonl"),
+        };
+        let store = WalletStore::new(&paths);
+        store.add(test_wallet("main", 2))?;
+        store.add(test_wallet("trading", 1))?;
+        assert!(
+            store.add(test_wallet(" MAIN ", 1)).is_err(),
+            "case/space variant"
+        );
+        assert_eq!(store.name_conflict("Trading")?, Some("trading".to_owned()));
+        store.set_active_for_chain("solana", "main", 1)?;
+
+        assert!(
+            store.rename("main", "TRADING").is_err(),
+            "taken by another wallet"
+        );
+        let w = store.rename("main", "savings")?;
+        assert_eq!(w.previous_names, vec!["main".to_owned()]);
+        let (active, _) = store
+            .get_active()?
+            .ok_or_else(|| eyre::eyre!("no active wallet"))?;
+        assert_eq!(
+            active.name, "savings",
+            "active selection follows the rename"
+        );
+        assert_eq!(
+            store
+                .active_by_chain()?
+                .get("solana")
+                .map(|s| s.wallet.as_str()),
+            Some("savings")
+        );
+
+        assert!(
+            store.add(test_wallet("main", 1)).is_err(),
+            "old names stay reserved so history is unambiguous"
+        );
+        let w = store.rename("savings", "Main")?;
+        assert_eq!(w.name, "Main", "a wallet may reclaim its own old name");
+        assert_eq!(
+            w.previous_names,
+            vec!["main".to_owned(), "savings".to_owned()],
+            "exact old spellings are kept for history matching"
+        );
+        Ok(())
+    }
+
     #[test]
     fn bitcoin_bip84_derivation_vectors_abandon_about() -> eyre::Result<()> {
         // This anchors our BIP84 derivation path and address formatting:
@@ -428,4 +1138,161 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn public_descriptors_match_bip84_vectors_and_derived_addresses() -> eyre::Result<()> {
+        let mnemonic = Mnemonic::parse_in_normalized(
+            Language::English,
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .context("parse mnemonic")?;
+        let d = public_descriptors_from_mnemonic(&mnemonic)?;
+
+        // BIP84 test vectors.
+        assert_eq!(d.master_fingerprint, "73c5da0a", "master fingerprint");
+        assert_eq!(
+            d.bitcoin.zpub,
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs",
+            "bip84 account zpub"
+        );
+        assert_eq!(
+            descriptor_checksum("raw(deadbeef)")?,
+            "89f8spxm",
+            "bip380 vector"
+        );
+
+        // Watch-only derivation from the xpubs reproduces the addresses Seashail derives.
+        let secp = BtcSecp256k1::verification_only();
+        let child: BtcDerivationPath = "m/0/1".parse().context("parse child path")?;
+        let btc_xpub: BtcXpub = d.bitcoin.xpub.parse().context("parse btc xpub")?;
+        let btc_pk = btc_xpub.derive_pub(&secp, &child)?.to_pub();
+        let (mainnet, _) = bitcoin_addresses_from_mnemonic(&mnemonic, &[1])?;
+        assert_eq!(
+            Some(BtcAddress::p2wpkh(&btc_pk, BtcKnownHrp::Mainnet).to_string()),
+            mainnet.first().cloned(),
+            "btc address from xpub"
+        );
+
+        let evm_xpub: BtcXpub = d.evm.xpub.parse().context("parse evm xpub")?;
+        let evm_pk = evm_xpub.derive_pub(&secp, &child)?.public_key;
+        let uncompressed = evm_pk.serialize_uncompressed();
+        let evm_addr = alloy::primitives::Address::from_raw_public_key(
+            uncompressed.get(1..).ok_or_else(|| eyre::eyre!("pubkey"))?,
+        );
+        let (evm, _) = addresses_from_mnemonic(&mnemonic, &[1])?;
+        assert_eq!(
+            Some(evm_addr.to_checksum(None)),
+            evm.first().cloned(),
+            "evm address from xpub"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn watch_xpub_and_address_list_reproduce_seashail_addresses() -> eyre::Result<()> {
+        let mnemonic = Mnemonic::parse_in_normalized(
+            Language::English,
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .context("parse mnemonic")?;
+        let d = public_descriptors_from_mnemonic(&mnemonic)?;
+        let (evm, sol) = addresses_from_mnemonic(&mnemonic, &[0, 1, 2])?;
+        let (btc_main, btc_test) = bitcoin_addresses_from_mnemonic(&mnemonic, &[0, 1, 2])?;
+
+        let w = watch_addresses_from_xpub(
+            &WatchXpub {
+                chain: WatchXpubChain::Evm,
+                xpub: d.evm.xpub.clone(),
+            },
+            &[0, 1, 2],
+        )?;
+        assert_eq!(w.evm, evm, "evm addresses from xpub");
+        assert!(w.bitcoin_mainnet.is_empty(), "evm xpub yields no btc");
+
+        // zpub input is normalized and derives the same BIP84 addresses.
+        let w = watch_addresses_from_xpub(
+            &WatchXpub {
+                chain: WatchXpubChain::Bitcoin,
+                xpub: d.bitcoin.zpub.clone(),
+            },
+            &[0, 1, 2],
+        )?;
+        assert_eq!(w.bitcoin_mainnet, btc_main, "btc mainnet from zpub");
+        assert_eq!(w.bitcoin_testnet, btc_test, "btc testnet from zpub");
+        assert_eq!(
+            parse_account_xpub(&d.bitcoin.zpub)?.to_string(),
+            d.bitcoin.xpub,
+            "zpub normalizes to xpub"
+        );
+
+        // Only account-level keys are accepted.
+        let master = BtcXpriv::new_master(BtcNetwork::Bitcoin, &mnemonic.to_seed_normalized(""))?;
+        let master_xpub = BtcXpub::from_priv(&BtcSecp256k1::new(), &master).to_string();
+        assert!(
+            parse_account_xpub(&master_xpub).is_err(),
+            "depth 0 rejected"
+        );
+        assert!(parse_account_xpub("xpub-nope").is_err(), "garbage rejected");
+
+        let list: Vec<String> = [
+            evm.first().cloned(),
+            sol.first().cloned(),
+            btc_main.first().cloned(),
+            btc_test.get(1).cloned(),
+            evm.first().map(|a| a.to_lowercase()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let w = watch_addresses_from_list(&list)?;
+        assert_eq!(w.evm.len(), 1, "duplicate evm address collapsed");
+        assert_eq!(w.solana.first(), sol.first(), "solana bucket");
+        assert_eq!(
+            w.bitcoin_mainnet.first(),
+            btc_main.first(),
+            "btc mainnet bucket"
+        );
+        assert_eq!(
+            w.bitcoin_testnet.first(),
+            btc_test.get(1),
+            "btc testnet bucket"
+        );
+        assert!(
+            watch_addresses_from_list(&["not-an-address".to_owned()]).is_err(),
+            "unrecognized address rejected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn scan_start_prefers_explicit_height_then_date_then_default() {
+        let now = 1_700_000_000_i64;
+        let mut b = WalletBirthday::at(now - 86_400);
+        // Date-derived: (1 day + 1 day margin) / 12s on Ethereum.
+        assert_eq!(
+            scan_start_height(Some(&b), "ethereum", 1_000_000, now),
+            1_000_000 - 14_400
+        );
+        b.heights.insert("ethereum".to_owned(), 999_000);
+        assert_eq!(
+            scan_start_height(Some(&b), "ethereum", 1_000_000, now),
+            999_000
+        );
+        // No birthday: default lookback, clamped at genesis.
+        assert_eq!(scan_start_height(None, "bitcoin", 100, now), 0);
+        assert_eq!(
+            scan_start_height(None, "ethereum", 10_000_000, now),
+            10_000_000 - 648_000
+        );
+    }
+
+    #[test]
+    fn parse_birthday_date_accepts_day_and_rfc3339() {
+        assert_eq!(parse_birthday_date("2024-01-01").ok(), Some(1_704_067_200));
+        assert_eq!(
+            parse_birthday_date("2024-01-01T12:00:00Z").ok(),
+            Some(1_704_110_400)
+        );
+        assert!(parse_birthday_date("last spring").is_err());
+    }
 }
@@ -0,0 +1,94 @@
+use std::process::Command;
+
+use eyre::Context as _;
+use serde_json::{json, Value};
+
+fn run_install(
+    cfg_dir: &std::path::Path,
+    data_dir: &std::path::Path,
+    path: &std::path::Path,
+) -> eyre::Result<std::process::Output> {
+    let exe = assert_cmd::cargo::cargo_bin!("seashail");
+    Command::new(exe)
+        .env("SEASHAIL_CONFIG_DIR", cfg_dir)
+        .env("SEASHAIL_DATA_DIR", data_dir)
+        .args(["agent", "install", "cursor", "--path"])
+        .arg(path)
+        .output()
+        .context("run seashail agent install")
+}
+
+#[test]
+fn agent_install_merges_existing_config_idempotently() -> eyre::Result<()> {
+    let cfg_dir = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let work = tempfile::tempdir()?;
+    let path = work.path().join("mcp.json");
+
+    let original = json!({
+      "theme": "dark",
+      "mcpServers": {
+        "other": { "command": "other-server" },
+        "seashail": { "command": "stale", "args": ["mcp"], "env": { "RUST_LOG": "debug" } }
+      }
+    });
+    std::fs::write(&path, serde_json::to_string_pretty(&original)?)?;
+
+    let out = run_install(cfg_dir.path(), data_dir.path(), &path)?;
+    assert!(
+        out.status.success(),
+        "install failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let report: Value = serde_json::from_slice(&out.stdout).context("parse install report")?;
+    assert_eq!(report.pointer("/action"), Some(&json!("updated")));
+    assert_eq!(report.pointer("/fields"), Some(&json!(["command"])));
+    assert_eq!(report.pointer("/kept_servers"), Some(&json!(["other"])));
+
+    let merged: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(merged.pointer("/theme"), Some(&json!("dark")));
+    assert_eq!(
+        merged.pointer("/mcpServers/other"),
+        original.pointer("/mcpServers/other")
+    );
+    assert_eq!(
+        merged.pointer("/mcpServers/seashail/env"),
+        Some(&json!({ "RUST_LOG": "debug" }))
+    );
+
+    let bak = work.path().join("mcp.json.bak.seashail");
+    let backed_up: Value = serde_json::from_str(&std::fs::read_to_string(&bak)?)?;
+    assert_eq!(backed_up, original);
+
+    let again = run_install(cfg_dir.path(), data_dir.path(), &path)?;
+    assert!(again.status.success());
+    let rerun: Value = serde_json::from_slice(&again.stdout)?;
+    assert_eq!(rerun.pointer("/action"), Some(&json!("unchanged")));
+
+    // A later change refreshes the backup to the config as it was right before that write.
+    let mut edited = merged;
+    let Some(cmd) = edited.pointer_mut("/mcpServers/seashail/command") else {
+        eyre::bail!("merged config has no seashail command");
+    };
+    *cmd = json!("stale-again");
+    std::fs::write(&path, serde_json::to_string_pretty(&edited)?)?;
+    let third = run_install(cfg_dir.path(), data_dir.path(), &path)?;
+    assert!(third.status.success());
+    let refreshed: Value = serde_json::from_str(&std::fs::read_to_string(&bak)?)?;
+    assert_eq!(refreshed, edited);
+    Ok(())
+}
+
+#[test]
+fn agent_install_refuses_non_object_config() -> eyre::Result<()> {
+    let cfg_dir = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let work = tempfile::tempdir()?;
+    let path = work.path().join("mcp.json");
+    std::fs::write(&path, "[1, 2, 3]\n")?;
+
+    let out = run_install(cfg_dir.path(), data_dir.path(), &path)?;
+    assert!(!out.status.success());
+    assert_eq!(std::fs::read_to_string(&path)?, "[1, 2, 3]\n");
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use std::process::Command;
 
-use eyre::Context as _;
+use eyre::{Context as _, ContextCompat as _};
 
 #[test]
 fn doctor_json_runs_and_returns_valid_json() -> eyre::Result<()> {
@@ -29,3 +29,48 @@ fn doctor_json_runs_and_returns_valid_json() -> eyre::Result<()> {
     assert!(v.get("paths").and_then(|x| x.as_object()).is_some());
     Ok(())
 }
+
+#[test]
+fn doctor_connectivity_matrix_redacts_endpoint_secrets() -> eyre::Result<()> {
+    let exe = assert_cmd::cargo::cargo_bin!("seashail");
+
+    let cfg_dir = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let secret = "doctorSecretKey0123456789";
+    std::fs::write(
+        cfg_dir.path().join("config.toml"),
+        format!(
+            "[rpc]\nsolana_rpc_url = \"https://127.0.0.1:1/v2/{secret}?api-key={secret}\"\nsolana_fallback_rpc_urls_mainnet = []\n\n[rpc.evm_rpc_urls]\nethereum = \"http://127.0.0.1:1/{secret}\"\n"
+        ),
+    )?;
+
+    let out = Command::new(exe)
+        .env("SEASHAIL_CONFIG_DIR", cfg_dir.path())
+        .env("SEASHAIL_DATA_DIR", data_dir.path())
+        .args(["doctor", "--json"])
+        .output()
+        .context("run seashail doctor --json")?;
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(!stdout.contains(secret), "secret leaked: {stdout}");
+
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).context("parse doctor json")?;
+    let probes = v
+        .pointer("/connectivity/probes")
+        .and_then(serde_json::Value::as_array)
+        .context("connectivity.probes")?;
+    let solana = probes
+        .iter()
+        .find(|p| p.get("service").and_then(|s| s.as_str()) == Some("solana"))
+        .context("solana probe")?;
+    assert_eq!(
+        solana.get("endpoint").and_then(|s| s.as_str()),
+        Some("https://127.0.0.1:1/...")
+    );
+    assert_eq!(
+        solana.get("ok").and_then(serde_json::Value::as_bool),
+        Some(false)
+    );
+    Ok(())
+}
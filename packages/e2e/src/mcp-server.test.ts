@@ -2,11 +2,20 @@ import { afterAll, beforeAll, describe, expect, test } from "bun:test";
 import assert from "node:assert/strict";
 import { execFileSync, execSync, spawn } from "node:child_process";
 import { randomBytes } from "node:crypto";
-import { existsSync, mkdtempSync } from "node:fs";
+import { existsSync, mkdtempSync, writeFileSync } from "node:fs";
 import net from "node:net";
 import { tmpdir } from "node:os";
 import { join } from "node:path";
 
+import {
+  abiString,
+  abiWords,
+  MAX_UINT256,
+  type MockEvmChain,
+  SELECTORS,
+  startMockEvmChain,
+} from "./tests/mock-evm-chain";
+
 type JsonRpcId = string | number;
 
 interface JsonRpcResponse {
@@ -1007,6 +1016,88 @@ async function handleBtcMockFetch(
   return new Response("not found", { status: 404 });
 }
 
+/**
+ * Spawn `seashail mcp` wired to a scripted mock EVM chain: prices come from the mock's ticker,
+ * `chain` RPC points at the mock with no fallbacks, and the wallet gets a permissive policy.
+ */
+async function startSeashailOnMockEvm(
+  chain: MockEvmChain,
+  chainName: string,
+  mode: "mainnet" | "testnet",
+  wallet: string
+) {
+  const dataDir = mkdtempSync(join(tmpdir(), "seashail-e2e-data-"));
+  const configDir = mkdtempSync(join(tmpdir(), "seashail-e2e-config-"));
+  writeFileSync(
+    join(configDir, "config.toml"),
+    `[http]\nbinance_base_url = "${chain.url}"\n`
+  );
+
+  const bin = new URL("../../../target/debug/seashail", import.meta.url)
+    .pathname;
+  expect(existsSync(bin)).toBe(true);
+
+  const p = spawn(bin, ["mcp"], {
+    cwd: new URL("../../../", import.meta.url).pathname,
+    stdio: ["pipe", "pipe", "pipe"],
+    env: {
+      ...process.env,
+      RUST_LOG: "warn",
+      SEASHAIL_DATA_DIR: dataDir,
+      SEASHAIL_CONFIG_DIR: configDir,
+      SEASHAIL_NETWORK_MODE: mode,
+    },
+  });
+
+  const outBuf = { text: "" };
+  await rpcCall(p, outBuf, 1, "initialize", {});
+
+  writeJsonLine(p, {
+    jsonrpc: "2.0",
+    id: 2,
+    method: "tools/call",
+    params: { name: "create_wallet", arguments: { name: wallet } },
+  });
+  await driveElicitationWithHandler(p, outBuf, 2, defaultElicitationHandler);
+
+  mustToolOk(
+    await rpcCall(p, outBuf, 3, "tools/call", {
+      name: "configure_rpc",
+      arguments: { chain: chainName, url: chain.url, fallback_urls: [] },
+    }),
+    "configure_rpc(mock)"
+  );
+
+  mustToolOk(
+    await rpcCall(p, outBuf, 4, "tools/call", {
+      name: "update_policy",
+      arguments: {
+        wallet,
+        policy: {
+          auto_approve_usd: 100_000,
+          confirm_up_to_usd: 100_000,
+          hard_block_over_usd: 100_000,
+          max_usd_per_tx: 100_000,
+          max_usd_per_day: 1_000_000,
+          enable_send: true,
+          send_allow_any: true,
+          enable_swap: true,
+        },
+      },
+    }),
+    "update_policy"
+  );
+
+  return {
+    p,
+    outBuf,
+    close: async () => {
+      p.stdin?.end();
+      await new Response(p.stderr).text();
+    },
+  };
+}
+
 describe("seashail MCP stdio (offline mocks/fixtures)", () => {
   test("pump.fun read tools can run deterministically via fixture (no RPC)", async () => {
     const dataDir = mkdtempSync(join(tmpdir(), "seashail-e2e-data-"));
@@ -1251,6 +1342,99 @@ describe("seashail MCP stdio (offline mocks/fixtures)", () => {
     await new Response(p.stderr).text();
     btc.stop(true);
   }, 90_000);
+
+  test("send_transaction (evm native) signs and broadcasts against a scripted mock chain", async () => {
+    const chain = startMockEvmChain({
+      chainId: 11_155_111,
+      prices: { ETHUSDT: "2000.00" },
+      // Native sends simulate as a plain value transfer with empty return data.
+      ethCall: () => "0x",
+    });
+    const s = await startSeashailOnMockEvm(chain, "sepolia", "testnet", "mock-send");
+
+    const to = "0x000000000000000000000000000000000000dEaD";
+    const sendRes = await rpcCall(s.p, s.outBuf, 10, "tools/call", {
+      name: "send_transaction",
+      arguments: {
+        wallet: "mock-send",
+        chain: "sepolia",
+        to,
+        token: "native",
+        amount: "0.01",
+      },
+    });
+    const sendObj = mustToolOk(sendRes, "send_transaction(sepolia mock)") as any;
+    expect(sendObj.chain).toBe("sepolia");
+    expect(sendObj.usd_value).toBeCloseTo(20, 6);
+    expect(sendObj.tx_hash).toMatch(/^0x[0-9a-f]{64}$/);
+
+    // Exactly one signed tx reached the chain, after a strict simulation of the same transfer.
+    expect(chain.state.rawTxs.length).toBe(1);
+    expect(chain.state.rawTxs[0]).toMatch(/^0x[0-9a-f]+$/);
+    expect(chain.state.ethCalls.some((c) => c.to === to.toLowerCase())).toBe(true);
+
+    await s.close();
+    chain.stop();
+  }, 90_000);
+
+  test("swap_tokens (uniswap) quotes, simulates, and broadcasts against a scripted mock chain", async () => {
+    const usdc = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const quoter = "0x61ffe014ba17989e743c5f6cb21bf9697530b21e";
+    const router = "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45";
+    const quotedOut = 5_000_000_000_000_000n; // 0.005 ETH for 10 USDC
+
+    const chain = startMockEvmChain({
+      chainId: 1,
+      ethCall: ({ to, data }) => {
+        const sel = data.slice(0, 10);
+        if (to === usdc && sel === SELECTORS.decimals) {
+          return abiWords(6n);
+        }
+        if (to === usdc && sel === SELECTORS.symbol) {
+          return abiString("USDC");
+        }
+        if (to === usdc && sel === SELECTORS.allowance) {
+          // Already approved: the swap should not send an approval first.
+          return abiWords(MAX_UINT256);
+        }
+        if (to === quoter && sel === SELECTORS.quoteExactInputSingle) {
+          // (amountOut, sqrtPriceX96After, initializedTicksCrossed, gasEstimate)
+          return abiWords(quotedOut, 0n, 1n, 100_000n);
+        }
+        if (to === router) {
+          return "0x";
+        }
+        return undefined;
+      },
+    });
+    const s = await startSeashailOnMockEvm(chain, "ethereum", "mainnet", "mock-swap");
+
+    const swapRes = await rpcCall(s.p, s.outBuf, 10, "tools/call", {
+      name: "swap_tokens",
+      arguments: {
+        wallet: "mock-swap",
+        chain: "ethereum",
+        token_in: usdc,
+        token_out: "native",
+        amount_in: "10",
+        provider: "uniswap",
+      },
+    });
+    const swapObj = mustToolOk(swapRes, "swap_tokens(uniswap mock)") as any;
+    expect(swapObj.provider).toBe("uniswap");
+    expect(swapObj.usd_value).toBeCloseTo(10, 6);
+    expect(swapObj.expected_out_base).toBe(quotedOut.toString());
+    expect(BigInt(swapObj.min_out_base)).toBeLessThan(quotedOut);
+    expect(swapObj.tx_hash).toMatch(/^0x[0-9a-f]{64}$/);
+
+    expect(chain.state.rawTxs.length).toBe(1);
+    const quoteCalls = chain.state.ethCalls.filter((c) => c.to === quoter);
+    expect(quoteCalls.length).toBe(3); // one per fee tier
+    expect(chain.state.ethCalls.some((c) => c.to === router)).toBe(true);
+
+    await s.close();
+    chain.stop();
+  }, 90_000);
 });
 
 describe("seashail MCP stdio (defi tx envelope: policy + allowlist + fail-closed)", () => {
@@ -0,0 +1,197 @@
+/**
+ * Scripted mock EVM chain for offline write-tool tests.
+ *
+ * Serves just enough Ethereum JSON-RPC for Seashail's EVM write path (fee/nonce lookup, gas
+ * estimation, `eth_call` simulation, raw-tx broadcast) plus a Binance-compatible price ticker so
+ * USD valuation never leaves the machine. Contract reads are scripted per `to` + selector via
+ * `ethCall`; everything the binary sends is recorded on `state` for assertions.
+ *
+ * Point Seashail at it with `configure_rpc { chain, url, fallback_urls: [] }` (no fallbacks, or
+ * broadcasts may retry against public RPCs) and `http.binance_base_url` in `config.toml`.
+ */
+
+export interface EthCallRequest {
+  /** Lowercased target address. */
+  to: string;
+  /** Lowercased calldata (`0x` + selector + args). */
+  data: string;
+  from?: string;
+}
+
+/** Return ABI-encoded output hex, or `undefined` to revert with "unscripted call". */
+export type EthCallHandler = (call: EthCallRequest) => string | undefined;
+
+export interface MockEvmChainState {
+  calls: { method: string; params: unknown[] }[];
+  ethCalls: EthCallRequest[];
+  rawTxs: string[];
+}
+
+export interface MockEvmChainOptions {
+  chainId: number;
+  /** Native balance returned for every address (wei). */
+  balanceWei?: bigint;
+  baseFeeWei?: bigint;
+  gasPriceWei?: bigint;
+  gasEstimate?: bigint;
+  ethCall?: EthCallHandler;
+  /** USD prices served at `/api/v3/ticker/price`, keyed by Binance pair (e.g. `ETHUSDT`). */
+  prices?: Record<string, string>;
+}
+
+export interface MockEvmChain {
+  url: string;
+  state: MockEvmChainState;
+  stop: () => void;
+}
+
+export const SELECTORS = {
+  allowance: "0xdd62ed3e",
+  balanceOf: "0x70a08231",
+  decimals: "0x313ce567",
+  symbol: "0x95d89b41",
+  quoteExactInputSingle: "0xc6a5026a",
+} as const;
+
+export const MAX_UINT256 = (1n << 256n) - 1n;
+
+const hexQty = (v: bigint | number) => `0x${BigInt(v).toString(16)}`;
+const zeroHash = (byte: string) => `0x${byte.repeat(32)}`;
+
+/** ABI-encode unsigned integers as consecutive 32-byte words. */
+export function abiWords(...vals: bigint[]): string {
+  return `0x${vals.map((v) => v.toString(16).padStart(64, "0")).join("")}`;
+}
+
+/** ABI-encode a single dynamic `string` return value. */
+export function abiString(s: string): string {
+  const bytes = Buffer.from(s, "utf8");
+  const padded = bytes.toString("hex").padEnd(Math.ceil(bytes.length / 32) * 64, "0");
+  return `${abiWords(32n, BigInt(bytes.length))}${padded}`;
+}
+
+function mockBlock(opts: MockEvmChainOptions) {
+  return {
+    hash: zeroHash("ab"),
+    parentHash: zeroHash("00"),
+    sha3Uncles: zeroHash("00"),
+    miner: `0x${"00".repeat(20)}`,
+    stateRoot: zeroHash("00"),
+    transactionsRoot: zeroHash("00"),
+    receiptsRoot: zeroHash("00"),
+    logsBloom: `0x${"00".repeat(256)}`,
+    difficulty: "0x0",
+    number: "0x64",
+    gasLimit: hexQty(30_000_000),
+    gasUsed: "0x0",
+    timestamp: hexQty(1_700_000_000),
+    extraData: "0x",
+    mixHash: zeroHash("00"),
+    nonce: "0x0000000000000000",
+    baseFeePerGas: hexQty(opts.baseFeeWei ?? 1_000_000_000n),
+    size: "0x0",
+    uncles: [],
+    transactions: [],
+  };
+}
+
+function rpcResult(
+  method: string,
+  params: unknown[],
+  opts: MockEvmChainOptions,
+  state: MockEvmChainState
+): { result?: unknown; error?: { code: number; message: string } } {
+  switch (method) {
+    case "eth_chainId":
+      return { result: hexQty(opts.chainId) };
+    case "net_version":
+      return { result: String(opts.chainId) };
+    case "eth_blockNumber":
+      return { result: "0x64" };
+    case "eth_getBlockByNumber":
+      return { result: mockBlock(opts) };
+    case "eth_gasPrice":
+      return { result: hexQty(opts.gasPriceWei ?? 2_000_000_000n) };
+    case "eth_maxPriorityFeePerGas":
+      return { result: hexQty(1_000_000_000n) };
+    case "eth_getBalance":
+      return { result: hexQty(opts.balanceWei ?? 10n ** 18n) };
+    case "eth_getTransactionCount":
+      return { result: hexQty(state.rawTxs.length) };
+    case "eth_getCode":
+      return { result: "0x" };
+    case "eth_estimateGas":
+      return { result: hexQty(opts.gasEstimate ?? 100_000n) };
+    case "eth_call": {
+      const tx = (params[0] ?? {}) as Record<string, string | undefined>;
+      const call: EthCallRequest = {
+        to: (tx.to ?? "").toLowerCase(),
+        data: (tx.input ?? tx.data ?? "0x").toLowerCase(),
+        from: tx.from?.toLowerCase(),
+      };
+      state.ethCalls.push(call);
+      const out = opts.ethCall?.(call);
+      if (out === undefined) {
+        return {
+          error: {
+            code: 3,
+            message: `execution reverted: unscripted call to ${call.to} (${call.data.slice(0, 10)})`,
+          },
+        };
+      }
+      return { result: out };
+    }
+    case "eth_sendRawTransaction": {
+      const raw = String(params[0] ?? "");
+      state.rawTxs.push(raw);
+      // The binary derives the tx hash locally; any well-formed hash is fine here.
+      return { result: zeroHash(state.rawTxs.length.toString(16).padStart(2, "0")) };
+    }
+    case "eth_getTransactionReceipt":
+      return { result: null };
+    default:
+      return { error: { code: -32601, message: `mock chain: unsupported method ${method}` } };
+  }
+}
+
+async function handleRpc(
+  req: Request,
+  opts: MockEvmChainOptions,
+  state: MockEvmChainState
+): Promise<Response> {
+  const body = (await req.json()) as unknown;
+  const one = (msg: any) => {
+    const method = String(msg?.method ?? "");
+    const params = Array.isArray(msg?.params) ? (msg.params as unknown[]) : [];
+    state.calls.push({ method, params });
+    return { jsonrpc: "2.0", id: msg?.id ?? null, ...rpcResult(method, params, opts, state) };
+  };
+  return Response.json(Array.isArray(body) ? body.map(one) : one(body));
+}
+
+export function startMockEvmChain(opts: MockEvmChainOptions): MockEvmChain {
+  const state: MockEvmChainState = { calls: [], ethCalls: [], rawTxs: [] };
+  const server = Bun.serve({
+    port: 0,
+    fetch(req) {
+      const u = new URL(req.url);
+      if (req.method === "GET" && u.pathname === "/api/v3/ticker/price") {
+        const symbol = u.searchParams.get("symbol") ?? "";
+        const price = opts.prices?.[symbol];
+        if (price === undefined) {
+          return Response.json({ code: -1121, msg: "Invalid symbol." }, { status: 400 });
+        }
+        return Response.json({ symbol, price });
+      }
+      if (req.method === "POST") {
+        return handleRpc(req, opts, state);
+      }
+      return new Response("not found", { status: 404 });
+    },
+  });
+  return {
+    url: `http://127.0.0.1:${server.port}`,
+    state,
+    stop: () => server.stop(true),
+  };
+}
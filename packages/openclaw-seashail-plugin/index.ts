@@ -521,9 +521,10 @@ function loadToolSpecsFromRepoSchema(): ToolSpec[] {
         type: "object",
         properties: {
           wallet: { type: "string" },
-          account_index: { type: "integer", minimum: 0 },
+          account_index: { type: "integer", minimum: 0, default: 0 },
+          chain: { type: "string" },
+          clear: { type: "boolean", default: false },
         },
-        required: ["wallet", "account_index"],
         additionalProperties: false,
       },
     },